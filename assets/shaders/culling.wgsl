@@ -8,6 +8,7 @@
 //! - 原子操作：使用原子计数器收集可见实例
 //! - 内存优化：紧凑的数据布局，减少内存带宽
 //! - 早期退出：使用分支优化，快速剔除不可见对象
+//! - LOD 选择：按相机距离为可见实例选择LOD，并将输出按LOD分组
 
 // 剔除 Uniform 数据
 struct CullingUniforms {
@@ -27,12 +28,22 @@ struct GpuInstance {
     flags: u32,
 }
 
+// LOD 选择 Uniform 数据
+struct LodUniforms {
+    camera_position: vec3<f32>,
+    lod_count: u32,  // 有效LOD级数（<= 1 表示不做LOD选择）
+    distances: array<vec4<f32>, 2>,  // 各级LOD的切换距离（共8个）
+}
+
 // 绑定组
 @group(0) @binding(0) var<uniform> uniforms: CullingUniforms;
 @group(0) @binding(1) var<storage, read> input_instances: array<GpuInstance>;
 @group(0) @binding(2) var<storage, read_write> output_instances: array<GpuInstance>;
 @group(0) @binding(3) var<storage, read_write> counter: atomic<u32>;
 @group(0) @binding(4) var<storage, read_write> indirect_commands: array<DrawIndexedIndirectArgs>;  // 可选的间接绘制命令缓冲区
+@group(0) @binding(5) var<uniform> lod: LodUniforms;
+@group(0) @binding(6) var<storage, read_write> lod_state: array<atomic<u32>, 16>;  // [0, 8): 各级数量, [8, 16): 各级写入游标
+@group(0) @binding(7) var<storage, read_write> output_lods: array<u32>;
 
 // 间接绘制参数结构
 struct DrawIndexedIndirectArgs {
@@ -88,62 +99,100 @@ fn is_sphere_visible(center: vec3<f32>, radius: f32) -> bool {
     return true;
 }
 
-/// 主剔除函数
+/// 按AABB中心到相机的距离选择LOD
 /// 
-/// 对每个实例执行视锥剔除，将可见实例写入输出缓冲区
-@compute @workgroup_size(64)
-fn cull_main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let idx = global_id.x;
-    
-    // 边界检查
-    if (idx >= uniforms.instance_count) {
-        return;
+/// `distances[i]` 为 LOD i 的最大距离，超出最后一级距离的实例使用最后一级
+fn select_lod(center: vec3<f32>) -> u32 {
+    if (lod.lod_count <= 1u) {
+        return 0u;
     }
-    
-    let instance = input_instances[idx];
-    
-    // 将 AABB 变换到世界空间
+    let dist = distance(center, lod.camera_position);
+    for (var i = 0u; i < lod.lod_count; i++) {
+        if (dist < lod.distances[i / 4u][i % 4u]) {
+            return i;
+        }
+    }
+    return lod.lod_count - 1u;
+}
+
+/// 将实例 AABB 变换到世界空间，返回 (min, max)
+fn world_aabb(instance: GpuInstance) -> array<vec3<f32>, 2> {
     // 注意：这里假设model矩阵已经包含了世界变换
     let world_min = (instance.model * vec4<f32>(instance.aabb_min, 1.0)).xyz;
     let world_max = (instance.model * vec4<f32>(instance.aabb_max, 1.0)).xyz;
     
     // 确保 min < max（处理负缩放的情况）
-    let actual_min = min(world_min, world_max);
-    let actual_max = max(world_min, world_max);
-    
-    // 视锥剔除
-    var visible = false;
-    
-    // 根据flags选择测试方法
-    // flags & 0x1: 使用球体测试（更快但可能不够精确）
-    // flags & 0x2: 使用AABB测试（更精确但稍慢）
+    return array<vec3<f32>, 2>(min(world_min, world_max), max(world_min, world_max));
+}
+
+/// 实例可见性测试
+/// 
+/// flags & 0x1: 使用球体测试（更快但可能不够精确）
+/// 否则使用AABB测试（更精确但稍慢）
+fn is_instance_visible(instance: GpuInstance, actual_min: vec3<f32>, actual_max: vec3<f32>) -> bool {
     if ((instance.flags & 0x1u) != 0u) {
         // 球体测试：使用AABB的中心和半径
         let center = (actual_min + actual_max) * 0.5;
         let radius = length(actual_max - actual_min) * 0.5;
-        visible = is_sphere_visible(center, radius);
-    } else {
-        // AABB测试：更精确但稍慢
-        visible = is_aabb_visible(actual_min, actual_max);
+        return is_sphere_visible(center, radius);
+    }
+    return is_aabb_visible(actual_min, actual_max);
+}
+
+/// LOD 计数函数
+/// 
+/// 在剔除前统计各级LOD的可见实例数量，供主剔除函数计算各级输出的起始位置
+@compute @workgroup_size(64)
+fn lod_count_main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let idx = global_id.x;
+    if (idx >= uniforms.instance_count) {
+        return;
     }
     
-    if (visible) {
-        // 原子增加计数器并获取输出索引
-        let output_idx = atomicAdd(&counter, 1u);
+    let instance = input_instances[idx];
+    let aabb = world_aabb(instance);
+    if (is_instance_visible(instance, aabb[0], aabb[1])) {
+        atomicAdd(&lod_state[select_lod((aabb[0] + aabb[1]) * 0.5)], 1u);
+    }
+}
+
+/// 主剔除函数
+/// 
+/// 对每个实例执行视锥剔除，将可见实例按LOD分组写入输出缓冲区
+@compute @workgroup_size(64)
+fn cull_main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let idx = global_id.x;
+    
+    // 边界检查
+    if (idx >= uniforms.instance_count) {
+        return;
+    }
+    
+    let instance = input_instances[idx];
+    let aabb = world_aabb(instance);
+    
+    if (is_instance_visible(instance, aabb[0], aabb[1])) {
+        // 输出按LOD分组：前面各级LOD的数量之和为本级的起始位置
+        let lod_index = select_lod((aabb[0] + aabb[1]) * 0.5);
+        var lod_offset = 0u;
+        for (var i = 0u; i < lod_index; i++) {
+            lod_offset += atomicLoad(&lod_state[i]);
+        }
+        let output_idx = lod_offset + atomicAdd(&lod_state[8u + lod_index], 1u);
+        atomicAdd(&counter, 1u);
         output_instances[output_idx] = instance;
+        output_lods[output_idx] = lod_index;
         
         // 如果提供了间接绘制命令缓冲区且index_count > 0，同时生成间接绘制命令
         // 这样可以完全避免CPU读取结果，实现完全GPU端剔除流程
         if (uniforms.index_count > 0u) {
-            indirect_commands[output_idx] = DrawIndexedIndirectArgs {
-                index_count: uniforms.index_count,
-                instance_count: 1u,              // 每个命令的实例数（单个实例）
-                first_index: 0u,                 // 第一个索引
-                base_vertex: 0i,                 // 基础顶点
-                first_instance: output_idx,      // 第一个实例（使用输出索引）
-            };
+            indirect_commands[output_idx] = DrawIndexedIndirectArgs(
+                uniforms.index_count,
+                1u,              // 每个命令的实例数（单个实例）
+                0u,              // 第一个索引
+                0i,              // 基础顶点
+                output_idx,      // 第一个实例（使用输出索引）
+            );
         }
     }
 }
-
-
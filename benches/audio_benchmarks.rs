@@ -0,0 +1,66 @@
+//! 音频混音性能基准测试
+//!
+//! 测试 32 路输入混合到立体声输出的吞吐量，对比 SIMD 与标量实现
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use game_engine::performance::optimization::{AudioChannel, AudioChannelMixer, MixInput};
+use game_engine_simd::SimdBackend;
+use std::hint::black_box;
+
+fn bench_mix_channels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("audio_mix_32_channels");
+
+    let mixer = AudioChannelMixer::new();
+    let output_channels = 2;
+    let channels = [
+        AudioChannel::Music,
+        AudioChannel::SFX,
+        AudioChannel::Voice,
+        AudioChannel::Ambient,
+    ];
+
+    for frames in [256, 1024, 4096] {
+        let len = frames * output_channels;
+        let buffers: Vec<Vec<f32>> = (0..32)
+            .map(|s| {
+                (0..len)
+                    .map(|i| ((i * 31 + s * 17) % 101) as f32 / 101.0 - 0.5)
+                    .collect()
+            })
+            .collect();
+        let inputs: Vec<MixInput> = buffers
+            .iter()
+            .enumerate()
+            .map(|(s, samples)| MixInput {
+                channel: channels[s % channels.len()],
+                samples,
+                gain: 0.5,
+            })
+            .collect();
+        let mut output = vec![0.0f32; len];
+
+        // 吞吐量按读取的输入样本数计算
+        group.throughput(Throughput::Elements((len * inputs.len()) as u64));
+
+        group.bench_with_input(BenchmarkId::new("simd", frames), &frames, |b, _| {
+            b.iter(|| {
+                mixer.mix_channels(black_box(&inputs), &mut output, output_channels);
+                black_box(&output);
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("scalar", frames), &frames, |b, _| {
+            SimdBackend::Scalar.with_override(|| {
+                b.iter(|| {
+                    mixer.mix_channels(black_box(&inputs), &mut output, output_channels);
+                    black_box(&output);
+                });
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_mix_channels);
+criterion_main!(benches);
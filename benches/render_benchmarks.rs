@@ -141,6 +141,7 @@ fn bench_gpu_indirect_draw(c: &mut Criterion) {
     let config = GpuDrivenConfig {
         frustum_culling: true,
         occlusion_culling: false,
+        occlusion_two_pass: true,
         lod_enabled: false,
         max_instances: 65536,
         workgroup_size: 64,
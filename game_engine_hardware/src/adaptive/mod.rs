@@ -2,11 +2,21 @@
 /// 
 /// 运行时动态调整画质以维持目标帧率
 
-use crate::config::auto_config::AutoConfig;
-use crate::soc::power::{PowerManager, ThermalState};
+use crate::config::auto_config::{AutoConfig, QualityPreset};
+use crate::soc::power::{PowerManager, ThermalEvent, ThermalState};
 use crate::utils::ring_buffer::RingBuffer;
 use std::time::{Instant, Duration};
 
+/// 画质变化回调：(旧预设, 新预设, 原因)
+pub type QualityChangedCallback = Box<dyn FnMut(QualityPreset, QualityPreset, &str) + Send>;
+
+/// 调整方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AdjustDirection {
+    Decrease,
+    Increase,
+}
+
 /// 自适应性能管理器
 pub struct AdaptivePerformance {
     config: AutoConfig,
@@ -21,6 +31,17 @@ pub struct AdaptivePerformance {
     last_adjustment: Instant,
     adjustment_cooldown: Duration,
     
+    // 冷却期间收到的最近一次热压力，冷却结束后立即处理
+    thermal_pressure: Option<ThermalEvent>,
+    
+    // 防抖：同一方向的压力需持续 debounce 时长才会调整
+    debounce: Duration,
+    pending: Option<(AdjustDirection, Instant)>,
+    
+    // 画质预设变化通知
+    current_preset: QualityPreset,
+    quality_callbacks: Vec<QualityChangedCallback>,
+    
     // 调整历史
     adjustment_count: u32,
     total_adjustments: u32,
@@ -31,6 +52,7 @@ impl AdaptivePerformance {
     pub fn new(config: AutoConfig, power_manager: PowerManager) -> Self {
         let target_fps = config.target_fps as f32;
         let target_frame_time_ms = 1000.0 / target_fps;
+        let current_preset = classify_preset(&config);
         
         Self {
             config,
@@ -40,29 +62,79 @@ impl AdaptivePerformance {
             frame_times: RingBuffer::new(300),
             last_adjustment: Instant::now(),
             adjustment_cooldown: Duration::from_secs(3),
+            thermal_pressure: None,
+            debounce: Duration::from_secs(1),
+            pending: None,
+            current_preset,
+            quality_callbacks: Vec::new(),
             adjustment_count: 0,
             total_adjustments: 0,
         }
     }
     
+    /// 注册画质预设变化回调，参数为 (旧预设, 新预设, 原因)
+    pub fn on_quality_changed(
+        &mut self,
+        cb: impl FnMut(QualityPreset, QualityPreset, &str) + Send + 'static,
+    ) {
+        self.quality_callbacks.push(Box::new(cb));
+    }
+    
+    /// 当前画质预设
+    pub fn quality_preset(&self) -> QualityPreset {
+        self.current_preset
+    }
+    
+    /// 设置两次调整之间的冷却时间
+    pub fn set_adjustment_cooldown(&mut self, cooldown: Duration) {
+        self.adjustment_cooldown = cooldown;
+    }
+    
+    /// 设置防抖时长：性能压力需持续该时长才会调整画质
+    pub fn set_debounce(&mut self, debounce: Duration) {
+        self.debounce = debounce;
+    }
+    
     /// 更新性能数据
     pub fn update(&mut self, frame_time_ms: f32) {
+        self.update_at(frame_time_ms, Instant::now());
+    }
+    
+    /// 更新性能数据，`now` 为当前帧的时间
+    pub fn update_at(&mut self, frame_time_ms: f32, now: Instant) {
         // 更新功耗管理器
         self.power_manager.update(frame_time_ms);
         
         // 记录帧时间（O(1)操作）
         self.frame_times.push(frame_time_ms);
         
+        // 热状态升到发热以上时立即降低画质，不等待防抖；
+        // 冷却期间保留最近一次热压力，冷却结束后再处理
+        while let Some(event) = self.power_manager.poll_thermal() {
+            if event.to >= ThermalState::Hot {
+                self.thermal_pressure = Some(event);
+            }
+        }
+        if now.saturating_duration_since(self.last_adjustment) >= self.adjustment_cooldown {
+            if let Some(event) = self.thermal_pressure.take() {
+                let reason = format!("热节流 ({:?}, {:.1}°C)", event.to, event.temperature);
+                self.pending = None;
+                self.decrease_quality(&reason, now);
+                self.notify_preset_change(&reason);
+                return;
+            }
+        }
+        
         // 检查是否需要调整
-        if self.should_adjust() {
-            self.perform_adjustment();
+        if self.should_adjust(now) {
+            self.perform_adjustment(now);
         }
     }
     
     /// 是否应该进行调整
-    fn should_adjust(&self) -> bool {
+    fn should_adjust(&self, now: Instant) -> bool {
         // 冷却时间未到
-        if self.last_adjustment.elapsed() < self.adjustment_cooldown {
+        if now.saturating_duration_since(self.last_adjustment) < self.adjustment_cooldown {
             return false;
         }
         
@@ -75,7 +147,7 @@ impl AdaptivePerformance {
     }
     
     /// 执行性能调整
-    fn perform_adjustment(&mut self) {
+    fn perform_adjustment(&mut self, now: Instant) {
         let avg_frame_time = self.average_frame_time();
         let target = self.target_frame_time_ms;
         
@@ -86,20 +158,60 @@ impl AdaptivePerformance {
         let thermal_state = self.power_manager.thermal_state();
         
         // 决定调整方向
-        if thermal_state >= ThermalState::Hot {
+        let (direction, reason) = if thermal_state >= ThermalState::Hot {
             // 热节流：降低画质
-            self.decrease_quality("热节流");
+            (AdjustDirection::Decrease, "热节流")
         } else if deviation > 0.2 {
             // 性能不足：降低画质
-            self.decrease_quality("性能不足");
+            (AdjustDirection::Decrease, "帧时间持续超出预算")
         } else if deviation < -0.3 && thermal_state == ThermalState::Normal {
             // 性能过剩：提升画质
-            self.increase_quality("性能过剩");
+            (AdjustDirection::Increase, "帧时间持续低于预算")
+        } else {
+            self.pending = None;
+            return;
+        };
+        
+        // 防抖：方向变化时重新计时，避免每帧来回切换
+        match self.pending {
+            Some((pending, since)) if pending == direction => {
+                if now.saturating_duration_since(since) < self.debounce {
+                    return;
+                }
+            }
+            _ => {
+                self.pending = Some((direction, now));
+                if !self.debounce.is_zero() {
+                    return;
+                }
+            }
+        }
+        self.pending = None;
+        
+        match direction {
+            AdjustDirection::Decrease => self.decrease_quality(reason, now),
+            AdjustDirection::Increase => self.increase_quality(reason, now),
+        }
+        self.notify_preset_change(reason);
+    }
+    
+    /// 画质预设变化时通知回调
+    fn notify_preset_change(&mut self, reason: &str) {
+        let new_preset = classify_preset(&self.config);
+        if new_preset == self.current_preset {
+            return;
+        }
+        
+        let old_preset = self.current_preset;
+        self.current_preset = new_preset;
+        self.config.quality_preset = new_preset;
+        for cb in &mut self.quality_callbacks {
+            cb(old_preset, new_preset, reason);
         }
     }
     
     /// 降低画质
-    fn decrease_quality(&mut self, reason: &str) {
+    fn decrease_quality(&mut self, reason: &str, now: Instant) {
         println!("[自适应] 降低画质 - 原因: {}", reason);
         
         // 优先级：分辨率 > 阴影 > 粒子 > 后处理
@@ -133,11 +245,11 @@ impl AdaptivePerformance {
         
         self.adjustment_count += 1;
         self.total_adjustments += 1;
-        self.last_adjustment = Instant::now();
+        self.last_adjustment = now;
     }
     
     /// 提升画质
-    fn increase_quality(&mut self, reason: &str) {
+    fn increase_quality(&mut self, reason: &str, now: Instant) {
         // 限制提升频率
         if self.adjustment_count > 0 {
             return;
@@ -170,7 +282,7 @@ impl AdaptivePerformance {
         }
         
         self.total_adjustments += 1;
-        self.last_adjustment = Instant::now();
+        self.last_adjustment = now;
     }
     
     /// 获取平均帧时间
@@ -181,6 +293,11 @@ impl AdaptivePerformance {
         self.frame_times.average()
     }
     
+    /// 获取功耗管理器（用于报告温度或设置功耗预算）
+    pub fn power_manager_mut(&mut self) -> &mut PowerManager {
+        &mut self.power_manager
+    }
+    
     /// 获取当前配置
     pub fn config(&self) -> &AutoConfig {
         &self.config
@@ -217,6 +334,30 @@ pub struct AdaptiveStats {
     pub thermal_state: ThermalState,
 }
 
+/// 根据当前设置推断画质预设
+fn classify_preset(config: &AutoConfig) -> QualityPreset {
+    let scale = config.resolution_scale;
+    let shadow = config.shadow_quality;
+    
+    if scale >= 1.5 && shadow >= ShadowQuality::High {
+        if shadow == ShadowQuality::Ultra {
+            QualityPreset::Ultra
+        } else {
+            QualityPreset::High
+        }
+    } else if scale >= 1.0 && shadow >= ShadowQuality::Medium {
+        if config.bloom {
+            QualityPreset::High
+        } else {
+            QualityPreset::Medium
+        }
+    } else if scale >= 0.75 && shadow >= ShadowQuality::Low {
+        QualityPreset::Medium
+    } else {
+        QualityPreset::Low
+    }
+}
+
 // 扩展ShadowQuality以支持增减
 use crate::config::auto_config::ShadowQuality;
 
@@ -261,7 +402,8 @@ impl QualityAdjustable for ShadowQuality {
 mod tests {
     use super::*;
     use crate::{detect_gpu, detect_npu, detect_soc};
-    use crate::{HardwareCapability, AutoConfig};
+    use crate::{HardwareCapability, AutoConfig, GpuTier, PerformanceTier};
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_adaptive_performance() {
@@ -300,4 +442,95 @@ mod tests {
         let stats = adaptive.stats();
         println!("Stats: {:#?}", stats);
     }
+    
+    #[test]
+    fn test_sustained_slow_frames_downgrade_quality() {
+        let capability = HardwareCapability {
+            tier: PerformanceTier::Medium,
+            gpu_tier: GpuTier::Medium,
+            gpu_vram_mb: 4096,
+            supports_raytracing: false,
+            supports_mesh_shaders: false,
+            supports_vrs: false,
+            has_npu: false,
+            npu_tops: 0.0,
+            system_ram_mb: 8192,
+            is_mobile: false,
+            thermal_limited: false,
+            max_resolution_scale: 1.0,
+            max_shadow_quality: 2,
+            max_texture_quality: 2,
+            recommended_vsync: true,
+        };
+        let config = AutoConfig::from_capability(&capability);
+        let mut adaptive = AdaptivePerformance::new(config, PowerManager::new(None));
+        adaptive.set_adjustment_cooldown(Duration::ZERO);
+        assert_eq!(adaptive.quality_preset(), QualityPreset::Medium);
+        
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+        adaptive.on_quality_changed(move |old, new, reason| {
+            recorded.lock().unwrap().push((old, new, reason.to_string()));
+        });
+        
+        // 模拟 10 秒 20fps 的持续卡顿
+        let t0 = Instant::now();
+        for i in 0..200 {
+            adaptive.update_at(50.0, t0 + Duration::from_millis(i * 50));
+        }
+        
+        let changes = changes.lock().unwrap();
+        assert!(!changes.is_empty());
+        let (old, new, reason) = &changes[0];
+        assert_eq!((*old, *new), (QualityPreset::Medium, QualityPreset::Low));
+        assert!(!reason.is_empty());
+        assert_eq!(adaptive.config().quality_preset, QualityPreset::Low);
+        // 防抖：同一方向的调整之间至少间隔 1 秒
+        assert!(adaptive.stats().total_adjustments <= 10);
+    }
+    
+    #[test]
+    fn test_thermal_event_triggers_downgrade() {
+        let config = AutoConfig::from_capability(&HardwareCapability::evaluate(
+            &crate::GpuInfo::default(),
+            &None,
+            &None,
+        ));
+        let scale = config.resolution_scale;
+        let mut adaptive = AdaptivePerformance::new(config, PowerManager::new(None));
+        adaptive.set_adjustment_cooldown(Duration::ZERO);
+        
+        adaptive.power_manager_mut().report_temperature(70.0);
+        adaptive.update_at(16.0, Instant::now());
+        
+        assert_eq!(adaptive.stats().total_adjustments, 1);
+        assert!(adaptive.config().resolution_scale < scale);
+    }
+    
+    #[test]
+    fn test_thermal_event_during_cooldown_is_deferred() {
+        let config = AutoConfig::from_capability(&HardwareCapability::evaluate(
+            &crate::GpuInfo::default(),
+            &None,
+            &None,
+        ));
+        let scale = config.resolution_scale;
+        let t0 = Instant::now();
+        let mut adaptive = AdaptivePerformance::new(config, PowerManager::new(None));
+        
+        // 冷却期间（默认 3 秒）收到热事件，暂不调整
+        adaptive.power_manager_mut().report_temperature(70.0);
+        adaptive.update_at(16.0, t0 + Duration::from_secs(1));
+        adaptive.update_at(16.0, t0 + Duration::from_secs(2));
+        assert_eq!(adaptive.stats().total_adjustments, 0);
+        
+        // 冷却结束后处理保留的热压力
+        adaptive.update_at(16.0, t0 + Duration::from_millis(3500));
+        assert_eq!(adaptive.stats().total_adjustments, 1);
+        assert!(adaptive.config().resolution_scale < scale);
+        
+        // 热压力只处理一次
+        adaptive.update_at(16.0, t0 + Duration::from_secs(7));
+        assert_eq!(adaptive.stats().total_adjustments, 1);
+    }
 }
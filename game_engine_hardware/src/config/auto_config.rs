@@ -47,9 +47,103 @@ pub struct AutoConfig {
     pub batch_size: usize,
     pub culling_distance: f32,
     pub lod_bias: f32,
+    
+    /// 检测到的显存 (MB)，0 表示未知
+    #[serde(default)]
+    pub vram_mb: u64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// 由自动配置得到的具体图形设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphicsSettings {
+    /// 渲染分辨率缩放
+    pub render_scale: f32,
+    /// 阴影贴图尺寸，0 表示关闭阴影
+    pub shadow_map_size: u32,
+    /// 抗锯齿模式
+    pub anti_aliasing: AntiAliasingMode,
+    /// MSAA 采样数 (未使用 MSAA 时为 1)
+    pub msaa_samples: u32,
+    /// 纹理最大边长
+    pub max_texture_size: u32,
+    /// 各向异性过滤级别
+    pub texture_anisotropy: u16,
+    pub bloom: bool,
+}
+
+/// 显存预算对应的上限
+struct VramBudget {
+    max_render_scale: f32,
+    max_shadow_map_size: u32,
+    max_texture_size: u32,
+}
+
+impl VramBudget {
+    fn for_vram(vram_mb: u64) -> Self {
+        let (max_render_scale, max_shadow_map_size, max_texture_size) = match vram_mb {
+            // 未知显存不做限制
+            0 => (f32::MAX, u32::MAX, u32::MAX),
+            1..=1023 => (0.75, 512, 1024),
+            1024..=2047 => (1.0, 1024, 2048),
+            2048..=4095 => (1.5, 2048, 4096),
+            _ => (f32::MAX, u32::MAX, u32::MAX),
+        };
+        Self {
+            max_render_scale,
+            max_shadow_map_size,
+            max_texture_size,
+        }
+    }
+}
+
+impl ShadowQuality {
+    /// 阴影贴图尺寸，关闭时为 0
+    pub fn shadow_map_size(&self) -> u32 {
+        match self {
+            ShadowQuality::Off => 0,
+            ShadowQuality::Low => 512,
+            ShadowQuality::Medium => 1024,
+            ShadowQuality::High => 2048,
+            ShadowQuality::Ultra => 4096,
+        }
+    }
+}
+
+impl TextureQuality {
+    /// 纹理最大边长
+    pub fn max_texture_size(&self) -> u32 {
+        match self {
+            TextureQuality::Low => 1024,
+            TextureQuality::Medium => 2048,
+            TextureQuality::High => 4096,
+            TextureQuality::Ultra => 8192,
+        }
+    }
+    
+    /// 各向异性过滤级别
+    pub fn anisotropy(&self) -> u16 {
+        match self {
+            TextureQuality::Low => 1,
+            TextureQuality::Medium => 4,
+            TextureQuality::High => 8,
+            TextureQuality::Ultra => 16,
+        }
+    }
+}
+
+impl AntiAliasingMode {
+    /// MSAA 采样数，非 MSAA 模式为 1
+    pub fn msaa_samples(&self) -> u32 {
+        match self {
+            AntiAliasingMode::MSAA2x => 2,
+            AntiAliasingMode::MSAA4x => 4,
+            AntiAliasingMode::MSAA8x => 8,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum ShadowQuality {
     Off,
     Low,
@@ -79,13 +173,45 @@ pub enum AntiAliasingMode {
 impl AutoConfig {
     /// 从硬件能力生成配置
     pub fn from_capability(capability: &HardwareCapability) -> Self {
-        match capability.tier {
+        let mut config = match capability.tier {
             PerformanceTier::Flagship => Self::ultra_preset(capability),
             PerformanceTier::High => Self::high_preset(capability),
             PerformanceTier::MediumHigh => Self::medium_high_preset(capability),
             PerformanceTier::Medium => Self::medium_preset(capability),
             PerformanceTier::MediumLow => Self::low_medium_preset(capability),
             PerformanceTier::Low => Self::low_preset(capability),
+        };
+        config.vram_mb = capability.gpu_vram_mb;
+        config
+    }
+    
+    /// 转换为具体的图形设置
+    /// 
+    /// 数值由质量预设决定，低显存设备会额外限制渲染缩放、阴影和纹理分辨率
+    pub fn to_graphics_settings(&self) -> GraphicsSettings {
+        let budget = VramBudget::for_vram(self.vram_mb);
+        let max_texture_size = self
+            .texture_quality
+            .max_texture_size()
+            .min(budget.max_texture_size);
+        // 纹理被降级时各向异性也随之降低
+        let texture_anisotropy = if max_texture_size < self.texture_quality.max_texture_size() {
+            self.texture_quality.anisotropy().min(2)
+        } else {
+            self.texture_quality.anisotropy()
+        };
+        
+        GraphicsSettings {
+            render_scale: self.resolution_scale.min(budget.max_render_scale),
+            shadow_map_size: self
+                .shadow_quality
+                .shadow_map_size()
+                .min(budget.max_shadow_map_size),
+            anti_aliasing: self.anti_aliasing,
+            msaa_samples: self.anti_aliasing.msaa_samples(),
+            max_texture_size,
+            texture_anisotropy,
+            bloom: self.bloom,
         }
     }
     
@@ -112,6 +238,7 @@ impl AutoConfig {
             batch_size: capability.recommended_batch_size(),
             culling_distance: 1000.0,
             lod_bias: 0.0,
+            vram_mb: 0,
         }
     }
     
@@ -138,6 +265,7 @@ impl AutoConfig {
             batch_size: capability.recommended_batch_size(),
             culling_distance: 800.0,
             lod_bias: 0.0,
+            vram_mb: 0,
         }
     }
     
@@ -164,6 +292,7 @@ impl AutoConfig {
             batch_size: capability.recommended_batch_size(),
             culling_distance: 600.0,
             lod_bias: 0.5,
+            vram_mb: 0,
         }
     }
     
@@ -190,6 +319,7 @@ impl AutoConfig {
             batch_size: capability.recommended_batch_size(),
             culling_distance: 400.0,
             lod_bias: 1.0,
+            vram_mb: 0,
         }
     }
     
@@ -216,6 +346,7 @@ impl AutoConfig {
             batch_size: capability.recommended_batch_size(),
             culling_distance: 300.0,
             lod_bias: 1.5,
+            vram_mb: 0,
         }
     }
     
@@ -242,6 +373,7 @@ impl AutoConfig {
             batch_size: capability.recommended_batch_size(),
             culling_distance: 200.0,
             lod_bias: 2.0,
+            vram_mb: 0,
         }
     }
     
@@ -263,7 +395,27 @@ impl AutoConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{detect_gpu, detect_npu, detect_soc, HardwareCapability};
+    use crate::{detect_gpu, detect_npu, detect_soc, GpuTier, HardwareCapability};
+    
+    fn capability(tier: PerformanceTier, gpu_vram_mb: u64) -> HardwareCapability {
+        HardwareCapability {
+            tier,
+            gpu_tier: GpuTier::Low,
+            gpu_vram_mb,
+            supports_raytracing: false,
+            supports_mesh_shaders: false,
+            supports_vrs: false,
+            has_npu: false,
+            npu_tops: 0.0,
+            system_ram_mb: 4096,
+            is_mobile: false,
+            thermal_limited: false,
+            max_resolution_scale: 1.0,
+            max_shadow_quality: 0,
+            max_texture_quality: 0,
+            recommended_vsync: true,
+        }
+    }
 
     #[test]
     fn test_auto_config() {
@@ -297,4 +449,27 @@ mod tests {
         
         std::fs::remove_file(path).ok();
     }
+    
+    #[test]
+    fn test_low_vram_graphics_settings() {
+        let config = AutoConfig::from_capability(&capability(PerformanceTier::MediumLow, 512));
+        let settings = config.to_graphics_settings();
+        
+        assert!(settings.render_scale < 1.0);
+        assert!(settings.shadow_map_size <= 512);
+        assert!(settings.max_texture_size <= 1024);
+        assert!(!settings.bloom);
+        
+        // 低显存限制与性能等级无关
+        let config = AutoConfig::from_capability(&capability(PerformanceTier::Flagship, 512));
+        let settings = config.to_graphics_settings();
+        assert!(settings.render_scale < 1.0);
+        assert_eq!(settings.shadow_map_size, 512);
+        assert_eq!(settings.max_texture_size, 1024);
+        
+        let config = AutoConfig::from_capability(&capability(PerformanceTier::Flagship, 16384));
+        let settings = config.to_graphics_settings();
+        assert_eq!(settings.shadow_map_size, 4096);
+        assert_eq!(settings.texture_anisotropy, 16);
+    }
 }
@@ -2,5 +2,5 @@
 
 pub mod auto_config;
 
-pub use auto_config::{AutoConfig, QualityPreset};
+pub use auto_config::{AutoConfig, GraphicsSettings, QualityPreset};
 
@@ -204,7 +204,7 @@ fn detect_gpu_linux() -> Option<GpuInfo> {
                 if line.contains("VGA") || line.contains("3D") {
                     let name = line.split(':').nth(2)?.trim().to_string();
                     let vendor = detect_vendor_from_name(&name);
-                    let tier = classify_gpu_tier(vendor, &name, wgpu::DeviceType::DiscreteGpu);
+                    let tier = classify_gpu_tier(vendor, &name, DeviceType::DiscreteGpu);
                     
                     return Some(GpuInfo {
                         vendor,
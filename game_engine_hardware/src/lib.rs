@@ -17,7 +17,7 @@ pub use gpu::{GpuInfo, GpuVendor, GpuTier, detect_gpu};
 pub use npu::{NpuInfo, NpuVendor, detect_npu};
 pub use soc::{SocInfo, SocVendor, detect_soc};
 pub use capability::{HardwareCapability, PerformanceTier};
-pub use config::{AutoConfig, QualityPreset};
+pub use config::{AutoConfig, GraphicsSettings, QualityPreset};
 pub use error::{HardwareError, HardwareResult};
 pub use npu::sdk::extended::{OpenVINOEngine, ROCmEngine, AscendEngine, SNPEEngine, NeuroPilotEngine};
 
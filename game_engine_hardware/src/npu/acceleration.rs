@@ -3,12 +3,24 @@
 /// 使用NPU进行AI推理和游戏逻辑加速
 
 use super::detect::NpuInfo;
+use super::sdk::{NpuBackend, NpuInferenceEngine};
 use std::sync::{Arc, Mutex};
 
+/// 推理后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccelerationBackend {
+    /// 在NPU上运行物理预测模型
+    Npu,
+    /// 没有可用NPU时的轻量CPU实现
+    CpuFallback,
+}
+
 /// NPU加速器
 pub struct NpuAccelerator {
     npu_info: Option<NpuInfo>,
     enabled: bool,
+    /// 已加载物理预测模型的NPU推理引擎
+    physics_model: Option<Box<dyn NpuInferenceEngine>>,
     inference_cache: Arc<Mutex<InferenceCache>>,
 }
 
@@ -47,6 +59,8 @@ pub enum NpcAction {
 
 impl NpuAccelerator {
     /// 创建NPU加速器
+    /// 
+    /// 物理预测在通过 `set_physics_model` 挂载NPU推理引擎之前走CPU回退路径
     pub fn new(npu_info: Option<NpuInfo>) -> Self {
         let enabled = npu_info.is_some() && 
                      npu_info.as_ref().map(|n| n.tops > 5.0).unwrap_or(false);
@@ -54,6 +68,7 @@ impl NpuAccelerator {
         Self {
             npu_info,
             enabled,
+            physics_model: None,
             inference_cache: Arc::new(Mutex::new(InferenceCache {
                 physics_predictions: Vec::new(),
                 behavior_decisions: Vec::new(),
@@ -71,21 +86,98 @@ impl NpuAccelerator {
         self.npu_info.as_ref()
     }
     
+    /// 挂载已加载物理预测模型的推理引擎
+    /// 
+    /// 模型输入为 `[位置xyz, 速度xyz, 时间步长]`，输出为 `[位置xyz, 速度xyz, 置信度]`。
+    /// CPU回退引擎不会被当作NPU后端。
+    pub fn set_physics_model(&mut self, engine: Box<dyn NpuInferenceEngine>) {
+        self.physics_model = Some(engine);
+    }
+    
+    /// 物理预测使用的推理后端
+    pub fn backend(&self) -> AccelerationBackend {
+        match &self.physics_model {
+            Some(engine) if engine.backend() != NpuBackend::CpuFallback => AccelerationBackend::Npu,
+            _ => AccelerationBackend::CpuFallback,
+        }
+    }
+    
     /// 预测物理运动
     /// 
-    /// 使用NPU进行物理模拟的预测，可以提前计算碰撞和轨迹
+    /// NPU后端运行物理预测模型，可以提前计算碰撞和轨迹；
+    /// 没有NPU或推理失败时使用CPU上的弹道积分
     pub fn predict_physics(
         &self,
         object_id: u64,
         current_position: [f32; 3],
         current_velocity: [f32; 3],
         time_delta: f32,
-    ) -> Option<PhysicsPrediction> {
-        if !self.enabled {
-            return None;
+    ) -> PhysicsPrediction {
+        if self.backend() == AccelerationBackend::Npu {
+            if let Some(prediction) =
+                self.infer_physics(object_id, current_position, current_velocity, time_delta)
+            {
+                return prediction;
+            }
         }
         
-        // 简化的物理预测（实际应该使用NPU推理）
+        let (predicted_position, predicted_velocity) =
+            Self::integrate_ballistic(current_position, current_velocity, time_delta);
+        
+        PhysicsPrediction {
+            object_id,
+            predicted_position,
+            predicted_velocity,
+            // CPU路径只做积分，不考虑碰撞，置信度较低
+            confidence: 0.8,
+        }
+    }
+    
+    /// 在NPU上运行物理预测模型，推理失败或输出长度不足时返回 `None`
+    fn infer_physics(
+        &self,
+        object_id: u64,
+        current_position: [f32; 3],
+        current_velocity: [f32; 3],
+        time_delta: f32,
+    ) -> Option<PhysicsPrediction> {
+        let engine = self.physics_model.as_ref()?;
+        let input = [
+            current_position[0],
+            current_position[1],
+            current_position[2],
+            current_velocity[0],
+            current_velocity[1],
+            current_velocity[2],
+            time_delta,
+        ];
+        
+        let output = match engine.infer(&input) {
+            Ok(output) if output.len() >= 7 => output,
+            Ok(output) => {
+                tracing::warn!(target: "npu", "物理预测模型输出长度 {} 不足，回退到CPU", output.len());
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!(target: "npu", "物理预测推理失败，回退到CPU: {}", e);
+                return None;
+            }
+        };
+        
+        Some(PhysicsPrediction {
+            object_id,
+            predicted_position: [output[0], output[1], output[2]],
+            predicted_velocity: [output[3], output[4], output[5]],
+            confidence: output[6].clamp(0.0, 1.0),
+        })
+    }
+    
+    /// 弹道积分：匀速运动加重力
+    fn integrate_ballistic(
+        current_position: [f32; 3],
+        current_velocity: [f32; 3],
+        time_delta: f32,
+    ) -> ([f32; 3], [f32; 3]) {
         let predicted_position = [
             current_position[0] + current_velocity[0] * time_delta,
             current_position[1] + current_velocity[1] * time_delta,
@@ -100,12 +192,7 @@ impl NpuAccelerator {
             current_velocity[2],
         ];
         
-        Some(PhysicsPrediction {
-            object_id,
-            predicted_position,
-            predicted_velocity,
-            confidence: 0.95,
-        })
+        (predicted_position, predicted_velocity)
     }
     
     /// 批量预测物理运动
@@ -114,20 +201,14 @@ impl NpuAccelerator {
         objects: &[(u64, [f32; 3], [f32; 3])],
         time_delta: f32,
     ) -> Vec<PhysicsPrediction> {
-        if !self.enabled {
-            return Vec::new();
-        }
-        
         objects.iter()
-            .filter_map(|(id, pos, vel)| {
-                self.predict_physics(*id, *pos, *vel, time_delta)
-            })
+            .map(|(id, pos, vel)| self.predict_physics(*id, *pos, *vel, time_delta))
             .collect()
     }
     
     /// NPC行为决策
     /// 
-    /// 使用NPU进行智能NPC的行为决策
+    /// 在CPU上按距离、血量和弹药做规则决策
     pub fn decide_npc_behavior(
         &self,
         npc_id: u64,
@@ -135,12 +216,8 @@ impl NpuAccelerator {
         player_position: [f32; 3],
         npc_health: f32,
         npc_ammo: u32,
-    ) -> Option<BehaviorDecision> {
-        if !self.enabled {
-            return None;
-        }
-        
-        // 简化的决策逻辑（实际应该使用NPU推理神经网络）
+    ) -> BehaviorDecision {
+        // 简化的决策逻辑
         let distance = Self::distance(&npc_position, &player_position);
         
         let action = if npc_health < 0.3 {
@@ -163,11 +240,11 @@ impl NpuAccelerator {
             _ => 0.1,
         };
         
-        Some(BehaviorDecision {
+        BehaviorDecision {
             npc_id,
             action,
             priority,
-        })
+        }
     }
     
     /// 批量NPC行为决策
@@ -176,12 +253,8 @@ impl NpuAccelerator {
         npcs: &[(u64, [f32; 3], f32, u32)],
         player_position: [f32; 3],
     ) -> Vec<BehaviorDecision> {
-        if !self.enabled {
-            return Vec::new();
-        }
-        
         npcs.iter()
-            .filter_map(|(id, pos, health, ammo)| {
+            .map(|(id, pos, health, ammo)| {
                 self.decide_npc_behavior(*id, *pos, player_position, *health, *ammo)
             })
             .collect()
@@ -299,6 +372,8 @@ impl NpuStats {
 mod tests {
     use super::*;
     use crate::detect_npu;
+    use crate::error::HardwareResult;
+    use crate::npu::sdk::InferenceHandle;
 
     #[test]
     fn test_npu_accelerator() {
@@ -320,31 +395,123 @@ mod tests {
         let npu = detect_npu();
         let accelerator = NpuAccelerator::new(npu);
         
-        if let Some(prediction) = accelerator.predict_physics(
+        let prediction = accelerator.predict_physics(
             1,
             [0.0, 10.0, 0.0],
             [1.0, 0.0, 0.0],
             1.0,
-        ) {
-            println!("Physics Prediction: {:#?}", prediction);
-            assert!(prediction.confidence > 0.0);
+        );
+        println!("Physics Prediction: {:#?}", prediction);
+        assert!(prediction.confidence > 0.0);
+    }
+    
+    #[test]
+    fn test_physics_prediction_cpu_fallback() {
+        let accelerator = NpuAccelerator::new(None);
+        assert_eq!(accelerator.backend(), AccelerationBackend::CpuFallback);
+        assert!(!accelerator.is_enabled());
+        
+        let prediction = accelerator.predict_physics(
+            7,
+            [0.0, 10.0, 0.0],
+            [2.0, 0.0, 0.0],
+            0.5,
+        );
+        assert_eq!(prediction.object_id, 7);
+        assert_eq!(prediction.predicted_position, [1.0, 10.0, 0.0]);
+        assert!((prediction.predicted_velocity[1] + 4.9).abs() < 1e-5);
+        assert!(prediction.confidence > 0.0 && prediction.confidence <= 1.0);
+        
+        let batch = accelerator.predict_physics_batch(&[(1, [0.0; 3], [0.0; 3])], 0.1);
+        assert_eq!(batch.len(), 1);
+    }
+
+    /// 返回固定输出的推理引擎
+    struct FixedOutputEngine {
+        output: Vec<f32>,
+        backend: NpuBackend,
+    }
+    
+    impl NpuInferenceEngine for FixedOutputEngine {
+        fn load_model(&mut self, _model_path: &std::path::Path) -> HardwareResult<()> {
+            Ok(())
+        }
+        
+        fn infer(&self, input: &[f32]) -> HardwareResult<Vec<f32>> {
+            assert_eq!(input.len(), 7);
+            Ok(self.output.clone())
+        }
+        
+        fn infer_async(&self, _input: &[f32]) -> HardwareResult<InferenceHandle> {
+            Ok(InferenceHandle {
+                backend: self.backend,
+            })
+        }
+        
+        fn infer_batch(&self, inputs: &[&[f32]]) -> HardwareResult<Vec<Vec<f32>>> {
+            inputs.iter().map(|input| self.infer(input)).collect()
+        }
+        
+        fn input_shape(&self) -> &[usize] {
+            &[1, 7]
+        }
+        
+        fn output_shape(&self) -> &[usize] {
+            &[1, 7]
+        }
+        
+        fn warmup(&mut self) -> HardwareResult<()> {
+            Ok(())
+        }
+        
+        fn backend(&self) -> NpuBackend {
+            self.backend
         }
     }
+    
+    #[test]
+    fn test_physics_prediction_uses_npu_model() {
+        let mut accelerator = NpuAccelerator::new(None);
+        accelerator.set_physics_model(Box::new(FixedOutputEngine {
+            output: vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 0.5],
+            backend: NpuBackend::OnnxRuntime,
+        }));
+        assert_eq!(accelerator.backend(), AccelerationBackend::Npu);
+        
+        let prediction = accelerator.predict_physics(3, [0.0; 3], [0.0; 3], 1.0);
+        assert_eq!(prediction.predicted_position, [1.0, 2.0, 3.0]);
+        assert_eq!(prediction.predicted_velocity, [4.0, 5.0, 6.0]);
+        assert_eq!(prediction.confidence, 0.5);
+        
+        // 输出不完整时回退到CPU积分
+        accelerator.set_physics_model(Box::new(FixedOutputEngine {
+            output: vec![1.0],
+            backend: NpuBackend::OnnxRuntime,
+        }));
+        let prediction = accelerator.predict_physics(3, [0.0; 3], [2.0, 0.0, 0.0], 0.5);
+        assert_eq!(prediction.predicted_position, [1.0, 0.0, 0.0]);
+        
+        // CPU回退引擎不算NPU后端
+        accelerator.set_physics_model(Box::new(FixedOutputEngine {
+            output: vec![0.0; 7],
+            backend: NpuBackend::CpuFallback,
+        }));
+        assert_eq!(accelerator.backend(), AccelerationBackend::CpuFallback);
+    }
 
     #[test]
     fn test_npc_behavior() {
         let npu = detect_npu();
         let accelerator = NpuAccelerator::new(npu);
         
-        if let Some(decision) = accelerator.decide_npc_behavior(
+        let decision = accelerator.decide_npc_behavior(
             1,
             [10.0, 0.0, 0.0],
             [0.0, 0.0, 0.0],
             0.5,
             10,
-        ) {
-            println!("NPC Decision: {:#?}", decision);
-            assert!(decision.priority > 0.0);
-        }
+        );
+        println!("NPC Decision: {:#?}", decision);
+        assert!(decision.priority > 0.0);
     }
 }
@@ -6,7 +6,7 @@ pub mod upscaling;
 pub mod sdk;
 
 pub use detect::{NpuInfo, NpuVendor, detect_npu};
-pub use acceleration::{NpuAccelerator, PhysicsPrediction, BehaviorDecision};
+pub use acceleration::{NpuAccelerator, AccelerationBackend, PhysicsPrediction, BehaviorDecision};
 pub use upscaling::{NpuUpscalingEngine, NpuUpscalingManager, HybridUpscalingStrategy, AiUpscalingModel};
 pub use sdk::extended::{OpenVINOEngine, ROCmEngine, AscendEngine, SNPEEngine, NeuroPilotEngine};
 
@@ -2,6 +2,8 @@
 /// 
 /// 检测移动和嵌入式平台的SoC信息
 
+use std::path::Path;
+
 /// SoC厂商
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum SocVendor {
@@ -24,6 +26,16 @@ pub struct SocInfo {
     pub max_cpu_freq_mhz: u32,
     pub process_node_nm: u32,
     pub thermal_design_power_w: f32,
+    /// 性能核（大核）数量，未知时为0
+    #[serde(default)]
+    pub performance_cores: u32,
+    /// 能效核（小核）数量，未知时为0
+    #[serde(default)]
+    pub efficiency_cores: u32,
+    #[serde(default)]
+    pub l2_cache_kb: Option<u32>,
+    #[serde(default)]
+    pub l3_cache_kb: Option<u32>,
 }
 
 impl Default for SocInfo {
@@ -36,12 +48,172 @@ impl Default for SocInfo {
             max_cpu_freq_mhz: 2000,
             process_node_nm: 7,
             thermal_design_power_w: 5.0,
+            performance_cores: 0,
+            efficiency_cores: 0,
+            l2_cache_kb: None,
+            l3_cache_kb: None,
+        }
+    }
+}
+
+impl SocInfo {
+    /// 应用检测到的CPU拓扑
+    pub fn apply_topology(&mut self, topology: &CpuTopology) {
+        if topology.core_count() > 0 {
+            self.cpu_cores = topology.core_count();
+            self.performance_cores = topology.performance_cores;
+            self.efficiency_cores = topology.efficiency_cores;
+        }
+        self.l2_cache_kb = topology.l2_cache_kb.or(self.l2_cache_kb);
+        self.l3_cache_kb = topology.l3_cache_kb.or(self.l3_cache_kb);
+    }
+}
+
+/// CPU核心拓扑（大小核划分和缓存层级）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuTopology {
+    pub performance_cores: u32,
+    pub efficiency_cores: u32,
+    pub l2_cache_kb: Option<u32>,
+    pub l3_cache_kb: Option<u32>,
+}
+
+impl CpuTopology {
+    pub fn core_count(&self) -> u32 {
+        self.performance_cores + self.efficiency_cores
+    }
+    
+    /// 从sysfs目录（通常为 `/sys/devices/system/cpu`）读取拓扑
+    /// 
+    /// 最高频率最大的核心视为性能核，其余为能效核；所有核心频率相同时全部视为性能核
+    pub fn from_sysfs(cpu_root: &Path) -> Option<Self> {
+        let mut max_freqs = Vec::new();
+        let mut l2_cache_kb = None;
+        let mut l3_cache_kb = None;
+        
+        for entry in std::fs::read_dir(cpu_root).ok()?.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Some(index) = name.strip_prefix("cpu") else {
+                continue;
+            };
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+            
+            let cpu_dir = entry.path();
+            // 没有cpufreq信息的核心频率记为0
+            let max_freq = read_trimmed(&cpu_dir.join("cpufreq/cpuinfo_max_freq"))
+                .and_then(|v| v.parse::<u64>().ok())
+                .unwrap_or(0);
+            max_freqs.push(max_freq);
+            
+            // 大小核的L2可能不同，取最大值
+            let Ok(caches) = std::fs::read_dir(cpu_dir.join("cache")) else {
+                continue;
+            };
+            for cache in caches.flatten() {
+                let path = cache.path();
+                let level = read_trimmed(&path.join("level"));
+                let size = read_trimmed(&path.join("size")).and_then(|v| parse_cache_size_kb(&v));
+                let slot = match level.as_deref() {
+                    Some("2") => &mut l2_cache_kb,
+                    Some("3") => &mut l3_cache_kb,
+                    _ => continue,
+                };
+                if let Some(size) = size {
+                    *slot = Some(slot.map_or(size, |current: u32| current.max(size)));
+                }
+            }
+        }
+        
+        if max_freqs.is_empty() {
+            return None;
+        }
+        
+        let (performance_cores, efficiency_cores) = classify_cores(&max_freqs);
+        Some(Self {
+            performance_cores,
+            efficiency_cores,
+            l2_cache_kb,
+            l3_cache_kb,
+        })
+    }
+    
+    /// 通过sysctl读取macOS的性能级别和缓存信息
+    #[cfg(target_os = "macos")]
+    fn from_sysctl() -> Option<Self> {
+        fn sysctl(name: &str) -> Option<u64> {
+            let output = std::process::Command::new("sysctl").arg("-n").arg(name).output().ok()?;
+            String::from_utf8(output.stdout).ok()?.trim().parse().ok()
         }
+        
+        let performance_cores = sysctl("hw.perflevel0.physicalcpu")? as u32;
+        let efficiency_cores = sysctl("hw.perflevel1.physicalcpu").unwrap_or(0) as u32;
+        let to_kb = |bytes: u64| (bytes / 1024) as u32;
+        Some(Self {
+            performance_cores,
+            efficiency_cores,
+            l2_cache_kb: sysctl("hw.perflevel0.l2cachesize")
+                .or_else(|| sysctl("hw.l2cachesize"))
+                .map(to_kb),
+            l3_cache_kb: sysctl("hw.l3cachesize").filter(|v| *v > 0).map(to_kb),
+        })
     }
 }
 
+/// 检测当前平台的CPU拓扑
+pub fn detect_cpu_topology() -> Option<CpuTopology> {
+    #[cfg(target_os = "macos")]
+    {
+        CpuTopology::from_sysctl()
+    }
+    
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        CpuTopology::from_sysfs(Path::new("/sys/devices/system/cpu"))
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "android")))]
+    {
+        None
+    }
+}
+
+/// 按最高频率划分性能核和能效核
+fn classify_cores(max_freqs: &[u64]) -> (u32, u32) {
+    let top = max_freqs.iter().copied().max().unwrap_or(0);
+    let performance = max_freqs.iter().filter(|f| **f == top).count() as u32;
+    (performance, max_freqs.len() as u32 - performance)
+}
+
+/// 解析sysfs缓存大小，如 "512K"、"8M"
+fn parse_cache_size_kb(value: &str) -> Option<u32> {
+    let value = value.trim();
+    if let Some(kb) = value.strip_suffix('K') {
+        kb.parse().ok()
+    } else if let Some(mb) = value.strip_suffix('M') {
+        mb.parse::<u32>().ok().map(|mb| mb * 1024)
+    } else {
+        value.parse::<u32>().ok().map(|bytes| bytes / 1024)
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|v| v.trim().to_string())
+}
+
 /// 检测SoC信息
 pub fn detect_soc() -> Option<SocInfo> {
+    let mut info = detect_soc_model()?;
+    if let Some(topology) = detect_cpu_topology() {
+        info.apply_topology(&topology);
+    }
+    Some(info)
+}
+
+/// 根据型号识别SoC
+fn detect_soc_model() -> Option<SocInfo> {
     #[cfg(target_os = "macos")]
     {
         if let Some(info) = detect_apple_soc() {
@@ -88,6 +260,7 @@ fn detect_apple_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 4050,
                     process_node_nm: 3,
                     thermal_design_power_w: 30.0,
+                    ..SocInfo::default()
                 });
             } else if brand_lower.contains("m3 pro") {
                 return Some(SocInfo {
@@ -98,6 +271,7 @@ fn detect_apple_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 4050,
                     process_node_nm: 3,
                     thermal_design_power_w: 20.0,
+                    ..SocInfo::default()
                 });
             } else if brand_lower.contains("m3") {
                 return Some(SocInfo {
@@ -108,6 +282,7 @@ fn detect_apple_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 4050,
                     process_node_nm: 3,
                     thermal_design_power_w: 15.0,
+                    ..SocInfo::default()
                 });
             } else if brand_lower.contains("m2") {
                 return Some(SocInfo {
@@ -118,6 +293,7 @@ fn detect_apple_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 3500,
                     process_node_nm: 5,
                     thermal_design_power_w: 15.0,
+                    ..SocInfo::default()
                 });
             } else if brand_lower.contains("m1") {
                 return Some(SocInfo {
@@ -128,6 +304,7 @@ fn detect_apple_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 3200,
                     process_node_nm: 5,
                     thermal_design_power_w: 15.0,
+                    ..SocInfo::default()
                 });
             }
         }
@@ -155,6 +332,7 @@ fn detect_android_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 3300,
                     process_node_nm: 4,
                     thermal_design_power_w: 10.0,
+                    ..SocInfo::default()
                 });
             } else if content_lower.contains("8 gen 2") {
                 return Some(SocInfo {
@@ -165,6 +343,7 @@ fn detect_android_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 3200,
                     process_node_nm: 4,
                     thermal_design_power_w: 9.0,
+                    ..SocInfo::default()
                 });
             }
         }
@@ -180,6 +359,7 @@ fn detect_android_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 3250,
                     process_node_nm: 4,
                     thermal_design_power_w: 10.0,
+                    ..SocInfo::default()
                 });
             }
         }
@@ -194,6 +374,7 @@ fn detect_android_soc() -> Option<SocInfo> {
                 max_cpu_freq_mhz: 2900,
                 process_node_nm: 5,
                 thermal_design_power_w: 8.0,
+                ..SocInfo::default()
             });
         }
         
@@ -207,6 +388,7 @@ fn detect_android_soc() -> Option<SocInfo> {
                 max_cpu_freq_mhz: 2860,
                 process_node_nm: 5,
                 thermal_design_power_w: 8.0,
+                ..SocInfo::default()
             });
         }
     }
@@ -228,6 +410,7 @@ fn detect_android_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 3000,
                     process_node_nm: 5,
                     thermal_design_power_w: 9.0,
+                    ..SocInfo::default()
                 });
             }
         }
@@ -254,6 +437,7 @@ fn detect_linux_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 2200,
                     process_node_nm: 8,
                     thermal_design_power_w: 60.0,
+                    ..SocInfo::default()
                 });
             } else if content_lower.contains("xavier") {
                 return Some(SocInfo {
@@ -264,6 +448,7 @@ fn detect_linux_soc() -> Option<SocInfo> {
                     max_cpu_freq_mhz: 2260,
                     process_node_nm: 12,
                     thermal_design_power_w: 30.0,
+                    ..SocInfo::default()
                 });
             }
         }
@@ -303,4 +488,39 @@ mod tests {
         let is_mobile = is_mobile_platform();
         println!("Is mobile platform: {}", is_mobile);
     }
+    
+    #[test]
+    fn test_synthetic_big_little_topology() {
+        let root = std::env::temp_dir().join(format!("soc_topology_{}", std::process::id()));
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        
+        // 2个大核 + 6个小核
+        for cpu in 0..8 {
+            let freq = if cpu < 6 { "1800000" } else { "3200000" };
+            write(&format!("cpu{}/cpufreq/cpuinfo_max_freq", cpu), freq);
+            let l2 = if cpu < 6 { "256K" } else { "1024K" };
+            write(&format!("cpu{}/cache/index2/level", cpu), "2");
+            write(&format!("cpu{}/cache/index2/size", cpu), l2);
+            write(&format!("cpu{}/cache/index3/level", cpu), "3");
+            write(&format!("cpu{}/cache/index3/size", cpu), "8M");
+        }
+        // 非核心目录应被忽略
+        write("cpufreq/policy0/scaling_governor", "schedutil");
+        
+        let topology = CpuTopology::from_sysfs(&root).unwrap();
+        std::fs::remove_dir_all(&root).ok();
+        
+        assert_eq!(topology.performance_cores, 2);
+        assert_eq!(topology.efficiency_cores, 6);
+        assert_eq!(topology.l2_cache_kb, Some(1024));
+        assert_eq!(topology.l3_cache_kb, Some(8192));
+        
+        let mut soc = SocInfo::default();
+        soc.apply_topology(&topology);
+        assert_eq!(soc.performance_cores + soc.efficiency_cores, soc.cpu_cores);
+    }
 }
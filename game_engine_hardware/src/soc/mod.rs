@@ -3,6 +3,6 @@
 pub mod detect;
 pub mod power;
 
-pub use detect::{SocInfo, SocVendor, detect_soc};
-pub use power::{PowerManager, PowerMode, ThermalState};
+pub use detect::{CpuTopology, SocInfo, SocVendor, detect_cpu_topology, detect_soc};
+pub use power::{PowerManager, PowerMode, ThermalEvent, ThermalState};
 
@@ -4,7 +4,14 @@
 
 use super::detect::{SocInfo, SocVendor};
 use crate::utils::ring_buffer::RingBuffer;
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 没有SoC信息时（桌面平台）假定的整机功耗
+const DESKTOP_POWER_W: f32 = 65.0;
+
+/// 传感器读数的有效期，超过后回退到按负载估算的温度
+const SENSOR_READING_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// 功耗管理器
 pub struct PowerManager {
@@ -13,6 +20,19 @@ pub struct PowerManager {
     power_mode: PowerMode,
     performance_history: PerformanceHistory,
     last_update: Instant,
+    thermal_events: VecDeque<ThermalEvent>,
+    power_budget_w: Option<f32>,
+    /// 最近一次传感器读数及其时间
+    reported_temperature: Option<(f32, Instant)>,
+}
+
+/// 热状态升高事件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalEvent {
+    pub from: ThermalState,
+    pub to: ThermalState,
+    /// 触发时的温度（°C）
+    pub temperature: f32,
 }
 
 /// 热状态
@@ -29,7 +49,7 @@ pub enum ThermalState {
 }
 
 /// 功耗模式
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PowerMode {
     /// 省电模式
     PowerSaver,
@@ -41,6 +61,18 @@ pub enum PowerMode {
     Extreme,
 }
 
+impl PowerMode {
+    /// 相对TDP的功耗系数
+    fn power_factor(&self) -> f32 {
+        match self {
+            PowerMode::PowerSaver => 0.4,
+            PowerMode::Balanced => 0.7,
+            PowerMode::Performance => 1.0,
+            PowerMode::Extreme => 1.3,
+        }
+    }
+}
+
 /// 性能历史记录
 struct PerformanceHistory {
     frame_times: RingBuffer<f32>,
@@ -104,6 +136,9 @@ impl PowerManager {
             power_mode,
             performance_history: PerformanceHistory::new(300), // 5秒历史（60fps）
             last_update: Instant::now(),
+            thermal_events: VecDeque::new(),
+            power_budget_w: None,
+            reported_temperature: None,
         }
     }
     
@@ -113,8 +148,65 @@ impl PowerManager {
     }
     
     /// 设置功耗模式
+    /// 
+    /// 设置了功耗预算时，模式会被限制在预算允许的最高档位
     pub fn set_power_mode(&mut self, mode: PowerMode) {
-        self.power_mode = mode;
+        self.power_mode = mode.min(self.max_mode_for_budget());
+    }
+    
+    /// 设置功耗预算（瓦）
+    pub fn set_power_budget(&mut self, watts: f32) {
+        self.power_budget_w = Some(watts.max(0.0));
+        self.power_mode = self.power_mode.min(self.max_mode_for_budget());
+    }
+    
+    /// 取消功耗预算
+    pub fn clear_power_budget(&mut self) {
+        self.power_budget_w = None;
+    }
+    
+    /// 当前功耗预算
+    pub fn power_budget(&self) -> Option<f32> {
+        self.power_budget_w
+    }
+    
+    /// 估算指定模式下的功耗（瓦）
+    pub fn estimated_power(&self, mode: PowerMode) -> f32 {
+        let tdp = self
+            .soc_info
+            .as_ref()
+            .map(|soc| soc.thermal_design_power_w)
+            .unwrap_or(DESKTOP_POWER_W);
+        tdp * mode.power_factor()
+    }
+    
+    /// 预算允许的最高功耗模式，预算低于所有模式时返回省电模式
+    fn max_mode_for_budget(&self) -> PowerMode {
+        let Some(budget) = self.power_budget_w else {
+            return PowerMode::Extreme;
+        };
+        [
+            PowerMode::Extreme,
+            PowerMode::Performance,
+            PowerMode::Balanced,
+        ]
+        .into_iter()
+        .find(|mode| self.estimated_power(*mode) <= budget)
+        .unwrap_or(PowerMode::PowerSaver)
+    }
+    
+    /// 取出下一个热状态升高事件
+    pub fn poll_thermal(&mut self) -> Option<ThermalEvent> {
+        self.thermal_events.pop_front()
+    }
+    
+    /// 报告传感器温度读数（°C）
+    ///
+    /// 读数在 `SENSOR_READING_TIMEOUT` 内优先于按负载估算的温度。
+    pub fn report_temperature(&mut self, temp: f32) {
+        self.reported_temperature = Some((temp, Instant::now()));
+        self.performance_history.add_thermal_reading(temp);
+        self.apply_temperature(temp);
     }
     
     /// 获取当前功耗模式
@@ -131,11 +223,18 @@ impl PowerManager {
     pub fn update(&mut self, frame_time_ms: f32) {
         self.performance_history.add_frame_time(frame_time_ms);
         
-        // 检测温度（简化实现）
-        let temp = self.estimate_temperature();
+        // 优先使用传感器读数，没有有效读数时按负载估算
+        let temp = self.current_temperature();
         self.performance_history.add_thermal_reading(temp);
         
         // 更新热状态
+        self.apply_temperature(temp);
+        
+        self.last_update = Instant::now();
+    }
+    
+    /// 根据温度更新热状态，升温时记录事件
+    fn apply_temperature(&mut self, temp: f32) {
         let new_thermal_state = self.classify_thermal_state(temp);
         
         // 如果热状态变化，记录日志
@@ -143,10 +242,15 @@ impl PowerManager {
             tracing::info!(target: "power_management", 
                 "Thermal state changed: {:?} -> {:?} (temp: {:.1}°C)", 
                 self.thermal_state, new_thermal_state, temp);
+            if new_thermal_state > self.thermal_state {
+                self.thermal_events.push_back(ThermalEvent {
+                    from: self.thermal_state,
+                    to: new_thermal_state,
+                    temperature: temp,
+                });
+            }
             self.thermal_state = new_thermal_state;
         }
-        
-        self.last_update = Instant::now();
     }
     
     /// 动态调整性能（根据热状态）
@@ -163,7 +267,7 @@ impl PowerManager {
            self.power_mode == PowerMode::PowerSaver {
             let avg_frame_time = self.performance_history.average_frame_time();
             // 如果帧时间稳定且低于目标，可以提升性能
-            if avg_frame_time < 20.0 {
+            if avg_frame_time < 20.0 && self.max_mode_for_budget() >= PowerMode::Balanced {
                 tracing::info!(target: "power_management", 
                     "Device temperature normal, switching to balanced mode");
                 self.power_mode = PowerMode::Balanced;
@@ -173,6 +277,14 @@ impl PowerManager {
         self.get_adjustment_recommendation()
     }
     
+    /// 当前温度：未过期的传感器读数，否则为估算温度
+    fn current_temperature(&self) -> f32 {
+        match self.reported_temperature {
+            Some((temp, at)) if at.elapsed() <= SENSOR_READING_TIMEOUT => temp,
+            _ => self.estimate_temperature(),
+        }
+    }
+    
     /// 估算温度
     fn estimate_temperature(&self) -> f32 {
         // 简化的温度估算（实际应该读取系统传感器）
@@ -392,4 +504,72 @@ mod tests {
             println!("  {}", tip);
         }
     }
+    
+    #[test]
+    fn test_rising_temperature_emits_thermal_event() {
+        let mut manager = PowerManager::new(Some(SocInfo::default()));
+        assert!(manager.poll_thermal().is_none());
+        
+        for temp in [38.0, 42.0, 44.0] {
+            manager.report_temperature(temp);
+        }
+        assert!(manager.poll_thermal().is_none());
+        
+        // 一次性从正常升到需要降频的温度
+        manager.report_temperature(68.0);
+        let event = manager.poll_thermal().unwrap();
+        assert_eq!(event.from, ThermalState::Normal);
+        assert_eq!(event.to, ThermalState::Critical);
+        assert_eq!(event.temperature, 68.0);
+        assert!(manager.poll_thermal().is_none());
+        
+        // 降温不产生事件
+        manager.report_temperature(40.0);
+        assert!(manager.poll_thermal().is_none());
+    }
+    
+    #[test]
+    fn test_update_prefers_reported_temperature() {
+        let mut manager = PowerManager::new(Some(SocInfo::default()));
+        
+        // 估算温度约为 55°C（发热），传感器读数为过热
+        manager.report_temperature(68.0);
+        for _ in 0..10 {
+            manager.update(16.67);
+        }
+        assert_eq!(manager.thermal_state(), ThermalState::Critical);
+        
+        // 传感器读数降温后同样以读数为准
+        manager.report_temperature(40.0);
+        manager.update(16.67);
+        assert_eq!(manager.thermal_state(), ThermalState::Normal);
+        
+        // 读数过期后回退到估算温度
+        let Some(stale) = Instant::now().checked_sub(SENSOR_READING_TIMEOUT * 2) else {
+            return;
+        };
+        manager.reported_temperature = Some((40.0, stale));
+        manager.update(16.67);
+        assert_eq!(manager.thermal_state(), ThermalState::Hot);
+    }
+    
+    #[test]
+    fn test_power_budget_caps_mode() {
+        let soc = SocInfo {
+            thermal_design_power_w: 10.0,
+            ..SocInfo::default()
+        };
+        let mut manager = PowerManager::new(Some(soc));
+        
+        manager.set_power_budget(8.0);
+        manager.set_power_mode(PowerMode::Extreme);
+        assert_eq!(manager.power_mode(), PowerMode::Balanced);
+        
+        manager.set_power_budget(2.0);
+        assert_eq!(manager.power_mode(), PowerMode::PowerSaver);
+        
+        manager.clear_power_budget();
+        manager.set_power_mode(PowerMode::Extreme);
+        assert_eq!(manager.power_mode(), PowerMode::Extreme);
+    }
 }
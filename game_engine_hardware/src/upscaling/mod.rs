@@ -7,6 +7,7 @@ pub mod sdk;
 pub use sdk::{UpscalingEngine, UpscalingTechnology, UpscalingQuality, TextureHandle};
 
 use crate::gpu::detect::{GpuInfo, GpuVendor};
+use crate::HardwareInfo;
 use serde::{Serialize, Deserialize};
 
 /// 超分辨率技术（兼容性别名）
@@ -24,6 +25,8 @@ pub enum UpscalingTech {
     MetalFX,
     /// 通用TAA超采样
     TAAUpsampling,
+    /// 基于NPU的AI超分辨率
+    NpuUpscaling,
 }
 
 // UpscalingQuality and its methods are defined in sdk.rs
@@ -39,7 +42,16 @@ pub struct UpscalingManager {
 impl UpscalingManager {
     /// 创建超分辨率管理器
     pub fn new(gpu_info: GpuInfo) -> Self {
-        let available_techs = Self::detect_available_techs(&gpu_info);
+        Self::create(gpu_info, false)
+    }
+    
+    /// 根据完整硬件信息创建（包含NPU超分辨率路径）
+    pub fn from_hardware(hw: &HardwareInfo) -> Self {
+        Self::create(hw.gpu.clone(), hw.npu.is_some())
+    }
+    
+    fn create(gpu_info: GpuInfo, has_npu: bool) -> Self {
+        let available_techs = Self::detect_available_techs(&gpu_info, has_npu);
         let active_tech = Self::select_best_tech(&available_techs, &gpu_info);
         let quality_mode = Self::default_quality_mode(&gpu_info);
         
@@ -51,8 +63,16 @@ impl UpscalingManager {
         }
     }
     
+    /// 当前硬件实际可用的超分辨率技术
+    /// 
+    /// DLSS仅限NVIDIA RTX，XeSS仅限Intel，FSR在所有GPU上可用，
+    /// 存在NPU时额外提供NPU超分辨率
+    pub fn available_techs_for(hw: &HardwareInfo) -> Vec<UpscalingTech> {
+        Self::detect_available_techs(&hw.gpu, hw.npu.is_some())
+    }
+    
     /// 检测可用的超分辨率技术
-    fn detect_available_techs(gpu: &GpuInfo) -> Vec<UpscalingTech> {
+    fn detect_available_techs(gpu: &GpuInfo, has_npu: bool) -> Vec<UpscalingTech> {
         let mut techs = vec![UpscalingTech::None, UpscalingTech::TAAUpsampling];
         
         match gpu.vendor {
@@ -79,6 +99,7 @@ impl UpscalingManager {
             GpuVendor::Apple => {
                 // Apple GPU支持MetalFX
                 techs.push(UpscalingTech::MetalFX);
+                techs.push(UpscalingTech::FSR);
             }
             _ => {
                 // 其他GPU至少支持FSR（开源）
@@ -86,6 +107,10 @@ impl UpscalingManager {
             }
         }
         
+        if has_npu {
+            techs.push(UpscalingTech::NpuUpscaling);
+        }
+        
         techs
     }
     
@@ -137,6 +162,31 @@ impl UpscalingManager {
         self.active_tech
     }
     
+    /// 选择超分辨率技术，返回实际启用的技术
+    /// 
+    /// 请求的技术在当前硬件上不可用时，回退到 FSR > TAA > None
+    pub fn select(&mut self, requested: UpscalingTech) -> UpscalingTech {
+        let chosen = [
+            requested,
+            UpscalingTech::FSR,
+            UpscalingTech::TAAUpsampling,
+        ]
+        .into_iter()
+        .find(|tech| self.available_techs.contains(tech))
+        .unwrap_or(UpscalingTech::None);
+        
+        if chosen != requested {
+            tracing::warn!(
+                "{:?} is not available on {}, falling back to {:?}",
+                requested,
+                self.gpu_info.name,
+                chosen
+            );
+        }
+        self.active_tech = chosen;
+        chosen
+    }
+    
     /// 设置激活的技术
     pub fn set_active_tech(&mut self, tech: UpscalingTech) -> Result<(), String> {
         if !self.available_techs.contains(&tech) {
@@ -186,6 +236,7 @@ impl UpscalingManager {
             UpscalingTech::FSR => 0.98,       // FSR开销极小
             UpscalingTech::MetalFX => 0.96,   // MetalFX开销小
             UpscalingTech::TAAUpsampling => 0.90, // TAA开销较大
+            UpscalingTech::NpuUpscaling => 0.97, // 在NPU上执行，GPU开销小
             _ => 1.0,
         };
         
@@ -201,6 +252,7 @@ impl UpscalingManager {
             UpscalingTech::XeSS => "Intel XeSS - 基于AI的超分辨率，适用于Intel GPU",
             UpscalingTech::MetalFX => "Apple MetalFX - Apple平台专用超分辨率",
             UpscalingTech::TAAUpsampling => "TAA超采样 - 通用时域抗锯齿超采样",
+            UpscalingTech::NpuUpscaling => "NPU超分辨率 - 在NPU上运行AI模型，减轻GPU负载",
         }
     }
     
@@ -256,7 +308,27 @@ impl UpscalingManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::detect_gpu;
+    use crate::{detect_gpu, GpuTier, HardwareCapability, AutoConfig};
+    
+    fn amd_hardware() -> HardwareInfo {
+        let gpu = GpuInfo {
+            vendor: GpuVendor::Amd,
+            name: "AMD Radeon RX 7800 XT".to_string(),
+            tier: GpuTier::High,
+            vram_mb: 16384,
+            supports_raytracing: true,
+            ..GpuInfo::default()
+        };
+        let capability = HardwareCapability::evaluate(&gpu, &None, &None);
+        let recommended_config = AutoConfig::from_capability(&capability);
+        HardwareInfo {
+            gpu,
+            npu: None,
+            soc: None,
+            capability,
+            recommended_config,
+        }
+    }
 
     #[test]
     fn test_upscaling_manager() {
@@ -287,4 +359,18 @@ mod tests {
             println!("  {}", rec);
         }
     }
+    
+    #[test]
+    fn test_dlss_on_amd_falls_back_to_fsr() {
+        let hw = amd_hardware();
+        let techs = UpscalingManager::available_techs_for(&hw);
+        assert!(techs.contains(&UpscalingTech::FSR));
+        assert!(!techs.contains(&UpscalingTech::DLSS));
+        assert!(!techs.contains(&UpscalingTech::NpuUpscaling));
+        
+        let mut manager = UpscalingManager::from_hardware(&hw);
+        assert_eq!(manager.select(UpscalingTech::DLSS), UpscalingTech::FSR);
+        assert_eq!(manager.active_tech(), UpscalingTech::FSR);
+        assert_eq!(manager.select(UpscalingTech::TAAUpsampling), UpscalingTech::TAAUpsampling);
+    }
 }
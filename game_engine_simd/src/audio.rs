@@ -7,6 +7,7 @@
 //! - 多普勒效应计算
 //! - 实时音频DSP处理
 
+use crate::SimdBackend;
 use glam::Vec3;
 
 /// SIMD 音频空间计算结果
@@ -694,6 +695,133 @@ impl AudioDSPOps {
         }
     }
     
+    /// 把多个音频流按增益累加到输出缓冲区 (SIMD 优化，不分配内存)
+    ///
+    /// `output[i] += sources[s][i] * gains[s]`，对所有源求和。每个源至少要有
+    /// `output.len()` 个样本，缺少增益的源按 1.0 处理。按 [`SimdBackend::best_available`]
+    /// 选择实现，不足一个 SIMD 寄存器宽度的尾部样本用标量处理，
+    /// 各实现的累加顺序相同，结果与标量实现一致。
+    ///
+    /// # Arguments
+    /// * `sources` - 音频源数组
+    /// * `gains` - 每个源的增益系数数组
+    /// * `output` - 累加目标，调用前的内容会保留
+    pub fn mix_into(sources: &[&[f32]], gains: &[f32], output: &mut [f32]) {
+        assert!(
+            sources.iter().all(|source| source.len() >= output.len()),
+            "every source must provide at least {} samples",
+            output.len()
+        );
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            match SimdBackend::best_available() {
+                SimdBackend::Avx512 | SimdBackend::Avx2 | SimdBackend::Avx => {
+                    if is_x86_feature_detected!("avx") {
+                        unsafe {
+                            return Self::mix_into_avx(sources, gains, output);
+                        }
+                    }
+                }
+                SimdBackend::Sse41 | SimdBackend::Sse2 => {
+                    if is_x86_feature_detected!("sse2") {
+                        unsafe {
+                            return Self::mix_into_sse2(sources, gains, output);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            let backend = SimdBackend::best_available();
+            if backend == SimdBackend::Neon || backend == SimdBackend::Sve {
+                unsafe {
+                    return Self::mix_into_neon(sources, gains, output);
+                }
+            }
+        }
+
+        // 标量回退
+        Self::mix_into_scalar(sources, gains, output, 0);
+    }
+
+    /// AVX 优化的累加混合，一次处理 8 个样本
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx")]
+    unsafe fn mix_into_avx(sources: &[&[f32]], gains: &[f32], output: &mut [f32]) {
+        use std::arch::x86_64::*;
+
+        let simd_len = output.len() - output.len() % 8;
+        for offset in (0..simd_len).step_by(8) {
+            let mut sum_v = _mm256_loadu_ps(output.as_ptr().add(offset));
+            for (source_idx, source) in sources.iter().enumerate() {
+                let gain_v = _mm256_set1_ps(gains.get(source_idx).copied().unwrap_or(1.0));
+                let source_v = _mm256_loadu_ps(source.as_ptr().add(offset));
+                sum_v = _mm256_add_ps(sum_v, _mm256_mul_ps(source_v, gain_v));
+            }
+            _mm256_storeu_ps(output.as_mut_ptr().add(offset), sum_v);
+        }
+
+        // 处理剩余样本
+        Self::mix_into_scalar(sources, gains, output, simd_len);
+    }
+
+    /// SSE2 优化的累加混合，一次处理 4 个样本
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "sse2")]
+    unsafe fn mix_into_sse2(sources: &[&[f32]], gains: &[f32], output: &mut [f32]) {
+        use std::arch::x86_64::*;
+
+        let simd_len = output.len() - output.len() % 4;
+        for offset in (0..simd_len).step_by(4) {
+            let mut sum_v = _mm_loadu_ps(output.as_ptr().add(offset));
+            for (source_idx, source) in sources.iter().enumerate() {
+                let gain_v = _mm_set1_ps(gains.get(source_idx).copied().unwrap_or(1.0));
+                let source_v = _mm_loadu_ps(source.as_ptr().add(offset));
+                sum_v = _mm_add_ps(sum_v, _mm_mul_ps(source_v, gain_v));
+            }
+            _mm_storeu_ps(output.as_mut_ptr().add(offset), sum_v);
+        }
+
+        // 处理剩余样本
+        Self::mix_into_scalar(sources, gains, output, simd_len);
+    }
+
+    /// ARM NEON 优化的累加混合，一次处理 4 个样本
+    #[cfg(target_arch = "aarch64")]
+    unsafe fn mix_into_neon(sources: &[&[f32]], gains: &[f32], output: &mut [f32]) {
+        use std::arch::aarch64::*;
+
+        let simd_len = output.len() - output.len() % 4;
+        for offset in (0..simd_len).step_by(4) {
+            let mut sum_v = vld1q_f32(output.as_ptr().add(offset));
+            for (source_idx, source) in sources.iter().enumerate() {
+                let gain_v = vdupq_n_f32(gains.get(source_idx).copied().unwrap_or(1.0));
+                let source_v = vld1q_f32(source.as_ptr().add(offset));
+                // 不使用融合乘加，保持与标量实现相同的舍入
+                sum_v = vaddq_f32(sum_v, vmulq_f32(source_v, gain_v));
+            }
+            vst1q_f32(output.as_mut_ptr().add(offset), sum_v);
+        }
+
+        // 处理剩余样本
+        Self::mix_into_scalar(sources, gains, output, simd_len);
+    }
+
+    /// 标量累加混合，处理 `start` 之后的样本
+    fn mix_into_scalar(sources: &[&[f32]], gains: &[f32], output: &mut [f32], start: usize) {
+        for (i, out) in output.iter_mut().enumerate().skip(start) {
+            let mut sum = *out;
+            for (source_idx, source) in sources.iter().enumerate() {
+                sum += source[i] * gains.get(source_idx).copied().unwrap_or(1.0);
+            }
+            *out = sum;
+        }
+    }
+
     /// 简单低通滤波器 (SIMD 优化)
     /// 
     /// # Arguments
@@ -859,6 +987,38 @@ mod tests {
         assert_eq!(result.samples.len(), 2);
     }
     
+    #[test]
+    fn test_mix_into_matches_scalar() {
+        // 长度不是任何 SIMD 宽度的整数倍，覆盖尾部处理
+        let len = 1027;
+        let sources: Vec<Vec<f32>> = (0..5)
+            .map(|s| (0..len).map(|i| ((i * 7 + s * 13) % 97) as f32 / 97.0 - 0.5).collect())
+            .collect();
+        let source_refs: Vec<&[f32]> = sources.iter().map(|s| &s[..]).collect();
+        let gains = [0.5, 0.25, 1.0, 0.75];
+
+        let mut expected = vec![0.1f32; len];
+        for (i, out) in expected.iter_mut().enumerate() {
+            for (s, source) in sources.iter().enumerate() {
+                *out += source[i] * gains.get(s).copied().unwrap_or(1.0);
+            }
+        }
+
+        for backend in SimdBackend::supported() {
+            let mut output = vec![0.1f32; len];
+            backend.with_override(|| AudioDSPOps::mix_into(&source_refs, &gains, &mut output));
+            for (actual, expected) in output.iter().zip(&expected) {
+                assert!(
+                    (actual - expected).abs() <= f32::EPSILON * 4.0,
+                    "{:?}: {} != {}",
+                    backend,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_batch_mix_streams_single_source() {
         let source1 = vec![0.1, 0.2, 0.3, 0.4, 0.5];
@@ -23,6 +23,9 @@ impl Default for BoneInfluence {
     }
 }
 
+/// 双四元数 `(real, dual)`，四元数按 `[w, x, y, z]` 排列
+type DualQuat = ([f32; 4], [f32; 4]);
+
 /// 批量蒙皮处理器
 pub struct BatchSkinning {
     config: BatchConfig,
@@ -135,77 +138,15 @@ impl BatchSkinning {
         let count = vertices.len();
         
         for i in 0..count {
-            let v = &vertices[i];
-            let n = &normals[i];
-            let inf = &influences[i];
-            
-            // 混合双四元数
-            let mut blended_real = [0.0f32; 4];
-            let mut blended_dual = [0.0f32; 4];
-            
-            // 确保所有四元数朝向同一方向
-            let first_bone_idx = inf.bone_indices[0] as usize;
-            if first_bone_idx >= bone_dual_quats.len() {
-                continue;
-            }
-            let reference_quat = bone_dual_quats[first_bone_idx].0;
-            
-            for j in 0..4 {
-                let weight = inf.bone_weights[j];
-                if weight > 0.0001 {
-                    let bone_idx = inf.bone_indices[j] as usize;
-                    if bone_idx < bone_dual_quats.len() {
-                        let (mut real, dual) = bone_dual_quats[bone_idx];
-                        
-                        // 检查点积，确保朝向一致
-                        let dot = real[0] * reference_quat[0] + real[1] * reference_quat[1]
-                                + real[2] * reference_quat[2] + real[3] * reference_quat[3];
-                        
-                        let sign = if dot < 0.0 { -1.0 } else { 1.0 };
-                        let adjusted_weight = weight * sign;
-                        
-                        for k in 0..4 {
-                            blended_real[k] += real[k] * adjusted_weight;
-                            blended_dual[k] += dual[k] * adjusted_weight;
-                        }
-                    }
+            match self.blend_dual_quat(&influences[i], bone_dual_quats) {
+                Some((real, dual)) => {
+                    output_vertices[i] = self.apply_dual_quat(&vertices[i], &real, &dual);
+                    output_normals[i] = self.rotate_by_quaternion(&normals[i], &real);
                 }
-            }
-            
-            // 归一化实部四元数
-            let real_len = (blended_real[0] * blended_real[0] + blended_real[1] * blended_real[1]
-                          + blended_real[2] * blended_real[2] + blended_real[3] * blended_real[3]).sqrt();
-            
-            if real_len > 1e-6 {
-                let inv_len = 1.0 / real_len;
-                for k in 0..4 {
-                    blended_real[k] *= inv_len;
-                    blended_dual[k] *= inv_len;
+                None => {
+                    output_vertices[i] = vertices[i];
+                    output_normals[i] = normals[i];
                 }
-                
-                // 从双四元数提取平移
-                let translation = [
-                    2.0 * (-blended_dual[0] * blended_real[1] + blended_dual[1] * blended_real[0]
-                          - blended_dual[2] * blended_real[3] + blended_dual[3] * blended_real[2]),
-                    2.0 * (-blended_dual[0] * blended_real[2] + blended_dual[1] * blended_real[3]
-                          + blended_dual[2] * blended_real[0] - blended_dual[3] * blended_real[1]),
-                    2.0 * (-blended_dual[0] * blended_real[3] - blended_dual[1] * blended_real[2]
-                          + blended_dual[2] * blended_real[1] + blended_dual[3] * blended_real[0]),
-                ];
-                
-                // 使用四元数旋转顶点和法线
-                let rotated_v = self.rotate_by_quaternion(v, &blended_real);
-                let rotated_n = self.rotate_by_quaternion(n, &blended_real);
-                
-                output_vertices[i] = [
-                    rotated_v[0] + translation[0],
-                    rotated_v[1] + translation[1],
-                    rotated_v[2] + translation[2],
-                ];
-                output_normals[i] = rotated_n;
-            } else {
-                output_vertices[i] = *v;
-                output_normals[i] = *n;
             }
         }
         
@@ -216,6 +157,165 @@ impl BatchSkinning {
         }
     }
     
+    /// 双四元数蒙皮（仅顶点位置）
+    ///
+    /// 与 [`BatchSkinning::dual_quaternion_skinning`] 相同的混合方式，
+    /// 适用于不需要法线的场合（如碰撞代理、布料约束点）。
+    /// 在扭转关节处能保持体积，不会出现LBS的"糖果包装"塌陷。
+    /// 默认的蒙皮路径仍是 [`BatchSkinning::linear_blend_skinning`]。
+    ///
+    /// # 参数
+    ///
+    /// * `vertices` - 绑定姿态下的顶点位置
+    /// * `weights` - 每个顶点的骨骼影响
+    /// * `bone_dquats` - 每根骨骼的单位双四元数 `(real, dual)`，四元数按 `[w, x, y, z]` 排列
+    pub fn skin_dual_quat(
+        &self,
+        vertices: &[[f32; 3]],
+        weights: &[BoneInfluence],
+        bone_dquats: &[([f32; 4], [f32; 4])],
+    ) -> Vec<[f32; 3]> {
+        assert_eq!(vertices.len(), weights.len());
+        
+        vertices
+            .iter()
+            .zip(weights)
+            .map(|(v, influence)| match self.blend_dual_quat(influence, bone_dquats) {
+                Some((real, dual)) => self.apply_dual_quat(v, &real, &dual),
+                None => *v,
+            })
+            .collect()
+    }
+    
+    /// 由旋转四元数 `[w, x, y, z]` 和平移构造单位双四元数 `(real, dual)`
+    ///
+    /// 对应先旋转后平移的刚体变换，`dual = 0.5 * t * real`
+    pub fn dual_quat_from_rotation_translation(
+        rotation: [f32; 4],
+        translation: [f32; 3],
+    ) -> ([f32; 4], [f32; 4]) {
+        let [w, x, y, z] = rotation;
+        let [tx, ty, tz] = translation;
+        let dual = [
+            -0.5 * (tx * x + ty * y + tz * z),
+            0.5 * (tx * w + ty * z - tz * y),
+            0.5 * (-tx * z + ty * w + tz * x),
+            0.5 * (tx * y - ty * x + tz * w),
+        ];
+        (rotation, dual)
+    }
+    
+    /// 按权重混合骨骼双四元数并归一化
+    ///
+    /// 以第一个有效影响的实部为参考，点积为负的骨骼取反以保证沿最短路径混合。
+    /// 没有有效影响或混合结果退化时返回 `None`。
+    fn blend_dual_quat(
+        &self,
+        influence: &BoneInfluence,
+        bone_dquats: &[([f32; 4], [f32; 4])],
+    ) -> Option<([f32; 4], [f32; 4])> {
+        let mut bones = influence
+            .bone_indices
+            .iter()
+            .zip(&influence.bone_weights)
+            .filter(|(_, &weight)| weight > 0.0001)
+            .filter_map(|(&bone_idx, &weight)| {
+                bone_dquats.get(bone_idx as usize).map(|dq| (dq, weight))
+            })
+            .peekable();
+        let reference = bones.peek()?.0 .0;
+        
+        // 调整符号后的权重
+        let mut signed: [(DualQuat, f32); 4] = [(([0.0; 4], [0.0; 4]), 0.0); 4];
+        let mut count = 0;
+        for ((real, dual), weight) in bones {
+            let dot = real[0] * reference[0] + real[1] * reference[1]
+                    + real[2] * reference[2] + real[3] * reference[3];
+            signed[count] = ((*real, *dual), if dot < 0.0 { -weight } else { weight });
+            count += 1;
+        }
+        let signed = &signed[..count];
+        
+        #[cfg(target_arch = "x86_64")]
+        let (mut real, mut dual) = if is_x86_feature_detected!("sse2") {
+            unsafe { Self::accumulate_dual_quats_sse2(signed) }
+        } else {
+            Self::accumulate_dual_quats_scalar(signed)
+        };
+        
+        #[cfg(not(target_arch = "x86_64"))]
+        let (mut real, mut dual) = Self::accumulate_dual_quats_scalar(signed);
+        
+        // 归一化实部，对偶部同比例缩放
+        let real_len = (real[0] * real[0] + real[1] * real[1]
+                      + real[2] * real[2] + real[3] * real[3]).sqrt();
+        if real_len <= 1e-6 {
+            return None;
+        }
+        let inv_len = 1.0 / real_len;
+        for k in 0..4 {
+            real[k] *= inv_len;
+            dual[k] *= inv_len;
+        }
+        Some((real, dual))
+    }
+    
+    /// SSE2加权累加双四元数，实部和对偶部各占一个128位寄存器
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "sse2")]
+    unsafe fn accumulate_dual_quats_sse2(
+        bones: &[(DualQuat, f32)],
+    ) -> ([f32; 4], [f32; 4]) {
+        use std::arch::x86_64::*;
+        
+        let mut real = _mm_setzero_ps();
+        let mut dual = _mm_setzero_ps();
+        for ((r, d), weight) in bones {
+            let w = _mm_set1_ps(*weight);
+            real = _mm_add_ps(real, _mm_mul_ps(_mm_loadu_ps(r.as_ptr()), w));
+            dual = _mm_add_ps(dual, _mm_mul_ps(_mm_loadu_ps(d.as_ptr()), w));
+        }
+        
+        let mut out_real = [0.0f32; 4];
+        let mut out_dual = [0.0f32; 4];
+        _mm_storeu_ps(out_real.as_mut_ptr(), real);
+        _mm_storeu_ps(out_dual.as_mut_ptr(), dual);
+        (out_real, out_dual)
+    }
+    
+    /// 标量回退
+    fn accumulate_dual_quats_scalar(bones: &[(DualQuat, f32)]) -> ([f32; 4], [f32; 4]) {
+        let mut real = [0.0f32; 4];
+        let mut dual = [0.0f32; 4];
+        for ((r, d), weight) in bones {
+            for k in 0..4 {
+                real[k] += r[k] * weight;
+                dual[k] += d[k] * weight;
+            }
+        }
+        (real, dual)
+    }
+    
+    /// 用单位双四元数变换点：先旋转，再加上从对偶部提取的平移
+    fn apply_dual_quat(&self, v: &[f32; 3], real: &[f32; 4], dual: &[f32; 4]) -> [f32; 3] {
+        // 平移 t = 2 * dual * conj(real)
+        let translation = [
+            2.0 * (-dual[0] * real[1] + dual[1] * real[0]
+                  - dual[2] * real[3] + dual[3] * real[2]),
+            2.0 * (-dual[0] * real[2] + dual[1] * real[3]
+                  + dual[2] * real[0] - dual[3] * real[1]),
+            2.0 * (-dual[0] * real[3] - dual[1] * real[2]
+                  + dual[2] * real[1] + dual[3] * real[0]),
+        ];
+        
+        let rotated = self.rotate_by_quaternion(v, real);
+        [
+            rotated[0] + translation[0],
+            rotated[1] + translation[1],
+            rotated[2] + translation[2],
+        ]
+    }
+    
     /// 使用四元数旋转向量
     fn rotate_by_quaternion(&self, v: &[f32; 3], q: &[f32; 4]) -> [f32; 3] {
         // q = [w, x, y, z]
@@ -297,4 +397,80 @@ mod tests {
         assert_eq!(stats.elements_processed, 1);
         assert!((output_vertices[0][0] - 1.0).abs() < 1e-5);
     }
+
+    #[test]
+    fn test_dual_quat_skinning_preserves_volume_on_twist() {
+        let skinning = BatchSkinning::new(BatchConfig::default());
+        
+        // 骨骼0为单位变换，骨骼1绕X轴（肢体方向）扭转90°
+        let angle = std::f32::consts::FRAC_PI_2;
+        let (s, c) = (angle * 0.5).sin_cos();
+        let twist = [c, s, 0.0, 0.0];
+        let identity_dq = BatchSkinning::dual_quat_from_rotation_translation([1.0, 0.0, 0.0, 0.0], [0.0; 3]);
+        let twist_dq = BatchSkinning::dual_quat_from_rotation_translation(twist, [0.0; 3]);
+        let identity = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let twist_matrix = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, -1.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        
+        // 半径为1的圆环上的顶点，沿肢体方向权重从骨骼0渐变到骨骼1
+        let mut vertices = Vec::new();
+        let mut influences = Vec::new();
+        let mut reference = Vec::new();
+        for step in 0..=4 {
+            let t = step as f32 / 4.0;
+            for k in 0..8 {
+                let phi = k as f32 * std::f32::consts::TAU / 8.0;
+                let v = [t, phi.cos(), phi.sin()];
+                vertices.push(v);
+                influences.push(BoneInfluence {
+                    bone_indices: [0, 1, 0, 0],
+                    bone_weights: [1.0 - t, t, 0.0, 0.0],
+                });
+                // 理想结果：按权重扭转 t * 90°
+                let twisted = phi + t * angle;
+                reference.push([t, twisted.cos(), twisted.sin()]);
+            }
+        }
+        
+        let dqs = skinning.skin_dual_quat(&vertices, &influences, &[identity_dq, twist_dq]);
+        
+        let normals = vec![[1.0, 0.0, 0.0]; vertices.len()];
+        let mut lbs = vec![[0.0; 3]; vertices.len()];
+        let mut lbs_normals = vec![[0.0; 3]; vertices.len()];
+        skinning.linear_blend_skinning(
+            &vertices,
+            &normals,
+            &influences,
+            &[identity, twist_matrix],
+            &mut lbs,
+            &mut lbs_normals,
+        );
+        
+        let distance = |a: &[f32; 3], b: &[f32; 3]| {
+            ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+        };
+        let mut dqs_error = 0.0;
+        let mut lbs_error = 0.0;
+        for i in 0..vertices.len() {
+            dqs_error += distance(&dqs[i], &reference[i]);
+            lbs_error += distance(&lbs[i], &reference[i]);
+            
+            // DQS 保持到扭转轴的半径
+            let radius = (dqs[i][1] * dqs[i][1] + dqs[i][2] * dqs[i][2]).sqrt();
+            assert!((radius - 1.0).abs() < 1e-4, "vertex {} radius {}", i, radius);
+        }
+        
+        // 权重各半的顶点处 LBS 塌陷到半径 cos(45°)；
+        // DQS 仅因线性混合四元数在非对称权重处有少量角度偏差
+        assert!(dqs_error * 4.0 < lbs_error, "DQS error {}, LBS error {}", dqs_error, lbs_error);
+    }
 }
@@ -25,11 +25,20 @@ pub struct CpuFeatures {
     pub sve: bool,
     pub sve2: bool,
     
+    // 缓存拓扑
+    /// 缓存行大小（字节），无法检测时为64
+    pub cache_line_bytes: usize,
+    /// NUMA节点数量，无法检测时为1
+    pub numa_nodes: usize,
+    
     // 厂商信息
     pub vendor: CpuVendor,
     pub brand: String,
 }
 
+/// 无法检测时使用的默认缓存行大小
+const DEFAULT_CACHE_LINE_BYTES: usize = 64;
+
 /// CPU厂商
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CpuVendor {
@@ -97,6 +106,8 @@ impl CpuFeatures {
             neon: false,
             sve: false,
             sve2: false,
+            cache_line_bytes: Self::detect_cache_line_bytes(),
+            numa_nodes: Self::detect_numa_nodes(),
             vendor,
             brand,
         }
@@ -130,6 +141,8 @@ impl CpuFeatures {
             neon,
             sve,
             sve2,
+            cache_line_bytes: Self::detect_cache_line_bytes(),
+            numa_nodes: Self::detect_numa_nodes(),
             vendor,
             brand,
         }
@@ -242,6 +255,94 @@ impl CpuFeatures {
         
         "Unknown".to_string()
     }
+    
+    /// 检测缓存行大小
+    ///
+    /// 依次尝试CPUID（x86）、sysfs（Linux）和sysctl（macOS），
+    /// 结果不是2的幂时视为无效，最终回退到64字节
+    fn detect_cache_line_bytes() -> usize {
+        let is_valid = |bytes: usize| bytes >= 16 && bytes.is_power_of_two();
+        
+        // CPUID leaf 1: EBX[15:8] 为CLFLUSH行大小（以8字节为单位）
+        #[cfg(target_arch = "x86_64")]
+        {
+            // SAFETY: x86_64 上CPUID总是可用，leaf 1 为所有CPU支持的基础leaf
+            // （较新的工具链中 __cpuid 已是安全函数）
+            #[allow(unused_unsafe)]
+            let result = unsafe { std::arch::x86_64::__cpuid(1) };
+            let bytes = ((result.ebx >> 8) & 0xff) as usize * 8;
+            if is_valid(bytes) {
+                return bytes;
+            }
+        }
+        
+        #[cfg(target_os = "linux")]
+        {
+            if let Ok(content) = std::fs::read_to_string(
+                "/sys/devices/system/cpu/cpu0/cache/index0/coherency_line_size",
+            ) {
+                if let Ok(bytes) = content.trim().parse::<usize>() {
+                    if is_valid(bytes) {
+                        return bytes;
+                    }
+                }
+            }
+        }
+        
+        #[cfg(target_os = "macos")]
+        {
+            if let Some(bytes) = Self::sysctl_usize("hw.cachelinesize") {
+                if is_valid(bytes) {
+                    return bytes;
+                }
+            }
+        }
+        
+        DEFAULT_CACHE_LINE_BYTES
+    }
+    
+    /// 检测NUMA节点数量
+    ///
+    /// Linux 上统计 `/sys/devices/system/node/node*`，macOS 上使用物理封装数量，
+    /// 无法检测时回退到1
+    fn detect_numa_nodes() -> usize {
+        #[cfg(target_os = "linux")]
+        {
+            if let Ok(entries) = std::fs::read_dir("/sys/devices/system/node") {
+                let nodes = entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| {
+                        entry.file_name().to_str().is_some_and(|name| {
+                            name.strip_prefix("node").is_some_and(|id| {
+                                !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+                            })
+                        })
+                    })
+                    .count();
+                if nodes > 0 {
+                    return nodes;
+                }
+            }
+        }
+        
+        #[cfg(target_os = "macos")]
+        {
+            if let Some(packages) = Self::sysctl_usize("hw.packages") {
+                if packages > 0 {
+                    return packages;
+                }
+            }
+        }
+        
+        1
+    }
+    
+    #[cfg(target_os = "macos")]
+    fn sysctl_usize(name: &str) -> Option<usize> {
+        use std::process::Command;
+        let output = Command::new("sysctl").arg("-n").arg(name).output().ok()?;
+        String::from_utf8(output.stdout).ok()?.trim().parse().ok()
+    }
 }
 
 impl Default for CpuFeatures {
@@ -261,6 +362,8 @@ impl Default for CpuFeatures {
             neon: false,
             sve: false,
             sve2: false,
+            cache_line_bytes: DEFAULT_CACHE_LINE_BYTES,
+            numa_nodes: 1,
             vendor: CpuVendor::Other,
             brand: "Unknown".to_string(),
         }
@@ -281,6 +384,8 @@ pub fn print_cpu_info() {
     println!("=== CPU Information ===");
     println!("Vendor: {:?}", features.vendor);
     println!("Brand: {}", features.brand);
+    println!("Cache line: {} bytes", features.cache_line_bytes);
+    println!("NUMA nodes: {}", features.numa_nodes);
     println!();
     
     #[cfg(target_arch = "x86_64")]
@@ -325,6 +430,14 @@ mod tests {
         assert!(features.neon, "NEON should be available on all aarch64 CPUs");
     }
 
+    #[test]
+    fn test_cache_topology() {
+        let features = detect_cpu_features();
+        assert!(features.cache_line_bytes.is_power_of_two());
+        assert!(features.cache_line_bytes >= 32);
+        assert!(features.numa_nodes >= 1);
+    }
+
     #[test]
     fn test_print_info() {
         print_cpu_info();
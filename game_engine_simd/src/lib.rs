@@ -92,6 +92,8 @@
 //! println!("吞吐量: {:.2} 顶点/秒", stats.throughput());
 //! ```
 
+use std::cell::Cell;
+
 pub mod cpu_detect;
 pub mod math;
 pub mod batch;
@@ -99,7 +101,7 @@ pub mod audio;
 
 // 重新导出主要类型
 pub use cpu_detect::{CpuFeatures, CpuVendor, detect_cpu_features, print_cpu_info};
-pub use math::{Vec3Simd, Vec4Simd, Mat4Simd, QuatSimd, MatrixBatchOps, VectorBatchOps, GeometryOps, TransformOps, PerformanceTest, VectorBatchResult};
+pub use math::{Vec3Simd, Vec4Simd, Mat4Simd, QuatSimd, MatrixBatchOps, VectorBatchOps, GeometryOps, TransformOps, PerformanceTest, BenchOp, VectorBatchResult};
 pub use batch::{BatchConfig, BatchStats};
 pub use audio::{AudioSpatialOps, AudioDSPOps, AudioSpatialResult, AudioDSPResult, DistanceModel};
 
@@ -137,10 +139,16 @@ pub enum SimdBackend {
     Sve,
 }
 
+thread_local! {
+    /// 当前线程的后端覆盖（用于基准对比和测试）
+    static BACKEND_OVERRIDE: Cell<Option<SimdBackend>> = const { Cell::new(None) };
+}
+
 impl SimdBackend {
     /// 获取当前平台最优的SIMD后端
     ///
     /// 根据运行时检测的CPU特性，返回当前平台支持的最高性能SIMD后端。
+    /// 如果当前线程通过 [`SimdBackend::with_override`] 设置了覆盖，则返回覆盖的后端。
     ///
     /// # 返回
     ///
@@ -155,6 +163,14 @@ impl SimdBackend {
     /// println!("使用后端: {:?}", backend);
     /// ```
     pub fn best_available() -> Self {
+        if let Some(backend) = BACKEND_OVERRIDE.with(Cell::get) {
+            return backend;
+        }
+        Self::detected()
+    }
+    
+    /// 获取CPU支持的最优后端（忽略覆盖）
+    pub fn detected() -> Self {
         let features = detect_cpu_features();
         
         #[cfg(target_arch = "x86_64")]
@@ -189,6 +205,67 @@ impl SimdBackend {
         Self::Scalar
     }
     
+    /// 当前CPU是否支持该后端
+    pub fn is_supported(&self) -> bool {
+        let features = detect_cpu_features();
+        match self {
+            Self::Scalar => true,
+            Self::Sse2 => features.sse2,
+            Self::Sse41 => features.sse41,
+            Self::Avx => features.avx,
+            Self::Avx2 => features.avx2,
+            Self::Avx512 => features.avx512f,
+            Self::Neon => features.neon,
+            Self::Sve => features.sve,
+        }
+    }
+    
+    /// 当前CPU支持的所有后端，按性能从低到高排序（第一个总是`Scalar`）
+    pub fn supported() -> Vec<Self> {
+        [
+            Self::Scalar,
+            Self::Sse2,
+            Self::Sse41,
+            Self::Avx,
+            Self::Avx2,
+            Self::Avx512,
+            Self::Neon,
+            Self::Sve,
+        ]
+        .into_iter()
+        .filter(Self::is_supported)
+        .collect()
+    }
+    
+    /// 在当前线程强制使用指定后端执行 `f`
+    ///
+    /// 覆盖期间 [`SimdBackend::best_available`] 返回 `self`，分发层据此选择实现；
+    /// `f` 返回后恢复之前的设置。CPU不支持的后端会被忽略。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use game_engine_simd::SimdBackend;
+    ///
+    /// let backend = SimdBackend::Scalar.with_override(SimdBackend::best_available);
+    /// assert_eq!(backend, SimdBackend::Scalar);
+    /// ```
+    pub fn with_override<R>(self, f: impl FnOnce() -> R) -> R {
+        if !self.is_supported() {
+            return f();
+        }
+        
+        struct Restore(Option<SimdBackend>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                BACKEND_OVERRIDE.with(|o| o.set(self.0));
+            }
+        }
+        
+        let _restore = Restore(BACKEND_OVERRIDE.with(|o| o.replace(Some(self))));
+        f()
+    }
+    
     /// 获取SIMD向量宽度
     ///
     /// # 返回
@@ -80,10 +80,11 @@ impl VectorOps for Vec4Simd {
     
     fn add(&self, other: &Self) -> Self {
         let mut result = Self::zero();
+        let backend = SimdBackend::best_available();
         
         #[cfg(target_arch = "x86_64")]
         {
-            if is_x86_feature_detected!("sse2") {
+            if backend != SimdBackend::Scalar && is_x86_feature_detected!("sse2") {
                 unsafe {
                     use std::arch::x86_64::*;
                     let va = _mm_loadu_ps(self.data.as_ptr());
@@ -97,9 +98,11 @@ impl VectorOps for Vec4Simd {
         
         #[cfg(target_arch = "aarch64")]
         {
-            unsafe {
-                add_vec4_neon(&self.data, &other.data, &mut result.data);
-                return result;
+            if backend != SimdBackend::Scalar {
+                unsafe {
+                    add_vec4_neon(&self.data, &other.data, &mut result.data);
+                    return result;
+                }
             }
         }
         
@@ -247,16 +250,18 @@ impl Mat4Simd {
     
     pub fn mul(&self, other: &Self) -> Self {
         let mut result = Self::zero();
+        let backend = SimdBackend::best_available();
         
         #[cfg(target_arch = "x86_64")]
         {
-            if is_x86_feature_detected!("avx") {
+            let avx_backend = matches!(backend, SimdBackend::Avx | SimdBackend::Avx2 | SimdBackend::Avx512);
+            if avx_backend && is_x86_feature_detected!("avx") {
                 unsafe {
                     mat4_mul_avx(&self.data, &other.data, &mut result.data);
                     return result;
                 }
             }
-            if is_x86_feature_detected!("sse2") {
+            if backend != SimdBackend::Scalar && is_x86_feature_detected!("sse2") {
                 unsafe {
                     mat4_mul_sse2(&self.data, &other.data, &mut result.data);
                     return result;
@@ -266,9 +271,11 @@ impl Mat4Simd {
         
         #[cfg(target_arch = "aarch64")]
         {
-            unsafe {
-                mat4_mul_neon(&self.data, &other.data, &mut result.data);
-                return result;
+            if backend != SimdBackend::Scalar {
+                unsafe {
+                    mat4_mul_neon(&self.data, &other.data, &mut result.data);
+                    return result;
+                }
             }
         }
         
@@ -306,8 +313,54 @@ impl Mat4Simd {
         }
         result
     }
+    
+    /// 行列式
+    pub fn determinant(&self) -> f32 {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("sse2") {
+                unsafe {
+                    return mat4_determinant_sse2(&self.data);
+                }
+            }
+        }
+        
+        mat4_determinant_scalar(&self.data)
+    }
+    
+    /// 逆矩阵
+    ///
+    /// 矩阵接近奇异时返回 `None`。判定以各行范数之积（Hadamard 上界）归一化，
+    /// 因此整体缩放、大平移或各向异性缩放的可逆矩阵不会被误判。
+    pub fn inverse(&self) -> Option<Self> {
+        let mut result = Self::zero();
+        
+        #[cfg(target_arch = "x86_64")]
+        let det = if is_x86_feature_detected!("sse2") {
+            unsafe { mat4_inverse_sse2(&self.data, &mut result.data) }
+        } else {
+            mat4_inverse_scalar(&self.data, &mut result.data)
+        };
+        
+        #[cfg(not(target_arch = "x86_64"))]
+        let det = mat4_inverse_scalar(&self.data, &mut result.data);
+        
+        // |det| 不超过各行范数之积，比值越接近 0 矩阵越接近奇异
+        let bound: f32 = self
+            .data
+            .iter()
+            .map(|row| row.iter().map(|v| v * v).sum::<f32>().sqrt())
+            .product();
+        if !det.is_finite() || det == 0.0 || det.abs() <= MAT4_SINGULAR_EPSILON * bound {
+            return None;
+        }
+        Some(result)
+    }
 }
 
+/// 判定矩阵奇异的阈值（相对于 Hadamard 上界）
+const MAT4_SINGULAR_EPSILON: f32 = 1e-6;
+
 /// 四元数（自动SIMD优化）
 #[derive(Debug, Clone, Copy)]
 pub struct QuatSimd {
@@ -345,8 +398,59 @@ impl QuatSimd {
                        - self.data[2]*other.data[1] + self.data[3]*other.data[0];
         result
     }
+    
+    /// 四元数点积
+    pub fn dot(&self, other: &Self) -> f32 {
+        Vec4Simd { data: self.data }.dot(&Vec4Simd { data: other.data })
+    }
+    
+    /// 归一化
+    pub fn normalize(&self) -> Self {
+        Self { data: Vec4Simd { data: self.data }.normalize().data }
+    }
+    
+    /// 归一化线性插值
+    ///
+    /// 点积为负时翻转 `other` 的符号以沿最短路径插值，结果已归一化
+    pub fn nlerp(&self, other: &Self, t: f32) -> Self {
+        let a = Vec4Simd { data: self.data };
+        let mut b = Vec4Simd { data: other.data };
+        if a.dot(&b) < 0.0 {
+            b = b.mul(-1.0);
+        }
+        let blended = a.add(&b.sub(&a).mul(t));
+        Self { data: blended.normalize().data }
+    }
+    
+    /// 球面线性插值
+    ///
+    /// 点积为负时翻转 `other` 的符号以沿最短弧插值；
+    /// 两个四元数接近平行时 `sin(theta)` 趋近0，回退到 [`QuatSimd::nlerp`]
+    pub fn slerp(&self, other: &Self, t: f32) -> Self {
+        let a = Vec4Simd { data: self.data };
+        let mut b = Vec4Simd { data: other.data };
+        let mut cos_theta = a.dot(&b);
+        if cos_theta < 0.0 {
+            b = b.mul(-1.0);
+            cos_theta = -cos_theta;
+        }
+        
+        if cos_theta > SLERP_NLERP_THRESHOLD {
+            return self.nlerp(&Self { data: b.data }, t);
+        }
+        
+        let theta = cos_theta.acos();
+        let inv_sin_theta = 1.0 / theta.sin();
+        let weight_a = ((1.0 - t) * theta).sin() * inv_sin_theta;
+        let weight_b = (t * theta).sin() * inv_sin_theta;
+        let blended = a.mul(weight_a).add(&b.mul(weight_b));
+        Self { data: blended.normalize().data }
+    }
 }
 
+/// 点积超过此值时 slerp 回退到 nlerp，避免除以接近0的 `sin(theta)`
+const SLERP_NLERP_THRESHOLD: f32 = 0.9995;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -373,4 +477,176 @@ mod tests {
         assert!((result.data[0][0] - 1.0).abs() < 1e-5);
         assert!((result.data[1][1] - 1.0).abs() < 1e-5);
     }
+
+    /// 简单的线性同余生成器，返回 [-1, 1) 范围的随机数
+    fn next_random(state: &mut u32) -> f32 {
+        *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+        (*state >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+    }
+
+    fn random_invertible_matrices() -> Vec<glam::Mat4> {
+        let mut state = 0x1234_5678;
+        let mut matrices: Vec<glam::Mat4> = (0..32)
+            .map(|_| {
+                let mut cols = [0.0f32; 16];
+                for (i, v) in cols.iter_mut().enumerate() {
+                    // 对角占优保证可逆
+                    *v = next_random(&mut state) + if i % 5 == 0 { 4.0 } else { 0.0 };
+                }
+                glam::Mat4::from_cols_array(&cols)
+            })
+            .collect();
+        matrices.push(glam::Mat4::from_scale_rotation_translation(
+            glam::Vec3::new(0.5, 2.0, 1.5),
+            glam::Quat::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0),
+            glam::Vec3::new(10.0, -4.0, 3.0),
+        ));
+        matrices.push(glam::Mat4::perspective_rh(1.0, 16.0 / 9.0, 0.1, 100.0));
+        matrices
+    }
+
+    #[test]
+    fn test_mat4_determinant_matches_glam() {
+        for m in random_invertible_matrices() {
+            let simd = Mat4Simd { data: m.to_cols_array_2d() };
+            let expected = m.determinant();
+            assert!(
+                (simd.determinant() - expected).abs() <= 1e-4 * expected.abs().max(1.0),
+                "determinant {} != {}",
+                simd.determinant(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_mat4_inverse_matches_glam() {
+        for m in random_invertible_matrices() {
+            let simd = Mat4Simd { data: m.to_cols_array_2d() };
+            let inverse = simd.inverse().expect("matrix should be invertible");
+            let expected = m.inverse().to_cols_array_2d();
+            for (i, (row, expected_row)) in inverse.data.iter().zip(&expected).enumerate() {
+                for (j, (value, expected)) in row.iter().zip(expected_row).enumerate() {
+                    assert!(
+                        (value - expected).abs() < 1e-4,
+                        "inverse[{}][{}]: {} != {}",
+                        i,
+                        j,
+                        value,
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mat4_inverse_singular_returns_none() {
+        // 第3行是前两行之和
+        let singular = Mat4Simd {
+            data: [
+                [1.0, 2.0, 3.0, 4.0],
+                [5.0, 6.0, 7.0, 8.0],
+                [6.0, 8.0, 10.0, 12.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        assert!(singular.determinant().abs() < 1e-4);
+        assert!(singular.inverse().is_none());
+        assert!(Mat4Simd::zero().inverse().is_none());
+    }
+
+    #[test]
+    fn test_mat4_inverse_large_translation_and_anisotropic_scale() {
+        let matrices = [
+            glam::Mat4::from_translation(glam::Vec3::new(100.0, 0.0, 0.0)),
+            glam::Mat4::from_translation(glam::Vec3::new(1.0e4, -2.5e3, 7.0e3)),
+            glam::Mat4::from_diagonal(glam::Vec4::new(100.0, 1.0, 1.0, 1.0)),
+            glam::Mat4::from_scale(glam::Vec3::new(1000.0, 0.01, 1.0)),
+            glam::Mat4::from_scale_rotation_translation(
+                glam::Vec3::new(50.0, 0.1, 2.0),
+                glam::Quat::from_rotation_y(0.7),
+                glam::Vec3::new(-300.0, 20.0, 500.0),
+            ),
+        ];
+        for m in matrices {
+            let simd = Mat4Simd { data: m.to_cols_array_2d() };
+            let inverse = simd.inverse().expect("matrix should be invertible");
+            let product = glam::Mat4::from_cols_array_2d(&inverse.data) * m;
+            assert!(
+                product.abs_diff_eq(glam::Mat4::IDENTITY, 1e-3),
+                "{:?} * m != I",
+                product
+            );
+        }
+    }
+
+    fn to_glam(q: &QuatSimd) -> glam::Quat {
+        glam::Quat::from_xyzw(q.data[1], q.data[2], q.data[3], q.data[0])
+    }
+
+    fn from_glam(q: glam::Quat) -> QuatSimd {
+        QuatSimd::new(q.w, q.x, q.y, q.z)
+    }
+
+    fn assert_quat_eq(a: &QuatSimd, b: &QuatSimd) {
+        for (x, y) in a.data.iter().zip(&b.data) {
+            assert!((x - y).abs() < 1e-5, "{:?} != {:?}", a.data, b.data);
+        }
+    }
+
+    #[test]
+    fn test_quat_slerp_endpoints() {
+        let a = from_glam(glam::Quat::from_rotation_y(0.3));
+        let b = from_glam(glam::Quat::from_euler(glam::EulerRot::XYZ, 1.0, -0.5, 2.0));
+        assert_quat_eq(&a.slerp(&b, 0.0), &a);
+        assert_quat_eq(&a.slerp(&b, 1.0), &b);
+    }
+
+    #[test]
+    fn test_quat_slerp_takes_shortest_arc() {
+        let a = from_glam(glam::Quat::from_rotation_z(0.2));
+        let b = from_glam(glam::Quat::from_rotation_z(1.0));
+        // -b 与 b 表示同一旋转，但点积为负
+        let neg_b = QuatSimd::new(-b.data[0], -b.data[1], -b.data[2], -b.data[3]);
+        assert!(a.dot(&neg_b) < 0.0);
+
+        let mid = a.slerp(&neg_b, 0.5);
+        let expected = from_glam(glam::Quat::from_rotation_z(0.6));
+        // 结果应位于较短弧上（与 a 同半球），并等于 0.6 弧度的旋转
+        assert!(mid.dot(&a) > 0.0);
+        assert_quat_eq(&mid, &expected);
+    }
+
+    #[test]
+    fn test_quat_slerp_matches_glam() {
+        let a = glam::Quat::from_euler(glam::EulerRot::XYZ, 0.1, 0.7, -0.3);
+        let b = glam::Quat::from_euler(glam::EulerRot::XYZ, -1.2, 0.4, 2.5);
+        let near = a * glam::Quat::from_rotation_x(1e-3);
+        for t in [0.1, 0.25, 0.5, 0.75, 0.9] {
+            for other in [b, -b, near] {
+                let result = from_glam(a).slerp(&from_glam(other), t);
+                let expected = a.slerp(other, t);
+                assert!((result.dot(&result) - 1.0).abs() < 1e-5);
+                assert!(
+                    to_glam(&result).abs_diff_eq(expected, 1e-4),
+                    "t = {}: {:?} != {:?}",
+                    t,
+                    to_glam(&result),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_quat_nlerp_is_normalized() {
+        let a = from_glam(glam::Quat::from_rotation_x(0.5));
+        let b = from_glam(glam::Quat::from_rotation_y(-2.0));
+        for t in [0.0, 0.3, 0.5, 1.0] {
+            let q = a.nlerp(&b, t);
+            assert!((q.dot(&q) - 1.0).abs() < 1e-5);
+        }
+        assert_quat_eq(&a.nlerp(&b, 0.0), &a);
+    }
 }
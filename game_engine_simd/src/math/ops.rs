@@ -3,6 +3,11 @@
 //! 为关键路径的数学运算提供SIMD优化，使用AVX2/AVX-512 (x86) 或 NEON (ARM) 指令集
 
 use glam::{Vec3, Mat4};
+use std::hint::black_box;
+use std::time::Instant;
+
+use super::{Mat4Simd, Vec4Simd, VectorOps};
+use crate::SimdBackend;
 
 /// SIMD向量批处理结果
 #[derive(Debug, Clone)]
@@ -177,8 +182,122 @@ impl VectorBatchOps {
     fn batch_length_fallback(vectors: &[Vec3]) -> Vec<f32> {
         vectors.iter().map(|v| v.length()).collect()
     }
+    
+    /// 批量归一化向量（原地）
+    ///
+    /// 长度小于 [`NORMALIZE_EPSILON`] 的向量替换为 `fallback`，避免产生NaN。
+    /// 常用于可能退化为零长度的法线。
+    ///
+    /// # 参数
+    ///
+    /// * `vecs` - 要归一化的向量数组（原地修改）
+    /// * `fallback` - 零长度向量的替代值
+    ///
+    /// # 返回
+    ///
+    /// 批量处理结果，包含归一化后的向量
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use game_engine_simd::math::ops::VectorBatchOps;
+    ///
+    /// let mut normals = [[3.0, 0.0, 4.0], [0.0, 0.0, 0.0]];
+    /// let result = VectorBatchOps::batch_normalize(&mut normals, [0.0, 1.0, 0.0]);
+    /// assert_eq!(result.count, 2);
+    /// assert_eq!(normals[1], [0.0, 1.0, 0.0]);
+    /// ```
+    pub fn batch_normalize(vecs: &mut [[f32; 3]], fallback: [f32; 3]) -> VectorBatchResult {
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            if is_x86_feature_detected!("sse2") {
+                Self::batch_normalize_sse2(vecs, fallback);
+                return Self::normalize_result(vecs);
+            }
+        }
+        
+        Self::batch_normalize_fallback(vecs, fallback);
+        Self::normalize_result(vecs)
+    }
+    
+    /// SSE2优化的批量归一化
+    ///
+    /// 每次按SoA方式处理4个向量，使用 `rsqrt` 近似加一次牛顿迭代求倒数平方根
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "sse2")]
+    unsafe fn batch_normalize_sse2(vecs: &mut [[f32; 3]], fallback: [f32; 3]) {
+        use std::arch::x86_64::*;
+        
+        let min_length_sq = _mm_set1_ps(NORMALIZE_EPSILON * NORMALIZE_EPSILON);
+        let half = _mm_set1_ps(0.5);
+        let three_halves = _mm_set1_ps(1.5);
+        let fallback_x = _mm_set1_ps(fallback[0]);
+        let fallback_y = _mm_set1_ps(fallback[1]);
+        let fallback_z = _mm_set1_ps(fallback[2]);
+        
+        let mut chunks = vecs.chunks_exact_mut(4);
+        for chunk in &mut chunks {
+            let x = _mm_setr_ps(chunk[0][0], chunk[1][0], chunk[2][0], chunk[3][0]);
+            let y = _mm_setr_ps(chunk[0][1], chunk[1][1], chunk[2][1], chunk[3][1]);
+            let z = _mm_setr_ps(chunk[0][2], chunk[1][2], chunk[2][2], chunk[3][2]);
+            
+            let length_sq = _mm_add_ps(_mm_add_ps(_mm_mul_ps(x, x), _mm_mul_ps(y, y)), _mm_mul_ps(z, z));
+            
+            // r' = r * (1.5 - 0.5 * len² * r²)
+            let estimate = _mm_rsqrt_ps(length_sq);
+            let correction = _mm_sub_ps(
+                three_halves,
+                _mm_mul_ps(_mm_mul_ps(half, length_sq), _mm_mul_ps(estimate, estimate)),
+            );
+            let inv_length = _mm_mul_ps(estimate, correction);
+            
+            // 长度过小的通道选择回退值
+            let valid = _mm_cmpgt_ps(length_sq, min_length_sq);
+            let select = |normalized: __m128, fallback: __m128| {
+                _mm_or_ps(_mm_and_ps(valid, normalized), _mm_andnot_ps(valid, fallback))
+            };
+            let out_x = select(_mm_mul_ps(x, inv_length), fallback_x);
+            let out_y = select(_mm_mul_ps(y, inv_length), fallback_y);
+            let out_z = select(_mm_mul_ps(z, inv_length), fallback_z);
+            
+            let mut xs = [0.0f32; 4];
+            let mut ys = [0.0f32; 4];
+            let mut zs = [0.0f32; 4];
+            _mm_storeu_ps(xs.as_mut_ptr(), out_x);
+            _mm_storeu_ps(ys.as_mut_ptr(), out_y);
+            _mm_storeu_ps(zs.as_mut_ptr(), out_z);
+            for (i, v) in chunk.iter_mut().enumerate() {
+                *v = [xs[i], ys[i], zs[i]];
+            }
+        }
+        
+        Self::batch_normalize_fallback(chunks.into_remainder(), fallback);
+    }
+    
+    /// 回退实现
+    fn batch_normalize_fallback(vecs: &mut [[f32; 3]], fallback: [f32; 3]) {
+        for v in vecs.iter_mut() {
+            let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+            *v = if length > NORMALIZE_EPSILON {
+                let inv_length = 1.0 / length;
+                [v[0] * inv_length, v[1] * inv_length, v[2] * inv_length]
+            } else {
+                fallback
+            };
+        }
+    }
+    
+    fn normalize_result(vecs: &[[f32; 3]]) -> VectorBatchResult {
+        VectorBatchResult {
+            count: vecs.len(),
+            results: vecs.iter().map(|v| Vec3::from_array(*v)).collect(),
+        }
+    }
 }
 
+/// 批量归一化时视为零长度的阈值
+pub const NORMALIZE_EPSILON: f32 = 1e-6;
+
 /// SIMD几何运算优化
 pub struct GeometryOps;
 
@@ -351,10 +470,89 @@ impl TransformOps {
     }
 }
 
+/// 后端对比基准的运算类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchOp {
+    /// 4维向量点积
+    Vec4Dot,
+    /// 4维向量加法
+    Vec4Add,
+    /// 4x4矩阵乘法
+    Mat4Mul,
+}
+
 /// SIMD性能测试工具
 pub struct PerformanceTest;
 
 impl PerformanceTest {
+    /// 在同一台机器上对比各SIMD后端的吞吐量
+    ///
+    /// 对CPU支持的每个后端，通过 [`SimdBackend::with_override`] 强制分发层使用该后端，
+    /// 执行相同的 `n` 次运算。每个后端取多轮中最快的一轮以减少噪声。
+    ///
+    /// # 参数
+    ///
+    /// * `op` - 要测试的运算
+    /// * `n` - 每轮执行的运算次数
+    ///
+    /// # 返回
+    ///
+    /// `(后端, 每秒运算次数)` 列表，按后端性能等级从低到高排序，第一项总是`Scalar`
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use game_engine_simd::{BenchOp, PerformanceTest, SimdBackend};
+    ///
+    /// let results = PerformanceTest::compare_backends(BenchOp::Vec4Dot, 1000);
+    /// assert_eq!(results[0].0, SimdBackend::Scalar);
+    /// ```
+    pub fn compare_backends(op: BenchOp, n: usize) -> Vec<(SimdBackend, f64)> {
+        const ROUNDS: usize = 3;
+        
+        SimdBackend::supported()
+            .into_iter()
+            .map(|backend| {
+                let best = backend.with_override(|| {
+                    (0..ROUNDS)
+                        .map(|_| Self::run_bench_op(op, n))
+                        .fold(f64::INFINITY, f64::min)
+                });
+                let throughput = if best > 0.0 { n as f64 / best } else { f64::INFINITY };
+                (backend, throughput)
+            })
+            .collect()
+    }
+    
+    /// 执行一轮基准，返回耗时（秒）
+    fn run_bench_op(op: BenchOp, n: usize) -> f64 {
+        let a = Vec4Simd::new(1.0, 2.0, 3.0, 4.0);
+        let b = Vec4Simd::new(0.5, -1.5, 2.5, -3.5);
+        let m = Mat4Simd {
+            data: Mat4::from_rotation_y(0.5).to_cols_array_2d(),
+        };
+        
+        let start = Instant::now();
+        match op {
+            BenchOp::Vec4Dot => {
+                for _ in 0..n {
+                    black_box(black_box(&a).dot(black_box(&b)));
+                }
+            }
+            BenchOp::Vec4Add => {
+                for _ in 0..n {
+                    black_box(black_box(&a).add(black_box(&b)));
+                }
+            }
+            BenchOp::Mat4Mul => {
+                for _ in 0..n {
+                    black_box(black_box(&m).mul(black_box(&m)));
+                }
+            }
+        }
+        start.elapsed().as_secs_f64()
+    }
+    
     /// 获取当前CPU的SIMD支持情况
     ///
     /// # 返回
@@ -446,6 +644,51 @@ mod tests {
         assert!((results[1] - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_batch_normalize_zero_guard() {
+        let fallback = [0.0, 0.0, 1.0];
+        let original = [
+            [3.0, 0.0, 4.0],
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [1e-9, 0.0, -1e-9],
+            [-2.0, 5.0, 0.5],
+            [0.0, 0.0, 0.0],
+            [0.0, -7.0, 0.0],
+        ];
+        let mut vecs = original;
+        
+        let result = VectorBatchOps::batch_normalize(&mut vecs, fallback);
+        assert_eq!(result.count, original.len());
+        
+        for (i, (v, o)) in vecs.iter().zip(&original).enumerate() {
+            assert!(v.iter().all(|c| c.is_finite()), "vector {} has NaN", i);
+            let o = Vec3::from_array(*o);
+            if o.length() <= NORMALIZE_EPSILON {
+                assert_eq!(*v, fallback, "vector {} should use fallback", i);
+            } else {
+                let v = Vec3::from_array(*v);
+                assert!((v.length() - 1.0).abs() < 1e-5, "vector {} not unit length", i);
+                assert!(v.abs_diff_eq(o.normalize(), 1e-5));
+            }
+            assert_eq!(result.results[i], Vec3::from_array(vecs[i]));
+        }
+    }
+
+    #[test]
+    fn test_compare_backends() {
+        let results = PerformanceTest::compare_backends(BenchOp::Mat4Mul, 20_000);
+        let throughput = |backend: SimdBackend| {
+            results.iter().find(|(b, _)| *b == backend).map(|(_, t)| *t)
+        };
+        let scalar = throughput(SimdBackend::Scalar).expect("Scalar should always be reported");
+        let best = throughput(SimdBackend::detected()).expect("best backend should be reported");
+        assert!(results.iter().all(|(_, t)| *t > 0.0));
+        // 单次运算很小，计时噪声和标量代码的自动向量化会让两者接近，
+        // 这里只要求最优后端不明显慢于标量
+        assert!(best >= scalar * 0.5, "best backend ({}) slower than scalar ({})", best, scalar);
+    }
+
     #[test]
     fn test_simd_capabilities() {
         let caps = PerformanceTest::get_simd_capabilities();
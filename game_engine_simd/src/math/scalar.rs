@@ -43,6 +43,58 @@ pub fn mat4_mul_scalar(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4], out: &mut [[f32; 4]
     }
 }
 
+/// 4x4矩阵的2x2子式（标量）
+///
+/// 返回 (s, c)，`s` 取自第0、1行，`c` 取自第2、3行，
+/// 按列对 (0,1), (0,2), (0,3), (1,2), (1,3), (2,3) 排列
+fn mat4_minors_scalar(m: &[[f32; 4]; 4]) -> ([f32; 6], [f32; 6]) {
+    const PAIRS: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+    let mut s = [0.0; 6];
+    let mut c = [0.0; 6];
+    for (k, &(i, j)) in PAIRS.iter().enumerate() {
+        s[k] = m[0][i] * m[1][j] - m[1][i] * m[0][j];
+        c[k] = m[2][i] * m[3][j] - m[3][i] * m[2][j];
+    }
+    (s, c)
+}
+
+/// 4x4矩阵行列式（标量，余子式展开）
+pub fn mat4_determinant_scalar(m: &[[f32; 4]; 4]) -> f32 {
+    let (s, c) = mat4_minors_scalar(m);
+    s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+}
+
+/// 4x4矩阵求逆（标量，伴随矩阵除以行列式）
+///
+/// 返回行列式；行列式为0时 `out` 的内容无效，由调用者判断
+pub fn mat4_inverse_scalar(m: &[[f32; 4]; 4], out: &mut [[f32; 4]; 4]) -> f32 {
+    let (s, c) = mat4_minors_scalar(m);
+    let det = s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
+    let inv_det = 1.0 / det;
+    
+    out[0][0] = ( m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3]) * inv_det;
+    out[0][1] = (-m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3]) * inv_det;
+    out[0][2] = ( m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3]) * inv_det;
+    out[0][3] = (-m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3]) * inv_det;
+    
+    out[1][0] = (-m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1]) * inv_det;
+    out[1][1] = ( m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1]) * inv_det;
+    out[1][2] = (-m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1]) * inv_det;
+    out[1][3] = ( m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1]) * inv_det;
+    
+    out[2][0] = ( m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0]) * inv_det;
+    out[2][1] = (-m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0]) * inv_det;
+    out[2][2] = ( m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0]) * inv_det;
+    out[2][3] = (-m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0]) * inv_det;
+    
+    out[3][0] = (-m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0]) * inv_det;
+    out[3][1] = ( m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0]) * inv_det;
+    out[3][2] = (-m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0]) * inv_det;
+    out[3][3] = ( m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0]) * inv_det;
+    
+    det
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,4 +111,27 @@ mod tests {
         add_vec4_scalar(&a, &b, &mut sum);
         assert_eq!(sum, [6.0, 8.0, 10.0, 12.0]);
     }
+
+    #[test]
+    fn test_mat4_inverse_scalar() {
+        let m = [
+            [2.0, 0.0, 0.0, 1.0],
+            [0.0, 4.0, 0.0, 2.0],
+            [0.0, 1.0, 1.0, 3.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        let mut inv = [[0.0; 4]; 4];
+        let det = mat4_inverse_scalar(&m, &mut inv);
+        assert_eq!(det, 8.0);
+        assert_eq!(mat4_determinant_scalar(&m), det);
+        
+        let mut product = [[0.0; 4]; 4];
+        mat4_mul_scalar(&m, &inv, &mut product);
+        for (i, row) in product.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-6);
+            }
+        }
+    }
 }
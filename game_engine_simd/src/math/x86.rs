@@ -610,6 +610,149 @@ pub unsafe fn transform_vectors_sse2(
     }
 }
 
+/// 两行之间按列对 (i, j) 计算的2x2子式向量: `a[i] * b[j] - b[i] * a[j]`
+///
+/// `SI`/`SJ` 为 `_mm_shuffle_ps` 立即数，分别选取每个通道的 i 列和 j 列
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn minors_sse2<const SI: i32, const SJ: i32>(a: __m128, b: __m128) -> __m128 {
+    let ai = _mm_shuffle_ps::<SI>(a, a);
+    let bj = _mm_shuffle_ps::<SJ>(b, b);
+    let bi = _mm_shuffle_ps::<SI>(b, b);
+    let aj = _mm_shuffle_ps::<SJ>(a, a);
+    _mm_sub_ps(_mm_mul_ps(ai, bj), _mm_mul_ps(bi, aj))
+}
+
+/// 伴随矩阵的一列
+///
+/// 以 `row` 的元素与另外两行的2x2子式做余子式展开，`sign` 为各通道的符号
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn adjugate_column_sse2(row: __m128, lo: __m128, hi: __m128, sign: __m128) -> __m128 {
+    // 子式按通道排列: m1 = [(2,3), (2,3), (1,3), (1,2)]
+    //                m2 = [(1,3), (0,3), (0,3), (0,2)]
+    //                m3 = [(1,2), (0,2), (0,1), (0,1)]
+    let m1 = minors_sse2::<0b01_01_10_10, 0b10_11_11_11>(lo, hi);
+    let m2 = minors_sse2::<0b00_00_00_01, 0b10_11_11_11>(lo, hi);
+    let m3 = minors_sse2::<0b00_00_00_01, 0b01_01_10_10>(lo, hi);
+    
+    let r1 = _mm_shuffle_ps::<0b00_00_00_01>(row, row);
+    let r2 = _mm_shuffle_ps::<0b01_01_10_10>(row, row);
+    let r3 = _mm_shuffle_ps::<0b10_11_11_11>(row, row);
+    
+    let sum = _mm_add_ps(_mm_sub_ps(_mm_mul_ps(r1, m1), _mm_mul_ps(r2, m2)), _mm_mul_ps(r3, m3));
+    _mm_mul_ps(sum, sign)
+}
+
+/// 4维向量水平求和
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn horizontal_sum_sse2(v: __m128) -> f32 {
+    let shuf = _mm_shuffle_ps::<0b10_11_00_01>(v, v);
+    let sums = _mm_add_ps(v, shuf);
+    let high = _mm_movehl_ps(sums, sums);
+    _mm_cvtss_f32(_mm_add_ss(sums, high))
+}
+
+/// 4x4矩阵行列式（SSE2优化，余子式展开）
+///
+/// # Safety
+///
+/// 调用者必须确保：
+/// 1. 当前CPU支持SSE2指令集（通过is_x86_feature_detected!检查）
+/// 2. `m` 矩阵内存有效且已初始化
+///
+/// # Examples
+///
+/// ```rust
+/// use game_engine_simd::math::x86::mat4_determinant_sse2;
+///
+/// let scale = [
+///     [2.0, 0.0, 0.0, 0.0],
+///     [0.0, 3.0, 0.0, 0.0],
+///     [0.0, 0.0, 4.0, 0.0],
+///     [0.0, 0.0, 0.0, 1.0],
+/// ];
+///
+/// if is_x86_feature_detected!("sse2") {
+///     let det = unsafe { mat4_determinant_sse2(&scale) };
+///     assert_eq!(det, 24.0);
+/// }
+/// ```
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+pub unsafe fn mat4_determinant_sse2(m: &[[f32; 4]; 4]) -> f32 {
+    let r0 = _mm_loadu_ps(m[0].as_ptr());
+    let r1 = _mm_loadu_ps(m[1].as_ptr());
+    let r2 = _mm_loadu_ps(m[2].as_ptr());
+    let r3 = _mm_loadu_ps(m[3].as_ptr());
+    
+    // 第0行与伴随矩阵第0列的点积
+    let sign = _mm_setr_ps(1.0, -1.0, 1.0, -1.0);
+    let col0 = adjugate_column_sse2(r1, r2, r3, sign);
+    horizontal_sum_sse2(_mm_mul_ps(r0, col0))
+}
+
+/// 4x4矩阵求逆（SSE2优化，伴随矩阵除以行列式）
+///
+/// 返回行列式；行列式为0时 `out` 的内容无效，由调用者判断。
+///
+/// # Safety
+///
+/// 调用者必须确保：
+/// 1. 当前CPU支持SSE2指令集（通过is_x86_feature_detected!检查）
+/// 2. `m` 矩阵内存有效且已初始化
+/// 3. `out` 矩阵可写且不与 `m` 重叠
+///
+/// # Examples
+///
+/// ```rust
+/// use game_engine_simd::math::x86::mat4_inverse_sse2;
+///
+/// let translation = [
+///     [1.0, 0.0, 0.0, 5.0],
+///     [0.0, 1.0, 0.0, 6.0],
+///     [0.0, 0.0, 1.0, 7.0],
+///     [0.0, 0.0, 0.0, 1.0],
+/// ];
+/// let mut out = [[0.0; 4]; 4];
+///
+/// if is_x86_feature_detected!("sse2") {
+///     let det = unsafe { mat4_inverse_sse2(&translation, &mut out) };
+///     assert_eq!(det, 1.0);
+///     assert_eq!(out[0][3], -5.0);
+/// }
+/// ```
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+pub unsafe fn mat4_inverse_sse2(m: &[[f32; 4]; 4], out: &mut [[f32; 4]; 4]) -> f32 {
+    let r0 = _mm_loadu_ps(m[0].as_ptr());
+    let r1 = _mm_loadu_ps(m[1].as_ptr());
+    let r2 = _mm_loadu_ps(m[2].as_ptr());
+    let r3 = _mm_loadu_ps(m[3].as_ptr());
+    
+    let sign_a = _mm_setr_ps(1.0, -1.0, 1.0, -1.0);
+    let sign_b = _mm_setr_ps(-1.0, 1.0, -1.0, 1.0);
+    
+    // 伴随矩阵的四列：前两列使用第2、3行的子式，后两列使用第0、1行的子式
+    let mut col0 = adjugate_column_sse2(r1, r2, r3, sign_a);
+    let mut col1 = adjugate_column_sse2(r0, r2, r3, sign_b);
+    let mut col2 = adjugate_column_sse2(r3, r0, r1, sign_a);
+    let mut col3 = adjugate_column_sse2(r2, r0, r1, sign_b);
+    
+    let det = horizontal_sum_sse2(_mm_mul_ps(r0, col0));
+    
+    // 列转置为行并除以行列式
+    _MM_TRANSPOSE4_PS(&mut col0, &mut col1, &mut col2, &mut col3);
+    let inv_det = _mm_set1_ps(1.0 / det);
+    _mm_storeu_ps(out[0].as_mut_ptr(), _mm_mul_ps(col0, inv_det));
+    _mm_storeu_ps(out[1].as_mut_ptr(), _mm_mul_ps(col1, inv_det));
+    _mm_storeu_ps(out[2].as_mut_ptr(), _mm_mul_ps(col2, inv_det));
+    _mm_storeu_ps(out[3].as_mut_ptr(), _mm_mul_ps(col3, inv_det));
+    
+    det
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
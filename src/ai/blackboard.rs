@@ -0,0 +1,95 @@
+//! 行为树黑板
+//!
+//! 行为树节点之间共享临时数据（例如选定的目标位置）的类型化键值存储。
+//! 每个 `BehaviorTree` 实例持有一块黑板，执行时传给每个动作和条件节点。
+
+use std::any::Any;
+use std::collections::HashMap;
+
+/// 类型化键值存储
+#[derive(Default)]
+pub struct Blackboard {
+    values: HashMap<String, Box<dyn Any + Send + Sync>>,
+}
+
+impl Blackboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 写入值，覆盖同名键（类型可以不同）
+    pub fn set<T: Any + Send + Sync>(&mut self, key: impl Into<String>, value: T) {
+        self.values.insert(key.into(), Box::new(value));
+    }
+
+    /// 读取值，键不存在或类型不符时返回 `None`
+    pub fn get<T: Any>(&self, key: &str) -> Option<&T> {
+        self.values.get(key)?.downcast_ref()
+    }
+
+    /// 可变读取值，键不存在或类型不符时返回 `None`
+    pub fn get_mut<T: Any>(&mut self, key: &str) -> Option<&mut T> {
+        self.values.get_mut(key)?.downcast_mut()
+    }
+
+    /// 移除并返回值，类型不符时保留原值并返回 `None`
+    pub fn remove<T: Any>(&mut self, key: &str) -> Option<T> {
+        if !self.values.get(key)?.is::<T>() {
+            return None;
+        }
+        self.values
+            .remove(key)
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl std::fmt::Debug for Blackboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.values.keys()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn test_typed_access() {
+        let mut blackboard = Blackboard::new();
+        blackboard.set("target_pos", Vec3::new(1.0, 0.0, 2.0));
+        blackboard.set("ammo", 3u32);
+
+        assert_eq!(
+            blackboard.get::<Vec3>("target_pos"),
+            Some(&Vec3::new(1.0, 0.0, 2.0))
+        );
+        // 类型不符
+        assert_eq!(blackboard.get::<f32>("target_pos"), None);
+        assert_eq!(blackboard.remove::<f32>("ammo"), None);
+
+        *blackboard.get_mut::<u32>("ammo").unwrap() -= 1;
+        assert_eq!(blackboard.remove::<u32>("ammo"), Some(2));
+        assert!(!blackboard.contains("ammo"));
+
+        blackboard.clear();
+        assert!(blackboard.is_empty());
+    }
+}
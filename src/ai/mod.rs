@@ -49,15 +49,19 @@
 //! ```
 
 pub mod behavior_tree;
+pub mod blackboard;
 pub mod flocking;
 pub mod navmesh;
 pub mod pathfinding;
 pub mod state_machine;
 
 pub use navmesh::{
-    ColliderGeometry, NavMesh, NavMeshConfig, NavMeshError, NavMeshGenerator, NavPolygon,
+    ColliderGeometry, NavMesh, NavMeshConfig, NavMeshError, NavMeshGenerator, NavPathSegment,
+    NavPolygon, OffMeshLink,
 };
 
+pub use blackboard::Blackboard;
+
 pub use flocking::{Agent, AgentId, FlockConfig, FlockManager, FlockingError, Obstacle};
 
 // 重新导出寻路相关类型
@@ -68,6 +72,8 @@ pub use pathfinding::{
 
 use bevy_ecs::prelude::*;
 use glam::Vec3;
+use crate::ecs::Time;
+use std::sync::Mutex;
 
 /// AI状态类型
 pub enum AIStatus {
@@ -116,14 +122,79 @@ impl Default for AI {
 /// 行为树
 pub struct BehaviorTree {
     pub root: BehaviorNode,
+    /// 节点间共享数据的黑板，每个行为树实例一块
+    pub blackboard: Blackboard,
+    /// 为 `false` 时每次执行前清空黑板
+    pub persist_blackboard: bool,
+}
+
+impl BehaviorTree {
+    pub fn new(root: BehaviorNode) -> Self {
+        Self {
+            root,
+            blackboard: Blackboard::new(),
+            persist_blackboard: true,
+        }
+    }
+
+    /// 设置黑板是否跨次执行保留
+    pub fn with_persistent_blackboard(mut self, persist: bool) -> Self {
+        self.persist_blackboard = persist;
+        self
+    }
 }
 
 /// 行为树节点
 pub enum BehaviorNode {
     Sequence(Vec<BehaviorNode>),
     Selector(Vec<BehaviorNode>),
-    Action(Box<dyn Fn(&mut World, Entity) -> BehaviorStatus + Send + Sync>),
-    Condition(Box<dyn Fn(&World, Entity) -> bool + Send + Sync>),
+    Action(Box<dyn Fn(&mut World, Entity, &mut Blackboard) -> BehaviorStatus + Send + Sync>),
+    Condition(Box<dyn Fn(&World, Entity, &Blackboard) -> bool + Send + Sync>),
+    /// 反转子节点结果（Success <-> Failure），Running 保持不变
+    Inverter(Box<BehaviorNode>),
+    /// 重复执行子节点
+    ///
+    /// `count` 为 `Some(n)` 时连续执行 n 次，子节点失败或运行中时提前返回；
+    /// 为 `None` 时每次执行只运行一次子节点并返回 Running，直到子节点失败。
+    Repeater {
+        child: Box<BehaviorNode>,
+        count: Option<u32>,
+    },
+    /// 冷却：距子节点上次结束不足 `seconds` 秒时直接返回 Failure
+    ///
+    /// 子节点返回 Success 或 Failure 时开始冷却，Running 期间每次执行都会继续运行子节点。
+    /// 时间取自 `Time` 资源的缩放后累计时间，暂停和时间缩放同样作用于冷却。
+    /// 世界中没有 `Time` 资源时不进行冷却，每次执行都运行子节点。
+    Cooldown {
+        child: Box<BehaviorNode>,
+        seconds: f32,
+        /// 子节点上次结束时的 `Time::elapsed_seconds`
+        last_run: Mutex<Option<f64>>,
+    },
+}
+
+impl BehaviorNode {
+    /// 创建反转节点
+    pub fn inverter(child: BehaviorNode) -> Self {
+        Self::Inverter(Box::new(child))
+    }
+
+    /// 创建重复节点
+    pub fn repeater(child: BehaviorNode, count: Option<u32>) -> Self {
+        Self::Repeater {
+            child: Box::new(child),
+            count,
+        }
+    }
+
+    /// 创建冷却节点
+    pub fn cooldown(child: BehaviorNode, seconds: f32) -> Self {
+        Self::Cooldown {
+            child: Box::new(child),
+            seconds,
+            last_run: Mutex::new(None),
+        }
+    }
 }
 
 /// 状态机
@@ -157,7 +228,7 @@ pub struct AIService;
 impl AIService {
     /// 创建行为树
     pub fn create_behavior_tree(root: BehaviorNode) -> BehaviorTree {
-        BehaviorTree { root }
+        BehaviorTree::new(root)
     }
 
     /// 执行行为树
@@ -165,16 +236,24 @@ impl AIService {
         &self,
         world: &mut World,
         entity: Entity,
-        tree: &BehaviorTree,
+        tree: &mut BehaviorTree,
     ) -> BehaviorStatus {
-        Self::execute_node(world, entity, &tree.root)
+        if !tree.persist_blackboard {
+            tree.blackboard.clear();
+        }
+        Self::execute_node(world, entity, &tree.root, &mut tree.blackboard)
     }
 
-    fn execute_node(world: &mut World, entity: Entity, node: &BehaviorNode) -> BehaviorStatus {
+    fn execute_node(
+        world: &mut World,
+        entity: Entity,
+        node: &BehaviorNode,
+        blackboard: &mut Blackboard,
+    ) -> BehaviorStatus {
         match node {
             BehaviorNode::Sequence(nodes) => {
                 for node in nodes {
-                    match Self::execute_node(world, entity, node) {
+                    match Self::execute_node(world, entity, node, blackboard) {
                         BehaviorStatus::Success => continue,
                         status => return status,
                     }
@@ -183,21 +262,67 @@ impl AIService {
             }
             BehaviorNode::Selector(nodes) => {
                 for node in nodes {
-                    match Self::execute_node(world, entity, node) {
+                    match Self::execute_node(world, entity, node, blackboard) {
                         BehaviorStatus::Failure => continue,
                         status => return status,
                     }
                 }
                 BehaviorStatus::Failure
             }
-            BehaviorNode::Action(action) => action(world, entity),
+            BehaviorNode::Action(action) => action(world, entity, blackboard),
             BehaviorNode::Condition(condition) => {
-                if condition(world, entity) {
+                if condition(world, entity, blackboard) {
                     BehaviorStatus::Success
                 } else {
                     BehaviorStatus::Failure
                 }
             }
+            BehaviorNode::Inverter(child) => {
+                match Self::execute_node(world, entity, child, blackboard) {
+                    BehaviorStatus::Success => BehaviorStatus::Failure,
+                    BehaviorStatus::Failure => BehaviorStatus::Success,
+                    status => status,
+                }
+            }
+            BehaviorNode::Repeater { child, count } => match count {
+                Some(count) => {
+                    for _ in 0..*count {
+                        match Self::execute_node(world, entity, child, blackboard) {
+                            BehaviorStatus::Success => continue,
+                            status => return status,
+                        }
+                    }
+                    BehaviorStatus::Success
+                }
+                None => match Self::execute_node(world, entity, child, blackboard) {
+                    BehaviorStatus::Failure => BehaviorStatus::Failure,
+                    _ => BehaviorStatus::Running,
+                },
+            },
+            BehaviorNode::Cooldown {
+                child,
+                seconds,
+                last_run,
+            } => {
+                let cooling_down = world.get_resource::<Time>().is_some_and(|time| {
+                    last_run
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .is_some_and(|last| time.elapsed_seconds - last < *seconds as f64)
+                });
+                if cooling_down {
+                    return BehaviorStatus::Failure;
+                }
+
+                let status = Self::execute_node(world, entity, child, blackboard);
+                if matches!(status, BehaviorStatus::Success | BehaviorStatus::Failure) {
+                    if let Some(time) = world.get_resource::<Time>() {
+                        *last_run.lock().unwrap_or_else(|e| e.into_inner()) =
+                            Some(time.elapsed_seconds);
+                    }
+                }
+                status
+            }
         }
     }
 
@@ -250,3 +375,206 @@ impl AIService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn execute(world: &mut World, entity: Entity, node: &BehaviorNode) -> BehaviorStatus {
+        AIService::execute_node(world, entity, node, &mut Blackboard::new())
+    }
+
+    fn counting_action(counter: &Arc<AtomicU32>) -> BehaviorNode {
+        let counter = counter.clone();
+        BehaviorNode::Action(Box::new(move |_, _, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            BehaviorStatus::Success
+        }))
+    }
+
+    #[test]
+    fn test_inverter() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+
+        let node = BehaviorNode::inverter(BehaviorNode::Condition(Box::new(|_, _, _| true)));
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Failure
+        ));
+
+        let node = BehaviorNode::inverter(BehaviorNode::Condition(Box::new(|_, _, _| false)));
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+    }
+
+    #[test]
+    fn test_repeater_runs_child_count_times() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let node = BehaviorNode::repeater(counting_action(&counter), Some(3));
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+        // 无限重复时每次执行运行一次子节点
+        let node = BehaviorNode::repeater(counting_action(&counter), None);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Running
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_cooldown_blocks_reentry() {
+        let mut world = World::new();
+        world.insert_resource(Time::default());
+        let entity = world.spawn_empty().id();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let node = BehaviorNode::cooldown(counting_action(&counter), 60.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Failure
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // 冷却按游戏时间计算，暂停期间不会结束
+        world.resource_mut::<Time>().time_scale = 0.0;
+        world.resource_mut::<Time>().advance(120.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Failure
+        ));
+        world.resource_mut::<Time>().time_scale = 1.0;
+        world.resource_mut::<Time>().advance(60.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+        let node = BehaviorNode::cooldown(counting_action(&counter), 0.0);
+        execute(&mut world, entity, &node);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_cooldown_without_time_resource_does_not_cool_down() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let node = BehaviorNode::cooldown(counting_action(&counter), 60.0);
+        for _ in 0..2 {
+            assert!(matches!(
+                execute(&mut world, entity, &node),
+                BehaviorStatus::Success
+            ));
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_cooldown_starts_when_child_finishes() {
+        let mut world = World::new();
+        world.insert_resource(Time::default());
+        let entity = world.spawn_empty().id();
+        let ticks = Arc::new(AtomicU32::new(0));
+
+        // 子节点运行两次后成功
+        let child = {
+            let ticks = ticks.clone();
+            BehaviorNode::Action(Box::new(move |_, _, _| {
+                if ticks.fetch_add(1, Ordering::SeqCst) < 2 {
+                    BehaviorStatus::Running
+                } else {
+                    BehaviorStatus::Success
+                }
+            }))
+        };
+        let node = BehaviorNode::cooldown(child, 5.0);
+
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Running
+        ));
+        world.resource_mut::<Time>().advance(1.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Running
+        ));
+        world.resource_mut::<Time>().advance(1.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+
+        // 冷却从子节点成功时开始计时
+        world.resource_mut::<Time>().advance(4.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Failure
+        ));
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+        world.resource_mut::<Time>().advance(1.0);
+        assert!(matches!(
+            execute(&mut world, entity, &node),
+            BehaviorStatus::Success
+        ));
+        assert_eq!(ticks.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_blackboard_shared_between_nodes() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+
+        let root = BehaviorNode::Sequence(vec![
+            BehaviorNode::Action(Box::new(|_, _, blackboard| {
+                blackboard.set("target_pos", Vec3::new(4.0, 0.0, 2.0));
+                BehaviorStatus::Success
+            })),
+            BehaviorNode::Condition(Box::new(|_, _, blackboard| {
+                blackboard
+                    .get::<Vec3>("target_pos")
+                    .is_some_and(|target| target.x > 3.0)
+            })),
+        ]);
+        let mut tree = AIService::create_behavior_tree(root);
+        assert!(matches!(
+            AIService.execute_behavior(&mut world, entity, &mut tree),
+            BehaviorStatus::Success
+        ));
+        assert!(tree.blackboard.contains("target_pos"));
+
+        // 不保留黑板时，上次写入的数据在执行前被清空
+        let condition = BehaviorNode::Condition(Box::new(|_, _, blackboard| {
+            blackboard.contains("target_pos")
+        }));
+        let mut tree = BehaviorTree::new(condition).with_persistent_blackboard(false);
+        tree.blackboard.set("target_pos", Vec3::ZERO);
+        assert!(matches!(
+            AIService.execute_behavior(&mut world, entity, &mut tree),
+            BehaviorStatus::Failure
+        ));
+    }
+}
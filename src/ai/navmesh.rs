@@ -65,6 +65,8 @@ pub struct NavMeshConfig {
     pub min_region_size: f32,
     /// 边缘最大长度（用于简化）
     pub max_edge_length: f32,
+    /// 离网连接（跳跃、梯子、传送门），生成时加入导航网格
+    pub off_mesh_links: Vec<OffMeshLink>,
 }
 
 impl_default!(NavMeshConfig {
@@ -74,8 +76,61 @@ impl_default!(NavMeshConfig {
     voxel_size: 0.2,
     min_region_size: 2.0,
     max_edge_length: 2.0,
+    off_mesh_links: Vec::new(),
 });
 
+/// 离网连接
+///
+/// 连接两个不相邻的多边形，代理需要播放跳跃、攀爬等动画才能通过。
+/// 端点会吸附到最近的多边形上。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffMeshLink {
+    /// 起点
+    pub start: Vec3,
+    /// 终点
+    pub end: Vec3,
+    /// 是否可以从终点反向通过
+    pub bidirectional: bool,
+    /// 通过连接本身的代价（与世界距离同单位）
+    pub cost: f32,
+}
+
+impl OffMeshLink {
+    /// 创建新的离网连接，代价默认为两端点间的距离
+    pub fn new(start: Vec3, end: Vec3, bidirectional: bool) -> Self {
+        Self {
+            start,
+            end,
+            bidirectional,
+            cost: start.distance(end),
+        }
+    }
+
+    /// 设置通过代价
+    pub fn with_cost(mut self, cost: f32) -> Self {
+        self.cost = cost.max(0.0);
+        self
+    }
+}
+
+/// 带标注的路径段
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NavPathSegment {
+    /// 段起点
+    pub start: Vec3,
+    /// 段终点
+    pub end: Vec3,
+    /// 若该段经由离网连接，记录其在 `NavMesh::off_mesh_links` 中的索引
+    pub link: Option<usize>,
+}
+
+impl NavPathSegment {
+    /// 是否为离网连接段
+    pub fn is_off_mesh_link(&self) -> bool {
+        self.link.is_some()
+    }
+}
+
 /// 碰撞体几何
 #[derive(Debug, Clone)]
 pub struct ColliderGeometry {
@@ -169,6 +224,8 @@ pub struct NavMesh {
     pub vertices: Vec<Vec3>,
     /// 多边形列表
     pub polygons: Vec<NavPolygon>,
+    /// 离网连接
+    pub off_mesh_links: Vec<OffMeshLink>,
     /// 区域映射（区域ID -> 多边形索引列表）
     regions: HashMap<u32, Vec<usize>>,
 }
@@ -188,10 +245,17 @@ impl NavMesh {
         Self {
             vertices,
             polygons,
+            off_mesh_links: Vec::new(),
             regions,
         }
     }
 
+    /// 添加离网连接，返回连接索引
+    pub fn add_off_mesh_link(&mut self, link: OffMeshLink) -> usize {
+        self.off_mesh_links.push(link);
+        self.off_mesh_links.len() - 1
+    }
+
     /// 查找最近的多边形
     pub fn find_nearest_polygon(&self, point: Vec3) -> Option<usize> {
         let mut nearest_idx = None;
@@ -210,6 +274,21 @@ impl NavMesh {
 
     /// 查找路径（使用A*算法）
     pub fn find_path(&self, start: Vec3, end: Vec3) -> Result<Vec<Vec3>, NavMeshError> {
+        let segments = self.find_path_with_links(start, end)?;
+
+        let mut path = vec![start];
+        path.extend(segments.iter().map(|segment| segment.end));
+        Ok(path)
+    }
+
+    /// 查找路径并标注经由离网连接的路径段
+    ///
+    /// 离网连接作为额外的图边参与A*，代价更低时才会被选用。
+    pub fn find_path_with_links(
+        &self,
+        start: Vec3,
+        end: Vec3,
+    ) -> Result<Vec<NavPathSegment>, NavMeshError> {
         if self.polygons.is_empty() {
             return Err(NavMeshError::PathNotFound);
         }
@@ -222,27 +301,95 @@ impl NavMesh {
             .ok_or(NavMeshError::PathNotFound)?;
 
         if start_poly == end_poly {
-            return Ok(vec![start, end]);
+            return Ok(vec![NavPathSegment {
+                start,
+                end,
+                link: None,
+            }]);
         }
 
         // A* 寻路
         let path_polys = self.astar_path(start_poly, end_poly)?;
 
-        // 将多边形路径转换为点路径
+        // 将多边形路径转换为点路径，links[i] 标注 path[i] -> path[i + 1] 这一段
         let mut path = vec![start];
-        for poly_idx in path_polys {
+        let mut links = Vec::new();
+        for (poly_idx, via) in path_polys.into_iter().skip(1) {
+            if let Some((link_idx, reversed)) = via {
+                let link = &self.off_mesh_links[link_idx];
+                let (entry, exit) = if reversed {
+                    (link.end, link.start)
+                } else {
+                    (link.start, link.end)
+                };
+                path.push(entry);
+                links.push(None);
+                path.push(exit);
+                links.push(Some(link_idx));
+            }
             path.push(self.polygons[poly_idx].center);
+            links.push(None);
         }
         path.push(end);
+        links.push(None);
 
         // 路径平滑（可选）
-        let smoothed_path = self.smooth_path(&path);
+        let (path, links) = self.smooth_path(&path, &links);
+
+        Ok(path
+            .windows(2)
+            .zip(links)
+            .map(|(points, link)| NavPathSegment {
+                start: points[0],
+                end: points[1],
+                link,
+            })
+            // 丢弃重合点产生的零长度步行段
+            .filter(|segment| segment.link.is_some() || segment.start != segment.end)
+            .collect())
+    }
 
-        Ok(smoothed_path)
+    /// 离网连接对应的图边：(起点多边形, 终点多边形, 连接索引, 是否反向)
+    fn link_edges(&self) -> Vec<(usize, usize, usize, bool)> {
+        let mut edges = Vec::new();
+        for (idx, link) in self.off_mesh_links.iter().enumerate() {
+            let (Some(from), Some(to)) = (
+                self.find_nearest_polygon(link.start),
+                self.find_nearest_polygon(link.end),
+            ) else {
+                continue;
+            };
+            edges.push((from, to, idx, false));
+            if link.bidirectional {
+                edges.push((to, from, idx, true));
+            }
+        }
+        edges
+    }
+
+    /// 经由离网连接从多边形 `a` 到多边形 `b` 的代价
+    fn link_distance(&self, a: usize, b: usize, link: usize, reversed: bool) -> i32 {
+        let link = &self.off_mesh_links[link];
+        let (entry, exit) = if reversed {
+            (link.end, link.start)
+        } else {
+            (link.start, link.end)
+        };
+        let dist = (self.polygons[a].center - entry).length()
+            + link.cost
+            + (exit - self.polygons[b].center).length();
+        (dist * 100.0) as i32
     }
 
     /// A* 寻路算法
-    fn astar_path(&self, start: usize, end: usize) -> Result<Vec<usize>, NavMeshError> {
+    ///
+    /// 返回多边形序列，并记录进入每个多边形时经由的离网连接（索引, 是否反向）。
+    #[allow(clippy::type_complexity)]
+    fn astar_path(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Result<Vec<(usize, Option<(usize, bool)>)>, NavMeshError> {
         use std::cmp::Ordering;
         use std::collections::BinaryHeap;
 
@@ -267,9 +414,12 @@ impl NavMesh {
 
         // 如果起点和终点相同，直接返回
         if start == end {
-            return Ok(vec![start]);
+            return Ok(vec![(start, None)]);
         }
 
+        let link_edges = self.link_edges();
+        let heuristic_scale = self.heuristic_scale();
+
         let mut open_set = BinaryHeap::new();
         let mut came_from = HashMap::new();
         let mut g_score = HashMap::new();
@@ -278,7 +428,7 @@ impl NavMesh {
         let start_node = Node {
             idx: start,
             cost: 0,
-            heuristic: self.heuristic(start, end),
+            heuristic: self.heuristic(start, end, heuristic_scale),
         };
 
         open_set.push(start_node);
@@ -291,28 +441,45 @@ impl NavMesh {
                 let mut path = Vec::new();
                 let mut current_idx = end;
 
-                while let Some(&prev_idx) = came_from.get(&current_idx) {
-                    path.push(current_idx);
+                while let Some(&(prev_idx, via)) = came_from.get(&current_idx) {
+                    path.push((current_idx, via));
                     current_idx = prev_idx;
                     if current_idx == start {
                         break;
                     }
                 }
-                path.push(start);
+                path.push((start, None));
                 path.reverse();
                 return Ok(path);
             }
 
             let current_g = *g_score.get(&current.idx).unwrap_or(&i32::MAX);
 
-            for &neighbor_idx in &self.polygons[current.idx].neighbors {
-                let tentative_g = current_g + self.distance(current.idx, neighbor_idx);
+            let walk_edges = self.polygons[current.idx]
+                .neighbors
+                .iter()
+                .map(|&neighbor_idx| {
+                    (neighbor_idx, None, self.distance(current.idx, neighbor_idx))
+                });
+            let off_mesh_edges = link_edges
+                .iter()
+                .filter(|(from, ..)| *from == current.idx)
+                .map(|&(from, to, link, reversed)| {
+                    (
+                        to,
+                        Some((link, reversed)),
+                        self.link_distance(from, to, link, reversed),
+                    )
+                });
+
+            for (neighbor_idx, via, edge_cost) in walk_edges.chain(off_mesh_edges) {
+                let tentative_g = current_g + edge_cost;
 
                 if tentative_g < *g_score.get(&neighbor_idx).unwrap_or(&i32::MAX) {
-                    came_from.insert(neighbor_idx, current.idx);
+                    came_from.insert(neighbor_idx, (current.idx, via));
                     g_score.insert(neighbor_idx, tentative_g);
 
-                    let h = self.heuristic(neighbor_idx, end);
+                    let h = self.heuristic(neighbor_idx, end, heuristic_scale);
                     f_score.insert(neighbor_idx, tentative_g + h);
 
                     open_set.push(Node {
@@ -327,10 +494,24 @@ impl NavMesh {
         Err(NavMeshError::PathNotFound)
     }
 
-    /// 启发式函数（曼哈顿距离）
-    fn heuristic(&self, a: usize, b: usize) -> i32 {
+    /// 启发式函数（按 `scale` 缩放的直线距离）
+    fn heuristic(&self, a: usize, b: usize, scale: f32) -> i32 {
         let dist = (self.polygons[a].center - self.polygons[b].center).length();
-        (dist * 100.0) as i32
+        (dist * scale * 100.0) as i32
+    }
+
+    /// 启发式的缩放系数
+    ///
+    /// 代价低于端点距离的离网连接（如传送）会让直线距离高估剩余代价，
+    /// 取所有连接 `代价 / 端点距离` 的最小值（不超过 1），保证启发式不高估。
+    fn heuristic_scale(&self) -> f32 {
+        self.off_mesh_links
+            .iter()
+            .filter_map(|link| {
+                let dist = link.start.distance(link.end);
+                (dist > f32::EPSILON).then(|| link.cost / dist)
+            })
+            .fold(1.0, f32::min)
     }
 
     /// 计算两个多边形之间的距离
@@ -340,12 +521,19 @@ impl NavMesh {
     }
 
     /// 路径平滑（使用简单的线性插值）
-    fn smooth_path(&self, path: &[Vec3]) -> Vec<Vec3> {
+    ///
+    /// `links[i]` 标注 `path[i] -> path[i + 1]` 这一段，离网连接的端点不会被移除。
+    fn smooth_path(
+        &self,
+        path: &[Vec3],
+        links: &[Option<usize>],
+    ) -> (Vec<Vec3>, Vec<Option<usize>>) {
         if path.len() <= 2 {
-            return path.to_vec();
+            return (path.to_vec(), links.to_vec());
         }
 
         let mut smoothed = vec![path[0]];
+        let mut smoothed_links = Vec::new();
 
         for i in 1..path.len() - 1 {
             // 简单的线性插值
@@ -353,6 +541,12 @@ impl NavMesh {
             let curr = path[i];
             let next = path[i + 1];
 
+            if links[i - 1].is_some() || links[i].is_some() {
+                smoothed.push(curr);
+                smoothed_links.push(links[i - 1]);
+                continue;
+            }
+
             let dir1 = (curr - prev).normalize();
             let dir2 = (next - curr).normalize();
 
@@ -362,10 +556,12 @@ impl NavMesh {
             }
 
             smoothed.push(curr);
+            smoothed_links.push(links[i - 1]);
         }
 
         smoothed.push(*path.last().unwrap());
-        smoothed
+        smoothed_links.push(links[links.len() - 1]);
+        (smoothed, smoothed_links)
     }
 
     /// 获取多边形数量
@@ -492,7 +688,9 @@ impl NavMeshGenerator {
         // 7. 简化网格（可选）
         // Self::simplify_mesh(&mut polygons, &mut vertices, config.max_edge_length);
 
-        Ok(NavMesh::new(vertices, polygons))
+        let mut navmesh = NavMesh::new(vertices, polygons);
+        navmesh.off_mesh_links = config.off_mesh_links;
+        Ok(navmesh)
     }
 
     /// 计算多边形邻居关系
@@ -655,4 +853,138 @@ mod tests {
         assert_eq!(path[0], start);
         assert_eq!(path[path.len() - 1], end);
     }
+
+    #[test]
+    fn test_off_mesh_link_across_gap() {
+        // 两块互不相邻的平台，中间隔着 3 米宽的缺口
+        let vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 2.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(5.0, 0.0, 0.0),
+            Vec3::new(7.0, 0.0, 0.0),
+            Vec3::new(7.0, 0.0, 2.0),
+            Vec3::new(5.0, 0.0, 2.0),
+        ];
+        let polygons = vec![
+            NavPolygon::new(vec![0, 1, 2, 3], &vertices),
+            NavPolygon::new(vec![4, 5, 6, 7], &vertices),
+        ];
+        let mut navmesh = NavMesh::new(vertices, polygons);
+
+        let start = Vec3::new(0.5, 0.0, 1.0);
+        let end = Vec3::new(6.5, 0.0, 1.0);
+        assert!(matches!(
+            navmesh.find_path(start, end),
+            Err(NavMeshError::PathNotFound)
+        ));
+
+        let jump = navmesh.add_off_mesh_link(
+            OffMeshLink::new(Vec3::new(2.0, 0.0, 1.0), Vec3::new(5.0, 0.0, 1.0), false)
+                .with_cost(4.0),
+        );
+
+        let segments = navmesh.find_path_with_links(start, end).unwrap();
+        assert_eq!(segments.first().unwrap().start, start);
+        assert_eq!(segments.last().unwrap().end, end);
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+
+        let link_segments: Vec<_> = segments.iter().filter(|s| s.is_off_mesh_link()).collect();
+        assert_eq!(link_segments.len(), 1);
+        assert_eq!(link_segments[0].link, Some(jump));
+        assert_eq!(link_segments[0].start, Vec3::new(2.0, 0.0, 1.0));
+        assert_eq!(link_segments[0].end, Vec3::new(5.0, 0.0, 1.0));
+
+        let path = navmesh.find_path(start, end).unwrap();
+        assert_eq!(path.len(), segments.len() + 1);
+
+        // 单向连接不能反向通过
+        assert!(navmesh.find_path(end, start).is_err());
+        navmesh.off_mesh_links[jump].bidirectional = true;
+        let back = navmesh.find_path_with_links(end, start).unwrap();
+        let link = back.iter().find(|s| s.is_off_mesh_link()).unwrap();
+        assert_eq!(link.start, Vec3::new(5.0, 0.0, 1.0));
+        assert_eq!(link.end, Vec3::new(2.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_cheap_off_mesh_link_keeps_heuristic_admissible() {
+        // 0 - 1 - 2 步行相连，远处孤立的平台 3 通过零代价的传送连接往返
+        let vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 2.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 2.0),
+            Vec3::new(6.0, 0.0, 0.0),
+            Vec3::new(6.0, 0.0, 2.0),
+            Vec3::new(40.0, 0.0, 0.0),
+            Vec3::new(42.0, 0.0, 0.0),
+            Vec3::new(42.0, 0.0, 2.0),
+            Vec3::new(40.0, 0.0, 2.0),
+        ];
+        let mut polygons = vec![
+            NavPolygon::new(vec![0, 1, 2, 3], &vertices),
+            NavPolygon::new(vec![1, 4, 5, 2], &vertices),
+            NavPolygon::new(vec![4, 6, 7, 5], &vertices),
+            NavPolygon::new(vec![8, 9, 10, 11], &vertices),
+        ];
+        polygons[0].neighbors = vec![1];
+        polygons[1].neighbors = vec![0, 2];
+        polygons[2].neighbors = vec![1];
+        let mut navmesh = NavMesh::new(vertices, polygons);
+
+        let start = Vec3::new(1.0, 0.0, 1.0);
+        let end = Vec3::new(5.0, 0.0, 1.0);
+        let far = Vec3::new(41.0, 0.0, 1.0);
+        let out = navmesh.add_off_mesh_link(OffMeshLink::new(start, far, false).with_cost(0.0));
+        let back = navmesh.add_off_mesh_link(OffMeshLink::new(far, end, false).with_cost(0.0));
+
+        // 未缩放的启发式会高估平台 3 的剩余代价，错过更便宜的传送路径
+        let segments = navmesh.find_path_with_links(start, end).unwrap();
+        let links: Vec<_> = segments.iter().filter_map(|s| s.link).collect();
+        assert_eq!(links, vec![out, back]);
+    }
+
+    #[test]
+    fn test_off_mesh_link_used_only_when_cheaper() {
+        // 三块相连的平台 0 - 1 - 2，外加一条从 0 直达 2 的连接
+        let vertices = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(2.0, 0.0, 2.0),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 2.0),
+            Vec3::new(6.0, 0.0, 0.0),
+            Vec3::new(6.0, 0.0, 2.0),
+        ];
+        let mut polygons = vec![
+            NavPolygon::new(vec![0, 1, 2, 3], &vertices),
+            NavPolygon::new(vec![1, 4, 5, 2], &vertices),
+            NavPolygon::new(vec![4, 6, 7, 5], &vertices),
+        ];
+        polygons[0].neighbors = vec![1];
+        polygons[1].neighbors = vec![0, 2];
+        polygons[2].neighbors = vec![1];
+        let mut navmesh = NavMesh::new(vertices, polygons);
+
+        let start = Vec3::new(1.0, 0.0, 1.0);
+        let end = Vec3::new(5.0, 0.0, 1.0);
+        let link = navmesh.add_off_mesh_link(
+            OffMeshLink::new(Vec3::new(1.0, 0.0, 1.0), Vec3::new(5.0, 0.0, 1.0), true)
+                .with_cost(20.0),
+        );
+
+        let segments = navmesh.find_path_with_links(start, end).unwrap();
+        assert!(segments.iter().all(|s| !s.is_off_mesh_link()));
+
+        navmesh.off_mesh_links[link].cost = 0.5;
+        let segments = navmesh.find_path_with_links(start, end).unwrap();
+        assert!(segments.iter().any(|s| s.link == Some(link)));
+    }
 }
@@ -17,6 +17,8 @@ pub struct AnimationClip {
     pub scale_tracks: HashMap<u64, KeyframeTrack<Vec3>>,
     /// 是否循环
     pub looping: bool,
+    /// 轨道名称 (骨骼名 -> 实体ID)
+    pub track_names: HashMap<String, u64>,
 }
 
 impl AnimationClip {
@@ -28,9 +30,20 @@ impl AnimationClip {
             rotation_tracks: HashMap::new(),
             scale_tracks: HashMap::new(),
             looping: false,
+            track_names: HashMap::new(),
         }
     }
 
+    /// 为轨道命名，用于按骨骼名查找（如根运动）
+    pub fn set_track_name(&mut self, name: impl Into<String>, entity_id: u64) {
+        self.track_names.insert(name.into(), entity_id);
+    }
+
+    /// 按名称查找轨道ID
+    pub fn track_id(&self, name: &str) -> Option<u64> {
+        self.track_names.get(name).copied()
+    }
+
     /// 添加位置轨道
     pub fn add_position_track(&mut self, entity_id: u64, track: KeyframeTrack<Vec3>) {
         self.position_tracks.insert(entity_id, track);
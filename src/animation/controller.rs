@@ -0,0 +1,220 @@
+//! 动画状态机
+//!
+//! `AnimController` 描述角色的动画状态、状态间的转换条件和混合时长，
+//! 由 `AnimationService::update` 每帧求值并在剪辑之间交叉淡入淡出。
+//!
+//! ```rust
+//! use game_engine::animation::{AnimCondition, AnimController, AnimTransition, AnimationClip};
+//!
+//! let mut controller = AnimController::new();
+//! let idle = controller.add_state("idle", AnimationClip::new("idle", 1.0));
+//! let run = controller.add_state("run", AnimationClip::new("run", 0.6));
+//! controller.add_transition(
+//!     AnimTransition::new(Some(idle), run, 0.2)
+//!         .with_condition(AnimCondition::FloatGreater("speed".into(), 0.1)),
+//! );
+//! controller.set_float("speed", 1.0);
+//! ```
+
+use super::clip::AnimationClip;
+use std::collections::HashMap;
+
+/// 状态机参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimParam {
+    Float(f32),
+    Bool(bool),
+    /// 触发器，被某个转换使用后自动复位
+    Trigger(bool),
+}
+
+/// 转换条件，参数不存在或类型不符时视为不满足
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimCondition {
+    /// 浮点参数大于阈值
+    FloatGreater(String, f32),
+    /// 浮点参数小于阈值
+    FloatLess(String, f32),
+    /// 布尔参数等于指定值
+    Bool(String, bool),
+    /// 触发器已被设置
+    Trigger(String),
+}
+
+/// 动画状态
+#[derive(Debug, Clone)]
+pub struct AnimState {
+    pub name: String,
+    pub clip: AnimationClip,
+    /// 播放速度倍率
+    pub speed: f32,
+}
+
+/// 状态转换
+#[derive(Debug, Clone)]
+pub struct AnimTransition {
+    /// 源状态，`None` 表示可从任意状态转换
+    pub from: Option<usize>,
+    /// 目标状态
+    pub to: usize,
+    /// 所有条件都满足时才转换
+    pub conditions: Vec<AnimCondition>,
+    /// 交叉淡入淡出时长（秒）
+    pub blend_duration: f32,
+}
+
+impl AnimTransition {
+    pub fn new(from: Option<usize>, to: usize, blend_duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            conditions: Vec::new(),
+            blend_duration: blend_duration.max(0.0),
+        }
+    }
+
+    /// 添加转换条件
+    pub fn with_condition(mut self, condition: AnimCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+}
+
+/// 正在进行的交叉淡出，记录上一个状态的播放进度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimFade {
+    /// 淡出的状态
+    pub from: usize,
+    /// 淡出状态的播放时间
+    pub from_time: f32,
+    /// 已混合时长
+    pub elapsed: f32,
+    /// 总混合时长
+    pub duration: f32,
+}
+
+impl AnimFade {
+    /// 目标状态的权重 (0.0 - 1.0)
+    pub fn weight(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// 动画状态机
+#[derive(Debug, Clone, Default)]
+pub struct AnimController {
+    pub states: Vec<AnimState>,
+    pub transitions: Vec<AnimTransition>,
+    pub params: HashMap<String, AnimParam>,
+    /// 当前状态，第一个添加的状态为入口
+    pub current: usize,
+    /// 正在进行的交叉淡出
+    pub fade: Option<AnimFade>,
+}
+
+impl AnimController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加状态，返回状态索引
+    pub fn add_state(&mut self, name: impl Into<String>, clip: AnimationClip) -> usize {
+        self.states.push(AnimState {
+            name: name.into(),
+            clip,
+            speed: 1.0,
+        });
+        self.states.len() - 1
+    }
+
+    /// 添加状态转换，按添加顺序求值
+    pub fn add_transition(&mut self, transition: AnimTransition) {
+        self.transitions.push(transition);
+    }
+
+    /// 按名称查找状态
+    pub fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+
+    /// 当前状态
+    pub fn current_state(&self) -> Option<&AnimState> {
+        self.states.get(self.current)
+    }
+
+    /// 是否正在交叉淡出
+    pub fn is_transitioning(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) {
+        self.params.insert(name.into(), AnimParam::Float(value));
+    }
+
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) {
+        self.params.insert(name.into(), AnimParam::Bool(value));
+    }
+
+    pub fn set_trigger(&mut self, name: impl Into<String>, value: bool) {
+        self.params.insert(name.into(), AnimParam::Trigger(value));
+    }
+
+    pub fn float(&self, name: &str) -> Option<f32> {
+        match self.params.get(name) {
+            Some(AnimParam::Float(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.params.get(name) {
+            Some(AnimParam::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn trigger(&self, name: &str) -> bool {
+        matches!(self.params.get(name), Some(AnimParam::Trigger(true)))
+    }
+
+    /// 判断条件是否满足
+    pub fn condition_met(&self, condition: &AnimCondition) -> bool {
+        match condition {
+            AnimCondition::FloatGreater(name, threshold) => {
+                self.float(name).is_some_and(|v| v > *threshold)
+            }
+            AnimCondition::FloatLess(name, threshold) => {
+                self.float(name).is_some_and(|v| v < *threshold)
+            }
+            AnimCondition::Bool(name, expected) => self.bool(name) == Some(*expected),
+            AnimCondition::Trigger(name) => self.trigger(name),
+        }
+    }
+
+    /// 查找第一个可以从当前状态触发的转换
+    ///
+    /// 不会转换到当前状态自身；命中的转换所使用的触发器会被复位。
+    pub fn next_transition(&mut self) -> Option<AnimTransition> {
+        let transition = self
+            .transitions
+            .iter()
+            .find(|t| {
+                t.from.is_none_or(|from| from == self.current)
+                    && t.to != self.current
+                    && t.to < self.states.len()
+                    && t.conditions.iter().all(|c| self.condition_met(c))
+            })
+            .cloned()?;
+
+        for condition in &transition.conditions {
+            if let AnimCondition::Trigger(name) = condition {
+                self.params.insert(name.clone(), AnimParam::Trigger(false));
+            }
+        }
+        Some(transition)
+    }
+}
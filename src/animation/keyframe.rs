@@ -1,4 +1,5 @@
 use glam::{Quat, Vec3};
+use std::ops::{Add, Mul};
 
 /// 插值模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -9,6 +10,8 @@ pub enum InterpolationMode {
     Step,
     /// 三次贝塞尔插值
     CubicBezier,
+    /// 三次 Hermite 插值，使用关键帧的入/出切线 (每秒变化量)
+    CubicHermite,
 }
 
 /// 关键帧
@@ -18,6 +21,42 @@ pub struct Keyframe<T> {
     pub time: f32,
     /// 值
     pub value: T,
+    /// 入切线 (仅 `CubicHermite` 使用，`None` 表示零切线)
+    pub in_tangent: Option<T>,
+    /// 出切线 (仅 `CubicHermite` 使用，`None` 表示零切线)
+    pub out_tangent: Option<T>,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(time: f32, value: T) -> Self {
+        Self {
+            time,
+            value,
+            in_tangent: None,
+            out_tangent: None,
+        }
+    }
+
+    /// 设置入/出切线
+    pub fn with_tangents(mut self, in_tangent: T, out_tangent: T) -> Self {
+        self.in_tangent = Some(in_tangent);
+        self.out_tangent = Some(out_tangent);
+        self
+    }
+}
+
+/// 三次 Hermite 插值，`m0`/`m1` 为每秒变化量，`dt` 为两个关键帧的时间间隔
+fn hermite<T>(p0: T, m0: T, p1: T, m1: T, dt: f32, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Mul<f32, Output = T>,
+{
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+    p0 * h00 + m0 * (h10 * dt) + p1 * h01 + m1 * (h11 * dt)
 }
 
 /// 关键帧轨道
@@ -42,14 +81,18 @@ where
 
     /// 添加关键帧
     pub fn add_keyframe(&mut self, time: f32, value: T) {
-        let keyframe = Keyframe { time, value };
+        self.insert_keyframe(Keyframe::new(time, value));
+    }
 
-        // 按时间排序插入
+    /// 按时间顺序插入关键帧 (可带切线)，返回插入位置
+    pub fn insert_keyframe(&mut self, keyframe: Keyframe<T>) -> usize {
+        let time = keyframe.time;
         let index = self
             .keyframes
             .binary_search_by(|k| k.time.partial_cmp(&time).unwrap())
             .unwrap_or_else(|i| i);
         self.keyframes.insert(index, keyframe);
+        index
     }
 
     /// 获取指定时间的值
@@ -123,6 +166,18 @@ impl KeyframeTrack<Vec3> {
                         let t = (time - k0.time) / (k1.time - k0.time);
                         return Some(k0.value.lerp(k1.value, t));
                     }
+                    InterpolationMode::CubicHermite => {
+                        let dt = k1.time - k0.time;
+                        let t = (time - k0.time) / dt;
+                        return Some(hermite(
+                            k0.value,
+                            k0.out_tangent.unwrap_or(Vec3::ZERO),
+                            k1.value,
+                            k1.in_tangent.unwrap_or(Vec3::ZERO),
+                            dt,
+                            t,
+                        ));
+                    }
                 }
             }
         }
@@ -131,6 +186,42 @@ impl KeyframeTrack<Vec3> {
     }
 }
 
+/// f32关键帧轨道的特化实现,用于曲线编辑器等标量动画
+impl KeyframeTrack<f32> {
+    pub fn sample_f32(&self, time: f32) -> Option<f32> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+
+        if time <= first.time {
+            return Some(first.value);
+        }
+        if time >= last.time {
+            return Some(last.value);
+        }
+
+        let k1_index = self.keyframes.iter().position(|k| k.time >= time)?;
+        let k0 = &self.keyframes[k1_index - 1];
+        let k1 = &self.keyframes[k1_index];
+        let dt = k1.time - k0.time;
+        let t = (time - k0.time) / dt;
+
+        Some(match self.interpolation {
+            InterpolationMode::Step => k0.value,
+            InterpolationMode::Linear | InterpolationMode::CubicBezier => {
+                k0.value + (k1.value - k0.value) * t
+            }
+            InterpolationMode::CubicHermite => hermite(
+                k0.value,
+                k0.out_tangent.unwrap_or(0.0),
+                k1.value,
+                k1.in_tangent.unwrap_or(0.0),
+                dt,
+                t,
+            ),
+        })
+    }
+}
+
 /// Quat关键帧轨道的特化实现,支持球面线性插值
 impl KeyframeTrack<Quat> {
     pub fn sample_quat(&self, time: f32) -> Option<Quat> {
@@ -159,7 +250,7 @@ impl KeyframeTrack<Quat> {
                         let t = (time - k0.time) / (k1.time - k0.time);
                         return Some(k0.value.slerp(k1.value, t));
                     }
-                    InterpolationMode::CubicBezier => {
+                    InterpolationMode::CubicBezier | InterpolationMode::CubicHermite => {
                         // 简化版本,使用球面线性插值
                         let t = (time - k0.time) / (k1.time - k0.time);
                         return Some(k0.value.slerp(k1.value, t));
@@ -189,4 +280,21 @@ mod tests {
         assert!((value.y - 0.5).abs() < 0.001);
         assert!((value.z - 0.5).abs() < 0.001);
     }
+
+    #[test]
+    fn test_cubic_hermite_track() {
+        let mut track = KeyframeTrack::<f32>::new(InterpolationMode::CubicHermite);
+        track.insert_keyframe(Keyframe::new(0.0, 0.0).with_tangents(1.0, 1.0));
+        track.insert_keyframe(Keyframe::new(1.0, 1.0).with_tangents(1.0, 1.0));
+
+        // 切线与直线斜率一致时退化为线性
+        assert!((track.sample_f32(0.25).unwrap() - 0.25).abs() < 1e-5);
+
+        // 零切线在中点对称
+        let mut flat = KeyframeTrack::<f32>::new(InterpolationMode::CubicHermite);
+        flat.add_keyframe(0.0, 0.0);
+        flat.add_keyframe(2.0, 1.0);
+        assert!((flat.sample_f32(1.0).unwrap() - 0.5).abs() < 1e-5);
+        assert!(flat.sample_f32(0.5).unwrap() < 0.25);
+    }
 }
@@ -8,6 +8,7 @@
 //! - 骨骼动画支持
 //! - 动画剪辑管理
 //! - 动画播放器
+//! - 动画状态机（条件转换与交叉淡入淡出）
 //!
 //! ## 使用示例
 //!
@@ -48,6 +49,7 @@
 //! ```
 
 pub mod clip;
+pub mod controller;
 pub mod keyframe;
 pub mod player;
 pub mod service;
@@ -55,12 +57,16 @@ pub mod skeleton;
 pub mod skinned_mesh;
 
 pub use clip::AnimationClip;
+pub use controller::{
+    AnimCondition, AnimController, AnimFade, AnimParam, AnimState, AnimTransition,
+};
 pub use keyframe::{InterpolationMode, Keyframe, KeyframeTrack};
 pub use player::{
-    animation_system, skeleton_update_system, AnimationPlayer, SkeletonAnimationPlayer,
+    animation_system, skeleton_update_system, AnimationPlayer, RootMotionDelta,
+    SkeletonAnimationPlayer,
 };
 pub use service::AnimationService;
-pub use skeleton::{Bone, BoneTransform, Skeleton, SkeletonPose};
+pub use skeleton::{Bone, BoneTransform, RetargetMap, Skeleton, SkeletonPose};
 pub use skinned_mesh::{SkinnedMesh, SkinnedMeshPipeline, SkinnedVertex3D};
 
 // GLTF 骨骼加载（需要启用 gltf feature）
@@ -1,4 +1,22 @@
 use super::clip::AnimationClip;
+use super::controller::AnimController;
+use glam::{Quat, Vec3};
+
+/// 根运动增量 - 一次更新中根骨骼的位移和旋转变化（剪辑空间）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootMotionDelta {
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+impl Default for RootMotionDelta {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
 
 /// 动画播放器组件 (贫血模型 - 纯数据结构)
 ///
@@ -25,6 +43,10 @@ pub struct AnimationPlayer {
     pub speed: f32,
     /// 是否正在播放
     pub playing: bool,
+    /// 动画状态机，设置后由状态机决定当前剪辑
+    pub controller: Option<AnimController>,
+    /// 根运动骨骼名，设置后该轨道的位移和旋转以增量形式输出而不写入姿态
+    pub root_motion_bone: Option<String>,
 }
 
 impl AnimationPlayer {
@@ -32,6 +54,11 @@ impl AnimationPlayer {
         Self::default()
     }
 
+    /// 启用或关闭根运动提取，`bone` 为剪辑中的轨道名
+    pub fn set_root_motion(&mut self, enabled: bool, bone: &str) {
+        self.root_motion_bone = enabled.then(|| bone.to_string());
+    }
+
     // ==========================================
     // 以下方法保留用于向后兼容，建议使用 AnimationService
     // ==========================================
@@ -72,8 +99,8 @@ impl AnimationPlayer {
     ///
     /// **建议使用**: `AnimationService::update()`
     #[deprecated(since = "0.2.0", note = "请使用 AnimationService::update() 代替")]
-    pub fn update(&mut self, delta_time: f32) {
-        super::service::AnimationService::update(self, delta_time);
+    pub fn update(&mut self, delta_time: f32) -> RootMotionDelta {
+        super::service::AnimationService::update(self, delta_time)
     }
 }
 
@@ -88,12 +115,13 @@ pub fn animation_system(
     mut query: Query<(Entity, &mut AnimationPlayer, &mut Transform)>,
 ) {
     for (entity, mut player, mut transform) in query.iter_mut() {
-        super::service::AnimationService::update(&mut player, time.delta_seconds);
+        let root_motion = super::service::AnimationService::update(&mut player, time.delta_seconds);
         super::service::AnimationService::apply_to_transform(
             &player,
             entity.to_bits(),
             &mut transform,
         );
+        super::service::AnimationService::apply_root_motion(&root_motion, &mut transform);
     }
 }
 
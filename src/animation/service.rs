@@ -3,8 +3,10 @@
 //! 遵循DDD贫血模型，将动画业务逻辑封装在Service中
 
 use super::clip::AnimationClip;
-use super::player::AnimationPlayer;
+use super::controller::{AnimController, AnimFade};
+use super::player::{AnimationPlayer, RootMotionDelta};
 use crate::ecs::Transform;
+use glam::{Quat, Vec3};
 
 /// 动画服务 - 封装动画业务逻辑
 ///
@@ -22,6 +24,14 @@ impl AnimationService {
         player.playing = true;
     }
 
+    /// 使用动画状态机播放，从入口状态开始
+    pub fn play_controller(player: &mut AnimationPlayer, controller: AnimController) {
+        player.current_clip = controller.current_state().map(|state| state.clip.clone());
+        player.controller = Some(controller);
+        player.current_time = 0.0;
+        player.playing = true;
+    }
+
     /// 暂停播放
     pub fn pause(player: &mut AnimationPlayer) {
         player.playing = false;
@@ -54,28 +64,189 @@ impl AnimationService {
     }
 
     /// 更新动画状态
-    pub fn update(player: &mut AnimationPlayer, delta_time: f32) {
+    ///
+    /// 设置了状态机时先求值状态转换并推进交叉淡出。
+    /// 启用根运动时返回本次更新中根骨骼的运动增量，否则返回零增量。
+    pub fn update(player: &mut AnimationPlayer, delta_time: f32) -> RootMotionDelta {
         if !player.playing {
-            return;
+            return RootMotionDelta::default();
         }
 
+        Self::update_controller(player, delta_time);
+        let state_speed = player
+            .controller
+            .as_ref()
+            .and_then(|controller| controller.current_state())
+            .map_or(1.0, |state| state.speed);
+
+        let mut root_motion = RootMotionDelta::default();
         if let Some(clip) = &player.current_clip {
-            player.current_time += delta_time * player.speed;
+            let previous_time = player.current_time;
+            player.current_time += delta_time * player.speed * state_speed;
+            let mut loops = 0;
 
             if player.current_time >= clip.duration {
                 if clip.looping {
+                    if clip.duration > 0.0 {
+                        loops = (player.current_time / clip.duration) as u32;
+                    }
                     player.current_time %= clip.duration;
                 } else {
                     player.current_time = clip.duration;
-                    player.playing = false;
+                    // 状态机中的非循环状态停在最后一帧，等待转换
+                    if player.controller.is_none() {
+                        player.playing = false;
+                    }
                 }
             }
+
+            if let Some(track) = player
+                .root_motion_bone
+                .as_deref()
+                .and_then(|bone| clip.track_id(bone))
+            {
+                root_motion = Self::extract_root_motion(
+                    clip,
+                    track,
+                    previous_time,
+                    player.current_time,
+                    loops,
+                );
+            }
+        }
+
+        root_motion
+    }
+
+    /// 计算根轨道从 `from` 到 `to` 的运动增量，`loops` 为期间跨过剪辑末尾的次数
+    ///
+    /// 跨过末尾时增量由三段组成：`from` 到末尾、若干完整循环、开头到 `to`。
+    fn extract_root_motion(
+        clip: &AnimationClip,
+        track: u64,
+        from: f32,
+        to: f32,
+        loops: u32,
+    ) -> RootMotionDelta {
+        let position = |t: f32| clip.sample_position(track, t).unwrap_or(Vec3::ZERO);
+        let rotation = |t: f32| clip.sample_rotation(track, t).unwrap_or(Quat::IDENTITY);
+
+        if loops == 0 {
+            return RootMotionDelta {
+                translation: position(to) - position(from),
+                rotation: (rotation(from).inverse() * rotation(to)).normalize(),
+            };
+        }
+
+        let (start, end) = (0.0, clip.duration);
+        let full_translation = position(end) - position(start);
+        let full_rotation = rotation(start).inverse() * rotation(end);
+
+        let mut translation = position(end) - position(from);
+        let mut rotation_delta = rotation(from).inverse() * rotation(end);
+        for _ in 1..loops {
+            translation += full_translation;
+            rotation_delta *= full_rotation;
+        }
+        translation += position(to) - position(start);
+        rotation_delta *= rotation(start).inverse() * rotation(to);
+
+        RootMotionDelta {
+            translation,
+            rotation: rotation_delta.normalize(),
+        }
+    }
+
+    /// 将根运动增量应用到 Transform，位移按实体当前朝向旋转
+    pub fn apply_root_motion(delta: &RootMotionDelta, transform: &mut Transform) {
+        transform.pos += transform.rot * delta.translation;
+        transform.rot = (transform.rot * delta.rotation).normalize();
+    }
+
+    /// 求值状态转换，并推进淡出中的上一个状态
+    fn update_controller(player: &mut AnimationPlayer, delta_time: f32) {
+        let Some(controller) = player.controller.as_mut() else {
+            return;
+        };
+
+        if let Some(transition) = controller.next_transition() {
+            controller.fade = (transition.blend_duration > 0.0).then_some(AnimFade {
+                from: controller.current,
+                from_time: player.current_time,
+                elapsed: 0.0,
+                duration: transition.blend_duration,
+            });
+            controller.current = transition.to;
+            player.current_clip = Some(controller.states[transition.to].clip.clone());
+            player.current_time = 0.0;
+        }
+
+        let AnimController { states, fade, .. } = controller;
+        if let Some(active) = fade {
+            active.elapsed += delta_time;
+            if active.elapsed >= active.duration {
+                *fade = None;
+            } else {
+                let from = &states[active.from];
+                let time = active.from_time + delta_time * player.speed * from.speed;
+                active.from_time = if from.clip.looping && from.clip.duration > 0.0 {
+                    time % from.clip.duration
+                } else {
+                    time.min(from.clip.duration)
+                };
+            }
         }
     }
 
     /// 应用动画到Transform组件
+    ///
+    /// 状态机交叉淡出期间按淡入权重混合上一个状态和当前状态的采样结果。
+    /// 根运动轨道的位移和旋转已由 `update` 的增量输出，不再写入姿态。
     pub fn apply_to_transform(player: &AnimationPlayer, entity_id: u64, transform: &mut Transform) {
-        if let Some(clip) = &player.current_clip {
+        let root_motion = player
+            .root_motion_bone
+            .as_deref()
+            .zip(player.current_clip.as_ref())
+            .and_then(|(bone, clip)| clip.track_id(bone))
+            == Some(entity_id);
+        let original = *transform;
+        let fade = player.controller.as_ref().and_then(|controller| {
+            controller
+                .fade
+                .map(|fade| (&controller.states[fade.from].clip, fade))
+        });
+
+        if let (Some(clip), Some((from_clip, fade))) = (&player.current_clip, fade) {
+            let weight = fade.weight();
+            let time = player.current_time;
+            let blend = |a: Option<Vec3>, b: Option<Vec3>| match (a, b) {
+                (Some(a), Some(b)) => Some(a.lerp(b, weight)),
+                (a, b) => b.or(a),
+            };
+            let blend_rotation = |a: Option<Quat>, b: Option<Quat>| match (a, b) {
+                (Some(a), Some(b)) => Some(a.slerp(b, weight)),
+                (a, b) => b.or(a),
+            };
+
+            if let Some(position) = blend(
+                from_clip.sample_position(entity_id, fade.from_time),
+                clip.sample_position(entity_id, time),
+            ) {
+                transform.pos = position;
+            }
+            if let Some(rotation) = blend_rotation(
+                from_clip.sample_rotation(entity_id, fade.from_time),
+                clip.sample_rotation(entity_id, time),
+            ) {
+                transform.rot = rotation;
+            }
+            if let Some(scale) = blend(
+                from_clip.sample_scale(entity_id, fade.from_time),
+                clip.sample_scale(entity_id, time),
+            ) {
+                transform.scale = scale;
+            }
+        } else if let Some(clip) = &player.current_clip {
             if let Some(position) = clip.sample_position(entity_id, player.current_time) {
                 transform.pos = position;
             }
@@ -88,6 +259,12 @@ impl AnimationService {
                 transform.scale = scale;
             }
         }
+
+        // 根运动轨道只保留缩放
+        if root_motion {
+            transform.pos = original.pos;
+            transform.rot = original.rot;
+        }
     }
 
     /// 获取当前播放进度 (0.0 - 1.0)
@@ -128,7 +305,8 @@ impl AnimationService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use glam::{Quat, Vec3};
+    use crate::animation::controller::{AnimCondition, AnimTransition};
+    use crate::animation::keyframe::{InterpolationMode, KeyframeTrack};
 
     fn create_test_clip() -> AnimationClip {
         AnimationClip {
@@ -138,6 +316,7 @@ mod tests {
             position_tracks: std::collections::HashMap::new(),
             rotation_tracks: std::collections::HashMap::new(),
             scale_tracks: std::collections::HashMap::new(),
+            track_names: std::collections::HashMap::new(),
         }
     }
 
@@ -198,4 +377,134 @@ mod tests {
         assert!((blended.pos.x - 5.0).abs() < 0.001);
         assert!((blended.scale.x - 1.5).abs() < 0.001);
     }
+
+    #[test]
+    fn test_controller_idle_to_run() {
+        let mut idle = AnimationClip::new("idle", 1.0);
+        idle.looping = true;
+        let mut track = KeyframeTrack::<Vec3>::new(InterpolationMode::Linear);
+        track.add_keyframe(0.0, Vec3::ZERO);
+        track.add_keyframe(1.0, Vec3::ZERO);
+        idle.add_position_track(1, track);
+        let mut run = AnimationClip::new("run", 1.0);
+        run.looping = true;
+        let mut track = KeyframeTrack::<Vec3>::new(InterpolationMode::Linear);
+        track.add_keyframe(0.0, Vec3::new(10.0, 0.0, 0.0));
+        track.add_keyframe(1.0, Vec3::new(10.0, 0.0, 0.0));
+        run.add_position_track(1, track);
+
+        let mut controller = AnimController::new();
+        let idle = controller.add_state("idle", idle);
+        let run = controller.add_state("run", run);
+        controller.add_transition(
+            AnimTransition::new(Some(idle), run, 0.2)
+                .with_condition(AnimCondition::FloatGreater("speed".into(), 0.1)),
+        );
+        controller.add_transition(
+            AnimTransition::new(Some(run), idle, 0.2)
+                .with_condition(AnimCondition::FloatLess("speed".into(), 0.1)),
+        );
+        controller.set_float("speed", 0.0);
+
+        let mut player = AnimationPlayer::default();
+        AnimationService::set_speed(&mut player, 1.0);
+        AnimationService::play_controller(&mut player, controller);
+
+        // 速度未超过阈值，保持在 idle
+        AnimationService::update(&mut player, 0.1);
+        let controller = player.controller.as_ref().unwrap();
+        assert_eq!(controller.current_state().unwrap().name, "idle");
+        assert!(!controller.is_transitioning());
+
+        player.controller.as_mut().unwrap().set_float("speed", 0.5);
+        AnimationService::update(&mut player, 0.1);
+        let controller = player.controller.as_ref().unwrap();
+        assert_eq!(controller.current_state().unwrap().name, "run");
+        assert!(controller.is_transitioning());
+
+        // 混合了 0.1 / 0.2 秒，位置取 idle 与 run 的中点
+        let mut transform = Transform::default();
+        AnimationService::apply_to_transform(&player, 1, &mut transform);
+        assert!(transform.pos.abs_diff_eq(Vec3::new(5.0, 0.0, 0.0), 1e-4));
+
+        // 超过混合时长后完成转换
+        AnimationService::update(&mut player, 0.15);
+        let controller = player.controller.as_ref().unwrap();
+        assert_eq!(controller.current_state().unwrap().name, "run");
+        assert!(!controller.is_transitioning());
+        assert!((player.current_time - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_controller_trigger_consumed() {
+        let mut controller = AnimController::new();
+        controller.add_state("idle", AnimationClip::new("idle", 1.0));
+        let jump = controller.add_state("jump", AnimationClip::new("jump", 0.5));
+        controller.add_transition(
+            AnimTransition::new(None, jump, 0.0)
+                .with_condition(AnimCondition::Trigger("jump".into())),
+        );
+
+        let mut player = AnimationPlayer::default();
+        AnimationService::play_controller(&mut player, controller);
+        player
+            .controller
+            .as_mut()
+            .unwrap()
+            .set_trigger("jump", true);
+        AnimationService::update(&mut player, 0.016);
+
+        let controller = player.controller.as_ref().unwrap();
+        assert_eq!(controller.current, jump);
+        assert!(!controller.is_transitioning());
+        assert!(!controller.trigger("jump"));
+        assert_eq!(player.current_clip.as_ref().unwrap().name, "jump");
+    }
+
+    #[test]
+    fn test_root_motion_accumulates_over_loop() {
+        let mut clip = AnimationClip::new("walk", 1.0);
+        clip.looping = true;
+        let mut track = KeyframeTrack::<Vec3>::new(InterpolationMode::Linear);
+        track.add_keyframe(0.0, Vec3::ZERO);
+        track.add_keyframe(1.0, Vec3::new(1.0, 0.0, 0.0));
+        clip.add_position_track(7, track);
+        clip.set_track_name("root", 7);
+
+        let mut player = AnimationPlayer::default();
+        AnimationService::set_speed(&mut player, 1.0);
+        player.set_root_motion(true, "root");
+        AnimationService::play(&mut player, clip);
+
+        // 一次完整播放（最后一步恰好回绕）累积 +1
+        let mut total = Vec3::ZERO;
+        for _ in 0..4 {
+            total += AnimationService::update(&mut player, 0.25).translation;
+        }
+        assert!((total - Vec3::new(1.0, 0.0, 0.0)).length() < 1e-5);
+
+        // 步长跨过末尾时回绕增量依然正确
+        let mut total = Vec3::ZERO;
+        for _ in 0..10 {
+            total += AnimationService::update(&mut player, 0.3).translation;
+        }
+        assert!((total - Vec3::new(3.0, 0.0, 0.0)).length() < 1e-4);
+        total += AnimationService::update(&mut player, 2.5).translation;
+        assert!((total - Vec3::new(5.5, 0.0, 0.0)).length() < 1e-4);
+
+        // 根轨道的位移不再写入姿态，而是由增量驱动
+        let mut transform = Transform::default();
+        AnimationService::apply_to_transform(&player, 7, &mut transform);
+        assert_eq!(transform.pos, Vec3::ZERO);
+        let delta = AnimationService::update(&mut player, 0.5);
+        AnimationService::apply_root_motion(&delta, &mut transform);
+        assert!((transform.pos - Vec3::new(0.5, 0.0, 0.0)).length() < 1e-4);
+
+        // 关闭根运动时不输出增量
+        player.set_root_motion(false, "root");
+        assert_eq!(
+            AnimationService::update(&mut player, 0.25),
+            RootMotionDelta::default()
+        );
+    }
 }
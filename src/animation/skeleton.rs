@@ -4,6 +4,7 @@
 
 use bevy_ecs::prelude::*;
 use glam::{Mat4, Quat, Vec3};
+use std::collections::HashMap;
 
 // ============================================================================
 // 骨骼节点
@@ -96,7 +97,7 @@ pub struct Skeleton {
     /// 所有骨骼
     pub bones: Vec<Bone>,
     /// 骨骼名称到索引的映射
-    pub bone_name_to_index: std::collections::HashMap<String, usize>,
+    pub bone_name_to_index: HashMap<String, usize>,
     /// 当前姿态的骨骼矩阵（世界空间）
     pub bone_matrices: Vec<Mat4>,
     /// 最终蒙皮矩阵（bone_matrix * inverse_bind_matrix）
@@ -184,6 +185,28 @@ impl Skeleton {
         self.compute_skin_matrices();
     }
 
+    /// 以当前局部变换为静止姿态计算骨骼高度（模型空间 Y 方向跨度）
+    pub fn rest_height(&self) -> f32 {
+        let mut world = Vec::with_capacity(self.bones.len());
+        let (mut min_y, mut max_y) = (f32::MAX, f32::MIN);
+        for bone in &self.bones {
+            let local = bone.local_transform.to_matrix();
+            let matrix = match bone.parent_index {
+                Some(parent) if parent < world.len() => world[parent] * local,
+                _ => local,
+            };
+            let y = matrix.w_axis.y;
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+            world.push(matrix);
+        }
+        if self.bones.is_empty() {
+            0.0
+        } else {
+            max_y - min_y
+        }
+    }
+
     /// 更新 GPU 缓冲区
     pub fn update_gpu_buffer(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
         if !self.dirty {
@@ -257,6 +280,40 @@ impl SkeletonPose {
         }
     }
 
+    /// 将源姿态重定向到目标骨骼
+    ///
+    /// 映射骨骼的旋转按相对源静止姿态的增量叠加到目标静止姿态上；
+    /// 启用 `root_translation` 时根骨骼位移按身高比例缩放后迁移。
+    /// 未映射的目标骨骼保持静止姿态。
+    pub fn retarget(source_pose: &SkeletonPose, map: &RetargetMap, target: &Skeleton) -> Self {
+        let mut pose = Self::from_skeleton(target);
+        let height_ratio = if map.source_height > f32::EPSILON {
+            target.rest_height() / map.source_height
+        } else {
+            1.0
+        };
+
+        for bone in &map.bones {
+            let Some(source) = source_pose.bone_transforms.get(bone.source_index) else {
+                continue;
+            };
+            let Some(target_index) = target.get_bone_index(&bone.target_name) else {
+                continue;
+            };
+
+            let rest = &mut pose.bone_transforms[target_index];
+            let delta = bone.source_rest.rotation.inverse() * source.rotation;
+            rest.rotation = (rest.rotation * delta).normalize();
+
+            if map.root_translation && target.bones[target_index].parent_index.is_none() {
+                let offset = source.translation - bone.source_rest.translation;
+                rest.translation += offset * height_ratio;
+            }
+        }
+
+        pose
+    }
+
     /// 应用姿态到骨骼
     pub fn apply_to_skeleton(&self, skeleton: &mut Skeleton) {
         for (i, transform) in self.bone_transforms.iter().enumerate() {
@@ -268,6 +325,71 @@ impl SkeletonPose {
     }
 }
 
+// ============================================================================
+// 骨骼重定向（Retarget）
+// ============================================================================
+
+/// 单根骨骼的重定向映射
+#[derive(Clone, Debug)]
+struct RetargetBone {
+    source_index: usize,
+    source_rest: BoneTransform,
+    target_name: String,
+}
+
+/// 骨骼重定向映射 - 将源骨骼的动画迁移到比例或骨骼数量不同的目标骨骼
+///
+/// 以源骨骼当前的局部变换作为静止姿态，映射时按名称解析源骨骼索引。
+#[derive(Clone, Debug)]
+pub struct RetargetMap {
+    bones: Vec<RetargetBone>,
+    source_names: HashMap<String, usize>,
+    source_rest: Vec<BoneTransform>,
+    source_height: f32,
+    /// 是否迁移根骨骼位移（按目标与源的身高比例缩放）
+    pub root_translation: bool,
+}
+
+impl RetargetMap {
+    pub fn new(source: &Skeleton) -> Self {
+        Self {
+            bones: Vec::new(),
+            source_names: source.bone_name_to_index.clone(),
+            source_rest: source.bones.iter().map(|b| b.local_transform).collect(),
+            source_height: source.rest_height(),
+            root_translation: false,
+        }
+    }
+
+    /// 添加源骨骼名到目标骨骼名的映射，源骨骼不存在时忽略
+    pub fn map_bone(&mut self, source: &str, target: impl Into<String>) -> &mut Self {
+        if let Some(&source_index) = self.source_names.get(source) {
+            self.bones.push(RetargetBone {
+                source_index,
+                source_rest: self.source_rest[source_index],
+                target_name: target.into(),
+            });
+        }
+        self
+    }
+
+    /// 启用根骨骼位移迁移
+    pub fn with_root_translation(mut self, enabled: bool) -> Self {
+        self.root_translation = enabled;
+        self
+    }
+
+    /// 已映射的骨骼数量
+    pub fn len(&self) -> usize {
+        self.bones.len()
+    }
+
+    /// 是否没有任何映射
+    pub fn is_empty(&self) -> bool {
+        self.bones.is_empty()
+    }
+}
+
 // ============================================================================
 // GLTF 导入辅助
 // ============================================================================
@@ -302,14 +424,14 @@ pub fn build_skeleton_from_gltf(
     };
 
     // 构建骨骼节点索引映射 (gltf node index -> skeleton bone index)
-    let joint_to_index: std::collections::HashMap<usize, usize> = joints
+    let joint_to_index: HashMap<usize, usize> = joints
         .iter()
         .enumerate()
         .map(|(i, joint)| (joint.index(), i))
         .collect();
 
     // 构建父子关系映射 (通过遍历节点的 children)
-    let mut parent_map: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
+    let mut parent_map: HashMap<usize, usize> = HashMap::new();
     for joint in &joints {
         for child in joint.children() {
             // 如果 child 也是骨骼节点，记录父子关系
@@ -481,4 +603,53 @@ mod tests {
         let lerped = pose1.lerp(&pose2, 0.5);
         assert!((lerped.bone_transforms[0].translation.x - 5.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_retarget_to_larger_skeleton() {
+        let mut source_bones = vec![
+            Bone::new("hips", None),
+            Bone::new("spine", Some(0)),
+            Bone::new("head", Some(1)),
+        ];
+        source_bones[1].local_transform.translation = Vec3::Y;
+        source_bones[2].local_transform.translation = Vec3::Y;
+        let source = Skeleton::new(source_bones);
+
+        // 目标骨骼更高且多出两根骨骼
+        let mut target_bones = vec![
+            Bone::new("Hips", None),
+            Bone::new("Spine", Some(0)),
+            Bone::new("Chest", Some(1)),
+            Bone::new("Neck", Some(2)),
+            Bone::new("Head", Some(3)),
+        ];
+        for bone in target_bones.iter_mut().skip(1) {
+            bone.local_transform.translation = Vec3::Y;
+        }
+        let target = Skeleton::new(target_bones);
+        assert_eq!(target.rest_height(), 2.0 * source.rest_height());
+
+        let mut map = RetargetMap::new(&source).with_root_translation(true);
+        map.map_bone("hips", "Hips").map_bone("spine", "Spine");
+        map.map_bone("missing", "Head");
+        assert_eq!(map.len(), 2);
+
+        let rotation = Quat::from_rotation_z(0.5);
+        let mut source_pose = SkeletonPose::from_skeleton(&source);
+        source_pose.bone_transforms[1].rotation = rotation;
+        source_pose.bone_transforms[0].translation = Vec3::new(0.0, 0.0, 1.0);
+
+        let pose = SkeletonPose::retarget(&source_pose, &map, &target);
+        assert_eq!(pose.bone_transforms.len(), 5);
+
+        // 映射的骨骼跟随旋转，根位移按身高比例放大
+        assert!(pose.bone_transforms[1].rotation.angle_between(rotation) < 1e-5);
+        assert!((pose.bone_transforms[0].translation - Vec3::new(0.0, 0.0, 2.0)).length() < 1e-5);
+
+        // 未映射的骨骼保持静止姿态
+        for transform in pose.bone_transforms.iter().skip(2) {
+            assert_eq!(transform.rotation, Quat::IDENTITY);
+            assert_eq!(transform.translation, Vec3::Y);
+        }
+    }
 }
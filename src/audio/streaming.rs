@@ -78,6 +78,8 @@ pub struct StreamConfig {
     pub sample_rate: Option<u32>,
     /// 声道数
     pub channels: Option<u16>,
+    /// Web平台每次Range请求下载的字节数
+    pub chunk_bytes: usize,
 }
 
 impl_default!(StreamConfig {
@@ -86,6 +88,7 @@ impl_default!(StreamConfig {
     looped: false,
     sample_rate: None,
     channels: None,
+    chunk_bytes: 256 * 1024,
 });
 
 /// 音频缓冲区
@@ -144,6 +147,44 @@ impl AudioBuffer {
     }
 }
 
+/// WAV文件中PCM数据的起始偏移
+///
+/// 流式播放只支持16位PCM的WAV文件，其他格式（非RIFF/WAVE文件、压缩编码或其他位深）
+/// 按原始字节解码会变成噪声，因此直接拒绝。
+#[cfg(any(target_arch = "wasm32", test))]
+fn wav_data_offset(bytes: &[u8]) -> Result<usize, StreamingError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(StreamingError::DecodeError(
+            "Unsupported audio format: expected a RIFF/WAVE file".to_string(),
+        ));
+    }
+    let read_u16 = |pos: usize| u16::from_le_bytes([bytes[pos], bytes[pos + 1]]);
+    let mut pcm16 = false;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let size = u32::from_le_bytes([
+            bytes[pos + 4],
+            bytes[pos + 5],
+            bytes[pos + 6],
+            bytes[pos + 7],
+        ]) as usize;
+        match &bytes[pos..pos + 4] {
+            b"fmt " if pos + 24 <= bytes.len() => {
+                // 格式标签 1 为PCM，位深位于 fmt 块的第14字节
+                pcm16 = read_u16(pos + 8) == 1 && read_u16(pos + 22) == 16;
+            }
+            b"data" if pcm16 => return Ok(pos + 8),
+            b"data" => break,
+            _ => {}
+        }
+        // 块按偶数字节对齐
+        pos += 8 + size + (size & 1);
+    }
+    Err(StreamingError::DecodeError(
+        "Unsupported WAV encoding: expected 16-bit PCM".to_string(),
+    ))
+}
+
 /// 音频流状态
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StreamState {
@@ -191,6 +232,20 @@ pub struct AudioStream {
     played_duration: f32,
     /// 解码器句柄（占位，实际应使用rodio或其他解码器）
     decoder_handle: Option<()>,
+    /// 已下载到的源文件字节偏移（Web平台按Range分块下载）
+    #[cfg(target_arch = "wasm32")]
+    source_offset: u64,
+    /// 源文件是否已下载完毕
+    #[cfg(target_arch = "wasm32")]
+    source_exhausted: bool,
+    /// 服务器忽略Range时返回的完整文件，之后的分块直接从中截取
+    #[cfg(target_arch = "wasm32")]
+    source_cache: Option<Vec<u8>>,
+    /// 是否有分块正在下载
+    #[cfg(target_arch = "wasm32")]
+    fetch_in_flight: bool,
+    /// 已下载但尚未解码的字节（16位小端PCM），由 `fill_buffer` 消费
+    pending_bytes: Vec<u8>,
 }
 
 impl AudioStream {
@@ -209,6 +264,15 @@ impl AudioStream {
             total_duration: None,
             played_duration: 0.0,
             decoder_handle: None,
+            #[cfg(target_arch = "wasm32")]
+            source_offset: 0,
+            #[cfg(target_arch = "wasm32")]
+            source_exhausted: false,
+            #[cfg(target_arch = "wasm32")]
+            source_cache: None,
+            #[cfg(target_arch = "wasm32")]
+            fetch_in_flight: false,
+            pending_bytes: Vec::new(),
         }
     }
 
@@ -243,7 +307,7 @@ impl AudioStream {
         // 检查是否需要填充缓冲区
         let buffers_to_fill = self.buffers.iter().filter(|b| !b.filled).count();
 
-        if buffers_to_fill < self.config.preload_buffers {
+        if buffers_to_fill > 0 {
             // 填充空缓冲区
             for i in 0..self.buffers.len() {
                 if !self.buffers[i].filled {
@@ -265,26 +329,165 @@ impl AudioStream {
         Ok(())
     }
 
+    /// 下载源文件的下一个分块（Web平台）
+    ///
+    /// 通过HTTP Range请求只下载 `chunk_bytes` 大小的数据，避免一次性拉取整个大文件。
+    /// 服务器忽略Range返回完整文件时缓存该文件，之后的分块不再发起请求。
+    /// 下载期间不持有流的锁，由 `AudioStreamLoader::update_all` 在待解码数据不足时发起。
+    /// 返回本次得到的字节数；返回的数据少于请求长度时视为已到文件末尾。
+    #[cfg(target_arch = "wasm32")]
+    pub async fn fetch_next_chunk(
+        stream: Arc<Mutex<AudioStream>>,
+        fs: Arc<crate::platform::web_fs::WebFilesystem>,
+    ) -> Result<usize, StreamingError> {
+        use crate::platform::http_range::{ByteRange, RangeResponse};
+
+        fn lock(
+            stream: &Mutex<AudioStream>,
+        ) -> Result<std::sync::MutexGuard<'_, AudioStream>, StreamingError> {
+            stream
+                .lock()
+                .map_err(|_| StreamingError::IoError("Audio stream lock poisoned".to_string()))
+        }
+
+        let (range, url, cached) = {
+            let mut s = lock(&stream)?;
+            let start = s.source_offset;
+            let range = match ByteRange::new(start, start + s.config.chunk_bytes as u64) {
+                Ok(range) if !s.source_exhausted => range,
+                Ok(_) => {
+                    s.fetch_in_flight = false;
+                    return Err(StreamingError::StreamEnded);
+                }
+                Err(e) => {
+                    s.fetch_in_flight = false;
+                    return Err(StreamingError::IoError(e.to_string()));
+                }
+            };
+            s.fetch_in_flight = true;
+            let cached = s
+                .source_cache
+                .as_ref()
+                .map(|full| range.slice(full).to_vec());
+            (range, s.path.to_string_lossy().into_owned(), cached)
+        };
+
+        let fetched = match cached {
+            Some(bytes) => Ok((bytes, None)),
+            None => fs
+                .read_range(&url, range.start, range.end)
+                .await
+                .map(|response| {
+                    let bytes = response.clone().into_range(&range);
+                    match response {
+                        RangeResponse::Full(full) => (bytes, Some(full)),
+                        RangeResponse::Partial(_) => (bytes, None),
+                    }
+                })
+                .map_err(|e| StreamingError::IoError(e.to_string())),
+        };
+
+        let mut s = lock(&stream)?;
+        s.fetch_in_flight = false;
+        let (bytes, full) = match fetched {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                s.state = StreamState::Error(e.to_string());
+                return Err(e);
+            }
+        };
+        if full.is_some() {
+            s.source_cache = full;
+        }
+
+        let len = bytes.len();
+        s.source_offset += len as u64;
+        if (len as u64) < range.len() {
+            s.source_exhausted = true;
+            s.source_cache = None;
+        }
+        let data_start = if range.start == 0 {
+            match wav_data_offset(&bytes) {
+                Ok(offset) => offset,
+                Err(e) => {
+                    s.source_exhausted = true;
+                    s.source_cache = None;
+                    s.state = StreamState::Error(e.to_string());
+                    return Err(e);
+                }
+            }
+        } else {
+            0
+        };
+        s.push_source_bytes(&bytes[data_start..]);
+
+        Ok(len)
+    }
+
+    /// 是否需要下载下一个分块：待解码数据不足一个缓冲区且没有正在进行的下载
+    #[cfg(target_arch = "wasm32")]
+    fn needs_source_chunk(&self) -> bool {
+        let low_water = self.config.buffer_size * self.channels as usize * 2;
+        !self.source_exhausted
+            && !self.fetch_in_flight
+            && !matches!(self.state, StreamState::Error(_))
+            && self.pending_bytes.len() < low_water
+    }
+
+    /// 追加待解码的源数据（16位小端PCM，不含文件头）
+    ///
+    /// Web平台由 `fetch_next_chunk` 调用；数据在 `update` 填充缓冲区时消费。
+    pub fn push_source_bytes(&mut self, bytes: &[u8]) {
+        self.pending_bytes.extend_from_slice(bytes);
+    }
+
+    /// 是否不会再有新的源数据
+    ///
+    /// 非Web平台尚未接入解码器，视为没有后续数据。
+    fn source_complete(&self) -> bool {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.source_exhausted
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            true
+        }
+    }
+
     /// 填充单个缓冲区
+    ///
+    /// 优先消费已下载的PCM数据；数据不足一个缓冲区且源文件尚未下载完时
+    /// 保持未填充，等待下一个分块。
     fn fill_buffer(&mut self, buffer_index: usize) -> Result<(), StreamingError> {
-        // NOTE: 实际实现中需要：
-        // 1. 从解码器读取样本
-        // 2. 转换为f32格式
-        // 3. 填充到缓冲区
-
         if buffer_index >= self.buffers.len() {
             return Err(StreamingError::BufferOverflow);
         }
 
-        let buffer = &mut self.buffers[buffer_index];
+        let wanted_bytes = self.buffers[buffer_index].data.len() * 2;
+        let source_complete = self.source_complete();
+        if self.pending_bytes.len() < wanted_bytes && !source_complete {
+            return Ok(());
+        }
 
-        // 占位实现：填充零数据
-        let sample_count = buffer.sample_count();
-        let data_size = sample_count * buffer.channels as usize;
-        let zero_data = vec![0.0; data_size];
-        buffer.fill(&zero_data)?;
+        let buffer = &mut self.buffers[buffer_index];
+        if self.pending_bytes.is_empty() {
+            // 没有源数据时填充静音（占位，本地文件解码尚未接入）
+            let zero_data = vec![0.0; buffer.data.len()];
+            return buffer.fill(&zero_data);
+        }
 
-        Ok(())
+        // 只取完整的样本，不足一个样本的尾部字节留给下一个分块
+        let take = self.pending_bytes.len().min(wanted_bytes) & !1;
+        let samples: Vec<f32> = self
+            .pending_bytes
+            .drain(..take)
+            .collect::<Vec<u8>>()
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / i16::MAX as f32)
+            .collect();
+        buffer.clear();
+        buffer.fill(&samples)
     }
 
     /// 获取当前播放的样本数据
@@ -424,6 +627,9 @@ pub struct AudioStreamLoader {
     streams: HashMap<StreamId, Arc<Mutex<AudioStream>>>,
     /// 下一个流ID
     next_stream_id: u64,
+    /// 下载源文件分块使用的文件系统（Web平台）
+    #[cfg(target_arch = "wasm32")]
+    fs: Option<Arc<crate::platform::web_fs::WebFilesystem>>,
 }
 
 impl AudioStreamLoader {
@@ -431,6 +637,10 @@ impl AudioStreamLoader {
     pub fn new() -> Self {
         Self {
             next_stream_id: 1,
+            #[cfg(target_arch = "wasm32")]
+            fs: crate::platform::web_fs::WebFilesystem::new()
+                .ok()
+                .map(Arc::new),
             ..Default::default()
         }
     }
@@ -443,7 +653,8 @@ impl AudioStreamLoader {
     ) -> Result<StreamId, StreamingError> {
         let path = path.as_ref();
 
-        // 检查文件是否存在
+        // 检查文件是否存在（Web平台路径为URL，由分块下载时报告错误）
+        #[cfg(not(target_arch = "wasm32"))]
         if !path.exists() {
             return Err(StreamingError::FileNotFound(path.display().to_string()));
         }
@@ -473,10 +684,30 @@ impl AudioStreamLoader {
     }
 
     /// 更新所有流
+    ///
+    /// Web平台上待解码数据不足时在后台发起下一个分块的下载。
     pub fn update_all(&self) -> Result<(), StreamingError> {
         for stream in self.streams.values() {
             if let Ok(mut s) = stream.lock() {
                 s.update()?;
+
+                #[cfg(target_arch = "wasm32")]
+                if let Some(fs) = &self.fs {
+                    if s.needs_source_chunk() {
+                        // 先标记下载中，避免下载开始前的下一帧重复发起
+                        s.fetch_in_flight = true;
+                        let (stream, fs) = (stream.clone(), fs.clone());
+                        wasm_bindgen_futures::spawn_local(async move {
+                            if let Err(e) = AudioStream::fetch_next_chunk(stream, fs).await {
+                                tracing::warn!(
+                                    target: "audio",
+                                    "Audio stream chunk fetch failed: {}",
+                                    e
+                                );
+                            }
+                        });
+                    }
+                }
             }
         }
         Ok(())
@@ -524,6 +755,65 @@ mod tests {
         // 更新后应该变为Ready状态（如果缓冲区已填充）
     }
 
+    #[test]
+    fn test_stream_fills_buffers_from_pushed_pcm() {
+        let config = StreamConfig {
+            buffer_size: 2,
+            preload_buffers: 2,
+            channels: Some(1),
+            ..Default::default()
+        };
+        let mut stream = AudioStream::new(StreamId::new(1), PathBuf::from("test.wav"), config);
+        stream.initialize_decoder().unwrap();
+
+        let pcm: Vec<u8> = [i16::MAX, 0, -i16::MAX]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        stream.push_source_bytes(&pcm);
+        // 多出的奇数字节不足一个样本，留在待解码数据中
+        stream.push_source_bytes(&[0xff]);
+
+        stream.update().unwrap();
+        stream.update().unwrap();
+        assert_eq!(*stream.state(), StreamState::Ready);
+        stream.play().unwrap();
+
+        let samples = stream.get_samples(4).unwrap();
+        assert_eq!(samples, vec![1.0, 0.0, -1.0, 0.0]);
+        assert_eq!(stream.pending_bytes, vec![0xff]);
+    }
+
+    #[test]
+    fn test_wav_data_offset() {
+        let mut wav = b"RIFF\0\0\0\0WAVEfmt ".to_vec();
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&[0; 16]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&4u32.to_le_bytes());
+        wav.extend_from_slice(&[1, 2, 3, 4]);
+
+        // fmt 块：PCM、单声道、44100Hz、16位
+        wav[20..22].copy_from_slice(&1u16.to_le_bytes());
+        wav[34..36].copy_from_slice(&16u16.to_le_bytes());
+        assert_eq!(wav_data_offset(&wav).unwrap(), 44);
+
+        // 非RIFF数据（如MP3）不能按PCM播放
+        assert!(matches!(
+            wav_data_offset(&[0x49, 0x44, 0x33, 0x04, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(StreamingError::DecodeError(_))
+        ));
+        assert!(wav_data_offset(&[1, 2, 3, 4]).is_err());
+
+        // 8位PCM和压缩编码同样拒绝
+        let mut pcm8 = wav.clone();
+        pcm8[34..36].copy_from_slice(&8u16.to_le_bytes());
+        assert!(wav_data_offset(&pcm8).is_err());
+        let mut adpcm = wav.clone();
+        adpcm[20..22].copy_from_slice(&2u16.to_le_bytes());
+        assert!(wav_data_offset(&adpcm).is_err());
+    }
+
     #[test]
     fn test_stream_loader() {
         let mut loader = AudioStreamLoader::new();
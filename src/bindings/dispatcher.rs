@@ -0,0 +1,284 @@
+//! Shared command handler
+//!
+//! Applies language-agnostic `BindingCommand`s to the ECS `World`, so every
+//! binding adapter (JS, Python, ...) gets identical entity semantics.
+
+use super::protocol::{
+    BindingCommand, BindingEvent, BindingResult, BindingValue, ComponentData, ComponentKind,
+};
+use crate::ecs::{Sprite, Transform, Velocity};
+use bevy_ecs::prelude::*;
+use glam::{Quat, Vec3};
+
+/// Applies binding commands to the world and collects the resulting events
+#[derive(Default)]
+pub struct WorldCommandHandler {
+    events: Vec<BindingEvent>,
+}
+
+impl WorldCommandHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a single command to the world
+    pub fn apply(&mut self, world: &mut World, cmd: BindingCommand) -> BindingResult {
+        match cmd {
+            BindingCommand::SpawnEntity { components } => self.spawn(world, components),
+            BindingCommand::DespawnEntity { entity_id } => match entity_from_id(entity_id) {
+                Some(entity) if world.despawn(entity) => BindingResult::Success,
+                _ => BindingResult::Error(format!("Entity {} not found", entity_id)),
+            },
+            BindingCommand::QueryComponent {
+                entity_id,
+                component,
+            } => self.query(world, entity_id, component),
+            _ => BindingResult::Error("Command not supported by world handler".to_string()),
+        }
+    }
+
+    /// Apply a batch of commands, returning one result per command
+    pub fn apply_all(
+        &mut self,
+        world: &mut World,
+        commands: Vec<BindingCommand>,
+    ) -> Vec<BindingResult> {
+        commands
+            .into_iter()
+            .map(|cmd| self.apply(world, cmd))
+            .collect()
+    }
+
+    /// Take the events produced since the last drain
+    pub fn drain_events(&mut self) -> Vec<BindingEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn spawn(&mut self, world: &mut World, components: Vec<ComponentData>) -> BindingResult {
+        // Validate everything before touching the world so a bad component
+        // doesn't leave a half-built entity behind
+        let mut transform = None;
+        let mut sprite = None;
+        for component in components {
+            match component {
+                ComponentData::Transform {
+                    position,
+                    rotation,
+                    scale,
+                } => {
+                    transform = Some(Transform {
+                        pos: Vec3::from_array(position),
+                        rot: Quat::from_array(rotation),
+                        scale: Vec3::from_array(scale),
+                    });
+                }
+                ComponentData::Sprite {
+                    color,
+                    texture_id,
+                    uv_offset,
+                    uv_scale,
+                } => {
+                    sprite = Some(Sprite {
+                        color,
+                        tex_index: texture_id,
+                        uv_off: uv_offset,
+                        uv_scale,
+                        ..Default::default()
+                    });
+                }
+                other => {
+                    return BindingResult::Error(format!(
+                        "Unsupported component for spawn: {:?}",
+                        other
+                    ));
+                }
+            }
+        }
+
+        let mut entity = world.spawn_empty();
+        if let Some(transform) = transform {
+            entity.insert(transform);
+        }
+        if let Some(sprite) = sprite {
+            entity.insert(sprite);
+        }
+
+        let entity_id = entity.id().to_bits();
+        self.events.push(BindingEvent::EntitySpawned { entity_id });
+        BindingResult::EntityId(entity_id)
+    }
+
+    fn query(&mut self, world: &World, entity_id: u64, component: ComponentKind) -> BindingResult {
+        match read_component(world, entity_id, component) {
+            Ok(value) => {
+                let json = serde_json::to_string(&value).unwrap_or_default();
+                self.events.push(BindingEvent::ComponentValue {
+                    entity_id,
+                    component,
+                    value,
+                });
+                BindingResult::Value(json)
+            }
+            Err(reason) => {
+                self.events.push(BindingEvent::QueryFailed {
+                    entity_id,
+                    component,
+                    reason: reason.clone(),
+                });
+                BindingResult::Error(reason)
+            }
+        }
+    }
+}
+
+fn read_component(
+    world: &World,
+    entity_id: u64,
+    component: ComponentKind,
+) -> Result<BindingValue, String> {
+    let entity = entity_from_id(entity_id)
+        .and_then(|entity| world.get_entity(entity))
+        .ok_or_else(|| format!("Entity {} not found", entity_id))?;
+    let missing = || format!("Entity {} has no {:?} component", entity_id, component);
+
+    match component {
+        ComponentKind::Transform => {
+            let t = entity.get::<Transform>().ok_or_else(missing)?;
+            Ok(BindingValue::Transform {
+                position: t.pos.to_array(),
+                rotation: t.rot.to_array(),
+                scale: t.scale.to_array(),
+            })
+        }
+        ComponentKind::SpriteColor => {
+            let sprite = entity.get::<Sprite>().ok_or_else(missing)?;
+            Ok(BindingValue::Color(sprite.color))
+        }
+        ComponentKind::Velocity => {
+            let v = entity.get::<Velocity>().ok_or_else(missing)?;
+            Ok(BindingValue::Velocity {
+                linear: v.lin.to_array(),
+                angular: v.ang.to_array(),
+            })
+        }
+    }
+}
+
+/// Convert a protocol entity id back into an ECS entity
+pub fn entity_from_id(entity_id: u64) -> Option<Entity> {
+    Entity::try_from_bits(entity_id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_entity_emits_event() {
+        let mut world = World::new();
+        let mut handler = WorldCommandHandler::new();
+
+        let result = handler.apply(
+            &mut world,
+            BindingCommand::SpawnEntity {
+                components: vec![ComponentData::Transform {
+                    position: [1.0, 2.0, 3.0],
+                    rotation: [0.0, 0.0, 0.0, 1.0],
+                    scale: [1.0, 1.0, 1.0],
+                }],
+            },
+        );
+        let BindingResult::EntityId(id) = result else {
+            panic!("Expected EntityId result, got {:?}", result);
+        };
+
+        let events = handler.drain_events();
+        assert!(matches!(
+            events.as_slice(),
+            [BindingEvent::EntitySpawned { entity_id }] if *entity_id == id
+        ));
+
+        let entity = entity_from_id(id).unwrap();
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.pos, Vec3::new(1.0, 2.0, 3.0));
+        assert!(world.get::<Sprite>(entity).is_none());
+
+        let result = handler.apply(&mut world, BindingCommand::DespawnEntity { entity_id: id });
+        assert!(matches!(result, BindingResult::Success));
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    fn test_query_component_returns_position() {
+        let mut world = World::new();
+        let mut handler = WorldCommandHandler::new();
+
+        let BindingResult::EntityId(id) = handler.apply(
+            &mut world,
+            BindingCommand::SpawnEntity {
+                components: vec![ComponentData::Transform {
+                    position: [4.0, 5.0, 6.0],
+                    rotation: [0.0, 0.0, 0.0, 1.0],
+                    scale: [1.0, 1.0, 1.0],
+                }],
+            },
+        ) else {
+            panic!("Expected EntityId result");
+        };
+        handler.drain_events();
+
+        handler.apply(
+            &mut world,
+            BindingCommand::QueryComponent {
+                entity_id: id,
+                component: ComponentKind::Transform,
+            },
+        );
+        match handler.drain_events().as_slice() {
+            [BindingEvent::ComponentValue {
+                entity_id,
+                value: BindingValue::Transform { position, .. },
+                ..
+            }] => {
+                assert_eq!(*entity_id, id);
+                assert_eq!(*position, [4.0, 5.0, 6.0]);
+            }
+            other => panic!("Expected ComponentValue event, got {:?}", other),
+        }
+
+        // A missing component reports QueryFailed
+        handler.apply(
+            &mut world,
+            BindingCommand::QueryComponent {
+                entity_id: id,
+                component: ComponentKind::Velocity,
+            },
+        );
+        assert!(matches!(
+            handler.drain_events().as_slice(),
+            [BindingEvent::QueryFailed {
+                component: ComponentKind::Velocity,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_spawn_rejects_unsupported_component() {
+        let mut world = World::new();
+        let mut handler = WorldCommandHandler::new();
+
+        let result = handler.apply(
+            &mut world,
+            BindingCommand::SpawnEntity {
+                components: vec![ComponentData::Script {
+                    source: "main.js".to_string(),
+                }],
+            },
+        );
+
+        assert!(matches!(result, BindingResult::Error(_)));
+        assert!(handler.drain_events().is_empty());
+        assert_eq!(world.entities().len(), 0);
+    }
+}
@@ -3,7 +3,11 @@
 //! This adapter provides JavaScript scripting support using QuickJS.
 
 use crate::impl_default;
-use super::protocol::{BindingAdapter, BindingCommand, BindingEvent, BindingResult, ComponentData};
+use super::dispatcher::WorldCommandHandler;
+use super::protocol::{
+    BindingAdapter, BindingCommand, BindingEvent, BindingResult, ComponentData, ComponentKind,
+};
+use bevy_ecs::world::World;
 use rquickjs::{Context, Function, Object, Runtime, Value};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
@@ -98,6 +102,24 @@ impl JsBindingAdapter {
                 )
                 .unwrap();
 
+            // Engine.queryComponent(entity_id, kind) - value arrives via ComponentValue event
+            let q = Arc::clone(&queue);
+            engine_obj
+                .set(
+                    "queryComponent",
+                    Function::new(ctx.clone(), move |entity_id: u64, kind: String| {
+                        if let Ok(component) =
+                            serde_json::from_value::<ComponentKind>(serde_json::Value::String(kind))
+                        {
+                            q.lock().unwrap().push(BindingCommand::QueryComponent {
+                                entity_id,
+                                component,
+                            });
+                        }
+                    }),
+                )
+                .unwrap();
+
             // Engine.setPosition(entity_id, x, y, z)
             let q = Arc::clone(&queue);
             engine_obj
@@ -183,6 +205,21 @@ impl JsBindingAdapter {
             .with(|ctx| ctx.eval::<(), _>(code).map_err(|e| format!("{:?}", e)))
     }
 
+    /// Apply queued script commands to the world and notify scripts of the
+    /// resulting events (e.g. `EntitySpawned` with the new entity id)
+    pub fn process_commands(
+        &mut self,
+        world: &mut World,
+        handler: &mut WorldCommandHandler,
+    ) -> Vec<BindingResult> {
+        let commands = self.poll_commands();
+        let results = handler.apply_all(world, commands);
+        for event in handler.drain_events() {
+            self.dispatch_event(event);
+        }
+        results
+    }
+
     pub fn call_function(&self, name: &str, args_json: &str) -> Result<String, String> {
         self.context.with(|ctx| {
             let global = ctx.globals();
@@ -275,4 +312,28 @@ mod tests {
             _ => panic!("Expected PlaySound command"),
         }
     }
+
+    #[test]
+    fn test_js_spawn_entity() {
+        let mut adapter = JsBindingAdapter::new();
+        adapter.init();
+        let mut world = World::new();
+        let mut handler = WorldCommandHandler::new();
+
+        let _ = adapter.execute_script(
+            r#"
+            var spawned = null;
+            function __onEngineEvent(e) {
+                if (e.EntitySpawned) spawned = e.EntitySpawned.entity_id;
+            }
+            Engine.spawn('[{"Transform":{"position":[1,2,3],"rotation":[0,0,0,1],"scale":[1,1,1]}}]');
+            "#,
+        );
+
+        let results = adapter.process_commands(&mut world, &mut handler);
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], BindingResult::EntityId(_)));
+        assert_eq!(world.entities().len(), 1);
+        assert!(adapter.execute_script("if (spawned === null) throw 'no event';").is_ok());
+    }
 }
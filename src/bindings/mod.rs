@@ -30,7 +30,10 @@
 //! └─────────────────────────────────────────────────────────────┘
 //! ```
 
+pub mod dispatcher;
+#[cfg(feature = "js_scripting")]
 pub mod js;
 pub mod protocol;
 
+pub use dispatcher::WorldCommandHandler;
 pub use protocol::*;
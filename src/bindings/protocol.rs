@@ -29,6 +29,10 @@ pub enum BindingCommand {
         entity_id: u64,
         component_type: String,
     },
+    QueryComponent {
+        entity_id: u64,
+        component: ComponentKind,
+    },
 
     // Transform
     SetPosition {
@@ -111,6 +115,11 @@ pub enum BindingEvent {
         entity_id: u64,
     },
 
+    // Entity Management
+    EntitySpawned {
+        entity_id: u64,
+    },
+
     // Physics
     OnCollisionEnter {
         entity_a: u64,
@@ -170,6 +179,16 @@ pub enum BindingEvent {
         key: String,
         pressed: bool,
     },
+    ComponentValue {
+        entity_id: u64,
+        component: ComponentKind,
+        value: BindingValue,
+    },
+    QueryFailed {
+        entity_id: u64,
+        component: ComponentKind,
+        reason: String,
+    },
 
     // Custom
     Custom {
@@ -218,6 +237,29 @@ pub enum ComponentData {
     },
 }
 
+/// Component kinds that can be read back with `QueryComponent`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComponentKind {
+    Transform,
+    SpriteColor,
+    Velocity,
+}
+
+/// Component values returned to scripts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BindingValue {
+    Transform {
+        position: [f32; 3],
+        rotation: [f32; 4],
+        scale: [f32; 3],
+    },
+    Color([f32; 4]),
+    Velocity {
+        linear: [f32; 3],
+        angular: [f32; 3],
+    },
+}
+
 /// Result type for binding operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BindingResult {
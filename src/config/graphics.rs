@@ -263,6 +263,8 @@ pub enum TextureCompressionFormat {
     BC1,
     /// BC3/DXT5
     BC3,
+    /// BC5（双通道，法线贴图）
+    BC5,
     /// BC7
     BC7,
     /// ETC2
@@ -279,6 +281,7 @@ impl From<TextureCompressionFormat>
             TextureCompressionFormat::Astc8x8 => Self::Astc8x8,
             TextureCompressionFormat::BC1 => Self::BC1,
             TextureCompressionFormat::BC3 => Self::BC3,
+            TextureCompressionFormat::BC5 => Self::BC5,
             TextureCompressionFormat::BC7 => Self::BC7,
             TextureCompressionFormat::ETC2 => Self::ETC2,
         }
@@ -295,6 +298,7 @@ impl From<crate::render::texture_compression::CompressedTextureFormat>
             crate::render::texture_compression::CompressedTextureFormat::Astc8x8 => Self::Astc8x8,
             crate::render::texture_compression::CompressedTextureFormat::BC1 => Self::BC1,
             crate::render::texture_compression::CompressedTextureFormat::BC3 => Self::BC3,
+            crate::render::texture_compression::CompressedTextureFormat::BC5 => Self::BC5,
             crate::render::texture_compression::CompressedTextureFormat::BC7 => Self::BC7,
             crate::render::texture_compression::CompressedTextureFormat::ETC2 => Self::ETC2,
         }
@@ -8,6 +8,8 @@ use crate::domain::actor::{
 };
 use crate::ecs::{PointLight, PreviousTransform, Sprite, Time, Transform};
 use crate::editor::{inspect_world_ui, EditorContext};
+use crate::impl_default;
+use crate::network::{network_update_system, NetworkState};
 #[cfg(feature = "physics_2d")]
 use crate::physics::physics3d::{
     init_physics_bodies_3d, physics_step_system_3d, sync_physics_to_transform_system_3d,
@@ -22,18 +24,20 @@ use crate::platform::winit::WinitWindow;
 use crate::platform::Window;
 use crate::platform::{InputBuffer, InputEvent, KeyCode, Modifiers, MouseButton};
 use crate::render::wgpu::{GpuPointLight, WgpuRenderer};
-use crate::resources::manager::{AssetEvent, AssetServer};
+use crate::resources::manager::{
+    material_changed_system, AssetEvent, AssetServer, MaterialChanged,
+};
 use crate::scripting::{setup_scripting, Script};
 use crate::services::audio::start_audio_driver;
 use crate::services::render::RenderService;
 use bevy_ecs::prelude::*;
 use glam::{Quat, Vec3};
 use winit::event::{Event, WindowEvent};
-use winit::event_loop::EventLoop;
+use winit::event_loop::{EventLoop, EventLoopBuilder};
 
 use super::error::{EngineError, EngineResult};
 use super::error_aggregator::ErrorAggregator;
-use super::resources::{AssetMetrics, Benchmark, LogEvents, RenderStats};
+use super::resources::{AssetMetrics, Benchmark, Headless, LogEvents, RenderStats};
 use super::systems::{
     apply_texture_handles, audio_input_system, rotate_system, save_previous_transform_system,
 };
@@ -64,13 +68,73 @@ use super::systems::{
 /// 3. **关闭阶段**：清理资源，关闭子系统
 pub struct Engine;
 
+/// 无头模式配置
+///
+/// 无头模式不创建窗口、表面和渲染器，适用于专用服务器、CI 和离线模拟。
+#[derive(Debug, Clone)]
+pub struct HeadlessConfig {
+    /// 每个tick推进的时间（秒），同时作为固定时间步长
+    pub tick_seconds: f64,
+    /// 最多运行的tick数，`None` 表示一直运行
+    pub max_ticks: Option<u64>,
+    /// 是否按真实时间节拍运行；关闭时尽快连续执行
+    pub realtime: bool,
+    /// 逻辑视口尺寸，供依赖 `Viewport` 的系统使用
+    pub viewport: (u32, u32),
+}
+
+impl_default!(HeadlessConfig {
+    tick_seconds: 1.0 / 60.0,
+    max_ticks: None,
+    realtime: true,
+    viewport: (800, 600),
+});
+
+/// `run_frames` 回读的最后一帧
+struct CapturedFrame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+static LAST_CAPTURE: std::sync::Mutex<Option<CapturedFrame>> = std::sync::Mutex::new(None);
+
 impl Engine {
     /// 运行引擎主循环
     pub fn run() -> EngineResult<()> {
+        Self::run_with_frame_limit(None)
+    }
+
+    /// 渲染指定帧数后退出主循环（仅原生平台）
+    ///
+    /// 用于自动化测试和截图。最后一帧在呈现前回读，
+    /// 退出后可通过 `capture_frame` 获取。
+    /// Linux/Windows 上事件循环允许在非主线程创建，以便在测试线程中运行。
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn run_frames(frames: u32) -> EngineResult<()> {
+        Self::run_with_frame_limit(Some(frames))
+    }
+
+    /// 读取 `run_frames` 最后一帧的像素
+    ///
+    /// 返回自上而下、紧密排列的 RGBA8 数据，尺寸见 `captured_frame_size`。
+    /// 尚未运行 `run_frames` 或交换链不支持回读时返回 `None`。
+    pub fn capture_frame() -> Option<Vec<u8>> {
+        let capture = LAST_CAPTURE.lock().ok()?;
+        capture.as_ref().map(|frame| frame.pixels.clone())
+    }
+
+    /// `capture_frame` 所返回帧的宽高
+    pub fn captured_frame_size() -> Option<(u32, u32)> {
+        let capture = LAST_CAPTURE.lock().ok()?;
+        capture.as_ref().map(|frame| (frame.width, frame.height))
+    }
+
+    fn run_with_frame_limit(frame_limit: Option<u32>) -> EngineResult<()> {
         Self::initialize_logging();
 
         let (event_loop, window, mut renderer, mut asset_server, mut editor_ctx) =
-            Self::initialize_window_and_renderer()?;
+            Self::initialize_window_and_renderer(frame_limit.is_some())?;
 
         let (
             mut world,
@@ -80,7 +144,7 @@ impl Engine {
             mut actor_system,
         ) = Self::initialize_ecs_and_actors(&mut renderer, &window)?;
 
-        Self::spawn_demo_scene(&mut world, &asset_server);
+        Self::spawn_demo_scene(&mut world, &mut asset_server);
 
         Self::run_event_loop(
             event_loop,
@@ -93,12 +157,109 @@ impl Engine {
             fixed_schedule,
             update_schedule,
             actor_system,
+            frame_limit,
         )?;
 
         tracing::info!(target: "engine", "Engine shutting down");
         Ok(())
     }
 
+    /// 以无头模式运行引擎
+    ///
+    /// 初始化ECS、物理、脚本和网络系统，不创建窗口和渲染器，
+    /// 按 `config.tick_seconds` 的固定节拍步进调度器。
+    ///
+    /// # 错误
+    ///
+    /// 如果 `tick_seconds` 不是有限正数，返回 `EngineError::Init`。
+    pub fn run_headless(config: HeadlessConfig) -> EngineResult<()> {
+        Self::initialize_logging();
+
+        if !config.tick_seconds.is_finite() || config.tick_seconds <= 0.0 {
+            return Err(EngineError::Init(format!(
+                "Headless tick_seconds must be positive and finite, got {}",
+                config.tick_seconds
+            )));
+        }
+
+        let (mut world, mut fixed_schedule, mut update_schedule) =
+            Self::initialize_headless(&config);
+        tracing::info!(target: "engine", "Running headless at {} s/tick", config.tick_seconds);
+
+        let tick = std::time::Duration::from_secs_f64(config.tick_seconds);
+        let mut accumulator = 0.0;
+        let mut ticks = 0u64;
+        let mut next_tick = std::time::Instant::now();
+        while config.max_ticks.map_or(true, |max| ticks < max) {
+            Self::tick_headless(
+                &mut world,
+                &mut fixed_schedule,
+                &mut update_schedule,
+                &mut accumulator,
+            );
+            ticks += 1;
+
+            if config.realtime {
+                next_tick += tick;
+                let now = std::time::Instant::now();
+                if next_tick > now {
+                    std::thread::sleep(next_tick - now);
+                } else {
+                    // 落后时不追赶，避免连续空转
+                    next_tick = now;
+                }
+            }
+        }
+
+        tracing::info!(target: "engine", "Headless engine stopped after {} ticks", ticks);
+        Ok(())
+    }
+
+    /// 初始化无头模式的ECS世界和调度器
+    ///
+    /// 插入 `Headless` 标记资源，渲染相关系统据此跳过。
+    pub fn initialize_headless(config: &HeadlessConfig) -> (World, Schedule, Schedule) {
+        let mut world = World::new();
+        world.insert_resource(Headless);
+        Self::setup_resources(&mut world, config.viewport);
+        if let Some(mut time) = world.get_resource_mut::<Time>() {
+            time.fixed_time_step = config.tick_seconds;
+        }
+        setup_scripting(&mut world, Default::default());
+        world.insert_resource(NetworkState::default());
+
+        (
+            world,
+            Self::create_fixed_schedule(),
+            Self::create_update_schedule(),
+        )
+    }
+
+    /// 执行一个无头tick
+    ///
+    /// 时间推进一个固定步长（受 `time_scale` 影响），
+    /// 然后依次运行固定时间步调度器和更新调度器。
+    pub fn tick_headless(
+        world: &mut World,
+        fixed_schedule: &mut Schedule,
+        update_schedule: &mut Schedule,
+        accumulator: &mut f64,
+    ) {
+        let scaled_delta = match world.get_resource_mut::<Time>() {
+            Some(mut time) => {
+                let step = time.fixed_time_step;
+                time.advance(step as f32);
+                // 直接用 f64 步长累积，避免 f32 舍入导致某个tick漏掉固定更新
+                step * time.time_scale.max(0.0)
+            }
+            None => 1.0 / 60.0,
+        };
+        *accumulator += scaled_delta;
+
+        Self::run_fixed_updates(world, fixed_schedule, accumulator, scaled_delta);
+        update_schedule.run(world);
+    }
+
     /// 初始化日志系统
     ///
     /// 配置tracing日志框架，设置环境变量过滤器。
@@ -113,6 +274,7 @@ impl Engine {
     /// 初始化窗口和渲染器
     ///
     /// 创建事件循环、窗口、wgpu渲染器和资源服务器。
+    /// `any_thread` 为真时允许在非主线程创建事件循环（Linux/Windows）。
     ///
     /// # 返回
     ///
@@ -121,14 +283,30 @@ impl Engine {
     /// # 错误
     ///
     /// 如果窗口创建失败或渲染器初始化失败，返回相应的错误。
-    fn initialize_window_and_renderer() -> EngineResult<(
+    fn initialize_window_and_renderer(
+        any_thread: bool,
+    ) -> EngineResult<(
         EventLoop<()>,
         WinitWindow,
         WgpuRenderer<'static>,
         AssetServer,
         EditorContext,
     )> {
-        let event_loop = EventLoop::new()
+        let mut builder = EventLoopBuilder::new();
+        #[cfg(target_os = "linux")]
+        if any_thread {
+            use winit::platform::x11::EventLoopBuilderExtX11;
+            builder.with_any_thread(true);
+        }
+        #[cfg(target_os = "windows")]
+        if any_thread {
+            use winit::platform::windows::EventLoopBuilderExtWindows;
+            builder.with_any_thread(true);
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        let _ = any_thread;
+        let event_loop = builder
+            .build()
             .map_err(|e| EngineError::EventLoop(format!("Failed to create event loop: {}", e)))?;
 
         let window = WinitWindow::try_new(&event_loop, (800, 600))
@@ -172,7 +350,13 @@ impl Engine {
         window: &WinitWindow,
     ) -> EngineResult<(World, RenderService, Schedule, Schedule, ActorSystem)> {
         let mut world = World::new();
-        Self::setup_resources(&mut world, renderer);
+        Self::setup_resources(
+            &mut world,
+            (renderer.config().width, renderer.config().height),
+        );
+        if let Some(audio_q) = start_audio_driver() {
+            world.insert_resource(audio_q);
+        }
         setup_scripting(&mut world, Default::default());
 
         let render_service = RenderService::new();
@@ -234,6 +418,7 @@ impl Engine {
     /// * `fixed_schedule` - 固定时间步调度器
     /// * `update_schedule` - 更新调度器
     /// * `actor_system` - Actor系统
+    /// * `frame_limit` - 渲染该帧数后退出，`None` 表示运行到窗口关闭
     ///
     /// # 错误
     ///
@@ -249,14 +434,23 @@ impl Engine {
         mut fixed_schedule: Schedule,
         mut update_schedule: Schedule,
         mut actor_system: ActorSystem,
+        frame_limit: Option<u32>,
     ) -> EngineResult<()> {
         let mut last_time = std::time::Instant::now();
         let mut accumulator = 0.0;
         let mut render_cache = crate::render::graph::RenderCache::new();
+        let mut frames_rendered = 0u32;
+        let limit_reached = move |frames: u32| frame_limit.is_some_and(|limit| frames >= limit);
 
         let result = event_loop.run(move |event, elwt| {
             match event {
                 Event::WindowEvent { event, .. } => {
+                    let is_redraw = matches!(event, WindowEvent::RedrawRequested);
+                    // 最后一帧呈现前回读交换链
+                    if is_redraw && limit_reached(frames_rendered + 1) {
+                        renderer.request_capture();
+                    }
+
                     let _ = editor_ctx.handle_event(window.raw(), &event);
                     Self::handle_window_event(
                         &event,
@@ -268,8 +462,20 @@ impl Engine {
                         &window,
                         elwt,
                     );
+
+                    if is_redraw {
+                        frames_rendered += 1;
+                        if limit_reached(frames_rendered) {
+                            Self::store_capture(&mut renderer);
+                            elwt.exit();
+                        }
+                    }
                 }
                 Event::AboutToWait => {
+                    if limit_reached(frames_rendered) {
+                        elwt.exit();
+                        return;
+                    }
                     // 更新循环：包括ECS系统更新和Actor消息处理
                     // Actor系统通过ECS系统（actor_message_system）异步处理消息
                     Self::update(
@@ -298,15 +504,30 @@ impl Engine {
         Ok(())
     }
 
+    /// 保存渲染器回读的帧，供 `capture_frame` 读取
+    fn store_capture(renderer: &mut WgpuRenderer) {
+        let width = renderer.config().width;
+        let height = renderer.config().height;
+        let frame = renderer.take_capture().map(|pixels| CapturedFrame {
+            width,
+            height,
+            pixels,
+        });
+        if let Ok(mut capture) = LAST_CAPTURE.lock() {
+            *capture = frame;
+        }
+    }
+
     /// 设置ECS资源
     ///
     /// 初始化引擎运行所需的所有ECS资源，包括时间、物理状态、输入缓冲区等。
+    /// 不依赖窗口和渲染器，窗口模式与无头模式共用。
     ///
     /// # 参数
     ///
     /// * `world` - ECS世界
-    /// * `renderer` - wgpu渲染器，用于获取视口配置
-    fn setup_resources(world: &mut World, renderer: &WgpuRenderer) {
+    /// * `viewport` - 初始视口尺寸
+    fn setup_resources(world: &mut World, viewport: (u32, u32)) {
         world.insert_resource(Time::default());
         #[cfg(feature = "physics_2d")]
         {
@@ -314,16 +535,13 @@ impl Engine {
             world.insert_resource(PhysicsWorld3D::default());
         }
         world.insert_resource(InputBuffer::default());
-        if let Some(audio_q) = start_audio_driver() {
-            world.insert_resource(audio_q);
-        }
         world.insert_resource(Benchmark {
             enabled: true,
             sprite_count: 0,
         });
         world.insert_resource(crate::ecs::Viewport {
-            width: renderer.config().width,
-            height: renderer.config().height,
+            width: viewport.0,
+            height: viewport.1,
         });
         world.insert_resource(AssetMetrics::default());
         world.insert_resource(crate::ecs::TileChunkConfig { size: [16, 16] });
@@ -336,6 +554,8 @@ impl Engine {
         world.insert_resource(crate::render::instance_batch::BatchManager::default());
         world.insert_resource(crate::render::instance_batch::BatchManager::default());
         world.insert_resource(crate::ecs::TileEntityPool::default());
+        world.insert_resource(crate::resources::manager::MaterialRegistry::default());
+        world.init_resource::<crate::ecs::Events<MaterialChanged>>();
         // 初始化错误聚合器
         world.insert_resource(ErrorAggregator::new());
     }
@@ -370,7 +590,7 @@ impl Engine {
     ///
     /// * `world` - ECS世界
     /// * `asset_server` - 资源服务器，用于加载纹理
-    fn spawn_demo_scene(world: &mut World, asset_server: &AssetServer) {
+    fn spawn_demo_scene(world: &mut World, asset_server: &mut AssetServer) {
         let atlas_path = std::path::Path::new("assets/atlas.png");
         let atlas_handle = asset_server.load_texture(atlas_path);
 
@@ -632,15 +852,28 @@ impl Engine {
     }
 
     /// 创建更新调度器
+    ///
+    /// 渲染相关系统在存在 `Headless` 资源时跳过，网络系统仅在存在 `NetworkState` 时运行。
     fn create_update_schedule() -> Schedule {
         let mut schedule = Schedule::default();
         schedule.add_systems(
             (
-                crate::render::instance_batch::batch_collection_system,
-                crate::render::instance_batch::batch_visibility_culling_system,
-                apply_texture_handles,
+                crate::ecs::propagate_transforms,
+                (
+                    crate::render::instance_batch::batch_collection_system,
+                    crate::render::instance_batch::batch_visibility_culling_system,
+                    apply_texture_handles,
+                )
+                    .chain()
+                    .run_if(not(resource_exists::<Headless>)),
+                network_update_system.run_if(resource_exists::<NetworkState>),
                 crate::ecs::flipbook_system,
                 crate::ecs::tilemap_chunk_system,
+                (
+                    crate::ecs::update_events_system::<MaterialChanged>,
+                    material_changed_system,
+                )
+                    .chain(),
                 audio_input_system,
                 crate::core::systems::actor::actor_message_system,
                 crate::core::systems::error_reporting::error_reporting_system,
@@ -668,6 +901,13 @@ impl Engine {
         let (egui_shapes, egui_renderer) = editor_ctx.end_frame(window.raw());
         let pixels_per_point = window.raw().scale_factor() as f32;
 
+        // 9-slice 精灵需要纹理尺寸换算边框UV
+        let texture_sizes = renderer.texture_sizes();
+        let mut synced = world.get_resource_or_insert_with(crate::ecs::TextureSizes::default);
+        if synced.0 != texture_sizes {
+            synced.0 = texture_sizes.to_vec();
+        }
+
         // Render with frustum culling
         let (layer_tree, culled, total) = crate::render::graph::build_from_world_culled(world);
         render_cache.culled_count = culled;
@@ -675,6 +915,18 @@ impl Engine {
         let _instances = render_cache.update(layer_tree);
         // 实例数量已由render_cache.update()内部记录
 
+        // Instanced 瓦片地图的chunk缓冲区
+        let mut tile_q = world.query::<(
+            Entity,
+            &crate::ecs::TileMap,
+            &crate::render::tilemap::TileChunkInstances,
+        )>();
+        let tile_maps: Vec<_> = tile_q
+            .iter(world)
+            .map(|(e, tm, chunks)| (e.to_bits(), chunks, tm.atlas_tex_index))
+            .collect();
+        renderer.sync_tile_chunks(&tile_maps);
+
         // Extract Lights
         let mut lights = Vec::new();
         let mut query = world.query::<(&Transform, &PointLight)>();
@@ -752,21 +1004,15 @@ impl Engine {
         } else {
             Vec::new()
         };
-        if !updates.is_empty() {
-            if let Some(mut reg) =
-                world.get_resource_mut::<crate::resources::manager::MaterialRegistry>()
-            {
-                if let Some(ref pbr) = renderer.pbr_renderer {
-                    for (id, mat) in updates {
-                        reg.update_material_params(
-                            renderer.device(),
-                            renderer.queue(),
-                            pbr,
-                            id,
-                            &mat,
-                        );
-                    }
+        if let Some(mut reg) =
+            world.get_resource_mut::<crate::resources::manager::MaterialRegistry>()
+        {
+            if let Some(ref pbr) = renderer.pbr_renderer {
+                for (id, mat) in updates {
+                    reg.update_material_params(renderer.device(), renderer.queue(), pbr, id, &mat);
                 }
+                // 命名材质接口修改的参数
+                reg.sync_dirty(renderer.device(), renderer.queue(), pbr);
             }
         }
 
@@ -810,6 +1056,11 @@ impl Engine {
             stats.ui_ms = ui;
             stats.offscreen_ms = renderer.offscreen_timing_ms();
 
+            let gpu_memory = crate::render::gpu_memory::GpuMemoryTracker::global().stats();
+            stats.vram_bytes_used = gpu_memory.bytes_used;
+            stats.buffer_count = gpu_memory.buffer_count;
+            stats.texture_count = gpu_memory.texture_count;
+
             // 性能警告
             if let Some(u) = stats.upload_ms {
                 if u > 2.0 {
@@ -887,31 +1138,19 @@ impl Engine {
         let delta = now.duration_since(*last_time).as_secs_f32();
         *last_time = now;
 
-        *accumulator += delta as f64;
-        let fixed_step = if let Some(time) = world.get_resource::<Time>() {
-            time.fixed_time_step
-        } else {
-            // 如果Time资源不存在，使用默认值
-            1.0 / 60.0 // 60 FPS
+        // 固定步长按缩放后的时间累积，time_scale 为 0 时物理等固定更新随之暂停
+        let scaled_delta = match world.get_resource_mut::<Time>() {
+            Some(mut time) => time.advance(delta),
+            // 如果Time资源不存在，不做缩放
+            None => delta as f64,
         };
+        *accumulator += scaled_delta;
 
         // 固定时间步更新
         let fixed_start = std::time::Instant::now();
-        while *accumulator >= fixed_step {
-            if let Some(mut time) = world.get_resource_mut::<Time>() {
-                time.delta_seconds = fixed_step as f32;
-                time.elapsed_seconds += fixed_step;
-            }
-            fixed_schedule.run(world);
-            *accumulator -= fixed_step;
-        }
+        Self::run_fixed_updates(world, fixed_schedule, accumulator, scaled_delta);
         let fixed_time = fixed_start.elapsed();
 
-        // 更新插值alpha
-        if let Some(mut time) = world.get_resource_mut::<Time>() {
-            time.alpha = *accumulator / fixed_step;
-        }
-
         // 可变时间步更新
         let update_schedule_start = std::time::Instant::now();
         update_schedule.run(world);
@@ -935,6 +1174,33 @@ impl Engine {
         }
     }
 
+    /// 消耗累积时间运行固定时间步调度器
+    ///
+    /// 结束后更新插值alpha，并为可变时间步恢复缩放后的帧间隔。
+    fn run_fixed_updates(
+        world: &mut World,
+        fixed_schedule: &mut Schedule,
+        accumulator: &mut f64,
+        scaled_delta: f64,
+    ) {
+        let fixed_step = world
+            .get_resource::<Time>()
+            .map_or(1.0 / 60.0, |time| time.fixed_time_step); // 60 FPS
+
+        while *accumulator >= fixed_step {
+            if let Some(mut time) = world.get_resource_mut::<Time>() {
+                time.delta_seconds = fixed_step as f32;
+            }
+            fixed_schedule.run(world);
+            *accumulator -= fixed_step;
+        }
+
+        if let Some(mut time) = world.get_resource_mut::<Time>() {
+            time.alpha = *accumulator / fixed_step;
+            time.delta_seconds = scaled_delta as f32;
+        }
+    }
+
     /// 处理资源加载事件
     fn process_asset_events(
         world: &mut World,
@@ -162,6 +162,9 @@ pub enum ScriptError {
 
     #[error("Script timeout after {0}ms")]
     Timeout(u64),
+
+    #[error("Script requires API version {required}, but the engine provides {current}")]
+    IncompatibleApiVersion { required: String, current: String },
 }
 
 /// 平台层错误
@@ -58,16 +58,21 @@ impl ErrorStats {
     }
 
     /// 获取错误趋势（最近N秒内的错误数）
+    ///
+    /// 去重后的记录按最后一次发生时间计入全部次数，结果为近似值。
     pub fn error_trend(&self, seconds: u64) -> u64 {
         let cutoff = Self::current_timestamp().saturating_sub(seconds);
         self.recent_errors
             .iter()
             .filter(|record| record.timestamp >= cutoff)
-            .count() as u64
+            .map(|record| record.count)
+            .sum()
     }
 }
 
 /// 错误记录
+///
+/// 类型、来源和消息相同的错误合并为一条记录，通过 `count` 累计次数。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorRecord {
     /// 错误类型
@@ -76,8 +81,12 @@ pub struct ErrorRecord {
     pub source: String,
     /// 错误消息
     pub message: String,
-    /// 时间戳（秒）
+    /// 最后一次发生的时间戳（秒）
     pub timestamp: u64,
+    /// 首次发生的时间戳（秒）
+    pub first_timestamp: u64,
+    /// 发生次数
+    pub count: u64,
     /// 错误详情（可选）
     pub details: Option<String>,
 }
@@ -88,15 +97,25 @@ impl ErrorRecord {
         source: impl Into<String>,
         message: impl Into<String>,
     ) -> Self {
+        let timestamp = Self::current_timestamp();
         Self {
             error_type: error_type.into(),
             source: source.into(),
             message: message.into(),
-            timestamp: Self::current_timestamp(),
+            timestamp,
+            first_timestamp: timestamp,
+            count: 1,
             details: None,
         }
     }
 
+    /// 是否与另一条记录属于同一错误（类型、来源、消息均相同）
+    pub fn same_error(&self, other: &ErrorRecord) -> bool {
+        self.error_type == other.error_type
+            && self.source == other.source
+            && self.message == other.message
+    }
+
     pub fn with_details(mut self, details: impl Into<String>) -> Self {
         self.details = Some(details.into());
         self
@@ -119,12 +138,15 @@ pub struct ErrorAggregator {
     max_recent_errors: usize,
     /// 错误率计算窗口（秒）
     error_rate_window: u64,
+    /// 日志采样间隔：同一错误只在第1次和每第N次时输出日志
+    log_sample_interval: u64,
 }
 
 impl_default!(ErrorAggregator {
     stats: Arc::new(Mutex::new(ErrorStats::default())),
     max_recent_errors: 1000,
     error_rate_window: 60,
+    log_sample_interval: 100,
 });
 
 impl ErrorAggregator {
@@ -136,38 +158,22 @@ impl ErrorAggregator {
     /// 创建带配置的错误聚合器
     pub fn with_config(max_recent_errors: usize, error_rate_window: u64) -> Self {
         Self {
-            stats: Arc::new(Mutex::new(ErrorStats::default())),
             max_recent_errors,
             error_rate_window,
+            ..Self::default()
         }
     }
 
+    /// 设置日志采样间隔，0 表示不输出日志
+    pub fn with_log_sample_interval(mut self, interval: u64) -> Self {
+        self.log_sample_interval = interval;
+        self
+    }
+
     /// 记录错误
     pub fn record_error(&self, error: &EngineError, source: impl Into<String>) {
-        let source_str = source.into();
         let error_type = self.error_type_name(error);
-        let message = error.to_string();
-
-        let record = ErrorRecord::new(&error_type, &source_str, &message);
-
-        let mut stats = self.stats.lock().unwrap();
-        stats.total_count += 1;
-
-        // 更新按类型统计
-        *stats.by_type.entry(error_type.clone()).or_insert(0) += 1;
-
-        // 更新按来源统计
-        *stats.by_source.entry(source_str.clone()).or_insert(0) += 1;
-
-        // 添加最近错误记录
-        stats.recent_errors.push(record);
-        if stats.recent_errors.len() > self.max_recent_errors {
-            stats.recent_errors.remove(0);
-        }
-
-        // 计算错误率
-        stats.error_rate = self.calculate_error_rate(&stats);
-        stats.last_updated = ErrorStats::current_timestamp();
+        self.record(ErrorRecord::new(error_type, source, error.to_string()));
     }
 
     /// 记录自定义错误
@@ -178,28 +184,67 @@ impl ErrorAggregator {
         message: impl Into<String>,
         details: Option<String>,
     ) {
-        let source_str = source.into();
-        let error_type_str = error_type.into();
-        let message_str = message.into();
-
-        let mut record = ErrorRecord::new(&error_type_str, &source_str, &message_str);
+        let mut record = ErrorRecord::new(error_type, source, message);
         if let Some(d) = details {
             record = record.with_details(d);
         }
+        self.record(record);
+    }
 
+    /// 合并重复错误并更新统计
+    fn record(&self, record: ErrorRecord) {
         let mut stats = self.stats.lock().unwrap();
         stats.total_count += 1;
 
-        *stats.by_type.entry(error_type_str.clone()).or_insert(0) += 1;
-        *stats.by_source.entry(source_str.clone()).or_insert(0) += 1;
+        // 更新按类型和来源统计
+        *stats.by_type.entry(record.error_type.clone()).or_insert(0) += 1;
+        *stats.by_source.entry(record.source.clone()).or_insert(0) += 1;
 
-        stats.recent_errors.push(record);
-        if stats.recent_errors.len() > self.max_recent_errors {
-            stats.recent_errors.remove(0);
-        }
+        // 重复错误累加次数，否则添加新记录
+        let count = match stats
+            .recent_errors
+            .iter_mut()
+            .rev()
+            .find(|existing| existing.same_error(&record))
+        {
+            Some(existing) => {
+                existing.count += 1;
+                existing.timestamp = record.timestamp;
+                if record.details.is_some() {
+                    existing.details = record.details.clone();
+                }
+                existing.count
+            }
+            None => {
+                stats.recent_errors.push(record.clone());
+                if stats.recent_errors.len() > self.max_recent_errors {
+                    stats.recent_errors.remove(0);
+                }
+                1
+            }
+        };
 
+        // 计算错误率
         stats.error_rate = self.calculate_error_rate(&stats);
         stats.last_updated = ErrorStats::current_timestamp();
+        drop(stats);
+
+        if self.should_log(count) {
+            tracing::error!(
+                target: "errors",
+                "[{}] {}: {} (occurrence {})",
+                record.source,
+                record.error_type,
+                record.message,
+                count
+            );
+        }
+    }
+
+    /// 限流：只在第1次和每第N次发生时输出日志
+    fn should_log(&self, count: u64) -> bool {
+        self.log_sample_interval > 0
+            && (count == 1 || count.is_multiple_of(self.log_sample_interval))
     }
 
     /// 获取错误统计
@@ -210,6 +255,8 @@ impl ErrorAggregator {
     /// 获取错误摘要
     pub fn get_summary(&self) -> ErrorSummary {
         let stats = self.stats.lock().unwrap();
+        let mut unique_errors = stats.recent_errors.clone();
+        unique_errors.sort_by_key(|record| std::cmp::Reverse(record.count));
         ErrorSummary {
             total_errors: stats.total_count,
             error_rate: stats.error_rate,
@@ -218,6 +265,7 @@ impl ErrorAggregator {
                 .most_common_error_source()
                 .map(|(s, c)| (s.clone(), *c)),
             recent_error_count: stats.recent_errors.len(),
+            unique_errors,
             last_updated: stats.last_updated,
         }
     }
@@ -272,11 +320,12 @@ impl ErrorAggregator {
         let now = ErrorStats::current_timestamp();
         let window_start = now.saturating_sub(self.error_rate_window);
 
-        let errors_in_window = stats
+        let errors_in_window: u64 = stats
             .recent_errors
             .iter()
             .filter(|record| record.timestamp >= window_start)
-            .count();
+            .map(|record| record.count)
+            .sum();
 
         errors_in_window as f64 / self.error_rate_window as f64
     }
@@ -293,8 +342,10 @@ pub struct ErrorSummary {
     pub most_common_type: Option<(String, u64)>,
     /// 最常见的错误来源
     pub most_common_source: Option<(String, u64)>,
-    /// 最近错误数量
+    /// 最近错误数量（去重后）
     pub recent_error_count: usize,
+    /// 去重后的错误，按发生次数降序
+    pub unique_errors: Vec<ErrorRecord>,
     /// 最后更新时间戳
     pub last_updated: u64,
 }
@@ -315,6 +366,17 @@ impl ErrorSummary {
         }
 
         lines.push(format!("最近错误数: {}", self.recent_error_count));
+        for record in &self.unique_errors {
+            lines.push(format!(
+                "  [{}] {}: {} ({}次, {} - {})",
+                record.source,
+                record.error_type,
+                record.message,
+                record.count,
+                record.first_timestamp,
+                record.timestamp
+            ));
+        }
 
         lines.join("\n")
     }
@@ -362,6 +424,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_deduplication() {
+        let aggregator = ErrorAggregator::new();
+        let err = EngineError::Render(RenderError::NoAdapter);
+
+        for _ in 0..1000 {
+            aggregator.record_error(&err, "render_system");
+        }
+        aggregator.record_custom_error("TestError", "test_module", "Test message", None);
+
+        let stats = aggregator.get_stats();
+        assert_eq!(stats.total_count, 1001);
+        assert_eq!(stats.recent_errors.len(), 2);
+
+        let summary = aggregator.get_summary();
+        assert_eq!(summary.unique_errors.len(), 2);
+        let record = &summary.unique_errors[0];
+        assert_eq!(record.source, "render_system");
+        assert_eq!(record.count, 1000);
+        assert!(record.first_timestamp <= record.timestamp);
+        assert_eq!(summary.unique_errors[1].count, 1);
+    }
+
+    #[test]
+    fn test_error_log_sampling() {
+        let aggregator = ErrorAggregator::new().with_log_sample_interval(10);
+        let logged: Vec<u64> = (1..=30).filter(|&n| aggregator.should_log(n)).collect();
+        assert_eq!(logged, vec![1, 10, 20, 30]);
+
+        let silent = ErrorAggregator::new().with_log_sample_interval(0);
+        assert!(!silent.should_log(1));
+    }
+
     #[test]
     fn test_error_export() {
         let aggregator = ErrorAggregator::new();
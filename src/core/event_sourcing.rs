@@ -5,7 +5,7 @@
 pub mod commands;
 
 use bevy_ecs::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
@@ -103,22 +103,137 @@ pub trait EventStore: Send + Sync {
     /// 获取事件范围
     fn get_events_range(&self, from: EventId, to: EventId) -> Vec<StoredEvent>;
 
+    /// 获取从指定索引（含）开始的事件
+    fn get_events_from(&self, index: usize) -> Vec<StoredEvent>;
+
+    /// 已保存的事件数
+    fn event_count(&self) -> usize;
+
+    /// 保存状态快照，超出保留数量时删除最旧的快照
+    fn save_state_snapshot(&mut self, snapshot: StateSnapshot) -> Result<(), EventError>;
+
+    /// 获取最新的状态快照
+    fn latest_state_snapshot(&self) -> Option<StateSnapshot>;
+
+    /// 获取所有保留的状态快照（从旧到新）
+    fn state_snapshots(&self) -> Vec<StateSnapshot>;
+
     /// 清除所有事件
     fn clear(&mut self);
+
+    /// 以序列化后的状态创建快照，记录当前事件索引，返回该索引
+    fn snapshot(&mut self, data: Vec<u8>) -> Result<usize, EventError> {
+        let event_index = self.event_count();
+        self.save_state_snapshot(StateSnapshot {
+            event_index,
+            data,
+            created_at: EventId::now(0).timestamp_ns,
+        })?;
+        Ok(event_index)
+    }
+
+    /// 获取最新快照及其之后的事件
+    ///
+    /// 没有快照时返回全部事件。
+    fn replay_from_latest_snapshot(&self) -> (Option<StateSnapshot>, Vec<StoredEvent>) {
+        let snapshot = self.latest_state_snapshot();
+        let events = self.get_events_from(snapshot.as_ref().map_or(0, |s| s.event_index));
+        (snapshot, events)
+    }
 }
 
-/// 内存事件存储（用于测试和开发）
+/// 可通过重放事件重建的状态
+pub trait ReplayState: Serialize + DeserializeOwned + Default {
+    /// 应用一个已存储的事件
+    fn apply_event(&mut self, event: &StoredEvent) -> Result<(), EventError>;
+
+    /// 序列化当前状态并在事件存储中创建快照，返回快照的事件索引
+    fn snapshot(&self, store: &mut dyn EventStore) -> Result<usize, EventError> {
+        let data =
+            bincode::serialize(self).map_err(|e| EventError::SerializationError(e.to_string()))?;
+        store.snapshot(data)
+    }
+
+    /// 从最新快照恢复状态，只应用快照之后的事件
+    ///
+    /// 没有快照时从默认状态开始重放全部事件。
+    fn replay_from_latest_snapshot(
+        store: &dyn EventStore,
+    ) -> Result<ReplayResult<Self>, EventError> {
+        let (snapshot, events) = store.replay_from_latest_snapshot();
+        let (mut state, snapshot_index) = match snapshot {
+            Some(snapshot) => {
+                let state = bincode::deserialize(&snapshot.data)
+                    .map_err(|e| EventError::SerializationError(e.to_string()))?;
+                (state, Some(snapshot.event_index))
+            }
+            None => (Self::default(), None),
+        };
 
-#[derive(Default)]
+        for event in &events {
+            state.apply_event(event)?;
+        }
+
+        Ok(ReplayResult {
+            state,
+            snapshot_index,
+            events_applied: events.len(),
+        })
+    }
+}
+
+/// 状态快照（序列化状态 + 对应的事件索引）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    /// 快照覆盖的事件数，重放从该索引开始
+    pub event_index: usize,
+    /// 序列化的状态
+    pub data: Vec<u8>,
+    /// 创建时间（纳秒）
+    pub created_at: i64,
+}
+
+/// 快照重放结果
+#[derive(Debug, Clone)]
+pub struct ReplayResult<S> {
+    /// 重建的状态
+    pub state: S,
+    /// 使用的快照的事件索引，没有快照时为 `None`
+    pub snapshot_index: Option<usize>,
+    /// 实际应用的事件数
+    pub events_applied: usize,
+}
+
+/// 内存事件存储（用于测试和开发）
 pub struct MemoryEventStore {
     events: Vec<StoredEvent>,
     next_sequence: u64,
+    snapshots: VecDeque<StateSnapshot>,
+    /// 保留的快照数量
+    max_snapshots: usize,
+}
+
+impl Default for MemoryEventStore {
+    fn default() -> Self {
+        Self {
+            events: Vec::new(),
+            next_sequence: 0,
+            snapshots: VecDeque::new(),
+            max_snapshots: 3,
+        }
+    }
 }
 
 impl MemoryEventStore {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// 设置保留的快照数量（至少保留1个）
+    pub fn with_max_snapshots(mut self, max_snapshots: usize) -> Self {
+        self.max_snapshots = max_snapshots.max(1);
+        self
+    }
 }
 
 impl EventStore for MemoryEventStore {
@@ -155,8 +270,42 @@ impl EventStore for MemoryEventStore {
             .collect()
     }
 
+    fn get_events_from(&self, index: usize) -> Vec<StoredEvent> {
+        self.events
+            .get(index..)
+            .map_or_else(Vec::new, <[_]>::to_vec)
+    }
+
+    fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    fn save_state_snapshot(&mut self, snapshot: StateSnapshot) -> Result<(), EventError> {
+        if snapshot.event_index > self.events.len() {
+            return Err(EventError::StoreFailed(format!(
+                "Snapshot index {} exceeds event count {}",
+                snapshot.event_index,
+                self.events.len()
+            )));
+        }
+        self.snapshots.push_back(snapshot);
+        while self.snapshots.len() > self.max_snapshots {
+            self.snapshots.pop_front();
+        }
+        Ok(())
+    }
+
+    fn latest_state_snapshot(&self) -> Option<StateSnapshot> {
+        self.snapshots.back().cloned()
+    }
+
+    fn state_snapshots(&self) -> Vec<StateSnapshot> {
+        self.snapshots.iter().cloned().collect()
+    }
+
     fn clear(&mut self) {
         self.events.clear();
+        self.snapshots.clear();
         self.next_sequence = 0;
     }
 }
@@ -553,3 +702,89 @@ impl DomainEvent for EntityTransformChangedEvent {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct ScoreState {
+        total: i64,
+        events: u64,
+    }
+
+    impl ReplayState for ScoreState {
+        fn apply_event(&mut self, event: &StoredEvent) -> Result<(), EventError> {
+            let delta: i64 = bincode::deserialize(&event.data)
+                .map_err(|e| EventError::SerializationError(e.to_string()))?;
+            self.total += delta;
+            self.events += 1;
+            Ok(())
+        }
+    }
+
+    fn save_score(store: &mut dyn EventStore, state: &mut ScoreState, delta: i64) {
+        let event = StoredEvent {
+            id: EventId::new(0, store.event_count() as u64),
+            event_type: "ScoreChanged".to_string(),
+            data: bincode::serialize(&delta).unwrap(),
+            aggregate_id: None,
+        };
+        state.apply_event(&event).unwrap();
+        store.save_event(event).unwrap();
+    }
+
+    #[test]
+    fn test_replay_from_snapshot_matches_full_replay() {
+        // 通过 trait 对象使用，与 EventSourcingManager 一致
+        let mut store: Box<dyn EventStore> =
+            Box::new(MemoryEventStore::new().with_max_snapshots(2));
+        let mut live = ScoreState::default();
+
+        for i in 0..1000 {
+            save_score(store.as_mut(), &mut live, i % 7 - 3);
+            if (i + 1) % 250 == 0 {
+                live.snapshot(store.as_mut()).unwrap();
+            }
+        }
+        for _ in 0..10 {
+            save_score(store.as_mut(), &mut live, 5);
+        }
+
+        // 只保留最近两个快照
+        let indices: Vec<_> = store
+            .state_snapshots()
+            .iter()
+            .map(|s| s.event_index)
+            .collect();
+        assert_eq!(indices, vec![750, 1000]);
+
+        let mut full = ScoreState::default();
+        for event in store.get_all_events() {
+            full.apply_event(&event).unwrap();
+        }
+
+        let replay = ScoreState::replay_from_latest_snapshot(store.as_ref()).unwrap();
+        assert_eq!(replay.state, full);
+        assert_eq!(replay.state, live);
+        assert_eq!(replay.snapshot_index, Some(1000));
+        assert_eq!(replay.events_applied, 10);
+    }
+
+    #[test]
+    fn test_replay_without_snapshot_applies_all_events() {
+        let mut store = MemoryEventStore::new();
+        let mut live = ScoreState::default();
+        for _ in 0..5 {
+            save_score(&mut store, &mut live, 2);
+        }
+
+        let replay = ScoreState::replay_from_latest_snapshot(&store).unwrap();
+        assert_eq!(replay.state, live);
+        assert_eq!(replay.snapshot_index, None);
+        assert_eq!(replay.events_applied, 5);
+
+        store.clear();
+        assert!(store.latest_state_snapshot().is_none());
+    }
+}
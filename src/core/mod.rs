@@ -31,8 +31,8 @@ pub use error::{
 pub use error_aggregator::{ErrorAggregator, ErrorRecord, ErrorStats, ErrorSummary};
 
 // 重新导出主要类型
-pub use engine::Engine;
-pub use resources::{AssetMetrics, Benchmark, LogEvents, RenderStats};
+pub use engine::{Engine, HeadlessConfig};
+pub use resources::{AssetMetrics, Benchmark, Headless, LogEvents, RenderStats};
 pub use systems::{
     apply_texture_handles, audio_input_system, benchmark_system, rotate_system,
     save_previous_transform_system,
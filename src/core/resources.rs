@@ -61,6 +61,12 @@ pub struct RenderStats {
     pub fixed_update_time_ms: f32,
     /// 可变时间步更新耗时 (毫秒)
     pub variable_update_time_ms: f32,
+    /// 已登记的显存占用 (字节，估算值)
+    pub vram_bytes_used: u64,
+    /// 存活的GPU缓冲区数量
+    pub buffer_count: u32,
+    /// 存活的GPU纹理数量
+    pub texture_count: u32,
 }
 
 /// 资源加载指标
@@ -74,6 +80,12 @@ pub struct AssetMetrics {
     pub atlases_loaded: u32,
 }
 
+/// 无头模式标记
+///
+/// 存在时引擎不创建窗口和渲染器，渲染相关系统自动跳过。
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Headless;
+
 /// 日志事件缓冲
 #[derive(Resource, Default)]
 pub struct LogEvents {
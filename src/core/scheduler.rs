@@ -8,12 +8,15 @@
 //! - 主线程回调
 //! - 任务优先级
 //! - 任务取消
+//! - 系统运行条件与执行顺序约束
 
 use crate::impl_default;
 use bevy_ecs::prelude::*;
 use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::collections::{BTreeSet, HashMap};
 use std::future::Future;
 use std::sync::{Arc, Mutex};
+use thiserror::Error;
 use tokio::sync::oneshot;
 
 /// 任务优先级
@@ -586,6 +589,255 @@ pub fn process_delayed_tasks_system(
     queue.update(time.delta_seconds as f64);
 }
 
+// ============================================================================
+// 系统调度
+// ============================================================================
+
+/// 系统函数
+type SystemFn = Box<dyn FnMut(&mut World) + Send + 'static>;
+
+/// 系统运行条件
+type RunCondition = Box<dyn Fn(&World) -> bool + Send + Sync + 'static>;
+
+/// 系统调度错误
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SchedulerError {
+    /// 系统名称重复
+    #[error("Duplicate system name: {0}")]
+    DuplicateSystem(String),
+    /// 顺序约束引用了不存在的系统
+    #[error("System '{system}' has an ordering constraint on unknown system '{target}'")]
+    UnknownSystem { system: String, target: String },
+    /// 顺序约束存在环
+    #[error("Cyclic ordering between systems: {0:?}")]
+    CyclicOrdering(Vec<String>),
+}
+
+/// 节点能否沿后继边回到自身
+fn on_cycle(successors: &[Vec<usize>], node: usize) -> bool {
+    let mut visited = vec![false; successors.len()];
+    let mut stack = successors[node].clone();
+    while let Some(i) = stack.pop() {
+        if i == node {
+            return true;
+        }
+        if !std::mem::replace(&mut visited[i], true) {
+            stack.extend(&successors[i]);
+        }
+    }
+    false
+}
+
+struct SystemEntry {
+    name: String,
+    system: SystemFn,
+    condition: Option<RunCondition>,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+/// 系统调度器
+///
+/// 按名称登记系统，支持运行条件和 `before` / `after` 顺序约束。
+/// `build` 将约束解析为执行顺序（无约束的系统保持添加顺序），约束成环时返回错误。
+///
+/// # 示例
+///
+/// ```ignore
+/// let mut scheduler = SystemScheduler::new();
+/// scheduler.add_system("physics", physics_step);
+/// scheduler
+///     .add_system_with_condition("net_sync", net_sync, |world| {
+///         world.contains_resource::<NetworkState>()
+///     })
+///     .after("physics");
+/// scheduler.build()?;
+/// scheduler.run(&mut world)?;
+/// ```
+#[derive(Default)]
+pub struct SystemScheduler {
+    systems: Vec<SystemEntry>,
+    /// 解析后的执行顺序，添加系统或约束后失效
+    order: Option<Vec<usize>>,
+}
+
+/// 系统配置，用于追加顺序约束
+pub struct SystemConfig<'a> {
+    scheduler: &'a mut SystemScheduler,
+    index: usize,
+}
+
+impl SystemConfig<'_> {
+    /// 在指定系统之前运行
+    pub fn before(self, other: impl Into<String>) -> Self {
+        self.scheduler.systems[self.index].before.push(other.into());
+        self.scheduler.order = None;
+        self
+    }
+
+    /// 在指定系统之后运行
+    pub fn after(self, other: impl Into<String>) -> Self {
+        self.scheduler.systems[self.index].after.push(other.into());
+        self.scheduler.order = None;
+        self
+    }
+}
+
+impl SystemScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加系统
+    pub fn add_system<F>(&mut self, name: impl Into<String>, system: F) -> SystemConfig<'_>
+    where
+        F: FnMut(&mut World) + Send + 'static,
+    {
+        self.push(name.into(), Box::new(system), None)
+    }
+
+    /// 添加带运行条件的系统，条件为 false 时跳过该系统
+    pub fn add_system_with_condition<F, C>(
+        &mut self,
+        name: impl Into<String>,
+        system: F,
+        condition: C,
+    ) -> SystemConfig<'_>
+    where
+        F: FnMut(&mut World) + Send + 'static,
+        C: Fn(&World) -> bool + Send + Sync + 'static,
+    {
+        self.push(name.into(), Box::new(system), Some(Box::new(condition)))
+    }
+
+    fn push(
+        &mut self,
+        name: String,
+        system: SystemFn,
+        condition: Option<RunCondition>,
+    ) -> SystemConfig<'_> {
+        self.systems.push(SystemEntry {
+            name,
+            system,
+            condition,
+            before: Vec::new(),
+            after: Vec::new(),
+        });
+        self.order = None;
+        SystemConfig {
+            index: self.systems.len() - 1,
+            scheduler: self,
+        }
+    }
+
+    /// 解析顺序约束，生成执行顺序
+    pub fn build(&mut self) -> Result<(), SchedulerError> {
+        let mut indices = HashMap::new();
+        for (i, entry) in self.systems.iter().enumerate() {
+            if indices.insert(entry.name.as_str(), i).is_some() {
+                return Err(SchedulerError::DuplicateSystem(entry.name.clone()));
+            }
+        }
+
+        // 边 a -> b 表示 a 必须在 b 之前运行
+        let count = self.systems.len();
+        let mut successors = vec![Vec::new(); count];
+        let mut in_degree = vec![0usize; count];
+        for (i, entry) in self.systems.iter().enumerate() {
+            let lookup = |target: &String| {
+                indices
+                    .get(target.as_str())
+                    .copied()
+                    .ok_or_else(|| SchedulerError::UnknownSystem {
+                        system: entry.name.clone(),
+                        target: target.clone(),
+                    })
+            };
+            for target in &entry.before {
+                let j = lookup(target)?;
+                successors[i].push(j);
+                in_degree[j] += 1;
+            }
+            for target in &entry.after {
+                let j = lookup(target)?;
+                successors[j].push(i);
+                in_degree[i] += 1;
+            }
+        }
+
+        // Kahn 拓扑排序，就绪系统中优先取添加顺序最早的
+        let mut ready: BTreeSet<usize> = (0..count).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(count);
+        while let Some(i) = ready.pop_first() {
+            order.push(i);
+            for &j in &successors[i] {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    ready.insert(j);
+                }
+            }
+        }
+
+        if order.len() < count {
+            // 入度未清零的系统可能只是排在环之后，仅报告真正处于环上的系统
+            let cyclic = (0..count)
+                .filter(|&i| in_degree[i] > 0 && on_cycle(&successors, i))
+                .map(|i| self.systems[i].name.clone())
+                .collect();
+            return Err(SchedulerError::CyclicOrdering(cyclic));
+        }
+
+        self.order = Some(order);
+        Ok(())
+    }
+
+    /// 按执行顺序运行所有系统，返回实际运行的系统数
+    ///
+    /// 尚未构建时先调用 `build`。
+    pub fn run(&mut self, world: &mut World) -> Result<usize, SchedulerError> {
+        if self.order.is_none() {
+            self.build()?;
+        }
+        let Some(order) = &self.order else {
+            return Ok(0);
+        };
+
+        let mut executed = 0;
+        for &i in order {
+            let entry = &mut self.systems[i];
+            if entry
+                .condition
+                .as_ref()
+                .is_some_and(|condition| !condition(world))
+            {
+                continue;
+            }
+            (entry.system)(world);
+            executed += 1;
+        }
+        Ok(executed)
+    }
+
+    /// 解析后的系统执行顺序（名称），未构建时返回 `None`
+    pub fn execution_order(&self) -> Option<Vec<&str>> {
+        self.order.as_ref().map(|order| {
+            order
+                .iter()
+                .map(|&i| self.systems[i].name.as_str())
+                .collect()
+        })
+    }
+
+    /// 已登记的系统数
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -640,4 +892,72 @@ mod tests {
         assert_eq!(counter.load(std::sync::atomic::Ordering::SeqCst), 1);
         assert_eq!(queue.pending_count(), 0);
     }
+    #[derive(Resource)]
+    struct NetworkConnection;
+
+    #[derive(Resource, Default)]
+    struct RunLog(Vec<&'static str>);
+
+    fn log_system(name: &'static str) -> impl FnMut(&mut World) + Send + 'static {
+        move |world: &mut World| world.resource_mut::<RunLog>().0.push(name)
+    }
+
+    #[test]
+    fn test_system_run_condition_and_ordering() {
+        let mut world = World::new();
+        world.init_resource::<RunLog>();
+
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system("render", log_system("render"));
+        scheduler
+            .add_system_with_condition("net_sync", log_system("net_sync"), |world| {
+                world.contains_resource::<NetworkConnection>()
+            })
+            .before("render");
+        scheduler
+            .add_system("input", log_system("input"))
+            .before("net_sync");
+        scheduler.build().unwrap();
+        assert_eq!(
+            scheduler.execution_order().unwrap(),
+            vec!["input", "net_sync", "render"]
+        );
+
+        // 缺少网络连接资源时跳过 net_sync
+        assert_eq!(scheduler.run(&mut world).unwrap(), 2);
+        assert_eq!(world.resource::<RunLog>().0, vec!["input", "render"]);
+
+        world.resource_mut::<RunLog>().0.clear();
+        world.insert_resource(NetworkConnection);
+        assert_eq!(scheduler.run(&mut world).unwrap(), 3);
+        assert_eq!(
+            world.resource::<RunLog>().0,
+            vec!["input", "net_sync", "render"]
+        );
+    }
+
+    #[test]
+    fn test_system_ordering_errors() {
+        let mut scheduler = SystemScheduler::new();
+        scheduler.add_system("a", |_: &mut World| {}).after("b");
+        scheduler.add_system("b", |_: &mut World| {}).after("c");
+        scheduler.add_system("c", |_: &mut World| {}).after("a");
+        scheduler.add_system("d", |_: &mut World| {});
+        // 依赖环但不在环上的系统不应出现在错误中
+        scheduler.add_system("e", |_: &mut World| {}).after("c");
+        match scheduler.build() {
+            Err(SchedulerError::CyclicOrdering(names)) => assert_eq!(names, vec!["a", "b", "c"]),
+            other => panic!("expected cycle error, got {other:?}"),
+        }
+        assert!(scheduler.run(&mut World::new()).is_err());
+
+        let mut scheduler = SystemScheduler::new();
+        scheduler
+            .add_system("a", |_: &mut World| {})
+            .before("missing");
+        assert!(matches!(
+            scheduler.build(),
+            Err(SchedulerError::UnknownSystem { .. })
+        ));
+    }
 }
@@ -76,6 +76,29 @@ mod ecs_integration_tests {
         assert!((time.elapsed_seconds - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_time_scale() {
+        let mut normal = Time::default();
+        normal.advance(0.02);
+
+        let mut slow = Time::default();
+        slow.time_scale = 0.5;
+        slow.advance(0.02);
+
+        assert!((slow.delta_seconds - normal.delta_seconds * 0.5).abs() < 1e-6);
+        assert!((slow.unscaled_delta_seconds - 0.02).abs() < 1e-6);
+        assert!((slow.elapsed_seconds - 0.01).abs() < 1e-6);
+        assert!((slow.unscaled_elapsed_seconds - 0.02).abs() < 1e-6);
+
+        // 暂停时游戏时间停止，真实时间继续
+        slow.time_scale = 0.0;
+        slow.advance(0.02);
+        assert!(slow.is_paused());
+        assert_eq!(slow.delta_seconds, 0.0);
+        assert!((slow.elapsed_seconds - 0.01).abs() < 1e-6);
+        assert!((slow.unscaled_elapsed_seconds - 0.04).abs() < 1e-6);
+    }
+
     #[test]
     fn test_world_creation_with_resources() {
         let mut world = World::default();
@@ -145,3 +168,108 @@ mod ecs_integration_tests {
         assert_eq!(transform.pos.z, 30.0);
     }
 }
+
+#[cfg(test)]
+mod headless_tests {
+    use crate::core::engine::{Engine, HeadlessConfig};
+    use crate::core::Headless;
+    use crate::ecs::{PreviousTransform, Time, Transform};
+    use glam::Vec3;
+
+    #[test]
+    fn test_headless_ticks_without_gpu() {
+        let config = HeadlessConfig {
+            realtime: false,
+            max_ticks: Some(10),
+            ..Default::default()
+        };
+        let (mut world, mut fixed_schedule, mut update_schedule) =
+            Engine::initialize_headless(&config);
+        assert!(world.contains_resource::<Headless>());
+
+        let body = world
+            .spawn((
+                Transform {
+                    pos: Vec3::new(0.0, 10.0, 0.0),
+                    ..Default::default()
+                },
+                PreviousTransform::default(),
+            ))
+            .id();
+        #[cfg(feature = "physics_2d")]
+        world.entity_mut(body).insert((
+            crate::physics::RigidBodyDesc {
+                body_type: crate::domain::physics::RigidBodyType::Dynamic,
+                position: Vec3::new(0.0, 10.0, 0.0),
+                rotation: glam::Quat::IDENTITY,
+            },
+            crate::physics::ColliderDesc {
+                shape_type: crate::domain::physics::ShapeType::Cuboid,
+                half_extents: Vec3::splat(0.5),
+                radius: 0.0,
+            },
+        ));
+
+        let mut accumulator = 0.0;
+        for _ in 0..10 {
+            Engine::tick_headless(
+                &mut world,
+                &mut fixed_schedule,
+                &mut update_schedule,
+                &mut accumulator,
+            );
+        }
+
+        let time = world.resource::<Time>();
+        assert!((time.elapsed_seconds - 10.0 * config.tick_seconds).abs() < 1e-5);
+        assert!((time.delta_seconds - config.tick_seconds as f32).abs() < 1e-6);
+
+        // 重力作用下刚体下落
+        #[cfg(feature = "physics_2d")]
+        assert!(world.get::<Transform>(body).unwrap().pos.y < 10.0);
+        #[cfg(not(feature = "physics_2d"))]
+        let _ = body;
+    }
+
+    #[test]
+    fn test_headless_rejects_invalid_tick() {
+        let config = HeadlessConfig {
+            tick_seconds: 0.0,
+            realtime: false,
+            max_ticks: Some(1),
+            ..Default::default()
+        };
+        assert!(Engine::run_headless(config).is_err());
+    }
+}
+
+// macOS 只允许在主线程创建事件循环，在测试线程上会直接 panic
+#[cfg(all(test, any(target_os = "linux", target_os = "windows")))]
+mod frame_capture_tests {
+    use crate::core::engine::Engine;
+
+    #[test]
+    fn test_run_frames_captures_last_frame() {
+        // 需要图形环境和支持回读的交换链，CI 无显示器时跳过
+        let has_display =
+            std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some();
+        if cfg!(target_os = "linux") && !has_display {
+            println!("No display available, skipping");
+            return;
+        }
+        if let Err(e) = Engine::run_frames(3) {
+            println!("Engine could not start ({}), skipping", e);
+            return;
+        }
+
+        let (Some(pixels), Some((width, height))) =
+            (Engine::capture_frame(), Engine::captured_frame_size())
+        else {
+            println!("Surface does not support readback, skipping");
+            return;
+        };
+        // 与引擎创建窗口时请求的物理尺寸一致
+        assert_eq!((width, height), (800, 600));
+        assert_eq!(pixels.len(), (width * height * 4) as usize);
+    }
+}
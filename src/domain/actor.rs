@@ -8,9 +8,48 @@ use crate::ecs::AiComponent;
 use bevy_ecs::prelude::*;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
-use tokio::sync::mpsc;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
 use crate::resources::runtime::global_runtime;
 
+/// 请求/响应消息的默认超时时间
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 发送请求消息并等待回复
+///
+/// `make` 用回复发送器构造请求消息；消息立即入队，返回的Future等待回复，
+/// 超时或Actor在回复前停止都会返回错误。
+fn send_request<T, R, F>(
+    sender: &mpsc::UnboundedSender<ActorMessage<T>>,
+    make: F,
+    timeout: Duration,
+) -> impl Future<Output = Result<R, DomainError>>
+where
+    F: FnOnce(oneshot::Sender<R>) -> T,
+{
+    let (reply_tx, reply_rx) = oneshot::channel();
+    let sent = sender
+        .send(ActorMessage::Handle(PrioritizedMessage::normal(make(
+            reply_tx,
+        ))))
+        .map_err(|_| DomainError::General("Failed to send request to actor".to_string()));
+
+    async move {
+        sent?;
+        match tokio::time::timeout(timeout, reply_rx).await {
+            Ok(Ok(reply)) => Ok(reply),
+            Ok(Err(_)) => Err(DomainError::General(
+                "Actor dropped the request without replying".to_string(),
+            )),
+            Err(_) => Err(DomainError::General(format!(
+                "Actor request timed out after {:?}",
+                timeout
+            ))),
+        }
+    }
+}
+
 /// 消息优先级
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MessagePriority {
@@ -135,6 +174,35 @@ where
         self.send_with_priority(message, MessagePriority::Urgent)
     }
 
+    /// 发送请求消息并等待回复（使用默认超时）
+    ///
+    /// ```ignore
+    /// let velocity = physics_handle
+    ///     .request(|reply| PhysicsActorMessage::Request {
+    ///         query: PhysicsQuery::Velocity { body_id: 1 },
+    ///         reply,
+    ///     })
+    ///     .await?;
+    /// ```
+    pub fn request<R, F>(&self, make: F) -> impl Future<Output = Result<R, DomainError>>
+    where
+        F: FnOnce(oneshot::Sender<R>) -> T,
+    {
+        send_request(&self.sender, make, DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// 发送请求消息并在指定时间内等待回复
+    pub fn request_with_timeout<R, F>(
+        &self,
+        make: F,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<R, DomainError>>
+    where
+        F: FnOnce(oneshot::Sender<R>) -> T,
+    {
+        send_request(&self.sender, make, timeout)
+    }
+
     /// 停止Actor
     /// 
     /// 发送停止信号，Actor会在处理完当前消息后停止
@@ -321,6 +389,26 @@ impl ActorSystem {
             .downcast_ref::<mpsc::UnboundedSender<ActorMessage<A::Message>>>()
     }
 
+    /// 向已注册的Actor发送请求并等待回复
+    ///
+    /// Actor不存在、超时或未回复时返回错误。
+    pub fn request<A, R, F>(
+        &self,
+        name: &str,
+        make: F,
+    ) -> impl Future<Output = Result<R, DomainError>>
+    where
+        A: Actor,
+        F: FnOnce(oneshot::Sender<R>) -> A::Message,
+    {
+        let request = self
+            .get_handle::<A>(name)
+            .map(|sender| send_request(sender, make, DEFAULT_REQUEST_TIMEOUT))
+            .ok_or_else(|| DomainError::General(format!("Actor '{}' not found", name)));
+
+        async move { request?.await }
+    }
+
     /// 停止所有Actor
     pub fn shutdown(&mut self) -> Result<(), DomainError> {
         for _actor in self.actors.values() {
@@ -357,17 +445,53 @@ pub enum AudioActorMessage {
     SetMasterVolume {
         volume: f32,
     },
+    /// 请求/响应消息，结果通过 `reply` 返回
+    Request {
+        query: AudioQuery,
+        reply: oneshot::Sender<AudioReply>,
+    },
+}
+
+/// 音频Actor查询
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioQuery {
+    /// 音频源是否正在播放
+    IsPlaying { source_id: u64 },
+    /// 音频源音量
+    Volume { source_id: u64 },
+    /// 主音量
+    MasterVolume,
+}
+
+/// 音频Actor查询结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioReply {
+    IsPlaying(bool),
+    Volume(Option<f32>),
+    MasterVolume(f32),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AudioSourceState {
+    volume: f32,
+    playing: bool,
 }
 
 /// 音频Actor
 pub struct AudioActor {
     // 这里可以包含音频后端状态
     ai: Option<AiComponent>,
+    sources: HashMap<u64, AudioSourceState>,
+    master_volume: f32,
 }
 
 impl AudioActor {
     pub fn new() -> Self {
-        Self { ai: None }
+        Self {
+            ai: None,
+            sources: HashMap::new(),
+            master_volume: 1.0,
+        }
     }
 
     /// 在创建 actor 时设置 AI 组件
@@ -390,31 +514,62 @@ impl Actor for AudioActor {
             AudioActorMessage::Play {
                 source_id,
                 path,
-                volume: _volume,
+                volume,
                 looped: _looped,
             } => {
                 tracing::info!(target: "audio_actor", "Playing {} for source {}", path, source_id);
                 // 实际的音频播放逻辑
+                self.sources.insert(
+                    source_id,
+                    AudioSourceState {
+                        volume,
+                        playing: true,
+                    },
+                );
             }
             AudioActorMessage::Stop { source_id } => {
                 tracing::info!(target: "audio_actor", "Stopping source {}", source_id);
                 // 实际的音频停止逻辑
+                self.sources.remove(&source_id);
             }
             AudioActorMessage::Pause { source_id } => {
                 tracing::info!(target: "audio_actor", "Pausing source {}", source_id);
                 // 实际的音频暂停逻辑
+                if let Some(source) = self.sources.get_mut(&source_id) {
+                    source.playing = false;
+                }
             }
             AudioActorMessage::Resume { source_id } => {
                 tracing::info!(target: "audio_actor", "Resuming source {}", source_id);
                 // 实际的音频恢复逻辑
+                if let Some(source) = self.sources.get_mut(&source_id) {
+                    source.playing = true;
+                }
             }
             AudioActorMessage::SetVolume { source_id, volume } => {
                 tracing::debug!(target: "audio_actor", "Setting volume {} for source {}", volume, source_id);
                 // 实际的音量设置逻辑
+                if let Some(source) = self.sources.get_mut(&source_id) {
+                    source.volume = volume;
+                }
             }
             AudioActorMessage::SetMasterVolume { volume } => {
                 tracing::debug!(target: "audio_actor", "Setting master volume {}", volume);
                 // 实际的主音量设置逻辑
+                self.master_volume = volume;
+            }
+            AudioActorMessage::Request { query, reply } => {
+                let result = match query {
+                    AudioQuery::IsPlaying { source_id } => AudioReply::IsPlaying(
+                        self.sources.get(&source_id).is_some_and(|s| s.playing),
+                    ),
+                    AudioQuery::Volume { source_id } => {
+                        AudioReply::Volume(self.sources.get(&source_id).map(|s| s.volume))
+                    }
+                    AudioQuery::MasterVolume => AudioReply::MasterVolume(self.master_volume),
+                };
+                // 请求方可能已超时放弃，忽略发送失败
+                let _ = reply.send(result);
             }
         }
         Ok(())
@@ -429,17 +584,48 @@ pub enum PhysicsActorMessage {
     ApplyImpulse { body_id: u64, impulse: [f32; 3] },
     SetPosition { body_id: u64, position: [f32; 3] },
     SetVelocity { body_id: u64, velocity: [f32; 3] },
+    /// 请求/响应消息，结果通过 `reply` 返回
+    Request {
+        query: PhysicsQuery,
+        reply: oneshot::Sender<PhysicsReply>,
+    },
+}
+
+/// 物理Actor查询
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhysicsQuery {
+    /// 刚体当前位置
+    Position { body_id: u64 },
+    /// 刚体当前速度
+    Velocity { body_id: u64 },
+}
+
+/// 物理Actor查询结果，刚体不存在时为 `None`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhysicsReply {
+    Position(Option<[f32; 3]>),
+    Velocity(Option<[f32; 3]>),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PhysicsBodyState {
+    position: [f32; 3],
+    velocity: [f32; 3],
 }
 
 /// 物理Actor
 pub struct PhysicsActor {
     // 这里可以包含物理世界状态
     ai: Option<AiComponent>,
+    bodies: HashMap<u64, PhysicsBodyState>,
 }
 
 impl PhysicsActor {
     pub fn new() -> Self {
-        Self { ai: None }
+        Self {
+            ai: None,
+            bodies: HashMap::new(),
+        }
     }
 
     /// 在创建 actor 时设置 AI 组件
@@ -462,6 +648,11 @@ impl Actor for PhysicsActor {
             PhysicsActorMessage::Step { delta_time } => {
                 tracing::debug!(target: "physics_actor", "Stepping with delta {}", delta_time);
                 // 实际的物理步进逻辑
+                for body in self.bodies.values_mut() {
+                    for axis in 0..3 {
+                        body.position[axis] += body.velocity[axis] * delta_time;
+                    }
+                }
             }
             PhysicsActorMessage::ApplyForce { body_id, force } => {
                 tracing::debug!(target: "physics_actor", "Applying force {:?} to body {}", force, body_id);
@@ -469,15 +660,33 @@ impl Actor for PhysicsActor {
             }
             PhysicsActorMessage::ApplyImpulse { body_id, impulse } => {
                 tracing::debug!(target: "physics_actor", "Applying impulse {:?} to body {}", impulse, body_id);
-                // 实际的冲量施加逻辑
+                // 实际的冲量施加逻辑（单位质量）
+                let body = self.bodies.entry(body_id).or_default();
+                for axis in 0..3 {
+                    body.velocity[axis] += impulse[axis];
+                }
             }
             PhysicsActorMessage::SetPosition { body_id, position } => {
                 tracing::debug!(target: "physics_actor", "Setting position {:?} for body {}", position, body_id);
                 // 实际的位置设置逻辑
+                self.bodies.entry(body_id).or_default().position = position;
             }
             PhysicsActorMessage::SetVelocity { body_id, velocity } => {
                 tracing::debug!(target: "physics_actor", "Setting velocity {:?} for body {}", velocity, body_id);
                 // 实际的速度设置逻辑
+                self.bodies.entry(body_id).or_default().velocity = velocity;
+            }
+            PhysicsActorMessage::Request { query, reply } => {
+                let result = match query {
+                    PhysicsQuery::Position { body_id } => {
+                        PhysicsReply::Position(self.bodies.get(&body_id).map(|b| b.position))
+                    }
+                    PhysicsQuery::Velocity { body_id } => {
+                        PhysicsReply::Velocity(self.bodies.get(&body_id).map(|b| b.velocity))
+                    }
+                };
+                // 请求方可能已超时放弃，忽略发送失败
+                let _ = reply.send(result);
             }
         }
         Ok(())
@@ -499,17 +708,44 @@ pub enum RenderActorMessage {
     UnloadTexture {
         texture_id: u64,
     },
+    /// 请求/响应消息，结果通过 `reply` 返回
+    Request {
+        query: RenderQuery,
+        reply: oneshot::Sender<RenderReply>,
+    },
+}
+
+/// 渲染Actor查询
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderQuery {
+    /// 已渲染的帧数
+    FrameCount,
+    /// 实体最近一次更新的变换
+    Transform { entity_id: u64 },
+}
+
+/// 渲染Actor查询结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderReply {
+    FrameCount(u64),
+    Transform(Option<([f32; 3], [f32; 4])>),
 }
 
 /// 渲染Actor
 pub struct RenderActor {
     // 这里可以包含渲染状态
     ai: Option<AiComponent>,
+    frame_count: u64,
+    transforms: HashMap<u64, ([f32; 3], [f32; 4])>,
 }
 
 impl RenderActor {
     pub fn new() -> Self {
-        Self { ai: None }
+        Self {
+            ai: None,
+            frame_count: 0,
+            transforms: HashMap::new(),
+        }
     }
 
     /// 在创建 actor 时设置 AI 组件
@@ -532,14 +768,16 @@ impl Actor for RenderActor {
             RenderActorMessage::RenderFrame => {
                 tracing::debug!(target: "render_actor", "Rendering frame");
                 // 实际的渲染逻辑
+                self.frame_count += 1;
             }
             RenderActorMessage::UpdateTransform {
                 entity_id,
-                position: _,
-                rotation: _,
+                position,
+                rotation,
             } => {
                 tracing::debug!(target: "render_actor", "Updating transform for entity {}", entity_id);
                 // 实际的变换更新逻辑
+                self.transforms.insert(entity_id, (position, rotation));
             }
             RenderActorMessage::LoadTexture { path } => {
                 tracing::info!(target: "render_actor", "Loading texture {}", path);
@@ -549,6 +787,16 @@ impl Actor for RenderActor {
                 tracing::info!(target: "render_actor", "Unloading texture {}", texture_id);
                 // 实际的纹理卸载逻辑
             }
+            RenderActorMessage::Request { query, reply } => {
+                let result = match query {
+                    RenderQuery::FrameCount => RenderReply::FrameCount(self.frame_count),
+                    RenderQuery::Transform { entity_id } => {
+                        RenderReply::Transform(self.transforms.get(&entity_id).copied())
+                    }
+                };
+                // 请求方可能已超时放弃，忽略发送失败
+                let _ = reply.send(result);
+            }
         }
         Ok(())
     }
@@ -602,4 +850,74 @@ mod tests {
         physics_handle.stop().unwrap();
         render_handle.stop().unwrap();
     }
+
+    #[test]
+    fn test_physics_actor_request_reply() {
+        let mut system = ActorSystem::new();
+        let physics_handle = system.register("physics", PhysicsActor::new()).unwrap();
+
+        physics_handle
+            .send(PhysicsActorMessage::SetVelocity {
+                body_id: 7,
+                velocity: [1.0, 2.0, 3.0],
+            })
+            .unwrap();
+
+        let reply = global_runtime()
+            .block_on(
+                physics_handle.request(|reply| PhysicsActorMessage::Request {
+                    query: PhysicsQuery::Velocity { body_id: 7 },
+                    reply,
+                }),
+            )
+            .unwrap();
+        assert_eq!(reply, PhysicsReply::Velocity(Some([1.0, 2.0, 3.0])));
+
+        // 通过ActorSystem按名称请求
+        let missing = global_runtime()
+            .block_on(system.request::<PhysicsActor, _, _>("physics", |reply| {
+                PhysicsActorMessage::Request {
+                    query: PhysicsQuery::Position { body_id: 99 },
+                    reply,
+                }
+            }))
+            .unwrap();
+        assert_eq!(missing, PhysicsReply::Position(None));
+
+        physics_handle.stop().unwrap();
+    }
+
+    #[test]
+    fn test_request_to_unknown_actor_errors() {
+        let system = ActorSystem::new();
+        let result =
+            global_runtime().block_on(system.request::<PhysicsActor, _, _>("missing", |reply| {
+                PhysicsActorMessage::Request {
+                    query: PhysicsQuery::Velocity { body_id: 1 },
+                    reply,
+                }
+            }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_request_times_out_without_reply() {
+        let mut system = ActorSystem::new();
+        let handle = system.register("render", RenderActor::new()).unwrap();
+
+        let result = global_runtime().block_on(handle.request_with_timeout(
+            |reply: oneshot::Sender<RenderReply>| {
+                // 由另一个任务持有回复发送器，模拟Actor迟迟不回复
+                global_runtime().spawn(async move {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    drop(reply);
+                });
+                RenderActorMessage::RenderFrame
+            },
+            Duration::from_millis(20),
+        ));
+        assert!(result.unwrap_err().to_string().contains("timed out"));
+
+        handle.stop().unwrap();
+    }
 }
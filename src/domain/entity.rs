@@ -1,7 +1,8 @@
 //! 游戏实体领域对象
 
 use crate::domain::errors::{DomainError, SceneError};
-use crate::ecs::{Camera, PointLight, Sprite, Transform};
+use crate::domain::scene::Scene;
+use crate::ecs::{Camera, Material, PointLight, Sprite, Transform};
 use crate::impl_default;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -39,6 +40,7 @@ impl std::fmt::Display for EntityId {
 /// - `sprite`：精灵渲染组件（可选）
 /// - `point_light`：点光源组件（可选）
 /// - `camera`：相机组件（可选）
+/// - `material`：材质组件（可选）
 /// - `properties`：自定义属性
 /// - `state`：实体状态
 ///
@@ -74,6 +76,8 @@ pub struct GameEntity {
     pub point_light: Option<PointLight>,
     /// 相机组件
     pub camera: Option<Camera>,
+    /// 材质组件
+    pub material: Option<Material>,
     /// 自定义属性
     pub properties: HashMap<String, serde_json::Value>,
     /// 实体状态
@@ -102,6 +106,7 @@ impl GameEntity {
             sprite: None,
             point_light: None,
             camera: None,
+            material: None,
             properties: HashMap::new(),
             state: EntityState::Active,
             last_modified: std::time::SystemTime::now()
@@ -146,6 +151,13 @@ impl GameEntity {
         self
     }
 
+    /// 设置材质组件
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = Some(material);
+        self.last_modified = Self::current_timestamp();
+        self
+    }
+
     /// 设置自定义属性
     pub fn set_property(
         &mut self,
@@ -287,6 +299,7 @@ impl Default for GameEntity {
             sprite: None,
             point_light: None,
             camera: None,
+            material: None,
             properties: HashMap::new(),
             state: EntityState::Active,
             last_modified: Self::current_timestamp(),
@@ -294,10 +307,186 @@ impl Default for GameEntity {
     }
 }
 
+/// 预制体组件描述
+///
+/// `component_type` 为组件类型名（如 `"Transform"`、`"Sprite"`），
+/// `value` 为该组件的 JSON 表示，缺省字段使用组件默认值。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDescriptor {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+impl ComponentDescriptor {
+    pub fn new(component_type: impl Into<String>, value: serde_json::Value) -> Self {
+        Self {
+            component_type: component_type.into(),
+            value,
+        }
+    }
+}
+
+/// 预制体模板：可序列化的组件集合，用于重复实例化实体
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrefabTemplate {
+    /// 实例名称（可选）
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 组件描述列表
+    #[serde(default)]
+    pub components: Vec<ComponentDescriptor>,
+    /// 自定义属性
+    #[serde(default)]
+    pub properties: HashMap<String, serde_json::Value>,
+}
+
+impl PrefabTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加组件描述
+    pub fn with_component(
+        mut self,
+        component_type: impl Into<String>,
+        value: serde_json::Value,
+    ) -> Self {
+        self.components
+            .push(ComponentDescriptor::new(component_type, value));
+        self
+    }
+
+    /// 从JSON解析模板
+    pub fn from_json(json: &str) -> Result<Self, DomainError> {
+        serde_json::from_str(json)
+            .map_err(|e| DomainError::Scene(SceneError::DeserializationFailed(e.to_string())))
+    }
+
+    /// 序列化为JSON
+    pub fn to_json(&self) -> Result<String, DomainError> {
+        serde_json::to_string(self)
+            .map_err(|e| DomainError::Scene(SceneError::SerializationFailed(e.to_string())))
+    }
+
+    /// 根据模板构建实体，未知组件类型返回错误
+    fn build(&self, prefab: &str, id: EntityId) -> Result<GameEntity, DomainError> {
+        let mut entity = GameEntity::new(id);
+        entity.name = self.name.clone();
+        entity.properties = self.properties.clone();
+
+        for desc in &self.components {
+            match desc.component_type.as_str() {
+                "Transform" => entity.transform = Some(Self::parse(prefab, desc)?),
+                "Sprite" => entity.sprite = Some(Self::parse(prefab, desc)?),
+                "PointLight" => entity.point_light = Some(Self::parse(prefab, desc)?),
+                "Camera" => entity.camera = Some(Self::parse(prefab, desc)?),
+                "Material" => entity.material = Some(Self::parse(prefab, desc)?),
+                other => {
+                    return Err(DomainError::Scene(SceneError::DeserializationFailed(
+                        format!("Unknown component type '{}' in prefab '{}'", other, prefab),
+                    )))
+                }
+            }
+        }
+
+        Ok(entity)
+    }
+
+    fn parse<T: serde::de::DeserializeOwned>(
+        prefab: &str,
+        desc: &ComponentDescriptor,
+    ) -> Result<T, DomainError> {
+        // null 表示使用组件默认值
+        let value = if desc.value.is_null() {
+            serde_json::Value::Object(Default::default())
+        } else {
+            desc.value.clone()
+        };
+        serde_json::from_value(value).map_err(|e| {
+            DomainError::Scene(SceneError::DeserializationFailed(format!(
+                "Invalid {} component in prefab '{}': {}",
+                desc.component_type, prefab, e
+            )))
+        })
+    }
+}
+
+/// 预制体实例化时的变换覆盖
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PrefabOverrides {
+    pub position: Option<glam::Vec3>,
+    pub rotation: Option<glam::Quat>,
+}
+
 /// 实体工厂
-pub struct EntityFactory;
+///
+/// 除了静态的便捷构造函数外，还维护一个预制体注册表，
+/// 用于从模板批量实例化实体。
+#[derive(Debug, Default)]
+pub struct EntityFactory {
+    prefabs: HashMap<String, PrefabTemplate>,
+}
 
 impl EntityFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册预制体，同名预制体会被覆盖
+    pub fn register_prefab(&mut self, name: &str, template: PrefabTemplate) {
+        self.prefabs.insert(name.to_string(), template);
+    }
+
+    /// 获取已注册的预制体模板
+    pub fn prefab(&self, name: &str) -> Option<&PrefabTemplate> {
+        self.prefabs.get(name)
+    }
+
+    /// 实例化预制体并加入场景
+    pub fn instantiate(&self, name: &str, scene: &mut Scene) -> Result<EntityId, DomainError> {
+        self.instantiate_with(name, scene, PrefabOverrides::default())
+    }
+
+    /// 实例化预制体，并应用位置/旋转覆盖
+    pub fn instantiate_with(
+        &self,
+        name: &str,
+        scene: &mut Scene,
+        overrides: PrefabOverrides,
+    ) -> Result<EntityId, DomainError> {
+        let template = self.prefabs.get(name).ok_or_else(|| {
+            DomainError::Scene(SceneError::EntityNotFound(format!(
+                "Prefab not registered: {}",
+                name
+            )))
+        })?;
+
+        let id = EntityId(
+            scene
+                .entities
+                .keys()
+                .map(|id| id.as_u64() + 1)
+                .max()
+                .unwrap_or(1),
+        );
+        let mut entity = template.build(name, id)?;
+
+        if overrides.position.is_some() || overrides.rotation.is_some() {
+            let transform = entity.transform.get_or_insert_with(Transform::default);
+            if let Some(position) = overrides.position {
+                transform.pos = position;
+            }
+            if let Some(rotation) = overrides.rotation {
+                transform.rot = rotation;
+            }
+        }
+
+        scene.add_entity(entity)?;
+        Ok(id)
+    }
+
     /// 创建基础实体
     pub fn create_basic(id: EntityId, position: glam::Vec3) -> GameEntity {
         GameEntity::new(id).with_transform(Transform {
@@ -326,6 +515,7 @@ impl EntityFactory {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::scene::SceneId;
 
     #[test]
     fn test_entity_creation() {
@@ -563,4 +753,63 @@ mod tests {
         );
         assert_eq!(entity.position(), Some(glam::Vec3::new(1.0, 2.0, 3.0)));
     }
+
+    #[test]
+    fn test_prefab_instances_are_independent() {
+        let mut factory = EntityFactory::new();
+        let template = PrefabTemplate::new()
+            .with_component("Transform", serde_json::json!({ "pos": [1.0, 0.0, 0.0] }))
+            .with_component(
+                "Sprite",
+                serde_json::json!({ "color": [1.0, 0.0, 0.0, 1.0] }),
+            );
+        factory.register_prefab("enemy", template);
+
+        let mut scene = Scene::new(SceneId(1), "test");
+        let a = factory.instantiate("enemy", &mut scene).unwrap();
+        let b = factory
+            .instantiate_with(
+                "enemy",
+                &mut scene,
+                PrefabOverrides {
+                    position: Some(glam::Vec3::new(5.0, 0.0, 0.0)),
+                    rotation: None,
+                },
+            )
+            .unwrap();
+        assert_ne!(a, b);
+
+        scene
+            .get_entity_mut(a)
+            .unwrap()
+            .set_position(glam::Vec3::new(9.0, 9.0, 9.0))
+            .unwrap();
+
+        let entity_a = scene.get_entity(a).unwrap();
+        let entity_b = scene.get_entity(b).unwrap();
+        assert_eq!(entity_a.position(), Some(glam::Vec3::new(9.0, 9.0, 9.0)));
+        assert_eq!(entity_b.position(), Some(glam::Vec3::new(5.0, 0.0, 0.0)));
+        assert_eq!(
+            entity_b.sprite.as_ref().unwrap().color,
+            [1.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(entity_b.transform.unwrap().scale, glam::Vec3::ONE);
+    }
+
+    #[test]
+    fn test_prefab_unknown_component_errors() {
+        let mut factory = EntityFactory::new();
+        let template = PrefabTemplate::from_json(
+            r#"{ "components": [{ "type": "Teleporter", "value": {} }] }"#,
+        )
+        .unwrap();
+        factory.register_prefab("broken", template);
+
+        let mut scene = Scene::new(SceneId(1), "test");
+        let err = factory.instantiate("broken", &mut scene).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown component type 'Teleporter'"));
+        assert!(factory.instantiate("missing", &mut scene).is_err());
+    }
 }
@@ -318,7 +318,9 @@ mod tests {
         assert_eq!(summary.total_errors, 3);
         assert_eq!(summary.most_common_type, Some(("ErrorA".to_string(), 2)));
         assert_eq!(summary.most_common_source, Some(("module1".to_string(), 2)));
-        assert_eq!(summary.recent_error_count, 3);
+        // 重复的 ErrorA 合并为一条记录
+        assert_eq!(summary.recent_error_count, 2);
+        assert_eq!(summary.unique_errors[0].count, 2);
     }
 
     #[test]
@@ -71,6 +71,9 @@ pub enum SceneError {
     /// 组件未找到
     #[error("Component not found: {0}")]
     ComponentNotFound(String),
+    /// 场景正在加载
+    #[error("Scene already loading: {0}")]
+    AlreadyLoading(String),
     /// 序列化失败
     #[error("Serialization failed: {0}")]
     SerializationFailed(String),
@@ -79,6 +82,29 @@ pub enum SceneError {
     DeserializationFailed(String),
 }
 
+/// 依赖注入错误
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DIError {
+    /// 服务未注册
+    #[error("Service not registered: {0}")]
+    NotRegistered(&'static str),
+    /// 依赖的服务未注册
+    #[error("Missing dependency: {service} requires {dependency}, which is not registered")]
+    MissingDependency {
+        service: &'static str,
+        dependency: &'static str,
+    },
+    /// 循环依赖，按依赖顺序列出路径
+    #[error("Cyclic service dependency: {}", .0.join(" -> "))]
+    Cycle(Vec<&'static str>),
+    /// 服务工厂构建失败
+    #[error("Failed to construct {service}: {reason}")]
+    FactoryFailed {
+        service: &'static str,
+        reason: String,
+    },
+}
+
 /// 错误恢复策略
 #[derive(Debug, Clone)]
 pub enum RecoveryStrategy {
@@ -17,17 +17,18 @@ pub mod value_objects;
 
 // 重新导出主要类型
 pub use actor::{
-    ActorSystem, AudioActor, AudioActorMessage, PhysicsActor, PhysicsActorMessage, RenderActor,
-    RenderActorMessage,
+    ActorSystem, AudioActor, AudioActorMessage, AudioQuery, AudioReply, PhysicsActor,
+    PhysicsActorMessage, PhysicsQuery, PhysicsReply, RenderActor, RenderActorMessage, RenderQuery,
+    RenderReply,
 };
 pub use audio::{AudioListener, AudioSource, AudioSourceId, SpatialAudioSource};
-pub use entity::{EntityFactory, EntityId, GameEntity};
-pub use errors::{AudioError, DomainError, PhysicsError, SceneError};
+pub use entity::{EntityFactory, EntityId, GameEntity, PrefabOverrides, PrefabTemplate};
+pub use errors::{AudioError, DIError, DomainError, PhysicsError, SceneError};
 pub use physics::{Collider, ColliderId, RigidBody, RigidBodyId, RigidBodyType};
 pub use render::{
     LightSource, PbrScene, RenderObject, RenderObjectId, RenderScene, RenderStrategy,
 };
-pub use scene::{Scene, SceneId, SceneManager};
+pub use scene::{Scene, SceneEvent, SceneId, SceneLoadHandle, SceneManager};
 pub use services::{
     AudioDomainService, DIContainer, DomainServiceFactory, PhysicsDomainService, SceneDomainService,
 };
@@ -4,8 +4,11 @@
 use crate::domain::entity::{EntityId, GameEntity};
 use crate::domain::errors::{CompensationAction, DomainError, RecoveryStrategy, SceneError};
 use crate::impl_default;
+use crate::resources::coroutine_loader::{AssetType, CoroutineAssetLoader, LoadPriority};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
 
 /// 场景ID
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -138,6 +141,8 @@ pub struct Scene {
     pub state: SceneState,
     /// 实体集合（应通过`add_entity`, `remove_entity`方法修改）
     pub entities: HashMap<EntityId, GameEntity>,
+    /// 场景依赖的资源，异步流式加载时预先加载
+    pub assets: Vec<SceneAsset>,
     /// 场景元数据
     pub metadata: SceneMetadata,
     /// 最后修改时间戳
@@ -146,6 +151,13 @@ pub struct Scene {
     pub recovery_strategy: RecoveryStrategy,
 }
 
+/// 场景依赖的资源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SceneAsset {
+    pub path: PathBuf,
+    pub asset_type: AssetType,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SceneMetadata {
     /// 作者
@@ -176,6 +188,7 @@ impl Scene {
             name: name.into(),
             state: SceneState::Unloaded,
             entities: HashMap::new(),
+            assets: Vec::new(),
             metadata: SceneMetadata {
                 created_at: now,
                 modified_at: now,
@@ -259,6 +272,14 @@ impl Scene {
         Ok(())
     }
 
+    /// 登记场景依赖的资源
+    pub fn add_asset(&mut self, path: impl Into<PathBuf>, asset_type: AssetType) {
+        self.assets.push(SceneAsset {
+            path: path.into(),
+            asset_type,
+        });
+    }
+
     /// 添加实体
     pub fn add_entity(&mut self, entity: GameEntity) -> Result<(), DomainError> {
         // 业务规则：确保实体ID唯一
@@ -521,12 +542,76 @@ impl SceneSnapshot {
     }
 }
 
+/// 场景流式加载事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneEvent {
+    /// 加载进度（0.0 - 1.0）
+    Progress(f32),
+    /// 场景加载完成并已激活
+    Loaded(SceneId),
+    /// 场景已卸载
+    Unloaded(SceneId),
+    /// 场景加载失败，当前场景保持不变
+    Failed(SceneId, String),
+}
+
+/// 异步场景加载句柄，用于接收加载事件（如显示加载进度条）
+#[derive(Debug)]
+pub struct SceneLoadHandle {
+    scene_id: SceneId,
+    events: mpsc::Receiver<SceneEvent>,
+}
+
+impl SceneLoadHandle {
+    /// 正在加载的场景ID
+    pub fn scene_id(&self) -> SceneId {
+        self.scene_id
+    }
+
+    /// 非阻塞地获取下一个事件
+    pub fn try_recv(&self) -> Option<SceneEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// 获取所有已到达的事件
+    pub fn drain(&self) -> Vec<SceneEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+/// 进行中的流式加载
+struct StreamingLoad {
+    scene_id: SceneId,
+    additive: bool,
+    pending: Vec<u64>,
+    total: usize,
+    error: Option<String>,
+    tx: mpsc::Sender<SceneEvent>,
+}
+
+impl StreamingLoad {
+    fn emit(&self, event: SceneEvent, out: &mut Vec<SceneEvent>) {
+        let _ = self.tx.send(event.clone());
+        out.push(event);
+    }
+
+    fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.total - self.pending.len()) as f32 / self.total as f32
+        }
+    }
+}
+
 /// 场景管理器 - 领域服务
 pub struct SceneManager {
     /// 场景集合
     scenes: HashMap<SceneId, Scene>,
     /// 当前活跃场景
     active_scene: Option<SceneId>,
+    /// 进行中的流式加载
+    streaming: Vec<StreamingLoad>,
     /// 最后更新时间戳
     last_updated: u64,
 }
@@ -534,6 +619,7 @@ pub struct SceneManager {
 impl_default!(SceneManager {
     scenes: HashMap::new(),
     active_scene: None,
+    streaming: Vec::new(),
     last_updated: Self::current_timestamp(),
 });
 
@@ -628,6 +714,160 @@ impl SceneManager {
         Ok(())
     }
 
+    /// 异步加载场景
+    ///
+    /// 场景资源通过协程加载器在后台加载，期间当前场景保持活跃。
+    /// 需要每帧调用 [`SceneManager::update_streaming`] 推进加载；
+    /// 全部资源就绪后激活新场景。`additive` 为 true 时不卸载当前场景。
+    ///
+    /// 注意：`update_streaming` 会消费加载器的完成事件，建议为场景流式加载使用独立的加载器。
+    pub fn load_scene_async(
+        &mut self,
+        id: SceneId,
+        loader: &CoroutineAssetLoader,
+        additive: bool,
+    ) -> Result<SceneLoadHandle, DomainError> {
+        if self.streaming.iter().any(|l| l.scene_id == id) {
+            return Err(DomainError::Scene(SceneError::AlreadyLoading(format!(
+                "Scene {}",
+                id.as_u64()
+            ))));
+        }
+
+        let scene = self.scenes.get_mut(&id).ok_or_else(|| {
+            DomainError::Scene(SceneError::SceneNotFound(format!(
+                "Scene {} not found",
+                id.as_u64()
+            )))
+        })?;
+
+        // 已加载的场景无需重新加载资源，下次更新时直接激活
+        let pending = match scene.state {
+            SceneState::Unloaded => {
+                scene.state = SceneState::Loading;
+                scene
+                    .assets
+                    .iter()
+                    .map(|asset| {
+                        loader
+                            .load_with_priority(&asset.path, asset.asset_type, LoadPriority::High)
+                            .id
+                    })
+                    .collect()
+            }
+            SceneState::Loaded | SceneState::Inactive => Vec::new(),
+            state => {
+                return Err(DomainError::Scene(SceneError::SceneNotFound(format!(
+                    "Cannot stream scene {}: invalid state {:?}",
+                    scene.name, state
+                ))))
+            }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let load = StreamingLoad {
+            scene_id: id,
+            additive,
+            total: pending.len(),
+            pending,
+            error: None,
+            tx,
+        };
+        load.emit(SceneEvent::Progress(load.progress()), &mut Vec::new());
+        self.streaming.push(load);
+
+        Ok(SceneLoadHandle {
+            scene_id: id,
+            events: rx,
+        })
+    }
+
+    /// 是否有场景正在流式加载
+    pub fn is_streaming(&self) -> bool {
+        !self.streaming.is_empty()
+    }
+
+    /// 推进流式加载，返回本次产生的所有场景事件
+    pub fn update_streaming(&mut self, loader: &CoroutineAssetLoader) -> Vec<SceneEvent> {
+        let mut events = Vec::new();
+
+        for complete in loader.poll_completed() {
+            for load in self.streaming.iter_mut() {
+                if let Some(pos) = load
+                    .pending
+                    .iter()
+                    .position(|&id| id == complete.request_id)
+                {
+                    load.pending.swap_remove(pos);
+                    if let Err(e) = &complete.result {
+                        load.error
+                            .get_or_insert_with(|| format!("{}: {}", complete.path.display(), e));
+                    }
+                    load.emit(SceneEvent::Progress(load.progress()), &mut events);
+                    break;
+                }
+            }
+        }
+
+        let (finished, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.streaming)
+            .into_iter()
+            .partition(|l| l.pending.is_empty());
+        self.streaming = pending;
+
+        for load in finished {
+            self.finish_streaming(load, &mut events);
+        }
+
+        if !events.is_empty() {
+            self.last_updated = Self::current_timestamp();
+        }
+        events
+    }
+
+    fn finish_streaming(&mut self, load: StreamingLoad, events: &mut Vec<SceneEvent>) {
+        let id = load.scene_id;
+
+        if let Some(error) = &load.error {
+            if let Some(scene) = self.scenes.get_mut(&id) {
+                scene.state = SceneState::Unloaded;
+            }
+            load.emit(SceneEvent::Failed(id, error.clone()), events);
+            return;
+        }
+
+        // 非叠加加载：新场景就绪后才卸载当前场景
+        if !load.additive {
+            if let Some(current_id) = self.active_scene.filter(|&c| c != id) {
+                if let Some(current) = self.scenes.get_mut(&current_id) {
+                    let _ = current.deactivate();
+                    let _ = current.unload();
+                }
+                self.active_scene = None;
+                load.emit(SceneEvent::Unloaded(current_id), events);
+            }
+        }
+
+        let Some(scene) = self.scenes.get_mut(&id) else {
+            load.emit(
+                SceneEvent::Failed(id, format!("Scene {} was removed", id.as_u64())),
+                events,
+            );
+            return;
+        };
+        if scene.state == SceneState::Loading {
+            scene.state = SceneState::Loaded;
+            scene.metadata.modified_at = Self::current_timestamp();
+        }
+        if let Err(e) = scene.activate() {
+            load.emit(SceneEvent::Failed(id, e.to_string()), events);
+            return;
+        }
+        if self.active_scene.is_none() {
+            self.active_scene = Some(id);
+        }
+        load.emit(SceneEvent::Loaded(id), events);
+    }
+
     /// 获取所有场景ID
     pub fn scene_ids(&self) -> Vec<SceneId> {
         self.scenes.keys().cloned().collect()
@@ -1209,4 +1449,80 @@ mod tests {
         assert!(compensation.data.get("entity_count").is_some());
         assert!(compensation.data.get("version").is_some());
     }
+
+    fn stream_until_done(
+        manager: &mut SceneManager,
+        loader: &CoroutineAssetLoader,
+        handle: &SceneLoadHandle,
+    ) -> Vec<SceneEvent> {
+        let mut events = handle.drain();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while manager.is_streaming() && std::time::Instant::now() < deadline {
+            manager.update_streaming(loader);
+            events.extend(handle.drain());
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        events
+    }
+
+    #[test]
+    fn test_scene_manager_load_scene_async() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_path = dir.path().join("level.bin");
+        std::fs::write(&asset_path, [1u8, 2, 3]).unwrap();
+
+        let mut manager = SceneManager::new();
+        manager.create_scene(SceneId(1), "Menu").unwrap();
+        manager.get_scene_mut(SceneId(1)).unwrap().load().unwrap();
+        manager.switch_to_scene(SceneId(1)).unwrap();
+
+        manager.create_scene(SceneId(2), "Level").unwrap();
+        manager
+            .get_scene_mut(SceneId(2))
+            .unwrap()
+            .add_asset(&asset_path, AssetType::Custom);
+
+        let loader = CoroutineAssetLoader::default();
+        let handle = manager
+            .load_scene_async(SceneId(2), &loader, false)
+            .unwrap();
+        // 新场景就绪前当前场景保持活跃
+        assert_eq!(manager.active_scene().unwrap().id, SceneId(1));
+        assert!(matches!(
+            manager.load_scene_async(SceneId(2), &loader, false),
+            Err(DomainError::Scene(SceneError::AlreadyLoading(_)))
+        ));
+
+        let events = stream_until_done(&mut manager, &loader, &handle);
+        assert_eq!(events.first(), Some(&SceneEvent::Progress(0.0)));
+        assert!(events.contains(&SceneEvent::Progress(1.0)));
+        assert!(events.contains(&SceneEvent::Unloaded(SceneId(1))));
+        assert_eq!(events.last(), Some(&SceneEvent::Loaded(SceneId(2))));
+        assert_eq!(manager.active_scene().unwrap().id, SceneId(2));
+        assert_eq!(
+            manager.get_scene(SceneId(1)).unwrap().state,
+            SceneState::Unloaded
+        );
+    }
+
+    #[test]
+    fn test_scene_manager_load_scene_async_additive() {
+        let mut manager = SceneManager::new();
+        manager.create_scene(SceneId(1), "World").unwrap();
+        manager.get_scene_mut(SceneId(1)).unwrap().load().unwrap();
+        manager.switch_to_scene(SceneId(1)).unwrap();
+        manager.create_scene(SceneId(2), "Interior").unwrap();
+
+        let loader = CoroutineAssetLoader::default();
+        let handle = manager.load_scene_async(SceneId(2), &loader, true).unwrap();
+        let events = stream_until_done(&mut manager, &loader, &handle);
+
+        assert_eq!(events.last(), Some(&SceneEvent::Loaded(SceneId(2))));
+        assert!(!events.contains(&SceneEvent::Unloaded(SceneId(1))));
+        assert_eq!(manager.active_scene().unwrap().id, SceneId(1));
+        assert_eq!(
+            manager.get_scene(SceneId(2)).unwrap().state,
+            SceneState::Active
+        );
+    }
 }
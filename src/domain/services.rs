@@ -2,12 +2,13 @@
 //! 实现依赖注入容器和真正的领域服务
 
 use crate::domain::audio::{AudioListener, AudioSource, AudioSourceId};
-use crate::domain::errors::{AudioError, DomainError, PhysicsError};
+use crate::domain::errors::{AudioError, DIError, DomainError, PhysicsError};
 use crate::domain::physics::{Collider, ColliderId, PhysicsWorld, RigidBody, RigidBodyId};
 use crate::domain::scene::{Scene, SceneId, SceneManager};
 use crate::domain::value_objects::Volume;
 use rapier3d::prelude::*;
 use std::any::{Any, TypeId};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -38,13 +39,77 @@ pub trait ServiceContainer: Send + Sync {
 /// container.register_singleton(42i32);
 ///
 /// // 解析服务
-/// if let Some(value) = container.resolve::<i32>() {
+/// if let Ok(value) = container.resolve::<i32>() {
 ///     assert_eq!(*value, 42);
 /// }
 /// ```
 #[derive(Default)]
 pub struct DIContainer {
     services: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    /// 工厂注册的服务及其依赖图
+    factories: HashMap<TypeId, ServiceRegistration>,
+}
+
+/// 服务类型标识，用于声明依赖
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ServiceKey {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl ServiceKey {
+    /// 获取类型 `T` 的服务标识
+    pub fn of<T: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// 服务类型名
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+type ServiceFactory =
+    Arc<dyn Fn(&DIContainer) -> Result<Arc<dyn Any + Send + Sync>, DIError> + Send + Sync>;
+
+struct ServiceRegistration {
+    key: ServiceKey,
+    dependencies: Vec<ServiceKey>,
+    factory: ServiceFactory,
+}
+
+thread_local! {
+    /// 当前线程正在解析的服务栈，防止工厂中未声明的循环依赖导致栈溢出
+    static RESOLVING: RefCell<Vec<ServiceKey>> = const { RefCell::new(Vec::new()) };
+}
+
+/// 解析栈守卫，离开作用域时弹出
+struct ResolvingGuard;
+
+impl ResolvingGuard {
+    fn enter(key: ServiceKey) -> Result<Self, DIError> {
+        RESOLVING.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().position(|k| k.id == key.id) {
+                let mut cycle: Vec<&'static str> = stack[pos..].iter().map(|k| k.name).collect();
+                cycle.push(key.name);
+                return Err(DIError::Cycle(cycle));
+            }
+            stack.push(key);
+            Ok(ResolvingGuard)
+        })
+    }
+}
+
+impl Drop for ResolvingGuard {
+    fn drop(&mut self) {
+        RESOLVING.with(|stack| {
+            stack.borrow_mut().pop();
+        });
+    }
 }
 
 impl DIContainer {
@@ -104,13 +169,94 @@ impl DIContainer {
         self.services.insert(TypeId::of::<T>(), service);
     }
 
+    /// 注册带依赖的服务工厂
+    ///
+    /// `dependencies` 声明该服务依赖的服务类型，用于在解析时检查缺失和循环依赖。
+    /// 每次`resolve`都会调用工厂创建新实例，工厂内可通过容器解析已声明的依赖。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use game_engine::domain::services::{DIContainer, ServiceKey};
+    ///
+    /// let mut container = DIContainer::new();
+    /// container.register_singleton(2u32);
+    /// container.register_factory(vec![ServiceKey::of::<u32>()], |c| {
+    ///     Ok(*c.resolve::<u32>()? as u64 * 10)
+    /// });
+    ///
+    /// assert_eq!(*container.resolve::<u64>().unwrap(), 20);
+    /// ```
+    pub fn register_factory<T, F>(&mut self, dependencies: Vec<ServiceKey>, factory: F)
+    where
+        T: 'static + Send + Sync,
+        F: Fn(&DIContainer) -> Result<T, DIError> + Send + Sync + 'static,
+    {
+        let factory: ServiceFactory = Arc::new(move |container| {
+            factory(container).map(|service| Arc::new(service) as Arc<dyn Any + Send + Sync>)
+        });
+        self.factories.insert(
+            TypeId::of::<T>(),
+            ServiceRegistration {
+                key: ServiceKey::of::<T>(),
+                dependencies,
+                factory,
+            },
+        );
+    }
+
+    /// 获取服务声明的依赖（单例或未注册的服务返回空列表）
+    pub fn dependencies_of<T: 'static>(&self) -> Vec<ServiceKey> {
+        self.factories
+            .get(&TypeId::of::<T>())
+            .map(|reg| reg.dependencies.clone())
+            .unwrap_or_default()
+    }
+
+    /// 检查整个依赖图，报告第一个缺失或循环依赖
+    pub fn validate(&self) -> Result<(), DIError> {
+        for reg in self.factories.values() {
+            self.check_graph(reg.key, &mut Vec::new())?;
+        }
+        Ok(())
+    }
+
+    /// 深度优先检查依赖图，`path` 为当前依赖链
+    fn check_graph(&self, key: ServiceKey, path: &mut Vec<ServiceKey>) -> Result<(), DIError> {
+        if let Some(pos) = path.iter().position(|k| k.id == key.id) {
+            let mut cycle: Vec<&'static str> = path[pos..].iter().map(|k| k.name).collect();
+            cycle.push(key.name);
+            return Err(DIError::Cycle(cycle));
+        }
+        if self.services.contains_key(&key.id) {
+            return Ok(());
+        }
+        let Some(reg) = self.factories.get(&key.id) else {
+            return Err(match path.last() {
+                Some(parent) => DIError::MissingDependency {
+                    service: parent.name,
+                    dependency: key.name,
+                },
+                None => DIError::NotRegistered(key.name),
+            });
+        };
+
+        path.push(key);
+        for dep in &reg.dependencies {
+            self.check_graph(*dep, path)?;
+        }
+        path.pop();
+        Ok(())
+    }
+
     /// 获取服务实例
     ///
-    /// 从容器中解析并返回服务实例的`Arc`引用。
+    /// 从容器中解析并返回服务实例的`Arc`引用。工厂注册的服务会先检查依赖图。
     ///
     /// # 返回
     ///
-    /// 如果服务已注册，返回`Some(Arc<T>)`；否则返回`None`。
+    /// 如果服务可解析，返回`Ok(Arc<T>)`；服务未注册、依赖缺失或存在循环依赖时返回
+    /// 带有类型名的[`DIError`]。
     ///
     /// # 示例
     ///
@@ -120,14 +266,29 @@ impl DIContainer {
     /// let mut container = DIContainer::new();
     /// container.register_singleton(42i32);
     ///
-    /// if let Some(value) = container.resolve::<i32>() {
+    /// if let Ok(value) = container.resolve::<i32>() {
     ///     assert_eq!(*value, 42);
     /// }
     /// ```
-    pub fn resolve<T: 'static + Send + Sync>(&self) -> Option<Arc<T>> {
-        self.services
-            .get(&TypeId::of::<T>())
-            .and_then(|service| service.clone().downcast::<T>().ok())
+    pub fn resolve<T: 'static + Send + Sync>(&self) -> Result<Arc<T>, DIError> {
+        let key = ServiceKey::of::<T>();
+        let downcast = |service: Arc<dyn Any + Send + Sync>| {
+            service
+                .downcast::<T>()
+                .map_err(|_| DIError::NotRegistered(key.name))
+        };
+
+        if let Some(service) = self.services.get(&key.id) {
+            return downcast(service.clone());
+        }
+        let reg = self
+            .factories
+            .get(&key.id)
+            .ok_or(DIError::NotRegistered(key.name))?;
+
+        self.check_graph(key, &mut Vec::new())?;
+        let _guard = ResolvingGuard::enter(key)?;
+        downcast((reg.factory)(self)?)
     }
 
     /// 检查服务是否已注册
@@ -136,7 +297,8 @@ impl DIContainer {
     ///
     /// 如果服务已注册，返回`true`；否则返回`false`。
     pub fn is_registered<T: 'static>(&self) -> bool {
-        self.services.contains_key(&TypeId::of::<T>())
+        let id = TypeId::of::<T>();
+        self.services.contains_key(&id) || self.factories.contains_key(&id)
     }
 
     /// 移除服务
@@ -147,7 +309,10 @@ impl DIContainer {
     ///
     /// 如果服务存在并被移除，返回`true`；否则返回`false`。
     pub fn remove<T: 'static>(&mut self) -> bool {
-        self.services.remove(&TypeId::of::<T>()).is_some()
+        let id = TypeId::of::<T>();
+        let removed_service = self.services.remove(&id).is_some();
+        let removed_factory = self.factories.remove(&id).is_some();
+        removed_service || removed_factory
     }
 
     /// 清空所有服务
@@ -155,6 +320,7 @@ impl DIContainer {
     /// 移除容器中的所有服务。
     pub fn clear(&mut self) {
         self.services.clear();
+        self.factories.clear();
     }
 
     /// 获取注册的服务数量
@@ -163,7 +329,7 @@ impl DIContainer {
     ///
     /// 返回当前注册的服务数量。
     pub fn service_count(&self) -> usize {
-        self.services.len()
+        self.services.len() + self.factories.len()
     }
 }
 
@@ -173,7 +339,7 @@ impl ServiceContainer for DIContainer {
     }
 
     fn get<T: 'static + Send + Sync>(&self) -> Option<Arc<T>> {
-        self.resolve::<T>()
+        self.resolve::<T>().ok()
     }
 
     fn has<T: 'static>(&self) -> bool {
@@ -761,10 +927,75 @@ mod tests {
 
         // 解析服务
         let audio_service = container.resolve::<AudioDomainService>();
-        assert!(audio_service.is_some());
+        assert!(audio_service.is_ok());
 
         let physics_service = container.resolve::<PhysicsDomainService>();
-        assert!(physics_service.is_some());
+        assert!(physics_service.is_ok());
+    }
+
+    struct ServiceA;
+    struct ServiceB;
+
+    #[test]
+    fn test_di_container_missing_dependency() {
+        let mut container = DIContainer::new();
+        container.register_factory(vec![ServiceKey::of::<ServiceB>()], |c| {
+            c.resolve::<ServiceB>()?;
+            Ok(ServiceA)
+        });
+
+        let err = container.resolve::<ServiceA>().err().unwrap();
+        assert_eq!(
+            err,
+            DIError::MissingDependency {
+                service: std::any::type_name::<ServiceA>(),
+                dependency: std::any::type_name::<ServiceB>(),
+            }
+        );
+        assert!(err.to_string().contains("ServiceB"));
+
+        container.register_singleton(ServiceB);
+        assert!(container.resolve::<ServiceA>().is_ok());
+        assert!(container.validate().is_ok());
+    }
+
+    #[test]
+    fn test_di_container_cyclic_dependency() {
+        let mut container = DIContainer::new();
+        container.register_factory(vec![ServiceKey::of::<ServiceB>()], |c| {
+            c.resolve::<ServiceB>()?;
+            Ok(ServiceA)
+        });
+        container.register_factory(vec![ServiceKey::of::<ServiceA>()], |c| {
+            c.resolve::<ServiceA>()?;
+            Ok(ServiceB)
+        });
+
+        let err = container.resolve::<ServiceA>().err().unwrap();
+        assert_eq!(
+            err,
+            DIError::Cycle(vec![
+                std::any::type_name::<ServiceA>(),
+                std::any::type_name::<ServiceB>(),
+                std::any::type_name::<ServiceA>(),
+            ])
+        );
+        assert!(matches!(container.validate(), Err(DIError::Cycle(_))));
+    }
+
+    #[test]
+    fn test_di_container_undeclared_cycle_does_not_overflow() {
+        let mut container = DIContainer::new();
+        // 工厂未声明依赖，但运行时仍会解析自身
+        container.register_factory(Vec::new(), |c| {
+            c.resolve::<ServiceA>()?;
+            Ok(ServiceA)
+        });
+
+        assert!(matches!(
+            container.resolve::<ServiceA>(),
+            Err(DIError::Cycle(_))
+        ));
     }
 
     #[test]
@@ -0,0 +1,71 @@
+//! 类型化游戏事件
+//!
+//! 直接使用 `bevy_ecs` 的事件类型：`Events<T>` 作为资源保存事件，内部双缓冲，
+//! 每次 `update` 交换缓冲区并丢弃上上一帧的事件，因此事件在发送的当帧和下一帧
+//! 都可读取，不论读取系统排在发送系统之前还是之后。系统中通过 `EventReader<T>`
+//! 和 `EventWriter<T>` 读写，系统外使用 `ManualEventReader<T>` 独立记录已读位置。
+
+use bevy_ecs::system::ResMut;
+
+pub use bevy_ecs::event::{Event, EventReader, EventWriter, Events, ManualEventReader};
+
+/// 每帧交换 `Events<T>` 缓冲区的系统
+pub fn update_events_system<T: Event>(mut events: ResMut<Events<T>>) {
+    events.update();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::prelude::{Schedule, World};
+
+    #[derive(Event, Debug, PartialEq)]
+    struct EnemyDied {
+        id: u32,
+    }
+
+    #[test]
+    fn test_independent_readers() {
+        let mut events = Events::<EnemyDied>::default();
+        let mut ui_reader = events.get_reader();
+        let mut score_reader = events.get_reader();
+
+        events.send(EnemyDied { id: 7 });
+
+        let ui: Vec<_> = ui_reader.read(&events).collect();
+        assert_eq!(ui, vec![&EnemyDied { id: 7 }]);
+        // 读取后不再重复返回
+        assert_eq!(ui_reader.read(&events).count(), 0);
+
+        let score: Vec<_> = score_reader.read(&events).collect();
+        assert_eq!(score, vec![&EnemyDied { id: 7 }]);
+    }
+
+    #[test]
+    fn test_events_cleared_after_swap() {
+        let mut world = World::new();
+        world.init_resource::<Events<EnemyDied>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_events_system::<EnemyDied>);
+
+        let mut late_reader = world.resource::<Events<EnemyDied>>().get_reader();
+        world.send_event(EnemyDied { id: 1 });
+
+        // 下一帧仍可读取
+        schedule.run(&mut world);
+        let events = world.resource::<Events<EnemyDied>>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.get_reader().len(events), 1);
+
+        // 再次交换后被丢弃
+        schedule.run(&mut world);
+        let events = world.resource::<Events<EnemyDied>>();
+        assert!(events.is_empty());
+        assert_eq!(late_reader.read(events).count(), 0);
+
+        world.send_event(EnemyDied { id: 2 });
+        let events = world.resource::<Events<EnemyDied>>();
+        let ids: Vec<_> = late_reader.read(events).map(|e| e.id).collect();
+        assert_eq!(ids, vec![2]);
+    }
+}
@@ -0,0 +1,257 @@
+//! 父子层级与变换传播
+//!
+//! `Transform` 表示相对父节点的局部变换，`propagate_transforms` 沿层级
+//! 自上而下组合局部变换，写入每个实体的 `GlobalTransform`。
+
+use super::Transform;
+use bevy_ecs::prelude::*;
+use glam::{Mat4, Quat, Vec3};
+use std::collections::HashSet;
+
+/// 父实体引用
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parent(pub Entity);
+
+/// 子实体列表，由父实体持有
+#[derive(Component, Clone, Debug, Default)]
+pub struct Children(pub Vec<Entity>);
+
+/// 世界空间变换，由 `propagate_transforms` 计算，不应手动修改
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GlobalTransform(pub Mat4);
+
+impl Default for GlobalTransform {
+    fn default() -> Self {
+        Self(Mat4::IDENTITY)
+    }
+}
+
+impl GlobalTransform {
+    /// 世界空间位置
+    pub fn translation(&self) -> Vec3 {
+        self.0.w_axis.truncate()
+    }
+
+    /// 分解为缩放、旋转、平移
+    pub fn to_scale_rotation_translation(&self) -> (Vec3, Quat, Vec3) {
+        self.0.to_scale_rotation_translation()
+    }
+
+    /// 将局部空间的点变换到世界空间
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.0.transform_point3(point)
+    }
+
+    /// 保留父级部分，把局部变换 `current` 替换为 `local`
+    ///
+    /// 渲染插值时局部变换在两帧之间插值，父级全局变换沿用本帧的传播结果。
+    /// `current` 不可逆（缩放为零）时直接返回当前全局变换。
+    pub fn with_local(&self, current: &Transform, local: &Transform) -> Mat4 {
+        let current = Self::from(current).0;
+        if current.determinant().abs() <= f32::EPSILON {
+            return self.0;
+        }
+        self.0 * current.inverse() * Self::from(local).0
+    }
+}
+
+impl From<&Transform> for GlobalTransform {
+    fn from(t: &Transform) -> Self {
+        Self(Mat4::from_scale_rotation_translation(t.scale, t.rot, t.pos))
+    }
+}
+
+/// 建立父子关系，同时维护双方的 `Parent` / `Children` 组件
+///
+/// 双方缺少 `GlobalTransform` 时一并插入，渲染提取读取的是传播后的世界变换。
+pub fn set_parent(world: &mut World, child: Entity, parent: Entity) {
+    if let Some(Parent(old)) = world.get::<Parent>(child).copied() {
+        if let Some(mut children) = world.get_mut::<Children>(old) {
+            children.0.retain(|&e| e != child);
+        }
+    }
+    world.entity_mut(child).insert(Parent(parent));
+    for entity in [child, parent] {
+        if world.get::<GlobalTransform>(entity).is_none() {
+            let global = world
+                .get::<Transform>(entity)
+                .map_or_else(GlobalTransform::default, GlobalTransform::from);
+            world.entity_mut(entity).insert(global);
+        }
+    }
+    match world.get_mut::<Children>(parent) {
+        Some(mut children) => {
+            if !children.0.contains(&child) {
+                children.0.push(child);
+            }
+        }
+        None => {
+            world.entity_mut(parent).insert(Children(vec![child]));
+        }
+    }
+}
+
+/// 变换传播系统
+///
+/// 从没有 `Parent` 的根实体出发深度优先遍历 `Children`，
+/// 为每个实体计算 `GlobalTransform = 父级全局变换 * 局部变换`。
+/// 缺少 `GlobalTransform` 的实体不写入结果，但其子节点照常传播。
+/// 父实体已失效的节点按根处理；层级中出现环时发出警告并在环上任选一点断开。
+pub fn propagate_transforms(
+    roots: Query<Entity, (With<Transform>, Without<Parent>)>,
+    orphans: Query<(Entity, &Parent), With<Transform>>,
+    nodes: Query<(&Transform, Option<&Children>)>,
+    mut globals: Query<&mut GlobalTransform>,
+) {
+    let mut visited = HashSet::new();
+
+    for root in roots.iter() {
+        propagate_recursive(root, Mat4::IDENTITY, &nodes, &mut globals, &mut visited);
+    }
+
+    // 未从根到达的节点：父实体失效、父节点未登记该子节点，或处于环中
+    for (entity, _) in orphans.iter() {
+        if visited.contains(&entity) {
+            continue;
+        }
+        // 沿 Parent 链向上找到最高的未访问祖先
+        let mut top = entity;
+        let mut chain = HashSet::new();
+        while let Ok((_, parent)) = orphans.get(top) {
+            if !chain.insert(top) {
+                tracing::warn!(
+                    "Transform hierarchy cycle detected at {:?}, using local transform",
+                    top
+                );
+                break;
+            }
+            if visited.contains(&parent.0) || nodes.get(parent.0).is_err() {
+                break;
+            }
+            top = parent.0;
+        }
+
+        let parent_matrix = orphans
+            .get(top)
+            .ok()
+            .filter(|(_, parent)| visited.contains(&parent.0))
+            .and_then(|(_, parent)| globals.get(parent.0).ok())
+            .map_or(Mat4::IDENTITY, |global| global.0);
+        propagate_recursive(top, parent_matrix, &nodes, &mut globals, &mut visited);
+    }
+}
+
+fn propagate_recursive(
+    entity: Entity,
+    parent_matrix: Mat4,
+    nodes: &Query<(&Transform, Option<&Children>)>,
+    globals: &mut Query<&mut GlobalTransform>,
+    visited: &mut HashSet<Entity>,
+) {
+    if !visited.insert(entity) {
+        tracing::warn!(
+            "Transform hierarchy cycle detected at {:?}, skipping",
+            entity
+        );
+        return;
+    }
+    let Ok((transform, children)) = nodes.get(entity) else {
+        return;
+    };
+
+    let matrix = parent_matrix * GlobalTransform::from(transform).0;
+    if let Ok(mut global) = globals.get_mut(entity) {
+        global.0 = matrix;
+    }
+
+    if let Some(children) = children {
+        for &child in &children.0 {
+            propagate_recursive(child, matrix, nodes, globals, visited);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn_node(world: &mut World, pos: Vec3) -> Entity {
+        world
+            .spawn((
+                Transform {
+                    pos,
+                    rot: Quat::IDENTITY,
+                    scale: Vec3::ONE,
+                },
+                GlobalTransform::default(),
+            ))
+            .id()
+    }
+
+    fn run_propagation(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(propagate_transforms);
+        schedule.run(world);
+    }
+
+    #[test]
+    fn test_two_level_hierarchy_rotation() {
+        let mut world = World::new();
+        let root = spawn_node(&mut world, Vec3::new(10.0, 0.0, 0.0));
+        let child = spawn_node(&mut world, Vec3::new(1.0, 0.0, 0.0));
+        let grandchild = spawn_node(&mut world, Vec3::new(1.0, 0.0, 0.0));
+        set_parent(&mut world, child, root);
+        set_parent(&mut world, grandchild, child);
+
+        run_propagation(&mut world);
+        let pos = world
+            .get::<GlobalTransform>(grandchild)
+            .unwrap()
+            .translation();
+        assert!((pos - Vec3::new(12.0, 0.0, 0.0)).length() < 1e-5);
+
+        // 根节点绕Z轴旋转90°，孙节点随之转到+Y方向
+        world.get_mut::<Transform>(root).unwrap().rot =
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        run_propagation(&mut world);
+        let pos = world
+            .get::<GlobalTransform>(grandchild)
+            .unwrap()
+            .translation();
+        assert!(
+            (pos - Vec3::new(10.0, 2.0, 0.0)).length() < 1e-5,
+            "pos = {pos}"
+        );
+    }
+
+    #[test]
+    fn test_cycle_does_not_hang() {
+        let mut world = World::new();
+        let a = spawn_node(&mut world, Vec3::X);
+        let b = spawn_node(&mut world, Vec3::Y);
+        set_parent(&mut world, a, b);
+        set_parent(&mut world, b, a);
+
+        run_propagation(&mut world);
+        let pos_a = world.get::<GlobalTransform>(a).unwrap().translation();
+        let pos_b = world.get::<GlobalTransform>(b).unwrap().translation();
+        assert!(pos_a.is_finite() && pos_b.is_finite());
+    }
+
+    #[test]
+    fn test_set_parent_inserts_global_transform() {
+        let mut world = World::new();
+        let parent = world.spawn(Transform::default()).id();
+        let child = world
+            .spawn(Transform {
+                pos: Vec3::new(1.0, 2.0, 3.0),
+                ..Default::default()
+            })
+            .id();
+        set_parent(&mut world, child, parent);
+
+        assert!(world.get::<GlobalTransform>(parent).is_some());
+        let global = world.get::<GlobalTransform>(child).unwrap();
+        assert_eq!(global.translation(), Vec3::new(1.0, 2.0, 3.0));
+    }
+}
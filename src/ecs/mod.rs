@@ -1,12 +1,19 @@
 use crate::impl_default;
 use crate::impl_default_and_new;
 use bevy_ecs::prelude::*;
-use glam::{Quat, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4Swizzles};
 
+pub mod events;
+pub mod hierarchy;
 pub mod soa_layout;
+pub use events::{
+    update_events_system, Event, EventReader, EventWriter, Events, ManualEventReader,
+};
+pub use hierarchy::{propagate_transforms, set_parent, Children, GlobalTransform, Parent};
 pub use soa_layout::{SoALayoutManager, SoAStats, SoATransformStorage, SoAVelocityStorage};
 
 #[derive(Component, Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Transform {
     pub pos: Vec3,
     pub rot: Quat,
@@ -34,7 +41,8 @@ impl Velocity {
 
 // 注意：Velocity已经使用#[derive(Default)]，new()方法调用default()是正确的模式
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Sprite {
     pub color: [f32; 4],
     pub tex_index: u32,
@@ -53,7 +61,39 @@ impl_default_and_new!(Sprite {
     layer: 0.0,
 });
 
-#[derive(Component, Clone, Debug)]
+/// 9-slice 边框（纹素），用于UI面板等缩放时边框不应拉伸的精灵
+///
+/// 四角保持原始尺寸，四边和中心拉伸填满精灵尺寸；边框全为 0 时与普通精灵一致。
+/// `top` 对应纹理 v 较小的一侧。
+#[derive(
+    Component, Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(default)]
+pub struct NineSlice {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl NineSlice {
+    /// 四边使用相同宽度的边框
+    pub fn uniform(border: f32) -> Self {
+        Self {
+            left: border,
+            right: border,
+            top: border,
+            bottom: border,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.left == 0.0 && self.right == 0.0 && self.top == 0.0 && self.bottom == 0.0
+    }
+}
+
+#[derive(Component, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct PointLight {
     pub color: [f32; 3],
     pub intensity: f32,
@@ -68,7 +108,7 @@ impl_default_and_new!(PointLight {
     falloff: 1.0,
 });
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum Projection {
     Orthographic {
         scale: f32,
@@ -100,7 +140,8 @@ impl Projection {
     }
 }
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Camera {
     pub is_active: bool,
     pub projection: Projection,
@@ -111,12 +152,97 @@ impl_default_and_new!(Camera {
     projection: Projection::default(),
 });
 
+impl Projection {
+    /// 投影矩阵，正交投影的宽高比取自视口
+    pub fn matrix(&self, viewport_aspect: f32) -> Mat4 {
+        match *self {
+            Projection::Orthographic { scale, near, far } => Mat4::orthographic_rh(
+                -viewport_aspect * scale,
+                viewport_aspect * scale,
+                -scale,
+                scale,
+                near,
+                far,
+            ),
+            Projection::Perspective {
+                fov,
+                aspect,
+                near,
+                far,
+            } => Mat4::perspective_rh(fov, aspect, near, far),
+        }
+    }
+
+    /// 近、远裁剪面距离
+    pub fn clip_planes(&self) -> (f32, f32) {
+        match *self {
+            Projection::Orthographic { near, far, .. }
+            | Projection::Perspective { near, far, .. } => (near, far),
+        }
+    }
+}
+
+impl Camera {
+    /// 相机的视图投影矩阵
+    pub fn view_projection(&self, transform: &Transform, viewport: Viewport) -> Mat4 {
+        let aspect = viewport.width.max(1) as f32 / viewport.height.max(1) as f32;
+        let view = Mat4::from_rotation_translation(transform.rot, transform.pos).inverse();
+        self.projection.matrix(aspect) * view
+    }
+
+    /// 将屏幕坐标（像素，左上角为原点）转换为世界空间射线
+    ///
+    /// 返回 `(origin, direction)`，原点位于近裁剪面上，方向已归一化。
+    pub fn screen_to_ray(
+        &self,
+        transform: &Transform,
+        screen_pos: Vec2,
+        viewport: Viewport,
+    ) -> (Vec3, Vec3) {
+        let size = Vec2::new(viewport.width.max(1) as f32, viewport.height.max(1) as f32);
+        let ndc = Vec2::new(
+            screen_pos.x / size.x * 2.0 - 1.0,
+            1.0 - screen_pos.y / size.y * 2.0,
+        );
+        let inv_view_proj = self.view_projection(transform, viewport).inverse();
+        let near = inv_view_proj.project_point3(ndc.extend(0.0));
+        let far = inv_view_proj.project_point3(ndc.extend(1.0));
+        (near, (far - near).normalize())
+    }
+
+    /// 将世界坐标投影到屏幕坐标（像素，左上角为原点）
+    ///
+    /// 点位于相机后方时返回 `None`；视口外的点仍会返回坐标。
+    pub fn world_to_screen(
+        &self,
+        transform: &Transform,
+        world_pos: Vec3,
+        viewport: Viewport,
+    ) -> Option<Vec2> {
+        let view = Mat4::from_rotation_translation(transform.rot, transform.pos).inverse();
+        // 相机朝向 -Z
+        if view.transform_point3(world_pos).z >= 0.0 {
+            return None;
+        }
+        let clip = self.view_projection(transform, viewport) * world_pos.extend(1.0);
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+        let ndc = clip.xy() / clip.w;
+        Some(Vec2::new(
+            (ndc.x + 1.0) * 0.5 * viewport.width as f32,
+            (1.0 - ndc.y) * 0.5 * viewport.height as f32,
+        ))
+    }
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct Mesh {
     pub handle: crate::resources::manager::Handle<crate::render::mesh::GpuMesh>,
 }
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Material {
     pub color: [f32; 4],
     pub metallic: f32,
@@ -185,8 +311,16 @@ impl_default_and_new!(DirectionalLightComp {
 
 #[derive(Resource)]
 pub struct Time {
+    /// 经过时间缩放的帧间隔，游戏逻辑应读取此值
     pub delta_seconds: f32,
+    /// 经过时间缩放的累计时间
     pub elapsed_seconds: f64,
+    /// 未缩放的真实帧间隔，适用于 UI 等不受慢动作/暂停影响的系统
+    pub unscaled_delta_seconds: f32,
+    /// 未缩放的累计真实时间
+    pub unscaled_elapsed_seconds: f64,
+    /// 全局时间缩放，1.0 为正常速度，0.0 暂停游戏逻辑
+    pub time_scale: f64,
     pub fixed_time_step: f64,
     pub alpha: f64,
 }
@@ -194,10 +328,32 @@ pub struct Time {
 impl_default!(Time {
     delta_seconds: 0.0,
     elapsed_seconds: 0.0,
+    unscaled_delta_seconds: 0.0,
+    unscaled_elapsed_seconds: 0.0,
+    time_scale: 1.0,
     fixed_time_step: 1.0 / 60.0,
     alpha: 0.0,
 });
 
+impl Time {
+    /// 以真实帧间隔推进时间，返回缩放后的间隔
+    ///
+    /// 负的 `time_scale` 按 0 处理，时间不会倒流。
+    pub fn advance(&mut self, unscaled_delta: f32) -> f64 {
+        let scaled = unscaled_delta as f64 * self.time_scale.max(0.0);
+        self.unscaled_delta_seconds = unscaled_delta;
+        self.unscaled_elapsed_seconds += unscaled_delta as f64;
+        self.delta_seconds = scaled as f32;
+        self.elapsed_seconds += scaled;
+        scaled
+    }
+
+    /// 游戏逻辑是否处于暂停状态（`time_scale` 为 0）
+    pub fn is_paused(&self) -> bool {
+        self.time_scale <= 0.0
+    }
+}
+
 #[derive(Component, Clone, Copy, Debug)]
 pub struct PreviousTransform {
     pub pos: Vec3,
@@ -216,21 +372,168 @@ pub struct Viewport {
     pub height: u32,
 }
 
+/// 按纹理索引排列的纹理像素尺寸，由渲染器每帧同步，用于把 `NineSlice` 边框换算为UV
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct TextureSizes(pub Vec<[u32; 2]>);
+
+impl TextureSizes {
+    pub fn get(&self, tex_index: u32) -> Option<[u32; 2]> {
+        self.0.get(tex_index as usize).copied()
+    }
+}
+
 #[derive(Resource, Clone, Copy, Debug, Default)]
 pub struct TileChunkConfig {
     pub size: [u32; 2],
 }
 
+/// 单个瓦片的翻转/旋转标记，可按位组合
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TileFlags(pub u8);
+
+impl TileFlags {
+    pub const NONE: Self = Self(0);
+    /// 水平翻转
+    pub const FLIP_X: Self = Self(1 << 0);
+    /// 垂直翻转
+    pub const FLIP_Y: Self = Self(1 << 1);
+    /// 顺时针旋转90°，在翻转之后应用
+    pub const ROTATE_90: Self = Self(1 << 2);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// 将翻转应用到图集UV矩形，返回 `(uv_off, uv_scale)`
+    ///
+    /// 翻转通过负的 `uv_scale` 并把 `uv_off` 移到对侧实现。
+    pub fn apply_uv(self, uv_off: [f32; 2], uv_scale: [f32; 2]) -> ([f32; 2], [f32; 2]) {
+        let mut off = uv_off;
+        let mut scale = uv_scale;
+        for (axis, flag) in [(0, Self::FLIP_X), (1, Self::FLIP_Y)] {
+            if self.contains(flag) {
+                off[axis] += scale[axis];
+                scale[axis] = -scale[axis];
+            }
+        }
+        (off, scale)
+    }
+
+    /// 绕Z轴的旋转角（弧度）
+    pub fn rotation(self) -> f32 {
+        if self.contains(Self::ROTATE_90) {
+            -std::f32::consts::FRAC_PI_2
+        } else {
+            0.0
+        }
+    }
+
+    /// 精灵尺寸，旋转90°时交换宽高以填满格子
+    pub fn sprite_size(self, tile_size: [f32; 2]) -> [f32; 2] {
+        if self.contains(Self::ROTATE_90) {
+            [tile_size[1], tile_size[0]]
+        } else {
+            tile_size
+        }
+    }
+}
+
+impl std::ops::BitOr for TileFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct TileMap {
     pub width: u32,
     pub height: u32,
     pub tile_size: [f32; 2],
     pub tiles: Vec<String>,
+    /// 与 `tiles` 平行的翻转/旋转标记，缺失的条目视为 `TileFlags::NONE`
+    pub flags: Vec<TileFlags>,
     pub layer: f32,
     pub atlas_tex_index: u32,
     pub dirty: bool,
     pub chunk_size: [u32; 2],
+    pub render_mode: crate::render::tilemap::TileMapRenderMode,
+}
+
+impl TileMap {
+    /// 第 `idx` 个瓦片的标记
+    pub fn tile_flags(&self, idx: usize) -> TileFlags {
+        self.flags.get(idx).copied().unwrap_or_default()
+    }
+
+    /// 遍历chunk `(cx, cy)` 内非空且已在图集中登记的瓦片
+    ///
+    /// 回调参数依次为瓦片索引、瓦片中心的世界坐标和图集UV。
+    pub(crate) fn for_each_chunk_tile(
+        &self,
+        tileset: &TileSet,
+        origin: Vec3,
+        (cx, cy): (i32, i32),
+        mut f: impl FnMut(usize, Vec3, [f32; 2], [f32; 2]),
+    ) {
+        let base_x = origin.x + cx as f32 * self.chunk_size[0] as f32 * self.tile_size[0];
+        let base_y = origin.y + cy as f32 * self.chunk_size[1] as f32 * self.tile_size[1];
+        let tiles_w = self.width as i32;
+        let tiles_h = self.height as i32;
+        for ty in 0..self.chunk_size[1] as i32 {
+            for tx in 0..self.chunk_size[0] as i32 {
+                let gx = cx * self.chunk_size[0] as i32 + tx;
+                let gy = cy * self.chunk_size[1] as i32 + ty;
+                if gx < 0 || gy < 0 || gx >= tiles_w || gy >= tiles_h {
+                    continue;
+                }
+                let idx = (gy as u32 * self.width + gx as u32) as usize;
+                let Some(id) = self.tiles.get(idx) else {
+                    continue;
+                };
+                if id.is_empty() {
+                    continue;
+                }
+                if let Some((uv_off, uv_scale)) = tileset.tiles.get(id).cloned() {
+                    let pos = Vec3::new(
+                        base_x + (tx as f32 + 0.5) * self.tile_size[0],
+                        base_y + (ty as f32 + 0.5) * self.tile_size[1],
+                        origin.z,
+                    );
+                    f(idx, pos, uv_off, uv_scale);
+                }
+            }
+        }
+    }
+
+    /// 应用瓦片标记后的精灵变换和UV
+    fn tile_sprite(
+        &self,
+        idx: usize,
+        pos: Vec3,
+        uv_off: [f32; 2],
+        uv_scale: [f32; 2],
+    ) -> (Transform, Sprite) {
+        let flags = self.tile_flags(idx);
+        let (uv_off, uv_scale) = flags.apply_uv(uv_off, uv_scale);
+        let [w, h] = flags.sprite_size(self.tile_size);
+        (
+            Transform {
+                pos,
+                rot: Quat::from_rotation_z(flags.rotation()),
+                scale: Vec3::new(w, h, 1.0),
+            },
+            Sprite {
+                color: [1.0, 1.0, 1.0, 1.0],
+                tex_index: self.atlas_tex_index,
+                normal_tex_index: 0,
+                uv_off,
+                uv_scale,
+                layer: self.layer,
+            },
+        )
+    }
 }
 
 pub fn tilemap_build_system(
@@ -248,7 +551,7 @@ pub fn tilemap_build_system(
         .map(|v| (v.width as f32, v.height as f32))
         .unwrap_or((800.0, 600.0));
     for (entity, t_base, mut tm) in query.iter_mut() {
-        if !tm.dirty {
+        if !tm.dirty || tm.render_mode == crate::render::tilemap::TileMapRenderMode::Instanced {
             continue;
         }
         // 记录正在更新的实体，用于调试和性能监控
@@ -274,22 +577,8 @@ pub fn tilemap_build_system(
                         continue;
                     }
                     let pos = Vec3::new(pos_x, pos_y, t_base.pos.z);
-                    commands.spawn((
-                        Transform {
-                            pos,
-                            rot: Quat::IDENTITY,
-                            scale: Vec3::new(tm.tile_size[0], tm.tile_size[1], 1.0),
-                        },
-                        PreviousTransform::default(),
-                        Sprite {
-                            color: [1.0, 1.0, 1.0, 1.0],
-                            tex_index: tm.atlas_tex_index,
-                            normal_tex_index: 0,
-                            uv_off,
-                            uv_scale,
-                            layer: tm.layer,
-                        },
-                    ));
+                    let (transform, sprite) = tm.tile_sprite(idx, pos, uv_off, uv_scale);
+                    commands.spawn((transform, PreviousTransform::default(), sprite));
                 }
             }
         }
@@ -353,10 +642,12 @@ pub struct ChunkTag {
     pub cy: i32,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn tilemap_chunk_system(
     mut commands: Commands,
     mut pool: ResMut<TileEntityPool>,
     mut maps: Query<(Entity, &Transform, &mut TileMap, Option<&mut TileChunks>)>,
+    mut chunk_instances: Query<&mut crate::render::tilemap::TileChunkInstances>,
     tileset: Option<Res<TileSet>>,
     viewport: Option<Res<Viewport>>,
     cam_q: Query<(&Transform, &Camera)>,
@@ -377,7 +668,7 @@ pub fn tilemap_chunk_system(
             break;
         }
     }
-    for (map_e, t_base, tm, opt_chunks) in maps.iter_mut() {
+    for (map_e, t_base, mut tm, opt_chunks) in maps.iter_mut() {
         let current_visible = if let Some(ch) = opt_chunks.as_ref() {
             ch.visible.clone()
         } else {
@@ -402,71 +693,70 @@ pub fn tilemap_chunk_system(
             }
         }
 
-        // 回收不再可见的chunk中的tile实体
-        for &(cx, cy) in current_visible.iter() {
-            if !new_vis.contains(&(cx, cy)) {
-                for (ent, tag) in chunk_entities.iter() {
-                    if tag.map == map_e && tag.cx == cx && tag.cy == cy {
-                        pool.recycle(ent, &mut commands);
-                    }
+        if tm.render_mode == crate::render::tilemap::TileMapRenderMode::Instanced {
+            // 只为可见chunk构建实例缓冲区，不生成瓦片实体
+            let mut created = None;
+            let inst = match chunk_instances.get_mut(map_e) {
+                Ok(inst) => inst.into_inner(),
+                Err(_) => created.insert(crate::render::tilemap::TileChunkInstances::default()),
+            };
+            let before = inst.chunks.len();
+            inst.chunks.retain(|key, _| new_vis.contains(key));
+            let mut changed = inst.chunks.len() != before;
+            for &key in new_vis.iter() {
+                if !tm.dirty && current_visible.contains(&key) {
+                    continue;
+                }
+                let instances =
+                    crate::render::tilemap::build_chunk_instances(&tm, &ts, t_base.pos, key);
+                if instances.is_empty() {
+                    changed |= inst.chunks.remove(&key).is_some();
+                } else {
+                    inst.chunks.insert(key, instances);
+                    changed = true;
                 }
             }
-        }
-
-        // 为新可见的chunk创建tile实体，使用实体池
-        for (cx, cy) in new_vis.iter() {
-            if !current_visible.contains(&(*cx, *cy)) {
-                let base_x = t_base.pos.x + *cx as f32 * cx_sz;
-                let base_y = t_base.pos.y + *cy as f32 * cy_sz;
-                let tiles_w = tm.width as i32;
-                let tiles_h = tm.height as i32;
-                for ty in 0..tm.chunk_size[1] as i32 {
-                    for tx in 0..tm.chunk_size[0] as i32 {
-                        let gx = *cx * tm.chunk_size[0] as i32 + tx;
-                        let gy = *cy * tm.chunk_size[1] as i32 + ty;
-                        if gx < 0 || gy < 0 || gx >= tiles_w || gy >= tiles_h {
-                            continue;
-                        }
-                        let idx = (gy as u32 * tm.width + gx as u32) as usize;
-                        if idx >= tm.tiles.len() {
-                            continue;
-                        }
-                        let id = tm.tiles[idx].clone();
-                        if id.is_empty() {
-                            continue;
-                        }
-                        if let Some((uv_off, uv_scale)) = ts.tiles.get(&id).cloned() {
-                            let pos = glam::Vec3::new(
-                                base_x + (tx as f32 + 0.5) * tm.tile_size[0],
-                                base_y + (ty as f32 + 0.5) * tm.tile_size[1],
-                                t_base.pos.z,
-                            );
-                            let entity = pool.get_or_spawn(&mut commands);
-                            commands.entity(entity).insert((
-                                Transform {
-                                    pos,
-                                    rot: Quat::IDENTITY,
-                                    scale: glam::Vec3::new(tm.tile_size[0], tm.tile_size[1], 1.0),
-                                },
-                                PreviousTransform::default(),
-                                Sprite {
-                                    color: [1.0, 1.0, 1.0, 1.0],
-                                    tex_index: tm.atlas_tex_index,
-                                    normal_tex_index: 0,
-                                    uv_off,
-                                    uv_scale,
-                                    layer: tm.layer,
-                                },
-                                ChunkTag {
-                                    map: map_e,
-                                    cx: *cx,
-                                    cy: *cy,
-                                },
-                            ));
+            if changed {
+                inst.revision += 1;
+            }
+            if let Some(inst) = created {
+                commands.entity(map_e).insert(inst);
+            }
+            if tm.dirty {
+                tm.dirty = false;
+            }
+        } else {
+            // 回收不再可见的chunk中的tile实体
+            for &(cx, cy) in current_visible.iter() {
+                if !new_vis.contains(&(cx, cy)) {
+                    for (ent, tag) in chunk_entities.iter() {
+                        if tag.map == map_e && tag.cx == cx && tag.cy == cy {
+                            pool.recycle(ent, &mut commands);
                         }
                     }
                 }
             }
+
+            // 为新可见的chunk创建tile实体，使用实体池
+            for &key in new_vis.iter() {
+                if current_visible.contains(&key) {
+                    continue;
+                }
+                tm.for_each_chunk_tile(&ts, t_base.pos, key, |idx, pos, uv_off, uv_scale| {
+                    let (transform, sprite) = tm.tile_sprite(idx, pos, uv_off, uv_scale);
+                    let entity = pool.get_or_spawn(&mut commands);
+                    commands.entity(entity).insert((
+                        transform,
+                        PreviousTransform::default(),
+                        sprite,
+                        ChunkTag {
+                            map: map_e,
+                            cx: key.0,
+                            cy: key.1,
+                        },
+                    ));
+                });
+            }
         }
         if let Some(mut ch) = opt_chunks {
             ch.visible = new_vis;
@@ -151,6 +151,16 @@ impl SoATransformStorage {
         }
     }
 
+    /// 各稠密数组的内存占用 `(已分配字节, 已使用字节)`
+    pub fn memory_usage(&self) -> (usize, usize) {
+        sum_usage(&[
+            vec_usage(&self.positions),
+            vec_usage(&self.rotations),
+            vec_usage(&self.scales),
+            vec_usage(&self.index_to_entity),
+        ])
+    }
+
     /// 转换为ECS组件（用于同步回ECS）
     pub fn sync_to_ecs(&self, mut commands: Commands) {
         for (entity, &index) in &self.entity_to_index {
@@ -225,6 +235,27 @@ impl SoAVelocityStorage {
     pub fn len(&self) -> usize {
         self.linear_velocities.len()
     }
+
+    /// 各稠密数组的内存占用 `(已分配字节, 已使用字节)`
+    pub fn memory_usage(&self) -> (usize, usize) {
+        sum_usage(&[
+            vec_usage(&self.linear_velocities),
+            vec_usage(&self.angular_velocities),
+            vec_usage(&self.index_to_entity),
+        ])
+    }
+}
+
+/// 单个数组的 `(容量字节, 长度字节)`
+fn vec_usage<T>(v: &Vec<T>) -> (usize, usize) {
+    let size = std::mem::size_of::<T>();
+    (v.capacity() * size, v.len() * size)
+}
+
+fn sum_usage(usages: &[(usize, usize)]) -> (usize, usize) {
+    usages
+        .iter()
+        .fold((0, 0), |(alloc, used), &(a, u)| (alloc + a, used + u))
 }
 
 /// SoA布局管理器
@@ -301,11 +332,25 @@ impl SoALayoutManager {
     }
 
     /// 获取统计信息
+    ///
+    /// 内存统计只计入稠密数组，不含实体到索引的哈希表。
     pub fn stats(&self) -> SoAStats {
+        let (bytes_allocated, bytes_used) = sum_usage(&[
+            self.transforms.memory_usage(),
+            self.velocities.memory_usage(),
+        ]);
+        let fragmentation_ratio = if bytes_allocated == 0 {
+            0.0
+        } else {
+            1.0 - bytes_used as f32 / bytes_allocated as f32
+        };
         SoAStats {
             transform_count: self.transforms.len(),
             velocity_count: self.velocities.len(),
             enabled: self.enabled,
+            bytes_allocated,
+            bytes_used,
+            fragmentation_ratio,
         }
     }
 }
@@ -316,6 +361,12 @@ pub struct SoAStats {
     pub transform_count: usize,
     pub velocity_count: usize,
     pub enabled: bool,
+    /// 所有SoA数组按容量分配的字节数
+    pub bytes_allocated: usize,
+    /// 所有SoA数组中存活元素占用的字节数
+    pub bytes_used: usize,
+    /// 未使用容量占已分配字节的比例，0表示完全紧凑
+    pub fragmentation_ratio: f32,
 }
 
 #[cfg(test)]
@@ -406,4 +457,32 @@ mod tests {
         assert_eq!(stats.transform_count, 0);
         assert_eq!(stats.velocity_count, 0);
     }
+
+    #[test]
+    fn test_soa_memory_stats() {
+        let mut manager = SoALayoutManager::new();
+        let stats = manager.stats();
+        assert_eq!(stats.bytes_allocated, 0);
+        assert_eq!(stats.fragmentation_ratio, 0.0);
+
+        let transforms = manager.transforms_mut();
+        for i in 0..8 {
+            transforms.add_entity(Entity::from_raw(i), Transform::default());
+        }
+        for i in 0..3 {
+            assert!(transforms.remove_entity(Entity::from_raw(i)));
+        }
+
+        let per_entity = std::mem::size_of::<Vec3>() * 2
+            + std::mem::size_of::<Quat>()
+            + std::mem::size_of::<Entity>();
+        let stats = manager.stats();
+        assert_eq!(stats.transform_count, 5);
+        assert_eq!(stats.bytes_used, 5 * per_entity);
+        assert!(stats.bytes_allocated >= 8 * per_entity);
+
+        let expected = 1.0 - stats.bytes_used as f32 / stats.bytes_allocated as f32;
+        assert!(stats.fragmentation_ratio > 0.0);
+        assert!((stats.fragmentation_ratio - expected).abs() < 1e-6);
+    }
 }
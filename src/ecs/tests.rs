@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use crate::ecs::{PointLight, Sprite, Transform};
+    use crate::ecs::{
+        tilemap_build_system, tilemap_chunk_system, Camera, ChunkTag, PointLight, Projection,
+        Sprite, TileEntityPool, TileFlags, TileMap, TileSet, Transform, Viewport,
+    };
+    use crate::render::tilemap::{TileChunkInstances, TileMapRenderMode};
     use bevy_ecs::prelude::*;
+    use glam::{Quat, Vec2, Vec3};
 
     #[test]
     fn test_entity_creation() {
@@ -41,4 +46,170 @@ mod tests {
         let mut query_single = world.query::<&Transform>();
         assert_eq!(query_single.iter(&world).count(), 3);
     }
+
+    #[test]
+    fn test_camera_screen_projection() {
+        let camera = Camera {
+            is_active: true,
+            projection: Projection::Perspective {
+                fov: std::f32::consts::FRAC_PI_3,
+                aspect: 16.0 / 9.0,
+                near: 0.1,
+                far: 100.0,
+            },
+        };
+        let transform = Transform {
+            pos: Vec3::new(1.0, 2.0, 3.0),
+            rot: Quat::IDENTITY,
+            scale: Vec3::ONE,
+        };
+        let viewport = Viewport {
+            width: 1600,
+            height: 900,
+        };
+        let center = Vec2::new(800.0, 450.0);
+        assert_eq!(camera.projection.clip_planes(), (0.1, 100.0));
+
+        let (origin, dir) = camera.screen_to_ray(&transform, center, viewport);
+        assert!((dir - Vec3::NEG_Z).length() < 1e-4, "dir = {dir}");
+        assert!((origin - Vec3::new(1.0, 2.0, 2.9)).length() < 1e-4);
+
+        let on_ray = origin + dir * 10.0;
+        let projected = camera
+            .world_to_screen(&transform, on_ray, viewport)
+            .unwrap();
+        assert!(
+            (projected - center).length() < 1e-2,
+            "projected = {projected}"
+        );
+
+        // 相机后方的点不可投影
+        let behind = transform.pos + Vec3::Z;
+        assert!(camera
+            .world_to_screen(&transform, behind, viewport)
+            .is_none());
+
+        // 正交投影下的射线同样往返一致
+        let ortho = Camera {
+            is_active: true,
+            projection: Projection::Orthographic {
+                scale: 5.0,
+                near: 0.0,
+                far: 100.0,
+            },
+        };
+        let corner = Vec2::new(200.0, 100.0);
+        let (origin, dir) = ortho.screen_to_ray(&transform, corner, viewport);
+        assert!((dir - Vec3::NEG_Z).length() < 1e-4);
+        let projected = ortho
+            .world_to_screen(&transform, origin + dir * 20.0, viewport)
+            .unwrap();
+        assert!((projected - corner).length() < 1e-2);
+    }
+
+    #[test]
+    fn test_tilemap_flip_flags() {
+        let mut world = World::new();
+        let mut tileset = TileSet::default();
+        tileset
+            .tiles
+            .insert("grass".to_string(), ([0.25, 0.5], [0.25, 0.25]));
+        world.insert_resource(tileset);
+        world.spawn((
+            Transform::default(),
+            TileMap {
+                width: 3,
+                height: 1,
+                tile_size: [16.0, 32.0],
+                tiles: vec!["grass".to_string(); 3],
+                flags: vec![
+                    TileFlags::NONE,
+                    TileFlags::FLIP_X,
+                    TileFlags::FLIP_Y | TileFlags::ROTATE_90,
+                ],
+                layer: 0.0,
+                atlas_tex_index: 0,
+                dirty: true,
+                chunk_size: [16, 16],
+                render_mode: TileMapRenderMode::Entities,
+            },
+        ));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(tilemap_build_system);
+        schedule.run(&mut world);
+
+        let mut query = world.query::<(&Transform, &Sprite)>();
+        let mut tiles: Vec<_> = query.iter(&world).map(|(t, s)| (*t, s.clone())).collect();
+        tiles.sort_by(|a, b| a.0.pos.x.total_cmp(&b.0.pos.x));
+        assert_eq!(tiles.len(), 3);
+
+        let (plain_t, plain) = &tiles[0];
+        assert_eq!(plain.uv_off, [0.25, 0.5]);
+        assert_eq!(plain.uv_scale, [0.25, 0.25]);
+        assert_eq!(plain_t.rot, Quat::IDENTITY);
+
+        // 水平翻转：U方向缩放取反，偏移移到右边界
+        let (_, flipped) = &tiles[1];
+        assert_eq!(flipped.uv_scale, [-0.25, 0.25]);
+        assert_eq!(flipped.uv_off, [0.5, 0.5]);
+
+        // 垂直翻转并旋转90°：V方向取反，精灵绕Z轴旋转且宽高交换
+        let (rotated_t, rotated) = &tiles[2];
+        assert_eq!(rotated.uv_scale, [0.25, -0.25]);
+        assert_eq!(rotated.uv_off, [0.25, 0.75]);
+        let angle = rotated_t.rot.to_axis_angle().1;
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+        assert_eq!(rotated_t.scale, Vec3::new(32.0, 16.0, 1.0));
+    }
+
+    #[test]
+    fn test_instanced_tilemap_chunk() {
+        let mut world = World::new();
+        let mut tileset = TileSet::default();
+        tileset
+            .tiles
+            .insert("grass".to_string(), ([0.0, 0.0], [0.5, 0.5]));
+        world.insert_resource(tileset);
+        world.insert_resource(TileEntityPool::new());
+
+        let mut tiles = vec!["grass".to_string(); 32 * 32];
+        tiles[0].clear();
+        tiles[100].clear();
+        let map = world
+            .spawn((
+                Transform::default(),
+                TileMap {
+                    width: 32,
+                    height: 32,
+                    tile_size: [16.0, 16.0],
+                    tiles,
+                    flags: Vec::new(),
+                    layer: 0.0,
+                    atlas_tex_index: 0,
+                    dirty: true,
+                    chunk_size: [32, 32],
+                    render_mode: TileMapRenderMode::Instanced,
+                },
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(tilemap_chunk_system);
+        schedule.run(&mut world);
+
+        // 可见范围内只有一个非空chunk，空瓦片不生成实例
+        let chunks = world.get::<TileChunkInstances>(map).unwrap();
+        assert_eq!(chunks.chunks.len(), 1);
+        assert_eq!(chunks.chunks[&(0, 0)].len(), 1024 - 2);
+        assert_eq!(chunks.revision, 1);
+
+        // 不生成逐瓦片实体
+        assert_eq!(world.query::<&Sprite>().iter(&world).count(), 0);
+        assert_eq!(world.query::<&ChunkTag>().iter(&world).count(), 0);
+
+        // 可见chunk不变时不重新构建
+        schedule.run(&mut world);
+        assert_eq!(world.get::<TileChunkInstances>(map).unwrap().revision, 1);
+    }
 }
@@ -0,0 +1,262 @@
+//! 增量资源打包
+//!
+//! 将资源目录打包为单个 `assets.pak` 文件，并维护一个清单记录每个资源的
+//! 内容哈希和在包中的偏移。再次打包时只重新写入哈希变化的资源，
+//! 未变化的资源直接从上一次的包中复制。
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 资源包文件名
+pub const PACK_FILE_NAME: &str = "assets.pak";
+/// 清单文件名
+pub const MANIFEST_FILE_NAME: &str = "assets.manifest.json";
+
+/// 清单中的单个资源
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// 内容的 SHA-256 (十六进制)
+    pub hash: String,
+    /// 在资源包中的偏移
+    pub offset: u64,
+    /// 字节数
+    pub size: u64,
+}
+
+/// 资源包清单：源路径 (相对资源目录，使用 `/` 分隔) -> 哈希和偏移
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl PackManifest {
+    /// 从文件加载清单，文件不存在时返回空清单
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read manifest: {}", e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse manifest: {}", e))
+    }
+
+    /// 保存清单
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write manifest: {}", e))
+    }
+}
+
+/// 打包报告
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    /// 新增的资源
+    pub added: Vec<String>,
+    /// 内容变化、已重新打包的资源
+    pub changed: Vec<String>,
+    /// 未变化、从旧包复制的资源
+    pub unchanged: Vec<String>,
+    /// 已从资源目录删除的资源
+    pub removed: Vec<String>,
+}
+
+impl BuildReport {
+    /// 本次重新写入的资源 (新增 + 变化)
+    pub fn repacked(&self) -> impl Iterator<Item = &str> {
+        self.added
+            .iter()
+            .chain(self.changed.iter())
+            .map(String::as_str)
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} changed, {} unchanged, {} removed",
+            self.added.len(),
+            self.changed.len(),
+            self.unchanged.len(),
+            self.removed.len()
+        )
+    }
+}
+
+/// 增量资源打包器
+pub struct AssetPacker {
+    /// 资源源目录
+    pub source_dir: PathBuf,
+    /// 输出目录 (存放资源包和清单)
+    pub output_dir: PathBuf,
+}
+
+impl AssetPacker {
+    pub fn new(source_dir: impl Into<PathBuf>, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            source_dir: source_dir.into(),
+            output_dir: output_dir.into(),
+        }
+    }
+
+    pub fn pack_path(&self) -> PathBuf {
+        self.output_dir.join(PACK_FILE_NAME)
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.output_dir.join(MANIFEST_FILE_NAME)
+    }
+
+    /// 打包资源，只重新写入内容哈希变化的资源
+    pub fn pack(&self) -> Result<BuildReport, String> {
+        fs::create_dir_all(&self.output_dir)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+        let previous = PackManifest::load(&self.manifest_path())?;
+        // 清单与资源包不一致时 (包被删除) 按全量打包处理
+        let previous_pack = if previous.entries.is_empty() {
+            Vec::new()
+        } else {
+            fs::read(self.pack_path()).unwrap_or_default()
+        };
+
+        let mut files = Vec::new();
+        collect_files(&self.source_dir, &self.source_dir, &mut files)?;
+        files.sort();
+
+        let mut report = BuildReport::default();
+        let mut manifest = PackManifest::default();
+        let mut pack = Vec::new();
+
+        for (key, path) in files {
+            let data = fs::read(&path)
+                .map_err(|e| format!("Failed to read asset {}: {}", path.display(), e))?;
+            let hash = hex::encode(Sha256::digest(&data));
+
+            let reused = previous
+                .entries
+                .get(&key)
+                .filter(|old| old.hash == hash)
+                .and_then(|old| {
+                    let start = old.offset as usize;
+                    previous_pack.get(start..start + old.size as usize)
+                });
+
+            let offset = pack.len() as u64;
+            match reused {
+                Some(blob) => {
+                    pack.extend_from_slice(blob);
+                    report.unchanged.push(key.clone());
+                }
+                None => {
+                    pack.extend_from_slice(&data);
+                    if previous.entries.contains_key(&key) {
+                        report.changed.push(key.clone());
+                    } else {
+                        report.added.push(key.clone());
+                    }
+                }
+            }
+
+            manifest.entries.insert(
+                key,
+                ManifestEntry {
+                    hash,
+                    offset,
+                    size: data.len() as u64,
+                },
+            );
+        }
+
+        report.removed = previous
+            .entries
+            .keys()
+            .filter(|key| !manifest.entries.contains_key(*key))
+            .cloned()
+            .collect();
+
+        // 先写临时文件再替换，避免中途失败留下损坏的资源包
+        let tmp_path = self.pack_path().with_extension("pak.tmp");
+        fs::write(&tmp_path, &pack).map_err(|e| format!("Failed to write pack: {}", e))?;
+        fs::rename(&tmp_path, self.pack_path())
+            .map_err(|e| format!("Failed to replace pack: {}", e))?;
+        manifest.save(&self.manifest_path())?;
+
+        Ok(report)
+    }
+
+    /// 从资源包读取单个资源
+    pub fn read_asset(&self, key: &str) -> Result<Vec<u8>, String> {
+        let manifest = PackManifest::load(&self.manifest_path())?;
+        let entry = manifest
+            .entries
+            .get(key)
+            .ok_or_else(|| format!("Asset not in pack: {}", key))?;
+        let pack = fs::read(self.pack_path()).map_err(|e| format!("Failed to read pack: {}", e))?;
+        let start = entry.offset as usize;
+        pack.get(start..start + entry.size as usize)
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| format!("Pack is truncated at {}", key))
+    }
+}
+
+/// 递归收集文件，返回 (相对路径键, 完整路径)
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}", e))? {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+        } else {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let key = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.push((key, path));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incremental_pack_repacks_only_changed() {
+        let source = tempfile::tempdir().unwrap();
+        let output = tempfile::tempdir().unwrap();
+        fs::create_dir_all(source.path().join("textures")).unwrap();
+        fs::write(source.path().join("textures/player.png"), b"player-v1").unwrap();
+        fs::write(source.path().join("level.json"), b"{\"level\":1}").unwrap();
+
+        let packer = AssetPacker::new(source.path(), output.path());
+        let first = packer.pack().unwrap();
+        assert_eq!(first.added.len(), 2);
+        assert!(first.changed.is_empty() && first.unchanged.is_empty());
+
+        fs::write(source.path().join("textures/player.png"), b"player-v2!").unwrap();
+        let second = packer.pack().unwrap();
+
+        assert_eq!(
+            second.repacked().collect::<Vec<_>>(),
+            ["textures/player.png"]
+        );
+        assert_eq!(second.changed, ["textures/player.png"]);
+        assert_eq!(second.unchanged, ["level.json"]);
+        assert_eq!(
+            second.summary(),
+            "0 added, 1 changed, 1 unchanged, 0 removed"
+        );
+
+        // 复制的旧数据与新数据都能正确读取
+        assert_eq!(packer.read_asset("level.json").unwrap(), b"{\"level\":1}");
+        assert_eq!(
+            packer.read_asset("textures/player.png").unwrap(),
+            b"player-v2!"
+        );
+    }
+}
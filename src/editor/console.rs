@@ -1,5 +1,6 @@
 use crate::impl_default;
-use std::collections::VecDeque;
+use bevy_ecs::prelude::*;
+use std::collections::{BTreeMap, VecDeque};
 
 /// 日志级别
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,7 +25,153 @@ pub struct LogEntry {
     pub source: Option<String>,
 }
 
+/// 控制台命令处理函数：参数为命令名之后的各个参数，返回要打印的文本
+pub type ConsoleCommandHandler =
+    Box<dyn Fn(&[String], &mut ConsoleContext) -> String + Send + Sync>;
+
+/// 命令执行时可访问的上下文
+pub struct ConsoleContext<'a> {
+    pub world: &'a mut World,
+    pub console: &'a mut EditorConsole,
+    pub registry: &'a ConsoleCommandRegistry,
+}
+
+struct RegisteredCommand {
+    description: String,
+    handler: ConsoleCommandHandler,
+}
+
+/// 控制台命令注册表
+///
+/// 作为资源插入 `World`，控制台输入框中的命令会通过它解析并执行。
+/// 默认包含 `clear`、`echo`、`entities`、`help` 和 `version` 内置命令。
+#[derive(Resource)]
+pub struct ConsoleCommandRegistry {
+    commands: BTreeMap<String, RegisteredCommand>,
+}
+
+impl Default for ConsoleCommandRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register_with_description("clear", "Clear console logs", |_, ctx| {
+            ctx.console.clear_logs();
+            String::new()
+        });
+        registry.register_with_description("echo", "Echo a message", |args, _| args.join(" "));
+        registry.register_with_description(
+            "entities",
+            "List entities in the world",
+            |_, ctx| {
+                let ids: Vec<String> = ctx
+                    .world
+                    .iter_entities()
+                    .take(20)
+                    .map(|e| format!("{:?}", e.id()))
+                    .collect();
+                format!("{} entities: {}", ctx.world.entities().len(), ids.join(", "))
+            },
+        );
+        registry.register_with_description("help", "Show available commands", |_, ctx| {
+            ctx.registry.help()
+        });
+        registry.register_with_description("version", "Show engine version", |_, _| {
+            "Game Engine v0.1.0".to_string()
+        });
+        registry
+    }
+}
+
+impl ConsoleCommandRegistry {
+    /// 创建包含内置命令的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建不含任何命令的注册表
+    pub fn empty() -> Self {
+        Self {
+            commands: BTreeMap::new(),
+        }
+    }
+
+    /// 注册命令，同名命令会被覆盖
+    pub fn register<F>(&mut self, name: impl Into<String>, handler: F)
+    where
+        F: Fn(&[String], &mut ConsoleContext) -> String + Send + Sync + 'static,
+    {
+        self.register_with_description(name, "", handler);
+    }
+
+    /// 注册带说明的命令，说明会显示在 `help` 中
+    pub fn register_with_description<F>(
+        &mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: F,
+    ) where
+        F: Fn(&[String], &mut ConsoleContext) -> String + Send + Sync + 'static,
+    {
+        self.commands.insert(
+            name.into(),
+            RegisteredCommand {
+                description: description.into(),
+                handler: Box::new(handler),
+            },
+        );
+    }
+
+    /// 检查命令是否已注册
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    /// 解析并执行一行命令
+    pub fn dispatch(
+        &self,
+        line: &str,
+        world: &mut World,
+        console: &mut EditorConsole,
+    ) -> Result<String, String> {
+        let mut parts = line.split_whitespace();
+        let Some(name) = parts.next() else {
+            return Err("Empty command".to_string());
+        };
+        let args: Vec<String> = parts.map(str::to_string).collect();
+
+        match self.commands.get(name) {
+            Some(command) => {
+                let mut ctx = ConsoleContext {
+                    world,
+                    console,
+                    registry: self,
+                };
+                Ok((command.handler)(&args, &mut ctx))
+            }
+            None => Err(format!(
+                "Unknown command: '{}'. Type 'help' to list available commands.",
+                name
+            )),
+        }
+    }
+
+    /// 生成帮助信息
+    pub fn help(&self) -> String {
+        let mut help = String::from("Available commands:");
+        for (name, command) in &self.commands {
+            if command.description.is_empty() {
+                help.push_str(&format!("\n - {}", name));
+            } else {
+                help.push_str(&format!("\n - {}: {}", name, command.description));
+            }
+        }
+        help
+    }
+}
+
 /// 编辑器控制台
+///
+/// 作为资源插入 `World`，由编辑器的控制台窗口渲染。
+#[derive(Resource)]
 pub struct EditorConsole {
     /// 日志历史
     logs: VecDeque<LogEntry>,
@@ -87,41 +234,72 @@ impl EditorConsole {
     }
 
     /// 执行命令
-    pub fn execute_command(&mut self, command: &str) -> Result<String, String> {
-        // 添加到命令历史
+    ///
+    /// 命令记入历史，并通过 `World` 中的 `ConsoleCommandRegistry` 执行，
+    /// 注册表不存在时插入包含内置命令的默认注册表。
+    pub fn execute_command(&mut self, command: &str, world: &mut World) -> Result<String, String> {
         self.command_history.push_back(command.to_string());
         while self.command_history.len() > self.max_commands {
             self.command_history.pop_front();
         }
 
-        // 解析并执行命令
-        self.parse_and_execute(command)
+        if !world.contains_resource::<ConsoleCommandRegistry>() {
+            world.init_resource::<ConsoleCommandRegistry>();
+        }
+        world.resource_scope(|world, registry: Mut<ConsoleCommandRegistry>| {
+            registry.dispatch(command, world, self)
+        })
     }
 
-    /// 解析并执行命令
-    fn parse_and_execute(&self, command: &str) -> Result<String, String> {
-        let parts: Vec<&str> = command.split_whitespace().collect();
-        if parts.is_empty() {
-            return Err("Empty command".to_string());
+    /// 提交一行输入，执行命令并把结果写入日志
+    pub fn submit(&mut self, line: &str, world: &mut World) {
+        let line = line.trim();
+        if line.is_empty() {
+            return;
         }
+        self.log(LogLevel::Info, format!("> {}", line), Some("console".into()));
 
-        match parts[0] {
-            "help" => Ok(self.get_help()),
-            "clear" => Ok("Logs cleared".to_string()),
-            "echo" => Ok(parts[1..].join(" ")),
-            "version" => Ok("Game Engine v0.1.0".to_string()),
-            _ => Err(format!("Unknown command: {}", parts[0])),
+        match self.execute_command(line, world) {
+            Ok(output) => {
+                if !output.is_empty() {
+                    self.log(LogLevel::Info, output, Some("console".into()));
+                }
+            }
+            Err(err) => self.log(LogLevel::Error, err, Some("console".into())),
         }
     }
 
-    /// 获取帮助信息
-    fn get_help(&self) -> String {
-        "Available commands:\n\
-         - help: Show this help message\n\
-         - clear: Clear console logs\n\
-         - echo <message>: Echo a message\n\
-         - version: Show engine version"
-            .to_string()
+    /// 渲染控制台面板 (日志 + 命令输入框)
+    pub fn render(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        ui.heading("Console");
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .max_height(ui.available_height() - 30.0)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for entry in self.get_filtered_logs() {
+                    let color = match entry.level {
+                        LogLevel::Error => egui::Color32::RED,
+                        LogLevel::Warning => egui::Color32::YELLOW,
+                        LogLevel::Trace | LogLevel::Debug => egui::Color32::GRAY,
+                        LogLevel::Info => egui::Color32::WHITE,
+                    };
+                    ui.label(egui::RichText::new(&entry.message).color(color));
+                }
+            });
+
+        ui.separator();
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.current_input)
+                .hint_text("Enter command (type 'help')")
+                .desired_width(f32::INFINITY),
+        );
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            let line = std::mem::take(&mut self.current_input);
+            self.submit(&line, world);
+            response.request_focus();
+        }
     }
 
     /// 设置过滤器
@@ -227,16 +405,45 @@ mod tests {
 
     #[test]
     fn test_execute_command() {
+        let mut world = World::new();
         let mut console = EditorConsole::new();
 
         // 执行命令
-        let result = console.execute_command("help");
-        assert!(result.is_ok());
+        let result = console.execute_command("help", &mut world);
+        assert!(result.unwrap().contains("version"));
 
-        let result = console.execute_command("echo Hello World");
+        let result = console.execute_command("echo Hello World", &mut world);
         assert_eq!(result.unwrap(), "Hello World");
 
-        let result = console.execute_command("unknown");
+        let result = console.execute_command("unknown", &mut world);
         assert!(result.is_err());
+        assert_eq!(console.get_command_history().len(), 3);
+    }
+
+    #[test]
+    fn test_console_command_registry() {
+        let mut world = World::new();
+        world.spawn_empty();
+        let mut console = EditorConsole::new();
+        let mut registry = ConsoleCommandRegistry::new();
+        registry.register("add", |args, _| {
+            let sum: i32 = args.iter().filter_map(|a| a.parse::<i32>().ok()).sum();
+            sum.to_string()
+        });
+
+        let mut dispatch = |line: &str| registry.dispatch(line, &mut world, &mut console);
+        assert_eq!(dispatch("add 1 2 3").unwrap(), "6");
+        assert!(dispatch("entities").unwrap().starts_with("1 entities"));
+        assert!(dispatch("help").unwrap().contains("add"));
+
+        let err = dispatch("spawn_boss").unwrap_err();
+        assert!(err.contains("spawn_boss") && err.contains("help"));
+
+        // 通过控制台提交，结果写入日志
+        world.insert_resource(registry);
+        console.submit("add 4 5", &mut world);
+        assert_eq!(console.get_filtered_logs().last().unwrap().message, "9");
+        console.submit("clear", &mut world);
+        assert!(console.get_filtered_logs().is_empty());
     }
 }
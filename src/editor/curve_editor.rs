@@ -1,3 +1,4 @@
+use crate::animation::{InterpolationMode, Keyframe, KeyframeTrack};
 use crate::impl_default;
 use glam::Vec2;
 
@@ -12,6 +13,8 @@ pub struct BezierControlPoint {
     pub left_tangent: Vec2,
     /// 右切线
     pub right_tangent: Vec2,
+    /// 左右切线是否独立 (否则拖动一侧时另一侧镜像)
+    pub broken_tangents: bool,
 }
 
 impl BezierControlPoint {
@@ -21,10 +24,25 @@ impl BezierControlPoint {
             value,
             left_tangent: Vec2::new(-0.1, 0.0),
             right_tangent: Vec2::new(0.1, 0.0),
+            broken_tangents: false,
         }
     }
 }
 
+/// 切线所在侧
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TangentSide {
+    Left,
+    Right,
+}
+
+/// 当前拖动的目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragTarget {
+    Point(usize),
+    Tangent(usize, TangentSide),
+}
+
 /// 动画曲线
 #[derive(Debug, Clone)]
 pub struct AnimationCurve {
@@ -57,6 +75,109 @@ impl AnimationCurve {
         }
     }
 
+    /// 在曲线上插入控制点，值取当前曲线在该时间的值，切线沿曲线斜率，
+    /// 使插入后曲线形状基本不变。返回插入位置
+    pub fn insert_on_curve(&mut self, time: f32) -> usize {
+        const H: f32 = 1e-3;
+        let value = self.evaluate(time);
+        let slope = (self.evaluate(time + H) - self.evaluate(time - H)) / (2.0 * H);
+
+        let mut point = BezierControlPoint::new(time, value);
+        let index = self
+            .control_points
+            .binary_search_by(|p| p.time.partial_cmp(&time).unwrap())
+            .unwrap_or_else(|i| i);
+        if let Some(prev) = index
+            .checked_sub(1)
+            .and_then(|i| self.control_points.get(i))
+        {
+            let dx = (time - prev.time) / 3.0;
+            point.left_tangent = Vec2::new(-dx, -slope * dx);
+        }
+        if let Some(next) = self.control_points.get(index) {
+            let dx = (next.time - time) / 3.0;
+            point.right_tangent = Vec2::new(dx, slope * dx);
+        }
+
+        self.control_points.insert(index, point);
+        index
+    }
+
+    /// 设置切线；切线未断开时另一侧镜像
+    pub fn set_tangent(&mut self, index: usize, side: TangentSide, tangent: Vec2) {
+        let Some(point) = self.control_points.get_mut(index) else {
+            return;
+        };
+        match side {
+            TangentSide::Left => {
+                point.left_tangent = tangent;
+                if !point.broken_tangents {
+                    point.right_tangent = -tangent;
+                }
+            }
+            TangentSide::Right => {
+                point.right_tangent = tangent;
+                if !point.broken_tangents {
+                    point.left_tangent = -tangent;
+                }
+            }
+        }
+    }
+
+    /// 转换为 `CubicHermite` 关键帧轨道
+    ///
+    /// 曲线的切线 y 分量是贝塞尔控制点相对关键帧值的偏移，
+    /// 对应 Hermite 斜率为 `3 * y / dt` (dt 为相邻段的时长)。
+    pub fn to_keyframe_track(&self) -> KeyframeTrack<f32> {
+        let mut track = KeyframeTrack::new(InterpolationMode::CubicHermite);
+        self.write_to_track(&mut track);
+        track
+    }
+
+    /// 写回到关键帧轨道，轨道会切换为 `CubicHermite` 模式
+    pub fn write_to_track(&self, track: &mut KeyframeTrack<f32>) {
+        let points = &self.control_points;
+        track.interpolation = InterpolationMode::CubicHermite;
+        track.keyframes = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let in_slope = i
+                    .checked_sub(1)
+                    .map(|prev| slope(-p.left_tangent.y, p.time - points[prev].time))
+                    .unwrap_or(0.0);
+                let out_slope = points
+                    .get(i + 1)
+                    .map(|next| slope(p.right_tangent.y, next.time - p.time))
+                    .unwrap_or(0.0);
+                Keyframe::new(p.time, p.value).with_tangents(in_slope, out_slope)
+            })
+            .collect();
+    }
+
+    /// 从关键帧轨道创建曲线，切线手柄放在相邻段的三分之一处
+    pub fn from_keyframe_track(track: &KeyframeTrack<f32>) -> Self {
+        let keys = &track.keyframes;
+        let control_points = keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| {
+                let mut point = BezierControlPoint::new(k.time, k.value);
+                if let Some(prev) = i.checked_sub(1).map(|prev| &keys[prev]) {
+                    let dx = (k.time - prev.time) / 3.0;
+                    point.left_tangent = Vec2::new(-dx, -k.in_tangent.unwrap_or(0.0) * dx);
+                }
+                if let Some(next) = keys.get(i + 1) {
+                    let dx = (next.time - k.time) / 3.0;
+                    point.right_tangent = Vec2::new(dx, k.out_tangent.unwrap_or(0.0) * dx);
+                }
+                point.broken_tangents = k.in_tangent != k.out_tangent;
+                point
+            })
+            .collect();
+        Self { control_points }
+    }
+
     /// 评估曲线在指定时间的值
     pub fn evaluate(&self, time: f32) -> f32 {
         if self.control_points.is_empty() {
@@ -106,6 +227,15 @@ impl AnimationCurve {
     }
 }
 
+/// 贝塞尔控制点偏移转换为 Hermite 斜率
+fn slope(offset: f32, dt: f32) -> f32 {
+    if dt > f32::EPSILON {
+        3.0 * offset / dt
+    } else {
+        0.0
+    }
+}
+
 impl_default!(AnimationCurve {
     control_points: Vec::new(),
 });
@@ -120,6 +250,8 @@ pub struct CurveEditor {
     pub zoom: f32,
     /// 视图偏移
     pub offset: Vec2,
+    /// 当前拖动的控制点或切线
+    dragging: Option<DragTarget>,
 }
 
 impl CurveEditor {
@@ -127,8 +259,80 @@ impl CurveEditor {
         Self::default()
     }
 
-    /// 渲染曲线编辑器UI
-    pub fn render(&mut self, ui: &mut egui::Ui) {
+    /// 编辑已有的关键帧轨道
+    pub fn from_track(track: &KeyframeTrack<f32>) -> Self {
+        Self {
+            curve: AnimationCurve::from_keyframe_track(track),
+            ..Self::default()
+        }
+    }
+
+    /// 删除选中的控制点
+    pub fn delete_selected(&mut self) -> bool {
+        match self.selected_point.take() {
+            Some(index) if index < self.curve.control_points.len() => {
+                self.curve.remove_control_point(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 曲线坐标 -> 屏幕坐标
+    fn to_screen(&self, rect: egui::Rect, time: f32, value: f32) -> egui::Pos2 {
+        egui::pos2(
+            rect.left() + (time * rect.width() * self.zoom) + self.offset.x,
+            rect.bottom() - (value * rect.height() * self.zoom) - self.offset.y,
+        )
+    }
+
+    /// 屏幕位移 -> 曲线坐标位移
+    fn to_curve_delta(&self, rect: egui::Rect, delta: egui::Vec2) -> Vec2 {
+        Vec2::new(
+            delta.x / (rect.width() * self.zoom),
+            -delta.y / (rect.height() * self.zoom),
+        )
+    }
+
+    /// 屏幕坐标 -> 曲线时间
+    fn to_time(&self, rect: egui::Rect, pos: egui::Pos2) -> f32 {
+        (pos.x - rect.left() - self.offset.x) / (rect.width() * self.zoom)
+    }
+
+    /// 切线手柄的屏幕位置
+    fn tangent_handle(&self, rect: egui::Rect, index: usize, side: TangentSide) -> egui::Pos2 {
+        let point = &self.curve.control_points[index];
+        let tangent = match side {
+            TangentSide::Left => point.left_tangent,
+            TangentSide::Right => point.right_tangent,
+        };
+        self.to_screen(rect, point.time + tangent.x, point.value + tangent.y)
+    }
+
+    /// 查找屏幕位置下的拖动目标 (选中点的切线手柄优先)
+    fn hit_test(&self, rect: egui::Rect, pos: egui::Pos2) -> Option<DragTarget> {
+        const RADIUS: f32 = 10.0;
+        if let Some(index) = self
+            .selected_point
+            .filter(|&i| i < self.curve.control_points.len())
+        {
+            for side in [TangentSide::Left, TangentSide::Right] {
+                if self.tangent_handle(rect, index, side).distance(pos) < RADIUS {
+                    return Some(DragTarget::Tangent(index, side));
+                }
+            }
+        }
+        self.curve
+            .control_points
+            .iter()
+            .position(|p| self.to_screen(rect, p.time, p.value).distance(pos) < RADIUS)
+            .map(DragTarget::Point)
+    }
+
+    /// 渲染曲线编辑器UI，返回曲线是否被修改
+    pub fn render(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
         ui.heading("Curve Editor");
         ui.separator();
 
@@ -136,13 +340,18 @@ impl CurveEditor {
         ui.horizontal(|ui| {
             if ui.button("Add Point").clicked() {
                 self.curve.add_control_point(0.5, 0.5);
+                changed = true;
             }
 
             if ui.button("Remove Point").clicked() {
-                if let Some(index) = self.selected_point {
-                    self.curve.remove_control_point(index);
-                    self.selected_point = None;
-                }
+                changed |= self.delete_selected();
+            }
+
+            if let Some(point) = self
+                .selected_point
+                .and_then(|i| self.curve.control_points.get_mut(i))
+            {
+                ui.checkbox(&mut point.broken_tangents, "Broken Tangents");
             }
 
             ui.separator();
@@ -215,8 +424,7 @@ impl CurveEditor {
 
         // 绘制控制点
         for (i, point) in self.curve.control_points.iter().enumerate() {
-            let x = rect.left() + (point.time * rect.width() * self.zoom) + self.offset.x;
-            let y = rect.bottom() - (point.value * rect.height() * self.zoom) - self.offset.y;
+            let pos = self.to_screen(rect, point.time, point.value);
 
             let is_selected = self.selected_point == Some(i);
             let color = if is_selected {
@@ -225,19 +433,86 @@ impl CurveEditor {
                 egui::Color32::from_rgb(255, 255, 255)
             };
 
-            painter.circle_filled(egui::pos2(x, y), 5.0, color);
+            painter.circle_filled(pos, 5.0, color);
 
-            // 检测点击
-            if response.clicked() {
-                let click_pos = response.interact_pointer_pos().unwrap();
-                let distance = ((click_pos.x - x).powi(2) + (click_pos.y - y).powi(2)).sqrt();
+            // 选中点显示切线手柄
+            if is_selected {
+                for side in [TangentSide::Left, TangentSide::Right] {
+                    let handle = self.tangent_handle(rect, i, side);
+                    let stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(160));
+                    painter.line_segment([pos, handle], stroke);
+                    painter.circle_filled(handle, 4.0, egui::Color32::from_rgb(180, 180, 255));
+                }
+            }
+        }
 
-                if distance < 10.0 {
+        // 交互：点击选择/在曲线上插入，拖动控制点或切线
+        if let Some(pointer) = response.interact_pointer_pos() {
+            if response.drag_started() {
+                self.dragging = self.hit_test(rect, pointer);
+                if let Some(DragTarget::Point(i)) = self.dragging {
                     self.selected_point = Some(i);
                 }
+            } else if response.clicked() {
+                match self.hit_test(rect, pointer) {
+                    Some(DragTarget::Point(i)) => self.selected_point = Some(i),
+                    Some(DragTarget::Tangent(..)) => {}
+                    None => {
+                        let time = self.to_time(rect, pointer);
+                        let on_curve = self.to_screen(rect, time, self.curve.evaluate(time));
+                        if self.curve.control_points.len() >= 2
+                            && (on_curve.y - pointer.y).abs() < 8.0
+                        {
+                            self.selected_point = Some(self.curve.insert_on_curve(time));
+                            changed = true;
+                        } else {
+                            self.selected_point = None;
+                        }
+                    }
+                }
             }
         }
 
+        if response.dragged() {
+            let delta = self.to_curve_delta(rect, response.drag_delta());
+            match self.dragging {
+                Some(DragTarget::Point(i)) => {
+                    if let Some(point) = self.curve.control_points.get_mut(i) {
+                        point.time += delta.x;
+                        point.value += delta.y;
+                        changed = true;
+                    }
+                }
+                Some(DragTarget::Tangent(i, side)) => {
+                    if let Some(point) = self.curve.control_points.get(i) {
+                        let current = match side {
+                            TangentSide::Left => point.left_tangent,
+                            TangentSide::Right => point.right_tangent,
+                        };
+                        self.curve.set_tangent(i, side, current + delta);
+                        changed = true;
+                    }
+                }
+                None => {}
+            }
+        }
+
+        if response.drag_stopped() {
+            // 拖动控制点可能改变顺序，重新排序并保持选中
+            if let Some(DragTarget::Point(i)) = self.dragging.take() {
+                let selected_time = self.curve.control_points.get(i).map(|p| p.time);
+                self.curve
+                    .control_points
+                    .sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+                self.selected_point = selected_time
+                    .and_then(|t| self.curve.control_points.iter().position(|p| p.time == t));
+            }
+        }
+
+        if response.hovered() && ui.input(|i| i.key_pressed(egui::Key::Delete)) {
+            changed |= self.delete_selected();
+        }
+
         ui.separator();
 
         // 控制点属性编辑
@@ -288,6 +563,8 @@ impl CurveEditor {
                 });
             }
         }
+
+        changed
     }
 }
 
@@ -296,6 +573,7 @@ impl_default!(CurveEditor {
     selected_point: None,
     zoom: 1.0,
     offset: Vec2::ZERO,
+    dragging: None,
 });
 
 #[cfg(test)]
@@ -313,4 +591,42 @@ mod tests {
         let value = curve.evaluate(0.5);
         assert!((value - 0.5).abs() < 0.1); // 近似线性插值
     }
+
+    #[test]
+    fn test_insert_keyframe_on_curve() {
+        let mut editor = CurveEditor::new();
+        editor.curve.add_control_point(0.0, 0.0);
+        editor.curve.add_control_point(1.0, 1.0);
+
+        let before = editor.curve.evaluate(0.5);
+        let index = editor.curve.insert_on_curve(0.5);
+        assert_eq!(index, 1);
+
+        let times: Vec<f32> = editor.curve.control_points.iter().map(|p| p.time).collect();
+        assert_eq!(times, [0.0, 0.5, 1.0]);
+        assert!((editor.curve.control_points[1].value - before).abs() < 1e-4);
+
+        let track = editor.curve.to_keyframe_track();
+        assert_eq!(track.interpolation, InterpolationMode::CubicHermite);
+        assert_eq!(track.keyframes.len(), 3);
+        assert!(track.keyframes.windows(2).all(|k| k[0].time < k[1].time));
+
+        // 删除选中的关键帧
+        editor.selected_point = Some(1);
+        assert!(editor.delete_selected());
+        assert_eq!(editor.curve.control_points.len(), 2);
+    }
+
+    #[test]
+    fn test_mirrored_and_broken_tangents() {
+        let mut curve = AnimationCurve::new();
+        curve.add_control_point(0.0, 0.0);
+
+        curve.set_tangent(0, TangentSide::Right, Vec2::new(0.2, 0.1));
+        assert_eq!(curve.control_points[0].left_tangent, Vec2::new(-0.2, -0.1));
+
+        curve.control_points[0].broken_tangents = true;
+        curve.set_tangent(0, TangentSide::Right, Vec2::new(0.3, 0.0));
+        assert_eq!(curve.control_points[0].left_tangent, Vec2::new(-0.2, -0.1));
+    }
 }
@@ -1,5 +1,391 @@
-use crate::ecs::Transform;
+use crate::ecs::{Material, PointLight, Transform};
+use crate::render::pbr::PbrMaterial;
 use bevy_ecs::prelude::*;
+use bevy_ecs::world::EntityWorldMut;
+use glam::{Quat, Vec2, Vec3};
+
+/// 反射字段类型，决定检查器使用的控件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldKind {
+    /// 浮点数；有范围时使用滑块，否则使用拖动输入
+    F32 {
+        range: Option<(f32, f32)>,
+    },
+    Vec2,
+    Vec3,
+    Quat,
+    /// RGB 颜色
+    Color3,
+    /// RGBA 颜色
+    Color4,
+    Bool,
+    Text,
+}
+
+/// 反射字段描述
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub kind: FieldKind,
+}
+
+impl FieldInfo {
+    pub const fn new(name: &'static str, kind: FieldKind) -> Self {
+        Self { name, kind }
+    }
+}
+
+/// 反射字段值
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    F32(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Quat(Quat),
+    Color3([f32; 3]),
+    Color4([f32; 4]),
+    Bool(bool),
+    Text(String),
+}
+
+/// 可被检查器通用编辑的组件
+///
+/// 组件通过字段元数据描述自身，检查器据此生成控件，新增组件无需修改检查器。
+pub trait ReflectInspect {
+    /// 在检查器中显示的组件名
+    fn type_name(&self) -> &'static str;
+
+    /// 字段列表（与结构体字段顺序一致）
+    fn fields(&self) -> &'static [FieldInfo];
+
+    /// 读取字段值
+    fn field(&self, name: &str) -> Option<FieldValue>;
+
+    /// 写入字段值，字段不存在或类型不匹配时返回 `false`
+    fn set_field(&mut self, name: &str, value: FieldValue) -> bool;
+}
+
+impl ReflectInspect for Transform {
+    fn type_name(&self) -> &'static str {
+        "Transform"
+    }
+
+    fn fields(&self) -> &'static [FieldInfo] {
+        const FIELDS: &[FieldInfo] = &[
+            FieldInfo::new("pos", FieldKind::Vec3),
+            FieldInfo::new("rot", FieldKind::Quat),
+            FieldInfo::new("scale", FieldKind::Vec3),
+        ];
+        FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        match name {
+            "pos" => Some(FieldValue::Vec3(self.pos)),
+            "rot" => Some(FieldValue::Quat(self.rot)),
+            "scale" => Some(FieldValue::Vec3(self.scale)),
+            _ => None,
+        }
+    }
+
+    fn set_field(&mut self, name: &str, value: FieldValue) -> bool {
+        match (name, value) {
+            ("pos", FieldValue::Vec3(v)) => self.pos = v,
+            ("rot", FieldValue::Quat(q)) => self.rot = q,
+            ("scale", FieldValue::Vec3(v)) => self.scale = v,
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl ReflectInspect for Material {
+    fn type_name(&self) -> &'static str {
+        "Material"
+    }
+
+    fn fields(&self) -> &'static [FieldInfo] {
+        const FIELDS: &[FieldInfo] = &[
+            FieldInfo::new("color", FieldKind::Color4),
+            FieldInfo::new(
+                "metallic",
+                FieldKind::F32 {
+                    range: Some((0.0, 1.0)),
+                },
+            ),
+            FieldInfo::new(
+                "roughness",
+                FieldKind::F32 {
+                    range: Some((0.0, 1.0)),
+                },
+            ),
+        ];
+        FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        match name {
+            "color" => Some(FieldValue::Color4(self.color)),
+            "metallic" => Some(FieldValue::F32(self.metallic)),
+            "roughness" => Some(FieldValue::F32(self.roughness)),
+            _ => None,
+        }
+    }
+
+    fn set_field(&mut self, name: &str, value: FieldValue) -> bool {
+        match (name, value) {
+            ("color", FieldValue::Color4(c)) => self.color = c,
+            ("metallic", FieldValue::F32(v)) => self.metallic = v,
+            ("roughness", FieldValue::F32(v)) => self.roughness = v,
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl ReflectInspect for PointLight {
+    fn type_name(&self) -> &'static str {
+        "PointLight"
+    }
+
+    fn fields(&self) -> &'static [FieldInfo] {
+        const FIELDS: &[FieldInfo] = &[
+            FieldInfo::new("color", FieldKind::Color3),
+            FieldInfo::new("intensity", FieldKind::F32 { range: None }),
+            FieldInfo::new("radius", FieldKind::F32 { range: None }),
+            FieldInfo::new("falloff", FieldKind::F32 { range: None }),
+        ];
+        FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        match name {
+            "color" => Some(FieldValue::Color3(self.color)),
+            "intensity" => Some(FieldValue::F32(self.intensity)),
+            "radius" => Some(FieldValue::F32(self.radius)),
+            "falloff" => Some(FieldValue::F32(self.falloff)),
+            _ => None,
+        }
+    }
+
+    fn set_field(&mut self, name: &str, value: FieldValue) -> bool {
+        match (name, value) {
+            ("color", FieldValue::Color3(c)) => self.color = c,
+            ("intensity", FieldValue::F32(v)) => self.intensity = v,
+            ("radius", FieldValue::F32(v)) => self.radius = v,
+            ("falloff", FieldValue::F32(v)) => self.falloff = v,
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl ReflectInspect for PbrMaterial {
+    fn type_name(&self) -> &'static str {
+        "PbrMaterial"
+    }
+
+    fn fields(&self) -> &'static [FieldInfo] {
+        const UNIT: FieldKind = FieldKind::F32 {
+            range: Some((0.0, 1.0)),
+        };
+        const ROUGHNESS: FieldKind = FieldKind::F32 {
+            range: Some((0.04, 1.0)),
+        };
+        const FIELDS: &[FieldInfo] = &[
+            FieldInfo::new("base_color", FieldKind::Color4),
+            FieldInfo::new("metallic", UNIT),
+            FieldInfo::new("roughness", ROUGHNESS),
+            FieldInfo::new("ambient_occlusion", UNIT),
+            FieldInfo::new("emissive", FieldKind::Color3),
+            FieldInfo::new(
+                "normal_scale",
+                FieldKind::F32 {
+                    range: Some((0.0, 4.0)),
+                },
+            ),
+            FieldInfo::new("uv_offset", FieldKind::Vec2),
+            FieldInfo::new("uv_scale", FieldKind::Vec2),
+            FieldInfo::new("uv_rotation", FieldKind::F32 { range: None }),
+            FieldInfo::new("clearcoat", UNIT),
+            FieldInfo::new("clearcoat_roughness", ROUGHNESS),
+            FieldInfo::new("anisotropy", UNIT),
+            FieldInfo::new("anisotropy_direction", FieldKind::Vec2),
+        ];
+        FIELDS
+    }
+
+    fn field(&self, name: &str) -> Option<FieldValue> {
+        match name {
+            "base_color" => Some(FieldValue::Color4(self.base_color.to_array())),
+            "metallic" => Some(FieldValue::F32(self.metallic)),
+            "roughness" => Some(FieldValue::F32(self.roughness)),
+            "ambient_occlusion" => Some(FieldValue::F32(self.ambient_occlusion)),
+            "emissive" => Some(FieldValue::Color3(self.emissive.to_array())),
+            "normal_scale" => Some(FieldValue::F32(self.normal_scale)),
+            "uv_offset" => Some(FieldValue::Vec2(Vec2::from(self.uv_offset))),
+            "uv_scale" => Some(FieldValue::Vec2(Vec2::from(self.uv_scale))),
+            "uv_rotation" => Some(FieldValue::F32(self.uv_rotation)),
+            "clearcoat" => Some(FieldValue::F32(self.clearcoat)),
+            "clearcoat_roughness" => Some(FieldValue::F32(self.clearcoat_roughness)),
+            "anisotropy" => Some(FieldValue::F32(self.anisotropy)),
+            "anisotropy_direction" => Some(FieldValue::Vec2(Vec2::from(self.anisotropy_direction))),
+            _ => None,
+        }
+    }
+
+    fn set_field(&mut self, name: &str, value: FieldValue) -> bool {
+        match (name, value) {
+            ("base_color", FieldValue::Color4(c)) => self.base_color = c.into(),
+            ("metallic", FieldValue::F32(v)) => self.metallic = v,
+            ("roughness", FieldValue::F32(v)) => self.roughness = v,
+            ("ambient_occlusion", FieldValue::F32(v)) => self.ambient_occlusion = v,
+            ("emissive", FieldValue::Color3(c)) => self.emissive = c.into(),
+            ("normal_scale", FieldValue::F32(v)) => self.normal_scale = v,
+            ("uv_offset", FieldValue::Vec2(v)) => self.uv_offset = v.to_array(),
+            ("uv_scale", FieldValue::Vec2(v)) => self.uv_scale = v.to_array(),
+            ("uv_rotation", FieldValue::F32(v)) => self.uv_rotation = v,
+            ("clearcoat", FieldValue::F32(v)) => self.clearcoat = v,
+            ("clearcoat_roughness", FieldValue::F32(v)) => self.clearcoat_roughness = v,
+            ("anisotropy", FieldValue::F32(v)) => self.anisotropy = v,
+            ("anisotropy_direction", FieldValue::Vec2(v)) => {
+                self.anisotropy_direction = v.to_array()
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// 根据反射元数据渲染组件的所有字段，返回是否有字段被修改
+pub fn inspect_reflected(ui: &mut egui::Ui, component: &mut dyn ReflectInspect) -> bool {
+    let mut changed = false;
+    ui.label(format!("{}:", component.type_name()));
+
+    for info in component.fields() {
+        let Some(mut value) = component.field(info.name) else {
+            continue;
+        };
+        let edited = ui
+            .horizontal(|ui| {
+                ui.label(info.name);
+                field_widget(ui, info.kind, &mut value)
+            })
+            .inner;
+        if edited {
+            changed |= component.set_field(info.name, value);
+        }
+    }
+
+    ui.separator();
+    changed
+}
+
+fn field_widget(ui: &mut egui::Ui, kind: FieldKind, value: &mut FieldValue) -> bool {
+    match value {
+        FieldValue::F32(v) => match kind {
+            FieldKind::F32 {
+                range: Some((min, max)),
+            } => ui.add(egui::Slider::new(v, min..=max)).changed(),
+            _ => ui.add(egui::DragValue::new(v).speed(0.1)).changed(),
+        },
+        FieldValue::Vec2(v) => {
+            let mut changed = false;
+            for (label, c) in [("X: ", &mut v.x), ("Y: ", &mut v.y)] {
+                changed |= ui
+                    .add(egui::DragValue::new(c).prefix(label).speed(0.01))
+                    .changed();
+            }
+            changed
+        }
+        FieldValue::Vec3(v) => {
+            let mut changed = false;
+            for (label, c) in [("X: ", &mut v.x), ("Y: ", &mut v.y), ("Z: ", &mut v.z)] {
+                changed |= ui
+                    .add(egui::DragValue::new(c).prefix(label).speed(0.1))
+                    .changed();
+            }
+            changed
+        }
+        FieldValue::Quat(q) => {
+            // 简化版，直接编辑四元数分量
+            let mut xyzw = q.to_array();
+            let mut changed = false;
+            for (label, c) in ["X: ", "Y: ", "Z: ", "W: "]
+                .into_iter()
+                .zip(xyzw.iter_mut())
+            {
+                changed |= ui
+                    .add(egui::DragValue::new(c).prefix(label).speed(0.01))
+                    .changed();
+            }
+            if changed {
+                *q = Quat::from_array(xyzw);
+            }
+            changed
+        }
+        FieldValue::Color3(c) => ui.color_edit_button_rgb(c).changed(),
+        FieldValue::Color4(c) => ui.color_edit_button_rgba_unmultiplied(c).changed(),
+        FieldValue::Bool(b) => ui.checkbox(b, "").changed(),
+        FieldValue::Text(t) => ui.text_edit_singleline(t).changed(),
+    }
+}
+
+type InspectFn = fn(&mut egui::Ui, &mut EntityWorldMut) -> bool;
+
+/// 检查器注册项
+pub struct InspectorEntry {
+    pub name: &'static str,
+    inspect: InspectFn,
+}
+
+/// 可在检查器中编辑的组件注册表
+pub struct InspectorRegistry {
+    entries: Vec<InspectorEntry>,
+}
+
+impl Default for InspectorRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            entries: Vec::new(),
+        };
+        registry.register::<Transform>("Transform");
+        registry.register::<Material>("Material");
+        registry.register::<PointLight>("PointLight");
+        registry
+    }
+}
+
+impl InspectorRegistry {
+    /// 创建包含内置组件的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册一个实现了 `ReflectInspect` 的组件
+    pub fn register<T: Component + ReflectInspect>(&mut self, name: &'static str) {
+        fn inspect<T: Component + ReflectInspect>(
+            ui: &mut egui::Ui,
+            entity: &mut EntityWorldMut,
+        ) -> bool {
+            match entity.get_mut::<T>() {
+                Some(mut component) => inspect_reflected(ui, &mut *component),
+                None => false,
+            }
+        }
+
+        self.entries.retain(|e| e.name != name);
+        self.entries.push(InspectorEntry {
+            name,
+            inspect: inspect::<T>,
+        });
+    }
+
+    /// 已注册组件的名称
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|e| e.name)
+    }
+}
 
 /// 属性检查器
 pub struct Inspector;
@@ -7,6 +393,16 @@ pub struct Inspector;
 impl Inspector {
     /// 渲染检查器 (使用egui)
     pub fn render(ui: &mut egui::Ui, world: &mut World, selected_entity: Option<Entity>) {
+        Self::render_with_registry(ui, world, selected_entity, &InspectorRegistry::default());
+    }
+
+    /// 使用自定义组件注册表渲染检查器
+    pub fn render_with_registry(
+        ui: &mut egui::Ui,
+        world: &mut World,
+        selected_entity: Option<Entity>,
+        registry: &InspectorRegistry,
+    ) {
         ui.heading("Inspector");
         ui.separator();
 
@@ -19,79 +415,10 @@ impl Inspector {
                 // Name组件编辑 (占位)
                 // ui.label("Name: Entity");
 
-                // 编辑Transform组件
-                if let Some(mut transform) = entity_mut.get_mut::<Transform>() {
-                    ui.label("Transform:");
-
-                    ui.horizontal(|ui| {
-                        ui.label("Position:");
-                        ui.add(
-                            egui::DragValue::new(&mut transform.pos.x)
-                                .prefix("X: ")
-                                .speed(0.1),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.pos.y)
-                                .prefix("Y: ")
-                                .speed(0.1),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.pos.z)
-                                .prefix("Z: ")
-                                .speed(0.1),
-                        );
-                    });
-
-                    ui.horizontal(|ui| {
-                        ui.label("Scale:");
-                        ui.add(
-                            egui::DragValue::new(&mut transform.scale.x)
-                                .prefix("X: ")
-                                .speed(0.01),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.scale.y)
-                                .prefix("Y: ")
-                                .speed(0.01),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.scale.z)
-                                .prefix("Z: ")
-                                .speed(0.01),
-                        );
-                    });
-
-                    // 旋转编辑 (简化版,直接编辑四元数)
-                    ui.horizontal(|ui| {
-                        ui.label("Rotation:");
-                        ui.add(
-                            egui::DragValue::new(&mut transform.rot.x)
-                                .prefix("X: ")
-                                .speed(0.01),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.rot.y)
-                                .prefix("Y: ")
-                                .speed(0.01),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.rot.z)
-                                .prefix("Z: ")
-                                .speed(0.01),
-                        );
-                        ui.add(
-                            egui::DragValue::new(&mut transform.rot.w)
-                                .prefix("W: ")
-                                .speed(0.01),
-                        );
-                    });
-
-                    ui.separator();
+                // 通过反射元数据编辑已注册的组件
+                for entry in &registry.entries {
+                    (entry.inspect)(ui, &mut entity_mut);
                 }
-
-                // 显示其他组件 (占位)
-                ui.label("Other Components:");
-                ui.label("(Component list will be displayed here)");
             } else {
                 ui.label("Entity not found");
             }
@@ -100,3 +427,63 @@ impl Inspector {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reflected_fields_match_struct() {
+        let names: Vec<_> = Transform::default()
+            .fields()
+            .iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(names, ["pos", "rot", "scale"]);
+
+        let light = PointLight::default();
+        for info in light.fields() {
+            assert!(light.field(info.name).is_some(), "missing {}", info.name);
+        }
+        assert_eq!(light.fields().len(), 4);
+
+        let mut material = PbrMaterial::default();
+        let names: Vec<_> = material.fields().iter().map(|f| f.name).collect();
+        assert_eq!(
+            names,
+            [
+                "base_color",
+                "metallic",
+                "roughness",
+                "ambient_occlusion",
+                "emissive",
+                "normal_scale",
+                "uv_offset",
+                "uv_scale",
+                "uv_rotation",
+                "clearcoat",
+                "clearcoat_roughness",
+                "anisotropy",
+                "anisotropy_direction",
+            ]
+        );
+        for info in material.fields() {
+            let value = material.field(info.name).expect(info.name);
+            assert!(material.set_field(info.name, value), "{}", info.name);
+        }
+        assert_eq!(material, PbrMaterial::default());
+    }
+
+    #[test]
+    fn test_set_reflected_f32_field() {
+        let mut material = Material::default();
+        assert!(material.set_field("roughness", FieldValue::F32(0.9)));
+        assert_eq!(material.roughness, 0.9);
+        assert_eq!(material.field("roughness"), Some(FieldValue::F32(0.9)));
+
+        // 类型不匹配或未知字段不会修改组件
+        assert!(!material.set_field("roughness", FieldValue::Bool(true)));
+        assert!(!material.set_field("unknown", FieldValue::F32(1.0)));
+        assert_eq!(material.roughness, 0.9);
+    }
+}
@@ -3,6 +3,7 @@ use egui_wgpu::Renderer;
 
 pub mod animation_editor;
 pub mod asset_browser;
+pub mod asset_pack;
 pub mod build_tool;
 pub mod config;
 pub mod console;
@@ -28,8 +29,8 @@ pub mod undo_redo;
 pub use config::{EditorConfig, EditorConfigManager, EditorTheme};
 use egui_winit::State;
 pub use hierarchy::HierarchyView;
-pub use inspector::Inspector;
-pub use shortcuts::{Modifiers, ShortcutAction, ShortcutManager};
+pub use inspector::{FieldInfo, FieldKind, FieldValue, Inspector, InspectorRegistry, ReflectInspect};
+pub use shortcuts::{KeyStep, Modifiers, Shortcut, ShortcutAction, ShortcutManager};
 pub use undo_redo::{
     Command, CommandError, CommandManager, CompositeCommand, PropertyChangeCommand,
 };
@@ -188,6 +189,12 @@ pub fn inspect_world_ui(ctx: &egui::Context, world: &mut World) {
                 "Alerts U:{} M:{} UI:{} O:{}",
                 stats.alerts_upload, stats.alerts_main, stats.alerts_ui, stats.alerts_offscreen
             ));
+            ui.label(format!(
+                "VRAM: {:.1} MB | Buffers: {} | Textures: {}",
+                stats.vram_bytes_used as f64 / (1024.0 * 1024.0),
+                stats.buffer_count,
+                stats.texture_count
+            ));
         }
         if let Some(am) = world.get_resource::<crate::core::AssetMetrics>() {
             if let Some(ms) = am.last_latency_ms {
@@ -214,6 +221,15 @@ pub fn inspect_world_ui(ctx: &egui::Context, world: &mut World) {
         }
     });
 
+    egui::Window::new("Console").show(ctx, |ui| {
+        if !world.contains_resource::<console::EditorConsole>() {
+            world.init_resource::<console::EditorConsole>();
+        }
+        world.resource_scope(|world, mut console: Mut<console::EditorConsole>| {
+            console.render(ui, world);
+        });
+    });
+
     egui::Window::new("Material Editor").show(ctx, |ui| {
         if let Some(reg) = world.get_resource::<crate::resources::manager::MaterialRegistry>() {
             ui.label(format!("Materials: {}", reg.materials.len()));
@@ -221,23 +237,9 @@ pub fn inspect_world_ui(ctx: &egui::Context, world: &mut World) {
             let mut updates: Vec<(u64, crate::render::pbr::PbrMaterial)> = Vec::new();
             for (id, _entry) in reg.materials.iter() {
                 ui.collapsing(format!("Material {}", id), |ui| {
-                    let mut mat = crate::render::pbr::PbrMaterial::default();
-                    let mut base = [mat.base_color.x, mat.base_color.y, mat.base_color.z, mat.base_color.w];
-                    ui.color_edit_button_rgba_unmultiplied(&mut base);
-                    mat.base_color = glam::Vec4::new(base[0], base[1], base[2], base[3]);
-                    ui.add(egui::Slider::new(&mut mat.metallic, 0.0..=1.0).text("Metallic"));
-                    ui.add(egui::Slider::new(&mut mat.roughness, 0.04..=1.0).text("Roughness"));
-                    ui.add(egui::Slider::new(&mut mat.normal_scale, 0.0..=4.0).text("Normal Scale"));
-                    ui.add(egui::Slider::new(&mut mat.clearcoat, 0.0..=1.0).text("Clearcoat"));
-                    ui.add(egui::Slider::new(&mut mat.clearcoat_roughness, 0.04..=1.0).text("Clearcoat Roughness"));
-                    ui.add(egui::Slider::new(&mut mat.anisotropy, 0.0..=1.0).text("Anisotropy"));
-                    let mut adir = mat.anisotropy_direction;
-                    ui.horizontal(|ui| {
-                        ui.add(egui::Slider::new(&mut adir[0], -1.0..=1.0).text("Aniso Dir X"));
-                        ui.add(egui::Slider::new(&mut adir[1], -1.0..=1.0).text("Aniso Dir Y"));
-                    });
-                    mat.anisotropy_direction = adir;
-                    if ui.button("Apply").clicked() {
+                    // 编辑结果立即提交，下一帧从注册表读取更新后的参数
+                    let mut mat = reg.params(*id).cloned().unwrap_or_default();
+                    if inspector::inspect_reflected(ui, &mut mat) {
                         updates.push((*id, mat));
                     }
                 });
@@ -1,4 +1,5 @@
 use crate::impl_default;
+use super::asset_pack::{AssetPacker, BuildReport};
 use super::build_tool::BuildTarget;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -254,12 +255,24 @@ impl PackageDeployManager {
         Err("iOS packaging requires Xcode tools".to_string())
     }
 
+    /// 增量打包资源目录到 `output_dir`，只重新写入内容变化的资源
+    pub fn pack_assets(&self, assets_dir: &Path, output_dir: &Path) -> Result<BuildReport, String> {
+        AssetPacker::new(assets_dir, output_dir).pack()
+    }
+
     /// 复制资源文件
     fn copy_assets(&self, target_dir: &Path) -> Result<(), String> {
-        let assets_dir = Path::new("assets");
+        self.copy_assets_from(Path::new("assets"), target_dir)
+    }
+
+    /// 复制资源目录，并在 `target_dir` 中增量生成资源包和清单
+    fn copy_assets_from(&self, assets_dir: &Path, target_dir: &Path) -> Result<(), String> {
         if assets_dir.exists() {
             let target_assets = target_dir.join("assets");
             self.copy_dir_all(assets_dir, &target_assets)?;
+
+            let report = self.pack_assets(assets_dir, target_dir)?;
+            tracing::info!(target: "package", "Packed assets: {}", report.summary());
         }
         Ok(())
     }
@@ -415,4 +428,26 @@ mod tests {
         let manager = PackageDeployManager::default();
         assert_eq!(manager.config.app_name, "Game");
     }
+
+    #[test]
+    fn test_copy_assets_writes_pack() {
+        use crate::editor::asset_pack::{MANIFEST_FILE_NAME, PACK_FILE_NAME};
+
+        let dir = tempfile::tempdir().unwrap();
+        let assets_dir = dir.path().join("assets");
+        fs::create_dir_all(assets_dir.join("textures")).unwrap();
+        fs::write(assets_dir.join("textures/grass.png"), b"grass").unwrap();
+        let target_dir = dir.path().join("package");
+
+        let manager = PackageDeployManager::default();
+        manager.copy_assets_from(&assets_dir, &target_dir).unwrap();
+
+        assert!(target_dir.join("assets/textures/grass.png").exists());
+        assert!(target_dir.join(PACK_FILE_NAME).exists());
+        assert!(target_dir.join(MANIFEST_FILE_NAME).exists());
+
+        // 再次打包时未变化的资源直接复用
+        let report = manager.pack_assets(&assets_dir, &target_dir).unwrap();
+        assert_eq!(report.unchanged, ["textures/grass.png"]);
+    }
 }
@@ -2,10 +2,219 @@ use super::build_tool::{BuildProfile, BuildResult, BuildTarget};
 use std::path::Path;
 use std::process::Command;
 
+/// 构建前检查问题的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    /// 可能导致构建失败，但仍允许构建
+    Warning,
+    /// 缺少必需的工具链，拒绝构建
+    Error,
+}
+
+/// 构建前检查发现的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub severity: IssueSeverity,
+    /// 问题描述
+    pub message: String,
+    /// 修复建议
+    pub fix: Option<String>,
+}
+
+impl ValidationIssue {
+    pub fn error(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Error,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>, fix: Option<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            message: message.into(),
+            fix,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.severity, self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, " ({})", fix)?;
+        }
+        Ok(())
+    }
+}
+
+/// 工具链探测接口，便于在测试中替换系统检查
+pub trait ToolchainProbe {
+    /// 已安装的 rustup 目标，无法查询时返回 `None`
+    fn installed_targets(&self) -> Option<Vec<String>>;
+    /// 命令是否可执行 (例如 `wasm-pack --version`)
+    fn has_command(&self, program: &str, args: &[&str]) -> bool;
+    /// 读取环境变量
+    fn env_var(&self, name: &str) -> Option<String>;
+    /// 当前主机操作系统 (`std::env::consts::OS`)
+    fn host_os(&self) -> &str;
+}
+
+/// 基于实际系统环境的探测器
+pub struct SystemProbe;
+
+impl ToolchainProbe for SystemProbe {
+    fn installed_targets(&self) -> Option<Vec<String>> {
+        let output = Command::new("rustup")
+            .args(["target", "list", "--installed"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect(),
+        )
+    }
+
+    fn has_command(&self, program: &str, args: &[&str]) -> bool {
+        Command::new(program)
+            .args(args)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok().filter(|v| !v.is_empty())
+    }
+
+    fn host_os(&self) -> &str {
+        std::env::consts::OS
+    }
+}
+
 /// 平台特定构建器
 pub struct PlatformBuilder;
 
 impl PlatformBuilder {
+    /// 检查目标平台所需的工具链和SDK
+    pub fn validate_target(target: BuildTarget) -> Vec<ValidationIssue> {
+        Self::validate_target_with(target, &SystemProbe)
+    }
+
+    /// 使用指定探测器检查目标平台
+    pub fn validate_target_with(
+        target: BuildTarget,
+        probe: &dyn ToolchainProbe,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let rust_target = target.rust_target();
+
+        match probe.installed_targets() {
+            Some(installed) if !installed.iter().any(|t| t == rust_target) => {
+                issues.push(ValidationIssue::error(
+                    format!("Rust target '{}' is not installed", rust_target),
+                    format!("rustup target add {}", rust_target),
+                ));
+            }
+            Some(_) => {}
+            None => issues.push(ValidationIssue::warning(
+                "Could not query installed Rust targets (is rustup installed?)",
+                Some("Install rustup from https://rustup.rs".to_string()),
+            )),
+        }
+
+        match target {
+            BuildTarget::Web => {
+                if !probe.has_command("wasm-pack", &["--version"]) {
+                    issues.push(ValidationIssue::error(
+                        "wasm-pack not found",
+                        "cargo install wasm-pack",
+                    ));
+                }
+            }
+            BuildTarget::Android => {
+                if !probe.has_command("cargo", &["ndk", "--version"]) {
+                    issues.push(ValidationIssue::error(
+                        "cargo-ndk not found",
+                        "cargo install cargo-ndk",
+                    ));
+                }
+                if probe.env_var("ANDROID_NDK_HOME").is_none()
+                    && probe.env_var("ANDROID_NDK_ROOT").is_none()
+                {
+                    issues.push(ValidationIssue::error(
+                        "Android NDK not found",
+                        "Set ANDROID_NDK_HOME to the NDK installation path",
+                    ));
+                }
+                if probe.env_var("ANDROID_HOME").is_none()
+                    && probe.env_var("ANDROID_SDK_ROOT").is_none()
+                {
+                    issues.push(ValidationIssue::warning(
+                        "Android SDK not found; APK packaging will not be available",
+                        Some("Set ANDROID_HOME to the SDK installation path".to_string()),
+                    ));
+                }
+            }
+            BuildTarget::iOS => {
+                if probe.host_os() != "macos" {
+                    issues.push(ValidationIssue::error(
+                        "iOS builds require a macOS host",
+                        "Build on macOS with Xcode installed",
+                    ));
+                } else if !probe.has_command("xcodebuild", &["-version"]) {
+                    issues.push(ValidationIssue::error(
+                        "Xcode command line tools not found",
+                        "xcode-select --install",
+                    ));
+                }
+            }
+            BuildTarget::MacOS if probe.host_os() != "macos" => {
+                issues.push(ValidationIssue::warning(
+                    "Cross-compiling for macOS requires the macOS SDK",
+                    None,
+                ));
+            }
+            BuildTarget::Windows if probe.host_os() != "windows" => {
+                issues.push(ValidationIssue::warning(
+                    "Cross-compiling for the MSVC target requires the Windows SDK",
+                    Some("Consider cargo-xwin for MSVC cross builds".to_string()),
+                ));
+            }
+            _ => {}
+        }
+
+        issues
+    }
+
+    /// 检查问题列表中是否有阻止构建的错误
+    fn ensure_buildable(target: BuildTarget) -> Result<(), String> {
+        let errors: Vec<String> = Self::validate_target(target)
+            .into_iter()
+            .filter(ValidationIssue::is_error)
+            .map(|issue| issue.to_string())
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Cannot build for {}:\n{}",
+                target.name(),
+                errors.join("\n")
+            ))
+        }
+    }
+
     /// 为Web平台构建
     pub fn build_web(
         project_path: &Path,
@@ -210,6 +419,9 @@ impl PlatformBuilder {
         profile: BuildProfile,
         output_dir: &Path,
     ) -> Result<BuildResult, String> {
+        // 在开始构建前检查工具链，避免构建中途失败
+        Self::ensure_buildable(target)?;
+
         match target {
             BuildTarget::Web => Self::build_web(project_path, profile, output_dir),
             BuildTarget::Android => Self::build_android(project_path, profile, output_dir),
@@ -277,4 +489,66 @@ mod tests {
         assert_eq!(target.rust_target(), "wasm32-unknown-unknown");
         assert_eq!(profile.name(), "Debug");
     }
+
+    struct MockProbe {
+        targets: Vec<&'static str>,
+        commands: Vec<&'static str>,
+    }
+
+    impl ToolchainProbe for MockProbe {
+        fn installed_targets(&self) -> Option<Vec<String>> {
+            Some(self.targets.iter().map(|t| t.to_string()).collect())
+        }
+
+        fn has_command(&self, program: &str, _args: &[&str]) -> bool {
+            self.commands.contains(&program)
+        }
+
+        fn env_var(&self, _name: &str) -> Option<String> {
+            None
+        }
+
+        fn host_os(&self) -> &str {
+            "linux"
+        }
+    }
+
+    #[test]
+    fn test_validate_web_target_missing() {
+        let probe = MockProbe {
+            targets: vec!["x86_64-unknown-linux-gnu"],
+            commands: vec!["wasm-pack"],
+        };
+        let issues = PlatformBuilder::validate_target_with(BuildTarget::Web, &probe);
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].is_error());
+        assert!(issues[0].message.contains("wasm32-unknown-unknown"));
+        assert_eq!(
+            issues[0].fix.as_deref(),
+            Some("rustup target add wasm32-unknown-unknown")
+        );
+
+        let probe = MockProbe {
+            targets: vec!["wasm32-unknown-unknown"],
+            commands: vec!["wasm-pack"],
+        };
+        assert!(PlatformBuilder::validate_target_with(BuildTarget::Web, &probe).is_empty());
+    }
+
+    #[test]
+    fn test_validate_android_requires_ndk() {
+        let probe = MockProbe {
+            targets: vec!["aarch64-linux-android"],
+            commands: vec!["cargo"],
+        };
+        let issues = PlatformBuilder::validate_target_with(BuildTarget::Android, &probe);
+
+        assert!(issues
+            .iter()
+            .any(|i| i.is_error() && i.message.contains("NDK")));
+        assert!(issues
+            .iter()
+            .any(|i| i.severity == IssueSeverity::Warning && i.message.contains("SDK")));
+    }
 }
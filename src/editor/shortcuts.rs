@@ -1,10 +1,14 @@
 //! 编辑器快捷键系统
 //!
-//! 提供统一的快捷键管理，支持快捷键绑定、冲突检测和配置持久化
+//! 提供统一的快捷键管理，支持快捷键绑定、多键序列（如 "Ctrl+K Ctrl+S"）、冲突检测和配置持久化
 
 use crate::impl_default;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// 多键序列中两次按键之间允许的最长间隔
+pub const DEFAULT_CHORD_TIMEOUT: Duration = Duration::from_millis(1500);
 
 /// 快捷键修饰键
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -71,11 +75,35 @@ impl Modifiers {
     }
 }
 
+/// 单次按键（修饰键 + 按键）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyStep {
+    pub modifiers: Modifiers,
+    pub key: String, // egui key name
+}
+
+impl KeyStep {
+    pub fn new(modifiers: Modifiers, key: impl Into<String>) -> Self {
+        Self {
+            modifiers,
+            key: key.into(),
+        }
+    }
+
+    pub fn matches(&self, modifiers: Modifiers, key: &str) -> bool {
+        self.modifiers == modifiers && self.key == key
+    }
+}
+
 /// 快捷键组合
+///
+/// `modifiers` + `key` 为第一步，`chord` 为后续按键；单步快捷键的 `chord` 为空。
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Shortcut {
     pub modifiers: Modifiers,
     pub key: String, // egui key name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chord: Vec<KeyStep>,
 }
 
 impl Shortcut {
@@ -83,11 +111,53 @@ impl Shortcut {
         Self {
             modifiers,
             key: key.into(),
+            chord: Vec::new(),
         }
     }
 
+    /// 创建多键序列快捷键，`steps` 不能为空
+    pub fn sequence(steps: Vec<KeyStep>) -> Self {
+        let mut steps = steps.into_iter();
+        let first = steps
+            .next()
+            .expect("shortcut sequence needs at least one step");
+        Self {
+            modifiers: first.modifiers,
+            key: first.key,
+            chord: steps.collect(),
+        }
+    }
+
+    /// 是否与单次按键匹配（仅适用于单步快捷键）
     pub fn matches(&self, modifiers: Modifiers, key: &str) -> bool {
-        self.modifiers == modifiers && self.key == key
+        self.chord.is_empty() && self.modifiers == modifiers && self.key == key
+    }
+
+    /// 序列的步数
+    pub fn step_count(&self) -> usize {
+        1 + self.chord.len()
+    }
+
+    /// 是否为多键序列
+    pub fn is_chord(&self) -> bool {
+        !self.chord.is_empty()
+    }
+
+    /// 第 `index` 步是否与按键匹配
+    fn step_matches(&self, index: usize, step: &KeyStep) -> bool {
+        match index {
+            0 => self.modifiers == step.modifiers && self.key == step.key,
+            i => self.chord.get(i - 1) == Some(step),
+        }
+    }
+
+    /// `steps` 是否为该快捷键的前缀（包括完全相同）
+    fn starts_with(&self, steps: &[KeyStep]) -> bool {
+        steps.len() <= self.step_count()
+            && steps
+                .iter()
+                .enumerate()
+                .all(|(i, step)| self.step_matches(i, step))
     }
 }
 
@@ -215,6 +285,12 @@ impl ShortcutAction {
 pub struct ShortcutManager {
     bindings: HashMap<ShortcutAction, Shortcut>,
     action_callbacks: HashMap<ShortcutAction, Box<dyn Fn() + Send + Sync>>,
+    /// 已输入但尚未完成的序列
+    pending: Vec<KeyStep>,
+    /// 等待中的序列本身也是完整匹配时的动作，序列超时或被打断时触发
+    fallback: Option<ShortcutAction>,
+    last_input: Option<Instant>,
+    chord_timeout: Duration,
 }
 
 impl ShortcutManager {
@@ -222,6 +298,10 @@ impl ShortcutManager {
         let mut manager = Self {
             bindings: HashMap::new(),
             action_callbacks: HashMap::new(),
+            pending: Vec::new(),
+            fallback: None,
+            last_input: None,
+            chord_timeout: DEFAULT_CHORD_TIMEOUT,
         };
 
         // 设置默认快捷键
@@ -276,7 +356,12 @@ impl ShortcutManager {
         self.action_callbacks.insert(action, callback);
     }
 
-    /// 检查快捷键是否被触发
+    /// 设置多键序列的超时时间
+    pub fn set_chord_timeout(&mut self, timeout: Duration) {
+        self.chord_timeout = timeout;
+    }
+
+    /// 检查单步快捷键是否被触发（不处理多键序列）
     pub fn check(&self, modifiers: Modifiers, key: &str) -> Option<ShortcutAction> {
         for (action, shortcut) in &self.bindings {
             if shortcut.matches(modifiers, key) {
@@ -286,15 +371,106 @@ impl ShortcutManager {
         None
     }
 
+    /// 输入一次按键，按触发顺序返回完整匹配的动作
+    ///
+    /// 按键是某个序列的前缀时进入等待状态；超时或按键不匹配时重置序列，
+    /// 并将当前按键作为新序列的第一步重新匹配。等待中的序列本身也是完整匹配时，
+    /// 重置前先触发该动作。
+    pub fn feed(&mut self, modifiers: Modifiers, key: &str, now: Instant) -> Vec<ShortcutAction> {
+        let mut actions: Vec<ShortcutAction> = self.poll(now).into_iter().collect();
+        self.last_input = Some(now);
+
+        let step = KeyStep::new(modifiers, key);
+        let mut candidate = std::mem::take(&mut self.pending);
+        candidate.push(step.clone());
+        let fallback = self.fallback.take();
+
+        if let Some(action) = self.match_steps(&candidate) {
+            actions.extend(action);
+            return actions;
+        }
+        actions.extend(fallback);
+        if candidate.len() > 1 {
+            // 中断的按键重置进度，再作为新序列的第一步处理
+            if let Some(action) = self.match_steps(&[step]) {
+                actions.extend(action);
+            }
+        }
+        actions
+    }
+
+    /// 检查等待中的序列是否超时，超时则重置序列并返回其完整匹配的动作
+    ///
+    /// 没有后续按键时由 `update` 每帧调用，使单步快捷键在超时后仍能触发。
+    pub fn poll(&mut self, now: Instant) -> Option<ShortcutAction> {
+        let expired = self
+            .last_input
+            .is_some_and(|last| now.saturating_duration_since(last) > self.chord_timeout);
+        if !expired || self.pending.is_empty() {
+            return None;
+        }
+        self.pending.clear();
+        self.fallback.take()
+    }
+
+    /// 匹配已输入的按键：`Some(Some(action))` 为完整匹配，`Some(None)` 为序列前缀，
+    /// `None` 为不匹配
+    ///
+    /// 更长的序列优先：已输入的按键同时是某个快捷键的完整匹配和另一个序列的前缀时，
+    /// 继续等待后续按键并记住该完整匹配，结果不依赖绑定的遍历顺序。
+    fn match_steps(&mut self, steps: &[KeyStep]) -> Option<Option<ShortcutAction>> {
+        let mut exact = None;
+        let mut is_prefix = false;
+        for (action, shortcut) in &self.bindings {
+            if !shortcut.starts_with(steps) {
+                continue;
+            }
+            if shortcut.step_count() == steps.len() {
+                exact = Some(action);
+            } else {
+                is_prefix = true;
+            }
+        }
+
+        if is_prefix {
+            self.pending = steps.to_vec();
+            self.fallback = exact.cloned();
+            Some(None)
+        } else if let Some(action) = exact {
+            self.pending.clear();
+            self.fallback = None;
+            Some(Some(action.clone()))
+        } else {
+            None
+        }
+    }
+
+    /// 当前是否处于多键序列的中间状态
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
     /// 处理快捷键输入
-    pub fn handle_input(&self, modifiers: Modifiers, key: &str) -> bool {
-        if let Some(action) = self.check(modifiers, key) {
-            if let Some(callback) = self.action_callbacks.get(&action) {
+    pub fn handle_input(&mut self, modifiers: Modifiers, key: &str) -> bool {
+        let actions = self.feed(modifiers, key, Instant::now());
+        self.run_callbacks(&actions)
+    }
+
+    /// 每帧更新，触发已超时序列的完整匹配
+    pub fn update(&mut self) -> bool {
+        let actions: Vec<ShortcutAction> = self.poll(Instant::now()).into_iter().collect();
+        self.run_callbacks(&actions)
+    }
+
+    fn run_callbacks(&self, actions: &[ShortcutAction]) -> bool {
+        let mut handled = false;
+        for action in actions {
+            if let Some(callback) = self.action_callbacks.get(action) {
                 callback();
-                return true;
+                handled = true;
             }
         }
-        false
+        handled
     }
 
     /// 获取动作的快捷键
@@ -305,24 +481,16 @@ impl ShortcutManager {
     /// 格式化快捷键显示
     pub fn format_shortcut(&self, action: &ShortcutAction) -> String {
         if let Some(shortcut) = self.get_shortcut(action) {
-            let mut parts = Vec::new();
-
-            if shortcut.modifiers.ctrl {
-                parts.push("Ctrl");
-            }
-            if shortcut.modifiers.alt {
-                parts.push("Alt");
-            }
-            if shortcut.modifiers.shift {
-                parts.push("Shift");
-            }
-            if shortcut.modifiers.meta {
-                parts.push("Cmd");
-            }
-
-            parts.push(&shortcut.key);
-
-            parts.join("+")
+            let first = format_step(shortcut.modifiers, &shortcut.key);
+            std::iter::once(first)
+                .chain(
+                    shortcut
+                        .chord
+                        .iter()
+                        .map(|step| format_step(step.modifiers, &step.key)),
+                )
+                .collect::<Vec<_>>()
+                .join(" ")
         } else {
             "None".to_string()
         }
@@ -356,3 +524,156 @@ impl Default for ShortcutManager {
         Self::new()
     }
 }
+
+fn format_step(modifiers: Modifiers, key: &str) -> String {
+    let mut parts = Vec::new();
+
+    if modifiers.ctrl {
+        parts.push("Ctrl");
+    }
+    if modifiers.alt {
+        parts.push("Alt");
+    }
+    if modifiers.shift {
+        parts.push("Shift");
+    }
+    if modifiers.meta {
+        parts.push("Cmd");
+    }
+
+    parts.push(key);
+
+    parts.join("+")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord_manager() -> ShortcutManager {
+        let mut manager = ShortcutManager::new();
+        manager.bind(
+            ShortcutAction::ToggleConsole,
+            Shortcut::sequence(vec![
+                KeyStep::new(Modifiers::ctrl(), "K"),
+                KeyStep::new(Modifiers::ctrl(), "S"),
+            ]),
+        );
+        manager
+    }
+
+    #[test]
+    fn test_chord_fires_after_full_sequence() {
+        let mut manager = chord_manager();
+        let t0 = Instant::now();
+
+        assert_eq!(manager.feed(Modifiers::ctrl(), "K", t0), vec![]);
+        assert!(manager.is_pending());
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "S", t0 + Duration::from_millis(100)),
+            vec![ShortcutAction::ToggleConsole]
+        );
+        assert!(!manager.is_pending());
+        assert_eq!(
+            manager.format_shortcut(&ShortcutAction::ToggleConsole),
+            "Ctrl+K Ctrl+S"
+        );
+
+        // 单步快捷键保持原有行为
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "S", t0 + Duration::from_millis(200)),
+            vec![ShortcutAction::SaveScene]
+        );
+    }
+
+    #[test]
+    fn test_chord_prefers_longest_match() {
+        // HashMap 的遍历顺序随实例变化，多建几个管理器覆盖不同顺序
+        for _ in 0..16 {
+            let mut manager = chord_manager();
+            manager.bind(
+                ShortcutAction::NewScene,
+                Shortcut::new(Modifiers::ctrl(), "K"),
+            );
+            let t0 = Instant::now();
+
+            assert_eq!(manager.feed(Modifiers::ctrl(), "K", t0), vec![]);
+            assert!(manager.is_pending());
+            assert_eq!(
+                manager.feed(Modifiers::ctrl(), "S", t0),
+                vec![ShortcutAction::ToggleConsole]
+            );
+        }
+    }
+
+    #[test]
+    fn test_chord_prefix_falls_back_to_exact_match() {
+        let mut manager = chord_manager();
+        manager.bind(
+            ShortcutAction::NewScene,
+            Shortcut::new(Modifiers::ctrl(), "K"),
+        );
+        let t0 = Instant::now();
+        let late = t0 + DEFAULT_CHORD_TIMEOUT + Duration::from_millis(1);
+
+        // 超时：等待中的 Ctrl+K 作为单步快捷键触发
+        assert_eq!(manager.feed(Modifiers::ctrl(), "K", t0), vec![]);
+        assert_eq!(manager.poll(t0), None);
+        assert_eq!(manager.poll(late), Some(ShortcutAction::NewScene));
+        assert!(!manager.is_pending());
+        assert_eq!(manager.poll(late), None);
+
+        // 被不匹配的按键打断：先触发 Ctrl+K
+        assert_eq!(manager.feed(Modifiers::ctrl(), "K", t0), vec![]);
+        assert_eq!(
+            manager.feed(Modifiers::none(), "X", t0),
+            vec![ShortcutAction::NewScene]
+        );
+        assert!(!manager.is_pending());
+
+        // 被另一个单步快捷键打断：两个动作按顺序触发
+        assert_eq!(manager.feed(Modifiers::ctrl(), "K", t0), vec![]);
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "O", t0),
+            vec![ShortcutAction::NewScene, ShortcutAction::OpenScene]
+        );
+
+        // 超时后才输入下一个按键：同样先触发 Ctrl+K
+        assert_eq!(manager.feed(Modifiers::ctrl(), "K", t0), vec![]);
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "S", late),
+            vec![ShortcutAction::NewScene, ShortcutAction::SaveScene]
+        );
+
+        // 完整的序列不会触发回退
+        let later = late + Duration::from_millis(100);
+        assert_eq!(manager.feed(Modifiers::ctrl(), "K", later), vec![]);
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "S", later),
+            vec![ShortcutAction::ToggleConsole]
+        );
+        assert_eq!(manager.poll(later + DEFAULT_CHORD_TIMEOUT * 2), None);
+    }
+
+    #[test]
+    fn test_chord_resets_on_interrupt_and_timeout() {
+        let mut manager = chord_manager();
+        let t0 = Instant::now();
+
+        manager.feed(Modifiers::ctrl(), "K", t0);
+        assert_eq!(manager.feed(Modifiers::none(), "X", t0), vec![]);
+        assert!(!manager.is_pending());
+        // 进度已重置，Ctrl+S 只触发单步绑定
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "S", t0),
+            vec![ShortcutAction::SaveScene]
+        );
+
+        manager.feed(Modifiers::ctrl(), "K", t0);
+        let late = t0 + DEFAULT_CHORD_TIMEOUT + Duration::from_millis(1);
+        assert_eq!(
+            manager.feed(Modifiers::ctrl(), "S", late),
+            vec![ShortcutAction::SaveScene]
+        );
+    }
+}
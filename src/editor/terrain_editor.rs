@@ -1,5 +1,8 @@
+use super::undo_redo::{Command, CommandError, CommandManager};
 use crate::impl_default;
 use glam::Vec3;
+use std::any::Any;
+use std::collections::HashMap;
 
 /// 地形数据
 #[derive(Clone, Debug)]
@@ -104,6 +107,111 @@ impl TerrainTool {
     }
 }
 
+/// 单个顶点的高度变更
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeightChange {
+    pub index: usize,
+    pub old_height: f32,
+    pub new_height: f32,
+}
+
+/// 地形笔刷编辑命令，上下文为 `TerrainData`
+#[derive(Debug, Clone)]
+pub struct TerrainEditCommand {
+    changes: Vec<HeightChange>,
+    description: String,
+}
+
+impl TerrainEditCommand {
+    pub fn new(tool: TerrainTool, changes: Vec<HeightChange>) -> Self {
+        Self {
+            changes,
+            description: format!("Terrain {}", tool.name()),
+        }
+    }
+
+    /// 变更列表
+    pub fn changes(&self) -> &[HeightChange] {
+        &self.changes
+    }
+
+    fn apply(&self, terrain: &mut TerrainData, undo: bool) {
+        for change in &self.changes {
+            if let Some(h) = terrain.heightmap.get_mut(change.index) {
+                *h = if undo {
+                    change.old_height
+                } else {
+                    change.new_height
+                };
+            }
+        }
+    }
+
+    fn terrain(context: &mut dyn Any) -> Result<&mut TerrainData, String> {
+        context
+            .downcast_mut::<TerrainData>()
+            .ok_or_else(|| "context is not TerrainData".to_string())
+    }
+}
+
+impl Command for TerrainEditCommand {
+    fn execute(&mut self, context: &mut dyn Any) -> Result<(), CommandError> {
+        let terrain = Self::terrain(context).map_err(CommandError::ExecutionFailed)?;
+        self.apply(terrain, false);
+        Ok(())
+    }
+
+    fn undo(&mut self, context: &mut dyn Any) -> Result<(), CommandError> {
+        let terrain = Self::terrain(context).map_err(CommandError::UndoFailed)?;
+        self.apply(terrain, true);
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn can_merge(&self, other: &dyn Command) -> bool {
+        other
+            .as_any()
+            .and_then(|any| any.downcast_ref::<Self>())
+            .is_some_and(|cmd| cmd.description == self.description)
+    }
+
+    fn merge(&mut self, other: &dyn Command) -> Result<(), CommandError> {
+        let other = other
+            .as_any()
+            .and_then(|any| any.downcast_ref::<Self>())
+            .ok_or(CommandError::CannotMerge)?;
+
+        // 每个顶点保留最早的旧值和最新的新值
+        let mut positions: HashMap<usize, usize> = self
+            .changes
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.index, i))
+            .collect();
+        for change in &other.changes {
+            match positions.get(&change.index) {
+                Some(&i) => self.changes[i].new_height = change.new_height,
+                None => {
+                    positions.insert(change.index, self.changes.len());
+                    self.changes.push(*change);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn command_id(&self) -> Option<&str> {
+        Some(&self.description)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+}
+
 /// 地形编辑器
 pub struct TerrainEditor {
     /// 地形数据
@@ -114,8 +222,8 @@ pub struct TerrainEditor {
     pub brush_size: f32,
     /// 笔刷强度
     pub brush_strength: f32,
-    /// 目标高度 (用于Flatten工具)
-    pub target_height: f32,
+    /// 笔刷衰减指数 (1.0 为线性衰减，越大边缘越柔和)
+    pub brush_falloff: f32,
 }
 
 impl TerrainEditor {
@@ -125,13 +233,20 @@ impl TerrainEditor {
             current_tool: TerrainTool::Raise,
             brush_size: 5.0,
             brush_strength: 1.0,
-            target_height: 0.0,
+            brush_falloff: 1.0,
         }
     }
 
-    /// 应用工具到指定位置
-    pub fn apply_tool(&mut self, x: usize, y: usize) {
+    /// 笔刷在距离 `dist` 处的权重 (0..=1)，`brush_falloff` 越大边缘衰减越快
+    fn brush_weight(&self, dist: f32) -> f32 {
+        let t = 1.0 - (dist / self.brush_size).min(1.0);
+        t.powf(self.brush_falloff.max(0.01))
+    }
+
+    /// 笔刷覆盖的所有顶点索引及权重
+    fn brush_cells(&self, x: usize, y: usize) -> Vec<(usize, f32)> {
         let brush_radius = self.brush_size as usize;
+        let mut cells = Vec::new();
 
         for dy in 0..=brush_radius * 2 {
             for dx in 0..=brush_radius * 2 {
@@ -151,50 +266,78 @@ impl TerrainEditor {
                     continue;
                 }
 
-                // 计算衰减
-                let falloff = 1.0 - (dist / self.brush_size).min(1.0);
-                let strength = self.brush_strength * falloff;
-
-                // 应用工具
-                if let Some(current_height) = self.terrain.get_height(tx, ty) {
-                    let new_height = match self.current_tool {
-                        TerrainTool::Raise => current_height + strength,
-                        TerrainTool::Lower => current_height - strength,
-                        TerrainTool::Flatten => {
-                            current_height + (self.target_height - current_height) * strength
-                        }
-                        TerrainTool::Smooth => {
-                            // 简化的平滑:取周围平均值
-                            let mut sum = current_height;
-                            let mut count = 1;
-
-                            if let Some(h) = self.terrain.get_height(tx.saturating_sub(1), ty) {
-                                sum += h;
-                                count += 1;
-                            }
-                            if let Some(h) = self.terrain.get_height(tx + 1, ty) {
-                                sum += h;
-                                count += 1;
-                            }
-                            if let Some(h) = self.terrain.get_height(tx, ty.saturating_sub(1)) {
-                                sum += h;
-                                count += 1;
-                            }
-                            if let Some(h) = self.terrain.get_height(tx, ty + 1) {
-                                sum += h;
-                                count += 1;
-                            }
-
-                            let avg = sum / count as f32;
-                            current_height + (avg - current_height) * strength
-                        }
-                        TerrainTool::Paint => current_height, // Paint工具不修改高度
-                    };
-
-                    self.terrain.set_height(tx, ty, new_height);
+                cells.push((ty * self.terrain.width + tx, self.brush_weight(dist)));
+            }
+        }
+
+        cells
+    }
+
+    /// 计算在指定位置应用当前工具产生的高度变更 (不修改地形)
+    ///
+    /// 所有变更基于应用前的高度快照计算，结果与遍历顺序无关。
+    pub fn compute_brush(&self, x: usize, y: usize) -> TerrainEditCommand {
+        let cells = self.brush_cells(x, y);
+        let heights = &self.terrain.heightmap;
+
+        // Smooth 的目标为笔刷内按衰减加权的平均高度，Flatten 的目标为笔刷中心高度
+        let target = match self.current_tool {
+            TerrainTool::Smooth => {
+                let (sum, weight) = cells
+                    .iter()
+                    .fold((0.0, 0.0), |(s, w), &(i, wt)| (s + heights[i] * wt, w + wt));
+                (weight > 0.0).then(|| sum / weight)
+            }
+            TerrainTool::Flatten => self.terrain.get_height(x, y),
+            _ => None,
+        };
+
+        let mut changes = Vec::with_capacity(cells.len());
+        for (index, weight) in cells {
+            let current_height = heights[index];
+            let strength = self.brush_strength * weight;
+
+            let new_height = match (self.current_tool, target) {
+                (TerrainTool::Raise, _) => current_height + strength,
+                (TerrainTool::Lower, _) => current_height - strength,
+                (TerrainTool::Smooth | TerrainTool::Flatten, Some(target)) => {
+                    current_height + (target - current_height) * strength.min(1.0)
                 }
+                _ => current_height, // Paint工具不修改高度
+            };
+
+            if new_height != current_height {
+                changes.push(HeightChange {
+                    index,
+                    old_height: current_height,
+                    new_height,
+                });
             }
         }
+
+        TerrainEditCommand::new(self.current_tool, changes)
+    }
+
+    /// 应用工具到指定位置
+    pub fn apply_tool(&mut self, x: usize, y: usize) {
+        let command = self.compute_brush(x, y);
+        command.apply(&mut self.terrain, false);
+    }
+
+    /// 通过命令管理器应用工具，使编辑可撤销
+    ///
+    /// 同一工具在合并窗口内的连续笔刷会合并为一个撤销步骤。
+    pub fn apply_tool_undoable(
+        &mut self,
+        x: usize,
+        y: usize,
+        commands: &mut CommandManager,
+    ) -> Result<(), CommandError> {
+        let command = self.compute_brush(x, y);
+        if command.changes.is_empty() {
+            return Ok(());
+        }
+        commands.execute(Box::new(command), &mut self.terrain)
     }
 
     /// 渲染地形编辑器UI
@@ -231,12 +374,10 @@ impl TerrainEditor {
             ui.add(egui::Slider::new(&mut self.brush_strength, 0.1..=5.0));
         });
 
-        if self.current_tool == TerrainTool::Flatten {
-            ui.horizontal(|ui| {
-                ui.label("Target Height:");
-                ui.add(egui::Slider::new(&mut self.target_height, -10.0..=10.0));
-            });
-        }
+        ui.horizontal(|ui| {
+            ui.label("Brush Falloff:");
+            ui.add(egui::Slider::new(&mut self.brush_falloff, 0.1..=4.0));
+        });
 
         ui.separator();
 
@@ -339,4 +480,57 @@ mod tests {
         let height = editor.terrain.get_height(5, 5).unwrap();
         assert!(height > 0.0);
     }
+
+    fn variance_within(terrain: &TerrainData, cx: usize, cy: usize, radius: f32) -> f32 {
+        let mut values = Vec::new();
+        for y in 0..terrain.height {
+            for x in 0..terrain.width {
+                let (dx, dy) = (x as f32 - cx as f32, y as f32 - cy as f32);
+                if (dx * dx + dy * dy).sqrt() <= radius {
+                    values.push(terrain.get_height(x, y).unwrap());
+                }
+            }
+        }
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32
+    }
+
+    #[test]
+    fn test_smooth_brush_reduces_variance() {
+        let mut editor = TerrainEditor::new(16, 16);
+        for y in 0..16 {
+            for x in 0..16 {
+                let spike = if (x + y) % 2 == 0 { 5.0 } else { 0.0 };
+                editor.terrain.set_height(x, y, spike);
+            }
+        }
+        editor.current_tool = TerrainTool::Smooth;
+        editor.brush_size = 4.0;
+        editor.brush_strength = 2.0;
+
+        let before = variance_within(&editor.terrain, 8, 8, 4.0);
+        let mut commands = CommandManager::new(10);
+        editor.apply_tool_undoable(8, 8, &mut commands).unwrap();
+        let after = variance_within(&editor.terrain, 8, 8, 4.0);
+        assert!(after < before, "variance {} -> {}", before, after);
+
+        // 可撤销
+        commands.undo(&mut editor.terrain).unwrap();
+        assert_eq!(variance_within(&editor.terrain, 8, 8, 4.0), before);
+    }
+
+    #[test]
+    fn test_flatten_brush_pulls_to_center() {
+        let mut editor = TerrainEditor::new(10, 10);
+        editor.terrain.set_height(5, 5, 3.0);
+        editor.terrain.set_height(6, 5, 1.0);
+        editor.current_tool = TerrainTool::Flatten;
+        editor.brush_size = 3.0;
+        editor.brush_strength = 1.0;
+
+        editor.apply_tool(5, 5);
+        let neighbor = editor.terrain.get_height(6, 5).unwrap();
+        assert!(neighbor > 1.0 && neighbor <= 3.0);
+        assert_eq!(editor.terrain.get_height(5, 5), Some(3.0));
+    }
 }
@@ -37,6 +37,57 @@ pub enum TransformAxis {
     All,
 }
 
+/// 变换工具吸附配置
+///
+/// 吸附作用于拖拽增量而不是绝对值，物体会保持原有的网格偏移。
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct GizmoSnapConfig {
+    /// 是否始终吸附 (否则仅在按住 Ctrl 时吸附)
+    pub enabled: bool,
+    /// 移动网格步长
+    pub translate_step: f32,
+    /// 旋转角度步长 (度)
+    pub rotate_step_degrees: f32,
+    /// 缩放步长
+    pub scale_step: f32,
+}
+
+impl Default for GizmoSnapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            translate_step: 1.0,
+            rotate_step_degrees: 15.0,
+            scale_step: 0.1,
+        }
+    }
+}
+
+impl GizmoSnapConfig {
+    /// 吸附移动增量
+    pub fn snap_translation(&self, delta: Vec3) -> Vec3 {
+        snap_delta(delta, self.translate_step)
+    }
+
+    /// 吸附旋转增量 (度)
+    pub fn snap_rotation_degrees(&self, delta: Vec3) -> Vec3 {
+        snap_delta(delta, self.rotate_step_degrees)
+    }
+
+    /// 吸附缩放增量
+    pub fn snap_scale(&self, delta: Vec3) -> Vec3 {
+        snap_delta(delta, self.scale_step)
+    }
+}
+
+/// 将增量按步长四舍五入，步长不大于 0 时不吸附
+pub fn snap_delta(delta: Vec3, step: f32) -> Vec3 {
+    if step <= 0.0 {
+        return delta;
+    }
+    (delta / step).round() * step
+}
+
 /// 变换工具
 pub struct TransformGizmo {
     /// 当前模式
@@ -47,6 +98,8 @@ pub struct TransformGizmo {
     drag_start: Option<egui::Pos2>,
     /// 拖拽起始值
     drag_start_value: Option<Vec3>,
+    /// 拖拽中未吸附的原始值
+    drag_raw_value: Option<Vec3>,
 }
 
 impl TransformGizmo {
@@ -86,6 +139,11 @@ impl TransformGizmo {
             );
         });
 
+        if let Some(mut config) = world.get_resource_mut::<GizmoSnapConfig>() {
+            ui.checkbox(&mut config.enabled, "Snap (hold Ctrl)");
+        }
+        let snap = Self::active_snap(ui, world.get_resource::<GizmoSnapConfig>().copied());
+
         ui.separator();
 
         // 如果有选中的实体,显示变换控制
@@ -93,13 +151,14 @@ impl TransformGizmo {
             if let Some(mut transform) = world.get_mut::<Transform>(entity) {
                 match self.mode {
                     GizmoMode::Translate => {
-                        transform_changed |= self.render_translate_controls(ui, &mut transform);
+                        transform_changed |=
+                            self.render_translate_controls(ui, &mut transform, snap);
                     }
                     GizmoMode::Rotate => {
-                        transform_changed |= self.render_rotate_controls(ui, &mut transform);
+                        transform_changed |= self.render_rotate_controls(ui, &mut transform, snap);
                     }
                     GizmoMode::Scale => {
-                        transform_changed |= self.render_scale_controls(ui, &mut transform);
+                        transform_changed |= self.render_scale_controls(ui, &mut transform, snap);
                     }
                 }
             } else {
@@ -112,32 +171,68 @@ impl TransformGizmo {
         transform_changed
     }
 
-    /// 渲染移动控制
-    fn render_translate_controls(&mut self, ui: &mut egui::Ui, transform: &mut Transform) -> bool {
+    /// 当前帧生效的吸附配置 (开启吸附或按住 Ctrl 时)
+    fn active_snap(ui: &egui::Ui, config: Option<GizmoSnapConfig>) -> Option<GizmoSnapConfig> {
+        let config = config?;
+        let ctrl_held = ui.input(|i| i.modifiers.ctrl || i.modifiers.command);
+        (config.enabled || ctrl_held).then_some(config)
+    }
+
+    /// 编辑三个分量，吸附作用于相对拖拽起点的增量
+    ///
+    /// 拖拽过程中保存未吸附的原始值，使增量可以跨越多个吸附步长累积。
+    fn drag_axes(
+        &mut self,
+        ui: &mut egui::Ui,
+        current: Vec3,
+        speed: f64,
+        suffix: &str,
+        snap_step: Option<f32>,
+    ) -> Option<Vec3> {
+        let start = self.drag_start_value.unwrap_or(current);
+        let mut raw = self.drag_raw_value.unwrap_or(current);
         let mut changed = false;
+        let mut dragging = false;
+
+        for (label, value) in [("X:", &mut raw.x), ("Y:", &mut raw.y), ("Z:", &mut raw.z)] {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                let response = ui.add(egui::DragValue::new(value).speed(speed).suffix(suffix));
+                changed |= response.changed();
+                dragging |= response.dragged();
+            });
+        }
 
-        ui.label("Position:");
+        if dragging {
+            self.drag_start_value = Some(start);
+            self.drag_raw_value = Some(raw);
+        } else {
+            self.drag_start_value = None;
+            self.drag_raw_value = None;
+        }
 
-        ui.horizontal(|ui| {
-            ui.label("X:");
-            changed |= ui
-                .add(egui::DragValue::new(&mut transform.pos.x).speed(0.1))
-                .changed();
-        });
+        changed.then(|| match snap_step {
+            Some(step) => start + snap_delta(raw - start, step),
+            None => raw,
+        })
+    }
 
-        ui.horizontal(|ui| {
-            ui.label("Y:");
-            changed |= ui
-                .add(egui::DragValue::new(&mut transform.pos.y).speed(0.1))
-                .changed();
-        });
+    /// 渲染移动控制
+    fn render_translate_controls(
+        &mut self,
+        ui: &mut egui::Ui,
+        transform: &mut Transform,
+        snap: Option<GizmoSnapConfig>,
+    ) -> bool {
+        let mut changed = false;
 
-        ui.horizontal(|ui| {
-            ui.label("Z:");
-            changed |= ui
-                .add(egui::DragValue::new(&mut transform.pos.z).speed(0.1))
-                .changed();
-        });
+        ui.label("Position:");
+
+        let step = snap.map(|s| s.translate_step);
+        if let Some(pos) = self.drag_axes(ui, transform.pos, 0.1, "", step) {
+            transform.pos = pos;
+            changed = true;
+        }
 
         ui.separator();
 
@@ -153,54 +248,29 @@ impl TransformGizmo {
     }
 
     /// 渲染旋转控制
-    fn render_rotate_controls(&mut self, ui: &mut egui::Ui, transform: &mut Transform) -> bool {
+    fn render_rotate_controls(
+        &mut self,
+        ui: &mut egui::Ui,
+        transform: &mut Transform,
+        snap: Option<GizmoSnapConfig>,
+    ) -> bool {
         let mut changed = false;
 
         ui.label("Rotation (Euler Angles):");
 
         // 转换为欧拉角
-        let (mut x, mut y, mut z) = transform.rot.to_euler(glam::EulerRot::XYZ);
-        x = x.to_degrees();
-        y = y.to_degrees();
-        z = z.to_degrees();
+        let (x, y, z) = transform.rot.to_euler(glam::EulerRot::XYZ);
+        let degrees = Vec3::new(x.to_degrees(), y.to_degrees(), z.to_degrees());
 
-        ui.horizontal(|ui| {
-            ui.label("X:");
-            if ui
-                .add(egui::DragValue::new(&mut x).speed(1.0).suffix("°"))
-                .changed()
-            {
-                changed = true;
-            }
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Y:");
-            if ui
-                .add(egui::DragValue::new(&mut y).speed(1.0).suffix("°"))
-                .changed()
-            {
-                changed = true;
-            }
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Z:");
-            if ui
-                .add(egui::DragValue::new(&mut z).speed(1.0).suffix("°"))
-                .changed()
-            {
-                changed = true;
-            }
-        });
-
-        if changed {
+        let step = snap.map(|s| s.rotate_step_degrees);
+        if let Some(euler) = self.drag_axes(ui, degrees, 1.0, "°", step) {
             transform.rot = Quat::from_euler(
                 glam::EulerRot::XYZ,
-                x.to_radians(),
-                y.to_radians(),
-                z.to_radians(),
+                euler.x.to_radians(),
+                euler.y.to_radians(),
+                euler.z.to_radians(),
             );
+            changed = true;
         }
 
         ui.separator();
@@ -217,43 +287,21 @@ impl TransformGizmo {
     }
 
     /// 渲染缩放控制
-    fn render_scale_controls(&mut self, ui: &mut egui::Ui, transform: &mut Transform) -> bool {
+    fn render_scale_controls(
+        &mut self,
+        ui: &mut egui::Ui,
+        transform: &mut Transform,
+        snap: Option<GizmoSnapConfig>,
+    ) -> bool {
         let mut changed = false;
 
         ui.label("Scale:");
 
-        ui.horizontal(|ui| {
-            ui.label("X:");
-            changed |= ui
-                .add(
-                    egui::DragValue::new(&mut transform.scale.x)
-                        .speed(0.01)
-                        .range(0.01..=10.0),
-                )
-                .changed();
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Y:");
-            changed |= ui
-                .add(
-                    egui::DragValue::new(&mut transform.scale.y)
-                        .speed(0.01)
-                        .range(0.01..=10.0),
-                )
-                .changed();
-        });
-
-        ui.horizontal(|ui| {
-            ui.label("Z:");
-            changed |= ui
-                .add(
-                    egui::DragValue::new(&mut transform.scale.z)
-                        .speed(0.01)
-                        .range(0.01..=10.0),
-                )
-                .changed();
-        });
+        let step = snap.map(|s| s.scale_step);
+        if let Some(scale) = self.drag_axes(ui, transform.scale, 0.01, "", step) {
+            transform.scale = scale.clamp(Vec3::splat(0.01), Vec3::splat(10.0));
+            changed = true;
+        }
 
         ui.separator();
 
@@ -413,6 +461,41 @@ impl Default for TransformGizmo {
             selected_axis: None,
             drag_start: None,
             drag_start_value: None,
+            drag_raw_value: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_translation_delta() {
+        let config = GizmoSnapConfig {
+            translate_step: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.snap_translation(Vec3::new(7.0, 0.0, 0.0)),
+            Vec3::new(5.0, 0.0, 0.0)
+        );
+
+        // 吸附作用于增量，保留原有的网格偏移
+        let start = Vec3::new(1.5, 0.0, 0.0);
+        let snapped = start + config.snap_translation(Vec3::new(8.0, 0.0, 0.0));
+        assert_eq!(snapped, Vec3::new(11.5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_snap_rotation_and_scale() {
+        let config = GizmoSnapConfig::default();
+        assert_eq!(
+            config.snap_rotation_degrees(Vec3::new(20.0, -8.0, 0.0)),
+            Vec3::new(15.0, -15.0, 0.0)
+        );
+        let scale = config.snap_scale(Vec3::splat(0.26));
+        assert!((scale - Vec3::splat(0.3)).abs().max_element() < 1e-5);
+        assert_eq!(snap_delta(Vec3::splat(0.7), 0.0), Vec3::splat(0.7));
+    }
+}
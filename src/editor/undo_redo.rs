@@ -57,6 +57,11 @@ pub trait Command: fmt::Debug + Send {
     fn command_id(&self) -> Option<&str> {
         None
     }
+
+    /// 获取具体类型 (用于合并时向下转型)
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
 /// 命令错误
@@ -92,6 +97,10 @@ pub struct CommandManager {
     merge_window_ms: u64,
     /// 上次命令时间戳
     last_command_time: std::time::Instant,
+    /// 是否处于显式合并组中
+    in_merge_group: bool,
+    /// 下一个命令不与栈顶合并 (合并组边界、撤销/重做后)
+    merge_sealed: bool,
     /// 变更监听器
     change_listeners: Vec<Box<dyn Fn(&dyn Command, bool) + Send>>,
 }
@@ -106,6 +115,8 @@ impl CommandManager {
             is_undoing: false,
             merge_window_ms: 500,
             last_command_time: std::time::Instant::now(),
+            in_merge_group: false,
+            merge_sealed: false,
             change_listeners: Vec::new(),
         }
     }
@@ -115,6 +126,26 @@ impl CommandManager {
         self.merge_window_ms = ms;
     }
 
+    /// 开始合并组
+    ///
+    /// 在 `end_merge_group` 之前，可合并的命令无论间隔多久都会合并为一个撤销步骤
+    /// (例如拖动滑块的整个过程)。组内第一个命令不会与组之前的命令合并。
+    pub fn begin_merge_group(&mut self) {
+        self.in_merge_group = true;
+        self.merge_sealed = true;
+    }
+
+    /// 结束合并组，之后的命令不会再合并到组内的命令
+    pub fn end_merge_group(&mut self) {
+        self.in_merge_group = false;
+        self.merge_sealed = true;
+    }
+
+    /// 是否处于合并组中
+    pub fn is_merge_group_active(&self) -> bool {
+        self.in_merge_group
+    }
+
     /// 添加变更监听器
     pub fn add_listener<F>(&mut self, listener: F)
     where
@@ -142,7 +173,9 @@ impl CommandManager {
         let now = std::time::Instant::now();
         let time_since_last = now.duration_since(self.last_command_time).as_millis() as u64;
 
-        let merged = if time_since_last < self.merge_window_ms {
+        let can_merge =
+            !self.merge_sealed && (self.in_merge_group || time_since_last < self.merge_window_ms);
+        let merged = if can_merge {
             if let Some(last) = self.undo_stack.back_mut() {
                 if last.can_merge(&*command) {
                     last.merge(&*command).is_ok()
@@ -171,6 +204,7 @@ impl CommandManager {
 
         // 更新时间戳
         self.last_command_time = now;
+        self.merge_sealed = false;
 
         // 通知监听器
         if let Some(cmd) = self.undo_stack.back() {
@@ -194,6 +228,7 @@ impl CommandManager {
             self.is_undoing = false;
 
             result?;
+            self.merge_sealed = true;
 
             // 通知监听器
             for listener in &self.change_listeners {
@@ -223,6 +258,7 @@ impl CommandManager {
             self.is_undoing = false;
 
             result?;
+            self.merge_sealed = true;
 
             // 通知监听器
             for listener in &self.change_listeners {
@@ -463,18 +499,32 @@ impl<T: Clone + Send + fmt::Debug + 'static> Command for PropertyChangeCommand<T
     }
 
     fn can_merge(&self, other: &dyn Command) -> bool {
-        // 尝试将 other 转换为相同类型的命令
-        if let Some(cmd_id) = other.command_id() {
-            if let Some(my_id) = self.command_id() {
-                return cmd_id == my_id;
-            }
-        }
-        false
+        // 只合并同一目标、同一属性 (描述相同) 的同类型命令
+        other
+            .as_any()
+            .and_then(|any| any.downcast_ref::<Self>())
+            .is_some_and(|cmd| {
+                cmd.target_id == self.target_id && cmd.command_id() == self.command_id()
+            })
+    }
+
+    fn merge(&mut self, other: &dyn Command) -> Result<(), CommandError> {
+        // 保留最初的旧值，只更新新值，撤销时一次恢复到变更前
+        let other = other
+            .as_any()
+            .and_then(|any| any.downcast_ref::<Self>())
+            .ok_or(CommandError::CannotMerge)?;
+        self.new_value = other.new_value.clone();
+        Ok(())
     }
 
     fn command_id(&self) -> Option<&str> {
         Some(&self.description)
     }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
 }
 
 // ============================================================================
@@ -627,6 +677,69 @@ mod tests {
         assert!(!manager.can_redo());
     }
 
+    fn set_value_command(old_value: f32, new_value: f32) -> Box<dyn Command> {
+        Box::new(PropertyChangeCommand::new(
+            1,
+            old_value,
+            new_value,
+            "Set Value",
+            |context, _, value: &f32| {
+                let target = context
+                    .downcast_mut::<f32>()
+                    .ok_or_else(|| "context is not f32".to_string())?;
+                *target = *value;
+                Ok(())
+            },
+        ))
+    }
+
+    #[test]
+    fn test_merge_group_single_undo() {
+        let mut manager = CommandManager::new(10);
+        let mut context: f32 = 0.0;
+
+        manager.begin_merge_group();
+        for i in 0..10 {
+            let old_value = context;
+            manager
+                .execute(set_value_command(old_value, (i + 1) as f32), &mut context)
+                .unwrap();
+        }
+        manager.end_merge_group();
+
+        assert_eq!(context, 10.0);
+        assert_eq!(manager.undo_count(), 1);
+
+        // 一次撤销恢复到拖动前的值
+        manager.undo(&mut context).unwrap();
+        assert_eq!(context, 0.0);
+        assert!(!manager.can_undo());
+
+        manager.redo(&mut context).unwrap();
+        assert_eq!(context, 10.0);
+    }
+
+    #[test]
+    fn test_merge_group_boundaries() {
+        let mut manager = CommandManager::new(10);
+        let mut context: f32 = 0.0;
+
+        manager
+            .execute(set_value_command(0.0, 1.0), &mut context)
+            .unwrap();
+        manager.begin_merge_group();
+        manager
+            .execute(set_value_command(1.0, 2.0), &mut context)
+            .unwrap();
+        manager.end_merge_group();
+        manager
+            .execute(set_value_command(2.0, 3.0), &mut context)
+            .unwrap();
+
+        // 合并组前后的命令各自独立
+        assert_eq!(manager.undo_count(), 3);
+    }
+
     #[test]
     fn test_composite_command() {
         let mut composite = CompositeCommand::new("Multiple Changes");
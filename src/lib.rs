@@ -117,6 +117,9 @@ pub mod ui;
 /// XR (VR/AR/MR) support
 pub mod xr;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
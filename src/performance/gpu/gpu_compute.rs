@@ -9,6 +9,12 @@
 use std::fmt;
 use std::sync::Arc;
 
+/// 间接调度参数大小 (x, y, z 三个 u32)
+pub const DISPATCH_INDIRECT_SIZE: u64 = 12;
+
+/// 归约和前缀和着色器的工作组大小，每个工作组处理这么多个元素
+pub const PRIMITIVE_WORKGROUP_SIZE: u32 = 256;
+
 /// 计算着色器配置
 #[derive(Debug, Clone)]
 pub struct ComputeShaderConfig {
@@ -99,6 +105,14 @@ pub struct ComputePipeline {
     pub buffers: Vec<Arc<GPUBuffer>>,
     /// 是否已编译
     pub compiled: bool,
+    /// 在设备上编译后的 WGPU 资源
+    gpu: Option<GpuComputeState>,
+}
+
+/// 设备上的计算管道和已绑定的绑定组
+struct GpuComputeState {
+    pipeline: wgpu::ComputePipeline,
+    bind_groups: Vec<(u32, wgpu::BindGroup)>,
 }
 
 impl ComputePipeline {
@@ -110,7 +124,112 @@ impl ComputePipeline {
             bind_groups: Vec::new(),
             buffers: Vec::new(),
             compiled: false,
+            gpu: None,
+        }
+    }
+
+    /// 在设备上编译着色器并创建计算管道 (绑定组布局由着色器推导)
+    pub fn compile_on_device(&mut self, device: &wgpu::Device) {
+        let label = format!("Compute Pipeline {}", self.id);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&label),
+            source: wgpu::ShaderSource::Wgsl(self.config.shader_code.as_str().into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(&label),
+            layout: None,
+            module: &shader,
+            entry_point: &self.config.entry_point,
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        });
+
+        self.gpu = Some(GpuComputeState {
+            pipeline,
+            bind_groups: Vec::new(),
+        });
+        self.compiled = true;
+    }
+
+    /// 获取绑定组布局，用于创建绑定组
+    pub fn bind_group_layout(&self, index: u32) -> Option<wgpu::BindGroupLayout> {
+        self.gpu
+            .as_ref()
+            .map(|gpu| gpu.pipeline.get_bind_group_layout(index))
+    }
+
+    /// 设置调度时使用的绑定组
+    pub fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: wgpu::BindGroup,
+    ) -> Result<(), String> {
+        let gpu = self
+            .gpu
+            .as_mut()
+            .ok_or_else(|| "Pipeline not compiled on a device".to_string())?;
+        gpu.bind_groups.retain(|(i, _)| *i != index);
+        gpu.bind_groups.push((index, bind_group));
+        Ok(())
+    }
+
+    /// 使用配置中的工作组数量调度
+    pub fn dispatch(&self, encoder: &mut wgpu::CommandEncoder) -> Result<(), String> {
+        let (x, y, z) = self.config.workgroup_count;
+        let mut pass = self.begin_pass(encoder)?;
+        pass.dispatch_workgroups(x, y, z);
+        Ok(())
+    }
+
+    /// 间接调度：工作组数量从 `indirect_buffer` 的 `offset` 处读取 (x, y, z 三个 u32)
+    ///
+    /// 用于 GPU 驱动的工作负载，数量可由前一个计算通道写入
+    pub fn dispatch_indirect(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        indirect_buffer: &wgpu::Buffer,
+        offset: u64,
+    ) -> Result<(), String> {
+        if !indirect_buffer
+            .usage()
+            .contains(wgpu::BufferUsages::INDIRECT)
+        {
+            return Err("Indirect buffer is missing BufferUsages::INDIRECT".to_string());
+        }
+        if !offset.is_multiple_of(4) {
+            return Err(format!("Indirect offset {} is not 4-byte aligned", offset));
+        }
+        if offset + DISPATCH_INDIRECT_SIZE > indirect_buffer.size() {
+            return Err(format!(
+                "Indirect buffer of {} bytes is too small for dispatch args at offset {}",
+                indirect_buffer.size(),
+                offset
+            ));
+        }
+
+        let mut pass = self.begin_pass(encoder)?;
+        pass.dispatch_workgroups_indirect(indirect_buffer, offset);
+        Ok(())
+    }
+
+    /// 开始计算通道并设置管道和绑定组
+    fn begin_pass<'a>(
+        &'a self,
+        encoder: &'a mut wgpu::CommandEncoder,
+    ) -> Result<wgpu::ComputePass<'a>, String> {
+        let gpu = self
+            .gpu
+            .as_ref()
+            .ok_or_else(|| "Pipeline not compiled on a device".to_string())?;
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Compute Pipeline Pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&gpu.pipeline);
+        for (index, bind_group) in &gpu.bind_groups {
+            pass.set_bind_group(*index, bind_group, &[]);
         }
+        Ok(pass)
     }
 
     /// 添加绑定组条目
@@ -145,6 +264,7 @@ impl fmt::Debug for ComputePipeline {
         f.debug_struct("ComputePipeline")
             .field("id", &self.id)
             .field("compiled", &self.compiled)
+            .field("on_device", &self.gpu.is_some())
             .field("buffers", &self.buffers.len())
             .field("bind_groups", &self.bind_groups.len())
             .finish()
@@ -234,6 +354,59 @@ impl ComputeResourceManager {
     }
 }
 
+/// 并行归约运算
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReduceOp {
+    Sum,
+    Min,
+    Max,
+}
+
+impl ReduceOp {
+    /// 合并两个值的 WGSL 表达式
+    fn wgsl_combine(self) -> &'static str {
+        match self {
+            ReduceOp::Sum => "a + b",
+            ReduceOp::Min => "min(a, b)",
+            ReduceOp::Max => "max(a, b)",
+        }
+    }
+
+    /// 运算的单位元，用于填充越界的线程
+    fn wgsl_identity(self, elem_type: ElementType) -> &'static str {
+        match (self, elem_type) {
+            (ReduceOp::Sum, ElementType::F32) => "0.0",
+            (ReduceOp::Sum, ElementType::U32) => "0u",
+            (ReduceOp::Min, ElementType::F32) => "3.40282347e+38",
+            (ReduceOp::Min, ElementType::U32) => "4294967295u",
+            (ReduceOp::Max, ElementType::F32) => "-3.40282347e+38",
+            (ReduceOp::Max, ElementType::U32) => "0u",
+        }
+    }
+}
+
+/// 归约和前缀和的元素类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementType {
+    F32,
+    U32,
+}
+
+impl ElementType {
+    /// WGSL 类型名
+    pub fn wgsl(self) -> &'static str {
+        match self {
+            ElementType::F32 => "f32",
+            ElementType::U32 => "u32",
+        }
+    }
+
+    /// 元素大小 (字节)
+    pub fn size(self) -> u64 {
+        4
+    }
+}
+
 /// WGSL (WebGPU Shading Language) 计算着色器生成器
 pub struct ComputeShaderGenerator;
 
@@ -359,6 +532,295 @@ fn main(@builtin(global_invocation_id) global_id: vec3u) {
         .to_string()
     }
 
+    /// 生成均匀网格宽相位着色器
+    ///
+    /// 入口点按顺序调度: `count_cells` -> `prefix_sum` -> `scatter_bodies` -> `find_pairs`。
+    /// 物体按所在格子散列分桶，只在相邻 27 个格子内生成候选对，
+    /// 候选对写入 `pairs` 后交给窄相位测试。调度前需将 `cell_counts` 和 `pair_count` 清零，
+    /// `cell_start` 长度为 `table_size + 1`。
+    pub fn generate_broadphase_shader() -> String {
+        r#"
+struct PhysicsBody {
+    position: vec3f,
+    inv_mass: f32,
+    velocity: vec3f,
+    angular_velocity: f32,
+    force: vec3f,
+    _padding: f32,
+}
+
+struct GridParams {
+    body_count: u32,
+    table_size: u32,
+    cell_size: f32,
+    max_pairs: u32,
+}
+
+@group(0) @binding(0)
+var<storage, read> bodies: array<PhysicsBody>;
+
+@group(0) @binding(1)
+var<storage, read_write> cell_counts: array<atomic<u32>>;
+
+@group(0) @binding(2)
+var<storage, read_write> cell_start: array<u32>;
+
+@group(0) @binding(3)
+var<storage, read_write> sorted_bodies: array<u32>;
+
+@group(0) @binding(4)
+var<storage, read_write> pairs: array<vec2u>;
+
+@group(0) @binding(5)
+var<storage, read_write> pair_count: atomic<u32>;
+
+@group(0) @binding(6)
+var<uniform> params: GridParams;
+
+fn cell_coord(position: vec3f) -> vec3i {
+    return vec3i(floor(position / params.cell_size));
+}
+
+fn cell_hash(cell: vec3i) -> u32 {
+    let h = (u32(cell.x) * 73856093u) ^ (u32(cell.y) * 19349663u) ^ (u32(cell.z) * 83492791u);
+    return h % params.table_size;
+}
+
+@compute @workgroup_size(64)
+fn count_cells(@builtin(global_invocation_id) global_id: vec3u) {
+    let idx = global_id.x;
+    if (idx >= params.body_count) {
+        return;
+    }
+    atomicAdd(&cell_counts[cell_hash(cell_coord(bodies[idx].position))], 1u);
+}
+
+// 前缀和计算每个桶的起始偏移，同时把 cell_counts 重置为写入游标
+@compute @workgroup_size(1)
+fn prefix_sum() {
+    var offset = 0u;
+    for (var h = 0u; h < params.table_size; h++) {
+        let count = atomicLoad(&cell_counts[h]);
+        cell_start[h] = offset;
+        atomicStore(&cell_counts[h], offset);
+        offset += count;
+    }
+    cell_start[params.table_size] = offset;
+}
+
+@compute @workgroup_size(64)
+fn scatter_bodies(@builtin(global_invocation_id) global_id: vec3u) {
+    let idx = global_id.x;
+    if (idx >= params.body_count) {
+        return;
+    }
+    let slot = atomicAdd(&cell_counts[cell_hash(cell_coord(bodies[idx].position))], 1u);
+    sorted_bodies[slot] = idx;
+}
+
+@compute @workgroup_size(64)
+fn find_pairs(@builtin(global_invocation_id) global_id: vec3u) {
+    let idx_a = global_id.x;
+    if (idx_a >= params.body_count) {
+        return;
+    }
+
+    let cell = cell_coord(bodies[idx_a].position);
+    // 不同格子可能散列到同一个桶，已访问的桶跳过以避免重复候选对
+    var visited: array<u32, 27>;
+    var visited_count = 0u;
+
+    for (var dz = -1; dz <= 1; dz++) {
+        for (var dy = -1; dy <= 1; dy++) {
+            for (var dx = -1; dx <= 1; dx++) {
+                let h = cell_hash(cell + vec3i(dx, dy, dz));
+
+                var seen = false;
+                for (var v = 0u; v < visited_count; v++) {
+                    if (visited[v] == h) {
+                        seen = true;
+                    }
+                }
+                if (seen) {
+                    continue;
+                }
+                visited[visited_count] = h;
+                visited_count++;
+
+                for (var s = cell_start[h]; s < cell_start[h + 1u]; s++) {
+                    let idx_b = sorted_bodies[s];
+                    if (idx_b <= idx_a) {
+                        continue;
+                    }
+                    let pair_idx = atomicAdd(&pair_count, 1u);
+                    if (pair_idx < params.max_pairs) {
+                        pairs[pair_idx] = vec2u(idx_a, idx_b);
+                    }
+                }
+            }
+        }
+    }
+}
+        "#
+        .to_string()
+    }
+
+    /// 生成并行归约着色器 (入口点 `reduce`)
+    ///
+    /// 每个工作组把 `PRIMITIVE_WORKGROUP_SIZE` 个元素归约为一个，写入 `output[workgroup_id]`。
+    /// 多遍调度直到只剩一个元素，见 [`GpuReduction`]。
+    pub fn reduction(op: ReduceOp, elem_type: ElementType) -> String {
+        let ty = elem_type.wgsl();
+        format!(
+            r#"
+const WORKGROUP_SIZE: u32 = {workgroup_size}u;
+
+struct PrimitiveParams {{
+    count: u32,
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+}}
+
+@group(0) @binding(0)
+var<storage, read> input: array<{ty}>;
+
+@group(0) @binding(1)
+var<storage, read_write> output: array<{ty}>;
+
+@group(0) @binding(2)
+var<uniform> params: PrimitiveParams;
+
+var<workgroup> shared_data: array<{ty}, WORKGROUP_SIZE>;
+
+fn combine(a: {ty}, b: {ty}) -> {ty} {{
+    return {combine};
+}}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn reduce(
+    @builtin(global_invocation_id) global_id: vec3u,
+    @builtin(local_invocation_id) local_id: vec3u,
+    @builtin(workgroup_id) workgroup_id: vec3u,
+) {{
+    let idx = global_id.x;
+    let lane = local_id.x;
+
+    var value: {ty} = {identity};
+    if (idx < params.count) {{
+        value = input[idx];
+    }}
+    shared_data[lane] = value;
+    workgroupBarrier();
+
+    // 工作组内树形归约
+    for (var stride = WORKGROUP_SIZE / 2u; stride > 0u; stride >>= 1u) {{
+        if (lane < stride) {{
+            shared_data[lane] = combine(shared_data[lane], shared_data[lane + stride]);
+        }}
+        workgroupBarrier();
+    }}
+
+    if (lane == 0u) {{
+        output[workgroup_id.x] = shared_data[0];
+    }}
+}}
+"#,
+            workgroup_size = PRIMITIVE_WORKGROUP_SIZE,
+            ty = ty,
+            combine = op.wgsl_combine(),
+            identity = op.wgsl_identity(elem_type),
+        )
+    }
+
+    /// 生成不包含自身的前缀和 (exclusive scan) 着色器
+    ///
+    /// 入口点 `scan_blocks` 在每个工作组内扫描，并把工作组总和写入 `block_sums`；
+    /// 对 `block_sums` 递归扫描后，`add_block_offsets` 把各工作组的偏移加回结果。
+    /// 多遍调度见 [`GpuPrefixSum`]。
+    pub fn prefix_sum(elem_type: ElementType) -> String {
+        let ty = elem_type.wgsl();
+        format!(
+            r#"
+const WORKGROUP_SIZE: u32 = {workgroup_size}u;
+
+struct PrimitiveParams {{
+    count: u32,
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+}}
+
+@group(0) @binding(0)
+var<storage, read> input: array<{ty}>;
+
+@group(0) @binding(1)
+var<storage, read_write> output: array<{ty}>;
+
+@group(0) @binding(2)
+var<storage, read_write> block_sums: array<{ty}>;
+
+@group(0) @binding(3)
+var<uniform> params: PrimitiveParams;
+
+var<workgroup> shared_data: array<{ty}, WORKGROUP_SIZE>;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn scan_blocks(
+    @builtin(global_invocation_id) global_id: vec3u,
+    @builtin(local_invocation_id) local_id: vec3u,
+    @builtin(workgroup_id) workgroup_id: vec3u,
+) {{
+    let idx = global_id.x;
+    let lane = local_id.x;
+
+    var value = {ty}(0);
+    if (idx < params.count) {{
+        value = input[idx];
+    }}
+    shared_data[lane] = value;
+    workgroupBarrier();
+
+    // 工作组内 Hillis-Steele 包含扫描
+    for (var offset = 1u; offset < WORKGROUP_SIZE; offset <<= 1u) {{
+        var addend = {ty}(0);
+        if (lane >= offset) {{
+            addend = shared_data[lane - offset];
+        }}
+        workgroupBarrier();
+        shared_data[lane] += addend;
+        workgroupBarrier();
+    }}
+
+    // 左移一位得到不包含自身的扫描
+    if (idx < params.count) {{
+        var exclusive = {ty}(0);
+        if (lane > 0u) {{
+            exclusive = shared_data[lane - 1u];
+        }}
+        output[idx] = exclusive;
+    }}
+    if (lane == WORKGROUP_SIZE - 1u) {{
+        block_sums[workgroup_id.x] = shared_data[lane];
+    }}
+}}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn add_block_offsets(
+    @builtin(global_invocation_id) global_id: vec3u,
+    @builtin(workgroup_id) workgroup_id: vec3u,
+) {{
+    let idx = global_id.x;
+    if (idx < params.count) {{
+        output[idx] += block_sums[workgroup_id.x];
+    }}
+}}
+"#,
+            workgroup_size = PRIMITIVE_WORKGROUP_SIZE,
+            ty = ty,
+        )
+    }
+
     /// 生成粒子系统更新着色器
     pub fn generate_particle_shader() -> String {
         r#"
@@ -408,6 +870,446 @@ fn main(@builtin(global_invocation_id) global_id: vec3u) {
     }
 }
 
+/// 处理 `count` 个元素所需的一维工作组数量
+fn primitive_workgroups(count: u32) -> Result<u32, String> {
+    if count == 0 {
+        return Err("Cannot dispatch over an empty input".to_string());
+    }
+    let groups = count.div_ceil(PRIMITIVE_WORKGROUP_SIZE);
+    if groups > u16::MAX as u32 {
+        return Err(format!(
+            "{} elements exceed the maximum of {} workgroups",
+            count,
+            u16::MAX
+        ));
+    }
+    Ok(groups)
+}
+
+/// 检查输入缓冲区能容纳 `count` 个元素并可作为存储缓冲区绑定
+fn check_primitive_input(
+    input: &wgpu::Buffer,
+    count: u32,
+    elem_type: ElementType,
+) -> Result<(), String> {
+    if !input.usage().contains(wgpu::BufferUsages::STORAGE) {
+        return Err("Input buffer is missing BufferUsages::STORAGE".to_string());
+    }
+    if input.size() < count as u64 * elem_type.size() {
+        return Err(format!(
+            "Input buffer of {} bytes is too small for {} elements",
+            input.size(),
+            count
+        ));
+    }
+    Ok(())
+}
+
+fn primitive_storage_buffer(
+    device: &wgpu::Device,
+    label: &str,
+    count: u32,
+    elem_type: ElementType,
+) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size: count as u64 * elem_type.size(),
+        usage: wgpu::BufferUsages::STORAGE
+            | wgpu::BufferUsages::COPY_SRC
+            | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+fn primitive_params_buffer(device: &wgpu::Device, count: u32) -> wgpu::Buffer {
+    use wgpu::util::DeviceExt;
+
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Primitive Params"),
+        contents: bytemuck::cast_slice(&[count, 0, 0, 0]),
+        usage: wgpu::BufferUsages::UNIFORM,
+    })
+}
+
+/// 多遍并行归约调度器
+pub struct GpuReduction {
+    pipeline: ComputePipeline,
+    elem_type: ElementType,
+}
+
+impl GpuReduction {
+    /// 在设备上编译 `op` 归约着色器
+    pub fn new(device: &wgpu::Device, op: ReduceOp, elem_type: ElementType) -> Self {
+        let config = ComputeShaderConfig::new(ComputeShaderGenerator::reduction(op, elem_type))
+            .with_entry_point("reduce".to_string())
+            .with_workgroup_size(PRIMITIVE_WORKGROUP_SIZE, 1, 1);
+        let mut pipeline = ComputePipeline::new(0, config);
+        pipeline.compile_on_device(device);
+        Self {
+            pipeline,
+            elem_type,
+        }
+    }
+
+    /// 记录归约 `input` 前 `count` 个元素的命令
+    ///
+    /// 每遍把元素数量缩小 `PRIMITIVE_WORKGROUP_SIZE` 倍，直到只剩一个。
+    /// 返回的缓冲区第一个元素为结果，提交编码器后可复制读取。
+    pub fn dispatch(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::Buffer,
+        count: u32,
+    ) -> Result<wgpu::Buffer, String> {
+        check_primitive_input(input, count, self.elem_type)?;
+        let layout = self
+            .pipeline
+            .bind_group_layout(0)
+            .ok_or_else(|| "Pipeline not compiled on a device".to_string())?;
+
+        let mut count = count;
+        let mut current: Option<wgpu::Buffer> = None;
+        loop {
+            let groups = primitive_workgroups(count)?;
+            let output =
+                primitive_storage_buffer(device, "Reduction Output", groups, self.elem_type);
+            let params = primitive_params_buffer(device, count);
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Reduction Bind Group"),
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: current.as_ref().unwrap_or(input).as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: output.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params.as_entire_binding(),
+                    },
+                ],
+            });
+
+            self.pipeline.set_bind_group(0, bind_group)?;
+            self.pipeline.config.workgroup_count = (groups, 1, 1);
+            self.pipeline.dispatch(encoder)?;
+
+            current = Some(output);
+            count = groups;
+            if groups == 1 {
+                break;
+            }
+        }
+
+        current.ok_or_else(|| "Reduction produced no output".to_string())
+    }
+}
+
+/// 多遍不包含自身的前缀和调度器
+pub struct GpuPrefixSum {
+    scan: ComputePipeline,
+    add_offsets: ComputePipeline,
+    elem_type: ElementType,
+}
+
+impl GpuPrefixSum {
+    /// 在设备上编译前缀和着色器的两个入口点
+    pub fn new(device: &wgpu::Device, elem_type: ElementType) -> Self {
+        let shader = ComputeShaderGenerator::prefix_sum(elem_type);
+        let compile = |id, entry_point: &str| {
+            let config = ComputeShaderConfig::new(shader.clone())
+                .with_entry_point(entry_point.to_string())
+                .with_workgroup_size(PRIMITIVE_WORKGROUP_SIZE, 1, 1);
+            let mut pipeline = ComputePipeline::new(id, config);
+            pipeline.compile_on_device(device);
+            pipeline
+        };
+        Self {
+            scan: compile(0, "scan_blocks"),
+            add_offsets: compile(1, "add_block_offsets"),
+            elem_type,
+        }
+    }
+
+    /// 记录对 `input` 前 `count` 个元素做前缀和的命令，返回包含 `count` 个元素的结果缓冲区
+    ///
+    /// 超过一个工作组时递归扫描各工作组总和，再把偏移加回每个工作组。
+    pub fn dispatch(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::Buffer,
+        count: u32,
+    ) -> Result<wgpu::Buffer, String> {
+        check_primitive_input(input, count, self.elem_type)?;
+        self.scan_level(device, encoder, input, count)
+    }
+
+    fn scan_level(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::Buffer,
+        count: u32,
+    ) -> Result<wgpu::Buffer, String> {
+        let groups = primitive_workgroups(count)?;
+        let output = primitive_storage_buffer(device, "Prefix Sum Output", count, self.elem_type);
+        let block_sums =
+            primitive_storage_buffer(device, "Prefix Sum Block Sums", groups, self.elem_type);
+        let params = primitive_params_buffer(device, count);
+
+        let layout = self
+            .scan
+            .bind_group_layout(0)
+            .ok_or_else(|| "Pipeline not compiled on a device".to_string())?;
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Prefix Sum Scan Bind Group"),
+            layout: &layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: input.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: block_sums.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: params.as_entire_binding(),
+                },
+            ],
+        });
+        self.scan.set_bind_group(0, bind_group)?;
+        self.scan.config.workgroup_count = (groups, 1, 1);
+        self.scan.dispatch(encoder)?;
+
+        if groups > 1 {
+            let block_offsets = self.scan_level(device, encoder, &block_sums, groups)?;
+
+            let layout = self
+                .add_offsets
+                .bind_group_layout(0)
+                .ok_or_else(|| "Pipeline not compiled on a device".to_string())?;
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Prefix Sum Add Offsets Bind Group"),
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: output.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: block_offsets.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: params.as_entire_binding(),
+                    },
+                ],
+            });
+            self.add_offsets.set_bind_group(0, bind_group)?;
+            self.add_offsets.config.workgroup_count = (groups, 1, 1);
+            self.add_offsets.dispatch(encoder)?;
+        }
+
+        Ok(output)
+    }
+}
+
+/// 宽相位着色器 `count_cells`、`scatter_bodies`、`find_pairs` 的工作组大小
+const BROADPHASE_WORKGROUP_SIZE: u32 = 64;
+
+/// 宽相位调度的输出缓冲区
+pub struct BroadphaseOutput {
+    /// 候选对 (`vec2u`，a < b)，最多保存 `max_pairs` 个
+    pub pairs: wgpu::Buffer,
+    /// 着色器生成的候选对总数 (`u32`)，超过 `max_pairs` 时多出的候选对被丢弃
+    pub pair_count: wgpu::Buffer,
+    /// `pairs` 能容纳的候选对数量
+    pub max_pairs: u32,
+}
+
+/// 均匀网格宽相位调度器
+///
+/// 按顺序调度 [`ComputeShaderGenerator::generate_broadphase_shader`] 的四个入口点。
+pub struct GpuBroadphase {
+    count_cells: ComputePipeline,
+    prefix_sum: ComputePipeline,
+    scatter_bodies: ComputePipeline,
+    find_pairs: ComputePipeline,
+}
+
+impl GpuBroadphase {
+    /// 在设备上编译宽相位着色器的四个入口点
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = ComputeShaderGenerator::generate_broadphase_shader();
+        let compile = |id, entry_point: &str, workgroup_size| {
+            let config = ComputeShaderConfig::new(shader.clone())
+                .with_entry_point(entry_point.to_string())
+                .with_workgroup_size(workgroup_size, 1, 1);
+            let mut pipeline = ComputePipeline::new(id, config);
+            pipeline.compile_on_device(device);
+            pipeline
+        };
+        Self {
+            count_cells: compile(0, "count_cells", BROADPHASE_WORKGROUP_SIZE),
+            prefix_sum: compile(1, "prefix_sum", 1),
+            scatter_bodies: compile(2, "scatter_bodies", BROADPHASE_WORKGROUP_SIZE),
+            find_pairs: compile(3, "find_pairs", BROADPHASE_WORKGROUP_SIZE),
+        }
+    }
+
+    /// 记录为 `bodies` 前 `body_count` 个物体 (`GPUPhysicsBody` 布局) 生成候选对的命令
+    ///
+    /// 候选对按散列桶生成，可能包含散列冲突带来的非相邻格子物体对，由调用方在窄相位前过滤。
+    pub fn dispatch(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        bodies: &wgpu::Buffer,
+        body_count: u32,
+        cell_size: f32,
+        max_pairs: u32,
+    ) -> Result<BroadphaseOutput, String> {
+        use wgpu::util::DeviceExt;
+
+        let body_size = std::mem::size_of::<super::gpu_physics::GPUPhysicsBody>() as u64;
+        if body_count == 0 {
+            return Err("Broadphase needs at least one body".to_string());
+        }
+        if !bodies.usage().contains(wgpu::BufferUsages::STORAGE) {
+            return Err("Body buffer is missing BufferUsages::STORAGE".to_string());
+        }
+        if bodies.size() < body_count as u64 * body_size {
+            return Err(format!(
+                "Body buffer of {} bytes is too small for {} bodies",
+                bodies.size(),
+                body_count
+            ));
+        }
+        let groups = body_count.div_ceil(BROADPHASE_WORKGROUP_SIZE);
+        if groups > u16::MAX as u32 {
+            return Err(format!(
+                "{} bodies exceed the maximum of {} workgroups",
+                body_count,
+                u16::MAX
+            ));
+        }
+
+        // 桶数量取物体数量的两倍以减少散列冲突
+        let table_size = body_count * 2;
+        let max_pairs = max_pairs.max(1);
+        let storage = |label, size| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            })
+        };
+        // 新建的缓冲区由 wgpu 清零，满足着色器对 `cell_counts` 和 `pair_count` 的要求
+        let cell_counts = storage("Broadphase Cell Counts", table_size as u64 * 4);
+        let cell_start = storage("Broadphase Cell Start", (table_size as u64 + 1) * 4);
+        let sorted_bodies = storage("Broadphase Sorted Bodies", body_count as u64 * 4);
+        let pairs = storage("Broadphase Pairs", max_pairs as u64 * 8);
+        let pair_count = storage("Broadphase Pair Count", 4);
+        let params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Broadphase Params"),
+            contents: bytemuck::cast_slice(&[
+                body_count,
+                table_size,
+                cell_size.to_bits(),
+                max_pairs,
+            ]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let stages = [
+            (
+                &mut self.count_cells,
+                vec![(0, bodies), (1, &cell_counts), (6, &params)],
+                groups,
+            ),
+            (
+                &mut self.prefix_sum,
+                vec![(1, &cell_counts), (2, &cell_start), (6, &params)],
+                1,
+            ),
+            (
+                &mut self.scatter_bodies,
+                vec![
+                    (0, bodies),
+                    (1, &cell_counts),
+                    (3, &sorted_bodies),
+                    (6, &params),
+                ],
+                groups,
+            ),
+            (
+                &mut self.find_pairs,
+                vec![
+                    (0, bodies),
+                    (2, &cell_start),
+                    (3, &sorted_bodies),
+                    (4, &pairs),
+                    (5, &pair_count),
+                    (6, &params),
+                ],
+                groups,
+            ),
+        ];
+        for (pipeline, bindings, workgroups) in stages {
+            bind_and_dispatch(pipeline, device, encoder, &bindings, workgroups)?;
+        }
+
+        Ok(BroadphaseOutput {
+            pairs,
+            pair_count,
+            max_pairs,
+        })
+    }
+}
+
+/// 按绑定号创建绑定组并调度
+///
+/// 自动推导的绑定组布局只包含入口点实际使用的绑定，`bindings` 须与之一致。
+fn bind_and_dispatch(
+    pipeline: &mut ComputePipeline,
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    bindings: &[(u32, &wgpu::Buffer)],
+    workgroups: u32,
+) -> Result<(), String> {
+    let layout = pipeline
+        .bind_group_layout(0)
+        .ok_or_else(|| "Pipeline not compiled on a device".to_string())?;
+    let entries: Vec<_> = bindings
+        .iter()
+        .map(|(binding, buffer)| wgpu::BindGroupEntry {
+            binding: *binding,
+            resource: buffer.as_entire_binding(),
+        })
+        .collect();
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Broadphase Bind Group"),
+        layout: &layout,
+        entries: &entries,
+    });
+    pipeline.set_bind_group(0, bind_group)?;
+    pipeline.config.workgroup_count = (workgroups, 1, 1);
+    pipeline.dispatch(encoder)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,5 +1354,225 @@ mod tests {
         assert!(!collision_shader.is_empty());
         assert!(!particle_shader.is_empty());
         assert!(physics_shader.contains("@compute"));
+        assert!(ComputeShaderGenerator::generate_broadphase_shader().contains("fn find_pairs"));
+    }
+
+    #[test]
+    fn test_dispatch_indirect_from_gpu_count() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        // 第一个通道把工作组数量 (5, 1, 1) 写入间接缓冲区
+        let write_args = ComputeShaderConfig::new(
+            r#"
+            @group(0) @binding(0) var<storage, read_write> args: array<u32, 3>;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                args[0] = 5u;
+                args[1] = 1u;
+                args[2] = 1u;
+            }
+            "#
+            .to_string(),
+        );
+        // 第二个通道每个工作组计数一次
+        let count_groups = ComputeShaderConfig::new(
+            r#"
+            @group(0) @binding(0) var<storage, read_write> counter: atomic<u32>;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                atomicAdd(&counter, 1u);
+            }
+            "#
+            .to_string(),
+        );
+
+        let args_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Dispatch Args"),
+            size: DISPATCH_INDIRECT_SIZE,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+        let counter_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Counter"),
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback"),
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut producer = ComputePipeline::new(1, write_args);
+        producer.compile_on_device(&device);
+        let layout = producer.bind_group_layout(0).unwrap();
+        producer
+            .set_bind_group(
+                0,
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: args_buffer.as_entire_binding(),
+                    }],
+                }),
+            )
+            .unwrap();
+
+        let mut consumer = ComputePipeline::new(2, count_groups);
+        consumer.compile_on_device(&device);
+        let layout = consumer.bind_group_layout(0).unwrap();
+        consumer
+            .set_bind_group(
+                0,
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: counter_buffer.as_entire_binding(),
+                    }],
+                }),
+            )
+            .unwrap();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        producer.dispatch(&mut encoder).unwrap();
+        // 缺少 INDIRECT 用途的缓冲区被拒绝
+        assert!(consumer
+            .dispatch_indirect(&mut encoder, &counter_buffer, 0)
+            .is_err());
+        consumer
+            .dispatch_indirect(&mut encoder, &args_buffer, 0)
+            .unwrap();
+        encoder.copy_buffer_to_buffer(&counter_buffer, 0, &readback, 0, 4);
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let count: u32 = bytemuck::cast_slice(&slice.get_mapped_range())[0];
+        assert_eq!(count, 5);
+    }
+
+    /// 提交编码器并读回缓冲区内容
+    fn read_back<T: bytemuck::Pod>(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        mut encoder: wgpu::CommandEncoder,
+        buffer: &wgpu::Buffer,
+    ) -> Vec<T> {
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback"),
+            size: buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_buffer_to_buffer(buffer, 0, &readback, 0, buffer.size());
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        data
+    }
+
+    fn storage_input<T: bytemuck::Pod>(device: &wgpu::Device, data: &[T]) -> wgpu::Buffer {
+        use wgpu::util::DeviceExt;
+
+        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Input"),
+            contents: bytemuck::cast_slice(data),
+            usage: wgpu::BufferUsages::STORAGE,
+        })
+    }
+
+    #[test]
+    fn test_prefix_sum_exclusive() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+        let mut scan = GpuPrefixSum::new(&device, ElementType::U32);
+
+        let input = storage_input(&device, &[1u32, 1, 1, 1]);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let output = scan.dispatch(&device, &mut encoder, &input, 4).unwrap();
+        assert_eq!(
+            read_back::<u32>(&device, &queue, encoder, &output),
+            vec![0, 1, 2, 3]
+        );
+
+        // 超过 PRIMITIVE_WORKGROUP_SIZE² 个元素时需要三层工作组偏移
+        let data: Vec<u32> = (0..70_000).map(|i| i % 7).collect();
+        let input = storage_input(&device, &data);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let output = scan
+            .dispatch(&device, &mut encoder, &input, data.len() as u32)
+            .unwrap();
+        let expected: Vec<u32> = data
+            .iter()
+            .scan(0, |sum, &x| {
+                let exclusive = *sum;
+                *sum += x;
+                Some(exclusive)
+            })
+            .collect();
+        assert_eq!(
+            read_back::<u32>(&device, &queue, encoder, &output),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_reduction_ops() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let data: Vec<f32> = (0..1000)
+            .map(|i| ((i * 37) % 1000) as f32 - 500.0)
+            .collect();
+        let input = storage_input(&device, &data);
+        for (op, expected) in [
+            (ReduceOp::Sum, data.iter().sum::<f32>()),
+            (ReduceOp::Min, -500.0),
+            (ReduceOp::Max, 499.0),
+        ] {
+            let mut reduction = GpuReduction::new(&device, op, ElementType::F32);
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let output = reduction
+                .dispatch(&device, &mut encoder, &input, data.len() as u32)
+                .unwrap();
+            assert_eq!(
+                read_back::<f32>(&device, &queue, encoder, &output)[0],
+                expected
+            );
+        }
+
+        let data: Vec<u32> = (1..=300).collect();
+        let input = storage_input(&device, &data);
+        let mut reduction = GpuReduction::new(&device, ReduceOp::Sum, ElementType::U32);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let output = reduction
+            .dispatch(&device, &mut encoder, &input, 300)
+            .unwrap();
+        assert_eq!(
+            read_back::<u32>(&device, &queue, encoder, &output)[0],
+            45_150
+        );
+        // 空输入被拒绝
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        assert!(reduction
+            .dispatch(&device, &mut encoder, &input, 0)
+            .is_err());
     }
 }
@@ -6,11 +6,17 @@
 //! - 约束求解
 //! - 力场计算
 
-use glam::Vec3;
+use super::gpu_compute::GpuBroadphase;
+use glam::{IVec3, Vec3};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 碰撞检测的最小距离 (两个单位直径球体)
+const COLLISION_DISTANCE: f32 = 1.0;
 
 /// GPU 物理体结构体
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct GPUPhysicsBody {
     /// 位置 (世界坐标)
     pub position: Vec3,
@@ -79,6 +85,8 @@ pub struct GPUPhysicsConfig {
     pub damping: f32,
     /// 碰撞裕度
     pub collision_margin: f32,
+    /// 宽相位均匀网格的格子大小，小于碰撞距离时按碰撞距离处理
+    pub grid_cell_size: f32,
 }
 
 impl Default for GPUPhysicsConfig {
@@ -89,6 +97,7 @@ impl Default for GPUPhysicsConfig {
             iterations: 8,
             damping: 0.999,
             collision_margin: 0.01,
+            grid_cell_size: 2.0,
         }
     }
 }
@@ -105,18 +114,21 @@ pub struct GPUPhysicsSimulator {
     collisions: Vec<GPUCollisionInfo>,
     /// 是否启用 GPU 计算
     gpu_enabled: bool,
+    /// 绑定的 GPU 设备和宽相位管道
+    gpu: Option<GpuPhysicsContext>,
+}
+
+/// 模拟器在设备上的资源
+struct GpuPhysicsContext {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    broadphase: GpuBroadphase,
 }
 
 impl GPUPhysicsSimulator {
     /// 创建新的 GPU 物理模拟器
     pub fn new() -> Self {
-        Self {
-            config: GPUPhysicsConfig::default(),
-            bodies: Vec::new(),
-            constraints: Vec::new(),
-            collisions: Vec::new(),
-            gpu_enabled: false,
-        }
+        Self::with_config(GPUPhysicsConfig::default())
     }
 }
 
@@ -135,6 +147,7 @@ impl GPUPhysicsSimulator {
             constraints: Vec::new(),
             collisions: Vec::new(),
             gpu_enabled: false,
+            gpu: None,
         }
     }
 
@@ -143,6 +156,16 @@ impl GPUPhysicsSimulator {
         self.gpu_enabled = enabled;
     }
 
+    /// 绑定 GPU 设备并编译宽相位管道，启用 GPU 计算后宽相位在设备上执行
+    pub fn attach_device(&mut self, device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) {
+        let broadphase = GpuBroadphase::new(&device);
+        self.gpu = Some(GpuPhysicsContext {
+            device,
+            queue,
+            broadphase,
+        });
+    }
+
     /// 添加物理体
     pub fn add_body(&mut self, position: Vec3, mass: f32) -> usize {
         let body = GPUPhysicsBody {
@@ -282,32 +305,174 @@ impl GPUPhysicsSimulator {
         self.solve_distance_constraint(constraint);
     }
 
-    /// 检测碰撞 (简化的碰撞检测)
+    /// 检测碰撞
+    ///
+    /// 先用均匀网格宽相位生成候选对 (与 `ComputeShaderGenerator::generate_broadphase_shader`
+    /// 的算法一致)，再对候选对做窄相位测试
     pub fn detect_collisions(&mut self) {
-        self.collisions.clear();
+        let pairs = self.broadphase_pairs();
+        self.narrowphase(pairs.into_iter());
+    }
+
+    /// 暴力检测所有物体对 (O(n²))
+    pub fn detect_collisions_brute_force(&mut self) {
+        let n = self.bodies.len() as u32;
+        self.narrowphase((0..n).flat_map(|i| ((i + 1)..n).map(move |j| (i, j))));
+    }
+
+    /// 均匀网格宽相位：只在相邻格子内生成候选对 (a < b)，按索引排序
+    ///
+    /// 启用 GPU 计算且已绑定设备时调度 `GpuBroadphase` 并读回结果，GPU 调度失败时回退到 CPU。
+    pub fn broadphase_pairs(&mut self) -> Vec<(u32, u32)> {
+        if self.gpu_enabled && self.gpu.is_some() {
+            match self.broadphase_pairs_gpu() {
+                Ok(pairs) => return pairs,
+                Err(e) => tracing::warn!("GPU broadphase failed, falling back to CPU: {}", e),
+            }
+        }
+        self.broadphase_pairs_cpu()
+    }
+
+    /// 宽相位格子大小，不能小于碰撞距离，否则只查相邻格子会漏检
+    fn cell_size(&self) -> f32 {
+        self.config.grid_cell_size.max(COLLISION_DISTANCE)
+    }
 
-        let n = self.bodies.len();
-        for i in 0..n {
-            for j in (i + 1)..n {
-                let dist = (self.bodies[j].position - self.bodies[i].position).length();
-                let min_dist = 1.0; // 假设最小碰撞距离
-
-                if dist < min_dist {
-                    let normal = (self.bodies[j].position - self.bodies[i].position).normalize();
-                    let collision = GPUCollisionInfo {
-                        body_a_idx: i as u32,
-                        body_b_idx: j as u32,
-                        normal,
-                        depth: min_dist - dist,
-                        contact_point_a: self.bodies[i].position + normal * 0.5,
-                        _padding0: 0.0,
-                        contact_point_b: self.bodies[j].position - normal * 0.5,
-                        _padding1: 0.0,
-                    };
-                    self.collisions.push(collision);
+    fn cell_of(&self, position: Vec3) -> IVec3 {
+        (position / self.cell_size()).floor().as_ivec3()
+    }
+
+    fn broadphase_pairs_cpu(&self) -> Vec<(u32, u32)> {
+        let mut cells: HashMap<IVec3, Vec<u32>> = HashMap::new();
+        for (idx, body) in self.bodies.iter().enumerate() {
+            cells
+                .entry(self.cell_of(body.position))
+                .or_default()
+                .push(idx as u32);
+        }
+
+        let mut pairs = Vec::new();
+        for (idx_a, body) in self.bodies.iter().enumerate() {
+            let idx_a = idx_a as u32;
+            let cell = self.cell_of(body.position);
+            for dz in -1..=1 {
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        let Some(members) = cells.get(&(cell + IVec3::new(dx, dy, dz))) else {
+                            continue;
+                        };
+                        pairs.extend(
+                            members
+                                .iter()
+                                .filter(|idx_b| **idx_b > idx_a)
+                                .map(|idx_b| (idx_a, *idx_b)),
+                        );
+                    }
                 }
             }
         }
+        // 与暴力检测保持相同的输出顺序
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// 在绑定的设备上执行宽相位，候选对缓冲区不足时按实际数量重新调度
+    fn broadphase_pairs_gpu(&mut self) -> Result<Vec<(u32, u32)>, String> {
+        use wgpu::util::DeviceExt;
+
+        if self.bodies.len() < 2 {
+            return Ok(Vec::new());
+        }
+        let body_count = self.bodies.len() as u32;
+        let cell_size = self.cell_size();
+        let gpu = self
+            .gpu
+            .as_mut()
+            .ok_or_else(|| "No GPU device attached".to_string())?;
+        let bodies = gpu
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Broadphase Bodies"),
+                contents: bytemuck::cast_slice(&self.bodies),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+
+        let mut max_pairs = body_count * 4;
+        let (pair_count, raw_pairs) = loop {
+            let mut encoder = gpu
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Broadphase Encoder"),
+                });
+            let output = gpu.broadphase.dispatch(
+                &gpu.device,
+                &mut encoder,
+                &bodies,
+                body_count,
+                cell_size,
+                max_pairs,
+            )?;
+
+            // 数量和候选对复制到同一个读回缓冲区
+            let readback = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Broadphase Readback"),
+                size: 4 + output.pairs.size(),
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            encoder.copy_buffer_to_buffer(&output.pair_count, 0, &readback, 0, 4);
+            encoder.copy_buffer_to_buffer(&output.pairs, 0, &readback, 4, output.pairs.size());
+            gpu.queue.submit(Some(encoder.finish()));
+
+            let slice = readback.slice(..);
+            slice.map_async(wgpu::MapMode::Read, |_| {});
+            gpu.device.poll(wgpu::Maintain::Wait);
+            let data: Vec<u32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+            let pair_count = data[0];
+            if pair_count <= output.max_pairs {
+                break (pair_count, data);
+            }
+            max_pairs = pair_count;
+        };
+
+        // 去掉散列冲突带来的非相邻格子物体对，结果与 CPU 宽相位一致
+        let mut pairs: Vec<(u32, u32)> = raw_pairs[1..1 + 2 * pair_count as usize]
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .filter(|&(a, b)| {
+                let delta = self.cell_of(self.bodies[a as usize].position)
+                    - self.cell_of(self.bodies[b as usize].position);
+                delta.abs().max_element() <= 1
+            })
+            .collect();
+        pairs.sort_unstable();
+        Ok(pairs)
+    }
+
+    /// 窄相位：对候选对做球体距离测试
+    fn narrowphase(&mut self, pairs: impl Iterator<Item = (u32, u32)>) {
+        self.collisions.clear();
+
+        for (i, j) in pairs {
+            let pos_a = self.bodies[i as usize].position;
+            let pos_b = self.bodies[j as usize].position;
+            let dist = (pos_b - pos_a).length();
+
+            if dist < COLLISION_DISTANCE {
+                let normal = (pos_b - pos_a).normalize();
+                let collision = GPUCollisionInfo {
+                    body_a_idx: i,
+                    body_b_idx: j,
+                    normal,
+                    depth: COLLISION_DISTANCE - dist,
+                    contact_point_a: pos_a + normal * 0.5,
+                    _padding0: 0.0,
+                    contact_point_b: pos_b - normal * 0.5,
+                    _padding1: 0.0,
+                };
+                self.collisions.push(collision);
+            }
+        }
     }
 
     /// 获取物体
@@ -463,6 +628,72 @@ mod tests {
         assert!(sim.get_collisions().len() > 0);
     }
 
+    /// 固定的伪随机分布，包含负坐标和跨格子的接近物体
+    fn scattered_simulator() -> GPUPhysicsSimulator {
+        let mut sim = GPUPhysicsSimulator::with_config(GPUPhysicsConfig {
+            grid_cell_size: 1.5,
+            ..Default::default()
+        });
+        let mut seed = 12345u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        for _ in 0..200 {
+            let position = Vec3::new(next(), next(), next()) * 12.0 - Vec3::splat(6.0);
+            sim.add_body(position, 1.0);
+        }
+        sim.add_body(Vec3::new(1.49, 0.0, 0.0), 1.0);
+        sim.add_body(Vec3::new(1.51, 0.0, 0.0), 1.0);
+        sim
+    }
+
+    fn collision_pairs(sim: &GPUPhysicsSimulator) -> Vec<(u32, u32)> {
+        sim.get_collisions()
+            .iter()
+            .map(|c| (c.body_a_idx, c.body_b_idx))
+            .collect()
+    }
+
+    #[test]
+    fn test_grid_broadphase_matches_brute_force() {
+        let mut sim = scattered_simulator();
+
+        sim.detect_collisions_brute_force();
+        let brute_force = collision_pairs(&sim);
+
+        sim.detect_collisions();
+        let grid = collision_pairs(&sim);
+
+        assert!(!brute_force.is_empty());
+        assert_eq!(grid.len(), brute_force.len());
+        assert_eq!(grid, brute_force);
+        // 宽相位确实剔除了大部分物体对
+        let n = sim.get_bodies().len();
+        assert!(sim.broadphase_pairs().len() < n * (n - 1) / 2);
+    }
+
+    #[test]
+    fn test_gpu_broadphase_matches_cpu() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut sim = scattered_simulator();
+        let cpu_pairs = sim.broadphase_pairs();
+        sim.detect_collisions();
+        let cpu_collisions = collision_pairs(&sim);
+
+        sim.attach_device(Arc::new(device), Arc::new(queue));
+        sim.set_gpu_enabled(true);
+        let gpu_pairs = sim.broadphase_pairs();
+        sim.detect_collisions();
+
+        assert!(!cpu_collisions.is_empty());
+        assert_eq!(gpu_pairs, cpu_pairs);
+        assert_eq!(collision_pairs(&sim), cpu_collisions);
+    }
+
     #[test]
     fn test_gpu_particle_system() {
         let mut particles = GPUParticleSystem::new(100);
@@ -3,14 +3,16 @@ pub mod gpu_physics;
 pub mod wgpu_integration;
 
 pub use gpu_compute::{
-    ComputePipeline, ComputeResourceManager, ComputeShaderConfig, ComputeShaderGenerator,
+    BroadphaseOutput, ComputePipeline, ComputeResourceManager, ComputeShaderConfig,
+    ComputeShaderGenerator, ElementType, GpuBroadphase, GpuPrefixSum, GpuReduction, ReduceOp,
 };
 pub use gpu_physics::{
     GPUCollisionInfo, GPUConstraint, GPUParticleSystem, GPUPhysicsBody, GPUPhysicsConfig,
     GPUPhysicsSimulator,
 };
 pub use wgpu_integration::{
-    ComputePipelineWGPU, GPUBuffer, GPUComputeDevice, GPUExecutionResult, GPUFeatures,
-    PerformanceComparison, WGSLShader,
+    ComputePipelineWGPU, FrameFence, GPUBuffer, GPUComputeDevice, GPUExecutionResult, GPUFeatures,
+    GpuUploadRing, PerformanceComparison, PreprocessedShader, QueueFrameFence, ShaderIncludeError,
+    SourceLocation, UploadRingError, WGSLShader,
 };
 
@@ -7,8 +7,17 @@
 //! - 性能监控
 
 use crate::impl_default;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use thiserror::Error;
+
+/// 全局着色器片段表，供 `#include "name"` 解析
+fn snippet_registry() -> &'static RwLock<HashMap<String, String>> {
+    static SNIPPETS: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+    SNIPPETS.get_or_init(|| RwLock::new(HashMap::new()))
+}
 
 /// GPU 计算设备
 pub struct GPUComputeDevice {
@@ -99,6 +108,53 @@ impl WGSLShader {
         }
     }
 
+    /// 注册可被 `#include "name"` 引用的着色器片段
+    pub fn register_snippet(name: impl Into<String>, code: impl Into<String>) {
+        snippet_registry()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.into(), code.into());
+    }
+
+    /// 展开 `#include` 指令
+    ///
+    /// 每个片段只展开一次；循环引用或未注册的片段返回错误
+    pub fn preprocess(&self) -> Result<PreprocessedShader, ShaderIncludeError> {
+        let snippets = snippet_registry().read().unwrap_or_else(|e| e.into_inner());
+        let mut output = PreprocessedShader {
+            source: String::new(),
+            line_map: Vec::new(),
+        };
+        let mut stack = vec![self.name.clone()];
+        let mut included = HashSet::new();
+        expand_includes(
+            &self.name,
+            &self.source,
+            &snippets,
+            &mut stack,
+            &mut included,
+            &mut output,
+        )?;
+        Ok(output)
+    }
+
+    /// 展开 `#include` 后在设备上创建着色器模块
+    ///
+    /// 编译错误中的行号会映射回原始着色器或片段
+    pub fn create_module(&self, device: &wgpu::Device) -> Result<wgpu::ShaderModule, String> {
+        let preprocessed = self.preprocess().map_err(|e| e.to_string())?;
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(&self.name),
+            source: wgpu::ShaderSource::Wgsl(preprocessed.source.as_str().into()),
+        });
+        match pollster::block_on(device.pop_error_scope()) {
+            Some(error) => Err(preprocessed.remap_error(&error.to_string())),
+            None => Ok(module),
+        }
+    }
+
     /// 为物理计算生成着色器
     pub fn physics_compute() -> Self {
         let source = r#"
@@ -274,6 +330,129 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 }
 
+/// 着色器 `#include` 展开错误
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ShaderIncludeError {
+    #[error("unknown shader snippet \"{name}\" included from {from}:{line}")]
+    UnknownSnippet {
+        name: String,
+        from: String,
+        line: u32,
+    },
+    #[error("circular shader include: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    #[error("malformed #include directive at {from}:{line}")]
+    MalformedDirective { from: String, line: u32 },
+}
+
+/// 展开后的源代码行对应的原始位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// 着色器或片段名称
+    pub origin: String,
+    /// 原始行号 (从 1 开始)
+    pub line: u32,
+}
+
+/// 展开 `#include` 后的着色器
+#[derive(Debug, Clone)]
+pub struct PreprocessedShader {
+    /// 展开后的源代码
+    pub source: String,
+    /// 每个输出行对应的原始位置
+    line_map: Vec<SourceLocation>,
+}
+
+impl PreprocessedShader {
+    /// 输出行号 (从 1 开始) 对应的原始位置
+    pub fn map_line(&self, line: u32) -> Option<&SourceLocation> {
+        self.line_map.get((line as usize).checked_sub(1)?)
+    }
+
+    /// 把编译错误中的 `wgsl:行:列` 改写为 `原始名称:原始行:列`
+    pub fn remap_error(&self, message: &str) -> String {
+        const MARKER: &str = "wgsl:";
+        let mut result = String::with_capacity(message.len());
+        let mut rest = message;
+
+        while let Some(pos) = rest.find(MARKER) {
+            result.push_str(&rest[..pos]);
+            let after = &rest[pos + MARKER.len()..];
+            let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+            let location = after[..digits]
+                .parse()
+                .ok()
+                .and_then(|line| self.map_line(line));
+            match location {
+                Some(location) => {
+                    result.push_str(&format!("{}:{}", location.origin, location.line));
+                    rest = &after[digits..];
+                }
+                None => {
+                    result.push_str(MARKER);
+                    rest = after;
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn expand_includes(
+    origin: &str,
+    source: &str,
+    snippets: &HashMap<String, String>,
+    stack: &mut Vec<String>,
+    included: &mut HashSet<String>,
+    output: &mut PreprocessedShader,
+) -> Result<(), ShaderIncludeError> {
+    for (index, text) in source.lines().enumerate() {
+        let line = index as u32 + 1;
+        let Some(directive) = text.trim().strip_prefix("#include") else {
+            output.source.push_str(text);
+            output.source.push('\n');
+            output.line_map.push(SourceLocation {
+                origin: origin.to_string(),
+                line,
+            });
+            continue;
+        };
+
+        let name = directive
+            .trim()
+            .strip_prefix('"')
+            .and_then(|d| d.strip_suffix('"'))
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| ShaderIncludeError::MalformedDirective {
+                from: origin.to_string(),
+                line,
+            })?;
+
+        if stack.iter().any(|s| s == name) {
+            let mut cycle = stack.clone();
+            cycle.push(name.to_string());
+            return Err(ShaderIncludeError::Cycle(cycle));
+        }
+        // 已展开过的片段不再重复展开，避免重复定义
+        if !included.insert(name.to_string()) {
+            continue;
+        }
+
+        let code = snippets
+            .get(name)
+            .ok_or_else(|| ShaderIncludeError::UnknownSnippet {
+                name: name.to_string(),
+                from: origin.to_string(),
+                line,
+            })?;
+        stack.push(name.to_string());
+        expand_includes(name, code, snippets, stack, included, output)?;
+        stack.pop();
+    }
+    Ok(())
+}
+
 /// GPU 缓冲区包装器
 #[derive(Debug, Clone)]
 pub struct GPUBuffer {
@@ -314,6 +493,220 @@ impl GPUBuffer {
     }
 }
 
+/// 帧完成栅栏，报告 GPU 是否已执行完某一帧提交的命令
+pub trait FrameFence {
+    fn is_frame_complete(&self, frame: u64) -> bool;
+}
+
+/// 基于 `Queue::on_submitted_work_done` 的帧栅栏
+#[derive(Debug, Clone, Default)]
+pub struct QueueFrameFence {
+    /// 已完成的最新帧号加一，0 表示还没有帧完成
+    completed: Arc<AtomicU64>,
+}
+
+impl QueueFrameFence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 在提交帧 `frame` 的命令之后调用，GPU 执行完这些命令时标记该帧完成
+    pub fn signal_after_submit(&self, queue: &wgpu::Queue, frame: u64) {
+        let completed = Arc::clone(&self.completed);
+        queue.on_submitted_work_done(move || {
+            completed.fetch_max(frame + 1, Ordering::AcqRel);
+        });
+    }
+}
+
+impl FrameFence for QueueFrameFence {
+    fn is_frame_complete(&self, frame: u64) -> bool {
+        self.completed.load(Ordering::Acquire) > frame
+    }
+}
+
+/// 上传环形缓冲区分配错误
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum UploadRingError {
+    #[error("upload of {requested} bytes exceeds ring capacity of {capacity} bytes")]
+    TooLarge { requested: u64, capacity: u64 },
+    #[error("upload ring is full: {requested} bytes requested, {available} bytes free")]
+    OutOfSpace { requested: u64, available: u64 },
+}
+
+/// 环形区间分配逻辑
+///
+/// `head` 和 `tail` 是单调递增的位置，对容量取模得到缓冲区偏移。
+/// 每帧结束时记录该帧的结束位置，帧完成后 `tail` 前移到该位置以回收区间。
+#[derive(Debug)]
+struct RingAllocator {
+    capacity: u64,
+    alignment: u64,
+    head: u64,
+    tail: u64,
+    /// 已结束但 GPU 可能仍在使用的帧: (帧号, 结束位置)
+    in_flight: VecDeque<(u64, u64)>,
+    next_frame: u64,
+}
+
+impl RingAllocator {
+    fn new(capacity: u64, alignment: u64) -> Self {
+        Self {
+            capacity: capacity.div_ceil(alignment) * alignment,
+            alignment,
+            head: 0,
+            tail: 0,
+            in_flight: VecDeque::new(),
+            next_frame: 0,
+        }
+    }
+
+    fn allocate(&mut self, size: u64) -> Result<u64, UploadRingError> {
+        let size = size.max(1).div_ceil(self.alignment) * self.alignment;
+        if size > self.capacity {
+            return Err(UploadRingError::TooLarge {
+                requested: size,
+                capacity: self.capacity,
+            });
+        }
+
+        // 区间不能跨越缓冲区末尾，放不下时跳过剩余部分从头开始
+        let mut start = self.head;
+        let offset = start % self.capacity;
+        if offset + size > self.capacity {
+            start += self.capacity - offset;
+        }
+        if start + size - self.tail > self.capacity {
+            return Err(UploadRingError::OutOfSpace {
+                requested: size,
+                available: self.capacity - (self.head - self.tail),
+            });
+        }
+
+        self.head = start + size;
+        Ok(start % self.capacity)
+    }
+
+    fn finish_frame(&mut self) -> u64 {
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        self.in_flight.push_back((frame, self.head));
+        frame
+    }
+
+    fn reclaim(&mut self, fence: &impl FrameFence) {
+        while let Some(&(frame, end)) = self.in_flight.front() {
+            if !fence.is_frame_complete(frame) {
+                break;
+            }
+            self.tail = end;
+            self.in_flight.pop_front();
+        }
+    }
+}
+
+/// 每帧上传数据的环形缓冲区
+///
+/// 从一个大缓冲区中按帧切出子区间，多个帧可以同时在 GPU 上使用各自的区间，
+/// 写入时不需要重建缓冲区或等待 GPU。WGPU 不支持持久映射 GPU 可见的缓冲区，
+/// 因此数据通过 `Queue::write_buffer` 写入分配到的区间。
+///
+/// ```ignore
+/// let (buffer, offset) = ring.write(&queue, bytemuck::bytes_of(&uniforms))?;
+/// // ... 使用 (buffer, offset) 绑定并记录命令
+/// let frame = ring.finish_frame();
+/// queue.submit(Some(encoder.finish()));
+/// fence.signal_after_submit(&queue, frame);
+/// // 下一帧开始时
+/// ring.reclaim(&fence);
+/// ```
+pub struct GpuUploadRing {
+    buffer: Arc<wgpu::Buffer>,
+    allocator: RingAllocator,
+}
+
+impl GpuUploadRing {
+    /// 创建容量为 `capacity` 字节的环形缓冲区，`usage` 会自动加上 `COPY_DST`
+    pub fn new(device: &wgpu::Device, capacity: u64, usage: wgpu::BufferUsages) -> Self {
+        let limits = device.limits();
+        let mut alignment = wgpu::COPY_BUFFER_ALIGNMENT;
+        if usage.contains(wgpu::BufferUsages::UNIFORM) {
+            alignment = alignment.max(limits.min_uniform_buffer_offset_alignment as u64);
+        }
+        if usage.contains(wgpu::BufferUsages::STORAGE) {
+            alignment = alignment.max(limits.min_storage_buffer_offset_alignment as u64);
+        }
+
+        let allocator = RingAllocator::new(capacity, alignment);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Upload Ring"),
+            size: allocator.capacity,
+            usage: usage | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            buffer: Arc::new(buffer),
+            allocator,
+        }
+    }
+
+    /// 分配 `size` 字节，返回缓冲区和满足绑定对齐要求的偏移
+    ///
+    /// 尚未完成的帧占用的区间不会被重复分配，空间不足时返回 `OutOfSpace`。
+    pub fn allocate(&mut self, size: u64) -> Result<(Arc<wgpu::Buffer>, u64), UploadRingError> {
+        let offset = self.allocator.allocate(size)?;
+        Ok((Arc::clone(&self.buffer), offset))
+    }
+
+    /// 分配区间并写入数据
+    pub fn write(
+        &mut self,
+        queue: &wgpu::Queue,
+        data: &[u8],
+    ) -> Result<(Arc<wgpu::Buffer>, u64), UploadRingError> {
+        let (buffer, offset) = self.allocate(data.len() as u64)?;
+        // write_buffer 要求长度为 4 的倍数
+        if (data.len() as u64).is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT) {
+            queue.write_buffer(&buffer, offset, data);
+        } else {
+            let mut padded = data.to_vec();
+            padded.resize(
+                (data.len() as u64).div_ceil(wgpu::COPY_BUFFER_ALIGNMENT) as usize
+                    * wgpu::COPY_BUFFER_ALIGNMENT as usize,
+                0,
+            );
+            queue.write_buffer(&buffer, offset, &padded);
+        }
+        Ok((buffer, offset))
+    }
+
+    /// 结束当前帧的分配，返回帧号，提交命令后用它通知栅栏
+    pub fn finish_frame(&mut self) -> u64 {
+        self.allocator.finish_frame()
+    }
+
+    /// 回收栅栏报告已完成的帧占用的区间
+    pub fn reclaim(&mut self, fence: &impl FrameFence) {
+        self.allocator.reclaim(fence);
+    }
+
+    /// 环形缓冲区容量（字节）
+    pub fn capacity(&self) -> u64 {
+        self.allocator.capacity
+    }
+
+    /// 偏移对齐要求（字节）
+    pub fn alignment(&self) -> u64 {
+        self.allocator.alignment
+    }
+
+    /// 尚未被 GPU 释放的帧数
+    pub fn frames_in_flight(&self) -> usize {
+        self.allocator.in_flight.len()
+    }
+}
+
 /// 计算管道
 pub struct ComputePipelineWGPU {
     /// 管道名称
@@ -521,6 +914,65 @@ mod tests {
         assert!(!pathfinding.source.is_empty());
     }
 
+    #[test]
+    fn test_shader_include() {
+        WGSLShader::register_snippet(
+            "test_include/projection",
+            "fn project(p: vec3<f32>, depth: f32) -> vec2<f32> {\n    return p.xy / max(p.z, depth);\n}",
+        );
+        let shader = WGSLShader::new(
+            "include_test".to_string(),
+            r#"#include "test_include/projection"
+@group(0) @binding(0)
+var<storage, read_write> points: array<vec4<f32>>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let p = points[id.x];
+    points[id.x] = vec4<f32>(project(p.xyz, 0.1), 0.0, 1.0);
+}"#
+            .to_string(),
+            "main".to_string(),
+        );
+
+        let preprocessed = shader.preprocess().unwrap();
+        assert!(preprocessed.source.contains("fn project"));
+        assert!(!preprocessed.source.contains("#include"));
+        // 片段占前 3 行，原着色器第 2 行在展开后位于第 4 行
+        let location = preprocessed.map_line(4).unwrap();
+        assert_eq!(location.origin, "include_test");
+        assert_eq!(location.line, 2);
+        assert_eq!(
+            preprocessed.remap_error("error\n  ┌─ wgsl:2:12"),
+            "error\n  ┌─ test_include/projection:2:12"
+        );
+
+        if let Some((device, _queue)) = crate::test_utils::headless_device() {
+            shader.create_module(&device).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_shader_include_cycle() {
+        WGSLShader::register_snippet("test_cycle/a", "#include \"test_cycle/b\"");
+        WGSLShader::register_snippet("test_cycle/b", "#include \"test_cycle/a\"");
+        let shader = WGSLShader::new(
+            "cycle".to_string(),
+            "#include \"test_cycle/a\"".to_string(),
+            "main".to_string(),
+        );
+
+        match shader.preprocess() {
+            Err(ShaderIncludeError::Cycle(path)) => {
+                assert_eq!(
+                    path,
+                    ["cycle", "test_cycle/a", "test_cycle/b", "test_cycle/a"]
+                );
+            }
+            other => panic!("Expected include cycle, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_gpu_buffer() {
         let buffer = GPUBuffer::storage("test".to_string(), 1024);
@@ -529,6 +981,127 @@ mod tests {
         assert_eq!(buffer.size, 1024);
     }
 
+    /// 手动控制完成进度的栅栏
+    struct MockFence {
+        completed: std::cell::Cell<Option<u64>>,
+    }
+
+    impl FrameFence for MockFence {
+        fn is_frame_complete(&self, frame: u64) -> bool {
+            self.completed
+                .get()
+                .is_some_and(|completed| frame <= completed)
+        }
+    }
+
+    #[test]
+    fn test_upload_ring_wraps_without_overlap() {
+        let fence = MockFence {
+            completed: std::cell::Cell::new(None),
+        };
+        let mut ring = RingAllocator::new(1024, 256);
+        // (帧号, 偏移, 大小)
+        let mut in_use: Vec<(u64, u64, u64)> = Vec::new();
+        let mut wrapped = false;
+        let mut last_offset = 0;
+
+        for frame in 0..12u64 {
+            // GPU 落后 CPU 两帧
+            if frame >= 2 {
+                fence.completed.set(Some(frame - 2));
+            }
+            ring.reclaim(&fence);
+            in_use.retain(|&(f, _, _)| !fence.is_frame_complete(f));
+
+            for size in [100, 200] {
+                let offset = ring.allocate(size).unwrap();
+                assert_eq!(offset % 256, 0);
+                assert!(offset + 256 <= 1024);
+                for &(_, used_offset, used_size) in &in_use {
+                    assert!(
+                        offset + 256 <= used_offset || used_offset + used_size <= offset,
+                        "frame {} got {} overlapping in-flight range {}..{}",
+                        frame,
+                        offset,
+                        used_offset,
+                        used_offset + used_size
+                    );
+                }
+                wrapped |= offset < last_offset;
+                last_offset = offset;
+                in_use.push((frame, offset, 256));
+            }
+            assert_eq!(ring.finish_frame(), frame);
+        }
+        assert!(wrapped);
+
+        // GPU 停滞时环形缓冲区填满，不会覆盖未完成的帧
+        let mut ring = RingAllocator::new(1024, 256);
+        ring.allocate(512).unwrap();
+        ring.finish_frame();
+        ring.allocate(512).unwrap();
+        ring.finish_frame();
+        let stalled = MockFence {
+            completed: std::cell::Cell::new(None),
+        };
+        ring.reclaim(&stalled);
+        assert_eq!(
+            ring.allocate(1),
+            Err(UploadRingError::OutOfSpace {
+                requested: 256,
+                available: 0
+            })
+        );
+
+        // 第一帧完成后只回收了它的区间
+        stalled.completed.set(Some(0));
+        ring.reclaim(&stalled);
+        assert_eq!(
+            ring.allocate(768),
+            Err(UploadRingError::OutOfSpace {
+                requested: 768,
+                available: 512
+            })
+        );
+        assert_eq!(ring.allocate(300).unwrap(), 0);
+        assert!(matches!(
+            ring.allocate(2048),
+            Err(UploadRingError::TooLarge { .. })
+        ));
+
+        // 放不下的尾部被跳过，从头开始分配
+        let mut ring = RingAllocator::new(1024, 256);
+        ring.allocate(768).unwrap();
+        ring.finish_frame();
+        ring.reclaim(&MockFence {
+            completed: std::cell::Cell::new(Some(0)),
+        });
+        assert_eq!(ring.allocate(512).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_upload_ring_queue_fence() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut ring = GpuUploadRing::new(&device, 4096, wgpu::BufferUsages::UNIFORM);
+        let fence = QueueFrameFence::new();
+        let (_, first) = ring.write(&queue, &[1, 2, 3]).unwrap();
+        let (_, second) = ring.write(&queue, &[0; 16]).unwrap();
+        assert_eq!(first % ring.alignment(), 0);
+        assert_eq!(second, first + ring.alignment());
+
+        let frame = ring.finish_frame();
+        queue.submit(None);
+        fence.signal_after_submit(&queue, frame);
+        device.poll(wgpu::Maintain::Wait);
+
+        assert!(fence.is_frame_complete(frame));
+        ring.reclaim(&fence);
+        assert_eq!(ring.frames_in_flight(), 0);
+    }
+
     #[test]
     fn test_compute_pipeline() {
         let device = GPUComputeDevice::new();
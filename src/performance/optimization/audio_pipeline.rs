@@ -7,9 +7,13 @@
 //! - 性能监控
 
 use crate::impl_default;
+use game_engine_simd::AudioDSPOps;
 use glam::Vec3;
 use std::collections::HashMap;
 
+/// SIMD 混音的分块大小（帧）
+pub const MIX_BLOCK_FRAMES: usize = 256;
+
 /// 音频处理效果类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AudioEffectType {
@@ -79,6 +83,17 @@ pub enum AudioChannel {
     Ambient,
 }
 
+/// 一路混音输入
+#[derive(Debug, Clone, Copy)]
+pub struct MixInput<'a> {
+    /// 所属通道，决定通道音量、启用状态和效果衰减
+    pub channel: AudioChannel,
+    /// 交错排列的样本，声道数与输出缓冲区相同
+    pub samples: &'a [f32],
+    /// 源增益
+    pub gain: f32,
+}
+
 /// 音频通道混合器
 pub struct AudioChannelMixer {
     /// 各通道音量
@@ -176,6 +191,51 @@ impl AudioChannelMixer {
     pub fn get_channel_effects(&self, channel: AudioChannel) -> Option<&Vec<AudioEffect>> {
         self.channel_effects.get(&channel)
     }
+
+    /// 把多路输入混合到交错的多声道输出缓冲区 (SIMD 优化)
+    ///
+    /// 每路输入的增益由 `calculate_output_gain` 计算，增益为 0 的输入（如被禁用的通道）直接跳过。
+    /// 按 `MIX_BLOCK_FRAMES` 帧分块，所有输入累加完一个块后再处理下一个块，
+    /// 使输出块保持在缓存中。`output` 会被覆盖，长度须为 `output_channels` 的整数倍，
+    /// 每路输入至少要有 `output.len()` 个样本。
+    pub fn mix_channels(&self, inputs: &[MixInput], output: &mut [f32], output_channels: usize) {
+        assert!(
+            output_channels > 0 && output.len().is_multiple_of(output_channels),
+            "output length {} is not a whole number of {}-channel frames",
+            output.len(),
+            output_channels
+        );
+        assert!(
+            inputs
+                .iter()
+                .all(|input| input.samples.len() >= output.len()),
+            "every mix input must provide at least {} samples",
+            output.len()
+        );
+
+        let (sources, gains): (Vec<&[f32]>, Vec<f32>) = inputs
+            .iter()
+            .map(|input| {
+                let gain = self.calculate_output_gain(input.channel, input.gain);
+                (&input.samples[..output.len()], gain)
+            })
+            .filter(|&(_, gain)| gain > 0.0)
+            .unzip();
+
+        output.fill(0.0);
+        let block_len = MIX_BLOCK_FRAMES * output_channels;
+        let mut block_sources = Vec::with_capacity(sources.len());
+        for (block_idx, block) in output.chunks_mut(block_len).enumerate() {
+            let start = block_idx * block_len;
+            block_sources.clear();
+            block_sources.extend(
+                sources
+                    .iter()
+                    .map(|source| &source[start..start + block.len()]),
+            );
+            AudioDSPOps::mix_into(&block_sources, &gains, block);
+        }
+    }
 }
 
 /// 实时音频处理管道
@@ -425,6 +485,65 @@ mod tests {
         assert_eq!(updater.pending_count(), 0);
     }
 
+    #[test]
+    fn test_mix_channels_matches_scalar() {
+        let mut mixer = AudioChannelMixer::new();
+        mixer.set_channel_volume(AudioChannel::Music, 0.6);
+        mixer.set_channel_enabled(AudioChannel::Voice, false);
+        mixer.add_channel_effect(
+            AudioChannel::SFX,
+            AudioEffect::new(AudioEffectType::Reverb).with_intensity(0.5),
+        );
+
+        // 3 声道、517 帧：既不是分块大小也不是 SIMD 宽度的整数倍
+        let output_channels = 3;
+        let len = 517 * output_channels;
+        let channels = [
+            AudioChannel::Music,
+            AudioChannel::SFX,
+            AudioChannel::Voice,
+            AudioChannel::Ambient,
+        ];
+        let buffers: Vec<Vec<f32>> = (0..8)
+            .map(|s| {
+                (0..len)
+                    .map(|i| ((i * 31 + s * 17) % 101) as f32 / 101.0 - 0.5)
+                    .collect()
+            })
+            .collect();
+        let inputs: Vec<MixInput> = buffers
+            .iter()
+            .enumerate()
+            .map(|(s, samples)| MixInput {
+                channel: channels[s % channels.len()],
+                samples,
+                gain: 0.3 + s as f32 * 0.1,
+            })
+            .collect();
+
+        let mut expected = vec![0.0f32; len];
+        for input in &inputs {
+            let gain = mixer.calculate_output_gain(input.channel, input.gain);
+            for (out, sample) in expected.iter_mut().zip(input.samples) {
+                *out += sample * gain;
+            }
+        }
+
+        for backend in game_engine_simd::SimdBackend::supported() {
+            let mut output = vec![1.0f32; len];
+            backend.with_override(|| mixer.mix_channels(&inputs, &mut output, output_channels));
+            for (actual, expected) in output.iter().zip(&expected) {
+                assert!(
+                    (actual - expected).abs() <= f32::EPSILON * 8.0,
+                    "{:?}: {} != {}",
+                    backend,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_channel_effects() {
         let mut mixer = AudioChannelMixer::new();
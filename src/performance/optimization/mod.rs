@@ -6,6 +6,6 @@ pub use ai_pathfinding::{
 };
 pub use audio_pipeline::{
     AudioChannel, AudioChannelMixer, AudioEffect, AudioEffectType, AudioProcessingPipeline,
-    AudioUpdate, BatchAudioUpdater,
+    AudioUpdate, BatchAudioUpdater, MixInput, MIX_BLOCK_FRAMES,
 };
 
@@ -0,0 +1,211 @@
+//! 手柄输入映射
+//!
+//! 将 W3C 标准手柄布局（Standard Gamepad）的按钮/轴索引映射为引擎的
+//! `GamepadButton` / `GamepadAxis`，并通过逐帧对比手柄快照生成 `InputEvent`。
+//! 该模块与平台无关，Web 平台的 `WebInput` 每帧将 `navigator.getGamepads()`
+//! 的结果转换为快照后交给 `GamepadTracker`。
+
+use super::{GamepadAxis, GamepadButton, InputEvent};
+use std::collections::BTreeMap;
+
+/// 轴值变化小于该阈值时不产生事件，避免摇杆抖动刷屏
+pub const AXIS_EPSILON: f32 = 0.01;
+
+/// 标准布局按钮索引到 `GamepadButton` 的映射
+pub fn standard_button(index: usize) -> Option<GamepadButton> {
+    Some(match index {
+        0 => GamepadButton::South,
+        1 => GamepadButton::East,
+        2 => GamepadButton::West,
+        3 => GamepadButton::North,
+        4 => GamepadButton::LeftBumper,
+        5 => GamepadButton::RightBumper,
+        6 => GamepadButton::LeftTrigger,
+        7 => GamepadButton::RightTrigger,
+        8 => GamepadButton::Select,
+        9 => GamepadButton::Start,
+        10 => GamepadButton::LeftThumb,
+        11 => GamepadButton::RightThumb,
+        12 => GamepadButton::DPadUp,
+        13 => GamepadButton::DPadDown,
+        14 => GamepadButton::DPadLeft,
+        15 => GamepadButton::DPadRight,
+        16 => GamepadButton::Mode,
+        _ => return None,
+    })
+}
+
+/// 标准布局轴索引到 `GamepadAxis` 的映射
+pub fn standard_axis(index: usize) -> Option<GamepadAxis> {
+    Some(match index {
+        0 => GamepadAxis::LeftStickX,
+        1 => GamepadAxis::LeftStickY,
+        2 => GamepadAxis::RightStickX,
+        3 => GamepadAxis::RightStickY,
+        _ => return None,
+    })
+}
+
+/// 标准布局中扳机是模拟按钮，其数值额外以轴事件上报
+fn trigger_axis(button: GamepadButton) -> Option<GamepadAxis> {
+    match button {
+        GamepadButton::LeftTrigger => Some(GamepadAxis::LeftTrigger),
+        GamepadButton::RightTrigger => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
+
+/// 单个按钮状态
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GamepadButtonState {
+    pub pressed: bool,
+    pub value: f32,
+}
+
+/// 某一帧的手柄状态快照
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GamepadSnapshot {
+    pub id: u32,
+    pub axes: Vec<f32>,
+    pub buttons: Vec<GamepadButtonState>,
+}
+
+/// 手柄状态跟踪器 - 对比相邻两帧的快照生成连接/断开/按钮/轴事件
+#[derive(Debug, Default)]
+pub struct GamepadTracker {
+    previous: BTreeMap<u32, GamepadSnapshot>,
+}
+
+impl GamepadTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前已连接的手柄ID
+    pub fn connected(&self) -> impl Iterator<Item = u32> + '_ {
+        self.previous.keys().copied()
+    }
+
+    /// 用本帧的快照更新状态，返回产生的输入事件
+    pub fn update(&mut self, current: Vec<GamepadSnapshot>) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        let mut next = BTreeMap::new();
+
+        for snapshot in current {
+            let id = snapshot.id;
+            let previous = self.previous.remove(&id);
+            if previous.is_none() {
+                events.push(InputEvent::GamepadConnected(id));
+            }
+            let previous = previous.unwrap_or_default();
+            Self::diff(&previous, &snapshot, &mut events);
+            next.insert(id, snapshot);
+        }
+
+        // 上一帧存在但本帧消失的手柄视为断开
+        for id in self.previous.keys() {
+            events.push(InputEvent::GamepadDisconnected(*id));
+        }
+
+        self.previous = next;
+        events
+    }
+
+    fn diff(previous: &GamepadSnapshot, current: &GamepadSnapshot, events: &mut Vec<InputEvent>) {
+        let id = current.id;
+
+        for (index, &value) in current.axes.iter().enumerate() {
+            let Some(axis) = standard_axis(index) else {
+                continue;
+            };
+            let old = previous.axes.get(index).copied().unwrap_or(0.0);
+            if (value - old).abs() > AXIS_EPSILON {
+                events.push(InputEvent::GamepadAxis { id, axis, value });
+            }
+        }
+
+        for (index, state) in current.buttons.iter().enumerate() {
+            let Some(button) = standard_button(index) else {
+                continue;
+            };
+            let old = previous.buttons.get(index).copied().unwrap_or_default();
+            if state.pressed != old.pressed {
+                events.push(InputEvent::GamepadButton {
+                    id,
+                    button,
+                    pressed: state.pressed,
+                });
+            }
+            if let Some(axis) = trigger_axis(button) {
+                if (state.value - old.value).abs() > AXIS_EPSILON {
+                    events.push(InputEvent::GamepadAxis {
+                        id,
+                        axis,
+                        value: state.value,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(id: u32, pressed: &[usize]) -> GamepadSnapshot {
+        let buttons = (0..17)
+            .map(|i| GamepadButtonState {
+                pressed: pressed.contains(&i),
+                value: if pressed.contains(&i) { 1.0 } else { 0.0 },
+            })
+            .collect();
+        GamepadSnapshot {
+            id,
+            axes: vec![0.0; 4],
+            buttons,
+        }
+    }
+
+    #[test]
+    fn test_standard_button_mapping() {
+        assert_eq!(standard_button(0), Some(GamepadButton::South));
+        assert_eq!(standard_button(3), Some(GamepadButton::North));
+        assert_eq!(standard_button(12), Some(GamepadButton::DPadUp));
+        assert_eq!(standard_button(16), Some(GamepadButton::Mode));
+        assert_eq!(standard_button(17), None);
+        assert_eq!(standard_axis(1), Some(GamepadAxis::LeftStickY));
+        assert_eq!(standard_axis(4), None);
+    }
+
+    #[test]
+    fn test_tracker_connect_press_disconnect() {
+        let mut tracker = GamepadTracker::new();
+
+        let events = tracker.update(vec![snapshot(0, &[])]);
+        assert!(matches!(
+            events.as_slice(),
+            [InputEvent::GamepadConnected(0)]
+        ));
+
+        let events = tracker.update(vec![snapshot(0, &[0])]);
+        assert!(matches!(
+            events.as_slice(),
+            [InputEvent::GamepadButton {
+                id: 0,
+                button: GamepadButton::South,
+                pressed: true
+            }]
+        ));
+
+        // 无变化时不产生事件
+        assert!(tracker.update(vec![snapshot(0, &[0])]).is_empty());
+
+        let events = tracker.update(Vec::new());
+        assert!(matches!(
+            events.as_slice(),
+            [InputEvent::GamepadDisconnected(0)]
+        ));
+        assert_eq!(tracker.connected().count(), 0);
+    }
+}
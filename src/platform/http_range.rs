@@ -0,0 +1,188 @@
+//! HTTP Range 请求辅助
+//!
+//! 构造 `Range` 请求头，并根据响应状态码解析响应内容。
+//! 不支持 Range 的服务器会返回 `200` 和完整内容，调用方可缓存完整内容，
+//! 之后的区间直接在客户端截取，不再重复下载。
+
+use super::FsError;
+
+/// 字节区间请求 `[start, end)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn new(start: u64, end: u64) -> Result<Self, FsError> {
+        if end <= start {
+            return Err(FsError::IoError(format!(
+                "Invalid byte range: {}..{}",
+                start, end
+            )));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// 区间长度（字节）
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// 区间是否为空（`new` 保证不会为空）
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// `Range` 请求头的值，HTTP 区间为闭区间
+    pub fn header_value(&self) -> String {
+        format!("bytes={}-{}", self.start, self.end - 1)
+    }
+
+    /// 从完整文件内容中截取本区间，超出文件末尾的部分截断
+    pub fn slice<'a>(&self, full: &'a [u8]) -> &'a [u8] {
+        let len = full.len() as u64;
+        let start = self.start.min(len) as usize;
+        let end = self.end.min(len) as usize;
+        &full[start..end]
+    }
+
+    /// 根据响应状态码、`Content-Range` 响应头和响应体解析响应内容
+    ///
+    /// - `206`: 服务器已按区间返回，`Content-Range` 必须从请求的起始位置开始，超出部分截断
+    /// - `200`: 服务器忽略了 Range，返回完整内容
+    /// - `416`: 起始位置超出文件末尾，返回空数据
+    pub fn resolve(
+        &self,
+        status: u16,
+        content_range: Option<&str>,
+        mut body: Vec<u8>,
+    ) -> Result<RangeResponse, FsError> {
+        match status {
+            206 => {
+                let (start, end) =
+                    content_range.and_then(parse_content_range).ok_or_else(|| {
+                        FsError::NetworkError(format!(
+                            "Invalid Content-Range for 206 response: {:?}",
+                            content_range
+                        ))
+                    })?;
+                if start != self.start || end > self.end {
+                    return Err(FsError::NetworkError(format!(
+                        "Content-Range {}..{} does not match requested range {}..{}",
+                        start, end, self.start, self.end
+                    )));
+                }
+                body.truncate((end - start) as usize);
+                Ok(RangeResponse::Partial(body))
+            }
+            200 => Ok(RangeResponse::Full(body)),
+            416 => Ok(RangeResponse::Partial(Vec::new())),
+            status => Err(FsError::NetworkError(format!("HTTP {}", status))),
+        }
+    }
+}
+
+/// 解析 `Content-Range: bytes <first>-<last>/<total>`，返回半开区间 `[first, last + 1)`
+fn parse_content_range(value: &str) -> Option<(u64, u64)> {
+    let (unit, rest) = value.trim().split_once(' ')?;
+    if !unit.eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let (range, _total) = rest.split_once('/')?;
+    let (first, last) = range.split_once('-')?;
+    let first: u64 = first.trim().parse().ok()?;
+    let last: u64 = last.trim().parse().ok()?;
+    (last >= first).then_some((first, last + 1))
+}
+
+/// Range 请求的响应内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeResponse {
+    /// 请求区间的字节
+    Partial(Vec<u8>),
+    /// 服务器忽略了 Range，返回的完整文件
+    Full(Vec<u8>),
+}
+
+impl RangeResponse {
+    /// 请求区间的字节
+    pub fn into_range(self, range: &ByteRange) -> Vec<u8> {
+        match self {
+            Self::Partial(bytes) => bytes,
+            Self::Full(full) => range.slice(&full).to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_header() {
+        let range = ByteRange::new(0, 1024).unwrap();
+        assert_eq!(range.header_value(), "bytes=0-1023");
+        assert_eq!(range.len(), 1024);
+
+        let range = ByteRange::new(500, 501).unwrap();
+        assert_eq!(range.header_value(), "bytes=500-500");
+
+        assert!(ByteRange::new(10, 10).is_err());
+    }
+
+    #[test]
+    fn test_partial_vs_full_response() {
+        let range = ByteRange::new(2, 5).unwrap();
+        let full: Vec<u8> = (0..10).collect();
+
+        // 206: 服务器已返回区间内容
+        let partial = range
+            .resolve(206, Some("bytes 2-4/10"), vec![2, 3, 4, 5])
+            .unwrap();
+        assert_eq!(partial, RangeResponse::Partial(vec![2, 3, 4]));
+        assert_eq!(partial.into_range(&range), vec![2, 3, 4]);
+
+        // 200: 服务器忽略 Range，保留完整内容，客户端截取
+        let whole = range.resolve(200, None, full.clone()).unwrap();
+        assert_eq!(whole, RangeResponse::Full(full.clone()));
+        assert_eq!(whole.into_range(&range), vec![2, 3, 4]);
+
+        // 区间超出文件末尾
+        let tail = ByteRange::new(8, 20).unwrap();
+        assert_eq!(tail.slice(&full), &[8, 9]);
+        assert!(ByteRange::new(12, 20).unwrap().slice(&full).is_empty());
+
+        let past_end = range.resolve(416, None, Vec::new()).unwrap();
+        assert_eq!(past_end, RangeResponse::Partial(Vec::new()));
+        assert!(range.resolve(404, None, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_partial_response_checks_content_range() {
+        let range = ByteRange::new(2, 5).unwrap();
+
+        // 文件末尾：服务器返回的区间比请求的短
+        let tail = range.resolve(206, Some("bytes 2-3/4"), vec![2, 3]).unwrap();
+        assert_eq!(tail, RangeResponse::Partial(vec![2, 3]));
+        // 总长度未知
+        assert!(range
+            .resolve(206, Some("bytes 2-4/*"), vec![2, 3, 4])
+            .is_ok());
+
+        // 起始位置不一致、超出请求区间、缺失或格式错误时拒绝
+        assert!(range
+            .resolve(206, Some("bytes 0-2/10"), vec![0, 1, 2])
+            .is_err());
+        assert!(range
+            .resolve(206, Some("bytes 2-7/10"), vec![2; 6])
+            .is_err());
+        assert!(range.resolve(206, None, vec![2, 3, 4]).is_err());
+        assert!(range
+            .resolve(206, Some("items 2-4/10"), vec![2, 3, 4])
+            .is_err());
+        assert!(range
+            .resolve(206, Some("bytes 4-2/10"), Vec::new())
+            .is_err());
+    }
+}
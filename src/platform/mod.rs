@@ -1,5 +1,7 @@
 pub mod winit;
 pub mod power_aware;
+pub mod gamepad;
+pub mod http_range;
 
 use thiserror::Error;
 
@@ -1,3 +1,4 @@
+use super::http_range::{ByteRange, RangeResponse};
 #[cfg(target_arch = "wasm32")]
 use super::{Filesystem, FsError};
 use std::future::Future;
@@ -5,7 +6,7 @@ use std::pin::Pin;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response, Window};
+use web_sys::{Headers, Request, RequestInit, RequestMode, Response, Window};
 
 /// Web平台文件系统实现
 /// 使用fetch API加载资源,使用localStorage作为缓存
@@ -25,6 +26,72 @@ impl WebFilesystem {
             .map_err(|_| FsError::IoError("Failed to access localStorage".to_string()))?
             .ok_or_else(|| FsError::IoError("localStorage not available".to_string()))
     }
+
+    /// 通过HTTP Range请求读取 `[start, end)` 区间的字节
+    ///
+    /// 用于流式加载大文件（如音频流），只下载需要的分块。
+    /// 服务器忽略Range时返回 `RangeResponse::Full`，调用方可缓存完整内容。
+    pub fn read_range(
+        &self,
+        url: &str,
+        start: u64,
+        end: u64,
+    ) -> Pin<Box<dyn Future<Output = Result<RangeResponse, FsError>> + Send>> {
+        let url = url.to_string();
+        let window = self.window.clone();
+
+        Box::pin(async move {
+            let range = ByteRange::new(start, end)?;
+
+            let headers = Headers::new()
+                .map_err(|e| FsError::NetworkError(format!("Failed to create headers: {:?}", e)))?;
+            headers
+                .set("Range", &range.header_value())
+                .map_err(|e| FsError::NetworkError(format!("Failed to set Range: {:?}", e)))?;
+
+            let mut opts = RequestInit::new();
+            opts.method("GET");
+            opts.mode(RequestMode::Cors);
+            opts.headers(&headers);
+
+            let request = Request::new_with_str_and_init(&url, &opts)
+                .map_err(|e| FsError::NetworkError(format!("Failed to create request: {:?}", e)))?;
+
+            let resp_value = JsFuture::from(window.fetch_with_request(&request))
+                .await
+                .map_err(|e| FsError::NetworkError(format!("Fetch failed: {:?}", e)))?;
+
+            let resp: Response = resp_value
+                .dyn_into()
+                .map_err(|_| FsError::NetworkError("Invalid response".to_string()))?;
+
+            // 416等非2xx状态交给ByteRange::resolve处理
+            let status = resp.status();
+            let content_range = resp.headers().get("Content-Range").ok().flatten();
+            let body = if resp.ok() {
+                read_body(&resp).await?
+            } else {
+                Vec::new()
+            };
+
+            range.resolve(status, content_range.as_deref(), body)
+        })
+    }
+}
+
+/// 读取响应体的全部字节
+async fn read_body(resp: &Response) -> Result<Vec<u8>, FsError> {
+    let array_buffer = JsFuture::from(
+        resp.array_buffer()
+            .map_err(|e| FsError::NetworkError(format!("Failed to get array buffer: {:?}", e)))?,
+    )
+    .await
+    .map_err(|e| FsError::NetworkError(format!("Failed to read array buffer: {:?}", e)))?;
+
+    let uint8_array = js_sys::Uint8Array::new(&array_buffer);
+    let mut data = vec![0u8; uint8_array.length() as usize];
+    uint8_array.copy_to(&mut data);
+    Ok(data)
 }
 
 impl Filesystem for WebFilesystem {
@@ -60,17 +127,7 @@ impl Filesystem for WebFilesystem {
             }
 
             // 读取数据
-            let array_buffer = JsFuture::from(resp.array_buffer().map_err(|e| {
-                FsError::NetworkError(format!("Failed to get array buffer: {:?}", e))
-            })?)
-            .await
-            .map_err(|e| FsError::NetworkError(format!("Failed to read array buffer: {:?}", e)))?;
-
-            let uint8_array = js_sys::Uint8Array::new(&array_buffer);
-            let mut data = vec![0u8; uint8_array.length() as usize];
-            uint8_array.copy_to(&mut data);
-
-            Ok(data)
+            read_body(&resp).await
         })
     }
 
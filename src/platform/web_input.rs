@@ -1,10 +1,13 @@
 #[cfg(target_arch = "wasm32")]
+use super::gamepad::{GamepadButtonState, GamepadSnapshot, GamepadTracker};
 use super::{Input, InputEvent, KeyCode, Modifiers, MouseButton};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlCanvasElement, KeyboardEvent, MouseEvent, TouchEvent, WheelEvent, Window};
+use web_sys::{
+    Gamepad, HtmlCanvasElement, KeyboardEvent, MouseEvent, TouchEvent, WheelEvent, Window,
+};
 
 /// Web平台输入处理器
 pub struct WebInput {
@@ -14,6 +17,7 @@ pub struct WebInput {
     keys_pressed: Arc<Mutex<HashSet<KeyCode>>>,
     mouse_buttons: Arc<Mutex<HashSet<MouseButton>>>,
     mouse_pos: Arc<Mutex<(f32, f32)>>,
+    gamepads: GamepadTracker,
 }
 
 impl WebInput {
@@ -39,6 +43,7 @@ impl WebInput {
             keys_pressed,
             mouse_buttons,
             mouse_pos,
+            gamepads: GamepadTracker::new(),
         };
 
         input.setup_event_listeners()?;
@@ -152,11 +157,46 @@ impl WebInput {
 
         Ok(())
     }
+
+    /// 读取 `navigator.getGamepads()` 的当前状态
+    ///
+    /// 浏览器没有手柄输入事件，只能逐帧轮询；空槽位为 `null`，直接跳过。
+    fn gamepad_snapshots(&self) -> Vec<GamepadSnapshot> {
+        let Ok(pads) = self.window.navigator().get_gamepads() else {
+            return Vec::new();
+        };
+        pads.iter()
+            .filter_map(|pad| pad.dyn_into::<Gamepad>().ok())
+            .filter(|pad| pad.connected())
+            .map(|pad| GamepadSnapshot {
+                id: pad.index(),
+                axes: pad
+                    .axes()
+                    .iter()
+                    .map(|v| v.as_f64().unwrap_or(0.0) as f32)
+                    .collect(),
+                buttons: pad
+                    .buttons()
+                    .iter()
+                    .map(|b| {
+                        let b: web_sys::GamepadButton = b.unchecked_into();
+                        GamepadButtonState {
+                            pressed: b.pressed(),
+                            value: b.value() as f32,
+                        }
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
 }
 
 impl Input for WebInput {
     fn poll_events(&mut self) -> Vec<InputEvent> {
-        self.events.lock().unwrap().drain(..).collect()
+        let mut events: Vec<InputEvent> = self.events.lock().unwrap().drain(..).collect();
+        let snapshots = self.gamepad_snapshots();
+        events.extend(self.gamepads.update(snapshots));
+        events
     }
 
     fn is_key_pressed(&self, key: KeyCode) -> bool {
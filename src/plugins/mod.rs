@@ -128,6 +128,16 @@ impl App {
         self
     }
 
+    /// 注册事件类型：插入 `Events<T>` 资源并在每次更新时交换其缓冲区
+    pub fn add_event<T: crate::ecs::Event>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<crate::ecs::Events<T>>() {
+            self.world.init_resource::<crate::ecs::Events<T>>();
+            self.schedule
+                .add_systems(crate::ecs::update_events_system::<T>);
+        }
+        self
+    }
+
     pub fn add_startup_system<M>(&mut self, system: impl IntoSystemConfigs<M>) -> &mut Self {
         self.startup_schedule.add_systems(system);
         self
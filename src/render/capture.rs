@@ -0,0 +1,153 @@
+//! 帧回读
+//!
+//! 将交换链或离屏纹理拷贝到可映射缓冲区，去除行对齐填充，
+//! 并按纹理格式转换为紧密排列的 RGBA8 数据，用于截图和自动化测试。
+
+/// 回读缓冲区中每行的字节数，按 `COPY_BYTES_PER_ROW_ALIGNMENT` 对齐
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    (width * 4).div_ceil(align) * align
+}
+
+/// 格式是否需要交换 R/B 通道，不支持回读的格式返回 `None`
+fn swap_red_blue(format: wgpu::TextureFormat) -> Option<bool> {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => Some(false),
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => Some(true),
+        _ => None,
+    }
+}
+
+/// 去除行填充并转换为 RGBA8
+///
+/// `data` 为按 `padded_bytes_per_row` 排列的原始回读数据。
+/// 格式不是 8 位 RGBA/BGRA 或数据长度不足时返回 `None`。
+pub fn unpad_to_rgba8(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    format: wgpu::TextureFormat,
+) -> Option<Vec<u8>> {
+    let swap = swap_red_blue(format)?;
+    let row_bytes = width as usize * 4;
+    let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+    for row in data
+        .chunks(padded_bytes_per_row as usize)
+        .take(height as usize)
+    {
+        let row = row.get(..row_bytes)?;
+        if swap {
+            for px in row.chunks_exact(4) {
+                pixels.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+            }
+        } else {
+            pixels.extend_from_slice(row);
+        }
+    }
+    (pixels.len() == row_bytes * height as usize).then_some(pixels)
+}
+
+/// 同步回读纹理第 0 级 mip 为 RGBA8
+///
+/// 纹理需带有 `COPY_SRC` 用途，调用会阻塞直到 GPU 完成拷贝。
+pub fn read_texture_rgba8(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+) -> Option<Vec<u8>> {
+    let (width, height, format) = (texture.width(), texture.height(), texture.format());
+    let copyable = texture.usage().contains(wgpu::TextureUsages::COPY_SRC);
+    if !copyable || swap_red_blue(format).is_none() {
+        return None;
+    }
+
+    let bytes_per_row = padded_bytes_per_row(width);
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Frame Capture Readback"),
+        size: bytes_per_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("Frame Capture Encoder"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &readback,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = readback.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    if !matches!(rx.recv(), Ok(Ok(()))) {
+        tracing::warn!(target: "render", "Failed to map frame capture buffer");
+        return None;
+    }
+
+    let pixels = unpad_to_rgba8(
+        &slice.get_mapped_range(),
+        width,
+        height,
+        bytes_per_row,
+        format,
+    );
+    readback.unmap();
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padded_bytes_per_row() {
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+        assert_eq!(padded_bytes_per_row(1), 256);
+    }
+
+    #[test]
+    fn test_unpad_bgra_to_rgba() {
+        // 3x2 的 BGRA 图像，每行填充到 256 字节
+        let (width, height) = (3, 2);
+        let stride = padded_bytes_per_row(width);
+        let mut data = vec![0xAAu8; (stride * height) as usize];
+        for y in 0..height {
+            for x in 0..width {
+                let i = (y * stride + x * 4) as usize;
+                data[i..i + 4].copy_from_slice(&[1, 2, 3 + (y * width + x) as u8, 255]);
+            }
+        }
+        let unpad = |data: &[u8], format| unpad_to_rgba8(data, width, height, stride, format);
+
+        let rgba = unpad(&data, wgpu::TextureFormat::Bgra8UnormSrgb).unwrap();
+        assert_eq!(rgba.len(), (width * height * 4) as usize);
+        assert_eq!(&rgba[..4], &[3, 2, 1, 255]);
+        assert_eq!(&rgba[20..], &[8, 2, 1, 255]);
+        // 填充字节被丢弃
+        assert!(!rgba.contains(&0xAA));
+
+        let rgba = unpad(&data, wgpu::TextureFormat::Rgba8Unorm).unwrap();
+        assert_eq!(&rgba[..4], &[1, 2, 3, 255]);
+
+        assert!(unpad(&data, wgpu::TextureFormat::Rgba16Float).is_none());
+        assert!(unpad(&data[..10], wgpu::TextureFormat::Rgba8Unorm).is_none());
+    }
+}
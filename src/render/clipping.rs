@@ -38,6 +38,11 @@ impl ClipRect {
         self.max - self.min
     }
 
+    /// 裁剪区域面积是否为零 (完全裁剪)
+    pub fn is_empty(&self) -> bool {
+        self.width() <= 0.0 || self.height() <= 0.0
+    }
+
     /// 检查点是否在裁剪区域内
     pub fn contains(&self, point: Vec2) -> bool {
         point.x >= self.min.x
@@ -84,10 +89,13 @@ impl ClipStack {
     }
 
     /// 推入新的裁剪区域
+    ///
+    /// 新区域会与栈顶求交集；父区域已被完全裁剪时子区域同样为空。
     pub fn push(&mut self, clip: ClipRect) {
         if let Some(current) = self.current() {
             // 与当前裁剪区域求交集
-            if let Some(intersection) = current.intersection(&clip) {
+            if let Some(intersection) = current.intersection(&clip).filter(|_| !current.is_empty())
+            {
                 self.stack.push(intersection);
             } else {
                 // 如果没有交集,推入一个空区域
@@ -108,6 +116,52 @@ impl ClipStack {
         self.stack.last().copied()
     }
 
+    /// 当前绘制是否被完全裁剪，应跳过
+    pub fn is_clipped(&self) -> bool {
+        self.current().is_some_and(|clip| clip.is_empty())
+    }
+
+    /// 检查给定包围盒在当前裁剪区域下是否需要绘制
+    pub fn should_draw(&self, bounds: &ClipRect) -> bool {
+        match self.current() {
+            None => true,
+            Some(clip) => clip
+                .intersection(bounds)
+                .is_some_and(|visible| !visible.is_empty()),
+        }
+    }
+
+    /// 获取当前裁剪区域对应的剪刀矩形 (x, y, width, height)
+    ///
+    /// 结果限制在渲染目标范围内，栈为空时返回整个目标，完全裁剪时返回 None。
+    pub fn scissor_rect(&self, target_width: u32, target_height: u32) -> Option<[u32; 4]> {
+        let target = ClipRect::new(
+            Vec2::ZERO,
+            Vec2::new(target_width as f32, target_height as f32),
+        );
+        let clip = match self.current() {
+            Some(clip) => clip.intersection(&target)?,
+            None => target,
+        };
+        if clip.is_empty() {
+            return None;
+        }
+
+        let min = clip.min.floor();
+        let max = clip.max.ceil();
+        Some([
+            min.x as u32,
+            min.y as u32,
+            (max.x - min.x) as u32,
+            (max.y - min.y) as u32,
+        ])
+    }
+
+    /// 栈深度
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
     /// 清空裁剪栈
     pub fn clear(&mut self) {
         self.stack.clear();
@@ -166,4 +220,53 @@ mod tests {
         assert_eq!(current.min, Vec2::new(0.0, 0.0));
         assert_eq!(current.max, Vec2::new(200.0, 200.0));
     }
+
+    #[test]
+    fn test_nested_clip_stack() {
+        let mut stack = ClipStack::new();
+        assert!(!stack.is_clipped());
+        assert_eq!(stack.scissor_rect(800, 600), Some([0, 0, 800, 600]));
+
+        stack.push(ClipRect::from_pos_size(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(100.0, 100.0),
+        ));
+        stack.push(ClipRect::from_pos_size(
+            Vec2::new(60.0, 40.0),
+            Vec2::new(100.0, 100.0),
+        ));
+
+        // 有效裁剪区域为两者交集
+        let effective = stack.current().unwrap();
+        assert_eq!(effective.min, Vec2::new(60.0, 40.0));
+        assert_eq!(effective.max, Vec2::new(100.0, 100.0));
+        assert_eq!(stack.scissor_rect(800, 600), Some([60, 40, 40, 60]));
+        assert!(!stack.is_clipped());
+
+        // 不相交的子区域导致完全裁剪
+        stack.push(ClipRect::from_pos_size(
+            Vec2::new(300.0, 300.0),
+            Vec2::new(50.0, 50.0),
+        ));
+        assert!(stack.current().unwrap().is_empty());
+        assert!(stack.is_clipped());
+        assert_eq!(stack.scissor_rect(800, 600), None);
+        assert!(!stack.should_draw(&ClipRect::from_pos_size(
+            Vec2::new(70.0, 50.0),
+            Vec2::new(10.0, 10.0),
+        )));
+
+        // 完全裁剪下的子区域仍为空
+        stack.push(ClipRect::from_pos_size(Vec2::ZERO, Vec2::new(800.0, 600.0)));
+        assert!(stack.is_clipped());
+
+        stack.pop();
+        stack.pop();
+        assert!(!stack.is_clipped());
+        assert_eq!(stack.depth(), 2);
+        assert!(stack.should_draw(&ClipRect::from_pos_size(
+            Vec2::new(70.0, 50.0),
+            Vec2::new(10.0, 10.0),
+        )));
+    }
 }
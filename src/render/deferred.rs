@@ -1,5 +1,141 @@
+use crate::render::csm::CascadedShadowMap;
+use crate::render::mesh::{GpuMesh, VertexLayout};
+use crate::render::pbr::PointLight3D;
+use glam::{Mat4, Vec3, Vec4};
 use wgpu::util::DeviceExt;
 
+/// 透明阶段单帧最多绘制的物体数
+pub const MAX_TRANSPARENT_OBJECTS: usize = 1024;
+/// 透明阶段最多使用的点光源数
+pub const MAX_TRANSPARENT_LIGHTS: usize = 256;
+
+/// 前向透明阶段绘制的物体
+pub struct TransparentObject<'a> {
+    pub mesh: &'a GpuMesh,
+    pub model: Mat4,
+    /// 线性空间颜色，alpha 为不透明度
+    pub color: [f32; 4],
+}
+
+impl TransparentObject<'_> {
+    /// 物体在世界空间的位置（模型矩阵的平移）
+    pub fn position(&self) -> Vec3 {
+        self.model.w_axis.truncate()
+    }
+}
+
+/// 按到相机的距离从远到近排序，返回绘制顺序的索引
+///
+/// 距离相同的物体保持原有顺序，避免闪烁。
+pub fn back_to_front_order(positions: &[Vec3], camera_pos: Vec3) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|&a, &b| {
+        let da = positions[a].distance_squared(camera_pos);
+        let db = positions[b].distance_squared(camera_pos);
+        db.total_cmp(&da)
+    });
+    order
+}
+
+/// 取最多 `max` 个离相机最近的物体，仍按从远到近返回绘制顺序
+///
+/// 超出上限时丢弃最远的物体。
+pub fn nearest_back_to_front_order(positions: &[Vec3], camera_pos: Vec3, max: usize) -> Vec<usize> {
+    let order = back_to_front_order(positions, camera_pos);
+    let skip = order.len().saturating_sub(max);
+    order.into_iter().skip(skip).collect()
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct TransparentCameraUniform {
+    view_proj: [[f32; 4]; 4],
+    camera_pos: [f32; 3],
+    light_count: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct TransparentInstance {
+    model: [[f32; 4]; 4],
+    color: [f32; 4],
+}
+
+const TRANSPARENT_INSTANCE_ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+    4 => Float32x4, 5 => Float32x4, 6 => Float32x4, 7 => Float32x4, 8 => Float32x4
+];
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuPointLight {
+    position: [f32; 3],
+    _pad1: f32,
+    color: [f32; 3],
+    intensity: f32,
+    radius: f32,
+    _pad2: [f32; 3],
+}
+
+/// 光照阶段与透明阶段共用的CSM方向光参数，对应 `shader_deferred_common.wgsl` 中的 `CsmUniforms`
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct DeferredCsmUniform {
+    light_view_proj: [[[f32; 4]; 4]; 4],
+    cascade_distances: [f32; 4],
+    light_direction: [f32; 3],
+    _pad: f32,
+}
+
+impl DeferredCsmUniform {
+    /// 调用 `update_shadows` 之前使用：所有点都投影到阴影贴图深度范围之外，不产生阴影
+    fn unshadowed(light_direction: Vec3) -> Self {
+        let outside = Mat4::from_cols(
+            Vec4::ZERO,
+            Vec4::ZERO,
+            Vec4::ZERO,
+            Vec4::new(0.0, 0.0, 2.0, 1.0),
+        );
+        Self {
+            light_view_proj: [outside.to_cols_array_2d(); 4],
+            cascade_distances: [f32::MAX; 4],
+            light_direction: light_direction.to_array(),
+            _pad: 0.0,
+        }
+    }
+
+    fn new(csm: &CascadedShadowMap, light_direction: Vec3) -> Self {
+        let mut light_view_proj = [Mat4::IDENTITY.to_cols_array_2d(); 4];
+        for (dst, src) in light_view_proj
+            .iter_mut()
+            .zip(&csm.light_view_proj_matrices)
+        {
+            *dst = src.to_cols_array_2d();
+        }
+        let mut cascade_distances = [f32::MAX; 4];
+        for (dst, src) in cascade_distances.iter_mut().zip(&csm.cascade_distances) {
+            *dst = *src;
+        }
+        Self {
+            light_view_proj,
+            cascade_distances,
+            light_direction: light_direction.normalize_or_zero().to_array(),
+            _pad: 0.0,
+        }
+    }
+}
+
+/// 延迟光照着色器源码，与透明阶段共用 `shader_deferred_common.wgsl`
+const DEFERRED_LIGHTING_SHADER: &str = concat!(
+    include_str!("shader_deferred_common.wgsl"),
+    include_str!("shader_deferred_lighting.wgsl")
+);
+
+/// 前向透明着色器源码
+const DEFERRED_TRANSPARENT_SHADER: &str = concat!(
+    include_str!("shader_deferred_common.wgsl"),
+    include_str!("shader_deferred_transparent.wgsl")
+);
+
 /// G-Buffer纹理
 pub struct GBuffer {
     /// 位置 + 深度 (RGB = 世界坐标, A = 深度)
@@ -163,7 +299,22 @@ pub struct DeferredRenderer {
     pub geometry_pipeline: wgpu::RenderPipeline,
     pub lighting_pipeline: wgpu::RenderPipeline,
     pub gbuffer_bind_group_layout: wgpu::BindGroupLayout,
+    /// 阴影贴图绑定组布局 (group 1)，`CascadedShadowMap` 需使用该布局创建
+    pub shadow_bind_group_layout: wgpu::BindGroupLayout,
+    /// CSM参数绑定组 (group 2)，由 `update_shadows` 写入
+    pub csm_bind_group: wgpu::BindGroup,
+    csm_uniform_buffer: wgpu::Buffer,
     pub fullscreen_vertex_buffer: wgpu::Buffer,
+    /// 前向透明阶段管线 (读取G-Buffer深度,混合到光照结果上)
+    pub transparent_pipeline: wgpu::RenderPipeline,
+    /// 分离顶点流（`VertexLayout::Separate`）网格使用的透明管线
+    pub transparent_pipeline_separate: wgpu::RenderPipeline,
+    transparent_bind_group: wgpu::BindGroup,
+    transparent_camera_buffer: wgpu::Buffer,
+    transparent_lights_buffer: wgpu::Buffer,
+    transparent_instance_buffer: wgpu::Buffer,
+    view_proj: Mat4,
+    camera_pos: Vec3,
 }
 
 impl DeferredRenderer {
@@ -229,7 +380,7 @@ impl DeferredRenderer {
         // 创建光照阶段着色器
         let lighting_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Deferred Lighting Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader_deferred_lighting.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(DEFERRED_LIGHTING_SHADER.into()),
         });
 
         // 创建几何阶段管线 (写入G-Buffer)
@@ -292,13 +443,45 @@ impl DeferredRenderer {
             multiview: None,
         });
 
+        // 创建CSM绑定组布局 (光照阶段与透明阶段共用)
+        let shadow_bind_group_layout = CascadedShadowMap::create_bind_group_layout(device);
+        let csm_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Deferred CSM Uniform BGL"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+        let csm_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Deferred CSM Uniform Buffer"),
+            contents: bytemuck::bytes_of(&DeferredCsmUniform::unshadowed(Vec3::Y)),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let csm_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Deferred CSM Uniform Bind Group"),
+            layout: &csm_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: csm_uniform_buffer.as_entire_binding(),
+            }],
+        });
+
         // 创建光照阶段管线 (读取G-Buffer,输出到屏幕)
-        // 注意: 这里假设CSM绑定组布局已经在其他地方定义
-        // 实际使用时需要传入CSM绑定组布局
         let lighting_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Deferred Lighting Pipeline Layout"),
-                bind_group_layouts: &[&gbuffer_bind_group_layout],
+                bind_group_layouts: &[
+                    &gbuffer_bind_group_layout,
+                    &shadow_bind_group_layout,
+                    &csm_bind_group_layout,
+                ],
                 push_constant_ranges: &[],
             });
 
@@ -351,12 +534,278 @@ impl DeferredRenderer {
                 usage: wgpu::BufferUsages::VERTEX,
             });
 
+        // 创建前向透明阶段资源
+        let transparent_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Deferred Transparent Shader"),
+            source: wgpu::ShaderSource::Wgsl(DEFERRED_TRANSPARENT_SHADER.into()),
+        });
+
+        let transparent_camera_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Transparent Camera Buffer"),
+            size: std::mem::size_of::<TransparentCameraUniform>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let transparent_lights_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Transparent Lights Buffer"),
+            size: (MAX_TRANSPARENT_LIGHTS * std::mem::size_of::<GpuPointLight>())
+                as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let transparent_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Transparent Instance Buffer"),
+            size: (MAX_TRANSPARENT_OBJECTS * std::mem::size_of::<TransparentInstance>())
+                as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let transparent_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Transparent BGL"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+        let transparent_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Transparent Bind Group"),
+            layout: &transparent_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: transparent_camera_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: transparent_lights_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let transparent_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Deferred Transparent Pipeline Layout"),
+                bind_group_layouts: &[
+                    &transparent_bind_group_layout,
+                    &shadow_bind_group_layout,
+                    &csm_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
+        // 两种顶点布局共用同一着色器，实例缓冲区紧随顶点流之后
+        let create_transparent_pipeline = |label: &str, layout: VertexLayout| {
+            let mut buffers = layout.buffer_layouts();
+            buffers.push(wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<TransparentInstance>() as u64,
+                step_mode: wgpu::VertexStepMode::Instance,
+                attributes: &TRANSPARENT_INSTANCE_ATTRIBUTES,
+            });
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&transparent_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &transparent_shader,
+                    entry_point: "vs_main",
+                    buffers: &buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &transparent_shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: surface_format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                // 只做深度测试，不写深度，透明物体之间依靠排序
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: wgpu::TextureFormat::Depth32Float,
+                    depth_write_enabled: false,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let transparent_pipeline =
+            create_transparent_pipeline("Deferred Transparent Pipeline", VertexLayout::Interleaved);
+        let transparent_pipeline_separate = create_transparent_pipeline(
+            "Deferred Transparent Separate Streams Pipeline",
+            VertexLayout::Separate,
+        );
+
         Self {
             gbuffer,
             geometry_pipeline,
             lighting_pipeline,
             gbuffer_bind_group_layout,
+            shadow_bind_group_layout,
+            csm_bind_group,
+            csm_uniform_buffer,
             fullscreen_vertex_buffer,
+            transparent_pipeline,
+            transparent_pipeline_separate,
+            transparent_bind_group,
+            transparent_camera_buffer,
+            transparent_lights_buffer,
+            transparent_instance_buffer,
+            view_proj: Mat4::IDENTITY,
+            camera_pos: Vec3::ZERO,
+        }
+    }
+
+    /// 设置透明阶段使用的相机
+    pub fn set_camera(&mut self, view_proj: Mat4, camera_pos: Vec3) {
+        self.view_proj = view_proj;
+        self.camera_pos = camera_pos;
+    }
+
+    /// 写入光照阶段与透明阶段共用的CSM方向光参数
+    ///
+    /// `light_direction` 指向光源，应在 `CascadedShadowMap::update_cascades` 之后调用。
+    pub fn update_shadows(
+        &self,
+        queue: &wgpu::Queue,
+        csm: &CascadedShadowMap,
+        light_direction: Vec3,
+    ) {
+        queue.write_buffer(
+            &self.csm_uniform_buffer,
+            0,
+            bytemuck::bytes_of(&DeferredCsmUniform::new(csm, light_direction)),
+        );
+    }
+
+    /// 前向透明阶段，应在延迟光照阶段之后调用
+    ///
+    /// 透明物体按到相机的距离从后往前绘制，与光照阶段使用同一Cook-Torrance BRDF、
+    /// CSM方向光及阴影，并叠加传入的点光源。`shadows` 需使用 `shadow_bind_group_layout` 创建。
+    /// 深度测试读取G-Buffer深度但不写入，使透明物体被不透明几何正确遮挡。
+    /// 超出 `MAX_TRANSPARENT_OBJECTS` 时只绘制最近的物体，最远的物体被丢弃。
+    pub fn render_transparent(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        shadows: &CascadedShadowMap,
+        transparents: &[TransparentObject],
+        lights: &[PointLight3D],
+    ) {
+        let positions: Vec<Vec3> = transparents.iter().map(|t| t.position()).collect();
+        let order =
+            nearest_back_to_front_order(&positions, self.camera_pos, MAX_TRANSPARENT_OBJECTS);
+        if order.is_empty() {
+            return;
+        }
+
+        let gpu_lights: Vec<GpuPointLight> = lights
+            .iter()
+            .take(MAX_TRANSPARENT_LIGHTS)
+            .map(|light| GpuPointLight {
+                position: light.position.to_array(),
+                _pad1: 0.0,
+                color: light.color.to_array(),
+                intensity: light.intensity,
+                radius: light.radius,
+                _pad2: [0.0; 3],
+            })
+            .collect();
+        if !gpu_lights.is_empty() {
+            queue.write_buffer(
+                &self.transparent_lights_buffer,
+                0,
+                bytemuck::cast_slice(&gpu_lights),
+            );
+        }
+        queue.write_buffer(
+            &self.transparent_camera_buffer,
+            0,
+            bytemuck::bytes_of(&TransparentCameraUniform {
+                view_proj: self.view_proj.to_cols_array_2d(),
+                camera_pos: self.camera_pos.to_array(),
+                light_count: gpu_lights.len() as u32,
+            }),
+        );
+
+        let instances: Vec<TransparentInstance> = order
+            .iter()
+            .map(|&i| TransparentInstance {
+                model: transparents[i].model.to_cols_array_2d(),
+                color: transparents[i].color,
+            })
+            .collect();
+        queue.write_buffer(
+            &self.transparent_instance_buffer,
+            0,
+            bytemuck::cast_slice(&instances),
+        );
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Deferred Transparent Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.gbuffer.depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_bind_group(0, &self.transparent_bind_group, &[]);
+        pass.set_bind_group(1, &shadows.bind_group, &[]);
+        pass.set_bind_group(2, &self.csm_bind_group, &[]);
+        for (slot, &i) in order.iter().enumerate() {
+            let mesh = transparents[i].mesh;
+            let slot = slot as u32;
+            pass.set_pipeline(match mesh.layout {
+                VertexLayout::Interleaved => &self.transparent_pipeline,
+                VertexLayout::Separate => &self.transparent_pipeline_separate,
+            });
+            let instance_slot = mesh.set_vertex_buffers(&mut pass, 0);
+            pass.set_vertex_buffer(instance_slot, self.transparent_instance_buffer.slice(..));
+            pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            pass.draw_indexed(0..mesh.index_count, 0, slot..slot + 1);
         }
     }
 
@@ -365,3 +814,188 @@ impl DeferredRenderer {
             .resize(device, width, height, &self.gbuffer_bind_group_layout);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::csm::CsmConfig;
+
+    #[test]
+    fn test_transparent_order_back_to_front() {
+        let camera_pos = Vec3::new(0.0, 0.0, 10.0);
+        let positions = [
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::new(0.0, 0.0, -20.0),
+            Vec3::new(3.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 9.0),
+        ];
+        let order = back_to_front_order(&positions, camera_pos);
+        assert_eq!(order, [1, 2, 0, 3]);
+
+        let distances: Vec<f32> = order
+            .iter()
+            .map(|&i| positions[i].distance(camera_pos))
+            .collect();
+        assert!(distances.windows(2).all(|w| w[0] >= w[1]));
+
+        // 距离相同时保持提交顺序
+        let tied = [Vec3::X, Vec3::NEG_X, Vec3::Y];
+        assert_eq!(back_to_front_order(&tied, Vec3::ZERO), [0, 1, 2]);
+
+        // 超出上限时丢弃最远的物体，剩余物体仍从后往前
+        assert_eq!(
+            nearest_back_to_front_order(&positions, camera_pos, 2),
+            [0, 3]
+        );
+        assert_eq!(
+            nearest_back_to_front_order(&positions, camera_pos, 8),
+            [1, 2, 0, 3]
+        );
+    }
+
+    /// 在清空的G-Buffer深度上绘制一个朝向相机的透明四边形并返回中心像素
+    fn render_transparent_center_pixel(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        layout: VertexLayout,
+    ) -> [u8; 4] {
+        const SIZE: u32 = 64;
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut renderer = DeferredRenderer::new(device, SIZE, SIZE, format);
+        let shadows = CascadedShadowMap::new(
+            device,
+            CsmConfig {
+                shadow_map_size: 16,
+                ..Default::default()
+            },
+            &renderer.shadow_bind_group_layout,
+        );
+        let camera_pos = Vec3::new(0.0, 0.0, 3.0);
+        renderer.set_camera(
+            Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 1.0, 0.1, 10.0)
+                * Mat4::look_at_rh(camera_pos, Vec3::ZERO, Vec3::Y),
+            camera_pos,
+        );
+
+        let vertex = |x: f32, y: f32| crate::render::mesh::Vertex3D {
+            pos: [x, y, 0.0],
+            normal: [0.0, 0.0, 1.0],
+            uv: [0.5, 0.5],
+            tangent: [1.0, 0.0, 0.0, 1.0],
+        };
+        let mesh = GpuMesh::with_layout(
+            device,
+            &[
+                vertex(-1.0, -1.0),
+                vertex(1.0, -1.0),
+                vertex(1.0, 1.0),
+                vertex(-1.0, 1.0),
+            ],
+            &[0, 1, 2, 0, 2, 3],
+            layout,
+        );
+        let light = PointLight3D {
+            position: Vec3::new(0.0, 0.0, 2.0),
+            intensity: 4.0,
+            radius: 10.0,
+            ..Default::default()
+        };
+
+        let color = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Test Color"),
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Test Clear"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &renderer.gbuffer.depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        renderer.render_transparent(
+            queue,
+            &mut encoder,
+            &color_view,
+            &shadows,
+            &[TransparentObject {
+                mesh: &mesh,
+                model: Mat4::IDENTITY,
+                color: [1.0, 0.5, 0.25, 1.0],
+            }],
+            &[light],
+        );
+
+        let bytes_per_row = SIZE * 4;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Readback"),
+            size: (bytes_per_row * SIZE) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            color.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            color.size(),
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let center = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+        [
+            data[center],
+            data[center + 1],
+            data[center + 2],
+            data[center + 3],
+        ]
+    }
+
+    #[test]
+    fn test_transparent_separate_layout_matches_interleaved() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let interleaved =
+            render_transparent_center_pixel(&device, &queue, VertexLayout::Interleaved);
+        let separate = render_transparent_center_pixel(&device, &queue, VertexLayout::Separate);
+        assert!(interleaved[0] > 0, "{:?}", interleaved);
+        assert_eq!(separate, interleaved);
+    }
+}
@@ -11,8 +11,26 @@
 
 use crate::render::gpu_driven::culling::GpuInstance;
 use crate::render::gpu_driven::indirect::{DrawIndexedIndirectArgs, IndirectDrawBuffer, IndirectDrawError};
+use wgpu::util::DeviceExt;
 use wgpu::{BindGroup, BindGroupLayout, Buffer, ComputePipeline, Device, PipelineLayout, ShaderModule};
 
+/// 多材质绘制批次
+///
+/// 一个批次对应一条间接绘制命令，批次内每个实例在材质索引缓冲区中各占一项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaterialBatch {
+    /// 索引数
+    pub index_count: u32,
+    /// 实例数
+    pub instance_count: u32,
+    /// 第一个索引
+    pub first_index: u32,
+    /// 基础顶点
+    pub base_vertex: i32,
+    /// 材质/纹理数组中的索引
+    pub material_index: u32,
+}
+
 /// GPU命令生成器
 ///
 /// 在GPU上生成间接绘制命令，从可见实例生成绘制参数。
@@ -211,6 +229,79 @@ impl GpuCommandGenerator {
         Ok(())
     }
 
+    /// 从多材质批次构建绘制命令和逐实例的材质索引
+    ///
+    /// 第 i 条命令的 `first_instance` 为前面所有批次实例数之和。WGSL 没有绘制索引，
+    /// 着色器只能拿到包含 `first_instance` 的 `instance_index`，因此材质索引按实例展开：
+    /// `material_indices[instance_index]` 即该实例所属批次的材质。
+    pub fn build_multi_material(
+        batches: &[MaterialBatch],
+    ) -> (Vec<DrawIndexedIndirectArgs>, Vec<u32>) {
+        let mut first_instance = 0u32;
+        let mut commands = Vec::with_capacity(batches.len());
+        let total_instances = batches.iter().map(|b| b.instance_count as usize).sum();
+        let mut material_indices = Vec::with_capacity(total_instances);
+
+        for batch in batches {
+            commands.push(DrawIndexedIndirectArgs {
+                index_count: batch.index_count,
+                instance_count: batch.instance_count,
+                first_index: batch.first_index,
+                base_vertex: batch.base_vertex,
+                first_instance,
+            });
+            material_indices.extend(std::iter::repeat_n(
+                batch.material_index,
+                batch.instance_count as usize,
+            ));
+            first_instance += batch.instance_count;
+        }
+
+        (commands, material_indices)
+    }
+
+    /// 为多材质批次生成间接绘制缓冲区和材质索引缓冲区
+    ///
+    /// 可用于一次 multi-draw 绘制多个材质。顶点着色器以 `instance_index` 读取材质索引缓冲区，
+    /// 间接绘制中非零 `first_instance` 需要设备开启 `Features::INDIRECT_FIRST_INSTANCE`。
+    ///
+    /// # 返回
+    ///
+    /// 返回 `(indirect_buffer, material_index_buffer)`。
+    pub fn generate_multi_material(device: &Device, batches: &[MaterialBatch]) -> (Buffer, Buffer) {
+        let (commands, material_indices) = Self::build_multi_material(batches);
+
+        // 空批次时仍然创建最小缓冲区，避免零大小绑定
+        let commands = if commands.is_empty() {
+            vec![DrawIndexedIndirectArgs::default()]
+        } else {
+            commands
+        };
+        let material_indices = if material_indices.is_empty() {
+            vec![0]
+        } else {
+            material_indices
+        };
+
+        let indirect_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Multi-Material Indirect Buffer"),
+            contents: bytemuck::cast_slice(&commands),
+            usage: wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+        });
+        let material_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Multi-Material Index Buffer"),
+            contents: bytemuck::cast_slice(&material_indices),
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+        });
+
+        (indirect_buffer, material_index_buffer)
+    }
+
     /// 获取工作组大小
     ///
     /// # 返回
@@ -295,5 +386,226 @@ mod tests {
         // assert_eq!(generator.max_instances(), 1000);
         // assert_eq!(generator.workgroup_size(), 64);
     }
+
+    fn read_u32s(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &Buffer) -> Vec<u32> {
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Readback"),
+            size: buffer.size(),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &readback, 0, buffer.size());
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let values = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback.unmap();
+        values
+    }
+
+    #[test]
+    fn test_multi_material_commands() {
+        let batches = [
+            MaterialBatch {
+                index_count: 36,
+                instance_count: 3,
+                first_index: 0,
+                base_vertex: 0,
+                material_index: 4,
+            },
+            MaterialBatch {
+                index_count: 6,
+                instance_count: 2,
+                first_index: 36,
+                base_vertex: 24,
+                material_index: 9,
+            },
+        ];
+
+        let (commands, material_indices) = GpuCommandGenerator::build_multi_material(&batches);
+        assert_eq!(material_indices, vec![4, 4, 4, 9, 9]);
+        assert_eq!(commands[0].first_instance, 0);
+        assert_eq!(commands[1].first_instance, 3);
+        assert_eq!(commands[1].index_count, 6);
+        assert_eq!(commands[1].base_vertex, 24);
+
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+        let (indirect_buffer, material_index_buffer) =
+            GpuCommandGenerator::generate_multi_material(&device, &batches);
+        assert_eq!(
+            read_u32s(&device, &queue, &material_index_buffer),
+            vec![4, 4, 4, 9, 9]
+        );
+
+        // 每条命令 5 个 u32: index_count, instance_count, first_index, base_vertex, first_instance
+        assert_eq!(
+            read_u32s(&device, &queue, &indirect_buffer),
+            vec![36, 3, 0, 0, 0, 6, 2, 36, 24, 3]
+        );
+    }
+
+    /// 每个实例绘制到 R32Uint 目标的一列，片元输出 `material_indices[instance_index]`
+    const MATERIAL_LOOKUP_SHADER: &str = r#"
+@group(0) @binding(0) var<storage, read> material_indices: array<u32>;
+@group(0) @binding(1) var<uniform> column_count: vec4<u32>;
+
+struct VsOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) material: u32,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) v: u32, @builtin(instance_index) i: u32) -> VsOut {
+    let width = f32(column_count.x);
+    let column = f32(i + (v & 1u));
+    var out: VsOut;
+    out.position = vec4<f32>(column / width * 2.0 - 1.0, select(-1.0, 1.0, (v & 2u) != 0u), 0.0, 1.0);
+    out.material = material_indices[i];
+    return out;
+}
+
+@fragment
+fn fs_main(in: VsOut) -> @location(0) u32 {
+    return in.material;
 }
+"#;
+
+    #[test]
+    fn test_multi_material_draw_reads_per_instance_material() {
+        let Some((device, queue)) = crate::test_utils::headless_device_with_features(
+            wgpu::Features::INDIRECT_FIRST_INSTANCE,
+        ) else {
+            return;
+        };
+
+        // 两个批次共用一个四边形，实例数均大于 1
+        let quad = |instance_count, material_index| MaterialBatch {
+            index_count: 6,
+            instance_count,
+            first_index: 0,
+            base_vertex: 0,
+            material_index,
+        };
+        let batches = [quad(3, 4), quad(2, 9)];
+        let columns: u32 = batches.iter().map(|b| b.instance_count).sum();
+        let (indirect_buffer, material_index_buffer) =
+            GpuCommandGenerator::generate_multi_material(&device, &batches);
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Quad Index Buffer"),
+            contents: bytemuck::cast_slice(&[0u32, 1, 2, 2, 1, 3]),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let columns_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Column Count"),
+            contents: bytemuck::cast_slice(&[columns, 0, 0, 0]),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Material Lookup Target"),
+            size: wgpu::Extent3d {
+                width: columns,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R32Uint,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Material Lookup Shader"),
+            source: wgpu::ShaderSource::Wgsl(MATERIAL_LOOKUP_SHADER.into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Material Lookup Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::TextureFormat::R32Uint.into())],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Material Lookup Bind Group"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: material_index_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: columns_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let pixels_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Material Lookup Pixels"),
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Material Lookup Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            let stride = std::mem::size_of::<DrawIndexedIndirectArgs>() as u64;
+            for i in 0..batches.len() as u64 {
+                pass.draw_indexed_indirect(&indirect_buffer, i * stride);
+            }
+        }
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &pixels_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                    rows_per_image: None,
+                },
+            },
+            target.size(),
+        );
+        queue.submit(Some(encoder.finish()));
 
+        let pixels = read_u32s(&device, &queue, &pixels_buffer);
+        assert_eq!(&pixels[..columns as usize], &[4, 4, 4, 9, 9]);
+    }
+}
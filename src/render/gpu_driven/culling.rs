@@ -36,8 +36,16 @@
 //! );
 //! ```
 
+use std::ops::Range;
+
 use crate::impl_default;
 
+/// 支持的最大 LOD 级数
+pub const MAX_LOD_LEVELS: usize = 8;
+
+/// LOD 状态缓冲区中的计数器数量（前半为各级可见数量，后半为各级写入游标）
+const LOD_STATE_LEN: usize = MAX_LOD_LEVELS * 2;
+
 /// 剔除 Uniform 数据
 #[repr(C)]
 #[derive(Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
@@ -142,6 +150,20 @@ impl CullingUniforms {
     }
 }
 
+/// LOD 选择 Uniform 数据
+///
+/// `distances[i]` 为 LOD `i` 的最大距离，超出最后一级距离的实例使用最后一级。
+#[repr(C)]
+#[derive(Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LodUniforms {
+    /// 相机世界坐标
+    pub camera_position: [f32; 3],
+    /// 有效的LOD级数（0或1表示全部使用LOD 0）
+    pub lod_count: u32,
+    /// 各级LOD的切换距离
+    pub distances: [f32; MAX_LOD_LEVELS],
+}
+
 /// GPU 剔除器
 ///
 /// 管理GPU端视锥剔除的计算着色器和资源。
@@ -160,6 +182,8 @@ impl CullingUniforms {
 pub struct GpuCuller {
     /// 剔除计算管线
     pipeline: wgpu::ComputePipeline,
+    /// LOD 计数管线（在剔除前统计各级LOD的可见数量）
+    lod_count_pipeline: wgpu::ComputePipeline,
     /// 绑定组布局
     bind_group_layout: wgpu::BindGroupLayout,
     /// Uniform 缓冲区
@@ -168,6 +192,18 @@ pub struct GpuCuller {
     workgroup_size: u32,
     /// 最大实例数
     max_instances: u32,
+    /// LOD 切换距离
+    lod_distances: Vec<f32>,
+    /// 相机位置（用于LOD距离计算）
+    camera_position: [f32; 3],
+    /// LOD Uniform 缓冲区
+    lod_uniform_buffer: wgpu::Buffer,
+    /// LOD 状态缓冲区（各级可见数量和写入游标）
+    lod_state_buffer: wgpu::Buffer,
+    /// 每个可见实例选择的LOD索引（与输出实例一一对应）
+    lod_output_buffer: wgpu::Buffer,
+    /// 未提供间接绘制缓冲区时绑定的占位缓冲区
+    dummy_indirect_buffer: wgpu::Buffer,
 }
 
 impl GpuCuller {
@@ -245,6 +281,39 @@ impl GpuCuller {
                     },
                     count: None,
                 },
+                // LOD Uniforms
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // LOD 状态（计数和游标）
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // 输出LOD索引
+                wgpu::BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -281,6 +350,14 @@ impl GpuCuller {
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         });
 
+        let lod_count_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Culling LOD Count Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "lod_count_main",
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        });
+
         // 创建 Uniform 缓冲区
         let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Culling Uniforms"),
@@ -289,12 +366,118 @@ impl GpuCuller {
             mapped_at_creation: false,
         });
 
+        let lod_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Culling LOD Uniforms"),
+            size: std::mem::size_of::<LodUniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let lod_state_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Culling LOD State"),
+            size: (LOD_STATE_LEN * std::mem::size_of::<u32>()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_DST
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let lod_output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Culling LOD Output"),
+            size: (max_instances.max(1) as usize * std::mem::size_of::<u32>())
+                as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let dummy_indirect_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Culling Dummy Indirect"),
+            size: std::mem::size_of::<wgpu::util::DrawIndexedIndirectArgs>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
         Self {
             pipeline,
+            lod_count_pipeline,
             bind_group_layout,
             uniform_buffer,
             workgroup_size,
             max_instances,
+            lod_distances: Vec::new(),
+            camera_position: [0.0; 3],
+            lod_uniform_buffer,
+            lod_state_buffer,
+            lod_output_buffer,
+            dummy_indirect_buffer,
+        }
+    }
+
+    /// 设置LOD切换距离
+    ///
+    /// `distances[i]` 为 LOD `i` 的最大距离（应递增），距离超出最后一项的实例
+    /// 使用最后一级LOD。传入空切片或单个距离时禁用LOD选择，所有实例使用LOD 0。
+    /// 超过 [`MAX_LOD_LEVELS`] 的部分会被忽略。
+    pub fn set_lod_distances(&mut self, distances: &[f32]) {
+        if distances.len() > MAX_LOD_LEVELS {
+            tracing::warn!(
+                target: "render",
+                "GPU culling supports at most {} LOD levels, ignoring {} extra distances",
+                MAX_LOD_LEVELS,
+                distances.len() - MAX_LOD_LEVELS
+            );
+        }
+        self.lod_distances = distances.iter().take(MAX_LOD_LEVELS).copied().collect();
+    }
+
+    /// 获取LOD切换距离
+    pub fn lod_distances(&self) -> &[f32] {
+        &self.lod_distances
+    }
+
+    /// 设置用于LOD距离计算的相机位置
+    pub fn set_camera_position(&mut self, position: [f32; 3]) {
+        self.camera_position = position;
+    }
+
+    /// 每个可见实例的LOD索引缓冲区
+    ///
+    /// 第 `i` 项对应输出实例缓冲区的第 `i` 个实例。
+    pub fn lod_output_buffer(&self) -> &wgpu::Buffer {
+        &self.lod_output_buffer
+    }
+
+    /// LOD 状态缓冲区
+    ///
+    /// 前 [`MAX_LOD_LEVELS`] 个 `u32` 为各级LOD的可见实例数量，
+    /// 可传入 [`GpuCuller::lod_ranges`] 得到各级LOD在输出缓冲区中的范围。
+    pub fn lod_state_buffer(&self) -> &wgpu::Buffer {
+        &self.lod_state_buffer
+    }
+
+    /// 根据各级LOD的可见数量计算输出缓冲区中的连续范围
+    ///
+    /// 剔除输出按LOD分组，第 `i` 个范围内的实例均使用 LOD `i`，
+    /// 可直接用作多重绘制的实例范围。
+    pub fn lod_ranges(counts: &[u32]) -> Vec<Range<u32>> {
+        let mut start = 0;
+        counts
+            .iter()
+            .map(|&count| {
+                let range = start..start + count;
+                start += count;
+                range
+            })
+            .collect()
+    }
+
+    fn lod_uniforms(&self) -> LodUniforms {
+        let mut distances = [0.0; MAX_LOD_LEVELS];
+        distances[..self.lod_distances.len()].copy_from_slice(&self.lod_distances);
+        LodUniforms {
+            camera_position: self.camera_position,
+            lod_count: self.lod_distances.len() as u32,
+            distances,
         }
     }
 
@@ -386,6 +569,13 @@ impl GpuCuller {
             CullingUniforms::from_view_proj(view_proj, instance_count)
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+        let lod_uniforms = self.lod_uniforms();
+        queue.write_buffer(
+            &self.lod_uniform_buffer,
+            0,
+            bytemuck::bytes_of(&lod_uniforms),
+        );
+        encoder.clear_buffer(&self.lod_state_buffer, 0, None);
 
         // 创建绑定组条目
         let mut entries = vec![
@@ -407,13 +597,24 @@ impl GpuCuller {
             },
         ];
 
-        // 如果提供了间接绘制缓冲区，添加到绑定组
-        if let Some(indirect_buf) = indirect_buffer {
-            entries.push(wgpu::BindGroupEntry {
-                binding: 4,
-                resource: indirect_buf.as_entire_binding(),
-            });
-        }
+        // 如果提供了间接绘制缓冲区，添加到绑定组，否则绑定占位缓冲区
+        let indirect_buf = indirect_buffer.unwrap_or(&self.dummy_indirect_buffer);
+        entries.push(wgpu::BindGroupEntry {
+            binding: 4,
+            resource: indirect_buf.as_entire_binding(),
+        });
+        entries.push(wgpu::BindGroupEntry {
+            binding: 5,
+            resource: self.lod_uniform_buffer.as_entire_binding(),
+        });
+        entries.push(wgpu::BindGroupEntry {
+            binding: 6,
+            resource: self.lod_state_buffer.as_entire_binding(),
+        });
+        entries.push(wgpu::BindGroupEntry {
+            binding: 7,
+            resource: self.lod_output_buffer.as_entire_binding(),
+        });
 
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Culling BG"),
@@ -436,8 +637,13 @@ impl GpuCuller {
             timestamp_writes: None,
         });
 
-        cpass.set_pipeline(&self.pipeline);
         cpass.set_bind_group(0, &bind_group, &[]);
+        // 多级LOD时先统计各级数量，剔除阶段据此把输出按LOD分组
+        if lod_uniforms.lod_count > 1 {
+            cpass.set_pipeline(&self.lod_count_pipeline);
+            cpass.dispatch_workgroups(workgroup_count, 1, 1);
+        }
+        cpass.set_pipeline(&self.pipeline);
         cpass.dispatch_workgroups(workgroup_count, 1, 1);
     }
 
@@ -480,6 +686,7 @@ impl GpuCuller {
 /// - 早期退出优化
 /// - 减少内存访问
 /// - 支持在剔除的同时生成间接绘制命令（可选）
+/// - 按相机距离为可见实例选择LOD，并将输出按LOD分组
 const CULLING_SHADER: &str = r#"
 struct CullingUniforms {
     view_proj: mat4x4<f32>,
@@ -505,11 +712,20 @@ struct DrawIndexedIndirectArgs {
     first_instance: u32,
 };
 
+struct LodUniforms {
+    camera_position: vec3<f32>,
+    lod_count: u32,  // 有效LOD级数（<= 1 表示不做LOD选择）
+    distances: array<vec4<f32>, 2>,  // 各级LOD的切换距离（共8个）
+};
+
 @group(0) @binding(0) var<uniform> uniforms: CullingUniforms;
 @group(0) @binding(1) var<storage, read> input_instances: array<GpuInstance>;
 @group(0) @binding(2) var<storage, read_write> output_instances: array<GpuInstance>;
 @group(0) @binding(3) var<storage, read_write> counter: atomic<u32>;
 @group(0) @binding(4) var<storage, read_write> indirect_commands: array<DrawIndexedIndirectArgs>;  // 可选的间接绘制命令缓冲区
+@group(0) @binding(5) var<uniform> lod: LodUniforms;
+@group(0) @binding(6) var<storage, read_write> lod_state: array<atomic<u32>, 16>;  // [0, 8): 各级数量, [8, 16): 各级写入游标
+@group(0) @binding(7) var<storage, read_write> output_lods: array<u32>;
 
 // 优化的AABB与平面相交检测
 // 使用select函数减少分支，提高GPU执行效率
@@ -536,6 +752,44 @@ fn is_visible(aabb_min: vec3<f32>, aabb_max: vec3<f32>) -> bool {
     return true;
 }
 
+// 按AABB中心到相机的距离选择LOD
+fn select_lod(center: vec3<f32>) -> u32 {
+    if (lod.lod_count <= 1u) {
+        return 0u;
+    }
+    let dist = distance(center, lod.camera_position);
+    for (var i = 0u; i < lod.lod_count; i++) {
+        if (dist < lod.distances[i / 4u][i % 4u]) {
+            return i;
+        }
+    }
+    return lod.lod_count - 1u;
+}
+
+// 变换实例AABB到世界空间，返回 (min, max)
+fn world_aabb(instance: GpuInstance) -> array<vec3<f32>, 2> {
+    // 优化的AABB变换：只变换min和max点
+    let world_min = (instance.model * vec4<f32>(instance.aabb_min, 1.0)).xyz;
+    let world_max = (instance.model * vec4<f32>(instance.aabb_max, 1.0)).xyz;
+    
+    // 确保min < max（处理负缩放的情况）
+    return array<vec3<f32>, 2>(min(world_min, world_max), max(world_min, world_max));
+}
+
+// LOD计数阶段：统计各级LOD的可见实例数量
+@compute @workgroup_size(64)
+fn lod_count_main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let idx = global_id.x;
+    if (idx >= uniforms.instance_count) {
+        return;
+    }
+    
+    let aabb = world_aabb(input_instances[idx]);
+    if (is_visible(aabb[0], aabb[1])) {
+        atomicAdd(&lod_state[select_lod((aabb[0] + aabb[1]) * 0.5)], 1u);
+    }
+}
+
 @compute @workgroup_size(64)
 fn cull_main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let idx = global_id.x;
@@ -546,32 +800,31 @@ fn cull_main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
     
     let instance = input_instances[idx];
-    
-    // 优化的AABB变换：只变换min和max点
-    // 变换到世界空间
-    let world_min = (instance.model * vec4<f32>(instance.aabb_min, 1.0)).xyz;
-    let world_max = (instance.model * vec4<f32>(instance.aabb_max, 1.0)).xyz;
-    
-    // 确保min < max（处理负缩放的情况）
-    let aabb_min = min(world_min, world_max);
-    let aabb_max = max(world_min, world_max);
+    let aabb = world_aabb(instance);
     
     // 视锥剔除
-    if (is_visible(aabb_min, aabb_max)) {
-        // 原子增加计数器并获取输出索引
-        let output_idx = atomicAdd(&counter, 1u);
+    if (is_visible(aabb[0], aabb[1])) {
+        // 输出按LOD分组：前面各级LOD的数量之和为本级的起始位置
+        let lod_index = select_lod((aabb[0] + aabb[1]) * 0.5);
+        var lod_offset = 0u;
+        for (var i = 0u; i < lod_index; i++) {
+            lod_offset += atomicLoad(&lod_state[i]);
+        }
+        let output_idx = lod_offset + atomicAdd(&lod_state[8u + lod_index], 1u);
+        atomicAdd(&counter, 1u);
         output_instances[output_idx] = instance;
+        output_lods[output_idx] = lod_index;
         
         // 如果提供了间接绘制命令缓冲区，同时生成间接绘制命令
         // 这样可以完全避免CPU读取结果，实现完全GPU端剔除流程
         if (uniforms.index_count > 0u) {
-            indirect_commands[output_idx] = DrawIndexedIndirectArgs {
-                index_count: uniforms.index_count,
-                instance_count: 1u,              // 每个命令的实例数（单个实例）
-                first_index: 0u,                 // 第一个索引
-                base_vertex: 0i,                 // 基础顶点
-                first_instance: output_idx,      // 第一个实例（使用输出索引）
-            };
+            indirect_commands[output_idx] = DrawIndexedIndirectArgs(
+                uniforms.index_count,
+                1u,              // 每个命令的实例数（单个实例）
+                0u,              // 第一个索引
+                0i,              // 基础顶点
+                output_idx,      // 第一个实例（使用输出索引）
+            );
         }
     }
 }
@@ -605,3 +858,113 @@ impl GpuInstance {
         Self::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{Mat4, Vec3};
+    use wgpu::util::DeviceExt;
+
+    fn read_buffer<T: bytemuck::Pod>(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        count: usize,
+    ) -> Vec<T> {
+        let size = (count * std::mem::size_of::<T>()) as wgpu::BufferAddress;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Culling Test Readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &readback, 0, size);
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        data
+    }
+
+    #[test]
+    fn test_lod_selection_by_distance() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        // 近处 (z = -5) 与远处 (z = -50) 的实例交错排列
+        let depths = [-5.0, -50.0, -5.0, -50.0];
+        let instances: Vec<GpuInstance> = depths
+            .iter()
+            .enumerate()
+            .map(|(i, &z)| GpuInstance {
+                model: Mat4::from_translation(Vec3::new(0.0, 0.0, z)).to_cols_array_2d(),
+                instance_id: i as u32,
+                ..GpuInstance::default()
+            })
+            .collect();
+
+        let input = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Culling Test Input"),
+            contents: bytemuck::cast_slice(&instances),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        let output = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Culling Test Output"),
+            size: std::mem::size_of_val(instances.as_slice()) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let counter = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Culling Test Counter"),
+            contents: bytemuck::bytes_of(&0u32),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        });
+
+        let mut culler = GpuCuller::new(&device, 64, 64);
+        culler.set_lod_distances(&[10.0, 100.0]);
+        culler.set_camera_position([0.0, 0.0, 0.0]);
+
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let proj = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 1000.0);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        culler.cull(
+            &mut encoder,
+            &device,
+            &queue,
+            &input,
+            &output,
+            &counter,
+            (proj * view).to_cols_array_2d(),
+            instances.len() as u32,
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let visible: Vec<u32> = read_buffer(&device, &queue, &counter, 1);
+        assert_eq!(visible[0], 4);
+
+        let state: Vec<u32> =
+            read_buffer(&device, &queue, culler.lod_state_buffer(), MAX_LOD_LEVELS);
+        assert_eq!(&state[..2], &[2, 2]);
+        assert_eq!(GpuCuller::lod_ranges(&state[..2]), vec![0..2, 2..4]);
+
+        let lods: Vec<u32> = read_buffer(&device, &queue, culler.lod_output_buffer(), 4);
+        let culled: Vec<GpuInstance> = read_buffer(&device, &queue, &output, 4);
+        assert_eq!(lods, vec![0, 0, 1, 1]);
+        for (instance, lod) in culled.iter().zip(&lods) {
+            let expected = if depths[instance.instance_id as usize] > -10.0 {
+                0
+            } else {
+                1
+            };
+            assert_eq!(
+                *lod, expected,
+                "instance {} selected wrong LOD",
+                instance.instance_id
+            );
+        }
+    }
+}
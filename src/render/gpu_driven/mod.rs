@@ -48,13 +48,13 @@ pub mod indirect_manager;
 
 // 遮挡剔除集成
 use crate::impl_default;
-use crate::render::occlusion_culling::HierarchicalZCulling;
+use crate::render::occlusion_culling::{HierarchicalZCulling, OcclusionCullingConfig};
 
 pub use culling::{CullingUniforms, GpuCuller, GpuInstance};
 pub use culling_manager::GpuCullingManager;
 pub use indirect::{DrawIndirectArgs, IndirectDrawBuffer};
 pub use instance_pool::InstanceDataPool;
-pub use command_generator::GpuCommandGenerator;
+pub use command_generator::{GpuCommandGenerator, MaterialBatch};
 pub use indirect_manager::{GpuIndirectDrawConfig, GpuIndirectDrawManager};
 
 /// GPU Driven 渲染配置
@@ -69,6 +69,7 @@ pub use indirect_manager::{GpuIndirectDrawConfig, GpuIndirectDrawManager};
 /// let config = GpuDrivenConfig {
 ///     frustum_culling: true,
 ///     occlusion_culling: false,
+///     occlusion_two_pass: true,
 ///     lod_enabled: true,
 ///     max_instances: 65536,
 ///     workgroup_size: 64,
@@ -80,6 +81,8 @@ pub struct GpuDrivenConfig {
     pub frustum_culling: bool,
     /// 是否启用遮挡剔除
     pub occlusion_culling: bool,
+    /// 遮挡剔除是否使用两遍剔除（见 `OcclusionCullingConfig::two_pass`）
+    pub occlusion_two_pass: bool,
     /// 是否启用 LOD
     pub lod_enabled: bool,
     /// 最大实例数
@@ -91,6 +94,7 @@ pub struct GpuDrivenConfig {
 impl_default!(GpuDrivenConfig {
     frustum_culling: true,
     occlusion_culling: false,
+    occlusion_two_pass: true,
     lod_enabled: false,
     max_instances: 65536,
     workgroup_size: 64,
@@ -179,9 +183,12 @@ impl GpuDrivenRenderer {
 
         // 如果启用遮挡剔除，创建Hi-Z剔除器
         let occlusion_culler = if config.occlusion_culling {
-            Some(HierarchicalZCulling::new(
+            Some(HierarchicalZCulling::with_config(
                 config.max_instances as u32, // 使用最大实例数作为参考分辨率
                 config.max_instances as u32,
+                OcclusionCullingConfig {
+                    two_pass: config.occlusion_two_pass,
+                },
             ))
         } else {
             None
@@ -491,6 +498,11 @@ impl GpuDrivenRenderer {
     pub fn occlusion_culler(&self) -> Option<&HierarchicalZCulling> {
         self.occlusion_culler.as_ref()
     }
+
+    /// 获取可变遮挡剔除器（如果启用），用于执行 `cull_frame`
+    pub fn occlusion_culler_mut(&mut self) -> Option<&mut HierarchicalZCulling> {
+        self.occlusion_culler.as_mut()
+    }
 }
 
 #[cfg(test)]
@@ -502,6 +514,7 @@ mod tests {
         let config = GpuDrivenConfig::default();
         assert!(config.frustum_culling);
         assert!(!config.occlusion_culling);
+        assert!(config.occlusion_two_pass);
         assert_eq!(config.max_instances, 65536);
     }
 
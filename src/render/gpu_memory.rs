@@ -0,0 +1,252 @@
+//! GPU 显存统计
+//!
+//! wgpu 不提供显存查询接口，因此由资源管理器在创建缓冲区和纹理时登记估算大小。
+//! 每次登记返回 `GpuAllocation` 守卫，随资源一起保存，释放时自动扣除。
+//!
+//! 目前登记的资源：`WgpuRenderer` 的纹理、顶点/索引/实例/uniform/光源缓冲区、深度缓冲与离屏目标，
+//! `GpuMesh` 的顶点与索引缓冲区，`OffscreenTarget` 的颜色与深度纹理，瓦片地图 chunk 的实例缓冲区，
+//! 以及 Staging Buffer。
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use wgpu::util::DeviceExt;
+
+/// 显存统计快照
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GpuMemoryStats {
+    /// 已登记的显存字节数（估算值）
+    pub bytes_used: u64,
+    /// 存活的缓冲区数量
+    pub buffer_count: u32,
+    /// 存活的纹理数量
+    pub texture_count: u32,
+}
+
+/// 分配的资源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuResourceKind {
+    Buffer,
+    Texture,
+}
+
+/// 显存统计器
+#[derive(Debug, Default)]
+pub struct GpuMemoryTracker {
+    bytes_used: AtomicU64,
+    buffer_count: AtomicU32,
+    texture_count: AtomicU32,
+}
+
+static GLOBAL_TRACKER: GpuMemoryTracker = GpuMemoryTracker::new();
+
+impl GpuMemoryTracker {
+    pub const fn new() -> Self {
+        Self {
+            bytes_used: AtomicU64::new(0),
+            buffer_count: AtomicU32::new(0),
+            texture_count: AtomicU32::new(0),
+        }
+    }
+
+    /// 进程级统计器，引擎的资源管理器均登记到此处
+    pub fn global() -> &'static Self {
+        &GLOBAL_TRACKER
+    }
+
+    /// 登记一次分配，返回的守卫释放时扣除
+    pub fn track(&'static self, kind: GpuResourceKind, bytes: u64) -> GpuAllocation {
+        self.bytes_used.fetch_add(bytes, Ordering::Relaxed);
+        self.counter(kind).fetch_add(1, Ordering::Relaxed);
+        GpuAllocation {
+            tracker: self,
+            kind,
+            bytes,
+        }
+    }
+
+    /// 创建缓冲区并登记到此统计器
+    pub fn create_buffer(
+        &'static self,
+        device: &wgpu::Device,
+        desc: &wgpu::BufferDescriptor,
+    ) -> (wgpu::Buffer, GpuAllocation) {
+        let buffer = device.create_buffer(desc);
+        let allocation = self.track(GpuResourceKind::Buffer, desc.size);
+        (buffer, allocation)
+    }
+
+    /// 创建带初始数据的缓冲区并登记到此统计器
+    pub fn create_buffer_init(
+        &'static self,
+        device: &wgpu::Device,
+        desc: &wgpu::util::BufferInitDescriptor,
+    ) -> (wgpu::Buffer, GpuAllocation) {
+        let buffer = device.create_buffer_init(desc);
+        let allocation = self.track(GpuResourceKind::Buffer, buffer.size());
+        (buffer, allocation)
+    }
+
+    /// 创建纹理并登记到此统计器
+    pub fn create_texture(
+        &'static self,
+        device: &wgpu::Device,
+        desc: &wgpu::TextureDescriptor,
+    ) -> (wgpu::Texture, GpuAllocation) {
+        let texture = device.create_texture(desc);
+        let allocation = self.track(GpuResourceKind::Texture, texture_size_bytes(desc));
+        (texture, allocation)
+    }
+
+    /// 当前统计
+    pub fn stats(&self) -> GpuMemoryStats {
+        GpuMemoryStats {
+            bytes_used: self.bytes_used.load(Ordering::Relaxed),
+            buffer_count: self.buffer_count.load(Ordering::Relaxed),
+            texture_count: self.texture_count.load(Ordering::Relaxed),
+        }
+    }
+
+    fn counter(&self, kind: GpuResourceKind) -> &AtomicU32 {
+        match kind {
+            GpuResourceKind::Buffer => &self.buffer_count,
+            GpuResourceKind::Texture => &self.texture_count,
+        }
+    }
+}
+
+/// 单个已登记分配，释放时从统计中扣除
+#[derive(Debug)]
+pub struct GpuAllocation {
+    tracker: &'static GpuMemoryTracker,
+    kind: GpuResourceKind,
+    bytes: u64,
+}
+
+impl GpuAllocation {
+    pub fn kind(&self) -> GpuResourceKind {
+        self.kind
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+impl Drop for GpuAllocation {
+    fn drop(&mut self) {
+        self.tracker
+            .bytes_used
+            .fetch_sub(self.bytes, Ordering::Relaxed);
+        self.tracker
+            .counter(self.kind)
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 估算纹理占用的显存字节数，包含完整 mip 链和多重采样
+pub fn texture_size_bytes(desc: &wgpu::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    // 深度模板组合格式没有统一的块大小，按 4 字节估算
+    let block_bytes = desc.format.block_copy_size(None).unwrap_or(4) as u64;
+    let is_3d = desc.dimension == wgpu::TextureDimension::D3;
+
+    (0..desc.mip_level_count.max(1))
+        .map(|mip| {
+            let size = desc.size.mip_level_size(mip, desc.dimension);
+            let blocks_x = size.width.div_ceil(block_width) as u64;
+            let blocks_y = size.height.div_ceil(block_height) as u64;
+            let layers = if is_3d {
+                size.depth_or_array_layers
+            } else {
+                desc.size.depth_or_array_layers
+            } as u64;
+            blocks_x * blocks_y * layers * block_bytes
+        })
+        .sum::<u64>()
+        * desc.sample_count.max(1) as u64
+}
+
+/// 创建缓冲区并登记到全局统计
+pub fn create_buffer(
+    device: &wgpu::Device,
+    desc: &wgpu::BufferDescriptor,
+) -> (wgpu::Buffer, GpuAllocation) {
+    GpuMemoryTracker::global().create_buffer(device, desc)
+}
+
+/// 创建带初始数据的缓冲区并登记到全局统计
+pub fn create_buffer_init(
+    device: &wgpu::Device,
+    desc: &wgpu::util::BufferInitDescriptor,
+) -> (wgpu::Buffer, GpuAllocation) {
+    GpuMemoryTracker::global().create_buffer_init(device, desc)
+}
+
+/// 创建纹理并登记到全局统计
+pub fn create_texture(
+    device: &wgpu::Device,
+    desc: &wgpu::TextureDescriptor,
+) -> (wgpu::Texture, GpuAllocation) {
+    GpuMemoryTracker::global().create_texture(device, desc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocation_tracking() {
+        static TRACKER: GpuMemoryTracker = GpuMemoryTracker::new();
+
+        let buffer = TRACKER.track(GpuResourceKind::Buffer, 1024);
+        let texture = TRACKER.track(GpuResourceKind::Texture, 4096);
+        assert_eq!(
+            TRACKER.stats(),
+            GpuMemoryStats {
+                bytes_used: 5120,
+                buffer_count: 1,
+                texture_count: 1,
+            }
+        );
+
+        drop(buffer);
+        assert_eq!(TRACKER.stats().bytes_used, 4096);
+        assert_eq!(TRACKER.stats().buffer_count, 0);
+
+        drop(texture);
+        assert_eq!(TRACKER.stats(), GpuMemoryStats::default());
+    }
+
+    #[test]
+    fn test_texture_size_estimate() {
+        let desc = |format, mip_level_count, layers| wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: layers,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+
+        // 4x4 + 2x2 + 1x1，每像素 4 字节
+        assert_eq!(
+            texture_size_bytes(&desc(wgpu::TextureFormat::Rgba8Unorm, 3, 1)),
+            84
+        );
+        // 立方体贴图 6 层
+        assert_eq!(
+            texture_size_bytes(&desc(wgpu::TextureFormat::Rgba16Float, 1, 6)),
+            4 * 4 * 8 * 6
+        );
+        // BC1 每 4x4 块 8 字节
+        assert_eq!(
+            texture_size_bytes(&desc(wgpu::TextureFormat::Bc1RgbaUnorm, 1, 1)),
+            8
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use crate::ecs::{TileChunkConfig, TileSet, Viewport};
+use crate::render::tilemap::TileMapRenderMode;
 use crate::render::wgpu::Instance;
 use glam::Mat4;
+use std::collections::{BTreeSet, HashMap};
+use thiserror::Error;
 
 // ============================================================================
 // 声明式场景图 (Flutter-like Layer Tree)
@@ -310,6 +313,274 @@ pub enum RenderCommand {
 #[derive(Default, Clone)]
 pub struct RenderGraph {
     pub commands: Vec<RenderCommand>,
+    /// 资源声明，索引即 `ResourceHandle`
+    resources: Vec<GraphResource>,
+    /// 渲染通道节点（按声明顺序）
+    passes: Vec<PassNode>,
+}
+
+// ============================================================================
+// 渲染通道依赖
+// ============================================================================
+
+/// 渲染图资源句柄
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceHandle(pub u32);
+
+/// 渲染图资源
+#[derive(Clone, Debug)]
+struct GraphResource {
+    name: String,
+    /// 外部导入的资源（如交换链）在图外已有内容，可直接读取
+    imported: bool,
+}
+
+/// 渲染通道节点，声明读取和写入的资源
+#[derive(Clone, Debug)]
+pub struct PassNode {
+    pub name: String,
+    pub reads: Vec<ResourceHandle>,
+    pub writes: Vec<ResourceHandle>,
+}
+
+/// 资源使用状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceState {
+    /// 尚未使用
+    Undefined,
+    /// 作为着色器输入读取
+    ShaderRead,
+    /// 作为渲染目标/存储写入
+    RenderTarget,
+}
+
+/// 通道执行前需要的资源状态转换
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResourceBarrier {
+    pub resource: ResourceHandle,
+    pub before: ResourceState,
+    pub after: ResourceState,
+}
+
+/// 渲染图校验错误
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RenderGraphError {
+    #[error("Pass '{pass}' references unknown resource {resource:?}")]
+    UnresolvedResource {
+        pass: String,
+        resource: ResourceHandle,
+    },
+    #[error("Pass '{pass}' reads '{resource}' which is never written")]
+    ReadBeforeWrite { pass: String, resource: String },
+    #[error("Render graph has a dependency cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// 排序后的渲染图
+#[derive(Clone, Debug, Default)]
+pub struct CompiledRenderGraph {
+    /// 通道执行顺序（`RenderGraph` 中的通道索引）
+    pub order: Vec<usize>,
+    /// 与 `order` 对应，每个通道执行前插入的状态转换
+    pub barriers: Vec<Vec<ResourceBarrier>>,
+}
+
+impl RenderGraph {
+    /// 声明由图内通道生成的资源
+    pub fn create_resource(&mut self, name: impl Into<String>) -> ResourceHandle {
+        self.push_resource(name.into(), false)
+    }
+
+    /// 导入外部资源（无需图内通道写入即可读取）
+    pub fn import_resource(&mut self, name: impl Into<String>) -> ResourceHandle {
+        self.push_resource(name.into(), true)
+    }
+
+    fn push_resource(&mut self, name: String, imported: bool) -> ResourceHandle {
+        self.resources.push(GraphResource { name, imported });
+        ResourceHandle(self.resources.len() as u32 - 1)
+    }
+
+    /// 添加渲染通道，返回通道索引
+    pub fn add_pass(
+        &mut self,
+        name: impl Into<String>,
+        reads: &[ResourceHandle],
+        writes: &[ResourceHandle],
+    ) -> usize {
+        self.passes.push(PassNode {
+            name: name.into(),
+            reads: reads.to_vec(),
+            writes: writes.to_vec(),
+        });
+        self.passes.len() - 1
+    }
+
+    /// 按声明顺序返回所有通道
+    pub fn passes(&self) -> &[PassNode] {
+        &self.passes
+    }
+
+    /// 资源名称
+    pub fn resource_name(&self, handle: ResourceHandle) -> Option<&str> {
+        self.resources
+            .get(handle.0 as usize)
+            .map(|r| r.name.as_str())
+    }
+
+    /// 校验资源句柄、读写关系和依赖环
+    pub fn validate(&self) -> Result<(), RenderGraphError> {
+        self.execution_order().map(|_| ())
+    }
+
+    /// 按读写依赖对通道进行拓扑排序
+    ///
+    /// 写入资源的通道排在读取该资源的通道之前；同一资源的多个写入者保持声明顺序。
+    /// 无依赖关系的通道保持声明顺序。
+    pub fn execution_order(&self) -> Result<Vec<usize>, RenderGraphError> {
+        let mut writers: HashMap<ResourceHandle, Vec<usize>> = HashMap::new();
+        for (index, pass) in self.passes.iter().enumerate() {
+            for &resource in pass.reads.iter().chain(&pass.writes) {
+                if self.resource_name(resource).is_none() {
+                    return Err(RenderGraphError::UnresolvedResource {
+                        pass: pass.name.clone(),
+                        resource,
+                    });
+                }
+            }
+            for &resource in &pass.writes {
+                writers.entry(resource).or_default().push(index);
+            }
+        }
+
+        let mut dependents: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); self.passes.len()];
+        for (index, pass) in self.passes.iter().enumerate() {
+            for &resource in &pass.reads {
+                let resource_writers = writers.get(&resource).map(Vec::as_slice).unwrap_or(&[]);
+                let imported = self.resources[resource.0 as usize].imported;
+                if resource_writers.iter().all(|&w| w == index) && !imported {
+                    return Err(RenderGraphError::ReadBeforeWrite {
+                        pass: pass.name.clone(),
+                        resource: self.resources[resource.0 as usize].name.clone(),
+                    });
+                }
+                for &writer in resource_writers {
+                    if writer != index {
+                        dependents[writer].insert(index);
+                    }
+                }
+            }
+        }
+        for resource_writers in writers.values() {
+            for pair in resource_writers.windows(2) {
+                dependents[pair[0]].insert(pair[1]);
+            }
+        }
+
+        // Kahn 算法，就绪通道按声明顺序取出
+        let mut in_degree = vec![0usize; self.passes.len()];
+        for targets in &dependents {
+            for &target in targets {
+                in_degree[target] += 1;
+            }
+        }
+        let mut ready: BTreeSet<usize> = (0..self.passes.len())
+            .filter(|&i| in_degree[i] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.passes.len());
+        while let Some(index) = ready.pop_first() {
+            order.push(index);
+            for &target in &dependents[index] {
+                in_degree[target] -= 1;
+                if in_degree[target] == 0 {
+                    ready.insert(target);
+                }
+            }
+        }
+
+        if order.len() < self.passes.len() {
+            return Err(RenderGraphError::Cycle(
+                self.find_cycle(&dependents, &in_degree),
+            ));
+        }
+        Ok(order)
+    }
+
+    /// 在未能排序的通道中找出一个依赖环
+    fn find_cycle(&self, dependents: &[BTreeSet<usize>], in_degree: &[usize]) -> Vec<String> {
+        let remaining = |i: usize| in_degree[i] > 0;
+        let start = (0..self.passes.len()).find(|&i| remaining(i)).unwrap_or(0);
+
+        // 未排序的通道都至少有一个未排序的前驱，沿前驱回溯必然回到已访问的通道
+        let mut path = vec![start];
+        loop {
+            let current = *path.last().unwrap();
+            let Some(prev) =
+                (0..self.passes.len()).find(|&p| remaining(p) && dependents[p].contains(&current))
+            else {
+                return vec![self.passes[current].name.clone()];
+            };
+            if let Some(pos) = path.iter().position(|&p| p == prev) {
+                // path 为回溯顺序，反转后得到依赖方向
+                let mut cycle: Vec<String> = path[pos..]
+                    .iter()
+                    .rev()
+                    .map(|&i| self.passes[i].name.clone())
+                    .collect();
+                cycle.push(cycle[0].clone());
+                return cycle;
+            }
+            path.push(prev);
+        }
+    }
+
+    /// 排序通道并计算每个通道前需要的资源状态转换
+    pub fn compile(&self) -> Result<CompiledRenderGraph, RenderGraphError> {
+        let order = self.execution_order()?;
+        let mut states = vec![ResourceState::Undefined; self.resources.len()];
+        let mut barriers = Vec::with_capacity(order.len());
+
+        for &index in &order {
+            let pass = &self.passes[index];
+            let mut pass_barriers = Vec::new();
+            let usages = pass
+                .reads
+                .iter()
+                .filter(|r| !pass.writes.contains(r))
+                .map(|&r| (r, ResourceState::ShaderRead))
+                .chain(
+                    pass.writes
+                        .iter()
+                        .map(|&r| (r, ResourceState::RenderTarget)),
+                );
+            for (resource, after) in usages {
+                let state = &mut states[resource.0 as usize];
+                if *state != after {
+                    pass_barriers.push(ResourceBarrier {
+                        resource,
+                        before: *state,
+                        after,
+                    });
+                    *state = after;
+                }
+            }
+            barriers.push(pass_barriers);
+        }
+
+        Ok(CompiledRenderGraph { order, barriers })
+    }
+
+    /// 按依赖顺序执行通道，回调中提供通道和执行前的状态转换
+    pub fn execute<F>(&self, mut run_pass: F) -> Result<(), RenderGraphError>
+    where
+        F: FnMut(&PassNode, &[ResourceBarrier]),
+    {
+        let compiled = self.compile()?;
+        for (&index, barriers) in compiled.order.iter().zip(&compiled.barriers) {
+            run_pass(&self.passes[index], barriers);
+        }
+        Ok(())
+    }
 }
 
 pub fn build_commands(instances: &[Instance]) -> RenderGraph {
@@ -374,43 +645,126 @@ pub fn build_commands(instances: &[Instance]) -> RenderGraph {
     g
 }
 
+/// 精灵的世界空间 `(位置, 缩放, 旋转)`
+///
+/// 局部变换先按 `PreviousTransform` 插值；实体有 `GlobalTransform` 时再组合父级全局变换，
+/// 挂在父节点下的精灵随父节点移动。
+fn sprite_world_transform(
+    t: &crate::ecs::Transform,
+    prev: Option<&crate::ecs::PreviousTransform>,
+    global: Option<&crate::ecs::GlobalTransform>,
+    alpha: f32,
+) -> (glam::Vec3, glam::Vec3, glam::Quat) {
+    let local = match prev {
+        Some(prev) => crate::ecs::Transform {
+            pos: prev.pos.lerp(t.pos, alpha),
+            scale: prev.scale.lerp(t.scale, alpha),
+            rot: prev.rot.slerp(t.rot, alpha),
+        },
+        None => *t,
+    };
+    match global {
+        Some(global) => {
+            let (scale, rot, pos) = global.with_local(t, &local).to_scale_rotation_translation();
+            (pos, scale, rot)
+        }
+        None => (local.pos, local.scale, local.rot),
+    }
+}
+
+/// 添加精灵渲染项，带 `NineSlice` 且已知纹理尺寸时拆分为最多 9 个渲染项
+fn push_sprite(
+    lt: &mut LayerTree,
+    (pos, scale, rot): (glam::Vec3, glam::Vec3, glam::Quat),
+    sprite: &crate::ecs::Sprite,
+    slice: Option<&crate::ecs::NineSlice>,
+    texture_sizes: Option<&crate::ecs::TextureSizes>,
+) {
+    let item = |pos: glam::Vec3, size: [f32; 2], uv_off: [f32; 2], uv_scale: [f32; 2]| LayerItem {
+        pos: [pos.x, pos.y],
+        scale: size,
+        rot: rot.to_euler(glam::EulerRot::XYZ).2,
+        color: sprite.color,
+        uv_off,
+        uv_scale,
+        tex: sprite.tex_index,
+        normal_tex: sprite.normal_tex_index,
+        layer: sprite.layer,
+        target: 0,
+        chunk: 0,
+    };
+
+    let texture_size = texture_sizes.and_then(|sizes| sizes.get(sprite.tex_index));
+    let (Some(slice), Some(texture_size)) = (slice, texture_size) else {
+        lt.add(item(
+            pos,
+            [scale.x, scale.y],
+            sprite.uv_off,
+            sprite.uv_scale,
+        ));
+        return;
+    };
+
+    // 在精灵局部空间切分，再按精灵的旋转放到世界空间
+    let pieces = crate::render::sprite_batch::SpriteInstance::nine_slice(
+        glam::Vec3::ZERO,
+        glam::Vec2::new(scale.x, scale.y),
+        glam::Vec4::new(
+            sprite.uv_off[0],
+            sprite.uv_off[1],
+            sprite.uv_scale[0],
+            sprite.uv_scale[1],
+        ),
+        glam::Vec2::new(texture_size[0] as f32, texture_size[1] as f32),
+        *slice,
+        glam::Vec4::from(sprite.color),
+    );
+    for piece in pieces {
+        let offset = glam::Vec3::new(piece.transform[3][0], piece.transform[3][1], 0.0);
+        let [u, v, du, dv] = piece.tex_coords;
+        lt.add(item(
+            pos + rot * offset,
+            [piece.transform[0][0], piece.transform[1][1]],
+            [u, v],
+            [du, dv],
+        ));
+    }
+}
+
+/// 实体的世界空间位置，没有 `GlobalTransform` 时取局部位置
+fn world_position(
+    t: &crate::ecs::Transform,
+    global: Option<&crate::ecs::GlobalTransform>,
+) -> glam::Vec3 {
+    global.map_or(t.pos, |g| g.translation())
+}
+
 pub fn build_from_world(world: &mut bevy_ecs::world::World) -> LayerTree {
-    use crate::ecs::{PreviousTransform, Sprite, TileMap, Time, Transform};
+    use crate::ecs::{
+        GlobalTransform, NineSlice, PreviousTransform, Sprite, TextureSizes, TileMap, Time,
+        Transform,
+    };
     let mut lt = LayerTree::default();
 
     let time = world.get_resource::<Time>().unwrap();
     let alpha = time.alpha as f32;
 
     // Sprites
-    let mut query = world.query::<(&Transform, Option<&PreviousTransform>, &Sprite)>();
-    for (t, pt, s) in query.iter(world) {
-        let (pos, scale, rot) = if let Some(prev) = pt {
-            (
-                prev.pos.lerp(t.pos, alpha),
-                prev.scale.lerp(t.scale, alpha),
-                prev.rot.slerp(t.rot, alpha),
-            )
-        } else {
-            (t.pos, t.scale, t.rot)
-        };
-
-        lt.add(LayerItem {
-            pos: [pos.x, pos.y],
-            scale: [scale.x, scale.y],
-            rot: rot.to_euler(glam::EulerRot::XYZ).2,
-            color: s.color,
-            uv_off: s.uv_off,
-            uv_scale: s.uv_scale,
-            tex: s.tex_index,
-            normal_tex: s.normal_tex_index,
-            layer: s.layer,
-            target: 0,
-            chunk: 0,
-        });
+    let texture_sizes = world.get_resource::<TextureSizes>().cloned();
+    let mut query = world.query::<(
+        &Transform,
+        Option<&PreviousTransform>,
+        Option<&GlobalTransform>,
+        &Sprite,
+        Option<&NineSlice>,
+    )>();
+    for (t, pt, g, s, slice) in query.iter(world) {
+        let world_transform = sprite_world_transform(t, pt, g, alpha);
+        push_sprite(&mut lt, world_transform, s, slice, texture_sizes.as_ref());
     }
 
     // TileMaps
-    let mut query_tm = world.query::<(&Transform, &TileMap)>();
+    let mut query_tm = world.query::<(&Transform, Option<&GlobalTransform>, &TileMap)>();
     let tileset = world.get_resource::<TileSet>().cloned();
     let vp = world.get_resource::<Viewport>().copied();
     let chunk_cfg = world.get_resource::<TileChunkConfig>().copied();
@@ -426,14 +780,19 @@ pub fn build_from_world(world: &mut bevy_ecs::world::World) -> LayerTree {
             break;
         }
     }
-    for (t, tm) in query_tm.iter(world) {
+    for (t, g, tm) in query_tm.iter(world) {
+        // Instanced 模式的地图由chunk实例缓冲区绘制
+        if tm.render_mode == TileMapRenderMode::Instanced {
+            continue;
+        }
         let (vpw, vph) = vp
             .map(|v| (v.width as f32, v.height as f32))
             .unwrap_or((800.0, 600.0));
         let half_w = vpw * 0.5;
         let half_h = vph * 0.5;
-        let base_x = t.pos.x - (tm.width as f32 * tm.tile_size[0]) * 0.5;
-        let base_y = t.pos.y - (tm.height as f32 * tm.tile_size[1]) * 0.5;
+        let map_pos = world_position(t, g);
+        let base_x = map_pos.x - (tm.width as f32 * tm.tile_size[0]) * 0.5;
+        let base_y = map_pos.y - (tm.height as f32 * tm.tile_size[1]) * 0.5;
         let view_min_x = cam_pos.x - half_w;
         let view_max_x = cam_pos.x + half_w;
         let view_min_y = cam_pos.y - half_h;
@@ -479,10 +838,12 @@ pub fn build_from_world(world: &mut bevy_ecs::world::World) -> LayerTree {
                         let cx = x / chunk_w;
                         let cy = y / chunk_h;
                         let chunk_id = cy * chunk_cols + cx;
+                        let flags = tm.tile_flags(idx);
+                        let (uv_off, uv_scale) = flags.apply_uv(uv_off, uv_scale);
                         lt.add(LayerItem {
                             pos: [px, py],
-                            scale: [tm.tile_size[0], tm.tile_size[1]],
-                            rot: 0.0,
+                            scale: flags.sprite_size(tm.tile_size),
+                            rot: flags.rotation(),
                             color: [1.0, 1.0, 1.0, 1.0],
                             uv_off,
                             uv_scale,
@@ -578,7 +939,10 @@ impl ViewportCuller {
 
 /// 带视锥剔除的世界构建函数
 pub fn build_from_world_culled(world: &mut bevy_ecs::world::World) -> (LayerTree, u32, u32) {
-    use crate::ecs::{PreviousTransform, Sprite, TileMap, Time, Transform};
+    use crate::ecs::{
+        GlobalTransform, NineSlice, PreviousTransform, Sprite, TextureSizes, TileMap, Time,
+        Transform,
+    };
     let mut lt = LayerTree::default();
     let mut culled_count = 0u32;
     let mut total_count = 0u32;
@@ -606,19 +970,19 @@ pub fn build_from_world_culled(world: &mut bevy_ecs::world::World) -> (LayerTree
     let culler = ViewportCuller::new(vpw, vph, cam_pos, 100.0);
 
     // Sprites with culling
-    let mut query = world.query::<(&Transform, Option<&PreviousTransform>, &Sprite)>();
-    for (t, pt, s) in query.iter(world) {
+    let texture_sizes = world.get_resource::<TextureSizes>().cloned();
+    let mut query = world.query::<(
+        &Transform,
+        Option<&PreviousTransform>,
+        Option<&GlobalTransform>,
+        &Sprite,
+        Option<&NineSlice>,
+    )>();
+    for (t, pt, g, s, slice) in query.iter(world) {
         total_count += 1;
 
-        let (pos, scale, rot) = if let Some(prev) = pt {
-            (
-                prev.pos.lerp(t.pos, alpha),
-                prev.scale.lerp(t.scale, alpha),
-                prev.rot.slerp(t.rot, alpha),
-            )
-        } else {
-            (t.pos, t.scale, t.rot)
-        };
+        let world_transform = sprite_world_transform(t, pt, g, alpha);
+        let (pos, scale, _) = world_transform;
 
         // 视锥剔除检查
         let half_w = scale.x * 0.5;
@@ -628,29 +992,21 @@ pub fn build_from_world_culled(world: &mut bevy_ecs::world::World) -> (LayerTree
             continue;
         }
 
-        lt.add(LayerItem {
-            pos: [pos.x, pos.y],
-            scale: [scale.x, scale.y],
-            rot: rot.to_euler(glam::EulerRot::XYZ).2,
-            color: s.color,
-            uv_off: s.uv_off,
-            uv_scale: s.uv_scale,
-            tex: s.tex_index,
-            normal_tex: s.normal_tex_index,
-            layer: s.layer,
-            target: 0,
-            chunk: 0,
-        });
+        push_sprite(&mut lt, world_transform, s, slice, texture_sizes.as_ref());
     }
 
     // TileMaps (已有视口剔除)
-    let mut query_tm = world.query::<(&Transform, &TileMap)>();
+    let mut query_tm = world.query::<(&Transform, Option<&GlobalTransform>, &TileMap)>();
     let tileset = world.get_resource::<TileSet>().cloned();
     let chunk_cfg = world.get_resource::<TileChunkConfig>().copied();
 
-    for (t, tm) in query_tm.iter(world) {
-        let base_x = t.pos.x - (tm.width as f32 * tm.tile_size[0]) * 0.5;
-        let base_y = t.pos.y - (tm.height as f32 * tm.tile_size[1]) * 0.5;
+    for (t, g, tm) in query_tm.iter(world) {
+        if tm.render_mode == TileMapRenderMode::Instanced {
+            continue;
+        }
+        let map_pos = world_position(t, g);
+        let base_x = map_pos.x - (tm.width as f32 * tm.tile_size[0]) * 0.5;
+        let base_y = map_pos.y - (tm.height as f32 * tm.tile_size[1]) * 0.5;
         let cfg_w = chunk_cfg.map(|c| c.size[0]).unwrap_or(0);
         let cfg_h = chunk_cfg.map(|c| c.size[1]).unwrap_or(0);
         let chunk_w = if cfg_w != 0 {
@@ -696,10 +1052,12 @@ pub fn build_from_world_culled(world: &mut bevy_ecs::world::World) -> (LayerTree
                         let cx = x / chunk_w;
                         let cy = y / chunk_h;
                         let chunk_id = cy * chunk_cols + cx;
+                        let flags = tm.tile_flags(idx);
+                        let (uv_off, uv_scale) = flags.apply_uv(uv_off, uv_scale);
                         lt.add(LayerItem {
                             pos: [px, py],
-                            scale: [tm.tile_size[0], tm.tile_size[1]],
-                            rot: 0.0,
+                            scale: flags.sprite_size(tm.tile_size),
+                            rot: flags.rotation(),
                             color: [1.0, 1.0, 1.0, 1.0],
                             uv_off,
                             uv_scale,
@@ -717,3 +1075,162 @@ pub fn build_from_world_culled(world: &mut bevy_ecs::world::World) -> (LayerTree
 
     (lt, culled_count, total_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passes_sorted_by_dependencies() {
+        let mut graph = RenderGraph::default();
+        let swapchain = graph.import_resource("swapchain");
+        let gbuffer = graph.create_resource("gbuffer");
+
+        // B 先声明但读取 A 的输出
+        graph.add_pass("lighting", &[gbuffer], &[swapchain]);
+        graph.add_pass("geometry", &[], &[gbuffer]);
+
+        let compiled = graph.compile().unwrap();
+        let names: Vec<&str> = compiled
+            .order
+            .iter()
+            .map(|&i| graph.passes()[i].name.as_str())
+            .collect();
+        assert_eq!(names, ["geometry", "lighting"]);
+
+        // lighting 执行前 gbuffer 从渲染目标转换为着色器读取
+        assert!(compiled.barriers[1].contains(&ResourceBarrier {
+            resource: gbuffer,
+            before: ResourceState::RenderTarget,
+            after: ResourceState::ShaderRead,
+        }));
+
+        let mut executed = Vec::new();
+        graph
+            .execute(|pass, _| executed.push(pass.name.clone()))
+            .unwrap();
+        assert_eq!(executed, ["geometry", "lighting"]);
+    }
+
+    #[test]
+    fn test_validate_reports_errors() {
+        let mut graph = RenderGraph::default();
+        let a = graph.create_resource("a");
+        let b = graph.create_resource("b");
+        graph.add_pass("pass_a", &[b], &[a]);
+        graph.add_pass("pass_b", &[a], &[b]);
+        match graph.validate() {
+            Err(RenderGraphError::Cycle(cycle)) => {
+                assert_eq!(cycle.first(), cycle.last());
+                assert!(cycle.contains(&"pass_a".to_string()));
+                assert!(cycle.contains(&"pass_b".to_string()));
+            }
+            other => panic!("Expected cycle error, got {:?}", other),
+        }
+
+        let mut graph = RenderGraph::default();
+        let never_written = graph.create_resource("shadow_map");
+        graph.add_pass("lighting", &[never_written], &[]);
+        assert!(matches!(
+            graph.validate(),
+            Err(RenderGraphError::ReadBeforeWrite { .. })
+        ));
+
+        let mut graph = RenderGraph::default();
+        graph.add_pass("broken", &[ResourceHandle(7)], &[]);
+        assert!(matches!(
+            graph.validate(),
+            Err(RenderGraphError::UnresolvedResource { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sprite_extraction_follows_parent() {
+        use crate::ecs::{set_parent, PreviousTransform, Sprite, Time, Transform};
+        use glam::{Quat, Vec3};
+
+        let mut world = bevy_ecs::world::World::new();
+        world.insert_resource(Time {
+            alpha: 0.5,
+            ..Default::default()
+        });
+        let parent = world
+            .spawn(Transform {
+                pos: Vec3::new(100.0, 0.0, 0.0),
+                rot: Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+                scale: Vec3::ONE,
+            })
+            .id();
+        let child = world
+            .spawn((
+                Transform {
+                    pos: Vec3::new(10.0, 0.0, 0.0),
+                    ..Default::default()
+                },
+                PreviousTransform {
+                    pos: Vec3::ZERO,
+                    rot: Quat::IDENTITY,
+                    scale: Vec3::ONE,
+                },
+                Sprite::default(),
+            ))
+            .id();
+        set_parent(&mut world, child, parent);
+
+        let mut schedule = bevy_ecs::schedule::Schedule::default();
+        schedule.add_systems(crate::ecs::propagate_transforms);
+        schedule.run(&mut world);
+
+        // 局部位置在 (0,0) 与 (10,0) 之间插值到 (5,0)，再随父节点旋转 90° 并平移
+        for tree in [
+            build_from_world(&mut world),
+            build_from_world_culled(&mut world).0,
+        ] {
+            assert_eq!(tree.items.len(), 1);
+            let item = &tree.items[0];
+            assert!((item.pos[0] - 100.0).abs() < 1e-4, "pos = {:?}", item.pos);
+            assert!((item.pos[1] - 5.0).abs() < 1e-4, "pos = {:?}", item.pos);
+            assert!((item.rot - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_sprite_extraction_expands_nine_slice() {
+        use crate::ecs::{NineSlice, Sprite, TextureSizes, Time, Transform};
+        use glam::Vec3;
+
+        let mut world = bevy_ecs::world::World::new();
+        world.insert_resource(Time::default());
+        world.insert_resource(TextureSizes(vec![[64, 64]]));
+        world.spawn((
+            Transform {
+                pos: Vec3::new(400.0, 300.0, 0.0),
+                scale: Vec3::new(128.0, 64.0, 1.0),
+                ..Default::default()
+            },
+            Sprite::default(),
+            NineSlice::uniform(16.0),
+        ));
+
+        for tree in [
+            build_from_world(&mut world),
+            build_from_world_culled(&mut world).0,
+        ] {
+            assert_eq!(tree.items.len(), 9);
+            // 左上角保持 16x16，UV 为纹理角落的 16 纹素
+            let corner = &tree.items[0];
+            assert_eq!(corner.scale, [16.0, 16.0]);
+            assert_eq!(corner.pos, [400.0 - 56.0, 300.0 - 24.0]);
+            assert_eq!(corner.uv_scale, [0.25, 0.25]);
+            // 中心横向拉伸
+            let center = &tree.items[4];
+            assert_eq!(center.scale, [96.0, 32.0]);
+            assert_eq!(center.pos, [400.0, 300.0]);
+            assert_eq!(center.uv_off, [0.25, 0.25]);
+        }
+
+        // 纹理尺寸未知时按普通精灵绘制
+        world.remove_resource::<TextureSizes>();
+        assert_eq!(build_from_world(&mut world).items.len(), 1);
+    }
+}
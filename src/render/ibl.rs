@@ -0,0 +1,313 @@
+//! 基于图像的光照 (IBL)
+//!
+//! 从等距柱状投影的 HDR 环境图预计算三张贴图供 PBR 着色器采样:
+//! 漫反射辐照度立方体贴图、按粗糙度预过滤的镜面立方体贴图 (mip 链)
+//! 以及 split-sum 近似所需的 BRDF 积分查找表。
+
+use wgpu::util::DeviceExt;
+
+/// 辐照度立方体贴图面尺寸
+pub const IRRADIANCE_SIZE: u32 = 32;
+/// 预过滤立方体贴图最高级面尺寸
+pub const PREFILTER_SIZE: u32 = 128;
+/// 预过滤立方体贴图 mip 层数，第 i 层对应粗糙度 i / (层数 - 1)
+pub const PREFILTER_MIP_LEVELS: u32 = 5;
+/// BRDF 查找表边长
+pub const BRDF_LUT_SIZE: u32 = 64;
+
+const IBL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+const IRRADIANCE_STEPS: u32 = 32;
+const PREFILTER_SAMPLES: u32 = 256;
+const BRDF_LUT_SAMPLES: u32 = 256;
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct IblParams {
+    env_width: u32,
+    env_height: u32,
+    face_size: u32,
+    sample_count: u32,
+    roughness: f32,
+    _pad: [f32; 3],
+}
+
+/// 预计算好的 IBL 贴图
+pub struct IblEnvironment {
+    pub irradiance: wgpu::Texture,
+    pub irradiance_view: wgpu::TextureView,
+    pub prefiltered: wgpu::Texture,
+    pub prefiltered_view: wgpu::TextureView,
+    pub brdf_lut: wgpu::Texture,
+    pub brdf_lut_view: wgpu::TextureView,
+    pub sampler: wgpu::Sampler,
+}
+
+impl IblEnvironment {
+    /// 从 RGBA `f32` 的等距柱状投影 HDR 数据计算 IBL 贴图
+    pub fn from_equirect(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        hdr_equirect: &[f32],
+        size: (u32, u32),
+    ) -> Result<Self, String> {
+        let (width, height) = size;
+        if width == 0 || height == 0 {
+            return Err("Environment map size must be non-zero".to_string());
+        }
+        let expected = width as usize * height as usize * 4;
+        if hdr_equirect.len() != expected {
+            return Err(format!(
+                "Environment map data has {} floats, expected {} for {}x{} RGBA",
+                hdr_equirect.len(),
+                expected,
+                width,
+                height
+            ));
+        }
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("IBL Precompute Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader_ibl.wgsl").into()),
+        });
+        let environment = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("IBL Environment Buffer"),
+            contents: bytemuck::cast_slice(hdr_equirect),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let irradiance = create_ibl_texture(device, "IBL Irradiance", IRRADIANCE_SIZE, 6, 1);
+        let prefiltered = create_ibl_texture(
+            device,
+            "IBL Prefiltered",
+            PREFILTER_SIZE,
+            6,
+            PREFILTER_MIP_LEVELS,
+        );
+        let brdf_lut = create_ibl_texture(device, "IBL BRDF LUT", BRDF_LUT_SIZE, 1, 1);
+
+        let params = |face_size: u32, sample_count: u32, roughness: f32| IblParams {
+            env_width: width,
+            env_height: height,
+            face_size,
+            sample_count,
+            roughness,
+            _pad: [0.0; 3],
+        };
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("IBL Precompute Encoder"),
+        });
+
+        let irradiance_pass = CubePass::new(device, &shader, "cs_irradiance");
+        irradiance_pass.dispatch(
+            device,
+            &mut encoder,
+            &environment,
+            &params(IRRADIANCE_SIZE, IRRADIANCE_STEPS, 0.0),
+            &irradiance,
+            0,
+        );
+
+        let prefilter_pass = CubePass::new(device, &shader, "cs_prefilter");
+        for mip in 0..PREFILTER_MIP_LEVELS {
+            let roughness = mip as f32 / (PREFILTER_MIP_LEVELS - 1) as f32;
+            prefilter_pass.dispatch(
+                device,
+                &mut encoder,
+                &environment,
+                &params(PREFILTER_SIZE >> mip, PREFILTER_SAMPLES, roughness),
+                &prefiltered,
+                mip,
+            );
+        }
+
+        let lut_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("IBL BRDF LUT Pipeline"),
+            layout: None,
+            module: &shader,
+            entry_point: "cs_brdf_lut",
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        });
+        let lut_params = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("IBL BRDF LUT Params"),
+            contents: bytemuck::bytes_of(&params(BRDF_LUT_SIZE, BRDF_LUT_SAMPLES, 0.0)),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let lut_storage_view = brdf_lut.create_view(&wgpu::TextureViewDescriptor::default());
+        let lut_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("IBL BRDF LUT BG"),
+            layout: &lut_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: lut_params.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&lut_storage_view),
+                },
+            ],
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("IBL BRDF LUT Pass"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&lut_pipeline);
+            pass.set_bind_group(0, &lut_bind_group, &[]);
+            let groups = BRDF_LUT_SIZE.div_ceil(8);
+            pass.dispatch_workgroups(groups, groups, 1);
+        }
+
+        queue.submit(Some(encoder.finish()));
+
+        Ok(Self::from_textures(
+            device,
+            irradiance,
+            prefiltered,
+            brdf_lut,
+        ))
+    }
+
+    /// 创建 1x1 的占位贴图，未设置环境时用于填充绑定组
+    pub fn placeholder(device: &wgpu::Device) -> Self {
+        let irradiance = create_ibl_texture(device, "IBL Irradiance Placeholder", 1, 6, 1);
+        let prefiltered = create_ibl_texture(device, "IBL Prefiltered Placeholder", 1, 6, 1);
+        let brdf_lut = create_ibl_texture(device, "IBL BRDF LUT Placeholder", 1, 1, 1);
+        Self::from_textures(device, irradiance, prefiltered, brdf_lut)
+    }
+
+    /// 预过滤贴图的最大 mip 级别
+    pub fn max_prefilter_mip(&self) -> f32 {
+        (self.prefiltered.mip_level_count() - 1) as f32
+    }
+
+    fn from_textures(
+        device: &wgpu::Device,
+        irradiance: wgpu::Texture,
+        prefiltered: wgpu::Texture,
+        brdf_lut: wgpu::Texture,
+    ) -> Self {
+        let cube_view = |texture: &wgpu::Texture| {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(wgpu::TextureViewDimension::Cube),
+                ..Default::default()
+            })
+        };
+        let irradiance_view = cube_view(&irradiance);
+        let prefiltered_view = cube_view(&prefiltered);
+        let brdf_lut_view = brdf_lut.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("IBL Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            irradiance,
+            irradiance_view,
+            prefiltered,
+            prefiltered_view,
+            brdf_lut,
+            brdf_lut_view,
+            sampler,
+        }
+    }
+}
+
+/// 写入立方体贴图某一 mip 级别的计算通道
+struct CubePass {
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl CubePass {
+    fn new(device: &wgpu::Device, shader: &wgpu::ShaderModule, entry_point: &str) -> Self {
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(entry_point),
+            layout: None,
+            module: shader,
+            entry_point,
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        });
+        Self { pipeline }
+    }
+
+    fn dispatch(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        environment: &wgpu::Buffer,
+        params: &IblParams,
+        target: &wgpu::Texture,
+        mip: u32,
+    ) {
+        let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("IBL Params"),
+            contents: bytemuck::bytes_of(params),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            base_mip_level: mip,
+            mip_level_count: Some(1),
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("IBL Cube Pass BG"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: environment.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("IBL Cube Pass"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        let groups = params.face_size.div_ceil(8);
+        pass.dispatch_workgroups(groups, groups, 6);
+    }
+}
+
+fn create_ibl_texture(
+    device: &wgpu::Device,
+    label: &str,
+    size: u32,
+    layers: u32,
+    mip_level_count: u32,
+) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: layers,
+        },
+        mip_level_count,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: IBL_FORMAT,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::STORAGE_BINDING
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
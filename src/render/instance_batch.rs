@@ -28,7 +28,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::mesh::GpuMesh;
-use super::pbr_renderer::Instance3D;
+use super::pbr_renderer::{Instance3D, PbrRenderer};
 
 // ============================================================================
 // 核心数据结构
@@ -1192,7 +1192,11 @@ impl Mesh3DRenderer {
 /// 批次收集系统 - 将所有可见实体分组到批次
 pub fn batch_collection_system(
     mut batch_manager: ResMut<BatchManager>,
-    query: Query<(&Mesh3DRenderer, &crate::ecs::Transform)>,
+    query: Query<(
+        &Mesh3DRenderer,
+        &crate::ecs::Transform,
+        Option<&crate::ecs::GlobalTransform>,
+    )>,
 ) {
     use glam::Mat4;
 
@@ -1200,16 +1204,18 @@ pub fn batch_collection_system(
     batch_manager.clear_instances();
 
     // 收集所有可见实体
-    for (renderer, transform) in query.iter() {
+    for (renderer, transform, global) in query.iter() {
         if !renderer.visible {
             continue;
         }
 
         let key = renderer.batch_key();
 
-        // 创建实例数据 - 从 Transform 构建模型矩阵
-        let model_matrix =
-            Mat4::from_scale_rotation_translation(transform.scale, transform.rot, transform.pos);
+        // 创建实例数据 - 优先使用层级传播后的世界变换，没有时从局部 Transform 构建
+        let model_matrix = global.map_or_else(
+            || Mat4::from_scale_rotation_translation(transform.scale, transform.rot, transform.pos),
+            |global| global.0,
+        );
 
         let instance = Instance3D {
             model: model_matrix.to_cols_array_2d(),
@@ -1327,17 +1333,25 @@ pub struct DrawIndexedIndirect {
 }
 
 /// 渲染所有可见批次
-pub fn render_batches<'a>(render_pass: &mut wgpu::RenderPass<'a>, batch_manager: &'a BatchManager) {
+///
+/// 按每个批次网格的顶点布局从 `pbr` 选择管线，调用方只需绑定相机、光源等共享绑定组。
+pub fn render_batches<'a>(
+    render_pass: &mut wgpu::RenderPass<'a>,
+    pbr: &'a PbrRenderer,
+    batch_manager: &'a BatchManager,
+) {
     for batch in batch_manager.visible_batches() {
+        render_pass.set_pipeline(pbr.pipeline_for(batch.mesh.layout));
+
         // 绑定顶点缓冲区
-        render_pass.set_vertex_buffer(0, batch.mesh.vertex_buffer.slice(..));
+        let instance_slot = batch.mesh.set_vertex_buffers(render_pass, 0);
 
         // 绑定索引缓冲区
         render_pass.set_index_buffer(batch.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
 
         // 绑定实例缓冲区
         if let Some(instance_buffer) = &batch.instance_buffer {
-            render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+            render_pass.set_vertex_buffer(instance_slot, instance_buffer.slice(..));
         }
 
         // 绑定材质
@@ -1368,13 +1382,15 @@ pub fn render_batches<'a>(render_pass: &mut wgpu::RenderPass<'a>, batch_manager:
 
 pub fn render_small_batches<'a>(
     render_pass: &mut wgpu::RenderPass<'a>,
+    pbr: &'a PbrRenderer,
     batch_manager: &'a BatchManager,
 ) {
     for batch in batch_manager.small_batches() {
-        render_pass.set_vertex_buffer(0, batch.mesh.vertex_buffer.slice(..));
+        render_pass.set_pipeline(pbr.pipeline_for(batch.mesh.layout));
+        let instance_slot = batch.mesh.set_vertex_buffers(render_pass, 0);
         render_pass.set_index_buffer(batch.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         if let Some(instance_buffer) = &batch.instance_buffer {
-            render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+            render_pass.set_vertex_buffer(instance_slot, instance_buffer.slice(..));
         }
         render_pass.set_bind_group(1, &batch.material_bind_group, &[]);
         if let Some(bg) = batch.extra_material_bind_groups.get(0) {
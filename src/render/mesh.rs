@@ -1,5 +1,5 @@
+use crate::render::gpu_memory::{self, GpuAllocation};
 use std::sync::Arc;
-use wgpu::util::DeviceExt;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -41,28 +41,118 @@ impl Vertex3D {
     }
 }
 
+/// 顶点缓冲区布局
+///
+/// - `Interleaved`: 所有属性交错存放在一个缓冲区（AoS），适合普通绘制
+/// - `Separate`: 位置/法线/UV/切线各占一个缓冲区（SoA），适合计算着色器蒙皮和局部属性更新
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum VertexLayout {
+    #[default]
+    Interleaved,
+    Separate,
+}
+
+const POSITION_ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![0 => Float32x3];
+const NORMAL_ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![1 => Float32x3];
+const UV_ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![2 => Float32x2];
+const TANGENT_ATTRIBUTES: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![3 => Float32x4];
+
+impl VertexLayout {
+    /// 渲染管线使用的顶点缓冲区布局，着色器位置与 `Vertex3D::desc` 一致
+    pub fn buffer_layouts(self) -> Vec<wgpu::VertexBufferLayout<'static>> {
+        match self {
+            Self::Interleaved => vec![Vertex3D::desc()],
+            Self::Separate => [
+                (12, &POSITION_ATTRIBUTES),
+                (12, &NORMAL_ATTRIBUTES),
+                (8, &UV_ATTRIBUTES),
+                (16, &TANGENT_ATTRIBUTES),
+            ]
+            .into_iter()
+            .map(|(stride, attributes)| wgpu::VertexBufferLayout {
+                array_stride: stride,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes,
+            })
+            .collect(),
+        }
+    }
+
+    /// 占用的顶点缓冲区槽位数量
+    pub fn buffer_count(self) -> u32 {
+        match self {
+            Self::Interleaved => 1,
+            Self::Separate => 4,
+        }
+    }
+}
+
+/// `Separate` 布局下的逐属性顶点流
+#[derive(Clone, Debug)]
+pub struct VertexStreams {
+    pub position: Arc<wgpu::Buffer>,
+    pub normal: Arc<wgpu::Buffer>,
+    pub uv: Arc<wgpu::Buffer>,
+    pub tangent: Arc<wgpu::Buffer>,
+}
+
 #[derive(Clone, Debug)]
 pub struct GpuMesh {
+    /// 槽位 0 的顶点缓冲区：交错布局下为完整顶点数据，分离布局下为位置流
     pub vertex_buffer: Arc<wgpu::Buffer>,
+    /// 分离布局下的各属性顶点流
+    pub streams: Option<VertexStreams>,
+    pub layout: VertexLayout,
+    pub vertex_count: u32,
     pub index_buffer: Arc<wgpu::Buffer>,
     pub index_count: u32,
     pub aabb_min: [f32; 3],
     pub aabb_max: [f32; 3],
+    /// 顶点与索引缓冲区的显存登记，所有克隆共享，最后一个克隆释放时扣除
+    _memory: Arc<Vec<GpuAllocation>>,
 }
 
 impl GpuMesh {
     pub fn new(device: &wgpu::Device, vertices: &[Vertex3D], indices: &[u32]) -> Self {
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Mesh Vertex Buffer"),
-            contents: bytemuck::cast_slice(vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Mesh Index Buffer"),
-            contents: bytemuck::cast_slice(indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
+        Self::with_layout(device, vertices, indices, VertexLayout::Interleaved)
+    }
+
+    /// 按指定顶点布局创建网格
+    pub fn with_layout(
+        device: &wgpu::Device,
+        vertices: &[Vertex3D],
+        indices: &[u32],
+        layout: VertexLayout,
+    ) -> Self {
+        let mut memory = Vec::with_capacity(layout.buffer_count() as usize + 1);
+        let (vertex_buffer, streams) = match layout {
+            VertexLayout::Interleaved => {
+                let (buffer, allocation) = gpu_memory::create_buffer_init(
+                    device,
+                    &wgpu::util::BufferInitDescriptor {
+                        label: Some("Mesh Vertex Buffer"),
+                        contents: bytemuck::cast_slice(vertices),
+                        usage: wgpu::BufferUsages::VERTEX,
+                    },
+                );
+                memory.push(allocation);
+                (Arc::new(buffer), None)
+            }
+            VertexLayout::Separate => {
+                let streams = create_vertex_streams(device, vertices, &mut memory);
+                (streams.position.clone(), Some(streams))
+            }
+        };
+
+        let (index_buffer, index_allocation) = gpu_memory::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Mesh Index Buffer"),
+                contents: bytemuck::cast_slice(indices),
+                usage: wgpu::BufferUsages::INDEX,
+            },
+        );
+        memory.push(index_allocation);
 
         let mut min = [f32::INFINITY; 3];
         let mut max = [f32::NEG_INFINITY; 3];
@@ -88,11 +178,251 @@ impl GpuMesh {
         }
 
         Self {
-            vertex_buffer: Arc::new(vertex_buffer),
+            vertex_buffer,
+            streams,
+            layout,
+            vertex_count: vertices.len() as u32,
             index_buffer: Arc::new(index_buffer),
             index_count: indices.len() as u32,
             aabb_min: min,
             aabb_max: max,
+            _memory: Arc::new(memory),
+        }
+    }
+
+    /// 位置缓冲区（仅分离布局）
+    pub fn position_buffer(&self) -> Option<&wgpu::Buffer> {
+        self.streams.as_ref().map(|s| s.position.as_ref())
+    }
+
+    /// 法线缓冲区（仅分离布局）
+    pub fn normal_buffer(&self) -> Option<&wgpu::Buffer> {
+        self.streams.as_ref().map(|s| s.normal.as_ref())
+    }
+
+    /// 从 `first_slot` 开始绑定顶点缓冲区，返回下一个可用槽位（例如实例缓冲区）
+    pub fn set_vertex_buffers<'a>(
+        &'a self,
+        pass: &mut wgpu::RenderPass<'a>,
+        first_slot: u32,
+    ) -> u32 {
+        match &self.streams {
+            Some(s) => {
+                for (i, buffer) in [&s.position, &s.normal, &s.uv, &s.tangent]
+                    .into_iter()
+                    .enumerate()
+                {
+                    pass.set_vertex_buffer(first_slot + i as u32, buffer.slice(..));
+                }
+            }
+            None => pass.set_vertex_buffer(first_slot, self.vertex_buffer.slice(..)),
         }
+        first_slot + self.layout.buffer_count()
+    }
+}
+
+/// 将顶点拆分为逐属性缓冲区
+///
+/// 额外带 `STORAGE | COPY_DST`，供计算着色器蒙皮写入或 `Queue::write_buffer` 局部更新。
+fn create_vertex_streams(
+    device: &wgpu::Device,
+    vertices: &[Vertex3D],
+    memory: &mut Vec<GpuAllocation>,
+) -> VertexStreams {
+    let usage =
+        wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST;
+    let mut stream = |label: &str, contents: &[u8]| {
+        let (buffer, allocation) = gpu_memory::create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents,
+                usage,
+            },
+        );
+        memory.push(allocation);
+        Arc::new(buffer)
+    };
+
+    let positions: Vec<[f32; 3]> = vertices.iter().map(|v| v.pos).collect();
+    let normals: Vec<[f32; 3]> = vertices.iter().map(|v| v.normal).collect();
+    let uvs: Vec<[f32; 2]> = vertices.iter().map(|v| v.uv).collect();
+    let tangents: Vec<[f32; 4]> = vertices.iter().map(|v| v.tangent).collect();
+
+    VertexStreams {
+        position: stream("Mesh Position Stream", bytemuck::cast_slice(&positions)),
+        normal: stream("Mesh Normal Stream", bytemuck::cast_slice(&normals)),
+        uv: stream("Mesh UV Stream", bytemuck::cast_slice(&uvs)),
+        tangent: stream("Mesh Tangent Stream", bytemuck::cast_slice(&tangents)),
+    }
+}
+
+/// CPU 端网格数据，上传为 `GpuMesh` 前可在此做预处理（如生成切线）
+#[derive(Clone, Debug, Default)]
+pub struct MeshData {
+    pub vertices: Vec<Vertex3D>,
+    pub indices: Vec<u32>,
+}
+
+impl MeshData {
+    pub fn new(vertices: Vec<Vertex3D>, indices: Vec<u32>) -> Self {
+        Self { vertices, indices }
+    }
+
+    /// 根据位置、法线和 UV 生成逐顶点切线
+    pub fn generate_tangents(&mut self) {
+        generate_tangents(&mut self.vertices, &self.indices);
+    }
+
+    /// 上传到 GPU
+    pub fn upload(&self, device: &wgpu::Device, layout: VertexLayout) -> GpuMesh {
+        GpuMesh::with_layout(device, &self.vertices, &self.indices, layout)
+    }
+}
+
+/// 按 Lengyel 方法生成切线
+///
+/// 每个三角形由 UV 梯度求出切线和副切线方向，累加到其顶点上（共享顶点即取平均），
+/// 再对法线做 Gram-Schmidt 正交化。`tangent.w` 为副切线符号（±1）。
+/// UV 空间面积为零的三角形被跳过；没有有效三角形的顶点取任一垂直于法线的方向。
+pub fn generate_tangents(vertices: &mut [Vertex3D], indices: &[u32]) {
+    use glam::{Vec2, Vec3};
+
+    let mut tan = vec![Vec3::ZERO; vertices.len()];
+    let mut bitan = vec![Vec3::ZERO; vertices.len()];
+
+    for tri in indices.chunks_exact(3) {
+        let [i0, i1, i2] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
+        if i0.max(i1).max(i2) >= vertices.len() {
+            continue;
+        }
+        let (v0, v1, v2) = (&vertices[i0], &vertices[i1], &vertices[i2]);
+
+        let e1 = Vec3::from(v1.pos) - Vec3::from(v0.pos);
+        let e2 = Vec3::from(v2.pos) - Vec3::from(v0.pos);
+        let d1 = Vec2::from(v1.uv) - Vec2::from(v0.uv);
+        let d2 = Vec2::from(v2.uv) - Vec2::from(v0.uv);
+
+        let det = d1.x * d2.y - d2.x * d1.y;
+        if det.abs() <= f32::EPSILON {
+            continue;
+        }
+        let r = 1.0 / det;
+        let sdir = (e1 * d2.y - e2 * d1.y) * r;
+        let tdir = (e2 * d1.x - e1 * d2.x) * r;
+
+        for i in [i0, i1, i2] {
+            tan[i] += sdir;
+            bitan[i] += tdir;
+        }
+    }
+
+    for (i, vertex) in vertices.iter_mut().enumerate() {
+        let n = Vec3::from(vertex.normal).normalize_or_zero();
+        let t = (tan[i] - n * n.dot(tan[i])).normalize_or_zero();
+        let t = if t == Vec3::ZERO {
+            n.any_orthonormal_vector()
+        } else {
+            t
+        };
+        let w = if n.cross(t).dot(bitan[i]) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        vertex.tangent = [t.x, t.y, t.z, w];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(pos: [f32; 3], uv: [f32; 2]) -> Vertex3D {
+        Vertex3D {
+            pos,
+            normal: [0.0, 0.0, 1.0],
+            uv,
+            tangent: [0.0; 4],
+        }
+    }
+
+    #[test]
+    fn test_generate_tangents_quad() {
+        // XY 平面上的四边形，U 沿 +X，V 沿 +Y
+        let mut mesh = MeshData::new(
+            vec![
+                vertex([0.0, 0.0, 0.0], [0.0, 0.0]),
+                vertex([1.0, 0.0, 0.0], [1.0, 0.0]),
+                vertex([1.0, 1.0, 0.0], [1.0, 1.0]),
+                vertex([0.0, 1.0, 0.0], [0.0, 1.0]),
+            ],
+            vec![0, 1, 2, 0, 2, 3],
+        );
+        mesh.generate_tangents();
+
+        for v in &mesh.vertices {
+            let [x, y, z, w] = v.tangent;
+            assert!((x - 1.0).abs() < 1e-5, "tangent {:?}", v.tangent);
+            assert!(y.abs() < 1e-5 && z.abs() < 1e-5);
+            assert_eq!(w, 1.0);
+        }
+
+        // V 翻转后副切线符号取反
+        for v in &mut mesh.vertices {
+            v.uv[1] = 1.0 - v.uv[1];
+        }
+        mesh.generate_tangents();
+        assert!(mesh.vertices.iter().all(|v| v.tangent[3] == -1.0));
+    }
+
+    #[test]
+    fn test_generate_tangents_degenerate_uvs() {
+        // 所有顶点 UV 相同，UV 面积为零
+        let mut mesh = MeshData::new(
+            vec![
+                vertex([0.0, 0.0, 0.0], [0.5, 0.5]),
+                vertex([1.0, 0.0, 0.0], [0.5, 0.5]),
+                vertex([0.0, 1.0, 0.0], [0.5, 0.5]),
+            ],
+            vec![0, 1, 2],
+        );
+        mesh.generate_tangents();
+
+        for v in &mesh.vertices {
+            let t = glam::Vec3::new(v.tangent[0], v.tangent[1], v.tangent[2]);
+            assert!(t.is_finite());
+            assert!((t.length() - 1.0).abs() < 1e-5);
+            assert!(t.dot(glam::Vec3::Z).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_separate_layout_streams() {
+        let Some((device, _queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let vertices = [
+            vertex([0.0, 0.0, 0.0], [0.0, 0.0]),
+            vertex([1.0, 0.0, 0.0], [1.0, 0.0]),
+            vertex([0.0, 1.0, 0.0], [0.0, 1.0]),
+        ];
+        let mesh = GpuMesh::with_layout(&device, &vertices, &[0, 1, 2], VertexLayout::Separate);
+
+        let position = mesh.position_buffer().unwrap();
+        let normal = mesh.normal_buffer().unwrap();
+        assert_eq!(mesh.vertex_count, 3);
+        assert_eq!(position.size(), 3 * 12);
+        assert_eq!(normal.size(), 3 * 12);
+        assert_ne!(position.global_id(), normal.global_id());
+        assert_eq!(VertexLayout::Separate.buffer_layouts().len(), 4);
+
+        let interleaved = GpuMesh::new(&device, &vertices, &[0, 1, 2]);
+        assert!(interleaved.position_buffer().is_none());
+        assert_eq!(
+            interleaved.vertex_buffer.size(),
+            3 * std::mem::size_of::<Vertex3D>() as u64
+        );
     }
 }
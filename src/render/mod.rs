@@ -13,12 +13,15 @@ pub trait RenderDevice {}
 pub trait RenderQueue {}
 pub mod backend;
 pub mod batch_builder;
+pub mod capture;
 pub mod clipping;
 pub mod csm;
 pub mod deferred;
 pub mod frustum;
 pub mod gpu_driven;
+pub mod gpu_memory;
 pub mod graph;
+pub mod ibl;
 pub mod instance_batch;
 pub mod lod;
 pub mod occlusion_culling;
@@ -45,8 +48,8 @@ pub use instance_batch::{
 
 // Re-export GPU Particle System components
 pub use particles::{
-    ColorGradient, ColorStop, GpuParticleSystem, ParticleEmitter, ParticleEmitterConfig,
-    ParticleShape, SizeOverLifetime,
+    ColorGradient, ColorStop, GpuParticleSystem, ParticleCollisionMode, ParticleEmitter,
+    ParticleEmitterConfig, ParticleShape, SizeOverLifetime,
 };
 
 // Re-export LOD System components
@@ -62,21 +65,26 @@ pub use csm::{CascadedShadowMap, CsmConfig, CsmRenderer, CsmUniforms, ShadowQual
 pub use frustum::{CullingResult, CullingSystem, Frustum, Plane};
 
 // Re-export Occlusion Culling components
-pub use occlusion_culling::HierarchicalZCulling;
+pub use occlusion_culling::{
+    HierarchicalZCulling, OcclusionCullResult, OcclusionCullingConfig, OcclusionCullingScheduler,
+};
 
 // Re-export Pipeline Optimization components
 pub use pipeline_optimization::{
-    CommandBuffer, DrawCallOptimizer, GPUMemoryManager, RenderMetrics, RenderPipelineOptimization,
+    BufferSuballocation, BufferSuballocator, CommandBuffer, DrawCallOptimizer, GPUMemoryManager,
+    QueuedDraw, RenderMetrics, RenderPipelineOptimization, StateChangeStats, StateSortResult,
 };
 
 // Re-export Ray Tracing components
 pub use ray_tracing::{
-    Camera as RayTracingCamera, Light, LightType, Material, RayTracingConfig, RayTracingPlane,
-    RayTracingRenderer, RayTracingScene, Sphere,
+    atrous_denoise, Camera as RayTracingCamera, DenoiseGuide, Light, LightType, Material,
+    RayTracingConfig, RayTracingPlane, RayTracingRenderer, RayTracingScene, Sphere,
 };
 
 // Re-export Volumetric Rendering components
-pub use volumetric::{Camera as VolumetricCamera, FogType, VolumetricConfig, VolumetricRenderer};
+pub use volumetric::{
+    Camera as VolumetricCamera, FogType, FogVolume, VolumetricConfig, VolumetricRenderer,
+};
 
 #[cfg(test)]
 mod tests;
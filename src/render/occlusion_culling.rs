@@ -38,6 +38,11 @@ pub enum OcclusionError {
 /// - 层次结构减少查询次数
 /// - 预期性能提升20-30%（复杂场景）
 ///
+/// ## 两遍剔除
+///
+/// `two_pass` 开启时 `cull_frame` 先绘制上一帧可见的物体并构建 Hi-Z，
+/// 再重新测试上一帧被剔除的物体，详见 [`OcclusionCullingConfig`]。
+///
 /// ## 生命周期管理
 ///
 /// 资源会在 `Drop` 时自动清理，也可以调用 `cleanup()` 方法显式清理。
@@ -74,6 +79,8 @@ pub struct HierarchicalZCulling {
     height: u32,
     /// 是否已初始化
     initialized: bool,
+    /// 剔除调度器（保存两遍剔除配置和上一帧可见性）
+    scheduler: OcclusionCullingScheduler,
 }
 
 impl HierarchicalZCulling {
@@ -88,6 +95,11 @@ impl HierarchicalZCulling {
     /// - 建议深度缓冲分辨率与渲染分辨率一致
     /// - 调用`initialize()`方法完成初始化
     pub fn new(width: u32, height: u32) -> Self {
+        Self::with_config(width, height, OcclusionCullingConfig::default())
+    }
+
+    /// 使用指定剔除配置创建Hi-Z遮挡剔除器
+    pub fn with_config(width: u32, height: u32, config: OcclusionCullingConfig) -> Self {
         // 计算Hi-Z层级数（log2(max(width, height)) + 1）
         let max_dim = width.max(height);
         let mip_levels = (max_dim as f32).log2().floor() as u32 + 1;
@@ -109,9 +121,53 @@ impl HierarchicalZCulling {
             width,
             height,
             initialized: false,
+            scheduler: OcclusionCullingScheduler::new(config),
         }
     }
 
+    /// 是否使用两遍剔除
+    pub fn two_pass(&self) -> bool {
+        self.scheduler.config().two_pass
+    }
+
+    pub fn set_two_pass(&mut self, two_pass: bool) {
+        self.scheduler.set_two_pass(two_pass);
+    }
+
+    /// 剔除调度器，可查询上一帧可见性或重置历史
+    pub fn scheduler_mut(&mut self) -> &mut OcclusionCullingScheduler {
+        &mut self.scheduler
+    }
+
+    /// 按 `two_pass` 配置执行一帧遮挡剔除
+    ///
+    /// 参数含义同 [`OcclusionCullingScheduler::cull_frame`]，
+    /// `build_hi_z` 和 `test` 会收到剔除器本身，通常在其中调用
+    /// [`build_hi_z`](Self::build_hi_z) 和 [`query_occlusion`](Self::query_occlusion)。
+    pub fn cull_frame<D, B, T>(
+        &mut self,
+        object_count: usize,
+        draw: D,
+        mut build_hi_z: B,
+        mut test: T,
+    ) -> OcclusionCullResult
+    where
+        D: FnMut(&[usize]),
+        B: FnMut(&Self),
+        T: FnMut(&Self, &[usize]) -> Vec<bool>,
+    {
+        // 调度期间取出调度器，让回调可以借用剔除器本身
+        let mut scheduler = std::mem::take(&mut self.scheduler);
+        let result = scheduler.cull_frame(
+            object_count,
+            draw,
+            || build_hi_z(self),
+            |indices| test(self, indices),
+        );
+        self.scheduler = scheduler;
+        result
+    }
+
     /// 初始化Hi-Z资源
     ///
     /// 创建Hi-Z纹理、计算管线和绑定组布局。
@@ -904,6 +960,156 @@ impl Drop for HierarchicalZCulling {
     }
 }
 
+/// 遮挡剔除配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OcclusionCullingConfig {
+    /// 是否使用两遍剔除
+    ///
+    /// 单遍剔除直接用上一帧的 Hi-Z 测试所有物体，相机快速移动时会把刚露出的物体错误剔除。
+    /// 两遍剔除先绘制上一帧可见的物体并用其深度构建新的 Hi-Z，
+    /// 再用新 Hi-Z 重新测试上一帧被剔除的物体，补绘变为可见的物体。
+    pub two_pass: bool,
+}
+
+impl Default for OcclusionCullingConfig {
+    fn default() -> Self {
+        Self { two_pass: true }
+    }
+}
+
+/// 一帧遮挡剔除的结果（物体索引）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OcclusionCullResult {
+    /// 第一遍绘制的物体
+    pub first_pass: Vec<usize>,
+    /// 第二遍补绘的物体（仅两遍剔除）
+    pub second_pass: Vec<usize>,
+    /// 被剔除的物体
+    pub culled: Vec<usize>,
+}
+
+impl OcclusionCullResult {
+    /// 本帧绘制的所有物体
+    pub fn drawn(&self) -> impl Iterator<Item = usize> + '_ {
+        self.first_pass
+            .iter()
+            .chain(self.second_pass.iter())
+            .copied()
+    }
+}
+
+/// 两遍遮挡剔除调度器
+///
+/// 保存每个物体上一帧的可见性，按配置安排绘制和 Hi-Z 测试的顺序。
+/// 具体的绘制、Hi-Z 构建和遮挡测试由调用方提供，
+/// 测试通常通过 [`HierarchicalZCulling::query_occlusion`] 完成。
+#[derive(Debug, Clone, Default)]
+pub struct OcclusionCullingScheduler {
+    config: OcclusionCullingConfig,
+    visible_last_frame: Vec<bool>,
+}
+
+impl OcclusionCullingScheduler {
+    pub fn new(config: OcclusionCullingConfig) -> Self {
+        Self {
+            config,
+            visible_last_frame: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> OcclusionCullingConfig {
+        self.config
+    }
+
+    pub fn set_two_pass(&mut self, two_pass: bool) {
+        self.config.two_pass = two_pass;
+    }
+
+    /// 上一帧的可见性，新加入的物体视为可见
+    pub fn was_visible(&self, index: usize) -> bool {
+        self.visible_last_frame.get(index).copied().unwrap_or(true)
+    }
+
+    /// 重置可见性历史（例如相机跳转后）
+    pub fn reset(&mut self) {
+        self.visible_last_frame.clear();
+    }
+
+    /// 执行一帧遮挡剔除
+    ///
+    /// - `draw`: 绘制给定物体（写入深度）
+    /// - `build_hi_z`: 用当前深度缓冲构建 Hi-Z
+    /// - `test`: 用当前 Hi-Z 测试给定物体，返回每个物体是否可见
+    pub fn cull_frame<D, B, T>(
+        &mut self,
+        object_count: usize,
+        mut draw: D,
+        mut build_hi_z: B,
+        mut test: T,
+    ) -> OcclusionCullResult
+    where
+        D: FnMut(&[usize]),
+        B: FnMut(),
+        T: FnMut(&[usize]) -> Vec<bool>,
+    {
+        let all: Vec<usize> = (0..object_count).collect();
+        let mut result = OcclusionCullResult::default();
+        let mut visible = vec![false; object_count];
+
+        if self.config.two_pass {
+            // 第一遍：绘制上一帧可见的物体，并用其深度构建新的 Hi-Z
+            result.first_pass = all
+                .iter()
+                .copied()
+                .filter(|&i| self.was_visible(i))
+                .collect();
+            draw(&result.first_pass);
+            build_hi_z();
+
+            // 第二遍：用新的 Hi-Z 重新测试上一帧被剔除的物体
+            let retest: Vec<usize> = all
+                .iter()
+                .copied()
+                .filter(|&i| !self.was_visible(i))
+                .collect();
+            let retest_visible = test(&retest);
+            for (&index, is_visible) in retest.iter().zip(retest_visible) {
+                if is_visible {
+                    result.second_pass.push(index);
+                } else {
+                    result.culled.push(index);
+                }
+            }
+            draw(&result.second_pass);
+
+            // 第一遍绘制的物体也用新的 Hi-Z 更新可见性，供下一帧使用
+            let first_visible = test(&result.first_pass);
+            for (&index, is_visible) in result.first_pass.iter().zip(first_visible) {
+                visible[index] = is_visible;
+            }
+            for &index in &result.second_pass {
+                visible[index] = true;
+            }
+        } else {
+            // 单遍：直接用上一帧的 Hi-Z 测试所有物体
+            let all_visible = test(&all);
+            for (&index, is_visible) in all.iter().zip(all_visible) {
+                visible[index] = is_visible;
+                if is_visible {
+                    result.first_pass.push(index);
+                } else {
+                    result.culled.push(index);
+                }
+            }
+            draw(&result.first_pass);
+            build_hi_z();
+        }
+
+        self.visible_last_frame = visible;
+        result
+    }
+}
+
 /// Hi-Z构建计算着色器（优化版）
 ///
 /// 从深度缓冲构建层次Z缓冲，每个mip级别存储该区域的最大深度值。
@@ -1184,6 +1390,7 @@ fn query_occlusion(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
 
     #[test]
     fn test_hi_z_creation() {
@@ -1213,6 +1420,154 @@ mod tests {
         assert_eq!(hi_z.height, height);
     }
 
+    /// 一维屏幕上的简化场景：物体覆盖 [start, end) 像素，深度越小越近
+    #[derive(Clone, Copy)]
+    struct ScreenObject {
+        start: usize,
+        end: usize,
+        depth: f32,
+    }
+
+    struct TestScene {
+        objects: Vec<ScreenObject>,
+        depth_buffer: Vec<f32>,
+        hi_z: Vec<f32>,
+    }
+
+    impl TestScene {
+        fn new(width: usize, objects: Vec<ScreenObject>) -> Self {
+            Self {
+                objects,
+                depth_buffer: vec![1.0; width],
+                hi_z: vec![1.0; width],
+            }
+        }
+
+        fn begin_frame(&mut self) {
+            self.depth_buffer.iter_mut().for_each(|d| *d = 1.0);
+        }
+
+        fn draw(&mut self, indices: &[usize]) {
+            for &i in indices {
+                let object = self.objects[i];
+                for depth in &mut self.depth_buffer[object.start..object.end] {
+                    *depth = depth.min(object.depth);
+                }
+            }
+        }
+
+        fn build_hi_z(&mut self) {
+            self.hi_z.clone_from(&self.depth_buffer);
+        }
+
+        /// 物体覆盖的任一像素不被更近的深度遮挡即可见
+        fn test(&self, indices: &[usize]) -> Vec<bool> {
+            indices
+                .iter()
+                .map(|&i| {
+                    let object = self.objects[i];
+                    self.hi_z[object.start..object.end]
+                        .iter()
+                        .any(|&d| object.depth <= d)
+                })
+                .collect()
+        }
+    }
+
+    fn run_frame(
+        scheduler: &mut OcclusionCullingScheduler,
+        scene: &RefCell<TestScene>,
+    ) -> OcclusionCullResult {
+        scene.borrow_mut().begin_frame();
+        let count = scene.borrow().objects.len();
+        scheduler.cull_frame(
+            count,
+            |indices| scene.borrow_mut().draw(indices),
+            || scene.borrow_mut().build_hi_z(),
+            |indices| scene.borrow().test(indices),
+        )
+    }
+
+    fn revealed_object_scene() -> RefCell<TestScene> {
+        // 物体 0 是遮挡墙，物体 1 在墙后
+        RefCell::new(TestScene::new(
+            16,
+            vec![
+                ScreenObject {
+                    start: 0,
+                    end: 8,
+                    depth: 0.2,
+                },
+                ScreenObject {
+                    start: 2,
+                    end: 6,
+                    depth: 0.8,
+                },
+            ],
+        ))
+    }
+
+    #[test]
+    fn test_two_pass_does_not_cull_revealed_object() {
+        for two_pass in [false, true] {
+            let scene = revealed_object_scene();
+            let mut scheduler = OcclusionCullingScheduler::new(OcclusionCullingConfig { two_pass });
+
+            // 第一帧之前没有历史，物体 1 被墙遮挡
+            run_frame(&mut scheduler, &scene);
+            let frame = run_frame(&mut scheduler, &scene);
+            assert_eq!(frame.culled, [1]);
+            assert!(!scheduler.was_visible(1));
+
+            // 相机快速移动：墙移到屏幕另一侧，物体 1 露出
+            scene.borrow_mut().objects[0].start = 8;
+            scene.borrow_mut().objects[0].end = 16;
+            let frame = run_frame(&mut scheduler, &scene);
+            let drawn: Vec<usize> = frame.drawn().collect();
+
+            if two_pass {
+                assert_eq!(frame.first_pass, [0]);
+                assert_eq!(frame.second_pass, [1]);
+                assert!(frame.culled.is_empty());
+                assert!(scheduler.was_visible(1));
+            } else {
+                // 单遍剔除使用上一帧的 Hi-Z，错误地剔除了物体 1
+                assert_eq!(drawn, [0]);
+                assert_eq!(frame.culled, [1]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hi_z_culling_two_pass_option() {
+        let mut hi_z =
+            HierarchicalZCulling::with_config(16, 1, OcclusionCullingConfig { two_pass: false });
+        assert!(!hi_z.two_pass());
+        assert!(HierarchicalZCulling::new(16, 1).two_pass());
+
+        let scene = revealed_object_scene();
+        let run = |hi_z: &mut HierarchicalZCulling| {
+            scene.borrow_mut().begin_frame();
+            let count = scene.borrow().objects.len();
+            hi_z.cull_frame(
+                count,
+                |indices| scene.borrow_mut().draw(indices),
+                |_| scene.borrow_mut().build_hi_z(),
+                |_, indices| scene.borrow().test(indices),
+            )
+        };
+        run(&mut hi_z);
+        assert_eq!(run(&mut hi_z).culled, [1]);
+
+        // 切换为两遍剔除后保留可见性历史，露出的物体在第二遍补绘
+        hi_z.set_two_pass(true);
+        scene.borrow_mut().objects[0].start = 8;
+        scene.borrow_mut().objects[0].end = 16;
+        let frame = run(&mut hi_z);
+        assert_eq!(frame.second_pass, [1]);
+        assert!(hi_z.scheduler_mut().was_visible(1));
+    }
+
     #[test]
     fn test_aabb_projection() {
         // 测试AABB投影逻辑（单元测试）
@@ -1,10 +1,11 @@
+use crate::render::gpu_memory::{self, GpuAllocation};
 use wgpu::{Device, Queue, Texture, TextureFormat, TextureUsages, TextureView};
 
 /// 离屏渲染目标
 pub struct OffscreenTarget {
     /// 纹理
     pub texture: Texture,
-    /// 纹理视图
+    /// 纹理视图（多层目标为二维数组视图）
     pub view: TextureView,
     /// 宽度
     pub width: u32,
@@ -12,27 +13,93 @@ pub struct OffscreenTarget {
     pub height: u32,
     /// 格式
     pub format: TextureFormat,
+    /// 数组层数（普通目标为1）
+    pub layers: u32,
+    /// 每一层的渲染附件视图
+    pub layer_views: Vec<TextureView>,
+    /// 深度纹理（多层目标才有，每层独立）
+    pub depth_texture: Option<Texture>,
+    /// 每一层的深度附件视图
+    pub depth_views: Vec<TextureView>,
+    /// `render_layer` 使用的清屏颜色
+    pub clear_color: wgpu::Color,
+    /// 颜色与深度纹理的显存登记
+    _memory: Vec<GpuAllocation>,
 }
 
 impl OffscreenTarget {
+    /// 多层目标的深度格式
+    pub const DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+
     /// 创建新的离屏渲染目标
     pub fn new(device: &Device, width: u32, height: u32, format: TextureFormat) -> Self {
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Offscreen Render Target"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
+        Self::create(device, width, height, 1, format)
+    }
+
+    /// 创建多层离屏渲染目标（立方体贴图使用6层，阴影图集按需指定层数）
+    ///
+    /// 每一层有独立的颜色和深度附件视图，可通过 [`Self::render_layer`] 单独渲染。
+    pub fn new_array(device: &Device, size: u32, layers: u32, format: TextureFormat) -> Self {
+        Self::create(device, size, size, layers.max(1), format)
+    }
+
+    fn create(
+        device: &Device,
+        width: u32,
+        height: u32,
+        layers: u32,
+        format: TextureFormat,
+    ) -> Self {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: layers,
+        };
+        let (texture, color_allocation) = gpu_memory::create_texture(
+            device,
+            &wgpu::TextureDescriptor {
+                label: Some("Offscreen Render Target"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: TextureUsages::RENDER_ATTACHMENT
+                    | TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_SRC,
+                view_formats: &[],
             },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
+        );
+        let mut memory = vec![color_allocation];
 
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let (view, layer_views, depth_texture, depth_views) = if layers == 1 {
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let layer_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            (view, vec![layer_view], None, Vec::new())
+        } else {
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                label: Some("Offscreen Array View"),
+                dimension: Some(wgpu::TextureViewDimension::D2Array),
+                ..Default::default()
+            });
+            let (depth_texture, depth_allocation) = gpu_memory::create_texture(
+                device,
+                &wgpu::TextureDescriptor {
+                    label: Some("Offscreen Depth Array"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: Self::DEPTH_FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+            );
+            memory.push(depth_allocation);
+            let layer_views = Self::layer_views(&texture, layers);
+            let depth_views = Self::layer_views(&depth_texture, layers);
+            (view, layer_views, Some(depth_texture), depth_views)
+        };
 
         Self {
             texture,
@@ -40,7 +107,87 @@ impl OffscreenTarget {
             width,
             height,
             format,
+            layers,
+            layer_views,
+            depth_texture,
+            depth_views,
+            clear_color: wgpu::Color::TRANSPARENT,
+            _memory: memory,
+        }
+    }
+
+    /// 为每一层创建单层二维视图
+    fn layer_views(texture: &Texture, layers: u32) -> Vec<TextureView> {
+        (0..layers)
+            .map(|layer| {
+                texture.create_view(&wgpu::TextureViewDescriptor {
+                    label: Some("Offscreen Layer View"),
+                    dimension: Some(wgpu::TextureViewDimension::D2),
+                    base_array_layer: layer,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// 创建立方体贴图视图（仅6层且宽高相等的目标可用）
+    pub fn cube_view(&self) -> Option<TextureView> {
+        if self.layers != 6 || self.width != self.height {
+            return None;
         }
+        Some(self.texture.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("Offscreen Cube View"),
+            dimension: Some(wgpu::TextureViewDimension::Cube),
+            ..Default::default()
+        }))
+    }
+
+    /// 渲染到指定的数组层或立方体面
+    ///
+    /// 颜色附件清除为 `clear_color`，多层目标的深度附件清除为1.0。
+    pub fn render_layer<F>(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        layer: u32,
+        draw_fn: F,
+    ) -> Result<(), String>
+    where
+        F: FnOnce(&mut wgpu::RenderPass<'_>),
+    {
+        let color_view = self.layer_views.get(layer as usize).ok_or_else(|| {
+            format!(
+                "Layer {} out of range for offscreen target with {} layers",
+                layer, self.layers
+            )
+        })?;
+        let depth_stencil_attachment = self.depth_views.get(layer as usize).map(|view| {
+            wgpu::RenderPassDepthStencilAttachment {
+                view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }
+        });
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Offscreen Layer Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: color_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(self.clear_color),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        draw_fn(&mut pass);
+        Ok(())
     }
 
     /// 调整大小
@@ -49,27 +196,9 @@ impl OffscreenTarget {
             return;
         }
 
-        self.width = width;
-        self.height = height;
-
-        self.texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Offscreen Render Target"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: self.format,
-            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        self.view = self
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let clear_color = self.clear_color;
+        *self = Self::create(device, width, height, self.layers, self.format);
+        self.clear_color = clear_color;
     }
 }
 
@@ -198,4 +327,90 @@ mod tests {
         assert_eq!(bloom.intensity, 1.5);
         assert_eq!(bloom.custom_params[0], 0.8);
     }
+
+    /// 读取指定层左上角像素
+    fn read_pixel(device: &Device, queue: &Queue, target: &OffscreenTarget, layer: u32) -> [u8; 4] {
+        let bytes_per_row = target.width * 4;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Layer Readback"),
+            size: (bytes_per_row * target.height) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(target.height),
+                },
+            },
+            wgpu::Extent3d {
+                width: target.width,
+                height: target.height,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let pixel = [data[0], data[1], data[2], data[3]];
+        drop(data);
+        readback.unmap();
+        pixel
+    }
+
+    #[test]
+    fn test_array_target_layers_are_independent() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let cube = OffscreenTarget::new_array(&device, 64, 6, TextureFormat::Rgba8Unorm);
+        assert_eq!(cube.layers, 6);
+        assert_eq!(cube.layer_views.len(), 6);
+        assert_eq!(cube.depth_views.len(), 6);
+        assert!(cube.cube_view().is_some());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for face in 0..6 {
+            cube.render_layer(&mut encoder, face, |_| {}).unwrap();
+        }
+        assert!(cube.render_layer(&mut encoder, 6, |_| {}).is_err());
+        queue.submit(Some(encoder.finish()));
+
+        // 阴影图集：只清除第2层为红色，其余层保持黑色
+        let mut atlas = OffscreenTarget::new_array(&device, 64, 4, TextureFormat::Rgba8Unorm);
+        assert!(atlas.cube_view().is_none());
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for layer in 0..atlas.layers {
+            atlas.clear_color = if layer == 2 {
+                wgpu::Color::RED
+            } else {
+                wgpu::Color::BLACK
+            };
+            atlas.render_layer(&mut encoder, layer, |_| {}).unwrap();
+        }
+        queue.submit(Some(encoder.finish()));
+
+        assert_eq!(read_pixel(&device, &queue, &atlas, 2), [255, 0, 0, 255]);
+        for layer in [0, 1, 3] {
+            assert_eq!(read_pixel(&device, &queue, &atlas, layer), [0, 0, 0, 255]);
+        }
+    }
 }
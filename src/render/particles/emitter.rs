@@ -3,9 +3,13 @@
 //! 支持大规模粒子模拟，完全在 GPU 上执行。
 
 use crate::impl_default;
+use crate::render::mesh::MeshData;
+use crate::render::postprocess::dof::linearize_depth;
+use crate::resources::manager::Handle;
 use bevy_ecs::prelude::*;
-use glam::{Vec3, Vec4};
+use glam::{Mat4, Vec2, Vec3, Vec4};
 use std::ops::Range;
+use wgpu::util::DeviceExt;
 
 // ============================================================================
 // 粒子发射器组件
@@ -26,6 +30,12 @@ pub struct ParticleEmitterConfig {
     pub prewarm_time: f32,
     /// 发射形状
     pub shape: ParticleShape,
+    /// 与场景深度缓冲的碰撞处理方式
+    pub collision_mode: ParticleCollisionMode,
+    /// 反弹时保留的速度比例
+    pub restitution: f32,
+    /// 深度缓冲表面的假定厚度（视空间距离），穿透更深的粒子视为被遮挡
+    pub collision_thickness: f32,
 }
 
 impl_default!(ParticleEmitterConfig {
@@ -35,6 +45,9 @@ impl_default!(ParticleEmitterConfig {
     looping: true,
     prewarm_time: 0.0,
     shape: ParticleShape::Point,
+    collision_mode: ParticleCollisionMode::None,
+    restitution: 0.5,
+    collision_thickness: 0.5,
 });
 
 /// 发射形状
@@ -55,12 +68,212 @@ pub enum ParticleShape {
     Circle { radius: f32 },
     /// 边缘发射
     Edge { length: f32 },
+    /// 从网格表面发射，按三角形面积加权
+    MeshSurface { mesh: Handle<MeshData> },
+    /// 按纹理 alpha 加权发射，纹理铺在发射器局部 XZ 平面上
+    Texture {
+        mask: Handle<image::RgbaImage>,
+        /// 纹理覆盖区域的半尺寸（X, Z）
+        half_extents: Vec2,
+    },
 }
 
 impl ParticleShape {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// 构建发射面
+    ///
+    /// 仅 `MeshSurface` / `Texture` 有发射面；资源尚未加载时返回 `None`。
+    pub fn emission_surface(&self) -> Option<EmissionSurface> {
+        match self {
+            Self::MeshSurface { mesh } => {
+                let mesh = mesh.get()?;
+                let positions: Vec<Vec3> = mesh.vertices.iter().map(|v| v.pos.into()).collect();
+                Some(EmissionSurface::from_mesh(&positions, &mesh.indices))
+            }
+            Self::Texture { mask, half_extents } => {
+                let mask = mask.get()?;
+                let alpha: Vec<f32> = mask.pixels().map(|p| p[3] as f32 / 255.0).collect();
+                Some(EmissionSurface::from_alpha_mask(
+                    mask.width(),
+                    mask.height(),
+                    &alpha,
+                    *half_extents,
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 发射面，按权重选出发射位置
+///
+/// 网格表面保存三角形，权重为三角形面积，选出三角形后在其中均匀采样；
+/// 纹理遮罩只保存每个像素 alpha 的累积分布，选出像素后在像素内均匀抖动。
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmissionSurface {
+    /// 网格表面
+    Triangles {
+        /// 三角形顶点（发射器局部空间）
+        triangles: Vec<[Vec3; 3]>,
+        /// 归一化的累积权重，最后一项为 1
+        cdf: Vec<f32>,
+    },
+    /// 铺在发射器局部 XZ 平面上的 alpha 遮罩
+    AlphaMask {
+        width: u32,
+        height: u32,
+        /// 遮罩覆盖区域的半尺寸（X, Z）
+        half_extents: Vec2,
+        /// 按行优先排列的每像素归一化累积 alpha；全透明时为空
+        cdf: Vec<f32>,
+    },
+}
+
+/// 发射面模式，与 `simulation.wgsl` 一致
+const EMISSION_TRIANGLES: u32 = 0;
+const EMISSION_ALPHA_MASK: u32 = 1;
+
+impl EmissionSurface {
+    /// 由带权重的三角形构建，忽略权重非正的三角形
+    pub fn from_weighted(weighted: impl IntoIterator<Item = ([Vec3; 3], f32)>) -> Self {
+        let mut triangles = Vec::new();
+        let mut cdf = Vec::new();
+        let mut total = 0.0;
+        for (triangle, weight) in weighted {
+            if weight > 0.0 {
+                total += weight;
+                triangles.push(triangle);
+                cdf.push(total);
+            }
+        }
+        for value in &mut cdf {
+            *value /= total;
+        }
+        Self::Triangles { triangles, cdf }
+    }
+
+    /// 网格表面，按三角形面积加权
+    pub fn from_mesh(positions: &[Vec3], indices: &[u32]) -> Self {
+        Self::from_weighted(indices.chunks_exact(3).filter_map(|tri| {
+            let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| positions.get(i as usize).copied());
+            let triangle = [a?, b?, c?];
+            let area = (triangle[1] - triangle[0])
+                .cross(triangle[2] - triangle[0])
+                .length()
+                * 0.5;
+            Some((triangle, area))
+        }))
+    }
+
+    /// 纹理遮罩，按 alpha 加权
+    ///
+    /// `alpha` 按行优先排列，第一行对应 -Z 边；遮罩覆盖 `[-half_extents, half_extents]`。
+    pub fn from_alpha_mask(width: u32, height: u32, alpha: &[f32], half_extents: Vec2) -> Self {
+        let texels = (width * height) as usize;
+        let mut total = 0.0;
+        let mut cdf: Vec<f32> = alpha
+            .iter()
+            .take(texels)
+            .map(|&a| {
+                total += a.max(0.0);
+                total
+            })
+            .collect();
+        if total > 0.0 {
+            for value in &mut cdf {
+                *value /= total;
+            }
+        } else {
+            cdf.clear();
+        }
+        Self::AlphaMask {
+            width,
+            height,
+            half_extents,
+            cdf,
+        }
+    }
+
+    fn cdf(&self) -> &[f32] {
+        match self {
+            Self::Triangles { cdf, .. } | Self::AlphaMask { cdf, .. } => cdf,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cdf().is_empty()
+    }
+
+    /// 用三个 `[0, 1)` 随机数采样一个点（与 `simulation.wgsl` 一致）
+    ///
+    /// `u` 按累积权重选出三角形或像素，`v`、`w` 决定其中的位置。
+    pub fn sample(&self, u: f32, v: f32, w: f32) -> Option<Vec3> {
+        let cdf = self.cdf();
+        let index = cdf
+            .partition_point(|&c| c <= u)
+            .min(cdf.len().checked_sub(1)?);
+        match self {
+            Self::Triangles { triangles, .. } => {
+                let [a, b, c] = triangles[index];
+
+                // 均匀三角形采样
+                let r = v.sqrt();
+                Some(a * (1.0 - r) + b * (r * (1.0 - w)) + c * (r * w))
+            }
+            Self::AlphaMask {
+                width,
+                height,
+                half_extents,
+                ..
+            } => {
+                // 在选中的像素内均匀抖动
+                let texel = Vec2::new((index as u32 % width) as f32, (index as u32 / width) as f32)
+                    + Vec2::new(v, w);
+                let size = Vec2::new(*width as f32, *height as f32);
+                let p = -*half_extents + texel * (2.0 * *half_extents / size);
+                Some(Vec3::new(p.x, 0.0, p.y))
+            }
+        }
+    }
+
+    /// 上传到 GPU 的数据
+    ///
+    /// 网格表面每个三角形 3 个 vec4，第一个顶点的 w 分量为累积权重；
+    /// 纹理遮罩每个 vec4 依次打包 4 个像素的累积权重。
+    pub fn gpu_data(&self) -> Vec<[f32; 4]> {
+        match self {
+            Self::Triangles { triangles, cdf } => triangles
+                .iter()
+                .zip(cdf)
+                .flat_map(|([a, b, c], &cdf)| [a.extend(cdf), b.extend(0.0), c.extend(0.0)])
+                .map(|v| v.to_array())
+                .collect(),
+            Self::AlphaMask { cdf, .. } => cdf
+                .chunks(4)
+                .map(|chunk| {
+                    let mut packed = [1.0; 4];
+                    packed[..chunk.len()].copy_from_slice(chunk);
+                    packed
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 粒子与深度缓冲碰撞后的处理方式
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParticleCollisionMode {
+    /// 不检测碰撞
+    #[default]
+    None = 0,
+    /// 沿表面法线反弹
+    Bounce = 1,
+    /// 碰撞后立即消亡
+    Kill = 2,
 }
 
 /// 粒子发射器组件
@@ -177,6 +390,13 @@ impl ParticleEmitter {
         self
     }
 
+    /// 设置深度碰撞
+    pub fn with_collision(mut self, mode: ParticleCollisionMode, restitution: f32) -> Self {
+        self.config.collision_mode = mode;
+        self.config.restitution = restitution.clamp(0.0, 1.0);
+        self
+    }
+
     /// 计算本帧应发射的粒子数
     pub fn particles_to_emit(&mut self, delta_time: f32) -> u32 {
         if !self.enabled {
@@ -322,6 +542,45 @@ fn sample_curve(points: &[(f32, f32)], t: f32) -> f32 {
     points.last().unwrap().1
 }
 
+// ============================================================================
+// 深度碰撞
+// ============================================================================
+
+/// 将世界坐标投影到屏幕，返回 UV（左上角为原点）和 `[0, 1]` 深度
+///
+/// 位于视锥之外时返回 `None`。
+pub fn project_to_screen(view_proj: Mat4, position: Vec3) -> Option<(Vec2, f32)> {
+    let clip = view_proj * position.extend(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    if ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 || !(0.0..=1.0).contains(&ndc.z) {
+        return None;
+    }
+    Some((Vec2::new(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5), ndc.z))
+}
+
+/// 判断粒子是否与深度缓冲中的表面碰撞（与 `simulation.wgsl` 一致）
+///
+/// 粒子位于表面之后且穿透深度不超过 `thickness` 时视为碰撞，穿透更深的粒子
+/// 视为位于物体背后；屏幕外的粒子不受影响。`depth_at` 按 UV 读取深度缓冲。
+pub fn depth_buffer_collision(
+    view_proj: Mat4,
+    near: f32,
+    far: f32,
+    thickness: f32,
+    position: Vec3,
+    depth_at: impl Fn(Vec2) -> f32,
+) -> bool {
+    let Some((uv, depth)) = project_to_screen(view_proj, position) else {
+        return false;
+    };
+    let scene_depth = linearize_depth(depth_at(uv), near, far);
+    let penetration = linearize_depth(depth, near, far) - scene_depth;
+    penetration > 0.0 && penetration <= thickness
+}
+
 // ============================================================================
 // GPU 粒子数据结构
 // ============================================================================
@@ -370,6 +629,30 @@ pub struct ParticleSystemUniforms {
     pub random_seed: f32,
     /// 填充
     pub _padding: f32,
+    /// 视图投影矩阵（用于深度碰撞）
+    pub view_proj: [[f32; 4]; 4],
+    /// 逆视图投影矩阵（用于从深度重建表面法线）
+    pub inv_view_proj: [[f32; 4]; 4],
+    /// 碰撞模式（`ParticleCollisionMode`）
+    pub collision_mode: u32,
+    /// 反弹系数
+    pub restitution: f32,
+    /// 表面厚度
+    pub collision_thickness: f32,
+    /// 近平面
+    pub near: f32,
+    /// 远平面
+    pub far: f32,
+    /// 发射面三角形或遮罩像素数量（0 = 使用发射器位置）
+    pub emission_count: u32,
+    /// 发射面模式（0 = 网格三角形，1 = alpha 遮罩）
+    pub emission_mode: u32,
+    /// 填充
+    pub _emission_padding: f32,
+    /// alpha 遮罩像素尺寸
+    pub emission_mask_size: [u32; 2],
+    /// alpha 遮罩覆盖区域的半尺寸（X, Z）
+    pub emission_half_extents: [f32; 2],
 }
 
 // ============================================================================
@@ -388,6 +671,14 @@ pub struct GpuParticleSystem {
     pub counter_buffer: wgpu::Buffer,
     /// Uniform 缓冲区
     pub uniform_buffer: wgpu::Buffer,
+    /// 发射面缓冲区（`EmissionSurface::gpu_data`）
+    pub emission_buffer: wgpu::Buffer,
+    /// 发射面三角形或遮罩像素数量
+    pub emission_count: u32,
+    /// 发射面模式及 alpha 遮罩参数
+    emission_mode: u32,
+    emission_mask_size: [u32; 2],
+    emission_half_extents: [f32; 2],
     /// 发射 Compute Pipeline
     pub emit_pipeline: Option<wgpu::ComputePipeline>,
     /// 更新 Compute Pipeline
@@ -402,6 +693,18 @@ pub struct GpuParticleSystem {
     pub max_particles: u32,
     /// 统计信息
     pub stats: ParticleSystemStats,
+    /// 深度碰撞模式
+    pub collision_mode: ParticleCollisionMode,
+    /// 反弹系数
+    pub restitution: f32,
+    /// 表面厚度
+    pub collision_thickness: f32,
+    /// 相机视图投影矩阵
+    pub view_proj: Mat4,
+    /// 相机近平面
+    pub near: f32,
+    /// 相机远平面
+    pub far: f32,
 }
 
 /// 粒子系统统计
@@ -470,12 +773,25 @@ impl GpuParticleSystem {
             mapped_at_creation: false,
         });
 
+        // 发射面（至少一个三角形大小，保证绑定有效）
+        let emission_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Emission Surface Buffer"),
+            size: std::mem::size_of::<[[f32; 4]; 3]>() as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         Self {
             particle_buffer,
             alive_list_buffer,
             dead_list_buffer,
             counter_buffer,
             uniform_buffer,
+            emission_buffer,
+            emission_count: 0,
+            emission_mode: EMISSION_TRIANGLES,
+            emission_mask_size: [0; 2],
+            emission_half_extents: [0.0; 2],
             emit_pipeline: None,
             update_pipeline: None,
             render_pipeline: None,
@@ -483,9 +799,214 @@ impl GpuParticleSystem {
             bind_group: None,
             max_particles,
             stats: ParticleSystemStats::default(),
+            collision_mode: ParticleCollisionMode::None,
+            restitution: 0.5,
+            collision_thickness: 0.5,
+            view_proj: Mat4::IDENTITY,
+            near: 0.1,
+            far: 1000.0,
         }
     }
 
+    /// 创建模拟用的 Bind Group Layout 和发射/更新 Compute Pipeline
+    pub fn create_pipelines(&mut self, device: &wgpu::Device) {
+        let storage = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Particle Simulation Bind Group Layout"),
+            entries: &[
+                storage(0),
+                storage(1),
+                storage(2),
+                storage(3),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // 场景深度缓冲
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // 发射面三角形
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Particle Simulation Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("simulation.wgsl").into()),
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Particle Simulation Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let create_pipeline = |label, entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point,
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            })
+        };
+
+        self.emit_pipeline = Some(create_pipeline("Particle Emit Pipeline", "emit"));
+        self.update_pipeline = Some(create_pipeline("Particle Update Pipeline", "update"));
+        self.bind_group_layout = Some(bind_group_layout);
+    }
+
+    /// 绑定场景深度缓冲，深度纹理重建（如窗口大小变化）后需要重新调用
+    pub fn bind_depth(&mut self, device: &wgpu::Device, depth_view: &wgpu::TextureView) {
+        let Some(layout) = &self.bind_group_layout else {
+            return;
+        };
+
+        self.bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Particle Simulation Bind Group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.particle_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: self.alive_list_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.dead_list_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.counter_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: self.emission_buffer.as_entire_binding(),
+                },
+            ],
+        }));
+    }
+
+    /// 上传发射面，`None` 或空发射面表示从发射器位置发射
+    ///
+    /// 会重建发射面缓冲区，之后需要重新调用 `bind_depth`。
+    pub fn set_emission_surface(
+        &mut self,
+        device: &wgpu::Device,
+        surface: Option<&EmissionSurface>,
+    ) {
+        let Some(surface) = surface.filter(|surface| !surface.is_empty()) else {
+            self.emission_count = 0;
+            return;
+        };
+        match surface {
+            EmissionSurface::Triangles { triangles, .. } => {
+                self.emission_count = triangles.len() as u32;
+                self.emission_mode = EMISSION_TRIANGLES;
+            }
+            EmissionSurface::AlphaMask {
+                width,
+                height,
+                half_extents,
+                cdf,
+            } => {
+                self.emission_count = cdf.len() as u32;
+                self.emission_mode = EMISSION_ALPHA_MASK;
+                self.emission_mask_size = [*width, *height];
+                self.emission_half_extents = half_extents.to_array();
+            }
+        }
+
+        self.emission_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle Emission Surface Buffer"),
+            contents: bytemuck::cast_slice(&surface.gpu_data()),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+    }
+
+    /// 从发射器配置读取碰撞设置
+    pub fn set_collision(&mut self, config: &ParticleEmitterConfig) {
+        self.collision_mode = config.collision_mode;
+        self.restitution = config.restitution;
+        self.collision_thickness = config.collision_thickness;
+    }
+
+    /// 设置用于深度碰撞的相机参数，需与深度缓冲对应的相机一致
+    pub fn set_camera(&mut self, view_proj: Mat4, near: f32, far: f32) {
+        self.view_proj = view_proj;
+        self.near = near;
+        self.far = far;
+    }
+
+    /// 记录发射和更新两个 Compute Pass
+    ///
+    /// 需要先调用 `create_pipelines` 和 `bind_depth`，否则不做任何事。
+    pub fn simulate(&self, encoder: &mut wgpu::CommandEncoder, emit_count: u32) {
+        let (Some(emit_pipeline), Some(update_pipeline), Some(bind_group)) =
+            (&self.emit_pipeline, &self.update_pipeline, &self.bind_group)
+        else {
+            return;
+        };
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Particle Simulation Pass"),
+            timestamp_writes: None,
+        });
+        pass.set_bind_group(0, bind_group, &[]);
+
+        if emit_count > 0 {
+            pass.set_pipeline(emit_pipeline);
+            pass.dispatch_workgroups(emit_count.div_ceil(64), 1, 1);
+        }
+
+        pass.set_pipeline(update_pipeline);
+        pass.dispatch_workgroups(self.max_particles.div_ceil(64), 1, 1);
+    }
+
     /// 初始化（重置死亡列表为所有粒子）
     pub fn initialize(&self, queue: &wgpu::Queue) {
         // 初始化死亡列表为 [0, 1, 2, ..., max_particles-1]
@@ -527,6 +1048,18 @@ impl GpuParticleSystem {
             time,
             random_seed: rand::random::<f32>(), // 使用rand替代fastrand
             _padding: 0.0,
+            view_proj: self.view_proj.to_cols_array_2d(),
+            inv_view_proj: self.view_proj.inverse().to_cols_array_2d(),
+            collision_mode: self.collision_mode as u32,
+            restitution: self.restitution,
+            collision_thickness: self.collision_thickness,
+            near: self.near,
+            far: self.far,
+            emission_count: self.emission_count,
+            emission_mode: self.emission_mode,
+            _emission_padding: 0.0,
+            emission_mask_size: self.emission_mask_size,
+            emission_half_extents: self.emission_half_extents,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
     }
@@ -617,4 +1150,128 @@ mod tests {
         let count = emitter.particles_to_emit(0.01);
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn test_depth_buffer_collision() {
+        let (near, far) = (0.1, 100.0);
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, near, far);
+        let view_proj = proj * view;
+
+        // 深度缓冲中是一面位于 z = 0（距相机 10）的墙
+        let (_, wall_depth) = project_to_screen(view_proj, Vec3::ZERO).unwrap();
+        let depth_at = |_: Vec2| wall_depth;
+        let collides =
+            |position| depth_buffer_collision(view_proj, near, far, 0.5, position, depth_at);
+
+        // 墙前方
+        assert!(!collides(Vec3::new(0.0, 0.0, 1.0)));
+        // 穿过墙面 0.2，在厚度内
+        assert!(collides(Vec3::new(0.0, 0.0, -0.2)));
+        // 穿透超过厚度，视为位于墙后而非碰撞
+        assert!(!collides(Vec3::new(0.0, 0.0, -2.0)));
+        // 屏幕外的粒子不受影响
+        assert!(!collides(Vec3::new(100.0, 0.0, -0.2)));
+        assert!(!collides(Vec3::new(0.0, 0.0, 20.0)));
+    }
+
+    /// 点是否位于三角形上（容差内）
+    fn on_triangle(p: Vec3, [a, b, c]: [Vec3; 3]) -> bool {
+        let n = (b - a).cross(c - a);
+        if (p - a).dot(n.normalize()).abs() > 1e-4 {
+            return false;
+        }
+        [(a, b), (b, c), (c, a)]
+            .iter()
+            .all(|&(from, to)| (to - from).cross(p - from).dot(n) >= -1e-4)
+    }
+
+    #[test]
+    fn test_mesh_surface_emission() {
+        use crate::render::mesh::Vertex3D;
+
+        let vertex = |pos: [f32; 3]| Vertex3D {
+            pos,
+            normal: [0.0, 1.0, 0.0],
+            uv: [0.0; 2],
+            tangent: [0.0; 4],
+        };
+        // 地面上的大三角形和一面竖直的小三角形
+        let mesh = MeshData::new(
+            vec![
+                vertex([0.0, 0.0, 0.0]),
+                vertex([4.0, 0.0, 0.0]),
+                vertex([0.0, 0.0, 4.0]),
+                vertex([0.0, 0.0, 0.0]),
+                vertex([0.0, 1.0, 0.0]),
+                vertex([1.0, 0.0, 0.0]),
+            ],
+            vec![0, 1, 2, 3, 4, 5],
+        );
+        let triangles = [
+            [
+                Vec3::ZERO,
+                Vec3::new(4.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 4.0),
+            ],
+            [Vec3::ZERO, Vec3::Y, Vec3::X],
+        ];
+
+        let shape = ParticleShape::MeshSurface {
+            mesh: Handle::new_loaded(mesh),
+        };
+        let surface = shape.emission_surface().unwrap();
+        let EmissionSurface::Triangles { cdf, .. } = &surface else {
+            panic!("expected triangles, got {surface:?}");
+        };
+        assert_eq!(cdf.len(), 2);
+        // 面积 8 与 0.5
+        assert!((cdf[0] - 8.0 / 8.5).abs() < 1e-5);
+        assert_eq!(*cdf.last().unwrap(), 1.0);
+        assert_eq!(surface.gpu_data().len(), 6);
+
+        let mut on_small = 0;
+        for _ in 0..1000 {
+            let p = surface
+                .sample(rand::random(), rand::random(), rand::random())
+                .unwrap();
+            assert!(triangles.iter().any(|&tri| on_triangle(p, tri)), "{p:?}");
+            if p.y > 1e-4 {
+                on_small += 1;
+            }
+        }
+        // 小三角形约占 1/17
+        assert!(on_small < 150);
+
+        // 边界随机数
+        for (u, v, w) in [(0.0, 0.0, 0.0), (0.9999, 0.9999, 0.9999)] {
+            let p = surface.sample(u, v, w).unwrap();
+            assert!(triangles.iter().any(|&tri| on_triangle(p, tri)));
+        }
+    }
+
+    #[test]
+    fn test_texture_mask_emission() {
+        // 2x1 遮罩，只有右侧像素不透明；每个像素只保存一个累积权重
+        let surface = EmissionSurface::from_alpha_mask(2, 1, &[0.0, 1.0], Vec2::new(1.0, 0.5));
+        assert!(matches!(&surface, EmissionSurface::AlphaMask { cdf, .. } if cdf == &[0.0, 1.0]));
+        assert_eq!(surface.gpu_data(), [[0.0, 1.0, 1.0, 1.0]]);
+
+        // 在选中的像素内按 v、w 抖动
+        let p = surface.sample(0.0, 0.25, 0.5).unwrap();
+        assert!(p.abs_diff_eq(Vec3::new(0.25, 0.0, 0.0), 1e-6), "{p:?}");
+
+        for _ in 0..200 {
+            let p = surface
+                .sample(rand::random(), rand::random(), rand::random())
+                .unwrap();
+            assert!((0.0..=1.0).contains(&p.x));
+            assert!((-0.5..=0.5).contains(&p.z));
+            assert_eq!(p.y, 0.0);
+        }
+
+        assert!(EmissionSurface::from_alpha_mask(1, 1, &[0.0], Vec2::ONE)
+            .sample(0.5, 0.5, 0.5)
+            .is_none());
+    }
 }
@@ -44,7 +44,8 @@ pub mod emitter;
 pub mod system;
 
 pub use emitter::{
-    ColorGradient, ColorStop, GpuParticle, GpuParticleSystem, ParticleEmitter, ParticleEmitterConfig,
-    ParticleShape, ParticleSystemStats, SizeOverLifetime,
+    ColorGradient, ColorStop, EmissionSurface, GpuParticle, GpuParticleSystem,
+    ParticleCollisionMode, ParticleEmitter, ParticleEmitterConfig, ParticleShape,
+    ParticleSystemStats, SizeOverLifetime,
 };
 pub use system::ParticleSystemManager;
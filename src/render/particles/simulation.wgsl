@@ -27,8 +27,27 @@ struct Uniforms {
     time: f32,
     random_seed: f32,
     _padding: f32,
+    view_proj: mat4x4<f32>,
+    inv_view_proj: mat4x4<f32>,
+    collision_mode: u32,
+    restitution: f32,
+    collision_thickness: f32,
+    near: f32,
+    far: f32,
+    emission_count: u32,
+    emission_mode: u32,
+    _emission_padding: f32,
+    emission_mask_size: vec2<u32>,
+    emission_half_extents: vec2<f32>,
 };
 
+// 发射面模式，与 EmissionSurface 一致
+const EMISSION_ALPHA_MASK: u32 = 1u;
+
+// 碰撞模式，与 ParticleCollisionMode 一致
+const COLLISION_NONE: u32 = 0u;
+const COLLISION_KILL: u32 = 2u;
+
 struct Counters {
     alive_count: atomic<u32>,
     dead_count: atomic<u32>,
@@ -45,6 +64,10 @@ struct Counters {
 @group(0) @binding(2) var<storage, read_write> dead_list: array<u32>;
 @group(0) @binding(3) var<storage, read_write> counters: Counters;
 @group(0) @binding(4) var<uniform> uniforms: Uniforms;
+@group(0) @binding(5) var depth_texture: texture_depth_2d;
+// 网格三角形：每个三角形 3 个 vec4，第一个的 w 为归一化的累积权重
+// alpha 遮罩：每个 vec4 打包 4 个像素的归一化累积 alpha
+@group(0) @binding(6) var<storage, read> emission_data: array<vec4<f32>>;
 
 // ============================================================================
 // 随机数生成
@@ -109,6 +132,48 @@ fn random_in_cone(direction: vec3<f32>, angle: f32) -> vec3<f32> {
     return local_dir;
 }
 
+// 第 i 个三角形或像素的累积权重
+fn emission_cdf(i: u32) -> f32 {
+    if (uniforms.emission_mode == EMISSION_ALPHA_MASK) {
+        return emission_data[i / 4u][i % 4u];
+    }
+    return emission_data[i * 3u].w;
+}
+
+// 按累积权重选出三角形或像素并在其中均匀采样（与 EmissionSurface::sample 一致）
+fn sample_emission_surface() -> vec3<f32> {
+    let u = random();
+    let v = random();
+    let w = random();
+
+    // 二分查找第一个累积权重大于 u 的三角形或像素
+    var lo = 0u;
+    var hi = uniforms.emission_count - 1u;
+    while (lo < hi) {
+        let mid = (lo + hi) / 2u;
+        if (emission_cdf(mid) <= u) {
+            lo = mid + 1u;
+        } else {
+            hi = mid;
+        }
+    }
+
+    if (uniforms.emission_mode == EMISSION_ALPHA_MASK) {
+        // 在选中的像素内均匀抖动
+        let size = uniforms.emission_mask_size;
+        let texel = vec2<f32>(f32(lo % size.x), f32(lo / size.x)) + vec2<f32>(v, w);
+        let extents = uniforms.emission_half_extents;
+        let p = -extents + texel * (2.0 * extents / vec2<f32>(size));
+        return vec3<f32>(p.x, 0.0, p.y);
+    }
+
+    let a = emission_data[lo * 3u].xyz;
+    let b = emission_data[lo * 3u + 1u].xyz;
+    let c = emission_data[lo * 3u + 2u].xyz;
+    let r = sqrt(v);
+    return a * (1.0 - r) + b * (r * (1.0 - w)) + c * (r * w);
+}
+
 // ============================================================================
 // 颜色插值
 // ============================================================================
@@ -117,6 +182,82 @@ fn lerp_color(start: vec4<f32>, end: vec4<f32>, t: f32) -> vec4<f32> {
     return mix(start, end, t);
 }
 
+// ============================================================================
+// 深度碰撞
+// ============================================================================
+
+fn linearize_depth(depth: f32) -> f32 {
+    let near = uniforms.near;
+    let far = uniforms.far;
+    return near * far / (far - depth * (far - near));
+}
+
+// 粒子位于深度缓冲表面之后且穿透不超过厚度时返回表面像素坐标，否则返回 (-1, -1)
+// 屏幕外的粒子不检测碰撞
+fn depth_collision_pixel(position: vec3<f32>) -> vec2<i32> {
+    let no_hit = vec2<i32>(-1, -1);
+    let clip = uniforms.view_proj * vec4<f32>(position, 1.0);
+    if (clip.w <= 0.0) {
+        return no_hit;
+    }
+
+    let ndc = clip.xyz / clip.w;
+    if (abs(ndc.x) > 1.0 || abs(ndc.y) > 1.0 || ndc.z < 0.0 || ndc.z > 1.0) {
+        return no_hit;
+    }
+
+    let dims = vec2<f32>(textureDimensions(depth_texture));
+    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    let pixel = vec2<i32>(min(uv * dims, dims - vec2<f32>(1.0)));
+
+    let scene_depth = linearize_depth(textureLoad(depth_texture, pixel, 0));
+    let penetration = linearize_depth(ndc.z) - scene_depth;
+    if (penetration > 0.0 && penetration <= uniforms.collision_thickness) {
+        return pixel;
+    }
+    return no_hit;
+}
+
+// 从深度缓冲重建像素对应的世界坐标
+fn world_from_depth(pixel: vec2<i32>) -> vec3<f32> {
+    let dims = vec2<f32>(textureDimensions(depth_texture));
+    let depth = textureLoad(depth_texture, pixel, 0);
+    let uv = (vec2<f32>(pixel) + 0.5) / dims;
+    let ndc = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, depth, 1.0);
+    let world = uniforms.inv_view_proj * ndc;
+    return world.xyz / world.w;
+}
+
+// 由相邻像素重建表面法线，方向与速度相反
+fn surface_normal(pixel: vec2<i32>, velocity: vec3<f32>) -> vec3<f32> {
+    let max_pixel = vec2<i32>(textureDimensions(depth_texture)) - vec2<i32>(1);
+    let px = vec2<i32>(select(pixel.x + 1, pixel.x - 1, pixel.x >= max_pixel.x), pixel.y);
+    let py = vec2<i32>(pixel.x, select(pixel.y + 1, pixel.y - 1, pixel.y >= max_pixel.y));
+
+    let center = world_from_depth(pixel);
+    var normal = cross(world_from_depth(px) - center, world_from_depth(py) - center);
+    if (dot(normal, normal) < 1e-12) {
+        // 无法重建时按水平地面处理
+        return vec3<f32>(0.0, 1.0, 0.0);
+    }
+
+    normal = normalize(normal);
+    if (dot(normal, velocity) > 0.0) {
+        normal = -normal;
+    }
+    return normal;
+}
+
+// 标记粒子死亡并放回死亡列表
+fn kill_particle(particle_idx: u32, particle: Particle) {
+    var p = particle;
+    p.alive = 0.0;
+    particles[particle_idx] = p;
+
+    let dead_idx = atomicAdd(&counters.dead_count, 1u);
+    dead_list[dead_idx] = particle_idx;
+}
+
 // ============================================================================
 // 发射 Shader
 // ============================================================================
@@ -150,7 +291,11 @@ fn emit(@builtin(global_invocation_id) global_id: vec3<u32>) {
     
     // 初始化粒子
     var p: Particle;
-    p.position = uniforms.emitter_position + random_vec3(vec3<f32>(-0.1), vec3<f32>(0.1));
+    if (uniforms.emission_count > 0u) {
+        p.position = uniforms.emitter_position + sample_emission_surface();
+    } else {
+        p.position = uniforms.emitter_position + random_vec3(vec3<f32>(-0.1), vec3<f32>(0.1));
+    }
     p.velocity = random_vec3(vec3<f32>(-1.0, 2.0, -1.0), vec3<f32>(1.0, 5.0, 1.0));
     p.lifetime = random_range(1.0, 3.0);
     p.age = 0.0;
@@ -193,13 +338,7 @@ fn update(@builtin(global_invocation_id) global_id: vec3<u32>) {
     
     // 检查是否死亡
     if (p.age >= p.lifetime) {
-        p.alive = 0.0;
-        particles[particle_idx] = p;
-        
-        // 添加到死亡列表
-        let dead_idx = atomicAdd(&counters.dead_count, 1u);
-        dead_list[dead_idx] = particle_idx;
-        
+        kill_particle(particle_idx, p);
         return;
     }
     
@@ -213,7 +352,24 @@ fn update(@builtin(global_invocation_id) global_id: vec3<u32>) {
     p.velocity *= 1.0 - uniforms.drag * dt;
     
     // 位置更新
+    let previous_position = p.position;
     p.position += p.velocity * dt;
+
+    // 屏幕空间深度碰撞
+    if (uniforms.collision_mode != COLLISION_NONE) {
+        let pixel = depth_collision_pixel(p.position);
+        if (pixel.x >= 0) {
+            if (uniforms.collision_mode == COLLISION_KILL) {
+                kill_particle(particle_idx, p);
+                return;
+            }
+
+            // 反弹：退回到碰撞前的位置并沿表面法线反射速度
+            let normal = surface_normal(pixel, p.velocity);
+            p.position = previous_position;
+            p.velocity = reflect(p.velocity, normal) * uniforms.restitution;
+        }
+    }
     
     // 旋转更新
     p.rotation += p.rotation_speed * dt;
@@ -1,5 +1,7 @@
+use super::ibl::IblEnvironment;
 use super::pbr::{DirectionalLight, PbrMaterial, PointLight3D};
-use crate::render::mesh::Vertex3D;
+use crate::render::mesh::VertexLayout;
+use wgpu::util::DeviceExt;
 
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
@@ -57,6 +59,8 @@ pub struct MaterialUniformPBR {
     ao: f32,
     normal_scale: f32,
     emissive: [f32; 3],
+    // WGSL 中 vec3 按 16 字节对齐，结构体大小向 16 字节取整
+    _pad0: f32,
     uv_offset: [f32; 2],
     uv_scale: [f32; 2],
     uv_rotation: f32,
@@ -64,6 +68,7 @@ pub struct MaterialUniformPBR {
     clearcoat_roughness: f32,
     anisotropy: f32,
     anisotropy_direction: [f32; 2],
+    _pad1: [f32; 2],
 }
 
 #[repr(C)]
@@ -86,8 +91,19 @@ struct GpuDirectionalLight {
     intensity: f32,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuEnvironmentParams {
+    intensity: f32,
+    max_mip: f32,
+    enabled: f32,
+    _pad: f32,
+}
+
 pub struct PbrRenderer {
     pub pipeline: wgpu::RenderPipeline,
+    /// 分离顶点流（`VertexLayout::Separate`）网格使用的管线，实例缓冲区位于槽位 4
+    pub pipeline_separate: wgpu::RenderPipeline,
     pub uniform_buffer: wgpu::Buffer,
     pub uniform_bind_group: wgpu::BindGroup,
     pub material_buffer: wgpu::Buffer,
@@ -97,6 +113,14 @@ pub struct PbrRenderer {
     pub lights_bind_group: wgpu::BindGroup,
     pub textures_bind_group: wgpu::BindGroup,
     pub textures_bgl: wgpu::BindGroupLayout,
+    lights_bgl: wgpu::BindGroupLayout,
+    dir_lights_buffer: wgpu::Buffer,
+    environment_buffer: wgpu::Buffer,
+    environment_intensity: f32,
+    /// 当前的 IBL 环境，为 None 时使用中性常量环境光
+    environment: Option<IblEnvironment>,
+    /// 未设置环境时填充绑定组的占位贴图
+    placeholder_environment: IblEnvironment,
 }
 
 pub struct PbrTextureSet {
@@ -386,6 +410,53 @@ impl PbrRenderer {
                     },
                     count: None,
                 },
+                // IBL 环境
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::Cube,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::Cube,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 5,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
             ],
         });
 
@@ -403,20 +474,26 @@ impl PbrRenderer {
             mapped_at_creation: false,
         });
 
-        let lights_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("PBR Lights BG"),
-            layout: &lights_bgl,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: lights_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: dir_lights_buffer.as_entire_binding(),
-                },
-            ],
+        // 未设置环境图时着色器使用中性常量环境光
+        let environment_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("PBR Environment Params"),
+            contents: bytemuck::bytes_of(&GpuEnvironmentParams {
+                intensity: 1.0,
+                max_mip: 0.0,
+                enabled: 0.0,
+                _pad: 0.0,
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
+        let placeholder_environment = IblEnvironment::placeholder(device);
+        let lights_bind_group = Self::create_lights_bind_group(
+            device,
+            &lights_bgl,
+            &lights_buffer,
+            &dir_lights_buffer,
+            &environment_buffer,
+            &placeholder_environment,
+        );
 
         let textures_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("PBR Textures BGL"),
@@ -533,54 +610,56 @@ impl PbrRenderer {
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("PBR Pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: "vs_main",
-                buffers: &[
-                    wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<Vertex3D>() as u64,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3, 2 => Float32x2, 3 => Float32x4],
-                    },
-                    Instance3D::desc(),
-                ],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        // 两种顶点布局共用同一着色器，仅顶点缓冲区布局不同，实例缓冲区紧随顶点流之后
+        let create_pipeline = |label: &str, layout: VertexLayout| {
+            let mut buffers = layout.buffer_layouts();
+            buffers.push(Instance3D::desc());
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    buffers: &buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::REPLACE),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Back),
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: wgpu::TextureFormat::Depth32Float,
+                    depth_write_enabled: true,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let pipeline = create_pipeline("PBR Pipeline", VertexLayout::Interleaved);
+        let pipeline_separate =
+            create_pipeline("PBR Separate Streams Pipeline", VertexLayout::Separate);
 
         Self {
             pipeline,
+            pipeline_separate,
             uniform_buffer,
             uniform_bind_group,
             material_buffer,
@@ -590,9 +669,123 @@ impl PbrRenderer {
             lights_bind_group,
             textures_bind_group,
             textures_bgl,
+            lights_bgl,
+            dir_lights_buffer,
+            environment_buffer,
+            environment_intensity: 1.0,
+            environment: None,
+            placeholder_environment,
         }
     }
 
+    fn create_lights_bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        lights_buffer: &wgpu::Buffer,
+        dir_lights_buffer: &wgpu::Buffer,
+        environment_buffer: &wgpu::Buffer,
+        environment: &IblEnvironment,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("PBR Lights BG"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: lights_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: dir_lights_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: environment_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&environment.irradiance_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(&environment.prefiltered_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::TextureView(&environment.brdf_lut_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::Sampler(&environment.sampler),
+                },
+            ],
+        })
+    }
+
+    /// 设置 IBL 环境
+    ///
+    /// `hdr_equirect` 为等距柱状投影的 RGBA `f32` HDR 数据，`size` 为 (宽, 高)。
+    /// 会预计算辐照度立方体贴图、预过滤镜面 mip 链和 BRDF 查找表，
+    /// 之后 PBR 着色器的环境光漫反射和镜面反射均从中采样。
+    pub fn set_environment(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        hdr_equirect: &[f32],
+        size: (u32, u32),
+    ) -> Result<(), String> {
+        let environment = IblEnvironment::from_equirect(device, queue, hdr_equirect, size)?;
+        self.lights_bind_group = Self::create_lights_bind_group(
+            device,
+            &self.lights_bgl,
+            &self.lights_buffer,
+            &self.dir_lights_buffer,
+            &self.environment_buffer,
+            &environment,
+        );
+        self.environment = Some(environment);
+        self.write_environment_params(queue);
+        Ok(())
+    }
+
+    /// 移除 IBL 环境，恢复中性常量环境光
+    pub fn clear_environment(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.environment = None;
+        self.lights_bind_group = Self::create_lights_bind_group(
+            device,
+            &self.lights_bgl,
+            &self.lights_buffer,
+            &self.dir_lights_buffer,
+            &self.environment_buffer,
+            &self.placeholder_environment,
+        );
+        self.write_environment_params(queue);
+    }
+
+    /// 设置环境光强度
+    pub fn set_environment_intensity(&mut self, queue: &wgpu::Queue, intensity: f32) {
+        self.environment_intensity = intensity;
+        self.write_environment_params(queue);
+    }
+
+    /// 当前的 IBL 环境
+    pub fn environment(&self) -> Option<&IblEnvironment> {
+        self.environment.as_ref()
+    }
+
+    fn write_environment_params(&self, queue: &wgpu::Queue) {
+        let params = GpuEnvironmentParams {
+            intensity: self.environment_intensity,
+            max_mip: self
+                .environment
+                .as_ref()
+                .map_or(0.0, IblEnvironment::max_prefilter_mip),
+            enabled: if self.environment.is_some() { 1.0 } else { 0.0 },
+            _pad: 0.0,
+        };
+        queue.write_buffer(&self.environment_buffer, 0, bytemuck::bytes_of(&params));
+    }
+
     pub fn create_material_bind_group(
         &self,
         device: &wgpu::Device,
@@ -609,6 +802,7 @@ impl PbrRenderer {
             ao: mat.ambient_occlusion,
             normal_scale: mat.normal_scale,
             emissive: mat.emissive.to_array(),
+            _pad0: 0.0,
             uv_offset: mat.uv_offset,
             uv_scale: mat.uv_scale,
             uv_rotation: mat.uv_rotation,
@@ -616,6 +810,7 @@ impl PbrRenderer {
             clearcoat_roughness: mat.clearcoat_roughness,
             anisotropy: mat.anisotropy,
             anisotropy_direction: mat.anisotropy_direction,
+            _pad1: [0.0; 2],
         };
         let buf = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("PBR Material Buffer (Per-Material)"),
@@ -643,6 +838,7 @@ impl PbrRenderer {
             ao: mat.ambient_occlusion,
             normal_scale: mat.normal_scale,
             emissive: mat.emissive.to_array(),
+            _pad0: 0.0,
             uv_offset: mat.uv_offset,
             uv_scale: mat.uv_scale,
             uv_rotation: mat.uv_rotation,
@@ -650,6 +846,7 @@ impl PbrRenderer {
             clearcoat_roughness: mat.clearcoat_roughness,
             anisotropy: mat.anisotropy,
             anisotropy_direction: mat.anisotropy_direction,
+            _pad1: [0.0; 2],
         }
     }
 
@@ -675,6 +872,7 @@ impl PbrRenderer {
             ao: material.ambient_occlusion,
             normal_scale: material.normal_scale,
             emissive: material.emissive.to_array(),
+            _pad0: 0.0,
             uv_offset: [0.0, 0.0],
             uv_scale: [1.0, 1.0],
             uv_rotation: 0.0,
@@ -682,6 +880,7 @@ impl PbrRenderer {
             clearcoat_roughness: 0.5,
             anisotropy: 0.0,
             anisotropy_direction: [1.0, 0.0],
+            _pad1: [0.0; 2],
         };
         queue.write_buffer(&self.material_buffer, 0, bytemuck::bytes_of(&uniform));
     }
@@ -733,6 +932,14 @@ impl PbrRenderer {
         }
     }
 
+    /// 与网格顶点布局匹配的管线
+    pub fn pipeline_for(&self, layout: VertexLayout) -> &wgpu::RenderPipeline {
+        match layout {
+            VertexLayout::Interleaved => &self.pipeline,
+            VertexLayout::Separate => &self.pipeline_separate,
+        }
+    }
+
     /// 渲染单个网格实例
     pub fn render_mesh<'a>(
         &'a self,
@@ -741,14 +948,14 @@ impl PbrRenderer {
         instance_buffer: &'a wgpu::Buffer,
         instance_count: u32,
     ) {
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(self.pipeline_for(mesh.layout));
         render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
         render_pass.set_bind_group(1, &self.material_bind_group, &[]);
         render_pass.set_bind_group(2, &self.lights_bind_group, &[]);
         render_pass.set_bind_group(3, &self.textures_bind_group, &[]);
 
-        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
-        render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+        let instance_slot = mesh.set_vertex_buffers(render_pass, 0);
+        render_pass.set_vertex_buffer(instance_slot, instance_buffer.slice(..));
         render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
 
         render_pass.draw_indexed(0..mesh.index_count, 0, 0..instance_count);
@@ -764,7 +971,7 @@ impl PbrRenderer {
             return;
         }
 
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(self.pipeline_for(batch.mesh.layout));
         render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
         render_pass.set_bind_group(1, &batch.material_bind_group, &[]);
         render_pass.set_bind_group(2, &self.lights_bind_group, &[]);
@@ -774,9 +981,9 @@ impl PbrRenderer {
             render_pass.set_bind_group(3, &self.textures_bind_group, &[]);
         }
 
-        render_pass.set_vertex_buffer(0, batch.mesh.vertex_buffer.slice(..));
+        let instance_slot = batch.mesh.set_vertex_buffers(render_pass, 0);
         if let Some(instance_buffer) = &batch.instance_buffer {
-            render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+            render_pass.set_vertex_buffer(instance_slot, instance_buffer.slice(..));
         }
         render_pass.set_index_buffer(batch.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
 
@@ -789,7 +996,6 @@ impl PbrRenderer {
         render_pass: &mut wgpu::RenderPass<'a>,
         batch_manager: &'a super::instance_batch::BatchManager,
     ) {
-        render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
         render_pass.set_bind_group(2, &self.lights_bind_group, &[]);
 
@@ -798,6 +1004,8 @@ impl PbrRenderer {
                 continue;
             }
 
+            // 按网格的顶点布局选择管线，两条管线共用管线布局，已绑定的绑定组保持有效
+            render_pass.set_pipeline(self.pipeline_for(batch.mesh.layout));
             render_pass.set_bind_group(1, &batch.material_bind_group, &[]);
             if let Some(bg) = batch.extra_material_bind_groups.get(0) {
                 render_pass.set_bind_group(3, bg, &[]);
@@ -806,9 +1014,9 @@ impl PbrRenderer {
             }
 
             // 绑定顶点缓冲区
-            render_pass.set_vertex_buffer(0, batch.mesh.vertex_buffer.slice(..));
+            let instance_slot = batch.mesh.set_vertex_buffers(render_pass, 0);
             if let Some(instance_buffer) = &batch.instance_buffer {
-                render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+                render_pass.set_vertex_buffer(instance_slot, instance_buffer.slice(..));
             }
             render_pass
                 .set_index_buffer(batch.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
@@ -818,3 +1026,238 @@ impl PbrRenderer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::mesh::{GpuMesh, Vertex3D};
+    use glam::{Mat4, Vec3, Vec4};
+
+    const TARGET_SIZE: u32 = 64;
+
+    /// 渲染一个朝向相机的四边形并返回中心像素
+    fn render_center_pixel(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &PbrRenderer,
+        layout: VertexLayout,
+    ) -> [u8; 4] {
+        let normal = [0.0, 0.0, 1.0];
+        let tangent = [1.0, 0.0, 0.0, 1.0];
+        let vertex = |x: f32, y: f32| Vertex3D {
+            pos: [x, y, 0.0],
+            normal,
+            uv: [0.5, 0.5],
+            tangent,
+        };
+        let mesh = GpuMesh::with_layout(
+            device,
+            &[
+                vertex(-1.0, -1.0),
+                vertex(1.0, -1.0),
+                vertex(1.0, 1.0),
+                vertex(-1.0, 1.0),
+            ],
+            &[0, 1, 2, 0, 2, 3],
+            layout,
+        );
+        let instances = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Test Instances"),
+            contents: bytemuck::bytes_of(&Instance3D {
+                model: Mat4::IDENTITY.to_cols_array_2d(),
+            }),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let camera_pos = Vec3::new(0.0, 0.0, 3.0);
+        let view_proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 1.0, 0.1, 10.0)
+            * Mat4::look_at_rh(camera_pos, Vec3::ZERO, Vec3::Y);
+        renderer.update_camera(queue, view_proj.to_cols_array_2d(), camera_pos.to_array());
+
+        let extent = wgpu::Extent3d {
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+            depth_or_array_layers: 1,
+        };
+        let color = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Test Color"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let depth = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Test Depth"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth32Float,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let color_view = color.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_view = depth.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Test PBR Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &color_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            renderer.render_mesh(&mut pass, &mesh, &instances, 1);
+        }
+
+        let bytes_per_row = TARGET_SIZE * 4;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Readback"),
+            size: (bytes_per_row * TARGET_SIZE) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            color.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(TARGET_SIZE),
+                },
+            },
+            extent,
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let center = ((TARGET_SIZE / 2 * TARGET_SIZE + TARGET_SIZE / 2) * 4) as usize;
+        [
+            data[center],
+            data[center + 1],
+            data[center + 2],
+            data[center + 3],
+        ]
+    }
+
+    #[test]
+    fn test_uniform_environment_lights_rough_dielectric() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut renderer = PbrRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm);
+        let solid = |rgba: [u8; 4]| image::RgbaImage::from_pixel(1, 1, image::Rgba(rgba));
+        let textures = renderer.create_texture_set_from_images(
+            &device,
+            &queue,
+            [
+                solid([255, 255, 255, 255]),
+                solid([255, 255, 255, 255]),
+                solid([128, 128, 255, 255]),
+                solid([255, 255, 255, 255]),
+                solid([0, 0, 0, 255]),
+            ],
+            [false; 5],
+        );
+        renderer.textures_bind_group = textures.bind_group;
+        renderer.update_material(
+            &queue,
+            &PbrMaterial {
+                base_color: Vec4::ONE,
+                metallic: 0.0,
+                roughness: 1.0,
+                ..Default::default()
+            },
+        );
+
+        // 未设置环境时使用中性环境光
+        let neutral = render_center_pixel(&device, &queue, &renderer, VertexLayout::Interleaved);
+        assert!(renderer.environment().is_none());
+
+        let black = [0.0f32, 0.0, 0.0, 1.0].repeat(8 * 4);
+        renderer
+            .set_environment(&device, &queue, &black, (8, 4))
+            .unwrap();
+        let unlit = render_center_pixel(&device, &queue, &renderer, VertexLayout::Interleaved);
+
+        let white = [1.0f32, 1.0, 1.0, 1.0].repeat(8 * 4);
+        renderer
+            .set_environment(&device, &queue, &white, (8, 4))
+            .unwrap();
+        let lit = render_center_pixel(&device, &queue, &renderer, VertexLayout::Interleaved);
+
+        assert!(
+            unlit[0] <= 1,
+            "black environment should not light: {:?}",
+            unlit
+        );
+        assert!(lit[0] > 0, "uniform environment should light: {:?}", lit);
+        assert!(lit[0] > neutral[0], "{:?} vs neutral {:?}", lit, neutral);
+
+        // 数据长度与尺寸不匹配时报错
+        assert!(renderer
+            .set_environment(&device, &queue, &white, (4, 4))
+            .is_err());
+    }
+
+    #[test]
+    fn test_separate_layout_matches_interleaved() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut renderer = PbrRenderer::new(&device, wgpu::TextureFormat::Rgba8Unorm);
+        let solid = |rgba: [u8; 4]| image::RgbaImage::from_pixel(1, 1, image::Rgba(rgba));
+        let textures = renderer.create_texture_set_from_images(
+            &device,
+            &queue,
+            [
+                solid([255, 255, 255, 255]),
+                solid([255, 255, 255, 255]),
+                solid([128, 128, 255, 255]),
+                solid([255, 255, 255, 255]),
+                solid([0, 0, 0, 255]),
+            ],
+            [false; 5],
+        );
+        renderer.textures_bind_group = textures.bind_group;
+        renderer.update_material(
+            &queue,
+            &PbrMaterial {
+                base_color: Vec4::ONE,
+                metallic: 0.0,
+                roughness: 1.0,
+                ..Default::default()
+            },
+        );
+
+        let interleaved =
+            render_center_pixel(&device, &queue, &renderer, VertexLayout::Interleaved);
+        let separate = render_center_pixel(&device, &queue, &renderer, VertexLayout::Separate);
+        assert!(interleaved[0] > 0, "{:?}", interleaved);
+        assert_eq!(separate, interleaved);
+    }
+}
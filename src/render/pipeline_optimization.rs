@@ -7,6 +7,7 @@
 /// - GPU 命令缓冲区管理
 use crate::impl_default;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// 渲染命令类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -40,6 +41,72 @@ pub struct DrawCommand {
     pub index_offset: u32,
 }
 
+impl DrawCommand {
+    /// 两个命令是否绘制相同几何体且实例区间相邻，可合并为一次实例化绘制
+    fn can_merge_instances(&self, next: &DrawCommand) -> bool {
+        self.command_type == next.command_type
+            && matches!(
+                self.command_type,
+                RenderCommandType::DrawIndexed | RenderCommandType::DrawInstanced
+            )
+            && self.vertex_count == next.vertex_count
+            && self.first_vertex == next.first_vertex
+            && self.index_count == next.index_count
+            && self.index_offset == next.index_offset
+            && self.first_instance + self.instance_count == next.first_instance
+    }
+}
+
+/// 等待状态排序的绘制
+#[derive(Debug, Clone)]
+pub struct QueuedDraw {
+    pub state: RenderStateKey,
+    pub vertex_buffer_id: u32,
+    pub command: DrawCommand,
+}
+
+/// 绘制序列中各类状态切换的次数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateChangeStats {
+    pub pipeline: u32,
+    pub bind_group: u32,
+    pub vertex_buffer: u32,
+}
+
+impl StateChangeStats {
+    /// 统计按顺序执行绘制时的状态切换 (第一次绑定也计入)
+    pub fn count(draws: &[QueuedDraw]) -> Self {
+        let mut stats = Self::default();
+        let mut previous: Option<&QueuedDraw> = None;
+        for draw in draws {
+            let changed =
+                |f: fn(&QueuedDraw) -> u32| !matches!(previous, Some(p) if f(p) == f(draw));
+            stats.pipeline += changed(|d| d.state.pipeline_id) as u32;
+            stats.bind_group += changed(|d| d.state.bind_group_id) as u32;
+            stats.vertex_buffer += changed(|d| d.vertex_buffer_id) as u32;
+            previous = Some(draw);
+        }
+        stats
+    }
+
+    pub fn total(&self) -> u32 {
+        self.pipeline + self.bind_group + self.vertex_buffer
+    }
+}
+
+/// 状态排序与合并的结果
+#[derive(Debug, Clone, Default)]
+pub struct StateSortResult {
+    /// 排序并合并后的绘制
+    pub draws: Vec<QueuedDraw>,
+    /// 排序前的状态切换
+    pub before: StateChangeStats,
+    /// 排序后的状态切换
+    pub after: StateChangeStats,
+    /// 被合并进实例化绘制的命令数
+    pub merged_draws: u32,
+}
+
 /// 绘制调用优化器
 #[derive(Default)]
 pub struct DrawCallOptimizer {
@@ -47,6 +114,7 @@ pub struct DrawCallOptimizer {
     command_batches: Vec<Vec<DrawCommand>>,
     current_state: Option<RenderStateKey>,
     state_changes: u32,
+    queued: Vec<QueuedDraw>,
 }
 
 impl DrawCallOptimizer {
@@ -97,11 +165,78 @@ impl DrawCallOptimizer {
         }
     }
 
+    /// 将绘制加入队列，等待 `sort_and_merge` 统一排序
+    pub fn queue_draw(
+        &mut self,
+        command: DrawCommand,
+        state: RenderStateKey,
+        vertex_buffer_id: u32,
+    ) {
+        self.queued.push(QueuedDraw {
+            state,
+            vertex_buffer_id,
+            command,
+        });
+    }
+
+    /// 队列中的绘制数
+    pub fn queued_count(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// 按 (管线, 绑定组, 顶点缓冲区) 排序队列中的绘制以减少状态切换，
+    /// 并将状态相同、几何体相同且实例区间相邻的绘制合并为实例化绘制
+    ///
+    /// 排序是稳定的，排序键相同的绘制保持提交顺序。节省的状态切换记录到 `metrics`。
+    pub fn sort_and_merge(&mut self, metrics: &mut RenderMetrics) -> StateSortResult {
+        let mut queued = std::mem::take(&mut self.queued);
+        let before = StateChangeStats::count(&queued);
+        let submitted = queued.len() as u32;
+
+        queued.sort_by_key(|draw| {
+            (
+                draw.state.pipeline_id,
+                draw.state.bind_group_id,
+                draw.vertex_buffer_id,
+            )
+        });
+
+        let mut draws: Vec<QueuedDraw> = Vec::with_capacity(queued.len());
+        let mut merged_draws = 0;
+        for draw in queued {
+            if let Some(last) = draws.last_mut() {
+                if last.state == draw.state
+                    && last.vertex_buffer_id == draw.vertex_buffer_id
+                    && last.command.can_merge_instances(&draw.command)
+                {
+                    last.command.instance_count += draw.command.instance_count;
+                    merged_draws += 1;
+                    continue;
+                }
+            }
+            draws.push(draw);
+        }
+
+        let after = StateChangeStats::count(&draws);
+        metrics.total_draw_calls += submitted;
+        metrics.batched_draw_calls += draws.len() as u32;
+        metrics.state_changes += after.total();
+        metrics.saved_state_changes += before.total().saturating_sub(after.total());
+
+        StateSortResult {
+            draws,
+            before,
+            after,
+            merged_draws,
+        }
+    }
+
     pub fn clear(&mut self) {
         self.state_cache.clear();
         self.command_batches.clear();
         self.current_state = None;
         self.state_changes = 0;
+        self.queued.clear();
     }
 }
 
@@ -229,6 +364,139 @@ pub struct GPUMemoryManager {
     allocated_vram: u64,
     max_vram: u64,
     allocation_count: usize,
+    /// 子分配器的后备缓冲区大小
+    backing_buffer_size: u64,
+    /// 按缓冲区用途区分的子分配器
+    suballocators: HashMap<wgpu::BufferUsages, BufferSuballocator>,
+}
+
+/// 子分配器默认的后备缓冲区大小 (4MB)
+pub const DEFAULT_BACKING_BUFFER_SIZE: u64 = 4 * 1024 * 1024;
+
+/// 从后备缓冲区切出的区域
+#[derive(Debug, Clone)]
+pub struct BufferSuballocation {
+    pub buffer: Arc<wgpu::Buffer>,
+    pub offset: u64,
+    pub size: u64,
+    backing_index: usize,
+    block_size: u64,
+}
+
+/// 缓冲区子分配器
+///
+/// 从较大的后备缓冲区中切分 2 的幂大小的固定块，每种块大小维护一个空闲列表。
+/// 块大小不小于偏移对齐要求，因此返回的偏移都满足 uniform/storage 绑定的对齐。
+pub struct BufferSuballocator {
+    usage: wgpu::BufferUsages,
+    backing_size: u64,
+    alignment: u64,
+    backings: Vec<Arc<wgpu::Buffer>>,
+    /// 最后一个后备缓冲区中尚未切分部分的起始偏移
+    cursor: u64,
+    free_lists: HashMap<u64, Vec<(usize, u64)>>,
+}
+
+impl BufferSuballocator {
+    pub fn new(device: &wgpu::Device, usage: wgpu::BufferUsages, backing_size: u64) -> Self {
+        let limits = device.limits();
+        let mut alignment = wgpu::COPY_BUFFER_ALIGNMENT;
+        if usage.contains(wgpu::BufferUsages::UNIFORM) {
+            alignment = alignment.max(limits.min_uniform_buffer_offset_alignment as u64);
+        }
+        if usage.contains(wgpu::BufferUsages::STORAGE) {
+            alignment = alignment.max(limits.min_storage_buffer_offset_alignment as u64);
+        }
+
+        Self {
+            usage,
+            backing_size,
+            alignment,
+            backings: Vec::new(),
+            cursor: 0,
+            free_lists: HashMap::new(),
+        }
+    }
+
+    /// 返回偏移的对齐要求
+    pub fn alignment(&self) -> u64 {
+        self.alignment
+    }
+
+    /// 已创建的后备缓冲区数量
+    pub fn backing_count(&self) -> usize {
+        self.backings.len()
+    }
+
+    /// 为请求的大小选择块大小
+    fn block_size_for(&self, size: u64) -> u64 {
+        size.max(self.alignment).next_power_of_two()
+    }
+
+    /// 分配一个区域。需要新的后备缓冲区时通过 `on_new_backing` 申请，返回错误则分配失败
+    fn allocate_with(
+        &mut self,
+        device: &wgpu::Device,
+        size: u64,
+        on_new_backing: impl FnOnce(u64) -> Result<(), String>,
+    ) -> Result<BufferSuballocation, String> {
+        let block_size = self.block_size_for(size);
+        if size == 0 || block_size > self.backing_size {
+            return Err(format!(
+                "Cannot suballocate {} bytes from {} byte backing buffers",
+                size, self.backing_size
+            ));
+        }
+
+        let (backing_index, offset) = match self
+            .free_lists
+            .get_mut(&block_size)
+            .and_then(|list| list.pop())
+        {
+            Some(region) => region,
+            None => {
+                if self.backings.is_empty() || self.cursor + block_size > self.backing_size {
+                    on_new_backing(self.backing_size)?;
+                    self.backings
+                        .push(Arc::new(device.create_buffer(&wgpu::BufferDescriptor {
+                            label: Some("Suballocator Backing Buffer"),
+                            size: self.backing_size,
+                            usage: self.usage,
+                            mapped_at_creation: false,
+                        })));
+                    self.cursor = 0;
+                }
+                let offset = self.cursor;
+                self.cursor += block_size;
+                (self.backings.len() - 1, offset)
+            }
+        };
+
+        Ok(BufferSuballocation {
+            buffer: self.backings[backing_index].clone(),
+            offset,
+            size,
+            backing_index,
+            block_size,
+        })
+    }
+
+    /// 分配一个区域
+    pub fn allocate(
+        &mut self,
+        device: &wgpu::Device,
+        size: u64,
+    ) -> Result<BufferSuballocation, String> {
+        self.allocate_with(device, size, |_| Ok(()))
+    }
+
+    /// 归还区域，之后相同块大小的分配会复用它
+    pub fn free(&mut self, allocation: BufferSuballocation) {
+        self.free_lists
+            .entry(allocation.block_size)
+            .or_default()
+            .push((allocation.backing_index, allocation.offset));
+    }
 }
 
 impl GPUMemoryManager {
@@ -237,9 +505,16 @@ impl GPUMemoryManager {
             allocated_vram: 0,
             max_vram,
             allocation_count: 0,
+            backing_buffer_size: DEFAULT_BACKING_BUFFER_SIZE,
+            suballocators: HashMap::new(),
         }
     }
 
+    /// 设置之后新建子分配器的后备缓冲区大小
+    pub fn set_backing_buffer_size(&mut self, size: u64) {
+        self.backing_buffer_size = size;
+    }
+
     pub fn allocate(&mut self, size: u64) -> Result<u64, String> {
         if self.allocated_vram + size > self.max_vram {
             return Err(format!(
@@ -265,10 +540,53 @@ impl GPUMemoryManager {
     pub fn get_available_memory(&self) -> u64 {
         self.max_vram.saturating_sub(self.allocated_vram)
     }
+
+    /// 从指定用途的后备缓冲区中子分配一个区域
+    ///
+    /// 只有新建后备缓冲区时才计入显存占用，小分配不会各自创建 wgpu 缓冲区。
+    pub fn suballocate(
+        &mut self,
+        device: &wgpu::Device,
+        usage: wgpu::BufferUsages,
+        size: u64,
+    ) -> Result<BufferSuballocation, String> {
+        let backing_size = self.backing_buffer_size;
+        let suballocator = self
+            .suballocators
+            .entry(usage)
+            .or_insert_with(|| BufferSuballocator::new(device, usage, backing_size));
+
+        let (allocated_vram, max_vram) = (&mut self.allocated_vram, self.max_vram);
+        let allocation_count = &mut self.allocation_count;
+        suballocator.allocate_with(device, size, |backing_size| {
+            if *allocated_vram + backing_size > max_vram {
+                return Err(format!(
+                    "Not enough VRAM: requested {}, available {}",
+                    backing_size,
+                    max_vram - *allocated_vram
+                ));
+            }
+            *allocated_vram += backing_size;
+            *allocation_count += 1;
+            Ok(())
+        })
+    }
+
+    /// 归还子分配的区域
+    pub fn free(&mut self, usage: wgpu::BufferUsages, allocation: BufferSuballocation) {
+        if let Some(suballocator) = self.suballocators.get_mut(&usage) {
+            suballocator.free(allocation);
+        }
+    }
+
+    /// 指定用途的子分配器
+    pub fn suballocator(&self, usage: wgpu::BufferUsages) -> Option<&BufferSuballocator> {
+        self.suballocators.get(&usage)
+    }
 }
 
 /// 渲染性能指标
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct RenderMetrics {
     pub total_draw_calls: u32,
     pub batched_draw_calls: u32,
@@ -277,6 +595,8 @@ pub struct RenderMetrics {
     pub vertex_count: u64,
     pub triangle_count: u64,
     pub state_changes: u32,
+    /// 状态排序节省的状态切换次数
+    pub saved_state_changes: u32,
 }
 
 impl RenderMetrics {
@@ -307,7 +627,7 @@ impl RenderMetrics {
         tracing::info!(target: "render", "CPU time: {:.2}ms", self.cpu_time_ms);
         tracing::info!(target: "render", "Vertices: {} ({:.2}M/ms)", self.vertex_count, self.get_vertices_per_ms() / 1_000_000.0);
         tracing::info!(target: "render", "Triangles: {} ({:.2}M/ms)", self.triangle_count, self.get_triangles_per_ms() / 1_000_000.0);
-        tracing::info!(target: "render", "State changes: {}", self.state_changes);
+        tracing::info!(target: "render", "State changes: {} ({} saved by sorting)", self.state_changes, self.saved_state_changes);
     }
 }
 
@@ -401,6 +721,96 @@ mod tests {
         assert!(alloc4.is_ok());
     }
 
+    #[test]
+    fn test_sort_and_merge_reduces_pipeline_switches() {
+        let state = |pipeline_id| RenderStateKey {
+            pipeline_id,
+            bind_group_id: 1,
+            blend_mode: 0,
+            depth_test: true,
+        };
+        let draw = |index_count, first_instance| DrawCommand {
+            command_type: RenderCommandType::DrawIndexed,
+            vertex_count: 0,
+            instance_count: 1,
+            first_vertex: 0,
+            first_instance,
+            index_count,
+            index_offset: 0,
+        };
+
+        let mut optimizer = DrawCallOptimizer::new();
+        // 管线 1 和 2 交替提交
+        for i in 0..4 {
+            optimizer.queue_draw(draw(36, i), state(1), 0);
+            optimizer.queue_draw(draw(600 + i, 0), state(2), 1);
+        }
+
+        let mut metrics = RenderMetrics::default();
+        let result = optimizer.sort_and_merge(&mut metrics);
+
+        assert_eq!(result.before.pipeline, 8);
+        assert_eq!(result.after.pipeline, 2);
+        // 管线 1 的四个实例相邻，合并为一次实例化绘制
+        assert_eq!(result.merged_draws, 3);
+        assert_eq!(result.draws.len(), 5);
+        assert_eq!(result.draws[0].command.instance_count, 4);
+        // 无法合并的绘制保持原有相对顺序
+        let index_counts: Vec<u32> = result.draws[1..]
+            .iter()
+            .map(|d| d.command.index_count)
+            .collect();
+        assert_eq!(index_counts, [600, 601, 602, 603]);
+
+        assert_eq!(metrics.total_draw_calls, 8);
+        assert_eq!(metrics.batched_draw_calls, 5);
+        assert_eq!(
+            metrics.saved_state_changes,
+            result.before.total() - result.after.total()
+        );
+        assert!(metrics.saved_state_changes > 0);
+        assert_eq!(optimizer.queued_count(), 0);
+    }
+
+    #[test]
+    fn test_suballocation_reuses_freed_blocks() {
+        let Some((device, _queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let usage = wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST;
+        let mut manager = GPUMemoryManager::new(64 * 1024 * 1024);
+        manager.set_backing_buffer_size(64 * 1024);
+
+        let mut regions: Vec<BufferSuballocation> = (0..100)
+            .map(|_| manager.suballocate(&device, usage, 200).unwrap())
+            .collect();
+        let suballocator = manager.suballocator(usage).unwrap();
+        let alignment = suballocator.alignment();
+        assert_eq!(suballocator.backing_count(), 1);
+        assert!(regions.iter().all(|r| r.offset % alignment == 0));
+        assert!(regions.iter().all(|r| r.size == 200));
+        assert_eq!(manager.get_available_memory(), 64 * 1024 * 1024 - 64 * 1024);
+
+        // 释放一半后重新分配，应复用释放的区域而不增加后备缓冲区
+        let freed: Vec<BufferSuballocation> = regions.drain(..50).collect();
+        let mut freed_offsets: Vec<u64> = freed.iter().map(|r| r.offset).collect();
+        for region in freed {
+            manager.free(usage, region);
+        }
+        let mut reused: Vec<u64> = (0..50)
+            .map(|_| manager.suballocate(&device, usage, 100).unwrap().offset)
+            .collect();
+        freed_offsets.sort_unstable();
+        reused.sort_unstable();
+        assert_eq!(reused, freed_offsets);
+        assert_eq!(manager.suballocator(usage).unwrap().backing_count(), 1);
+        assert_eq!(manager.get_available_memory(), 64 * 1024 * 1024 - 64 * 1024);
+
+        // 超过后备缓冲区大小的请求失败
+        assert!(manager.suballocate(&device, usage, 128 * 1024).is_err());
+    }
+
     #[test]
     fn test_render_metrics() {
         let metrics = RenderMetrics {
@@ -411,6 +821,7 @@ mod tests {
             vertex_count: 1_000_000,
             triangle_count: 333_333,
             state_changes: 50,
+            saved_state_changes: 0,
         };
 
         assert_eq!(metrics.get_draw_call_reduction(), 0.9);
@@ -11,8 +11,24 @@
 //! let mut config = PostProcessConfig::default();
 //! config.antialiasing = AntialiasingMode::FXAA;
 //! config.fxaa_quality = FxaaQuality::High;
+//! config.fxaa_edge_threshold = clamp_edge_threshold(0.1);
 //! ```
 
+/// FXAA 边缘检测阈值的有效范围，过低会处理纹理细节导致模糊，过高则漏掉边缘
+pub const EDGE_THRESHOLD_RANGE: (f32, f32) = (0.063, 0.333);
+/// FXAA 最小边缘阈值的有效范围，用于跳过暗部的低对比度像素
+pub const EDGE_THRESHOLD_MIN_RANGE: (f32, f32) = (0.0156, 0.0833);
+
+/// 将边缘检测阈值限制在有效范围内
+pub fn clamp_edge_threshold(threshold: f32) -> f32 {
+    threshold.clamp(EDGE_THRESHOLD_RANGE.0, EDGE_THRESHOLD_RANGE.1)
+}
+
+/// 将最小边缘阈值限制在有效范围内
+pub fn clamp_edge_threshold_min(threshold: f32) -> f32 {
+    threshold.clamp(EDGE_THRESHOLD_MIN_RANGE.0, EDGE_THRESHOLD_MIN_RANGE.1)
+}
+
 /// 抗锯齿模式
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AntialiasingMode {
@@ -42,7 +58,7 @@ pub enum FxaaQuality {
 }
 
 impl FxaaQuality {
-    /// 获取对应的采样迭代次数
+    /// 获取沿边缘搜索端点的迭代次数
     pub fn iterations(&self) -> u32 {
         match self {
             FxaaQuality::Low => 4,
@@ -87,8 +103,8 @@ pub struct FxaaUniforms {
     pub edge_threshold_min: f32,
     /// 子像素质量
     pub subpix_quality: f32,
-    /// 填充
-    pub _pad: f32,
+    /// 沿边缘搜索端点的迭代次数
+    pub search_steps: u32,
 }
 
 /// FXAA 渲染通道
@@ -103,6 +119,10 @@ pub struct FxaaPass {
     sampler: wgpu::Sampler,
     /// 当前质量
     quality: FxaaQuality,
+    /// 边缘检测阈值
+    edge_threshold: f32,
+    /// 最小边缘阈值
+    edge_threshold_min: f32,
 }
 
 impl FxaaPass {
@@ -212,12 +232,16 @@ impl FxaaPass {
             uniform_buffer,
             sampler,
             quality: FxaaQuality::default(),
+            edge_threshold: FxaaQuality::default().edge_threshold(),
+            edge_threshold_min: FxaaQuality::default().edge_threshold_min(),
         }
     }
 
-    /// 设置 FXAA 质量
+    /// 设置 FXAA 质量，阈值重置为该预设的值
     pub fn set_quality(&mut self, quality: FxaaQuality) {
         self.quality = quality;
+        self.edge_threshold = quality.edge_threshold();
+        self.edge_threshold_min = quality.edge_threshold_min();
     }
 
     /// 获取当前质量
@@ -225,6 +249,33 @@ impl FxaaPass {
         self.quality
     }
 
+    /// 设置边缘检测阈值，越低越平滑，越高越锐利
+    pub fn set_edge_threshold(&mut self, threshold: f32) {
+        self.edge_threshold = clamp_edge_threshold(threshold);
+    }
+
+    /// 获取边缘检测阈值
+    pub fn edge_threshold(&self) -> f32 {
+        self.edge_threshold
+    }
+
+    /// 设置最小边缘阈值
+    pub fn set_edge_threshold_min(&mut self, threshold: f32) {
+        self.edge_threshold_min = clamp_edge_threshold_min(threshold);
+    }
+
+    /// 获取最小边缘阈值
+    pub fn edge_threshold_min(&self) -> f32 {
+        self.edge_threshold_min
+    }
+
+    /// 应用后处理配置中的 FXAA 设置
+    pub fn apply_config(&mut self, config: &super::PostProcessConfig) {
+        self.quality = config.fxaa_quality;
+        self.set_edge_threshold(config.fxaa_edge_threshold);
+        self.set_edge_threshold_min(config.fxaa_edge_threshold_min);
+    }
+
     /// 执行 FXAA 渲染
     pub fn render(
         &self,
@@ -240,10 +291,10 @@ impl FxaaPass {
         let uniforms = FxaaUniforms {
             screen_size: [width as f32, height as f32],
             pixel_size: [1.0 / width as f32, 1.0 / height as f32],
-            edge_threshold: self.quality.edge_threshold(),
-            edge_threshold_min: self.quality.edge_threshold_min(),
+            edge_threshold: self.edge_threshold,
+            edge_threshold_min: self.edge_threshold_min,
             subpix_quality: 0.75,
-            _pad: 0.0,
+            search_steps: self.quality.iterations(),
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
 
@@ -545,6 +596,35 @@ mod tests {
         assert!(FxaaQuality::Low.iterations() < FxaaQuality::High.iterations());
     }
 
+    #[test]
+    fn test_fxaa_ultra_and_threshold_clamp() {
+        assert!(FxaaQuality::Ultra.iterations() > FxaaQuality::Medium.iterations());
+        assert!(FxaaQuality::Ultra.iterations() > FxaaQuality::High.iterations());
+
+        // 所有预设都在有效范围内
+        for quality in [
+            FxaaQuality::Low,
+            FxaaQuality::Medium,
+            FxaaQuality::High,
+            FxaaQuality::Ultra,
+        ] {
+            assert_eq!(
+                clamp_edge_threshold(quality.edge_threshold()),
+                quality.edge_threshold()
+            );
+            assert_eq!(
+                clamp_edge_threshold_min(quality.edge_threshold_min()),
+                quality.edge_threshold_min()
+            );
+        }
+
+        assert_eq!(clamp_edge_threshold(0.0), EDGE_THRESHOLD_RANGE.0);
+        assert_eq!(clamp_edge_threshold(1.0), EDGE_THRESHOLD_RANGE.1);
+        assert_eq!(clamp_edge_threshold(0.1), 0.1);
+        assert_eq!(clamp_edge_threshold_min(-1.0), EDGE_THRESHOLD_MIN_RANGE.0);
+        assert_eq!(clamp_edge_threshold_min(0.5), EDGE_THRESHOLD_MIN_RANGE.1);
+    }
+
     #[test]
     fn test_halton_sequence() {
         let sequence = TaaPass::generate_halton_sequence(8);
@@ -0,0 +1,583 @@
+//! 景深（Depth of Field）后处理效果
+//!
+//! 根据深度缓冲计算每个像素的弥散圆（Circle of Confusion），再按弥散圆大小做可分离的散景模糊。
+//!
+//! ## 算法流程
+//! 1. CoC 计算：将深度线性化，与焦距比较得到弥散圆半径
+//! 2. 水平模糊：按中心像素的 CoC 缩放采样范围
+//! 3. 垂直模糊：同上，得到近似方形的散景
+//!
+//! CoC 为 0 的像素只采样自身，焦点范围内的区域保持清晰；
+//! 采样点自身的 CoC 小于偏移距离时不参与混合，避免清晰物体渗入模糊背景。
+
+/// 弥散圆的最大半径（像素）
+pub const MAX_COC_RADIUS: f32 = 8.0;
+
+/// 将 `[0, 1]` 的透视深度还原为视空间线性距离
+pub fn linearize_depth(depth: f32, near: f32, far: f32) -> f32 {
+    near * far / (far - depth * (far - near))
+}
+
+/// 计算弥散圆大小，返回 `[0, 1]`，1 对应 `MAX_COC_RADIUS`
+///
+/// `focal_distance` 两侧各 `focal_range / 2` 内为清晰区域，之外按薄透镜模型
+/// `aperture * |d - f| / d` 增长，离焦点越远越模糊。
+pub fn circle_of_confusion(
+    distance: f32,
+    focal_distance: f32,
+    focal_range: f32,
+    aperture: f32,
+) -> f32 {
+    let excess = ((distance - focal_distance).abs() - focal_range * 0.5).max(0.0);
+    (aperture * excess / distance.max(1e-4)).clamp(0.0, 1.0)
+}
+
+/// 景深通道 Uniform 数据
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct DofUniforms {
+    /// 纹理尺寸 (width, height)
+    pub texture_size: [f32; 2],
+    /// 相机近裁剪面
+    pub near: f32,
+    /// 相机远裁剪面
+    pub far: f32,
+    /// 焦距
+    pub focal_distance: f32,
+    /// 清晰范围
+    pub focal_range: f32,
+    /// 光圈大小，越大越模糊
+    pub aperture: f32,
+    /// 弥散圆最大半径（像素）
+    pub max_coc_radius: f32,
+}
+
+/// 景深渲染通道
+pub struct DofPass {
+    /// CoC 计算管线
+    coc_pipeline: wgpu::RenderPipeline,
+    /// 水平模糊管线
+    blur_h_pipeline: wgpu::RenderPipeline,
+    /// 垂直模糊管线
+    blur_v_pipeline: wgpu::RenderPipeline,
+
+    /// CoC 计算绑定组布局
+    coc_bind_group_layout: wgpu::BindGroupLayout,
+    /// 模糊绑定组布局
+    blur_bind_group_layout: wgpu::BindGroupLayout,
+
+    /// CoC 纹理
+    coc_texture: wgpu::Texture,
+    coc_view: wgpu::TextureView,
+
+    /// 水平模糊结果
+    temp_texture: wgpu::Texture,
+    temp_view: wgpu::TextureView,
+
+    /// 输出纹理
+    output_texture: wgpu::Texture,
+    output_view: wgpu::TextureView,
+
+    /// 采样器
+    sampler: wgpu::Sampler,
+
+    /// Uniform 缓冲区
+    uniform_buffer: wgpu::Buffer,
+
+    /// 相机裁剪面
+    near: f32,
+    far: f32,
+
+    /// 屏幕尺寸
+    width: u32,
+    height: u32,
+}
+
+impl DofPass {
+    /// 创建景深通道
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("DoF Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        let uniform_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let color_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        let coc_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("DoF CoC BGL"),
+                entries: &[
+                    // Uniforms
+                    uniform_entry(2),
+                    // 深度纹理
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 4,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
+        let blur_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("DoF Blur BGL"),
+                entries: &[
+                    // 输入颜色
+                    color_entry(0),
+                    // 采样器
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    // Uniforms
+                    uniform_entry(2),
+                    // CoC 纹理
+                    color_entry(3),
+                ],
+            });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("DoF Uniform Buffer"),
+            size: std::mem::size_of::<DofUniforms>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("DoF Shader"),
+            source: wgpu::ShaderSource::Wgsl(DOF_SHADER.into()),
+        });
+
+        let coc_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("DoF CoC Pipeline Layout"),
+            bind_group_layouts: &[&coc_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let blur_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("DoF Blur Pipeline Layout"),
+            bind_group_layouts: &[&blur_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let coc_pipeline = Self::create_pipeline(
+            device,
+            &coc_layout,
+            &shader,
+            "fs_coc",
+            wgpu::TextureFormat::R16Float,
+        );
+        let blur_h_pipeline = Self::create_pipeline(
+            device,
+            &blur_layout,
+            &shader,
+            "fs_blur_h",
+            wgpu::TextureFormat::Rgba16Float,
+        );
+        let blur_v_pipeline = Self::create_pipeline(
+            device,
+            &blur_layout,
+            &shader,
+            "fs_blur_v",
+            wgpu::TextureFormat::Rgba16Float,
+        );
+
+        let (coc_texture, coc_view) = Self::create_target(
+            device,
+            width,
+            height,
+            "DoF CoC",
+            wgpu::TextureFormat::R16Float,
+        );
+        let (temp_texture, temp_view) = Self::create_target(
+            device,
+            width,
+            height,
+            "DoF Temp",
+            wgpu::TextureFormat::Rgba16Float,
+        );
+        let (output_texture, output_view) = Self::create_target(
+            device,
+            width,
+            height,
+            "DoF Output",
+            wgpu::TextureFormat::Rgba16Float,
+        );
+
+        Self {
+            coc_pipeline,
+            blur_h_pipeline,
+            blur_v_pipeline,
+            coc_bind_group_layout,
+            blur_bind_group_layout,
+            coc_texture,
+            coc_view,
+            temp_texture,
+            temp_view,
+            output_texture,
+            output_view,
+            sampler,
+            uniform_buffer,
+            near: 0.1,
+            far: 1000.0,
+            width,
+            height,
+        }
+    }
+
+    /// 创建渲染目标
+    fn create_target(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        label: &str,
+        format: wgpu::TextureFormat,
+    ) -> (wgpu::Texture, wgpu::TextureView) {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        (texture, view)
+    }
+
+    /// 创建渲染管线
+    fn create_pipeline(
+        device: &wgpu::Device,
+        layout: &wgpu::PipelineLayout,
+        shader: &wgpu::ShaderModule,
+        fs_entry: &str,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&format!("DoF {} Pipeline", fs_entry)),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: "vs_fullscreen",
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: fs_entry,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        })
+    }
+
+    /// 设置相机裁剪面，用于将深度线性化
+    pub fn set_clip_planes(&mut self, near: f32, far: f32) {
+        self.near = near.max(1e-4);
+        self.far = far.max(self.near + 1e-3);
+    }
+
+    /// 调整大小
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        if width == self.width && height == self.height {
+            return;
+        }
+
+        self.width = width;
+        self.height = height;
+
+        (self.coc_texture, self.coc_view) = Self::create_target(
+            device,
+            width,
+            height,
+            "DoF CoC",
+            wgpu::TextureFormat::R16Float,
+        );
+        (self.temp_texture, self.temp_view) = Self::create_target(
+            device,
+            width,
+            height,
+            "DoF Temp",
+            wgpu::TextureFormat::Rgba16Float,
+        );
+        (self.output_texture, self.output_view) = Self::create_target(
+            device,
+            width,
+            height,
+            "DoF Output",
+            wgpu::TextureFormat::Rgba16Float,
+        );
+    }
+
+    /// 执行景深渲染
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        input_view: &wgpu::TextureView,
+        depth_view: &wgpu::TextureView,
+        focal_distance: f32,
+        focal_range: f32,
+        aperture: f32,
+    ) {
+        let uniforms = DofUniforms {
+            texture_size: [self.width as f32, self.height as f32],
+            near: self.near,
+            far: self.far,
+            focal_distance,
+            focal_range,
+            aperture,
+            max_coc_radius: MAX_COC_RADIUS,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        // 1. CoC 计算
+        let coc_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("DoF CoC BG"),
+            layout: &self.coc_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(depth_view),
+                },
+            ],
+        });
+        self.draw(
+            encoder,
+            "DoF CoC Pass",
+            &self.coc_view,
+            &self.coc_pipeline,
+            &coc_bind_group,
+        );
+
+        // 2. 水平模糊
+        let blur_h_bind_group = self.create_blur_bind_group(device, input_view);
+        self.draw(
+            encoder,
+            "DoF Horizontal Blur Pass",
+            &self.temp_view,
+            &self.blur_h_pipeline,
+            &blur_h_bind_group,
+        );
+
+        // 3. 垂直模糊
+        let blur_v_bind_group = self.create_blur_bind_group(device, &self.temp_view);
+        self.draw(
+            encoder,
+            "DoF Vertical Blur Pass",
+            &self.output_view,
+            &self.blur_v_pipeline,
+            &blur_v_bind_group,
+        );
+    }
+
+    /// 创建模糊绑定组
+    fn create_blur_bind_group(
+        &self,
+        device: &wgpu::Device,
+        input_view: &wgpu::TextureView,
+    ) -> wgpu::BindGroup {
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("DoF Blur BG"),
+            layout: &self.blur_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&self.coc_view),
+                },
+            ],
+        })
+    }
+
+    /// 绘制全屏三角形到目标纹理
+    fn draw(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        label: &str,
+        target: &wgpu::TextureView,
+        pipeline: &wgpu::RenderPipeline,
+        bind_group: &wgpu::BindGroup,
+    ) {
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        rpass.set_pipeline(pipeline);
+        rpass.set_bind_group(0, bind_group, &[]);
+        rpass.draw(0..3, 0..1);
+    }
+
+    /// 获取输出纹理视图
+    pub fn output_view(&self) -> &wgpu::TextureView {
+        &self.output_view
+    }
+}
+
+/// 景深着色器
+const DOF_SHADER: &str = r#"
+struct DofUniforms {
+    texture_size: vec2<f32>,
+    near: f32,
+    far: f32,
+    focal_distance: f32,
+    focal_range: f32,
+    aperture: f32,
+    max_coc_radius: f32,
+};
+
+// 模糊阶段使用 0-3，CoC 计算阶段使用 2 和 4
+@group(0) @binding(0) var input_texture: texture_2d<f32>;
+@group(0) @binding(1) var input_sampler: sampler;
+@group(0) @binding(2) var<uniform> uniforms: DofUniforms;
+@group(0) @binding(3) var coc_texture: texture_2d<f32>;
+@group(0) @binding(4) var depth_texture: texture_depth_2d;
+
+// 每侧采样数
+const BLUR_TAPS: i32 = 8;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// 全屏三角形顶点着色器
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let x = f32((vertex_index << 1u) & 2u);
+    let y = f32(vertex_index & 2u);
+
+    out.position = vec4<f32>(x * 2.0 - 1.0, y * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, 1.0 - y);
+
+    return out;
+}
+
+fn linearize_depth(depth: f32) -> f32 {
+    let near = uniforms.near;
+    let far = uniforms.far;
+    return near * far / (far - depth * (far - near));
+}
+
+@fragment
+fn fs_coc(in: VertexOutput) -> @location(0) f32 {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let coord = clamp(vec2<i32>(in.position.xy), vec2<i32>(0), size - vec2<i32>(1));
+    let distance = linearize_depth(textureLoad(depth_texture, coord, 0));
+
+    // 焦点两侧各 focal_range / 2 内保持清晰
+    let excess = max(abs(distance - uniforms.focal_distance) - uniforms.focal_range * 0.5, 0.0);
+    return clamp(uniforms.aperture * excess / max(distance, 1e-4), 0.0, 1.0);
+}
+
+fn bokeh_blur(uv: vec2<f32>, direction: vec2<f32>) -> vec4<f32> {
+    let center = textureSample(input_texture, input_sampler, uv);
+    let center_coc = textureSample(coc_texture, input_sampler, uv).r;
+    let radius = center_coc * uniforms.max_coc_radius;
+    let texel = direction / uniforms.texture_size;
+
+    var color = center.rgb;
+    var total_weight = 1.0;
+
+    for (var i = -BLUR_TAPS; i <= BLUR_TAPS; i++) {
+        if i == 0 {
+            continue;
+        }
+        let offset = f32(i) / f32(BLUR_TAPS) * radius;
+        let sample_uv = uv + texel * offset;
+        // 采样点自身的 CoC 覆盖不到中心时不参与混合，避免清晰物体被抹开
+        let sample_coc = textureSample(coc_texture, input_sampler, sample_uv).r;
+        let weight = select(0.0, 1.0, sample_coc * uniforms.max_coc_radius >= abs(offset) - 0.5);
+        color += textureSample(input_texture, input_sampler, sample_uv).rgb * weight;
+        total_weight += weight;
+    }
+
+    return vec4<f32>(color / total_weight, center.a);
+}
+
+@fragment
+fn fs_blur_h(in: VertexOutput) -> @location(0) vec4<f32> {
+    return bokeh_blur(in.uv, vec2<f32>(1.0, 0.0));
+}
+
+@fragment
+fn fs_blur_v(in: VertexOutput) -> @location(0) vec4<f32> {
+    return bokeh_blur(in.uv, vec2<f32>(0.0, 1.0));
+}
+"#;
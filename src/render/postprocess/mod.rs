@@ -4,6 +4,7 @@
 //! - Antialiasing（抗锯齿：FXAA/TAA）
 //! - Bloom（辉光效果）
 //! - SSAO（屏幕空间环境光遮蔽）
+//! - DoF（景深）
 //! - Tonemap（HDR色调映射）
 //!
 //! # 示例
@@ -13,21 +14,25 @@
 //! postprocess.set_antialiasing(AntialiasingMode::FXAA);
 //! postprocess.set_bloom_enabled(true);
 //! postprocess.set_bloom_intensity(0.8);
-//! postprocess.render(&mut encoder, &scene_texture, &output_view);
+//! postprocess.render(&mut encoder, &device, &queue, &scene_view, Some(&depth_view), Some(&camera), &output_view);
 //! ```
 
+use crate::ecs::Camera;
 use crate::impl_default;
 
 pub mod antialiasing;
 pub mod bloom;
+pub mod dof;
 pub mod ssao;
 pub mod tonemap;
 
 pub use antialiasing::{AntialiasingMode, FxaaPass, FxaaQuality, TaaPass};
 pub use bloom::BloomPass;
-pub use ssao::SsaoPass;
+pub use dof::DofPass;
+pub use ssao::{SsaoMode, SsaoPass};
 pub use tonemap::{TonemapOperator, TonemapPass};
 
+use antialiasing::{clamp_edge_threshold, clamp_edge_threshold_min};
 use wgpu::TextureFormat;
 
 /// 后处理配置
@@ -37,6 +42,10 @@ pub struct PostProcessConfig {
     pub antialiasing: AntialiasingMode,
     /// FXAA 质量等级
     pub fxaa_quality: FxaaQuality,
+    /// FXAA 边缘检测阈值，越低越平滑，越高越锐利
+    pub fxaa_edge_threshold: f32,
+    /// FXAA 最小边缘阈值，低于该对比度的像素不处理
+    pub fxaa_edge_threshold_min: f32,
 
     /// 是否启用 Bloom
     pub bloom_enabled: bool,
@@ -49,6 +58,8 @@ pub struct PostProcessConfig {
 
     /// 是否启用 SSAO
     pub ssao_enabled: bool,
+    /// SSAO 算法
+    pub ssao_mode: SsaoMode,
     /// SSAO 采样半径
     pub ssao_radius: f32,
     /// SSAO 强度
@@ -56,6 +67,15 @@ pub struct PostProcessConfig {
     /// SSAO 偏移
     pub ssao_bias: f32,
 
+    /// 是否启用景深
+    pub dof_enabled: bool,
+    /// 焦距（视空间距离）
+    pub focal_distance: f32,
+    /// 焦点前后保持清晰的范围
+    pub focal_range: f32,
+    /// 光圈大小，越大焦外越模糊
+    pub aperture: f32,
+
     /// 是否启用色调映射
     pub tonemap_enabled: bool,
     /// 色调映射算法
@@ -69,14 +89,21 @@ pub struct PostProcessConfig {
 impl_default!(PostProcessConfig {
     antialiasing: AntialiasingMode::FXAA,
     fxaa_quality: FxaaQuality::Medium,
+    fxaa_edge_threshold: 0.166,
+    fxaa_edge_threshold_min: 0.0625,
     bloom_enabled: true,
     bloom_intensity: 0.5,
     bloom_threshold: 1.0,
     bloom_radius: 5.0,
     ssao_enabled: false,
+    ssao_mode: SsaoMode::Classic,
     ssao_radius: 0.5,
     ssao_intensity: 1.0,
     ssao_bias: 0.025,
+    dof_enabled: false,
+    focal_distance: 10.0,
+    focal_range: 5.0,
+    aperture: 1.0,
     tonemap_enabled: true,
     tonemap_operator: TonemapOperator::ACES,
     exposure: 1.0,
@@ -97,7 +124,7 @@ pub struct PostProcessUniforms {
     pub exposure: f32,
     /// Gamma 值
     pub gamma: f32,
-    /// 色调映射算法 (0=None, 1=Reinhard, 2=ACES, 3=Filmic)
+    /// 色调映射算法 (0=None, 1=Reinhard, 2=ACES, 3=Filmic, 4=AgX, 5=PbrNeutral)
     pub tonemap_mode: u32,
     /// 填充对齐
     pub _pad: u32,
@@ -116,6 +143,9 @@ pub struct PostProcessPipeline {
     /// SSAO 通道
     ssao_pass: SsaoPass,
 
+    /// 景深通道
+    dof_pass: DofPass,
+
     /// Tonemap 通道
     tonemap_pass: TonemapPass,
 
@@ -198,12 +228,14 @@ impl PostProcessPipeline {
         // 创建各个后处理通道
         let bloom_pass = BloomPass::new(device, width, height);
         let ssao_pass = SsaoPass::new(device, width, height);
+        let dof_pass = DofPass::new(device, width, height);
         let tonemap_pass = TonemapPass::new(device, output_format);
 
         Self {
             config: PostProcessConfig::default(),
             bloom_pass,
             ssao_pass,
+            dof_pass,
             tonemap_pass,
             uniform_buffer,
             uniform_bind_group,
@@ -248,6 +280,7 @@ impl PostProcessPipeline {
         // 调整各通道大小
         self.bloom_pass.resize(device, width, height);
         self.ssao_pass.resize(device, width, height);
+        self.dof_pass.resize(device, width, height);
     }
 
     /// 更新 Uniform 数据
@@ -258,7 +291,7 @@ impl PostProcessPipeline {
             bloom_threshold: self.config.bloom_threshold,
             exposure: self.config.exposure,
             gamma: self.config.gamma,
-            tonemap_mode: self.config.tonemap_operator as u32,
+            tonemap_mode: self.config.tonemap_operator.tonemap_mode(),
             _pad: 0,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
@@ -271,20 +304,29 @@ impl PostProcessPipeline {
     /// - `device`: GPU 设备
     /// - `queue`: 命令队列
     /// - `scene_view`: 场景纹理视图（输入）
-    /// - `depth_view`: 深度纹理视图（用于 SSAO）
+    /// - `depth_view`: 深度纹理视图（用于 SSAO 和景深）
+    /// - `camera`: 当前活动相机，景深按其裁剪面线性化深度
     /// - `output_view`: 输出纹理视图
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
-        &self,
+        &mut self,
         encoder: &mut wgpu::CommandEncoder,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         scene_view: &wgpu::TextureView,
         depth_view: Option<&wgpu::TextureView>,
+        camera: Option<&Camera>,
         output_view: &wgpu::TextureView,
     ) {
         // 更新 uniforms
         self.update_uniforms(queue);
 
+        // 景深裁剪面跟随活动相机
+        if let Some(camera) = camera {
+            let (near, far) = camera.projection.clip_planes();
+            self.set_dof_clip_planes(near, far);
+        }
+
         let mut current_input = scene_view;
 
         // 1. SSAO 通道
@@ -299,12 +341,30 @@ impl PostProcessPipeline {
                     self.config.ssao_radius,
                     self.config.ssao_intensity,
                     self.config.ssao_bias,
+                    self.config.ssao_mode,
                 );
                 current_input = self.ssao_pass.output_view();
             }
         }
 
-        // 2. Bloom 通道
+        // 2. 景深通道
+        if self.config.dof_enabled {
+            if let Some(depth) = depth_view {
+                self.dof_pass.render(
+                    encoder,
+                    device,
+                    queue,
+                    current_input,
+                    depth,
+                    self.config.focal_distance,
+                    self.config.focal_range,
+                    self.config.aperture,
+                );
+                current_input = self.dof_pass.output_view();
+            }
+        }
+
+        // 3. Bloom 通道
         if self.config.bloom_enabled {
             self.bloom_pass.render(
                 encoder,
@@ -318,7 +378,7 @@ impl PostProcessPipeline {
             current_input = self.bloom_pass.output_view();
         }
 
-        // 3. Tonemap 通道（最终输出）
+        // 4. Tonemap 通道（最终输出）
         self.tonemap_pass.render(
             encoder,
             device,
@@ -336,6 +396,19 @@ impl PostProcessPipeline {
         &self.hdr_view
     }
 
+    /// 设置 FXAA 质量，阈值重置为该预设的值
+    pub fn set_fxaa_quality(&mut self, quality: FxaaQuality) {
+        self.config.fxaa_quality = quality;
+        self.config.fxaa_edge_threshold = quality.edge_threshold();
+        self.config.fxaa_edge_threshold_min = quality.edge_threshold_min();
+    }
+
+    /// 设置 FXAA 边缘检测阈值
+    pub fn set_fxaa_edge_threshold(&mut self, threshold: f32, threshold_min: f32) {
+        self.config.fxaa_edge_threshold = clamp_edge_threshold(threshold);
+        self.config.fxaa_edge_threshold_min = clamp_edge_threshold_min(threshold_min);
+    }
+
     /// 设置 Bloom 启用状态
     pub fn set_bloom_enabled(&mut self, enabled: bool) {
         self.config.bloom_enabled = enabled;
@@ -356,6 +429,11 @@ impl PostProcessPipeline {
         self.config.ssao_enabled = enabled;
     }
 
+    /// 设置 SSAO 算法
+    pub fn set_ssao_mode(&mut self, mode: SsaoMode) {
+        self.config.ssao_mode = mode;
+    }
+
     /// 设置 SSAO 参数
     pub fn set_ssao_params(&mut self, radius: f32, intensity: f32, bias: f32) {
         self.config.ssao_radius = radius.max(0.01);
@@ -363,6 +441,25 @@ impl PostProcessPipeline {
         self.config.ssao_bias = bias.clamp(0.0, 0.1);
     }
 
+    /// 设置景深启用状态
+    pub fn set_dof_enabled(&mut self, enabled: bool) {
+        self.config.dof_enabled = enabled;
+    }
+
+    /// 设置景深参数
+    pub fn set_dof_params(&mut self, focal_distance: f32, focal_range: f32, aperture: f32) {
+        self.config.focal_distance = focal_distance.max(0.01);
+        self.config.focal_range = focal_range.max(0.0);
+        self.config.aperture = aperture.clamp(0.0, 10.0);
+    }
+
+    /// 设置相机裁剪面，景深需要据此将深度线性化
+    ///
+    /// `render` 传入活动相机时会自动同步，无需手动调用。
+    pub fn set_dof_clip_planes(&mut self, near: f32, far: f32) {
+        self.dof_pass.set_clip_planes(near, far);
+    }
+
     /// 设置色调映射算法
     pub fn set_tonemap_operator(&mut self, operator: TonemapOperator) {
         self.config.tonemap_operator = operator;
@@ -9,7 +9,7 @@ struct FxaaUniforms {
     edge_threshold: f32,
     edge_threshold_min: f32,
     subpix_quality: f32,
-    _pad: f32,
+    search_steps: u32,
 };
 
 @group(0) @binding(0) var input_texture: texture_2d<f32>;
@@ -144,8 +144,7 @@ fn fxaa(uv: vec2<f32>) -> vec4<f32> {
     var reached_both = reached_neg && reached_pos;
     
     // 迭代搜索边缘端点
-    let iterations: i32 = 12;
-    for (var i: i32 = 0; i < iterations && !reached_both; i = i + 1) {
+    for (var i: u32 = 0u; i < uniforms.search_steps && !reached_both; i = i + 1u) {
         if !reached_neg {
             uv_neg -= offset;
             luma_end_neg = fxaa_luma(uv_neg) - luma_local_avg;
@@ -8,9 +8,26 @@
 //! 3. 比较采样点深度与实际深度，累积遮蔽因子
 //! 4. 应用模糊降噪
 //! 5. 与场景颜色混合
+//!
+//! `SsaoMode::Hbao` 将第 2、3 步替换为基于地平线的采样：沿若干屏幕空间方向步进，
+//! 累积高于切平面的地平线角，接触阴影更准确且噪点更少。
 
+use glam::{Mat4, Vec2, Vec3, Vec4};
 use rand::Rng;
 
+/// HBAO 每像素的采样方向数，每个方向的步数为 `sample_count / HBAO_DIRECTIONS`
+pub const HBAO_DIRECTIONS: u32 = 8;
+
+/// SSAO 算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SsaoMode {
+    /// 半球随机采样
+    #[default]
+    Classic,
+    /// 基于地平线角的 HBAO
+    Hbao,
+}
+
 /// SSAO Uniform 数据
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
@@ -45,6 +62,8 @@ pub struct SsaoKernel {
 pub struct SsaoPass {
     /// SSAO 计算管线
     ssao_pipeline: wgpu::RenderPipeline,
+    /// HBAO 计算管线
+    hbao_pipeline: wgpu::RenderPipeline,
     /// 模糊管线
     blur_pipeline: wgpu::RenderPipeline,
     /// 合成管线
@@ -179,6 +198,17 @@ impl SsaoPass {
                     },
                     count: None,
                 },
+                // AO 纹理（模糊和合成阶段的输入）
+                wgpu::BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -229,6 +259,15 @@ impl SsaoPass {
             wgpu::TextureFormat::R8Unorm,
         );
 
+        let hbao_pipeline = Self::create_pipeline(
+            device,
+            &pipeline_layout,
+            &shader,
+            "vs_fullscreen",
+            "fs_hbao",
+            wgpu::TextureFormat::R8Unorm,
+        );
+
         let blur_pipeline = Self::create_pipeline(
             device,
             &pipeline_layout,
@@ -254,6 +293,7 @@ impl SsaoPass {
 
         Self {
             ssao_pipeline,
+            hbao_pipeline,
             blur_pipeline,
             composite_pipeline,
             bind_group_layout,
@@ -462,6 +502,7 @@ impl SsaoPass {
         radius: f32,
         intensity: f32,
         bias: f32,
+        mode: SsaoMode,
     ) {
         // 更新 uniforms
         let uniforms = SsaoUniforms {
@@ -490,41 +531,50 @@ impl SsaoPass {
         let kernel = Self::generate_kernel();
         queue.write_buffer(&self.kernel_buffer, 0, bytemuck::bytes_of(&kernel));
 
-        // 创建绑定组
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("SSAO BG"),
-            layout: &self.bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(depth_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(scene_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::TextureView(&self.noise_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 4,
-                    resource: wgpu::BindingResource::Sampler(&self.noise_sampler),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 5,
-                    resource: self.uniform_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 6,
-                    resource: self.kernel_buffer.as_entire_binding(),
-                },
-            ],
-        });
+        // 创建绑定组：AO 输入分别为模糊结果（计算和合成阶段）和原始 AO（模糊阶段），
+        // 避免同一纹理在一个通道中既被采样又被写入
+        let create_bind_group = |ao_view: &wgpu::TextureView| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("SSAO BG"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(depth_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(scene_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&self.noise_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: wgpu::BindingResource::Sampler(&self.noise_sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: self.uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: self.kernel_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(ao_view),
+                    },
+                ],
+            })
+        };
+        let bind_group = create_bind_group(&self.blur_view);
+        let raw_ao_bind_group = create_bind_group(&self.ssao_view);
 
         // 1. SSAO 计算
         {
@@ -543,13 +593,36 @@ impl SsaoPass {
                 timestamp_writes: None,
             });
 
-            rpass.set_pipeline(&self.ssao_pipeline);
+            let pipeline = match mode {
+                SsaoMode::Classic => &self.ssao_pipeline,
+                SsaoMode::Hbao => &self.hbao_pipeline,
+            };
+            rpass.set_pipeline(pipeline);
             rpass.set_bind_group(0, &bind_group, &[]);
             rpass.draw(0..3, 0..1);
         }
 
-        // 2. 模糊
-        // (简化：跳过模糊步骤，直接使用 SSAO 结果)
+        // 2. 深度感知模糊降噪
+        {
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("SSAO Blur Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.blur_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+
+            rpass.set_pipeline(&self.blur_pipeline);
+            rpass.set_bind_group(0, &raw_ao_bind_group, &[]);
+            rpass.draw(0..3, 0..1);
+        }
 
         // 3. 合成
         {
@@ -580,6 +653,106 @@ impl SsaoPass {
     }
 }
 
+/// HBAO 的 CPU 参考实现，与着色器中的 `fs_hbao` 一致，但不做噪声旋转和步长抖动
+///
+/// `depth` 为按行存储的 `[0, 1]` 深度，尺寸取自 `uniforms.screen_size`。
+/// 返回每个像素的环境光可见度，1.0 表示没有遮蔽。
+pub fn hbao_reference(depth: &[f32], uniforms: &SsaoUniforms) -> Vec<f32> {
+    let width = uniforms.screen_size[0] as i32;
+    let height = uniforms.screen_size[1] as i32;
+    let projection = Mat4::from_cols_array_2d(&uniforms.projection);
+    let inv_projection = Mat4::from_cols_array_2d(&uniforms.inv_projection);
+
+    let load_depth = |x: i32, y: i32| {
+        let x = x.clamp(0, width - 1);
+        let y = y.clamp(0, height - 1);
+        depth[(y * width + x) as usize]
+    };
+    let load_position = |x: i32, y: i32| {
+        let x = x.clamp(0, width - 1);
+        let y = y.clamp(0, height - 1);
+        let uv = Vec2::new(
+            (x as f32 + 0.5) / width as f32,
+            (y as f32 + 0.5) / height as f32,
+        );
+        let ndc = Vec4::new(uv.x * 2.0 - 1.0, uv.y * 2.0 - 1.0, load_depth(x, y), 1.0);
+        let view = inv_projection * ndc;
+        view.truncate() / view.w
+    };
+
+    let step_count = (uniforms.sample_count / HBAO_DIRECTIONS).max(1);
+    let mut result = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            if load_depth(x, y) >= 1.0 {
+                result.push(1.0);
+                continue;
+            }
+            let p = load_position(x, y);
+
+            // 从相邻像素重建法线，取深度差较小的一侧以避免跨越边缘
+            let right = load_position(x + 1, y) - p;
+            let left = p - load_position(x - 1, y);
+            let down = load_position(x, y + 1) - p;
+            let up = p - load_position(x, y - 1);
+            let dx = if x + 1 >= width || (x > 0 && left.z.abs() < right.z.abs()) {
+                left
+            } else {
+                right
+            };
+            let dy = if y + 1 >= height || (y > 0 && up.z.abs() < down.z.abs()) {
+                up
+            } else {
+                down
+            };
+            let mut n = dx.cross(dy).normalize_or_zero();
+            if n.dot(-p) < 0.0 {
+                n = -n;
+            }
+
+            // 采样半径投影到像素
+            let center = projection * p.extend(1.0);
+            let edge = projection * (p + Vec3::new(uniforms.radius, 0.0, 0.0)).extend(1.0);
+            let radius_px =
+                (edge.x / edge.w - center.x / center.w).abs() * 0.5 * uniforms.screen_size[0];
+            if radius_px < 1.0 {
+                result.push(1.0);
+                continue;
+            }
+
+            let mut occlusion = 0.0;
+            for d in 0..HBAO_DIRECTIONS {
+                let angle = d as f32 / HBAO_DIRECTIONS as f32 * std::f32::consts::TAU;
+                let dir = Vec2::new(angle.cos(), angle.sin());
+                let mut max_sin = uniforms.bias;
+                for s in 0..step_count {
+                    let t = (s as f32 + 1.0) / step_count as f32 * radius_px;
+                    let offset = (dir * t).round();
+                    let sx = x + offset.x as i32;
+                    let sy = y + offset.y as i32;
+                    if sx < 0 || sy < 0 || sx >= width || sy >= height {
+                        break;
+                    }
+                    let v = load_position(sx, sy) - p;
+                    let dist = v.length();
+                    if dist < 1e-4 || dist > uniforms.radius {
+                        continue;
+                    }
+                    let sin_h = n.dot(v) / dist;
+                    if sin_h > max_sin {
+                        let falloff = 1.0 - (dist * dist) / (uniforms.radius * uniforms.radius);
+                        occlusion += (sin_h - max_sin) * falloff;
+                        max_sin = sin_h;
+                    }
+                }
+            }
+            occlusion /= HBAO_DIRECTIONS as f32;
+            result.push((1.0 - occlusion).clamp(0.0, 1.0).powf(uniforms.intensity));
+        }
+    }
+    result
+}
+
 /// SSAO 着色器
 const SSAO_SHADER: &str = r#"
 struct SsaoUniforms {
@@ -604,6 +777,12 @@ struct SsaoKernel {
 @group(0) @binding(4) var noise_sampler: sampler;
 @group(0) @binding(5) var<uniform> uniforms: SsaoUniforms;
 @group(0) @binding(6) var<uniform> kernel: SsaoKernel;
+@group(0) @binding(7) var ao_texture: texture_2d<f32>;
+
+const PI: f32 = 3.14159265359;
+const HBAO_DIRECTIONS: u32 = 8u;
+// 模糊时深度差的权重衰减
+const BLUR_DEPTH_SHARPNESS: f32 = 1000.0;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -677,25 +856,131 @@ fn fs_ssao(in: VertexOutput) -> @location(0) f32 {
     return pow(occlusion, uniforms.intensity);
 }
 
+// 按像素坐标读取深度并重建视空间位置
+fn load_view_position(coord: vec2<i32>) -> vec3<f32> {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let c = clamp(coord, vec2<i32>(0), size - vec2<i32>(1));
+    let depth = textureLoad(depth_texture, c, 0);
+    let uv = (vec2<f32>(c) + 0.5) / vec2<f32>(size);
+    return reconstruct_position(uv, depth);
+}
+
+// 从相邻像素重建法线，取深度差较小的一侧以避免跨越边缘
+fn reconstruct_normal(coord: vec2<i32>, p: vec3<f32>) -> vec3<f32> {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let right = load_view_position(coord + vec2<i32>(1, 0)) - p;
+    let left = p - load_view_position(coord - vec2<i32>(1, 0));
+    let down = load_view_position(coord + vec2<i32>(0, 1)) - p;
+    let up = p - load_view_position(coord - vec2<i32>(0, 1));
+
+    var dx = right;
+    if coord.x + 1 >= size.x || (coord.x > 0 && abs(left.z) < abs(right.z)) {
+        dx = left;
+    }
+    var dy = down;
+    if coord.y + 1 >= size.y || (coord.y > 0 && abs(up.z) < abs(down.z)) {
+        dy = up;
+    }
+
+    var n = cross(dx, dy);
+    if dot(n, n) > 0.0 {
+        n = normalize(n);
+    }
+    if dot(n, -p) < 0.0 {
+        n = -n;
+    }
+    return n;
+}
+
+@fragment
+fn fs_hbao(in: VertexOutput) -> @location(0) f32 {
+    let size = vec2<i32>(textureDimensions(depth_texture));
+    let coord = clamp(vec2<i32>(in.uv * vec2<f32>(size)), vec2<i32>(0), size - vec2<i32>(1));
+    let depth = textureLoad(depth_texture, coord, 0);
+
+    if (depth >= 1.0) {
+        return 1.0;
+    }
+
+    let p = load_view_position(coord);
+    let n = reconstruct_normal(coord, p);
+
+    // 采样半径投影到像素
+    let center = uniforms.projection * vec4<f32>(p, 1.0);
+    let edge = uniforms.projection * vec4<f32>(p + vec3<f32>(uniforms.radius, 0.0, 0.0), 1.0);
+    let radius_px = abs(edge.x / edge.w - center.x / center.w) * 0.5 * uniforms.screen_size.x;
+    if (radius_px < 1.0) {
+        return 1.0;
+    }
+
+    // 噪声旋转采样方向并抖动步长，由模糊阶段消除条纹
+    let noise = textureLoad(noise_texture, coord % vec2<i32>(4), 0).xy;
+    let rotation = noise.x * 2.0 * PI;
+    let jitter = 0.5 + 0.5 * noise.y;
+
+    let step_count = max(uniforms.sample_count / HBAO_DIRECTIONS, 1u);
+    var occlusion = 0.0;
+
+    for (var d = 0u; d < HBAO_DIRECTIONS; d++) {
+        let angle = f32(d) / f32(HBAO_DIRECTIONS) * 2.0 * PI + rotation;
+        let dir = vec2<f32>(cos(angle), sin(angle));
+        var max_sin = uniforms.bias;
+
+        for (var s = 0u; s < step_count; s++) {
+            let t = (f32(s) + jitter) / f32(step_count) * radius_px;
+            let sample_coord = coord + vec2<i32>(round(dir * t));
+            if (any(sample_coord < vec2<i32>(0)) || any(sample_coord >= size)) {
+                break;
+            }
+
+            let v = load_view_position(sample_coord) - p;
+            let dist = length(v);
+            if (dist < 1e-4 || dist > uniforms.radius) {
+                continue;
+            }
+
+            // 地平线角高于切平面的部分计入遮蔽
+            let sin_h = dot(n, v) / dist;
+            if (sin_h > max_sin) {
+                let falloff = 1.0 - (dist * dist) / (uniforms.radius * uniforms.radius);
+                occlusion += (sin_h - max_sin) * falloff;
+                max_sin = sin_h;
+            }
+        }
+    }
+
+    occlusion = occlusion / f32(HBAO_DIRECTIONS);
+    return pow(clamp(1.0 - occlusion, 0.0, 1.0), uniforms.intensity);
+}
+
+// 深度感知模糊：深度差大的邻居权重低，避免 AO 渗过物体边缘
 @fragment
 fn fs_blur(in: VertexOutput) -> @location(0) f32 {
-    let texel_size = 1.0 / uniforms.screen_size;
+    let size = vec2<i32>(textureDimensions(ao_texture));
+    let depth_size = vec2<i32>(textureDimensions(depth_texture));
+    let coord = clamp(vec2<i32>(in.uv * vec2<f32>(size)), vec2<i32>(0), size - vec2<i32>(1));
+    let center_depth = textureLoad(depth_texture, min(coord, depth_size - vec2<i32>(1)), 0);
+
     var result = 0.0;
-    
+    var weight_sum = 0.0;
+
     for (var x = -2; x <= 2; x++) {
         for (var y = -2; y <= 2; y++) {
-            let offset = vec2<f32>(f32(x), f32(y)) * texel_size;
-            result += textureSample(scene_texture, tex_sampler, in.uv + offset).r;
+            let c = clamp(coord + vec2<i32>(x, y), vec2<i32>(0), size - vec2<i32>(1));
+            let depth = textureLoad(depth_texture, min(c, depth_size - vec2<i32>(1)), 0);
+            let weight = exp(-abs(depth - center_depth) * BLUR_DEPTH_SHARPNESS);
+            result += textureLoad(ao_texture, c, 0).r * weight;
+            weight_sum += weight;
         }
     }
-    
-    return result / 25.0;
+
+    return result / max(weight_sum, 1e-4);
 }
 
 @fragment
 fn fs_composite(in: VertexOutput) -> @location(0) vec4<f32> {
     let scene_color = textureSample(scene_texture, tex_sampler, in.uv).rgb;
-    let ao = textureSample(scene_texture, tex_sampler, in.uv).r; // 简化：使用场景纹理
+    let ao = textureSample(ao_texture, tex_sampler, in.uv).r;
     return vec4<f32>(scene_color * ao, 1.0);
 }
 "#;
@@ -7,8 +7,14 @@
 //! - Reinhard: 简单的 Reinhard 算法
 //! - ACES: Academy Color Encoding System，电影级色调映射
 //! - Filmic: 类似胶片的色调映射
+//! - AgX: Blender 4 默认的胶片曲线，高光去饱和更自然
+//! - PbrNeutral: Khronos PBR Neutral，尽量保留材质的基础色
+
+use glam::{Mat3, Vec3};
 
 /// 色调映射算法
+///
+/// 判别值会写入配置和 `tonemap_mode` uniform，新增算法只能追加新值，不能调整已有值。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[repr(u32)]
 pub enum TonemapOperator {
@@ -21,6 +27,71 @@ pub enum TonemapOperator {
     ACES = 2,
     /// Filmic 算法
     Filmic = 3,
+    /// AgX 算法
+    AgX = 4,
+    /// Khronos PBR Neutral 算法
+    PbrNeutral = 5,
+}
+
+impl TonemapOperator {
+    /// 着色器中 `tonemap_mode` 对应的值
+    pub fn tonemap_mode(self) -> u32 {
+        self as u32
+    }
+}
+
+/// AgX 对数编码的曝光范围 (EV)
+const AGX_MIN_EV: f32 = -12.47393;
+const AGX_MAX_EV: f32 = 4.026069;
+
+/// AgX 对比度曲线（6 次多项式拟合），输入为 `[0, 1]` 的对数编码值
+pub fn agx_contrast(x: f32) -> f32 {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x2 * x + 0.4298 * x2 + 0.1191 * x
+        - 0.00232
+}
+
+/// AgX 色调映射的 CPU 参考实现，与着色器中的 `tonemap_agx` 一致，输出为线性颜色
+pub fn agx(color: Vec3) -> Vec3 {
+    let inset = Mat3::from_cols(
+        Vec3::new(0.84247906, 0.042328242, 0.042375655),
+        Vec3::new(0.0784336, 0.87846864, 0.0784336),
+        Vec3::new(0.079223745, 0.07916613, 0.879143),
+    );
+    let outset = Mat3::from_cols(
+        Vec3::new(1.196879, -0.052896852, -0.052971636),
+        Vec3::new(-0.09802088, 1.1519031, -0.09804345),
+        Vec3::new(-0.09902974, -0.098961177, 1.1510737),
+    );
+    let encoded = (inset * color).max(Vec3::splat(1e-10)).to_array().map(|c| {
+        let ev = c.log2().clamp(AGX_MIN_EV, AGX_MAX_EV);
+        agx_contrast((ev - AGX_MIN_EV) / (AGX_MAX_EV - AGX_MIN_EV))
+    });
+    // 曲线输出为显示编码，转回线性以便后续统一做 gamma 校正
+    (outset * Vec3::from_array(encoded))
+        .max(Vec3::ZERO)
+        .powf(2.2)
+}
+
+/// Khronos PBR Neutral 色调映射的 CPU 参考实现，与着色器中的 `tonemap_pbr_neutral` 一致
+pub fn pbr_neutral(color: Vec3) -> Vec3 {
+    const START_COMPRESSION: f32 = 0.8 - 0.04;
+    const DESATURATION: f32 = 0.15;
+
+    let x = color.min_element();
+    let offset = if x < 0.08 { x - 6.25 * x * x } else { 0.04 };
+    let color = color - Vec3::splat(offset);
+
+    let peak = color.max_element();
+    if peak < START_COMPRESSION {
+        return color;
+    }
+    let d = 1.0 - START_COMPRESSION;
+    let new_peak = 1.0 - d * d / (peak + d - START_COMPRESSION);
+    let color = color * (new_peak / peak);
+    let g = 1.0 - 1.0 / (DESATURATION * (peak - new_peak) + 1.0);
+    color.lerp(Vec3::splat(new_peak), g)
 }
 
 /// Tonemap Uniform 数据
@@ -31,7 +102,7 @@ pub struct TonemapUniforms {
     pub exposure: f32,
     /// Gamma 校正值
     pub gamma: f32,
-    /// 色调映射算法 (0=None, 1=Reinhard, 2=ACES, 3=Filmic)
+    /// 色调映射算法 (0=None, 1=Reinhard, 2=ACES, 3=Filmic, 4=AgX, 5=PbrNeutral)
     pub tonemap_mode: u32,
     /// 填充
     pub _pad: u32,
@@ -178,7 +249,7 @@ impl TonemapPass {
         let uniforms = TonemapUniforms {
             exposure,
             gamma,
-            tonemap_mode: operator as u32,
+            tonemap_mode: operator.tonemap_mode(),
             _pad: 0,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
@@ -306,6 +377,62 @@ fn tonemap_filmic(color: vec3<f32>) -> vec3<f32> {
     return result / white;
 }
 
+// AgX 色调映射 (对数编码 + 对比度曲线)
+fn agx_contrast(x: vec3<f32>) -> vec3<f32> {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    return 15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x2 * x + 0.4298 * x2
+        + 0.1191 * x - 0.00232;
+}
+
+fn tonemap_agx(color: vec3<f32>) -> vec3<f32> {
+    let inset = mat3x3<f32>(
+        vec3<f32>(0.842479062253094, 0.0423282422610123, 0.0423756549057051),
+        vec3<f32>(0.0784335999999992, 0.878468636469772, 0.0784336),
+        vec3<f32>(0.0792237451477643, 0.0791661274605434, 0.879142973793104)
+    );
+    let outset = mat3x3<f32>(
+        vec3<f32>(1.19687900512017, -0.0528968517574562, -0.0529716355144438),
+        vec3<f32>(-0.0980208811401368, 1.15190312990417, -0.0980434501171241),
+        vec3<f32>(-0.0990297440797205, -0.0989611768448433, 1.15107367264116)
+    );
+    let min_ev = -12.47393;
+    let max_ev = 4.026069;
+
+    var c = max(inset * color, vec3<f32>(1e-10));
+    c = clamp(log2(c), vec3<f32>(min_ev), vec3<f32>(max_ev));
+    c = (c - min_ev) / (max_ev - min_ev);
+    c = agx_contrast(c);
+
+    // 曲线输出为显示编码，转回线性以便统一做 gamma 校正
+    return pow(max(outset * c, vec3<f32>(0.0)), vec3<f32>(2.2));
+}
+
+// Khronos PBR Neutral 色调映射
+fn tonemap_pbr_neutral(color: vec3<f32>) -> vec3<f32> {
+    let start_compression = 0.8 - 0.04;
+    let desaturation = 0.15;
+
+    let x = min(color.r, min(color.g, color.b));
+    var offset = 0.04;
+    if x < 0.08 {
+        offset = x - 6.25 * x * x;
+    }
+    var c = color - offset;
+
+    let peak = max(c.r, max(c.g, c.b));
+    if peak < start_compression {
+        return c;
+    }
+
+    let d = 1.0 - start_compression;
+    let new_peak = 1.0 - d * d / (peak + d - start_compression);
+    c = c * (new_peak / peak);
+
+    let g = 1.0 - 1.0 / (desaturation * (peak - new_peak) + 1.0);
+    return mix(c, vec3<f32>(new_peak), g);
+}
+
 // 主色调映射片段着色器
 @fragment
 fn fs_tonemap(in: VertexOutput) -> @location(0) vec4<f32> {
@@ -332,6 +459,14 @@ fn fs_tonemap(in: VertexOutput) -> @location(0) vec4<f32> {
             // Filmic
             color = tonemap_filmic(color);
         }
+        case 4u: {
+            // AgX
+            color = tonemap_agx(color);
+        }
+        case 5u: {
+            // PBR Neutral
+            color = tonemap_pbr_neutral(color);
+        }
         default: {
             color = tonemap_aces(color);
         }
@@ -11,6 +11,7 @@
 use crate::core::error::RenderError;
 use crate::impl_default;
 use glam::{Mat4, Vec3, Vec4};
+use rand::Rng;
 use wgpu::util::DeviceExt;
 use wgpu::{
     BindGroup, BindGroupLayout, Buffer, CommandEncoder, ComputePipeline, Device, Queue, Texture,
@@ -34,6 +35,10 @@ pub struct RayTracingConfig {
     pub global_illumination: bool,
     /// 是否启用环境光遮蔽
     pub ambient_occlusion: bool,
+    /// À-Trous 降噪迭代次数，0 表示不降噪，超过 [`MAX_DENOISE_ITERATIONS`] 时按上限处理
+    pub denoise_iterations: u32,
+    /// 面光源每个着色点的采样数，向上取整为完整的分层网格，关闭软阴影时只采样中心
+    pub area_light_samples: u32,
 }
 
 impl_default!(RayTracingConfig {
@@ -44,8 +49,20 @@ impl_default!(RayTracingConfig {
     soft_shadows: true,
     global_illumination: false,
     ambient_occlusion: true,
+    denoise_iterations: 3,
+    area_light_samples: 16,
 });
 
+/// À-Trous 降噪的最大迭代次数，第 8 次迭代的采样步长已达 128 像素
+pub const MAX_DENOISE_ITERATIONS: u32 = 8;
+
+impl RayTracingConfig {
+    /// 实际执行的降噪迭代次数，限制在 [`MAX_DENOISE_ITERATIONS`] 以内
+    pub fn denoise_passes(&self) -> u32 {
+        self.denoise_iterations.min(MAX_DENOISE_ITERATIONS)
+    }
+}
+
 /// 光线追踪场景数据
 #[derive(Debug, Clone)]
 pub struct RayTracingScene {
@@ -123,6 +140,243 @@ pub enum LightType {
     Directional { direction: Vec3 },
     /// 聚光灯
     Spot { direction: Vec3, angle: f32 },
+    /// 矩形面光源，以 `position` 为中心，只向 `normal` 一侧发光
+    Area {
+        width: f32,
+        height: f32,
+        normal: Vec3,
+    },
+}
+
+/// 阴影光线起点偏移，避免与自身表面相交
+const SHADOW_RAY_EPSILON: f32 = 1e-3;
+
+/// 面光源分层采样网格的列数和行数
+///
+/// 采样数不是完全平方数时向上取整为完整的 `columns × rows` 网格，
+/// 保证每个格子都被采样，每个采样点代表相同的面积。
+pub fn area_light_grid(samples: u32) -> (u32, u32) {
+    let samples = samples.max(1);
+    let columns = (samples as f32).sqrt().ceil() as u32;
+    (columns, samples.div_ceil(columns))
+}
+
+impl Sphere {
+    /// 光线与球体最近的正向交点距离，`direction` 需为单位向量
+    pub fn intersect(&self, origin: Vec3, direction: Vec3) -> Option<f32> {
+        let oc = origin - self.center;
+        let b = oc.dot(direction);
+        let c = oc.length_squared() - self.radius * self.radius;
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let sqrt_d = discriminant.sqrt();
+        [-b - sqrt_d, -b + sqrt_d]
+            .into_iter()
+            .find(|&t| t > SHADOW_RAY_EPSILON)
+    }
+}
+
+impl RayTracingPlane {
+    /// 光线与平面的正向交点距离，`direction` 需为单位向量
+    pub fn intersect(&self, origin: Vec3, direction: Vec3) -> Option<f32> {
+        let denom = self.normal.dot(direction);
+        if denom.abs() < 1e-6 {
+            return None;
+        }
+        let t = (self.point - origin).dot(self.normal) / denom;
+        (t > SHADOW_RAY_EPSILON).then_some(t)
+    }
+}
+
+impl Light {
+    /// 面光源的面积，其他光源为 0
+    pub fn area(&self) -> f32 {
+        match self.light_type {
+            LightType::Area { width, height, .. } => width * height,
+            _ => 0.0,
+        }
+    }
+
+    /// 在光源上生成阴影光线的目标点
+    ///
+    /// 面光源在 [`area_light_grid`] 给出的网格上分层采样（每个采样点代表相同面积），
+    /// 采样点在所属格子内随机抖动，避免规则网格在半影中产生条带；其他光源只返回自身位置。
+    pub fn sample_points(&self, samples: u32) -> Vec<Vec3> {
+        let LightType::Area {
+            width,
+            height,
+            normal,
+        } = self.light_type
+        else {
+            return vec![self.position];
+        };
+
+        let (columns, rows) = area_light_grid(samples);
+        let count = columns * rows;
+        let (tangent, bitangent) = normal.normalize().any_orthonormal_pair();
+        let mut rng = rand::thread_rng();
+
+        (0..count)
+            .map(|i| {
+                // 单个采样点固定在中心，多个采样点在各自的格子内随机抖动
+                let (du, dv) = if count == 1 {
+                    (0.5, 0.5)
+                } else {
+                    (rng.gen::<f32>(), rng.gen::<f32>())
+                };
+                let u = ((i % columns) as f32 + du) / columns as f32 - 0.5;
+                let v = ((i / columns) as f32 + dv) / rows as f32 - 0.5;
+                self.position + tangent * (u * width) + bitangent * (v * height)
+            })
+            .collect()
+    }
+}
+
+impl RayTracingScene {
+    /// 主光线最近命中点的降噪引导，与 `RAY_TRACING_SHADER` 写入的引导一致
+    ///
+    /// 未命中时法线和距离均为 0。
+    pub fn primary_guide(&self, origin: Vec3, direction: Vec3) -> DenoiseGuide {
+        let mut guide = DenoiseGuide {
+            normal: Vec3::ZERO,
+            depth: 0.0,
+        };
+        let mut closest = f32::INFINITY;
+        for sphere in &self.spheres {
+            if let Some(t) = sphere.intersect(origin, direction).filter(|&t| t < closest) {
+                closest = t;
+                guide = DenoiseGuide {
+                    normal: (origin + direction * t - sphere.center).normalize(),
+                    depth: t,
+                };
+            }
+        }
+        for plane in &self.planes {
+            if let Some(t) = plane.intersect(origin, direction).filter(|&t| t < closest) {
+                closest = t;
+                guide = DenoiseGuide {
+                    normal: plane.normal.normalize(),
+                    depth: t,
+                };
+            }
+        }
+        guide
+    }
+
+    /// 在 `max_distance` 之内光线是否被场景几何体遮挡
+    pub fn occluded(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> bool {
+        let blocks = |t: Option<f32>| t.is_some_and(|t| t < max_distance);
+        self.spheres
+            .iter()
+            .any(|sphere| blocks(sphere.intersect(origin, direction)))
+            || self
+                .planes
+                .iter()
+                .any(|plane| blocks(plane.intersect(origin, direction)))
+    }
+
+    /// 着色点到光源的可见比例 (0.0 - 1.0)
+    ///
+    /// 面光源向 `samples` 个采样点各发射一条阴影光线，半影区域得到部分可见；
+    /// 点光源、聚光灯和方向光只有一条阴影光线，结果为 0 或 1。
+    pub fn light_visibility(&self, light: &Light, point: Vec3, samples: u32) -> f32 {
+        if let LightType::Directional { direction } = light.light_type {
+            let to_light = -direction.normalize();
+            let origin = point + to_light * SHADOW_RAY_EPSILON;
+            return if self.occluded(origin, to_light, f32::INFINITY) {
+                0.0
+            } else {
+                1.0
+            };
+        }
+
+        let targets = light.sample_points(samples);
+        let visible = targets
+            .iter()
+            .filter(|&&target| {
+                let offset = target - point;
+                let distance = offset.length();
+                if distance <= SHADOW_RAY_EPSILON {
+                    return true;
+                }
+                let direction = offset / distance;
+                let origin = point + direction * SHADOW_RAY_EPSILON;
+                !self.occluded(origin, direction, distance - 2.0 * SHADOW_RAY_EPSILON)
+            })
+            .count();
+        visible as f32 / targets.len() as f32
+    }
+
+    /// 计算单个光源对着色点的直接光照（含阴影）
+    ///
+    /// 面光源在光源表面上重要性采样：每个采样点的贡献按立体角换算
+    /// `cosθ_surface * cosθ_light / d² * (area / N)`，被遮挡的采样点不计入。
+    pub fn direct_lighting(
+        &self,
+        light: &Light,
+        point: Vec3,
+        normal: Vec3,
+        config: &RayTracingConfig,
+    ) -> Vec3 {
+        let radiance = light.color * light.intensity;
+
+        match light.light_type {
+            LightType::Directional { direction } => {
+                let cos_theta = normal.dot(-direction.normalize()).max(0.0);
+                radiance * cos_theta * self.light_visibility(light, point, 1)
+            }
+            LightType::Point | LightType::Spot { .. } => {
+                let offset = light.position - point;
+                let distance_sq = offset.length_squared().max(SHADOW_RAY_EPSILON);
+                let to_light = offset.normalize_or_zero();
+                if let LightType::Spot { direction, angle } = light.light_type {
+                    if (-to_light).dot(direction.normalize()) < angle.cos() {
+                        return Vec3::ZERO;
+                    }
+                }
+                let cos_theta = normal.dot(to_light).max(0.0);
+                radiance * cos_theta / distance_sq * self.light_visibility(light, point, 1)
+            }
+            LightType::Area {
+                normal: light_normal,
+                ..
+            } => {
+                let samples = if config.soft_shadows {
+                    config.area_light_samples.max(1)
+                } else {
+                    1
+                };
+                let targets = light.sample_points(samples);
+                let sample_area = light.area() / targets.len() as f32;
+                let light_normal = light_normal.normalize();
+
+                targets
+                    .iter()
+                    .map(|&target| {
+                        let offset = target - point;
+                        let distance_sq = offset.length_squared();
+                        if distance_sq <= SHADOW_RAY_EPSILON * SHADOW_RAY_EPSILON {
+                            return Vec3::ZERO;
+                        }
+                        let distance = distance_sq.sqrt();
+                        let direction = offset / distance;
+                        let cos_surface = normal.dot(direction).max(0.0);
+                        let cos_light = light_normal.dot(-direction).max(0.0);
+                        if cos_surface == 0.0 || cos_light == 0.0 {
+                            return Vec3::ZERO;
+                        }
+                        let origin = point + direction * SHADOW_RAY_EPSILON;
+                        if self.occluded(origin, direction, distance - 2.0 * SHADOW_RAY_EPSILON) {
+                            return Vec3::ZERO;
+                        }
+                        radiance * (cos_surface * cos_light / distance_sq * sample_area)
+                    })
+                    .sum()
+            }
+        }
+    }
 }
 
 /// 光线追踪渲染器
@@ -132,6 +386,14 @@ pub struct RayTracingRenderer {
     bind_group_layout: Option<BindGroupLayout>,
     output_texture: Option<Texture>,
     output_view: Option<TextureView>,
+    /// 主光线命中的法线 (xyz) 和距离 (w)，作为降噪引导
+    guide_texture: Option<Texture>,
+    guide_view: Option<TextureView>,
+    /// 降噪乒乓纹理
+    denoise_texture: Option<Texture>,
+    denoise_view: Option<TextureView>,
+    denoise_pipeline: Option<ComputePipeline>,
+    denoise_bind_group_layout: Option<BindGroupLayout>,
     scene_buffer: Option<Buffer>,
     config_buffer: Option<Buffer>,
 }
@@ -146,6 +408,12 @@ impl RayTracingRenderer {
                 bind_group_layout: None,
                 output_texture: None,
                 output_view: None,
+                guide_texture: None,
+                guide_view: None,
+                denoise_texture: None,
+                denoise_view: None,
+                denoise_pipeline: None,
+                denoise_bind_group_layout: None,
                 scene_buffer: None,
                 config_buffer: None,
             });
@@ -194,6 +462,17 @@ impl RayTracingRenderer {
                     },
                     count: None,
                 },
+                // 降噪引导纹理
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: wgpu::TextureFormat::Rgba16Float,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -212,17 +491,92 @@ impl RayTracingRenderer {
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         });
 
+        let (denoise_pipeline, denoise_bind_group_layout) = Self::create_denoise_pipeline(device);
+
         Ok(Self {
             config,
             pipeline: Some(pipeline),
             bind_group_layout: Some(bind_group_layout),
             output_texture: None,
             output_view: None,
+            guide_texture: None,
+            guide_view: None,
+            denoise_texture: None,
+            denoise_view: None,
+            denoise_pipeline: Some(denoise_pipeline),
+            denoise_bind_group_layout: Some(denoise_bind_group_layout),
             scene_buffer: None,
             config_buffer: None,
         })
     }
 
+    /// 创建 À-Trous 降噪管线
+    fn create_denoise_pipeline(device: &Device) -> (ComputePipeline, BindGroupLayout) {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Ray Tracing Denoise Shader"),
+            source: wgpu::ShaderSource::Wgsl(DENOISE_SHADER.into()),
+        });
+
+        let input_texture = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Ray Tracing Denoise BGL"),
+            entries: &[
+                // 输入颜色
+                input_texture(0),
+                // 法线/深度引导
+                input_texture(1),
+                // 输出颜色
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: wgpu::TextureFormat::Rgba16Float,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                // 迭代参数
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Ray Tracing Denoise Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Ray Tracing Denoise Pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "main",
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        });
+
+        (pipeline, bind_group_layout)
+    }
+
     /// 更新配置
     pub fn update_config(
         &mut self,
@@ -250,26 +604,40 @@ impl RayTracingRenderer {
         let rt_width = (width as f32 * self.config.resolution_scale) as u32;
         let rt_height = (height as f32 * self.config.resolution_scale) as u32;
 
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Ray Tracing Output"),
-            size: wgpu::Extent3d {
-                width: rt_width,
-                height: rt_height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba16Float,
-            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
-
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let create_target = |label| {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: rt_width,
+                    height: rt_height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba16Float,
+                usage: wgpu::TextureUsages::STORAGE_BINDING
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC
+                    | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            (texture, view)
+        };
 
+        let (texture, view) = create_target("Ray Tracing Output");
         self.output_texture = Some(texture);
         self.output_view = Some(view);
 
+        let (texture, view) = create_target("Ray Tracing Denoise Guide");
+        self.guide_texture = Some(texture);
+        self.guide_view = Some(view);
+
+        let (texture, view) = create_target("Ray Tracing Denoise Ping");
+        self.denoise_texture = Some(texture);
+        self.denoise_view = Some(view);
+
         Ok(())
     }
 
@@ -309,12 +677,18 @@ impl RayTracingRenderer {
             } else {
                 0u32
             },
+            area_light_samples: if self.config.soft_shadows {
+                self.config.area_light_samples.max(1)
+            } else {
+                1
+            },
+            _pad0: [0u32; 2],
             ambient_color: [
                 scene.ambient_color.x,
                 scene.ambient_color.y,
                 scene.ambient_color.z,
             ],
-            _padding: [0u32; 2],
+            _padding: 0,
         };
         let uniforms_array = [uniforms];
         let config_data = bytemuck::cast_slice(&uniforms_array);
@@ -344,6 +718,12 @@ impl RayTracingRenderer {
             ));
         };
 
+        let Some(guide_view) = &self.guide_view else {
+            return Err(RenderError::InvalidState(
+                "Guide view not initialized".into(),
+            ));
+        };
+
         let Some(scene_buffer) = &self.scene_buffer else {
             return Err(RenderError::InvalidState(
                 "Scene buffer not initialized".into(),
@@ -372,15 +752,22 @@ impl RayTracingRenderer {
                     binding: 2,
                     resource: config_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(guide_view),
+                },
             ],
         }))
     }
 
     /// 执行光线追踪
     ///
+    /// `denoise_iterations > 0` 时在光线追踪通道之后记录 À-Trous 降噪通道。
+    ///
     /// 注意：bind_group 需要在外部创建并传入
     pub fn render(
         &self,
+        device: &Device,
         encoder: &mut CommandEncoder,
         bind_group: &BindGroup,
         _camera: &Camera,
@@ -411,24 +798,123 @@ impl RayTracingRenderer {
             ));
         };
 
-        // 开始计算通道
-        let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some("Ray Tracing Compute Pass"),
-            timestamp_writes: None,
-        });
+        {
+            // 开始计算通道
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Ray Tracing Compute Pass"),
+                timestamp_writes: None,
+            });
+
+            compute_pass.set_pipeline(pipeline);
+            compute_pass.set_bind_group(0, bind_group, &[]);
+
+            // 计算工作组数量
+            let output_texture = self.output_texture.as_ref().unwrap();
+            let width = output_texture.width();
+            let height = output_texture.height();
+            let workgroup_size = 8; // 8x8 工作组
+            let workgroups_x = width.div_ceil(workgroup_size);
+            let workgroups_y = height.div_ceil(workgroup_size);
+
+            compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+        }
+
+        self.denoise(device, encoder)
+    }
+
+    /// 对光线追踪输出执行 À-Trous 降噪后处理
+    ///
+    /// 由 `render` 在光线追踪通道之后记录到同一个编码器中。每次迭代的采样间距翻倍，
+    /// 在输出纹理和乒乓纹理之间交替，结果最终写回输出纹理。
+    fn denoise(&self, device: &Device, encoder: &mut CommandEncoder) -> Result<(), RenderError> {
+        if !self.config.enabled || self.config.denoise_passes() == 0 {
+            return Ok(());
+        }
+
+        let (Some(pipeline), Some(bind_group_layout)) =
+            (&self.denoise_pipeline, &self.denoise_bind_group_layout)
+        else {
+            return Ok(());
+        };
+
+        let (Some(output_texture), Some(output_view)) = (&self.output_texture, &self.output_view)
+        else {
+            return Err(RenderError::InvalidState(
+                "Ray tracing output texture not prepared".into(),
+            ));
+        };
+
+        let (Some(guide_view), Some(denoise_texture), Some(denoise_view)) =
+            (&self.guide_view, &self.denoise_texture, &self.denoise_view)
+        else {
+            return Err(RenderError::InvalidState(
+                "Denoise textures not prepared".into(),
+            ));
+        };
 
-        compute_pass.set_pipeline(pipeline);
-        compute_pass.set_bind_group(0, bind_group, &[]);
+        let bind_groups: Vec<BindGroup> = (0..self.config.denoise_passes())
+            .map(|iteration| {
+                let (input, output) = if iteration % 2 == 0 {
+                    (output_view, denoise_view)
+                } else {
+                    (denoise_view, output_view)
+                };
+                let params = DenoiseParams::for_iteration(iteration);
+                let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Ray Tracing Denoise Params"),
+                    contents: bytemuck::bytes_of(&params),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Ray Tracing Denoise Bind Group"),
+                    layout: bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(input),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(guide_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::TextureView(output),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: params_buffer.as_entire_binding(),
+                        },
+                    ],
+                })
+            })
+            .collect();
 
-        // 计算工作组数量
-        let output_texture = self.output_texture.as_ref().unwrap();
         let width = output_texture.width();
         let height = output_texture.height();
-        let workgroup_size = 8; // 8x8 工作组
-        let workgroups_x = (width + workgroup_size - 1) / workgroup_size;
-        let workgroups_y = (height + workgroup_size - 1) / workgroup_size;
+        let workgroups_x = width.div_ceil(8);
+        let workgroups_y = height.div_ceil(8);
+
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Ray Tracing Denoise Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(pipeline);
+            for bind_group in &bind_groups {
+                compute_pass.set_bind_group(0, bind_group, &[]);
+                compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+            }
+        }
 
-        compute_pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+        // 奇数次迭代的结果在乒乓纹理中，复制回输出纹理
+        if self.config.denoise_passes() % 2 == 1 {
+            encoder.copy_texture_to_texture(
+                denoise_texture.as_image_copy(),
+                output_texture.as_image_copy(),
+                output_texture.size(),
+            );
+        }
 
         Ok(())
     }
@@ -461,15 +947,232 @@ struct RayTracingUniforms {
     soft_shadows: u32,
     global_illumination: u32,
     ambient_occlusion: u32,
+    area_light_samples: u32,
+    _pad0: [u32; 2],
     ambient_color: [f32; 3],
-    _padding: [u32; 2],
+    _padding: u32,
+}
+
+/// À-Trous 降噪的 B3 样条核
+const DENOISE_KERNEL: [f32; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+/// 首次迭代的颜色权重参数，每次迭代减半
+const DENOISE_COLOR_PHI: f32 = 1.0;
+/// 法线权重的指数，越大越保留法线不连续处的边缘
+const DENOISE_NORMAL_POWER: f32 = 128.0;
+/// 相对深度差的权重参数
+const DENOISE_DEPTH_PHI: f32 = 0.1;
+
+/// 降噪引导数据，来自主光线命中点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DenoiseGuide {
+    /// 命中点法线，未命中时为零向量
+    pub normal: Vec3,
+    /// 命中距离，未命中时为 0
+    pub depth: f32,
+}
+
+/// 单次降噪迭代参数，与 `DENOISE_SHADER` 中的 `DenoiseParams` 对应
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct DenoiseParams {
+    step_width: u32,
+    color_phi: f32,
+    normal_power: f32,
+    depth_phi: f32,
+}
+
+impl DenoiseParams {
+    fn for_iteration(iteration: u32) -> Self {
+        Self {
+            step_width: 1 << iteration,
+            color_phi: DENOISE_COLOR_PHI / (1 << iteration) as f32,
+            normal_power: DENOISE_NORMAL_POWER,
+            depth_phi: DENOISE_DEPTH_PHI,
+        }
+    }
+
+    /// 边缘停止权重：颜色、法线和深度差异越大权重越小
+    ///
+    /// 任一像素未命中几何体（法线为零）时没有几何引导，只使用颜色权重。
+    fn edge_weight(&self, cp: Vec3, cq: Vec3, gp: DenoiseGuide, gq: DenoiseGuide) -> f32 {
+        let dc = cp - cq;
+        let w_color = (-dc.dot(dc) / self.color_phi).exp();
+        if gp.normal == Vec3::ZERO || gq.normal == Vec3::ZERO {
+            return w_color;
+        }
+        let w_normal = gp.normal.dot(gq.normal).max(0.0).powf(self.normal_power);
+        let w_depth = (-(gp.depth - gq.depth).abs()
+            / (self.depth_phi * self.step_width as f32 * gp.depth.max(1e-3)))
+        .exp();
+        w_color * w_normal * w_depth
+    }
 }
 
-/// 序列化场景数据（简化实现）
-fn serialize_scene(_scene: &RayTracingScene) -> Vec<u8> {
-    // 简化实现：返回空数据
-    // 实际实现需要序列化所有球体、平面和光源
-    vec![]
+/// À-Trous 小波降噪的 CPU 参考实现，与 GPU 降噪通道的算法一致
+///
+/// `color` 和 `guide` 按行主序存储，长度均为 `width * height`，
+/// `iterations` 超过 [`MAX_DENOISE_ITERATIONS`] 时按上限处理。
+pub fn atrous_denoise(
+    color: &[Vec3],
+    guide: &[DenoiseGuide],
+    width: usize,
+    height: usize,
+    iterations: u32,
+) -> Vec<Vec3> {
+    assert_eq!(color.len(), width * height);
+    assert_eq!(guide.len(), width * height);
+
+    let mut current = color.to_vec();
+    for iteration in 0..iterations.min(MAX_DENOISE_ITERATIONS) {
+        let params = DenoiseParams::for_iteration(iteration);
+        let step = params.step_width as i64;
+        let mut next = vec![Vec3::ZERO; current.len()];
+
+        for y in 0..height as i64 {
+            for x in 0..width as i64 {
+                let p = (y * width as i64 + x) as usize;
+                let center_weight = DENOISE_KERNEL[2] * DENOISE_KERNEL[2];
+                let mut sum = current[p] * center_weight;
+                let mut weight_sum = center_weight;
+
+                for (ky, hy) in DENOISE_KERNEL.iter().enumerate() {
+                    for (kx, hx) in DENOISE_KERNEL.iter().enumerate() {
+                        if kx == 2 && ky == 2 {
+                            continue;
+                        }
+                        let qx = x + (kx as i64 - 2) * step;
+                        let qy = y + (ky as i64 - 2) * step;
+                        if qx < 0 || qy < 0 || qx >= width as i64 || qy >= height as i64 {
+                            continue;
+                        }
+                        let q = (qy * width as i64 + qx) as usize;
+                        let weight = hx
+                            * hy
+                            * params.edge_weight(current[p], current[q], guide[p], guide[q]);
+                        sum += current[q] * weight;
+                        weight_sum += weight;
+                    }
+                }
+
+                next[p] = sum / weight_sum;
+            }
+        }
+        current = next;
+    }
+    current
+}
+
+/// 场景缓冲区头部：球体数、平面数、光源数和一个填充字
+const SCENE_HEADER_WORDS: usize = 4;
+/// 每个球体占用的字数：中心、半径和材质
+const SPHERE_WORDS: usize = 12;
+/// 每个平面占用的字数：法线、平面上的点和材质
+const PLANE_WORDS: usize = 16;
+/// 每个光源占用的字数：位置和类型、辐射度和聚光锥角余弦、方向、面光源的两条边
+const LIGHT_WORDS: usize = 20;
+
+/// 场景缓冲区中的光源类型编号，与 `RAY_TRACING_SHADER` 一致
+const LIGHT_TYPE_POINT: u32 = 0;
+const LIGHT_TYPE_DIRECTIONAL: u32 = 1;
+const LIGHT_TYPE_SPOT: u32 = 2;
+const LIGHT_TYPE_AREA: u32 = 3;
+
+/// 材质按 `albedo, metallic, emissive, roughness` 写入 8 个字
+fn push_material(words: &mut Vec<u32>, material: &Material) {
+    for value in material.albedo.extend(material.metallic).to_array() {
+        words.push(value.to_bits());
+    }
+    for value in material.emissive.extend(material.roughness).to_array() {
+        words.push(value.to_bits());
+    }
+}
+
+/// 光源按 `position, type, radiance, cos(angle), direction, edge_u, edge_v` 写入 20 个字
+///
+/// 面光源的两条边由 `normal.any_orthonormal_pair()` 乘以宽高得到，与 `sample_points` 一致。
+fn push_light(words: &mut Vec<u32>, light: &Light) {
+    let (light_type, direction, cos_angle, edge_u, edge_v) = match light.light_type {
+        LightType::Point => (LIGHT_TYPE_POINT, Vec3::ZERO, 0.0, Vec3::ZERO, Vec3::ZERO),
+        LightType::Directional { direction } => (
+            LIGHT_TYPE_DIRECTIONAL,
+            direction.normalize(),
+            0.0,
+            Vec3::ZERO,
+            Vec3::ZERO,
+        ),
+        LightType::Spot { direction, angle } => (
+            LIGHT_TYPE_SPOT,
+            direction.normalize(),
+            angle.cos(),
+            Vec3::ZERO,
+            Vec3::ZERO,
+        ),
+        LightType::Area {
+            width,
+            height,
+            normal,
+        } => {
+            let normal = normal.normalize();
+            let (tangent, bitangent) = normal.any_orthonormal_pair();
+            (
+                LIGHT_TYPE_AREA,
+                normal,
+                0.0,
+                tangent * width,
+                bitangent * height,
+            )
+        }
+    };
+
+    for value in light.position.to_array() {
+        words.push(value.to_bits());
+    }
+    words.push(light_type);
+    for value in (light.color * light.intensity).extend(cos_angle).to_array() {
+        words.push(value.to_bits());
+    }
+    for vector in [direction, edge_u, edge_v] {
+        for value in vector.extend(0.0).to_array() {
+            words.push(value.to_bits());
+        }
+    }
+}
+
+/// 序列化场景几何体和光源，布局与 `RAY_TRACING_SHADER` 中的 `scene_data` 一致
+fn serialize_scene(scene: &RayTracingScene) -> Vec<u8> {
+    let mut words = Vec::with_capacity(
+        SCENE_HEADER_WORDS
+            + scene.spheres.len() * SPHERE_WORDS
+            + scene.planes.len() * PLANE_WORDS
+            + scene.lights.len() * LIGHT_WORDS,
+    );
+    words.extend([
+        scene.spheres.len() as u32,
+        scene.planes.len() as u32,
+        scene.lights.len() as u32,
+        0,
+    ]);
+
+    for sphere in &scene.spheres {
+        for value in sphere.center.extend(sphere.radius).to_array() {
+            words.push(value.to_bits());
+        }
+        push_material(&mut words, &sphere.material);
+    }
+    for plane in &scene.planes {
+        for value in plane.normal.normalize().extend(0.0).to_array() {
+            words.push(value.to_bits());
+        }
+        for value in plane.point.extend(0.0).to_array() {
+            words.push(value.to_bits());
+        }
+        push_material(&mut words, &plane.material);
+    }
+    for light in &scene.lights {
+        push_light(&mut words, light);
+    }
+
+    bytemuck::cast_slice(&words).to_vec()
 }
 
 /// 光线追踪计算着色器
@@ -477,6 +1180,7 @@ const RAY_TRACING_SHADER: &str = r#"
 @group(0) @binding(0) var output_texture: texture_storage_2d<rgba16float, write>;
 @group(0) @binding(1) var<storage, read> scene_data: array<u32>;
 @group(0) @binding(2) var<uniform> config: RayTracingConfig;
+@group(0) @binding(3) var guide_texture: texture_storage_2d<rgba16float, write>;
 
 struct RayTracingConfig {
     rays_per_pixel: u32,
@@ -484,8 +1188,11 @@ struct RayTracingConfig {
     soft_shadows: u32,
     global_illumination: u32,
     ambient_occlusion: u32,
+    area_light_samples: u32,
+    _pad0: u32,
+    _pad1: u32,
     ambient_color: vec3<f32>,
-    _padding: vec2<u32>,
+    _padding: u32,
 }
 
 struct Ray {
@@ -498,6 +1205,213 @@ struct HitInfo {
     position: vec3<f32>,
     normal: vec3<f32>,
     distance: f32,
+    albedo: vec3<f32>,
+    emissive: vec3<f32>,
+}
+
+// 与 serialize_scene 的布局一致
+const SCENE_HEADER_WORDS: u32 = 4u;
+const SPHERE_WORDS: u32 = 12u;
+const PLANE_WORDS: u32 = 16u;
+const LIGHT_WORDS: u32 = 20u;
+const LIGHT_TYPE_DIRECTIONAL: u32 = 1u;
+const LIGHT_TYPE_SPOT: u32 = 2u;
+const LIGHT_TYPE_AREA: u32 = 3u;
+const HIT_EPSILON: f32 = 1e-3;
+
+fn read_f32(index: u32) -> f32 {
+    return bitcast<f32>(scene_data[index]);
+}
+
+fn read_vec3(index: u32) -> vec3<f32> {
+    return vec3<f32>(read_f32(index), read_f32(index + 1u), read_f32(index + 2u));
+}
+
+// 求主光线与场景球体、平面的最近交点
+fn trace_primary(ray: Ray) -> HitInfo {
+    var hit: HitInfo;
+    hit.hit = false;
+    hit.distance = 3.4e38;
+
+    let sphere_count = scene_data[0];
+    let plane_count = scene_data[1];
+
+    for (var i = 0u; i < sphere_count; i++) {
+        let base = SCENE_HEADER_WORDS + i * SPHERE_WORDS;
+        let center = read_vec3(base);
+        let radius = read_f32(base + 3u);
+        let oc = ray.origin - center;
+        let b = dot(oc, ray.direction);
+        let c = dot(oc, oc) - radius * radius;
+        let discriminant = b * b - c;
+        if (discriminant < 0.0) {
+            continue;
+        }
+        let sqrt_d = sqrt(discriminant);
+        var t = -b - sqrt_d;
+        if (t <= HIT_EPSILON) {
+            t = -b + sqrt_d;
+        }
+        if (t > HIT_EPSILON && t < hit.distance) {
+            hit.hit = true;
+            hit.distance = t;
+            hit.position = ray.origin + ray.direction * t;
+            hit.normal = normalize(hit.position - center);
+            hit.albedo = read_vec3(base + 4u);
+            hit.emissive = read_vec3(base + 8u);
+        }
+    }
+
+    let plane_base = SCENE_HEADER_WORDS + sphere_count * SPHERE_WORDS;
+    for (var i = 0u; i < plane_count; i++) {
+        let base = plane_base + i * PLANE_WORDS;
+        let normal = read_vec3(base);
+        let point = read_vec3(base + 4u);
+        let denom = dot(normal, ray.direction);
+        if (abs(denom) < 1e-6) {
+            continue;
+        }
+        let t = dot(point - ray.origin, normal) / denom;
+        if (t > HIT_EPSILON && t < hit.distance) {
+            hit.hit = true;
+            hit.distance = t;
+            hit.position = ray.origin + ray.direction * t;
+            hit.normal = normal;
+            hit.albedo = read_vec3(base + 8u);
+            hit.emissive = read_vec3(base + 12u);
+        }
+    }
+
+    return hit;
+}
+
+// 在 max_distance 之内光线是否被场景几何体遮挡，与 RayTracingScene::occluded 一致
+fn occluded(origin: vec3<f32>, direction: vec3<f32>, max_distance: f32) -> bool {
+    let sphere_count = scene_data[0];
+    let plane_count = scene_data[1];
+
+    for (var i = 0u; i < sphere_count; i++) {
+        let base = SCENE_HEADER_WORDS + i * SPHERE_WORDS;
+        let oc = origin - read_vec3(base);
+        let radius = read_f32(base + 3u);
+        let b = dot(oc, direction);
+        let discriminant = b * b - (dot(oc, oc) - radius * radius);
+        if (discriminant < 0.0) {
+            continue;
+        }
+        let sqrt_d = sqrt(discriminant);
+        var t = -b - sqrt_d;
+        if (t <= HIT_EPSILON) {
+            t = -b + sqrt_d;
+        }
+        if (t > HIT_EPSILON && t < max_distance) {
+            return true;
+        }
+    }
+
+    let plane_base = SCENE_HEADER_WORDS + sphere_count * SPHERE_WORDS;
+    for (var i = 0u; i < plane_count; i++) {
+        let base = plane_base + i * PLANE_WORDS;
+        let normal = read_vec3(base);
+        let denom = dot(normal, direction);
+        if (abs(denom) < 1e-6) {
+            continue;
+        }
+        let t = dot(read_vec3(base + 4u) - origin, normal) / denom;
+        if (t > HIT_EPSILON && t < max_distance) {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+// PCG 哈希，用于面光源采样点的抖动
+fn pcg_hash(value: u32) -> u32 {
+    let state = value * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+fn random_f32(seed: ptr<function, u32>) -> f32 {
+    *seed = pcg_hash(*seed);
+    return f32(*seed >> 8u) / 16777216.0;
+}
+
+// 单个光源对着色点的直接光照（含阴影），与 RayTracingScene::direct_lighting 一致
+fn direct_lighting(light_index: u32, point: vec3<f32>, normal: vec3<f32>, seed: ptr<function, u32>) -> vec3<f32> {
+    let base = SCENE_HEADER_WORDS + scene_data[0] * SPHERE_WORDS + scene_data[1] * PLANE_WORDS
+        + light_index * LIGHT_WORDS;
+    let position = read_vec3(base);
+    let light_type = scene_data[base + 3u];
+    let radiance = read_vec3(base + 4u);
+    let cos_angle = read_f32(base + 7u);
+    let direction = read_vec3(base + 8u);
+
+    if (light_type == LIGHT_TYPE_DIRECTIONAL) {
+        let to_light = -direction;
+        let cos_theta = max(dot(normal, to_light), 0.0);
+        if (occluded(point + to_light * HIT_EPSILON, to_light, 3.4e38)) {
+            return vec3<f32>(0.0);
+        }
+        return radiance * cos_theta;
+    }
+
+    if (light_type == LIGHT_TYPE_AREA) {
+        // 完整的 columns x rows 分层网格，每个采样点代表相同面积
+        let edge_u = read_vec3(base + 12u);
+        let edge_v = read_vec3(base + 16u);
+        let samples = max(config.area_light_samples, 1u);
+        let columns = u32(ceil(sqrt(f32(samples))));
+        let rows = (samples + columns - 1u) / columns;
+        let count = columns * rows;
+        let sample_area = length(edge_u) * length(edge_v) / f32(count);
+
+        var total = vec3<f32>(0.0);
+        for (var i = 0u; i < count; i++) {
+            var jitter = vec2<f32>(0.5);
+            if (count > 1u) {
+                jitter = vec2<f32>(random_f32(seed), random_f32(seed));
+            }
+            let u = (f32(i % columns) + jitter.x) / f32(columns) - 0.5;
+            let v = (f32(i / columns) + jitter.y) / f32(rows) - 0.5;
+            let offset = position + edge_u * u + edge_v * v - point;
+            let distance_sq = dot(offset, offset);
+            if (distance_sq <= HIT_EPSILON * HIT_EPSILON) {
+                continue;
+            }
+            let distance = sqrt(distance_sq);
+            let to_light = offset / distance;
+            let cos_surface = max(dot(normal, to_light), 0.0);
+            let cos_light = max(dot(direction, -to_light), 0.0);
+            if (cos_surface == 0.0 || cos_light == 0.0) {
+                continue;
+            }
+            if (occluded(point + to_light * HIT_EPSILON, to_light, distance - 2.0 * HIT_EPSILON)) {
+                continue;
+            }
+            total += radiance * (cos_surface * cos_light / distance_sq * sample_area);
+        }
+        return total;
+    }
+
+    // 点光源和聚光灯
+    let offset = position - point;
+    let distance_sq = max(dot(offset, offset), HIT_EPSILON);
+    let distance = sqrt(dot(offset, offset));
+    var to_light = vec3<f32>(0.0);
+    if (distance > 0.0) {
+        to_light = offset / distance;
+    }
+    if (light_type == LIGHT_TYPE_SPOT && dot(-to_light, direction) < cos_angle) {
+        return vec3<f32>(0.0);
+    }
+    let cos_theta = max(dot(normal, to_light), 0.0);
+    if (distance > HIT_EPSILON
+        && occluded(point + to_light * HIT_EPSILON, to_light, distance - 2.0 * HIT_EPSILON)) {
+        return vec3<f32>(0.0);
+    }
+    return radiance * cos_theta / distance_sq;
 }
 
 @compute @workgroup_size(8, 8)
@@ -510,26 +1424,103 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
     
     // 计算UV坐标
-    let uv = vec2<f32>(global_id.xy) / vec2<f32>(width, height);
+    let uv = vec2<f32>(global_id.xy) / vec2<f32>(f32(width), f32(height));
     
     // 生成相机光线（简化实现）
-    let ray = Ray {
-        origin: vec3<f32>(0.0, 0.0, -5.0),
-        direction: normalize(vec3<f32>(
+    let ray = Ray(
+        vec3<f32>(0.0, 0.0, -5.0),
+        normalize(vec3<f32>(
             (uv.x - 0.5) * 2.0,
             (uv.y - 0.5) * 2.0,
             1.0
         )),
-    };
+    );
     
     // 追踪光线
-    var color = vec3<f32>(0.0, 0.0, 0.0);
-    
-    // 简化实现：返回环境色
-    color = config.ambient_color;
+    let hit = trace_primary(ray);
+
+    // 未命中时返回环境色，命中时计算环境光、自发光和各光源带阴影的直接光照
+    var color = config.ambient_color;
+    var guide = vec4<f32>(0.0);
+    if (hit.hit) {
+        var seed = pcg_hash(global_id.x + global_id.y * width);
+        var direct = vec3<f32>(0.0);
+        for (var i = 0u; i < scene_data[2]; i++) {
+            direct += direct_lighting(i, hit.position, hit.normal, &seed);
+        }
+        color = hit.albedo * (config.ambient_color + direct) + hit.emissive;
+        guide = vec4<f32>(hit.normal, hit.distance);
+    }
     
     // 写入输出
     textureStore(output_texture, vec2<i32>(global_id.xy), vec4<f32>(color, 1.0));
+
+    // 写入降噪引导：主光线命中的法线和距离，未命中时均为 0
+    textureStore(guide_texture, vec2<i32>(global_id.xy), guide);
+}
+"#;
+
+/// À-Trous 降噪计算着色器，与 `atrous_denoise` 的算法一致
+const DENOISE_SHADER: &str = r#"
+@group(0) @binding(0) var color_input: texture_2d<f32>;
+@group(0) @binding(1) var guide_input: texture_2d<f32>;
+@group(0) @binding(2) var color_output: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(3) var<uniform> params: DenoiseParams;
+
+struct DenoiseParams {
+    step_width: u32,
+    color_phi: f32,
+    normal_power: f32,
+    depth_phi: f32,
+}
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(color_input));
+    let p = vec2<i32>(global_id.xy);
+    if (p.x >= size.x || p.y >= size.y) {
+        return;
+    }
+
+    let cp = textureLoad(color_input, p, 0);
+    let gp = textureLoad(guide_input, p, 0);
+    let step = i32(params.step_width);
+
+    // B3 样条核
+    var kernel = array<f32, 5>(1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0);
+    let center_weight = kernel[2] * kernel[2];
+    var sum = cp.rgb * center_weight;
+    var weight_sum = center_weight;
+
+    for (var ky = 0; ky < 5; ky++) {
+        for (var kx = 0; kx < 5; kx++) {
+            if (kx == 2 && ky == 2) {
+                continue;
+            }
+            let q = p + vec2<i32>(kx - 2, ky - 2) * step;
+            if (q.x < 0 || q.y < 0 || q.x >= size.x || q.y >= size.y) {
+                continue;
+            }
+
+            let cq = textureLoad(color_input, q, 0).rgb;
+            let gq = textureLoad(guide_input, q, 0);
+
+            // 边缘停止权重，任一像素未命中（法线为零）时只使用颜色权重
+            let dc = cp.rgb - cq;
+            var weight = kernel[kx] * kernel[ky] * exp(-dot(dc, dc) / params.color_phi);
+            if (any(gp.xyz != vec3<f32>(0.0)) && any(gq.xyz != vec3<f32>(0.0))) {
+                let w_normal = pow(max(dot(gp.xyz, gq.xyz), 0.0), params.normal_power);
+                let w_depth = exp(-abs(gp.w - gq.w)
+                    / (params.depth_phi * f32(params.step_width) * max(gp.w, 1e-3)));
+                weight *= w_normal * w_depth;
+            }
+
+            sum += cq * weight;
+            weight_sum += weight;
+        }
+    }
+
+    textureStore(color_output, p, vec4<f32>(sum / weight_sum, cp.a));
 }
 "#;
 
@@ -545,10 +1536,552 @@ mod tests {
         assert_eq!(config.max_bounces, 2);
     }
 
+    #[test]
+    fn test_denoise_iterations_are_capped() {
+        let config = RayTracingConfig {
+            denoise_iterations: 40,
+            ..Default::default()
+        };
+        assert_eq!(config.denoise_passes(), MAX_DENOISE_ITERATIONS);
+
+        let color = vec![Vec3::ONE; 4];
+        let guide = vec![
+            DenoiseGuide {
+                normal: Vec3::Z,
+                depth: 1.0,
+            };
+            4
+        ];
+        let denoised = atrous_denoise(&color, &guide, 2, 2, 40);
+        assert!(denoised.iter().all(|c| c.abs_diff_eq(Vec3::ONE, 1e-5)));
+    }
+
     #[test]
     fn test_material_default() {
         let material = Material::default();
         assert_eq!(material.metallic, 0.0);
         assert_eq!(material.roughness, 0.5);
     }
+
+    #[test]
+    fn test_area_light_soft_shadow() {
+        // 地面上方一个球形遮挡物，光源位于遮挡物正上方
+        let scene = RayTracingScene {
+            spheres: vec![Sphere {
+                center: Vec3::new(0.0, 2.0, 0.0),
+                radius: 0.5,
+                material: Material::default(),
+            }],
+            planes: vec![RayTracingPlane {
+                normal: Vec3::Y,
+                point: Vec3::ZERO,
+                material: Material::default(),
+            }],
+            lights: vec![],
+            ambient_color: Vec3::ZERO,
+        };
+        let point_light = Light {
+            position: Vec3::new(0.0, 4.0, 0.0),
+            color: Vec3::ONE,
+            intensity: 1.0,
+            light_type: LightType::Point,
+        };
+        let area_light = Light {
+            light_type: LightType::Area {
+                width: 2.0,
+                height: 2.0,
+                normal: -Vec3::Y,
+            },
+            ..point_light.clone()
+        };
+
+        // 遮挡物边缘附近：点光源完全照亮，面光源处于半影
+        let penumbra = Vec3::new(1.2, 0.0, 0.0);
+        assert_eq!(scene.light_visibility(&point_light, penumbra, 16), 1.0);
+        let visibility = scene.light_visibility(&area_light, penumbra, 16);
+        assert!(visibility > 0.0 && visibility < 1.0, "{}", visibility);
+
+        // 正下方：点光源完全被遮挡
+        assert_eq!(scene.light_visibility(&point_light, Vec3::ZERO, 16), 0.0);
+
+        // 远离遮挡物：两者都完全可见
+        let lit = Vec3::new(4.0, 0.0, 0.0);
+        assert_eq!(scene.light_visibility(&area_light, lit, 16), 1.0);
+
+        // 半影处的直接光照介于全影和完全照亮之间
+        let config = RayTracingConfig::default();
+        let shaded = scene.direct_lighting(&area_light, penumbra, Vec3::Y, &config);
+        let unoccluded = RayTracingScene {
+            spheres: vec![],
+            ..scene.clone()
+        }
+        .direct_lighting(&area_light, penumbra, Vec3::Y, &config);
+        assert!(shaded.x > 0.0 && shaded.x < unoccluded.x);
+    }
+
+    #[test]
+    fn test_area_light_samples_jittered_within_strata() {
+        let light = Light {
+            position: Vec3::ZERO,
+            color: Vec3::ONE,
+            intensity: 1.0,
+            light_type: LightType::Area {
+                width: 2.0,
+                height: 2.0,
+                normal: -Vec3::Y,
+            },
+        };
+        let (tangent, bitangent) = Vec3::NEG_Y.any_orthonormal_pair();
+
+        // 2x2 分层：每个采样点落在各自的格子内，多次采样不会全部落在格子中心
+        let mut off_center = false;
+        for _ in 0..8 {
+            for (i, point) in light.sample_points(4).into_iter().enumerate() {
+                let (u, v) = (point.dot(tangent), point.dot(bitangent));
+                let (column, row) = ((i % 2) as f32, (i / 2) as f32);
+                assert!((column - 1.0..=column).contains(&u), "{} {}", i, u);
+                assert!((row - 1.0..=row).contains(&v), "{} {}", i, v);
+                off_center |= (u - (column - 0.5)).abs() > 1e-4;
+            }
+        }
+        assert!(off_center);
+
+        // 单个采样点固定在光源中心
+        assert_eq!(light.sample_points(1), vec![Vec3::ZERO]);
+    }
+
+    #[test]
+    fn test_area_light_grid_is_complete() {
+        assert_eq!(area_light_grid(16), (4, 4));
+        assert_eq!(area_light_grid(5), (3, 2));
+        assert_eq!(area_light_grid(0), (1, 1));
+
+        // 采样数不是完全平方数时采样完整的 3x2 网格，每个格子恰好一个采样点
+        let light = Light {
+            position: Vec3::ZERO,
+            color: Vec3::ONE,
+            intensity: 1.0,
+            light_type: LightType::Area {
+                width: 3.0,
+                height: 2.0,
+                normal: -Vec3::Y,
+            },
+        };
+        let (tangent, bitangent) = Vec3::NEG_Y.any_orthonormal_pair();
+        let points = light.sample_points(5);
+        assert_eq!(points.len(), 6);
+        let mut cells: Vec<(i32, i32)> = points
+            .iter()
+            .map(|p| {
+                (
+                    (p.dot(tangent) + 1.5).floor() as i32,
+                    (p.dot(bitangent) + 1.0).floor() as i32,
+                )
+            })
+            .collect();
+        cells.sort_unstable();
+        assert_eq!(cells, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn test_serialize_scene_includes_lights() {
+        let scene = RayTracingScene {
+            spheres: vec![],
+            planes: vec![],
+            lights: vec![Light {
+                position: Vec3::new(1.0, 2.0, 3.0),
+                color: Vec3::ONE,
+                intensity: 2.0,
+                light_type: LightType::Area {
+                    width: 2.0,
+                    height: 0.5,
+                    normal: Vec3::NEG_Y,
+                },
+            }],
+            ambient_color: Vec3::ZERO,
+        };
+        let data = serialize_scene(&scene);
+        let words: &[u32] = bytemuck::cast_slice(&data);
+        assert_eq!(words.len(), SCENE_HEADER_WORDS + LIGHT_WORDS);
+        assert_eq!(words[2], 1);
+
+        let light = &words[SCENE_HEADER_WORDS..];
+        let vec3_at = |i: usize| Vec3::from_array([0, 1, 2].map(|k| f32::from_bits(light[i + k])));
+        assert_eq!(vec3_at(0), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(light[3], LIGHT_TYPE_AREA);
+        assert_eq!(vec3_at(4), Vec3::splat(2.0));
+        assert_eq!(vec3_at(8), Vec3::NEG_Y);
+        let (tangent, bitangent) = Vec3::NEG_Y.any_orthonormal_pair();
+        assert_eq!(vec3_at(12), tangent * 2.0);
+        assert_eq!(vec3_at(16), bitangent * 0.5);
+    }
+
+    #[test]
+    fn test_atrous_denoise_preserves_normal_edge() {
+        // 左半朝向相机、右半朝向侧面的两个平面，颜色带确定性噪声
+        let (width, height) = (16usize, 16usize);
+        let mut color = Vec::with_capacity(width * height);
+        let mut guide = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let hash = ((y * width + x) as u32).wrapping_mul(2_654_435_761) >> 16;
+                let noise = (hash % 1000) as f32 / 1000.0 * 0.4 - 0.2;
+                let (base, normal) = if x < width / 2 {
+                    (0.2, Vec3::Z)
+                } else {
+                    (0.8, Vec3::X)
+                };
+                color.push(Vec3::splat(base + noise));
+                guide.push(DenoiseGuide { normal, depth: 5.0 });
+            }
+        }
+
+        let denoised = atrous_denoise(&color, &guide, width, height, 3);
+
+        let variance = |image: &[Vec3], columns: std::ops::Range<usize>| {
+            let values: Vec<f32> = (0..height)
+                .flat_map(|y| columns.clone().map(move |x| y * width + x))
+                .map(|i| image[i].x)
+                .collect();
+            let mean = values.iter().sum::<f32>() / values.len() as f32;
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32
+        };
+
+        // 平坦区域的方差显著降低
+        assert!(variance(&denoised, 0..8) < variance(&color, 0..8) * 0.25);
+        assert!(variance(&denoised, 8..16) < variance(&color, 8..16) * 0.25);
+
+        // 法线不连续处两侧不会互相渗色
+        for y in 0..height {
+            let left = denoised[y * width + width / 2 - 1].x;
+            let right = denoised[y * width + width / 2].x;
+            assert!((left - 0.2).abs() < 0.1, "left edge bled: {}", left);
+            assert!((right - 0.8).abs() < 0.1, "right edge bled: {}", right);
+        }
+    }
+
+    #[test]
+    fn test_atrous_denoise_smooths_miss_pixels() {
+        // 未命中像素的引导为零，只按颜色权重平滑
+        let (width, height) = (16usize, 16usize);
+        let color: Vec<Vec3> = (0..width * height)
+            .map(|i| {
+                let hash = (i as u32).wrapping_mul(2_654_435_761) >> 16;
+                Vec3::splat(0.5 + (hash % 1000) as f32 / 1000.0 * 0.4 - 0.2)
+            })
+            .collect();
+        let guide = vec![
+            DenoiseGuide {
+                normal: Vec3::ZERO,
+                depth: 0.0,
+            };
+            width * height
+        ];
+
+        let denoised = atrous_denoise(&color, &guide, width, height, 3);
+        let variance = |image: &[Vec3]| {
+            let mean = image.iter().map(|c| c.x).sum::<f32>() / image.len() as f32;
+            image.iter().map(|c| (c.x - mean).powi(2)).sum::<f32>() / image.len() as f32
+        };
+        assert!(variance(&denoised) < variance(&color) * 0.25);
+    }
+
+    fn f16_to_f32(bits: u16) -> f32 {
+        let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+        let exponent = ((bits >> 10) & 0x1f) as i32;
+        let fraction = (bits & 0x3ff) as f32 / 1024.0;
+        sign * match exponent {
+            0 => fraction * 2f32.powi(-14),
+            31 => f32::INFINITY,
+            _ => (1.0 + fraction) * 2f32.powi(exponent - 15),
+        }
+    }
+
+    #[test]
+    fn test_gpu_guide_matches_primary_hit() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        const SIZE: u32 = 32;
+        let config = RayTracingConfig {
+            enabled: true,
+            resolution_scale: 1.0,
+            ..Default::default()
+        };
+        let scene = RayTracingScene {
+            spheres: vec![Sphere {
+                center: Vec3::ZERO,
+                radius: 1.0,
+                material: Material::default(),
+            }],
+            planes: vec![],
+            lights: vec![],
+            ambient_color: Vec3::splat(0.1),
+        };
+        let camera = Camera {
+            view: Mat4::IDENTITY,
+            projection: Mat4::IDENTITY,
+            position: Vec3::new(0.0, 0.0, -5.0),
+            direction: Vec3::Z,
+        };
+
+        let mut renderer = RayTracingRenderer::new(&device, config).unwrap();
+        renderer.prepare_output(&device, SIZE, SIZE).unwrap();
+        renderer.update_scene(&device, &queue, &scene).unwrap();
+        let bind_group = renderer.create_bind_group(&device).unwrap();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        renderer
+            .render(&device, &mut encoder, &bind_group, &camera)
+            .unwrap();
+
+        let guide_texture = renderer.guide_texture.as_ref().unwrap();
+        let bytes_per_row = SIZE * 8;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Guide Readback"),
+            size: (bytes_per_row * SIZE) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            guide_texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            guide_texture.size(),
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let texels: &[u16] = bytemuck::cast_slice(&data);
+        let guide_at = |x: u32, y: u32| {
+            let i = ((y * SIZE + x) * 4) as usize;
+            let texel: Vec<f32> = texels[i..i + 4].iter().map(|&h| f16_to_f32(h)).collect();
+            DenoiseGuide {
+                normal: Vec3::new(texel[0], texel[1], texel[2]),
+                depth: texel[3],
+            }
+        };
+
+        // 与着色器中的主光线一致
+        let expected_at = |x: u32, y: u32| {
+            let uv = Vec3::new(x as f32 / SIZE as f32, y as f32 / SIZE as f32, 0.0);
+            let direction = Vec3::new((uv.x - 0.5) * 2.0, (uv.y - 0.5) * 2.0, 1.0).normalize();
+            scene.primary_guide(camera.position, direction)
+        };
+
+        // 中心像素命中球体正面，角落像素未命中
+        let center = guide_at(SIZE / 2, SIZE / 2);
+        assert!(center.normal.distance(Vec3::NEG_Z) < 1e-2, "{:?}", center);
+        assert!((center.depth - 4.0).abs() < 1e-2, "{:?}", center);
+        for (x, y) in [(SIZE / 2, SIZE / 2), (SIZE / 2 + 3, SIZE / 2 - 2), (0, 0)] {
+            let (actual, expected) = (guide_at(x, y), expected_at(x, y));
+            assert!(
+                actual.normal.distance(expected.normal) < 1e-2
+                    && (actual.depth - expected.depth).abs() < 1e-2,
+                "({}, {}): {:?} != {:?}",
+                x,
+                y,
+                actual,
+                expected
+            );
+        }
+        assert_eq!(guide_at(0, 0).normal, Vec3::ZERO);
+    }
+
+    /// 渲染一帧并读回输出纹理的颜色，不做降噪
+    fn render_color(
+        device: &Device,
+        queue: &Queue,
+        config: RayTracingConfig,
+        scene: &RayTracingScene,
+        size: u32,
+    ) -> Vec<Vec3> {
+        let camera = Camera {
+            view: Mat4::IDENTITY,
+            projection: Mat4::IDENTITY,
+            position: Vec3::new(0.0, 0.0, -5.0),
+            direction: Vec3::Z,
+        };
+        let mut renderer = RayTracingRenderer::new(device, config).unwrap();
+        renderer.prepare_output(device, size, size).unwrap();
+        renderer.update_scene(device, queue, scene).unwrap();
+        let bind_group = renderer.create_bind_group(device).unwrap();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        renderer
+            .render(device, &mut encoder, &bind_group, &camera)
+            .unwrap();
+
+        let output_texture = renderer.output_texture.as_ref().unwrap();
+        let bytes_per_row = size * 8;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Test Color Readback"),
+            size: (bytes_per_row * size) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            output_texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(size),
+                },
+            },
+            output_texture.size(),
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let texels: &[u16] = bytemuck::cast_slice(&data);
+        texels
+            .chunks_exact(4)
+            .map(|t| Vec3::new(f16_to_f32(t[0]), f16_to_f32(t[1]), f16_to_f32(t[2])))
+            .collect()
+    }
+
+    #[test]
+    fn test_gpu_area_light_soft_shadow() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        // 相机前方朝向相机的墙面，面光源位于相机与遮挡球之间并朝向墙面
+        const SIZE: u32 = 32;
+        let scene = RayTracingScene {
+            spheres: vec![Sphere {
+                center: Vec3::new(0.0, 0.0, 0.5),
+                radius: 0.5,
+                material: Material::default(),
+            }],
+            planes: vec![RayTracingPlane {
+                normal: Vec3::NEG_Z,
+                point: Vec3::new(0.0, 0.0, 2.0),
+                material: Material::default(),
+            }],
+            lights: vec![Light {
+                position: Vec3::new(0.0, 0.0, -1.0),
+                color: Vec3::ONE,
+                intensity: 10.0,
+                light_type: LightType::Area {
+                    width: 1.0,
+                    height: 1.0,
+                    normal: Vec3::Z,
+                },
+            }],
+            ambient_color: Vec3::ZERO,
+        };
+        let unoccluded = RayTracingScene {
+            spheres: vec![],
+            ..scene.clone()
+        };
+        let config = RayTracingConfig {
+            enabled: true,
+            resolution_scale: 1.0,
+            denoise_iterations: 0,
+            ..Default::default()
+        };
+
+        // 与着色器中的主光线一致（中间一行），返回墙面上的着色点
+        let origin = Vec3::new(0.0, 0.0, -5.0);
+        let ray = |x: u32| Vec3::new((x as f32 / SIZE as f32 - 0.5) * 2.0, 0.0, 1.0).normalize();
+        let wall_point = |x: u32| origin + ray(x) * (7.0 / ray(x).z);
+        let y = SIZE / 2;
+        let albedo = Material::default().albedo.x;
+
+        // 关闭软阴影时只采样面光源中心，结果与 CPU 参考实现一致
+        let hard = RayTracingConfig {
+            soft_shadows: false,
+            ..config.clone()
+        };
+        let colors = render_color(&device, &queue, hard.clone(), &scene, SIZE);
+        for x in [2, 8, 26, 30] {
+            let expected =
+                scene.direct_lighting(&scene.lights[0], wall_point(x), Vec3::NEG_Z, &hard);
+            let actual = colors[(y * SIZE + x) as usize];
+            assert!(
+                (actual.x - albedo * expected.x).abs() < 1e-2 * (1.0 + expected.x),
+                "{}: {} != {}",
+                x,
+                actual.x,
+                albedo * expected.x
+            );
+        }
+
+        // 软阴影：半影中的像素部分可见，介于全影和无遮挡之间
+        let penumbra = (SIZE / 2..SIZE)
+            .filter(|&x| scene.spheres[0].intersect(origin, ray(x)).is_none())
+            .find(|&x| {
+                let visibility = scene.light_visibility(&scene.lights[0], wall_point(x), 256);
+                (0.25..0.75).contains(&visibility)
+            })
+            .expect("no penumbra pixel on the wall");
+        let colors = render_color(&device, &queue, config.clone(), &scene, SIZE);
+        let open = render_color(&device, &queue, config.clone(), &unoccluded, SIZE);
+        let i = (y * SIZE + penumbra) as usize;
+        assert!(
+            colors[i].x > 0.0 && colors[i].x < open[i].x,
+            "{} not in (0, {})",
+            colors[i].x,
+            open[i].x
+        );
+
+        // 非完全平方的采样数使用完整网格：漏采的格子位于光源一侧，会使左右两半
+        // 墙面的光照总和偏离高采样数的估计，逐像素噪声则在求和中抵消
+        let near = RayTracingScene {
+            lights: vec![Light {
+                position: Vec3::ZERO,
+                light_type: LightType::Area {
+                    width: 6.0,
+                    height: 6.0,
+                    normal: Vec3::Z,
+                },
+                ..scene.lights[0].clone()
+            }],
+            ..unoccluded
+        };
+        let sparse = RayTracingConfig {
+            area_light_samples: 5,
+            ..config.clone()
+        };
+        let open = render_color(&device, &queue, sparse, &near, SIZE);
+        let reference = RayTracingConfig {
+            area_light_samples: 256,
+            ..config
+        };
+        let (mut actual, mut expected) = ([0.0f32; 2], [0.0f32; 2]);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let uv =
+                    Vec3::new(x as f32, y as f32, 0.0) / SIZE as f32 - Vec3::new(0.5, 0.5, 0.0);
+                let direction = Vec3::new(uv.x * 2.0, uv.y * 2.0, 1.0).normalize();
+                let point = origin + direction * (7.0 / direction.z);
+                let half = (x >= SIZE / 2) as usize;
+                actual[half] += open[(y * SIZE + x) as usize].x;
+                expected[half] += albedo
+                    * near
+                        .direct_lighting(&near.lights[0], point, Vec3::NEG_Z, &reference)
+                        .x;
+            }
+        }
+        for half in 0..2 {
+            let ratio = actual[half] / expected[half];
+            assert!((ratio - 1.0).abs() < 0.05, "half {}: {}", half, ratio);
+        }
+    }
 }
@@ -9,12 +9,17 @@
 //! 3. **进度跟踪**: 实时追踪编译进度
 //! 4. **超时处理**: 防止编译任务无限期阻塞
 //! 5. **优先级管理**: 关键着色器优先编译
+//! 6. **取消与错误回调**: 通过`ShaderCompileHandle`取消不再需要的编译，
+//!    WGSL编译错误通过`on_error`回调上报
 
 use crate::core::error::RenderError;
 use crate::impl_default;
-use crate::render::shader_cache::{ShaderCache, ShaderCacheKey};
+use crate::render::shader_cache::{
+    CacheAdapterInfo, PipelineCacheDesc, ShaderCache, ShaderCacheKey,
+};
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -61,6 +66,8 @@ pub struct ShaderCompileRequest {
     pub priority: ShaderCompilePriority,
     /// 创建时间
     pub created_at: Instant,
+    /// 取消标记（由`ShaderCompileHandle::cancel`设置）
+    pub cancelled: Arc<AtomicBool>,
     /// 响应通道
     pub response_tx: oneshot::Sender<Result<CompiledShader, CompileError>>,
 }
@@ -120,6 +127,57 @@ pub enum CompileError {
     Cancelled,
 }
 
+/// 编译错误回调，参数为着色器标签和错误
+pub type CompileErrorCallback = Arc<dyn Fn(Option<&str>, &CompileError) + Send + Sync>;
+
+/// 编译请求句柄
+///
+/// 取消后编译结果不会被缓存或返回，等待句柄会得到`CompileError::Cancelled`。
+pub struct ShaderCompileHandle {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+    rx: oneshot::Receiver<Result<CompiledShader, CompileError>>,
+}
+
+impl ShaderCompileHandle {
+    /// 请求ID
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// 取消编译
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Release);
+    }
+
+    /// 是否已取消
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Acquire)
+    }
+
+    /// 等待编译完成
+    pub async fn wait(self) -> Result<CompiledShader, CompileError> {
+        if self.is_cancelled() {
+            return Err(CompileError::Cancelled);
+        }
+        wait_for_compile(self.rx).await
+    }
+}
+
+/// 使用naga解析并验证WGSL源码，返回带源码位置的错误信息
+fn validate_wgsl(source: &str) -> Result<(), String> {
+    use wgpu::naga;
+
+    let module = naga::front::wgsl::parse_str(source).map_err(|e| e.emit_to_string(source))?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|e| e.emit_to_string(source))?;
+    Ok(())
+}
+
 /// 编译进度信息
 #[derive(Debug, Clone)]
 pub struct CompileProgress {
@@ -175,19 +233,61 @@ pub struct AsyncShaderCompiler {
     next_id: Arc<Mutex<u64>>,
     /// 着色器缓存（可选）
     cache: Option<Arc<Mutex<ShaderCache>>>,
+    /// 编译错误回调
+    error_callbacks: Arc<Mutex<Vec<CompileErrorCallback>>>,
+}
+
+/// 参与缓存键的设备特性和限制
+#[derive(Debug, Clone, Default)]
+struct DeviceCaps {
+    features: wgpu::Features,
+    limits: wgpu::Limits,
 }
 
 impl AsyncShaderCompiler {
     /// 创建新的异步着色器编译器
+    ///
+    /// 缓存键使用默认的设备特性和限制；面向具体设备时使用 `for_device`。
     pub fn new(
         config: AsyncShaderCompilerConfig,
         cache: Option<ShaderCache>,
+    ) -> Result<Self, RenderError> {
+        Self::with_device_caps(config, cache, DeviceCaps::default())
+    }
+
+    /// 创建面向指定设备的异步着色器编译器
+    ///
+    /// 缓存绑定到 `adapter` 的GPU信息，缓存键包含 `device` 的特性和限制，
+    /// 其他GPU或设备配置生成的缓存不会被使用。
+    pub fn for_device(
+        config: AsyncShaderCompilerConfig,
+        cache: Option<ShaderCache>,
+        adapter: &wgpu::Adapter,
+        device: &wgpu::Device,
+    ) -> Result<Self, RenderError> {
+        let adapter_info = CacheAdapterInfo::from_adapter_info(&adapter.get_info());
+        let caps = DeviceCaps {
+            features: device.features(),
+            limits: device.limits(),
+        };
+        Self::with_device_caps(
+            config,
+            cache.map(|cache| cache.with_adapter(adapter_info)),
+            caps,
+        )
+    }
+
+    fn with_device_caps(
+        config: AsyncShaderCompilerConfig,
+        cache: Option<ShaderCache>,
+        caps: DeviceCaps,
     ) -> Result<Self, RenderError> {
         let (request_tx, request_rx) = mpsc::unbounded_channel();
         let (progress_tx, progress_rx) = mpsc::unbounded_channel();
 
         let cache_arc = cache.map(|c| Arc::new(Mutex::new(c)));
         let cache_clone = cache_arc.clone();
+        let error_callbacks: Arc<Mutex<Vec<CompileErrorCallback>>> = Arc::default();
 
         let max_concurrent = config.max_concurrent_compiles;
         let timeout_ms = config.compile_timeout_ms;
@@ -198,6 +298,8 @@ impl AsyncShaderCompiler {
             request_rx,
             progress_tx,
             cache_clone,
+            Arc::new(caps),
+            error_callbacks.clone(),
             max_concurrent,
             timeout_ms,
             enable_cache,
@@ -209,6 +311,7 @@ impl AsyncShaderCompiler {
             progress_rx: Arc::new(Mutex::new(progress_rx)),
             next_id: Arc::new(Mutex::new(1)),
             cache: cache_arc,
+            error_callbacks,
         })
     }
 
@@ -232,10 +335,13 @@ impl AsyncShaderCompiler {
     }
 
     /// 编译任务处理器（后台运行）
+    #[allow(clippy::too_many_arguments)]
     async fn compiler_task(
         mut request_rx: mpsc::UnboundedReceiver<ShaderCompileRequest>,
         progress_tx: mpsc::UnboundedSender<CompileProgress>,
         cache: Option<Arc<Mutex<ShaderCache>>>,
+        caps: Arc<DeviceCaps>,
+        error_callbacks: Arc<Mutex<Vec<CompileErrorCallback>>>,
         max_concurrent: usize,
         timeout_ms: u64,
         enable_cache: bool,
@@ -270,6 +376,8 @@ impl AsyncShaderCompiler {
                         priority_queue.clone(),
                         semaphore.clone(),
                         cache.clone(),
+                        caps.clone(),
+                        error_callbacks.clone(),
                         &mut stats,
                         progress_tx.clone(),
                         timeout_ms,
@@ -282,10 +390,13 @@ impl AsyncShaderCompiler {
     }
 
     /// 处理编译队列
+    #[allow(clippy::too_many_arguments)]
     async fn process_queue(
         queue: Arc<Mutex<BinaryHeap<ShaderCompileRequest>>>,
         semaphore: Arc<tokio::sync::Semaphore>,
         cache: Option<Arc<Mutex<ShaderCache>>>,
+        caps: Arc<DeviceCaps>,
+        error_callbacks: Arc<Mutex<Vec<CompileErrorCallback>>>,
         stats: &mut CompileProgress,
         progress_tx: mpsc::UnboundedSender<CompileProgress>,
         timeout_ms: u64,
@@ -293,10 +404,19 @@ impl AsyncShaderCompiler {
     ) {
         // 尝试获取信号量许可
         if let Ok(permit) = semaphore.clone().try_acquire_owned() {
-            // 从队列取出最高优先级的请求
+            // 从队列取出最高优先级的请求，跳过已取消的请求
             let request = {
                 let mut q = queue.lock().unwrap();
-                q.pop()
+                let mut next = None;
+                while let Some(request) = q.pop() {
+                    if !request.cancelled.load(AtomicOrdering::Acquire) {
+                        next = Some(request);
+                        break;
+                    }
+                    stats.pending -= 1;
+                    let _ = request.response_tx.send(Err(CompileError::Cancelled));
+                }
+                next
             };
 
             if let Some(request) = request {
@@ -309,13 +429,21 @@ impl AsyncShaderCompiler {
                 let label = request.label.clone();
                 let source = request.source.clone();
                 let compile_options = request.compile_options.clone();
+                let cancelled = request.cancelled;
+                let caps = caps.clone();
 
                 // 在新任务中执行编译
                 tokio::spawn(async move {
                     let start = Instant::now();
 
                     // 生成缓存键
-                    let cache_key = ShaderCacheKey::from_source(&source, &compile_options);
+                    let cache_key = ShaderCacheKey::for_pipeline(&PipelineCacheDesc::for_module(
+                        label.as_deref().unwrap_or_default(),
+                        &source,
+                        &compile_options,
+                        caps.features,
+                        &caps.limits,
+                    ));
 
                     // 检查缓存
                     let cached_result = if enable_cache {
@@ -340,9 +468,9 @@ impl AsyncShaderCompiler {
                         // 缓存未命中，执行编译
                         // 注意：wgpu的create_shader_module是同步的，需要在阻塞任务中执行
                         let compile_future = tokio::task::spawn_blocking(move || {
-                            // 这里只是验证和预处理源码
-                            // 实际的wgpu编译需要在主线程进行
-                            // 当前实现：返回源码，实际编译由调用者完成
+                            // 这里只验证源码，实际的wgpu编译需要在主线程进行，
+                            // 由调用者使用返回的源码完成
+                            validate_wgsl(&source).map_err(CompileError::CompilationFailed)?;
                             Ok(CompiledShader {
                                 cache_key,
                                 source,
@@ -358,6 +486,19 @@ impl AsyncShaderCompiler {
                         }
                     };
 
+                    // 已取消的编译不缓存、不上报错误
+                    if cancelled.load(AtomicOrdering::Acquire) {
+                        let _ = response_tx.send(Err(CompileError::Cancelled));
+                        drop(permit);
+                        return;
+                    }
+
+                    if let Err(ref error) = result {
+                        for callback in error_callbacks.lock().unwrap().iter() {
+                            callback(label.as_deref(), error);
+                        }
+                    }
+
                     // 如果编译成功，存储到缓存
                     if let Ok(ref compiled) = result {
                         if enable_cache {
@@ -387,6 +528,18 @@ impl AsyncShaderCompiler {
         compile_options: &str,
         priority: ShaderCompilePriority,
     ) -> Result<oneshot::Receiver<Result<CompiledShader, CompileError>>, RenderError> {
+        self.submit(label, source, compile_options, priority)
+            .map(|handle| handle.rx)
+    }
+
+    /// 提交编译请求并返回可取消的句柄
+    pub fn submit(
+        &self,
+        label: Option<&str>,
+        source: &str,
+        compile_options: &str,
+        priority: ShaderCompilePriority,
+    ) -> Result<ShaderCompileHandle, RenderError> {
         let id = {
             let mut next_id = self.next_id.lock().unwrap();
             let id = *next_id;
@@ -395,6 +548,7 @@ impl AsyncShaderCompiler {
         };
 
         let (response_tx, response_rx) = oneshot::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
 
         let request = ShaderCompileRequest {
             id,
@@ -403,6 +557,7 @@ impl AsyncShaderCompiler {
             compile_options: compile_options.to_string(),
             priority,
             created_at: Instant::now(),
+            cancelled: cancelled.clone(),
             response_tx,
         };
 
@@ -410,7 +565,22 @@ impl AsyncShaderCompiler {
             RenderError::InvalidState(format!("Failed to send compile request: {}", e))
         })?;
 
-        Ok(response_rx)
+        Ok(ShaderCompileHandle {
+            id,
+            cancelled,
+            rx: response_rx,
+        })
+    }
+
+    /// 注册编译错误回调，WGSL编译失败时以着色器标签和错误调用
+    pub fn on_error<F>(&self, callback: F)
+    where
+        F: Fn(Option<&str>, &CompileError) + Send + Sync + 'static,
+    {
+        self.error_callbacks
+            .lock()
+            .unwrap()
+            .push(Arc::new(callback));
     }
 
     /// 编译着色器（简化版本）
@@ -480,4 +650,102 @@ mod tests {
         let _ = wait_for_compile(rx_high).await;
         let _ = wait_for_compile(rx_low).await;
     }
+
+    #[tokio::test]
+    async fn test_broken_shader_reports_error() {
+        let compiler = AsyncShaderCompiler::with_default_config().unwrap();
+        let (error_tx, mut error_rx) = mpsc::unbounded_channel();
+        compiler.on_error(move |label, error| {
+            let _ = error_tx.send((label.map(str::to_string), error.to_string()));
+        });
+
+        let handle = compiler
+            .submit(
+                Some("broken"),
+                "fn main() -> f32 { return undefined_value; }",
+                "",
+                ShaderCompilePriority::High,
+            )
+            .unwrap();
+        let result = handle.wait().await;
+        assert!(matches!(result, Err(CompileError::CompilationFailed(_))));
+
+        let (label, message) = error_rx.recv().await.unwrap();
+        assert_eq!(label.as_deref(), Some("broken"));
+        assert!(!message.is_empty());
+        assert!(message.contains("undefined_value"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_cancelled_compile_is_not_returned() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = ShaderCache::new(crate::render::shader_cache::ShaderCacheConfig {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let compiler =
+            AsyncShaderCompiler::new(AsyncShaderCompilerConfig::default(), Some(cache)).unwrap();
+        let cache = compiler.cache().unwrap();
+        let source = "fn main() {}";
+
+        // 持有缓存锁，让编译任务在开始后停在缓存查询处，保证取消发生在编译完成之前
+        let guard = cache.lock().unwrap();
+        let handle = compiler
+            .submit(None, source, "", ShaderCompilePriority::Normal)
+            .unwrap();
+        while !matches!(compiler.get_progress(), Some(progress) if progress.in_progress > 0) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        handle.cancel();
+        drop(guard);
+
+        // 编译照常完成，但工作任务丢弃结果，既不返回也不写入缓存
+        assert!(matches!(handle.rx.await, Ok(Err(CompileError::Cancelled))));
+        let limits = wgpu::Limits::default();
+        let key = ShaderCacheKey::for_pipeline(&PipelineCacheDesc::for_module(
+            "",
+            source,
+            "",
+            wgpu::Features::empty(),
+            &limits,
+        ));
+        assert!(cache.lock().unwrap().get(&key).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compiled_shader_is_cached_under_its_label() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = ShaderCache::new(crate::render::shader_cache::ShaderCacheConfig {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+        let compiler =
+            AsyncShaderCompiler::new(AsyncShaderCompilerConfig::default(), Some(cache)).unwrap();
+        let source = "@compute @workgroup_size(1) fn main() {}";
+
+        let handle = compiler
+            .submit(Some("blur"), source, "", ShaderCompilePriority::Normal)
+            .unwrap();
+        assert!(matches!(handle.rx.await, Ok(Ok(_))));
+
+        // 缓存条目带有标签，源码变化后可以按标签失效
+        let cache = compiler.cache().unwrap();
+        let mut cache = cache.lock().unwrap();
+        let limits = wgpu::Limits::default();
+        let key = ShaderCacheKey::for_pipeline(&PipelineCacheDesc::for_module(
+            "blur",
+            source,
+            "",
+            wgpu::Features::empty(),
+            &limits,
+        ));
+        assert!(cache.get(&key).unwrap().is_some());
+        assert_eq!(
+            cache.invalidate_changed("blur", "fn changed() {}").unwrap(),
+            1
+        );
+        assert!(cache.get(&key).unwrap().is_none());
+    }
 }
@@ -4,10 +4,11 @@
 //!
 //! ## 设计原则
 //!
-//! 1. **缓存键生成**: 基于着色器源码的SHA256哈希
+//! 1. **缓存键生成**: 基于着色器源码的SHA256哈希，管线缓存键还包含入口点、
+//!    管线布局以及设备特性/限制
 //! 2. **存储格式**: 文件系统缓存（跨平台路径管理）
 //! 3. **失效策略**: 源码变更时自动失效（hash不匹配）
-//! 4. **验证机制**: 缓存验证（hash匹配、格式兼容性检查）
+//! 4. **验证机制**: 缓存验证（hash匹配、格式兼容性检查、后端与适配器匹配）
 //!
 //! ## 架构设计
 //!
@@ -55,25 +56,42 @@ struct CacheMetadata {
     last_accessed: u64,
     /// 编译选项哈希（用于区分不同编译选项）
     compile_options_hash: String,
+    /// 着色器标签（用于按着色器失效旧条目）
+    #[serde(default)]
+    label: String,
+    /// 生成缓存时的wgpu后端
+    #[serde(default)]
+    backend: String,
+    /// 生成缓存时的适配器名称
+    #[serde(default)]
+    adapter_name: String,
 }
 
 impl CacheMetadata {
     /// 创建新的缓存元数据
-    fn new(source_hash: String, compile_options_hash: String) -> Self {
+    fn new(key: &ShaderCacheKey, adapter: Option<&CacheAdapterInfo>) -> Self {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
         Self {
-            source_hash,
+            source_hash: key.source_hash.clone(),
             format_version: CACHE_FORMAT_VERSION,
             created_at: now,
             last_accessed: now,
-            compile_options_hash,
+            compile_options_hash: key.compile_options_hash.clone(),
+            label: key.label.clone(),
+            backend: adapter.map(|a| a.backend.clone()).unwrap_or_default(),
+            adapter_name: adapter.map(|a| a.adapter_name.clone()).unwrap_or_default(),
         }
     }
 
+    /// 缓存是否由指定适配器生成
+    fn matches_adapter(&self, adapter: &CacheAdapterInfo) -> bool {
+        self.backend == adapter.backend && self.adapter_name == adapter.adapter_name
+    }
+
     /// 更新最后访问时间
     fn update_access_time(&mut self) {
         self.last_accessed = std::time::SystemTime::now()
@@ -84,15 +102,89 @@ impl CacheMetadata {
 }
 
 /// 缓存格式版本（用于兼容性检查）
-const CACHE_FORMAT_VERSION: u32 = 1;
+const CACHE_FORMAT_VERSION: u32 = 2;
+
+/// 生成缓存的GPU信息
+///
+/// 持久化缓存会记录后端和适配器名称，其他GPU生成的缓存不会被加载。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheAdapterInfo {
+    /// wgpu后端（Vulkan、Metal、Dx12等）
+    pub backend: String,
+    /// 适配器名称
+    pub adapter_name: String,
+}
+
+impl CacheAdapterInfo {
+    pub fn new(backend: impl Into<String>, adapter_name: impl Into<String>) -> Self {
+        Self {
+            backend: backend.into(),
+            adapter_name: adapter_name.into(),
+        }
+    }
+
+    /// 从wgpu适配器信息创建
+    pub fn from_adapter_info(info: &wgpu::AdapterInfo) -> Self {
+        Self::new(format!("{:?}", info.backend), info.name.clone())
+    }
+}
+
+/// 管线缓存键的输入
+///
+/// 除源码外，入口点、管线布局和设备特性/限制的任何变化都会产生不同的缓存键。
+#[derive(Debug, Clone)]
+pub struct PipelineCacheDesc<'a> {
+    /// 着色器标签（同一着色器的不同版本共享标签）
+    pub label: &'a str,
+    /// WGSL源码
+    pub source: &'a str,
+    /// 入口点
+    pub entry_point: &'a str,
+    /// 各绑定组布局的条目
+    pub bind_group_layouts: &'a [&'a [wgpu::BindGroupLayoutEntry]],
+    /// 推送常量范围
+    pub push_constant_ranges: &'a [wgpu::PushConstantRange],
+    /// 设备特性
+    pub features: wgpu::Features,
+    /// 设备限制
+    pub limits: &'a wgpu::Limits,
+    /// 额外的编译选项（如预处理宏定义）
+    pub compile_options: &'a str,
+}
+
+impl<'a> PipelineCacheDesc<'a> {
+    /// 着色器模块的缓存键输入
+    ///
+    /// 编译模块时还没有入口点和管线布局，只由源码、编译选项和设备特性/限制区分。
+    pub fn for_module(
+        label: &'a str,
+        source: &'a str,
+        compile_options: &'a str,
+        features: wgpu::Features,
+        limits: &'a wgpu::Limits,
+    ) -> Self {
+        Self {
+            label,
+            source,
+            entry_point: "",
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+            features,
+            limits,
+            compile_options,
+        }
+    }
+}
 
 /// 着色器缓存键
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShaderCacheKey {
     /// 源码哈希（64字符hex字符串）
     source_hash: String,
-    /// 编译选项哈希
+    /// 编译选项哈希（管线缓存键中包含入口点、布局和设备特性/限制）
     compile_options_hash: String,
+    /// 着色器标签
+    label: String,
 }
 
 impl ShaderCacheKey {
@@ -104,9 +196,34 @@ impl ShaderCacheKey {
         Self {
             source_hash,
             compile_options_hash,
+            label: String::new(),
+        }
+    }
+
+    /// 生成管线缓存键
+    pub fn for_pipeline(desc: &PipelineCacheDesc<'_>) -> Self {
+        let pipeline_state = format!(
+            "entry={}\nlayouts={:?}\npush_constants={:?}\nfeatures={:?}\nlimits={:?}\noptions={}",
+            desc.entry_point,
+            desc.bind_group_layouts,
+            desc.push_constant_ranges,
+            desc.features,
+            desc.limits,
+            desc.compile_options
+        );
+
+        Self {
+            source_hash: Self::hash_string(desc.source),
+            compile_options_hash: Self::hash_string(&pipeline_state),
+            label: desc.label.to_string(),
         }
     }
 
+    /// 着色器标签
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
     /// 计算字符串的SHA256哈希
     fn hash_string(input: &str) -> String {
         let mut hasher = Sha256::new();
@@ -211,6 +328,7 @@ pub enum CleanupStrategy {
 pub struct ShaderCache {
     config: ShaderCacheConfig,
     stats: ShaderCacheStats,
+    adapter: Option<CacheAdapterInfo>,
 }
 
 impl ShaderCache {
@@ -226,6 +344,7 @@ impl ShaderCache {
         let mut cache = Self {
             config,
             stats: ShaderCacheStats::default(),
+            adapter: None,
         };
 
         // 初始化统计信息
@@ -253,6 +372,27 @@ impl ShaderCache {
         Self::new(ShaderCacheConfig::default())
     }
 
+    /// 绑定当前GPU，之后写入的缓存会记录后端和适配器名称，
+    /// 读取时不同GPU生成的缓存视为失效
+    pub fn with_adapter(mut self, adapter: CacheAdapterInfo) -> Self {
+        self.adapter = Some(adapter);
+        self
+    }
+
+    /// 创建绑定到指定GPU的着色器缓存
+    pub fn for_adapter(
+        config: ShaderCacheConfig,
+        adapter: &wgpu::Adapter,
+    ) -> Result<Self, RenderError> {
+        Ok(Self::new(config)?
+            .with_adapter(CacheAdapterInfo::from_adapter_info(&adapter.get_info())))
+    }
+
+    /// 当前绑定的GPU信息
+    pub fn adapter(&self) -> Option<&CacheAdapterInfo> {
+        self.adapter.as_ref()
+    }
+
     /// 获取缓存的着色器二进制数据
     ///
     /// 如果缓存命中，返回缓存的二进制数据（SPIR-V格式）
@@ -299,8 +439,12 @@ impl ShaderCache {
             return Ok(None);
         }
 
-        // 验证格式版本
-        if metadata.format_version != CACHE_FORMAT_VERSION {
+        // 验证格式版本以及生成缓存的GPU
+        let adapter_mismatch = self
+            .adapter
+            .as_ref()
+            .is_some_and(|adapter| !metadata.matches_adapter(adapter));
+        if metadata.format_version != CACHE_FORMAT_VERSION || adapter_mismatch {
             // 格式版本不匹配，删除旧缓存
             let _ = fs::remove_file(&cache_path);
             let _ = fs::remove_file(&metadata_path);
@@ -357,8 +501,7 @@ impl ShaderCache {
         })?;
 
         // 写入元数据
-        let metadata =
-            CacheMetadata::new(key.source_hash.clone(), key.compile_options_hash.clone());
+        let metadata = CacheMetadata::new(key, self.adapter.as_ref());
         self.save_metadata(&metadata_path, &metadata)?;
 
         // 更新统计
@@ -393,8 +536,7 @@ impl ShaderCache {
         })?;
 
         // 写入元数据
-        let metadata =
-            CacheMetadata::new(key.source_hash.clone(), key.compile_options_hash.clone());
+        let metadata = CacheMetadata::new(key, self.adapter.as_ref());
         self.save_metadata(&metadata_path, &metadata)?;
 
         // 更新统计
@@ -616,6 +758,44 @@ impl ShaderCache {
         Ok(())
     }
 
+    /// 删除指定着色器中源码已变化的缓存条目
+    ///
+    /// 遍历缓存元数据，标签为 `label` 但源码哈希与 `source` 不同的条目会被删除。
+    /// 返回删除的条目数量。
+    pub fn invalidate_changed(&mut self, label: &str, source: &str) -> Result<usize, RenderError> {
+        if !self.config.cache_dir.exists() {
+            return Ok(0);
+        }
+
+        let source_hash = ShaderCacheKey::hash_string(source);
+        let entries = fs::read_dir(&self.config.cache_dir).map_err(|e| {
+            RenderError::InvalidState(format!("Failed to read shader cache directory: {}", e))
+        })?;
+
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let meta_path = entry.path();
+            if meta_path.extension().and_then(|s| s.to_str()) != Some("meta") {
+                continue;
+            }
+            let Ok(metadata) = self.load_metadata(&meta_path) else {
+                continue;
+            };
+            if metadata.label != label || metadata.source_hash == source_hash {
+                continue;
+            }
+
+            // `{hash}.spv.meta` 对应 `{hash}.spv`
+            let _ = fs::remove_file(meta_path.with_extension(""));
+            let _ = fs::remove_file(&meta_path);
+            removed += 1;
+        }
+
+        self.stats.invalidations += removed as u64;
+        self.update_stats();
+        Ok(removed)
+    }
+
     /// 清除所有缓存
     pub fn clear(&mut self) -> Result<(), RenderError> {
         if !self.config.cache_dir.exists() {
@@ -739,6 +919,96 @@ mod tests {
         assert!(result.is_none());
     }
 
+    fn pipeline_desc<'a>(
+        source: &'a str,
+        layouts: &'a [&'a [wgpu::BindGroupLayoutEntry]],
+        limits: &'a wgpu::Limits,
+    ) -> PipelineCacheDesc<'a> {
+        PipelineCacheDesc {
+            label: "sprite",
+            source,
+            entry_point: "vs_main",
+            bind_group_layouts: layouts,
+            push_constant_ranges: &[],
+            features: wgpu::Features::empty(),
+            limits,
+            compile_options: "",
+        }
+    }
+
+    #[test]
+    fn test_pipeline_key_and_invalidate_changed() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ShaderCacheConfig {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let mut cache = ShaderCache::new(config)
+            .unwrap()
+            .with_adapter(CacheAdapterInfo::new("Vulkan", "Test GPU"));
+
+        let uniform = [wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::VERTEX,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }];
+        let layouts: [&[wgpu::BindGroupLayoutEntry]; 1] = [&uniform];
+        let limits = wgpu::Limits::default();
+
+        let old_source =
+            "@vertex fn vs_main() -> @builtin(position) vec4<f32> { return vec4<f32>(0.0); }";
+        let new_source =
+            "@vertex fn vs_main() -> @builtin(position) vec4<f32> { return vec4<f32>(1.0); }";
+        let old_key = ShaderCacheKey::for_pipeline(&pipeline_desc(old_source, &layouts, &limits));
+        let new_key = ShaderCacheKey::for_pipeline(&pipeline_desc(new_source, &layouts, &limits));
+        assert_ne!(old_key, new_key);
+        assert_ne!(old_key.cache_filename(), new_key.cache_filename());
+
+        // 设备限制或布局变化也会产生不同的键
+        let downlevel = wgpu::Limits::downlevel_defaults();
+        let other_limits =
+            ShaderCacheKey::for_pipeline(&pipeline_desc(old_source, &layouts, &downlevel));
+        let no_layout = ShaderCacheKey::for_pipeline(&pipeline_desc(old_source, &[], &limits));
+        assert_ne!(old_key.cache_filename(), other_limits.cache_filename());
+        assert_ne!(old_key.cache_filename(), no_layout.cache_filename());
+
+        cache.put_source(&old_key, old_source).unwrap();
+        assert!(cache.get(&old_key).unwrap().is_some());
+
+        // 源码未变化时不删除
+        assert_eq!(cache.invalidate_changed("sprite", old_source).unwrap(), 0);
+        assert_eq!(cache.invalidate_changed("sprite", new_source).unwrap(), 1);
+        assert!(cache.get(&old_key).unwrap().is_none());
+        assert_eq!(cache.stats().cache_file_count, 0);
+    }
+
+    #[test]
+    fn test_cache_from_other_adapter_is_not_loaded() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = ShaderCacheConfig {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let key = ShaderCacheKey::from_source("fn main() {}", "");
+
+        let mut cache = ShaderCache::new(config.clone())
+            .unwrap()
+            .with_adapter(CacheAdapterInfo::new("Vulkan", "GPU A"));
+        cache.put_source(&key, "fn main() {}").unwrap();
+        assert!(cache.get(&key).unwrap().is_some());
+
+        let mut other = ShaderCache::new(config)
+            .unwrap()
+            .with_adapter(CacheAdapterInfo::new("Metal", "GPU B"));
+        assert!(other.get(&key).unwrap().is_none());
+        assert_eq!(other.stats().invalidations, 1);
+    }
+
     #[test]
     fn test_cache_stats() {
         let temp_dir = TempDir::new().unwrap();
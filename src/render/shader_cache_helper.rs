@@ -4,12 +4,13 @@
 
 use crate::core::error::RenderError;
 use crate::render::shader_async::{wait_for_compile, AsyncShaderCompiler, ShaderCompilePriority};
-use crate::render::shader_cache::{ShaderCache, ShaderCacheKey};
+use crate::render::shader_cache::{PipelineCacheDesc, ShaderCache, ShaderCacheKey};
 
 /// 创建带缓存的着色器模块
 ///
 /// 自动检查缓存，如果缓存命中则使用缓存的二进制（如果支持），
-/// 否则编译源码并存储到缓存。
+/// 否则编译源码并存储到缓存。缓存键包含标签和设备的特性/限制，
+/// `cache` 应通过 `ShaderCache::for_adapter` 绑定到创建 `device` 的适配器。
 ///
 /// # 参数
 /// - `device`: WGPU设备
@@ -28,7 +29,14 @@ pub fn create_cached_shader_module(
     compile_options: &str,
 ) -> Result<wgpu::ShaderModule, RenderError> {
     // 生成缓存键
-    let key = ShaderCacheKey::from_source(source, compile_options);
+    let limits = device.limits();
+    let key = ShaderCacheKey::for_pipeline(&PipelineCacheDesc::for_module(
+        label.unwrap_or_default(),
+        source,
+        compile_options,
+        device.features(),
+        &limits,
+    ));
 
     // 尝试从缓存获取
     // 注意：当前wgpu不支持直接加载SPIR-V二进制（需要naga集成）
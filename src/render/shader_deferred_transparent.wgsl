@@ -0,0 +1,100 @@
+// 延迟渲染 - 前向透明阶段着色器
+// 在延迟光照之后绘制透明物体，使用G-Buffer深度做遮挡测试
+// 需拼接在 shader_deferred_common.wgsl 之后，与光照阶段共用BRDF和CSM方向光
+
+struct Camera {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    light_count: u32,
+};
+
+struct PointLight {
+    position: vec3<f32>,
+    color: vec3<f32>,
+    intensity: f32,
+    radius: f32,
+};
+
+@group(0) @binding(0) var<uniform> camera: Camera;
+@group(0) @binding(1) var<storage, read> point_lights: array<PointLight>;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct InstanceInput {
+    @location(4) model_0: vec4<f32>,
+    @location(5) model_1: vec4<f32>,
+    @location(6) model_2: vec4<f32>,
+    @location(7) model_3: vec4<f32>,
+    @location(8) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) view_depth: f32,
+};
+
+// 透明物体只有颜色，材质参数与几何阶段写入G-Buffer的默认值一致
+const TRANSPARENT_ROUGHNESS: f32 = 0.5;
+const TRANSPARENT_METALLIC: f32 = 0.0;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    let model = mat4x4<f32>(instance.model_0, instance.model_1, instance.model_2, instance.model_3);
+    let world = model * vec4<f32>(vertex.position, 1.0);
+
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * world;
+    // 透视投影下裁剪空间w即视图空间深度，用于选择阴影级联
+    out.view_depth = out.clip_position.w;
+    out.world_position = world.xyz;
+    out.world_normal = normalize((model * vec4<f32>(vertex.normal, 0.0)).xyz);
+    out.color = instance.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let N = normalize(in.world_normal);
+    let V = normalize(camera.camera_pos - in.world_position);
+    let albedo = in.color.rgb;
+
+    // 环境光与CSM方向光与延迟光照阶段保持一致
+    var color = vec3<f32>(0.03) * albedo;
+    color += directional_light(
+        in.world_position,
+        in.view_depth,
+        N,
+        V,
+        albedo,
+        TRANSPARENT_ROUGHNESS,
+        TRANSPARENT_METALLIC,
+    );
+
+    for (var i = 0u; i < camera.light_count; i++) {
+        let light = point_lights[i];
+        let to_light = light.position - in.world_position;
+        let distance = length(to_light);
+        if distance > light.radius {
+            continue;
+        }
+        let L = to_light / max(distance, 0.0001);
+        let attenuation = pow(clamp(1.0 - distance / light.radius, 0.0, 1.0), 2.0);
+        let radiance = light.color * light.intensity * attenuation;
+
+        color += cook_torrance(N, V, L, albedo, TRANSPARENT_ROUGHNESS, TRANSPARENT_METALLIC) * radiance;
+    }
+
+    // HDR色调映射
+    color = color / (color + vec3<f32>(1.0));
+    // Gamma校正
+    color = pow(color, vec3<f32>(1.0 / 2.2));
+
+    return vec4<f32>(color, in.color.a);
+}
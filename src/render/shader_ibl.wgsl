@@ -0,0 +1,173 @@
+// IBL 预计算: 辐照度立方体贴图、预过滤镜面立方体贴图和 BRDF 积分查找表
+
+struct IblParams {
+    // 等距柱状投影环境图尺寸
+    env_width: u32,
+    env_height: u32,
+    // 当前输出的面尺寸 (立方体贴图) 或边长 (LUT)
+    face_size: u32,
+    sample_count: u32,
+    roughness: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+};
+
+@group(0) @binding(0) var<uniform> params: IblParams;
+@group(0) @binding(1) var<storage, read> environment: array<vec4<f32>>;
+@group(0) @binding(2) var output_cube: texture_storage_2d_array<rgba16float, write>;
+@group(0) @binding(3) var output_lut: texture_storage_2d<rgba16float, write>;
+
+const PI: f32 = 3.14159265359;
+
+// 立方体贴图面顺序: +X, -X, +Y, -Y, +Z, -Z
+fn cube_direction(face: u32, texel: vec2<u32>, size: u32) -> vec3<f32> {
+    let uv = (vec2<f32>(texel) + vec2<f32>(0.5)) / f32(size) * 2.0 - vec2<f32>(1.0);
+    var dir: vec3<f32>;
+    switch face {
+        case 0u: { dir = vec3<f32>(1.0, -uv.y, -uv.x); }
+        case 1u: { dir = vec3<f32>(-1.0, -uv.y, uv.x); }
+        case 2u: { dir = vec3<f32>(uv.x, 1.0, uv.y); }
+        case 3u: { dir = vec3<f32>(uv.x, -1.0, -uv.y); }
+        case 4u: { dir = vec3<f32>(uv.x, -uv.y, 1.0); }
+        default: { dir = vec3<f32>(-uv.x, -uv.y, -1.0); }
+    }
+    return normalize(dir);
+}
+
+fn env_texel(x: i32, y: i32) -> vec3<f32> {
+    let w = i32(params.env_width);
+    let h = i32(params.env_height);
+    let wrapped_x = ((x % w) + w) % w;
+    let clamped_y = clamp(y, 0, h - 1);
+    return environment[u32(clamped_y * w + wrapped_x)].rgb;
+}
+
+// 双线性采样等距柱状投影环境图
+fn sample_equirect(dir: vec3<f32>) -> vec3<f32> {
+    let u = atan2(dir.z, dir.x) / (2.0 * PI) + 0.5;
+    let v = acos(clamp(dir.y, -1.0, 1.0)) / PI;
+    let p = vec2<f32>(u * f32(params.env_width), v * f32(params.env_height)) - vec2<f32>(0.5);
+    let base = floor(p);
+    let f = p - base;
+    let x = i32(base.x);
+    let y = i32(base.y);
+    let top = mix(env_texel(x, y), env_texel(x + 1, y), f.x);
+    let bottom = mix(env_texel(x, y + 1), env_texel(x + 1, y + 1), f.x);
+    return mix(top, bottom, f.y);
+}
+
+fn tangent_frame(n: vec3<f32>) -> mat3x3<f32> {
+    var up = vec3<f32>(0.0, 1.0, 0.0);
+    if abs(n.y) > 0.999 {
+        up = vec3<f32>(0.0, 0.0, 1.0);
+    }
+    let tangent = normalize(cross(up, n));
+    let bitangent = cross(n, tangent);
+    return mat3x3<f32>(tangent, bitangent, n);
+}
+
+fn radical_inverse_vdc(index: u32) -> f32 {
+    var bits = index;
+    bits = (bits << 16u) | (bits >> 16u);
+    bits = ((bits & 0x55555555u) << 1u) | ((bits & 0xAAAAAAAAu) >> 1u);
+    bits = ((bits & 0x33333333u) << 2u) | ((bits & 0xCCCCCCCCu) >> 2u);
+    bits = ((bits & 0x0F0F0F0Fu) << 4u) | ((bits & 0xF0F0F0F0u) >> 4u);
+    bits = ((bits & 0x00FF00FFu) << 8u) | ((bits & 0xFF00FF00u) >> 8u);
+    return f32(bits) * 2.3283064365386963e-10;
+}
+
+fn hammersley(i: u32, count: u32) -> vec2<f32> {
+    return vec2<f32>(f32(i) / f32(count), radical_inverse_vdc(i));
+}
+
+// GGX 重要性采样，返回切线空间半程向量
+fn importance_sample_ggx(xi: vec2<f32>, roughness: f32) -> vec3<f32> {
+    let a = roughness * roughness;
+    let phi = 2.0 * PI * xi.x;
+    let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (a * a - 1.0) * xi.y));
+    let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+    return vec3<f32>(cos(phi) * sin_theta, sin(phi) * sin_theta, cos_theta);
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_irradiance(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.face_size || id.y >= params.face_size {
+        return;
+    }
+    let n = cube_direction(id.z, id.xy, params.face_size);
+    let frame = tangent_frame(n);
+
+    // 对半球做余弦加权积分
+    let steps = params.sample_count;
+    var sum = vec3<f32>(0.0);
+    for (var i = 0u; i < steps; i++) {
+        let phi = 2.0 * PI * (f32(i) + 0.5) / f32(steps);
+        for (var j = 0u; j < steps; j++) {
+            let theta = 0.5 * PI * (f32(j) + 0.5) / f32(steps);
+            let local = vec3<f32>(sin(theta) * cos(phi), sin(theta) * sin(phi), cos(theta));
+            sum += sample_equirect(frame * local) * cos(theta) * sin(theta);
+        }
+    }
+    let irradiance = PI * sum / f32(steps * steps);
+    textureStore(output_cube, id.xy, id.z, vec4<f32>(irradiance, 1.0));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_prefilter(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.face_size || id.y >= params.face_size {
+        return;
+    }
+    let n = cube_direction(id.z, id.xy, params.face_size);
+    let frame = tangent_frame(n);
+
+    // 假设 N = V = R
+    var color = vec3<f32>(0.0);
+    var weight = 0.0;
+    for (var i = 0u; i < params.sample_count; i++) {
+        let h = frame * importance_sample_ggx(hammersley(i, params.sample_count), params.roughness);
+        let l = normalize(2.0 * dot(n, h) * h - n);
+        let n_dot_l = dot(n, l);
+        if n_dot_l > 0.0 {
+            color += sample_equirect(l) * n_dot_l;
+            weight += n_dot_l;
+        }
+    }
+    textureStore(output_cube, id.xy, id.z, vec4<f32>(color / max(weight, 0.0001), 1.0));
+}
+
+fn geometry_schlick_ggx_ibl(n_dot_v: f32, roughness: f32) -> f32 {
+    let k = roughness * roughness / 2.0;
+    return n_dot_v / (n_dot_v * (1.0 - k) + k);
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn cs_brdf_lut(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = params.face_size;
+    if id.x >= size || id.y >= size {
+        return;
+    }
+    let n_dot_v = max((f32(id.x) + 0.5) / f32(size), 0.001);
+    let roughness = (f32(id.y) + 0.5) / f32(size);
+    let v = vec3<f32>(sqrt(1.0 - n_dot_v * n_dot_v), 0.0, n_dot_v);
+
+    var scale = 0.0;
+    var bias = 0.0;
+    for (var i = 0u; i < params.sample_count; i++) {
+        let h = importance_sample_ggx(hammersley(i, params.sample_count), roughness);
+        let l = normalize(2.0 * dot(v, h) * h - v);
+        let n_dot_l = max(l.z, 0.0);
+        let n_dot_h = max(h.z, 0.0);
+        let v_dot_h = max(dot(v, h), 0.0);
+        if n_dot_l > 0.0 {
+            let g = geometry_schlick_ggx_ibl(n_dot_v, roughness)
+                * geometry_schlick_ggx_ibl(n_dot_l, roughness);
+            let g_vis = g * v_dot_h / (n_dot_h * n_dot_v);
+            let fc = pow(1.0 - v_dot_h, 5.0);
+            scale += (1.0 - fc) * g_vis;
+            bias += fc * g_vis;
+        }
+    }
+    let count = f32(params.sample_count);
+    textureStore(output_lut, id.xy, vec4<f32>(scale / count, bias / count, 0.0, 1.0));
+}
@@ -38,10 +38,23 @@ struct DirectionalLight {
     intensity: f32,
 };
 
+struct EnvironmentParams {
+    intensity: f32,
+    max_mip: f32,
+    // 0: 未设置环境图，使用中性常量环境光
+    enabled: f32,
+    _pad: f32,
+};
+
 @group(0) @binding(0) var<uniform> uniforms: Uniforms3D;
 @group(1) @binding(0) var<uniform> material: MaterialUniform;
 @group(2) @binding(0) var<storage, read> point_lights: array<PointLight>;
 @group(2) @binding(1) var<storage, read> dir_lights: array<DirectionalLight>;
+@group(2) @binding(2) var<uniform> environment: EnvironmentParams;
+@group(2) @binding(3) var irradiance_map: texture_cube<f32>;
+@group(2) @binding(4) var prefiltered_map: texture_cube<f32>;
+@group(2) @binding(5) var brdf_lut: texture_2d<f32>;
+@group(2) @binding(6) var env_sampler: sampler;
 @group(3) @binding(0) var base_color_texture: texture_2d<f32>;
 @group(3) @binding(1) var metallic_roughness_texture: texture_2d<f32>;
 @group(3) @binding(2) var normal_texture: texture_2d<f32>;
@@ -128,6 +141,22 @@ fn fresnel_schlick(cosTheta: f32, F0: vec3<f32>) -> vec3<f32> {
     return F0 + (1.0 - F0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
 }
 
+fn fresnel_schlick_roughness(cosTheta: f32, F0: vec3<f32>, roughness: f32) -> vec3<f32> {
+    return F0 + (max(vec3<f32>(1.0 - roughness), F0) - F0) * pow(clamp(1.0 - cosTheta, 0.0, 1.0), 5.0);
+}
+
+// 未设置环境图时的中性环境光亮度
+const NEUTRAL_AMBIENT: f32 = 0.03;
+
+// 解析近似的环境 BRDF (Karis)，用于中性环境
+fn env_brdf_approx(NdotV: f32, roughness: f32) -> vec2<f32> {
+    let c0 = vec4<f32>(-1.0, -0.0275, -0.572, 0.022);
+    let c1 = vec4<f32>(1.0, 0.0425, 1.04, -0.04);
+    let r = roughness * c0 + c1;
+    let a004 = min(r.x * r.x, exp2(-9.28 * NdotV)) * r.x + r.y;
+    return vec2<f32>(-1.04, 1.04) * a004 + r.zw;
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var N = normalize(in.world_normal);
@@ -151,6 +180,12 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     roughness = clamp(mr.g * roughness, 0.04, 1.0);
     let ao_tex = textureSample(ao_texture, tex_sampler, in.uv);
     ao = ao * ao_tex.r;
+    // 法线扰动（TBN）
+    var nm = textureSample(normal_texture, tex_sampler, in.uv).xyz * 2.0 - vec3<f32>(1.0);
+    nm = vec3<f32>(nm.x * material.normal_scale, nm.y * material.normal_scale, nm.z);
+    let B = normalize(cross(N, in.world_tangent) * in.tangent_w);
+    let TBN = mat3x3<f32>(in.world_tangent, B, N);
+    N = normalize(TBN * nm);
     
     // 计算F0 (表面反射率)
     var F0 = vec3<f32>(0.04); // 非金属的默认值
@@ -163,6 +198,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let num_point_lights = arrayLength(&point_lights);
     for (var i = 0u; i < num_point_lights; i++) {
         let light = point_lights[i];
+        if light.intensity <= 0.0 {
+            continue;
+        }
         let L = normalize(light.position - in.world_position);
         let H = normalize(V + L);
         let distance = length(light.position - in.world_position);
@@ -190,6 +228,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let num_dir_lights = arrayLength(&dir_lights);
     for (var i = 0u; i < num_dir_lights; i++) {
         let light = dir_lights[i];
+        if light.intensity <= 0.0 {
+            continue;
+        }
         let L = normalize(-light.direction);
         let H = normalize(V + L);
         let radiance = light.color * light.intensity;
@@ -210,13 +251,35 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         Lo += (kD * albedo / PI + specular) * radiance * NdotL;
     }
     
-    // 环境光
-    let ambient = vec3<f32>(0.03) * albedo * ao;
+    // 环境光 (IBL，split-sum 近似)
+    let NdotV = max(dot(N, V), 0.0);
+    let R = reflect(-V, N);
+    let F_ambient = fresnel_schlick_roughness(NdotV, F0, roughness);
+    let kD_ambient = (vec3<f32>(1.0) - F_ambient) * (1.0 - metallic);
+    let has_environment = environment.enabled > 0.5;
+    let irradiance = select(
+        vec3<f32>(NEUTRAL_AMBIENT),
+        textureSampleLevel(irradiance_map, env_sampler, N, 0.0).rgb * environment.intensity,
+        has_environment
+    );
+    let prefiltered = select(
+        vec3<f32>(NEUTRAL_AMBIENT),
+        textureSampleLevel(prefiltered_map, env_sampler, R, roughness * environment.max_mip).rgb
+            * environment.intensity,
+        has_environment
+    );
+    let env_brdf = select(
+        env_brdf_approx(NdotV, roughness),
+        textureSampleLevel(brdf_lut, env_sampler, vec2<f32>(NdotV, roughness), 0.0).rg,
+        has_environment
+    );
+    let ambient_specular = prefiltered * (F_ambient * env_brdf.x + env_brdf.y);
+    let ambient = (kD_ambient * irradiance * albedo + ambient_specular) * ao;
     // 简化清漆层：提升镜面能量并根据粗糙度调节
     let clearcoat_factor = clamp(material.clearcoat, 0.0, 1.0);
     let clearcoat_rough = clamp(material.clearcoat_roughness, 0.04, 1.0);
     let cc_spec = distribution_ggx(N, normalize(V + N), clearcoat_rough);
-    var Lo = Lo + cc_spec * clearcoat_factor;
+    Lo = Lo + cc_spec * clearcoat_factor;
     // 简化各向异性：通过方向调制高光
     let aniso = clamp(material.anisotropy, 0.0, 1.0);
     let adir = normalize(vec3<f32>(material.anisotropy_direction, 0.0));
@@ -231,9 +294,3 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     
     return vec4<f32>(color, material.base_color.a);
 }
-    // 法线扰动（TBN）
-    var nm = textureSample(normal_texture, tex_sampler, in.uv).xyz * 2.0 - vec3<f32>(1.0);
-    nm = vec3<f32>(nm.x * material.normal_scale, nm.y * material.normal_scale, nm.z);
-    let B = normalize(cross(N, in.world_tangent) * in.tangent_w);
-    let TBN = mat3x3<f32>(in.world_tangent, B, N);
-    N = normalize(TBN * nm);
@@ -1,3 +1,4 @@
+use crate::ecs::NineSlice;
 use glam::{Mat4, Vec2, Vec3, Vec4};
 use wgpu::{Buffer, BufferUsages, Device, Queue};
 
@@ -28,6 +29,75 @@ impl SpriteInstance {
             color: color.to_array(),
         }
     }
+
+    /// 创建 9-slice 精灵，拆分为最多 9 个实例
+    ///
+    /// `tex_coords` 为精灵在纹理中的UV区域，`texture_size` 为纹理的像素尺寸，用于把纹素边框
+    /// 换算为UV。四角保持边框尺寸，四边和中心拉伸；边框之和超过 `size` 时按比例缩小。
+    /// 边框全为 0 时返回与 `new` 相同的单个实例。
+    pub fn nine_slice(
+        position: Vec3,
+        size: Vec2,
+        tex_coords: Vec4,
+        texture_size: Vec2,
+        slice: NineSlice,
+        color: Vec4,
+    ) -> Vec<Self> {
+        if slice.is_zero() {
+            return vec![Self::new(position, size, tex_coords, color)];
+        }
+
+        let fit = |a: f32, b: f32, extent: f32| {
+            let total = a + b;
+            if total > extent && total > 0.0 {
+                (a * extent / total, b * extent / total)
+            } else {
+                (a, b)
+            }
+        };
+        let (left, right) = fit(slice.left, slice.right, size.x);
+        let (top, bottom) = fit(slice.top, slice.bottom, size.y);
+
+        // 局部坐标与UV的三段分割点，局部 y 与纹理 v 同向
+        let half = size * 0.5;
+        let xs = [-half.x, -half.x + left, half.x - right, half.x];
+        let ys = [-half.y, -half.y + top, half.y - bottom, half.y];
+        let (u0, v0) = (tex_coords.x, tex_coords.y);
+        let (u1, v1) = (u0 + tex_coords.z, v0 + tex_coords.w);
+        let us = [
+            u0,
+            u0 + slice.left / texture_size.x,
+            u1 - slice.right / texture_size.x,
+            u1,
+        ];
+        let vs = [
+            v0,
+            v0 + slice.top / texture_size.y,
+            v1 - slice.bottom / texture_size.y,
+            v1,
+        ];
+
+        let mut instances = Vec::with_capacity(9);
+        for row in 0..3 {
+            for col in 0..3 {
+                let w = xs[col + 1] - xs[col];
+                let h = ys[row + 1] - ys[row];
+                // 跳过宽度为 0 的边框块
+                if w <= 0.0 || h <= 0.0 {
+                    continue;
+                }
+                let center = position + Vec3::new(xs[col] + w * 0.5, ys[row] + h * 0.5, 0.0);
+                let uv = Vec4::new(
+                    us[col],
+                    vs[row],
+                    us[col + 1] - us[col],
+                    vs[row + 1] - vs[row],
+                );
+                instances.push(Self::new(center, Vec2::new(w, h), uv, color));
+            }
+        }
+        instances
+    }
 }
 
 /// 精灵批次
@@ -110,9 +180,44 @@ impl SpriteBatch {
     pub fn buffer(&self) -> Option<&Buffer> {
         self.instance_buffer.as_ref()
     }
+
+    /// 获取批次中的实例 (按绘制顺序)
+    pub fn instances(&self) -> &[SpriteInstance] {
+        &self.instances
+    }
+}
+
+/// 精灵批量渲染配置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteBatchConfig {
+    /// 最大批次大小
+    pub max_batch_size: usize,
+    /// 是否对透明精灵按层级和深度从后往前排序
+    pub sort_transparent: bool,
+}
+
+impl Default for SpriteBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1024,
+            sort_transparent: true,
+        }
+    }
+}
+
+/// 等待排序的精灵
+#[derive(Debug, Clone, Copy)]
+struct PendingSprite {
+    instance: SpriteInstance,
+    layer: f32,
+    depth: f32,
+    transparent: bool,
 }
 
 /// 精灵批量渲染器
+///
+/// 精灵在 `finish` 时才排序并写入批次：不透明精灵从前往后绘制以利用 early-Z，
+/// 透明精灵随后按层级、视图空间深度从后往前绘制以保证混合正确。
 pub struct SpriteBatchRenderer {
     /// 当前批次
     current_batch: SpriteBatch,
@@ -120,20 +225,92 @@ pub struct SpriteBatchRenderer {
     completed_batches: Vec<SpriteBatch>,
     /// 最大批次大小
     max_batch_size: usize,
+    /// 是否对透明精灵排序
+    sort_transparent: bool,
+    /// 视图矩阵，用于计算视图空间深度
+    view: Mat4,
+    /// 尚未写入批次的精灵
+    pending: Vec<PendingSprite>,
 }
 
 impl SpriteBatchRenderer {
     /// 创建新的批量渲染器
     pub fn new(max_batch_size: usize) -> Self {
+        Self::with_config(SpriteBatchConfig {
+            max_batch_size,
+            ..Default::default()
+        })
+    }
+
+    /// 使用配置创建批量渲染器
+    pub fn with_config(config: SpriteBatchConfig) -> Self {
         Self {
-            current_batch: SpriteBatch::new(max_batch_size),
+            current_batch: SpriteBatch::new(config.max_batch_size),
             completed_batches: Vec::new(),
-            max_batch_size,
+            max_batch_size: config.max_batch_size,
+            sort_transparent: config.sort_transparent,
+            view: Mat4::IDENTITY,
+            pending: Vec::new(),
         }
     }
 
-    /// 添加精灵
+    /// 设置是否对透明精灵排序
+    pub fn set_sort_transparent(&mut self, enabled: bool) {
+        self.sort_transparent = enabled;
+    }
+
+    /// 设置视图矩阵
+    pub fn set_view(&mut self, view: Mat4) {
+        self.view = view;
+    }
+
+    /// 添加精灵，颜色 alpha 小于 1 时视为透明，层级为 0
     pub fn add_sprite(&mut self, instance: SpriteInstance) {
+        self.add_layered_sprite(instance, 0.0, instance.color[3] < 1.0);
+    }
+
+    /// 添加指定层级 (`Sprite::layer`) 的精灵，层级越大越靠前
+    pub fn add_layered_sprite(&mut self, instance: SpriteInstance, layer: f32, transparent: bool) {
+        let position = Mat4::from_cols_array_2d(&instance.transform)
+            .w_axis
+            .truncate();
+        // 右手系视图空间中相机看向 -Z，z 越小离相机越远
+        let depth = self.view.transform_point3(position).z;
+        self.pending.push(PendingSprite {
+            instance,
+            layer,
+            depth,
+            transparent,
+        });
+    }
+
+    /// 排序等待中的精灵并写入批次
+    fn flush_pending(&mut self) {
+        let mut pending = std::mem::take(&mut self.pending);
+        if self.sort_transparent {
+            // sort_by 是稳定排序，相同层级和深度的精灵保持提交顺序，避免闪烁
+            pending.sort_by(|a, b| {
+                a.transparent.cmp(&b.transparent).then_with(|| {
+                    if a.transparent {
+                        // 从后往前
+                        a.layer
+                            .total_cmp(&b.layer)
+                            .then(a.depth.total_cmp(&b.depth))
+                    } else {
+                        // 从前往后
+                        b.layer
+                            .total_cmp(&a.layer)
+                            .then(b.depth.total_cmp(&a.depth))
+                    }
+                })
+            });
+        }
+        for sprite in pending {
+            self.push_instance(sprite.instance);
+        }
+    }
+
+    fn push_instance(&mut self, instance: SpriteInstance) {
         if !self.current_batch.add(instance) {
             // 当前批次已满,创建新批次
             let mut new_batch = SpriteBatch::new(self.max_batch_size);
@@ -146,6 +323,7 @@ impl SpriteBatchRenderer {
 
     /// 完成批次
     pub fn finish(&mut self) {
+        self.flush_pending();
         if !self.current_batch.is_empty() {
             let new_batch = SpriteBatch::new(self.max_batch_size);
             let old_batch = std::mem::replace(&mut self.current_batch, new_batch);
@@ -175,6 +353,7 @@ impl SpriteBatchRenderer {
     pub fn clear(&mut self) {
         self.current_batch.clear();
         self.completed_batches.clear();
+        self.pending.clear();
     }
 }
 
@@ -218,4 +397,122 @@ mod tests {
         renderer.finish();
         assert_eq!(renderer.batches().count(), 2);
     }
+
+    #[test]
+    fn test_transparent_sprites_sorted_back_to_front() {
+        let sprite = |z: f32, alpha: f32| {
+            SpriteInstance::new(
+                Vec3::new(0.0, 0.0, z),
+                Vec2::new(2.0, 2.0),
+                Vec4::new(0.0, 0.0, 1.0, 1.0),
+                Vec4::new(1.0, 1.0, 1.0, alpha),
+            )
+        };
+
+        let mut renderer = SpriteBatchRenderer::with_config(SpriteBatchConfig {
+            max_batch_size: 16,
+            sort_transparent: true,
+        });
+        // 前景层先提交，背景层后提交，位置重叠
+        renderer.add_layered_sprite(sprite(0.0, 0.5), 2.0, true);
+        renderer.add_layered_sprite(sprite(0.0, 0.5), 1.0, true);
+        // 不透明精灵应排在透明精灵之前，且从前往后
+        renderer.add_layered_sprite(sprite(-5.0, 1.0), 0.0, false);
+        renderer.add_layered_sprite(sprite(-1.0, 1.0), 0.0, false);
+        renderer.finish();
+
+        let batch = renderer.batches().next().unwrap();
+        let order: Vec<(f32, f32)> = batch
+            .instances()
+            .iter()
+            .map(|i| (i.transform[3][2], i.color[3]))
+            .collect();
+        assert_eq!(order, [(-1.0, 1.0), (-5.0, 1.0), (0.0, 0.5), (0.0, 0.5)]);
+
+        // 透明部分：层级 1 在层级 2 之前绘制
+        let mut renderer = SpriteBatchRenderer::new(16);
+        let back = sprite(0.0, 0.25);
+        let front = sprite(0.0, 0.75);
+        renderer.add_layered_sprite(front, 2.0, true);
+        renderer.add_layered_sprite(back, 1.0, true);
+        renderer.finish();
+        let alphas: Vec<f32> = renderer
+            .batches()
+            .next()
+            .unwrap()
+            .instances()
+            .iter()
+            .map(|i| i.color[3])
+            .collect();
+        assert_eq!(alphas, [0.25, 0.75]);
+
+        // 关闭排序时保持提交顺序
+        let mut renderer = SpriteBatchRenderer::new(16);
+        renderer.set_sort_transparent(false);
+        renderer.add_layered_sprite(front, 2.0, true);
+        renderer.add_layered_sprite(back, 1.0, true);
+        renderer.finish();
+        let alphas: Vec<f32> = renderer
+            .batches()
+            .next()
+            .unwrap()
+            .instances()
+            .iter()
+            .map(|i| i.color[3])
+            .collect();
+        assert_eq!(alphas, [0.75, 0.25]);
+    }
+
+    #[test]
+    fn test_nine_slice_keeps_corners() {
+        let tex = Vec4::new(0.0, 0.0, 1.0, 1.0);
+        let texture_size = Vec2::new(64.0, 64.0);
+        let slice = NineSlice::uniform(16.0);
+
+        // 原始尺寸为 64x64，横向拉伸到 2 倍
+        let pieces = SpriteInstance::nine_slice(
+            Vec3::ZERO,
+            Vec2::new(128.0, 64.0),
+            tex,
+            texture_size,
+            slice,
+            Vec4::ONE,
+        );
+        assert_eq!(pieces.len(), 9);
+        let size = |i: &SpriteInstance| (i.transform[0][0], i.transform[1][1]);
+
+        // 四角尺寸不变，UV 为纹理角落的 16 纹素
+        for corner in [0, 2, 6, 8] {
+            assert_eq!(size(&pieces[corner]), (16.0, 16.0));
+            assert_eq!(pieces[corner].tex_coords[2], 0.25);
+            assert_eq!(pieces[corner].tex_coords[3], 0.25);
+        }
+        assert_eq!(pieces[0].transform[3][0], -56.0);
+        assert_eq!(pieces[8].transform[3][0], 56.0);
+
+        // 中心块横向拉伸，UV 仍为纹理中间部分
+        let center = &pieces[4];
+        assert_eq!(size(center), (96.0, 32.0));
+        assert_eq!(center.tex_coords, [0.25, 0.25, 0.5, 0.5]);
+        assert_eq!(center.transform[3][0], 0.0);
+
+        // 边框为 0 时与普通精灵一致
+        let plain = SpriteInstance::nine_slice(
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec2::new(128.0, 64.0),
+            tex,
+            texture_size,
+            NineSlice::default(),
+            Vec4::ONE,
+        );
+        assert_eq!(plain.len(), 1);
+        let expected = SpriteInstance::new(
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec2::new(128.0, 64.0),
+            tex,
+            Vec4::ONE,
+        );
+        assert_eq!(plain[0].transform, expected.transform);
+        assert_eq!(plain[0].tex_coords, expected.tex_coords);
+    }
 }
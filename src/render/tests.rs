@@ -195,5 +195,119 @@ mod tests {
         assert_eq!(TonemapOperator::Reinhard as u32, 1);
         assert_eq!(TonemapOperator::ACES as u32, 2);
         assert_eq!(TonemapOperator::Filmic as u32, 3);
+        assert_eq!(TonemapOperator::AgX as u32, 4);
+        assert_eq!(TonemapOperator::PbrNeutral as u32, 5);
+
+        let modes: std::collections::HashSet<u32> = [
+            TonemapOperator::None,
+            TonemapOperator::Reinhard,
+            TonemapOperator::ACES,
+            TonemapOperator::Filmic,
+            TonemapOperator::AgX,
+            TonemapOperator::PbrNeutral,
+        ]
+        .iter()
+        .map(|op| op.tonemap_mode())
+        .collect();
+        assert_eq!(modes.len(), 6);
+    }
+
+    #[test]
+    fn test_agx_curve() {
+        use super::super::postprocess::tonemap::{agx, agx_contrast, pbr_neutral};
+        use glam::Vec3;
+
+        // 曲线两端
+        assert!(agx_contrast(0.0).abs() < 0.01);
+        assert!((agx_contrast(1.0) - 1.0).abs() < 0.01);
+
+        // 18% 中灰经对数编码后约落在显示值 0.5
+        let mid_grey_log = (0.18f32.log2() + 12.47393) / (12.47393 + 4.026069);
+        assert!((agx_contrast(mid_grey_log) - 0.4967).abs() < 1e-3);
+
+        // 完整 AgX：中灰保持中性，输出线性值约 0.2145
+        let grey = agx(Vec3::splat(0.18));
+        assert!((grey.x - 0.2145).abs() < 1e-3);
+        assert!((grey.x - grey.z).abs() < 1e-3);
+        assert!(agx(Vec3::splat(16.0)).max_element() < 1.0);
+        assert_eq!(agx(Vec3::ZERO), Vec3::ZERO);
+
+        // PBR Neutral：低亮度只做偏移，高亮度被压缩到 1 以内
+        let dark = pbr_neutral(Vec3::splat(0.5));
+        assert!((dark.x - 0.46).abs() < 1e-6);
+        assert!(pbr_neutral(Vec3::splat(10.0)).max_element() < 1.0);
+    }
+
+    #[test]
+    fn test_hbao_flat_and_step() {
+        use super::super::postprocess::ssao::{hbao_reference, SsaoUniforms};
+        use glam::{Mat4, Vec4};
+
+        let (width, height) = (32u32, 32u32);
+        let projection = Mat4::perspective_rh(60f32.to_radians(), 1.0, 0.1, 100.0);
+        let uniforms = SsaoUniforms {
+            projection: projection.to_cols_array_2d(),
+            inv_projection: projection.inverse().to_cols_array_2d(),
+            screen_size: [width as f32, height as f32],
+            radius: 1.0,
+            intensity: 1.0,
+            bias: 0.025,
+            sample_count: 32,
+            _pad: [0.0; 2],
+        };
+        let ndc_depth = |view_z: f32| {
+            let clip = projection * Vec4::new(0.0, 0.0, view_z, 1.0);
+            clip.z / clip.w
+        };
+
+        // 正对相机的平面：没有任何地平线高于切平面
+        let flat = vec![ndc_depth(-10.0); (width * height) as usize];
+        let ao = hbao_reference(&flat, &uniforms);
+        assert!(ao.iter().all(|v| (v - 1.0).abs() < 1e-4));
+
+        // 右半边更靠近相机，左半边靠近台阶处被遮蔽
+        let step: Vec<f32> = (0..width * height)
+            .map(|i| {
+                if i % width < width / 2 {
+                    ndc_depth(-10.0)
+                } else {
+                    ndc_depth(-9.7)
+                }
+            })
+            .collect();
+        let ao = hbao_reference(&step, &uniforms);
+        let row = (height / 2 * width) as usize;
+        assert!(ao[row + (width / 2 - 1) as usize] < 0.95);
+        assert!((ao[row + 2] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_dof_circle_of_confusion() {
+        use super::super::postprocess::dof::{circle_of_confusion, linearize_depth};
+
+        let (focal_distance, focal_range, aperture) = (10.0, 2.0, 1.0);
+        let coc = |d: f32| circle_of_confusion(d, focal_distance, focal_range, aperture);
+
+        // 焦点及清晰范围内 CoC 为 0
+        assert_eq!(coc(10.0), 0.0);
+        assert_eq!(coc(10.9), 0.0);
+        assert_eq!(coc(9.1), 0.0);
+
+        // 离焦点越远 CoC 越大，前景和背景都会模糊
+        assert!(coc(12.0) > 0.0);
+        assert!(coc(30.0) > coc(12.0));
+        assert!(coc(5.0) > 0.0);
+        assert!(coc(2.0) > coc(5.0));
+        assert!(coc(1000.0) <= 1.0);
+
+        // 光圈为 0 时全部清晰
+        assert_eq!(
+            circle_of_confusion(100.0, focal_distance, focal_range, 0.0),
+            0.0
+        );
+
+        // 深度线性化：0 为近平面，1 为远平面
+        assert!((linearize_depth(0.0, 0.1, 100.0) - 0.1).abs() < 1e-5);
+        assert!((linearize_depth(1.0, 0.1, 100.0) - 100.0).abs() < 1e-2);
     }
 }
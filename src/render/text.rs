@@ -4,6 +4,7 @@
 // ============================================================================
 
 use crate::impl_default;
+use crate::resources::font::{FontStack, GlyphProvider};
 use std::collections::HashMap;
 
 /// MSDF 字体资源
@@ -136,6 +137,8 @@ pub struct GlyphInstance {
     pub color: [f32; 4],
     /// 是否是阴影/描边层
     pub layer: GlyphLayer,
+    /// 字形所在的图集纹理（回退字体的字形来自不同图集）
+    pub atlas_texture: u32,
 }
 
 /// 字形层类型
@@ -248,11 +251,17 @@ impl MsdfFont {
     }
 }
 
+impl GlyphProvider for MsdfFont {
+    fn has_glyph(&self, codepoint: u32) -> bool {
+        char::from_u32(codepoint).is_some_and(|ch| self.glyphs.contains_key(&ch))
+    }
+}
+
 /// 文本排版器
 #[derive(Default)]
 pub struct TextLayouter {
-    /// 缓存的字体
-    fonts: HashMap<String, MsdfFont>,
+    /// 缓存的字体回退链
+    fonts: HashMap<String, FontStack<MsdfFont>>,
 }
 
 impl TextLayouter {
@@ -262,11 +271,32 @@ impl TextLayouter {
 
     /// 注册字体
     pub fn register_font(&mut self, name: String, font: MsdfFont) {
-        self.fonts.insert(name, font);
+        self.fonts.insert(name, FontStack::new(font));
     }
 
-    /// 获取字体
+    /// 注册带回退链的字体
+    pub fn register_font_stack(&mut self, name: String, stack: FontStack<MsdfFont>) {
+        self.fonts.insert(name, stack);
+    }
+
+    /// 为已注册的字体追加回退字体
+    pub fn add_fallback(&mut self, name: &str, font: MsdfFont) -> bool {
+        match self.fonts.get_mut(name) {
+            Some(stack) => {
+                stack.push_fallback(font);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 获取字体（回退链中的主字体）
     pub fn get_font(&self, name: &str) -> Option<&MsdfFont> {
+        self.fonts.get(name).map(|stack| stack.primary())
+    }
+
+    /// 获取字体回退链
+    pub fn get_font_stack(&self, name: &str) -> Option<&FontStack<MsdfFont>> {
         self.fonts.get(name)
     }
 
@@ -278,7 +308,9 @@ impl TextLayouter {
         style: &TextStyle,
         max_width: Option<f32>,
     ) -> Option<TextLayout> {
-        let font = self.fonts.get(font_name)?;
+        let stack = self.fonts.get(font_name)?;
+        // 行高、基线等度量以主字体为准
+        let font = stack.primary();
 
         let scale = style.font_size / font.line_height;
         let line_height = font.line_height * scale * style.line_spacing;
@@ -310,22 +342,31 @@ impl TextLayouter {
                 continue;
             }
 
-            let glyph_data = match font.get_glyph(ch) {
-                Some(g) => g,
-                None => font.get_glyph('?').unwrap_or_else(|| {
-                    // 回退到空格
-                    font.get_glyph(' ').unwrap()
-                }),
+            // 沿回退链查找提供该字形的字体
+            let resolved = stack
+                .font_for(ch as u32)
+                .and_then(|f| Some((f, f.get_glyph(ch)?)));
+            let (glyph_font, glyph_data) = match resolved {
+                Some(found) => found,
+                None => (
+                    font,
+                    font.get_glyph('?').unwrap_or_else(|| {
+                        // 回退到空格
+                        font.get_glyph(' ').unwrap()
+                    }),
+                ),
             };
+            // 回退字体按自身行高缩放到相同字号
+            let glyph_scale = style.font_size / glyph_font.line_height;
 
             // 应用字距调整
             if let Some(prev) = prev_char {
-                cursor_x += font.get_kerning(prev, ch) * scale;
+                cursor_x += glyph_font.get_kerning(prev, ch) * glyph_scale;
             }
 
             // 自动换行检查
             if let Some(max_w) = max_width {
-                let advance = glyph_data.advance * scale + style.letter_spacing;
+                let advance = glyph_data.advance * glyph_scale + style.letter_spacing;
                 if cursor_x + advance > max_w && cursor_x > 0.0 {
                     lines.push(LineInfo {
                         start_glyph: line_start,
@@ -343,12 +384,13 @@ impl TextLayouter {
 
             // 生成字形实例
             if let (Some(plane), Some(atlas)) = (glyph_data.plane_bounds, glyph_data.atlas_bounds) {
-                let x = cursor_x + plane.left * scale;
-                let y = cursor_y + plane.bottom * scale;
-                let w = plane.width() * scale;
-                let h = plane.height() * scale;
+                let x = cursor_x + plane.left * glyph_scale;
+                let y = cursor_y + plane.bottom * glyph_scale;
+                let w = plane.width() * glyph_scale;
+                let h = plane.height() * glyph_scale;
 
-                let atlas_size = font.atlas_size;
+                let atlas_size = glyph_font.atlas_size;
+                let atlas_texture = glyph_font.atlas_texture;
                 let uv_min = [
                     atlas.left / atlas_size[0] as f32,
                     1.0 - atlas.top / atlas_size[1] as f32, // 翻转 Y
@@ -367,6 +409,7 @@ impl TextLayouter {
                         uv_max,
                         color: style.shadow_color,
                         layer: GlyphLayer::Shadow,
+                        atlas_texture,
                     });
                 }
 
@@ -379,6 +422,7 @@ impl TextLayouter {
                         uv_max,
                         color: style.stroke_color,
                         layer: GlyphLayer::Stroke,
+                        atlas_texture,
                     });
                 }
 
@@ -390,10 +434,11 @@ impl TextLayouter {
                     uv_max,
                     color: style.color,
                     layer: GlyphLayer::Fill,
+                    atlas_texture,
                 });
             }
 
-            cursor_x += glyph_data.advance * scale + style.letter_spacing;
+            cursor_x += glyph_data.advance * glyph_scale + style.letter_spacing;
             line_width = cursor_x;
             prev_char = Some(ch);
         }
@@ -710,4 +755,60 @@ mod tests {
         let layout = layout.unwrap();
         assert!(!layout.glyphs.is_empty());
     }
+
+    fn font_with_glyphs(name: &str, atlas_texture: u32, chars: &[char]) -> MsdfFont {
+        let bounds = GlyphBounds {
+            left: 0.0,
+            bottom: 0.0,
+            right: 0.5,
+            top: 0.7,
+        };
+        let glyphs = chars
+            .iter()
+            .map(|&ch| {
+                (
+                    ch,
+                    GlyphData {
+                        unicode: ch as u32,
+                        advance: 0.6,
+                        plane_bounds: Some(bounds),
+                        atlas_bounds: Some(bounds),
+                    },
+                )
+            })
+            .collect();
+        MsdfFont {
+            name: name.to_string(),
+            atlas_texture,
+            atlas_size: [1024, 1024],
+            distance_range: 4.0,
+            glyphs,
+            kerning: HashMap::new(),
+            line_height: 1.0,
+            ascender: 0.8,
+            descender: -0.2,
+        }
+    }
+
+    #[test]
+    fn test_font_fallback_chain() {
+        let primary = font_with_glyphs("Latin", 1, &['A', '?', ' ']);
+        let cjk = font_with_glyphs("CJK", 2, &['中']);
+        let stack = FontStack::new(primary).with_fallback(cjk);
+
+        // 主字体缺少的字形由回退字体提供，并缓存结果
+        assert_eq!(stack.resolve('A' as u32), Some(0));
+        assert_eq!(stack.resolve('中' as u32), Some(1));
+        assert_eq!(stack.font_for('中' as u32).unwrap().name, "CJK");
+        assert_eq!(stack.resolve('😀' as u32), None);
+        assert_eq!(stack.cached_count(), 3);
+
+        let mut layouter = TextLayouter::new();
+        layouter.register_font_stack("ui".to_string(), stack);
+        let layout = layouter
+            .layout_text("A中", "ui", &TextStyle::default(), None)
+            .unwrap();
+        let textures: Vec<u32> = layout.glyphs.iter().map(|g| g.atlas_texture).collect();
+        assert_eq!(textures, vec![1, 2]);
+    }
 }
@@ -3,6 +3,7 @@
 //! 提供ASTC、BC等压缩纹理格式的加载和解码支持。
 
 use crate::core::error::RenderError;
+use game_engine_hardware::{GpuVendor, HardwareInfo, SocVendor};
 
 /// 压缩纹理格式
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -17,6 +18,8 @@ pub enum CompressedTextureFormat {
     BC1,
     /// BC3/DXT5 (桌面端，透明)
     BC3,
+    /// BC5 (桌面端，双通道，法线贴图)
+    BC5,
     /// BC7 (桌面端，高质量)
     BC7,
     /// ETC2 (移动端)
@@ -82,6 +85,7 @@ impl CompressedTextureFormat {
             match fourcc {
                 b"DXT1" => Some(Self::BC1),
                 b"DXT5" => Some(Self::BC3),
+                b"ATI2" | b"BC5U" => Some(Self::BC5),
                 b"BC7\0" | b"DX10" => {
                     // BC7或DX10格式（需要检查DX10头）
                     if data.len() >= 148 {
@@ -133,7 +137,7 @@ impl CompressedTextureFormat {
             Self::Astc4x4 => (4, 4),
             Self::Astc6x6 => (6, 6),
             Self::Astc8x8 => (8, 8),
-            Self::BC1 | Self::BC3 | Self::BC7 | Self::ETC2 => (4, 4),
+            Self::BC1 | Self::BC3 | Self::BC5 | Self::BC7 | Self::ETC2 => (4, 4),
         }
     }
 
@@ -145,6 +149,7 @@ impl CompressedTextureFormat {
             Self::Astc8x8 => "ASTC 8x8",
             Self::BC1 => "BC1/DXT1",
             Self::BC3 => "BC3/DXT5",
+            Self::BC5 => "BC5",
             Self::BC7 => "BC7",
             Self::ETC2 => "ETC2",
         }
@@ -183,6 +188,12 @@ impl CompressedTextureFormat {
                 let blocks_y = (height + 3) / 4;
                 (blocks_x * blocks_y * 16) as usize
             }
+            Self::BC5 => {
+                // BC5: 每个块16字节，块大小4x4像素
+                let blocks_x = (width + 3) / 4;
+                let blocks_y = (height + 3) / 4;
+                (blocks_x * blocks_y * 16) as usize
+            }
             Self::BC7 => {
                 // BC7: 每个块16字节，块大小4x4像素
                 let blocks_x = (width + 3) / 4;
@@ -353,6 +364,7 @@ impl TextureFormatDetector {
         let dimensions = match format {
             CompressedTextureFormat::BC1
             | CompressedTextureFormat::BC3
+            | CompressedTextureFormat::BC5
             | CompressedTextureFormat::BC7 => CompressedTextureFormat::parse_dds_dimensions(data),
             _ => None, // ASTC需要特殊解析
         };
@@ -391,6 +403,60 @@ impl TextureFormatDetector {
             }
         }
     }
+
+    /// 根据检测到的硬件推荐压缩格式
+    ///
+    /// 桌面GPU使用BCn，移动端和Apple GPU使用ASTC；法线贴图优先使用双通道格式
+    /// （BC5，ASTC下使用4x4块保留精度）。两者都不支持时返回None（未压缩）。
+    pub fn recommended_format(
+        hw: &HardwareInfo,
+        has_alpha: bool,
+        is_normal_map: bool,
+    ) -> Option<CompressedTextureFormat> {
+        let (supports_astc, supports_bc) = gpu_compression_support(hw);
+
+        if supports_astc {
+            Some(if is_normal_map || has_alpha {
+                CompressedTextureFormat::Astc4x4
+            } else {
+                CompressedTextureFormat::Astc6x6
+            })
+        } else if supports_bc {
+            Some(if is_normal_map {
+                CompressedTextureFormat::BC5
+            } else if has_alpha {
+                CompressedTextureFormat::BC7
+            } else {
+                CompressedTextureFormat::BC1
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// 判断硬件支持的压缩格式族，返回 (ASTC, BCn)
+///
+/// 移动端SoC和Apple GPU优先使用ASTC，即使部分设备（Apple Silicon、Tegra）也支持BCn。
+fn gpu_compression_support(hw: &HardwareInfo) -> (bool, bool) {
+    let mobile_soc = hw.soc.as_ref().is_some_and(|soc| {
+        matches!(
+            soc.vendor,
+            SocVendor::Apple
+                | SocVendor::Qualcomm
+                | SocVendor::MediaTek
+                | SocVendor::Samsung
+                | SocVendor::HiSilicon
+        )
+    });
+
+    match hw.gpu.vendor {
+        GpuVendor::Apple | GpuVendor::Qualcomm | GpuVendor::Mali | GpuVendor::PowerVR => {
+            (true, false)
+        }
+        GpuVendor::Nvidia | GpuVendor::Amd | GpuVendor::Intel => (mobile_soc, !mobile_soc),
+        GpuVendor::Unknown => (mobile_soc, false),
+    }
 }
 
 /// 平台类型
@@ -434,6 +500,7 @@ impl Platform {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use game_engine_hardware::{AutoConfig, GpuInfo, HardwareCapability, SocInfo};
 
     #[test]
     fn test_format_detection() {
@@ -498,6 +565,70 @@ mod tests {
         assert_eq!(CompressedTextureFormat::BC7.name(), "BC7");
     }
 
+    fn hardware(gpu: GpuInfo, soc: Option<SocInfo>) -> HardwareInfo {
+        let capability = HardwareCapability::evaluate(&gpu, &None, &soc);
+        let recommended_config = AutoConfig::from_capability(&capability);
+        HardwareInfo {
+            gpu,
+            npu: None,
+            soc,
+            capability,
+            recommended_config,
+        }
+    }
+
+    #[test]
+    fn test_recommended_format_for_hardware() {
+        let mobile = hardware(
+            GpuInfo {
+                vendor: GpuVendor::Qualcomm,
+                name: "Adreno 740".to_string(),
+                ..GpuInfo::default()
+            },
+            Some(SocInfo {
+                vendor: SocVendor::Qualcomm,
+                name: "Snapdragon 8 Gen 2".to_string(),
+                ..SocInfo::default()
+            }),
+        );
+        assert_eq!(
+            TextureFormatDetector::recommended_format(&mobile, true, false),
+            Some(CompressedTextureFormat::Astc4x4)
+        );
+        assert_eq!(
+            TextureFormatDetector::recommended_format(&mobile, false, false),
+            Some(CompressedTextureFormat::Astc6x6)
+        );
+
+        let desktop = hardware(
+            GpuInfo {
+                vendor: GpuVendor::Nvidia,
+                name: "NVIDIA GeForce RTX 4070".to_string(),
+                ..GpuInfo::default()
+            },
+            None,
+        );
+        assert_eq!(
+            TextureFormatDetector::recommended_format(&desktop, true, false),
+            Some(CompressedTextureFormat::BC7)
+        );
+        assert_eq!(
+            TextureFormatDetector::recommended_format(&desktop, false, false),
+            Some(CompressedTextureFormat::BC1)
+        );
+        assert_eq!(
+            TextureFormatDetector::recommended_format(&desktop, false, true),
+            Some(CompressedTextureFormat::BC5)
+        );
+
+        // 无法识别的GPU回退到未压缩
+        let unknown = hardware(GpuInfo::default(), None);
+        assert_eq!(
+            TextureFormatDetector::recommended_format(&unknown, true, false),
+            None
+        );
+    }
+
     #[test]
     fn test_format_selection() {
         // 测试Android平台格式选择
@@ -1,6 +1,9 @@
 use crate::impl_default;
+use crate::render::gpu_memory::{GpuAllocation, GpuMemoryTracker};
+use crate::render::wgpu::Instance;
 use bevy_ecs::prelude::*;
-use glam::Vec2;
+use glam::{Vec2, Vec3};
+use std::collections::HashMap;
 
 #[derive(Component, Clone)]
 pub struct TileMap {
@@ -36,3 +39,136 @@ impl TileMap {
         }
     }
 }
+
+/// `ecs::TileMap` 的渲染方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TileMapRenderMode {
+    /// 每个瓦片生成一个 `Sprite` 实体
+    #[default]
+    Entities,
+    /// 每个可见chunk构建一个实例缓冲区，一次绘制调用渲染整个chunk，不生成瓦片实体
+    Instanced,
+}
+
+/// Instanced 模式下瓦片地图各可见chunk的实例数据
+///
+/// 由 `tilemap_chunk_system` 维护，只包含可见且非空的chunk。
+#[derive(Component, Clone, Default)]
+pub struct TileChunkInstances {
+    pub chunks: HashMap<(i32, i32), Vec<Instance>>,
+    /// chunk集合或内容变化时递增，渲染器据此判断是否需要重新上传
+    pub revision: u64,
+}
+
+impl TileChunkInstances {
+    /// 所有chunk的实例总数
+    pub fn instance_count(&self) -> usize {
+        self.chunks.values().map(Vec::len).sum()
+    }
+}
+
+/// 构建chunk `(cx, cy)` 的瓦片实例，`origin` 为地图实体的位置
+pub fn build_chunk_instances(
+    map: &crate::ecs::TileMap,
+    tileset: &crate::ecs::TileSet,
+    origin: Vec3,
+    chunk: (i32, i32),
+) -> Vec<Instance> {
+    let chunk_w = map.chunk_size[0].max(1);
+    let chunk_cols = map.width.div_ceil(chunk_w);
+    let chunk_id = chunk.1.max(0) as u32 * chunk_cols + chunk.0.max(0) as u32;
+    let mut instances = Vec::new();
+    map.for_each_chunk_tile(tileset, origin, chunk, |idx, pos, uv_off, uv_scale| {
+        let flags = map.tile_flags(idx);
+        let (uv_off, uv_scale) = flags.apply_uv(uv_off, uv_scale);
+        instances.push(Instance {
+            pos: [pos.x, pos.y],
+            scale: flags.sprite_size(map.tile_size),
+            rot: flags.rotation(),
+            target: 0,
+            chunk: chunk_id,
+            color: [1.0, 1.0, 1.0, 1.0],
+            uv_offset: uv_off,
+            uv_scale,
+            layer: map.layer,
+            tex_index: map.atlas_tex_index,
+            normal_tex_index: 0,
+            msdf: 0.0,
+            px_range: 0.0,
+        });
+    });
+    instances
+}
+
+/// 单个chunk的GPU实例缓冲区
+pub struct GpuTileChunk {
+    pub buffer: wgpu::Buffer,
+    pub count: u32,
+    pub tex_idx: u32,
+    /// 显存登记，随缓冲区释放
+    _allocation: GpuAllocation,
+}
+
+impl GpuTileChunk {
+    /// 上传chunk实例，空chunk返回 `None`
+    pub fn new(device: &wgpu::Device, instances: &[Instance], tex_idx: u32) -> Option<Self> {
+        Self::with_tracker(device, instances, tex_idx, GpuMemoryTracker::global())
+    }
+
+    /// 上传chunk实例，显存登记到指定统计器
+    pub(crate) fn with_tracker(
+        device: &wgpu::Device,
+        instances: &[Instance],
+        tex_idx: u32,
+        tracker: &'static GpuMemoryTracker,
+    ) -> Option<Self> {
+        if instances.is_empty() {
+            return None;
+        }
+        let (buffer, allocation) = tracker.create_buffer_init(
+            device,
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("Tile Chunk Instances"),
+                contents: bytemuck::cast_slice(instances),
+                usage: wgpu::BufferUsages::VERTEX,
+            },
+        );
+        Some(Self {
+            buffer,
+            count: instances.len() as u32,
+            tex_idx,
+            _allocation: allocation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::gpu_memory::GpuMemoryStats;
+
+    #[test]
+    fn test_tile_chunk_memory_tracking() {
+        // 独立统计器，不受并行测试中其他分配的影响
+        static TRACKER: GpuMemoryTracker = GpuMemoryTracker::new();
+
+        let Some((device, _queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let instances = [bytemuck::Zeroable::zeroed(); 4];
+        assert!(GpuTileChunk::with_tracker(&device, &[], 0, &TRACKER).is_none());
+        let chunk = GpuTileChunk::with_tracker(&device, &instances, 0, &TRACKER).unwrap();
+        assert_eq!(
+            TRACKER.stats(),
+            GpuMemoryStats {
+                bytes_used: std::mem::size_of_val(&instances) as u64,
+                buffer_count: 1,
+                texture_count: 0,
+            }
+        );
+
+        drop(chunk);
+        assert_eq!(TRACKER.stats(), GpuMemoryStats::default());
+    }
+}
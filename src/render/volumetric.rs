@@ -2,6 +2,7 @@
 //!
 //! 实现体积渲染效果，包括：
 //! - 雾效果（线性、指数、高度雾）
+//! - 局部雾体积（盒形区域，可重叠）
 //! - 体积光（God Rays）
 //! - 云渲染
 //! - 体积阴影
@@ -59,6 +60,83 @@ impl_default!(VolumetricConfig {
     cloud_height_range: (50.0, 200.0),
 });
 
+/// 着色器支持的最大雾体积数量
+pub const MAX_FOG_VOLUMES: usize = 16;
+
+/// 轴对齐包围盒
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    /// 由中心和半尺寸创建
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// 射线段 `origin + dir * t, t ∈ [0, max_t]` 位于盒内的长度（slab 方法）
+    ///
+    /// `dir` 应为单位向量，结果才是世界距离。
+    pub fn ray_overlap(&self, origin: Vec3, dir: Vec3, max_t: f32) -> f32 {
+        let inv = dir.recip();
+        let t0 = (self.min - origin) * inv;
+        let t1 = (self.max - origin) * inv;
+        let t_near = t0.min(t1).max_element().max(0.0);
+        let t_far = t0.max(t1).min_element().min(max_t);
+        (t_far - t_near).max(0.0)
+    }
+}
+
+/// 局部雾体积，密度在盒内均匀，重叠区域的密度相加
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FogVolume {
+    pub bounds: Aabb,
+    /// 消光系数（每单位距离）
+    pub density: f32,
+    pub color: Vec3,
+}
+
+impl FogVolume {
+    pub fn new(bounds: Aabb, density: f32, color: Vec3) -> Self {
+        Self {
+            bounds,
+            density,
+            color,
+        }
+    }
+
+    /// 沿射线段积分得到的光学深度（与着色器一致）
+    pub fn optical_depth(&self, origin: Vec3, dir: Vec3, max_t: f32) -> f32 {
+        self.density.max(0.0) * self.bounds.ray_overlap(origin, dir, max_t)
+    }
+}
+
+/// 从 `origin` 到 `end` 的视线穿过所有雾体积累积的光学深度
+pub fn fog_volumes_optical_depth(volumes: &[FogVolume], origin: Vec3, end: Vec3) -> f32 {
+    let distance = origin.distance(end);
+    if distance <= f32::EPSILON {
+        return 0.0;
+    }
+    let dir = (end - origin) / distance;
+    volumes
+        .iter()
+        .map(|volume| volume.optical_depth(origin, dir, distance))
+        .sum()
+}
+
 /// 雾类型
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FogType {
@@ -80,6 +158,11 @@ pub struct VolumetricRenderer {
     uniform_buffer: Option<Buffer>,
     fog_texture: Option<Texture>,
     fog_view: Option<TextureView>,
+    fog_volumes: Vec<FogVolume>,
+    fog_volume_buffer: Option<Buffer>,
+    quad_buffer: Option<Buffer>,
+    inv_view_proj: Mat4,
+    camera_position: Vec3,
 }
 
 impl VolumetricRenderer {
@@ -93,6 +176,11 @@ impl VolumetricRenderer {
                 uniform_buffer: None,
                 fog_texture: None,
                 fog_view: None,
+                fog_volumes: Vec::new(),
+                fog_volume_buffer: None,
+                quad_buffer: None,
+                inv_view_proj: Mat4::IDENTITY,
+                camera_position: Vec3::ZERO,
             });
         }
 
@@ -135,6 +223,17 @@ impl VolumetricRenderer {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                // 雾体积
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -179,6 +278,19 @@ impl VolumetricRenderer {
             multiview: None,
         });
 
+        let fog_volume_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Volumetric Fog Volume Buffer"),
+            size: std::mem::size_of::<[GpuFogVolume; MAX_FOG_VOLUMES]>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let quad_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Volumetric Quad Buffer"),
+            contents: bytemuck::cast_slice(&QuadVertex::FULLSCREEN),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
         Ok(Self {
             config,
             pipeline: Some(pipeline),
@@ -186,9 +298,80 @@ impl VolumetricRenderer {
             uniform_buffer: None,
             fog_texture: None,
             fog_view: None,
+            fog_volumes: Vec::new(),
+            fog_volume_buffer: Some(fog_volume_buffer),
+            quad_buffer: Some(quad_buffer),
+            inv_view_proj: Mat4::IDENTITY,
+            camera_position: Vec3::ZERO,
         })
     }
 
+    /// 设置局部雾体积，超过 `MAX_FOG_VOLUMES` 的部分被忽略
+    ///
+    /// 在下一次 `update_camera` 时上传到 GPU。
+    pub fn set_fog_volumes(&mut self, volumes: &[FogVolume]) {
+        self.fog_volumes = volumes.iter().take(MAX_FOG_VOLUMES).copied().collect();
+    }
+
+    /// 当前的局部雾体积
+    pub fn fog_volumes(&self) -> &[FogVolume] {
+        &self.fog_volumes
+    }
+
+    /// 更新相机并上传统一缓冲区和雾体积，每帧渲染前调用
+    pub fn update_camera(&mut self, queue: &Queue, camera: &Camera) {
+        self.inv_view_proj = (camera.projection * camera.view).inverse();
+        self.camera_position = camera.position;
+
+        if let Some(buffer) = &self.uniform_buffer {
+            queue.write_buffer(buffer, 0, bytemuck::bytes_of(&self.uniforms()));
+        }
+
+        if let Some(buffer) = &self.fog_volume_buffer {
+            let mut volumes: [GpuFogVolume; MAX_FOG_VOLUMES] = bytemuck::Zeroable::zeroed();
+            for (gpu, volume) in volumes.iter_mut().zip(&self.fog_volumes) {
+                *gpu = GpuFogVolume::from(volume);
+            }
+            queue.write_buffer(buffer, 0, bytemuck::cast_slice(&volumes));
+        }
+    }
+
+    /// 由当前配置和相机生成统一缓冲区数据
+    fn uniforms(&self) -> VolumetricUniforms {
+        let config = &self.config;
+        let fog_type_u32 = match config.fog_type {
+            FogType::Linear => 0,
+            FogType::Exponential => 1,
+            FogType::ExponentialSquared => 2,
+            FogType::Height { .. } => 3,
+        };
+
+        VolumetricUniforms {
+            fog_type: fog_type_u32,
+            _pad0: [0; 3],
+            fog_color: [config.fog_color.x, config.fog_color.y, config.fog_color.z],
+            fog_density: config.fog_density,
+            fog_start: config.fog_start,
+            fog_end: config.fog_end,
+            volumetric_lighting: if config.volumetric_lighting {
+                1u32
+            } else {
+                0u32
+            },
+            volumetric_light_intensity: config.volumetric_light_intensity,
+            volumetric_light_samples: config.volumetric_light_samples,
+            cloud_rendering: if config.cloud_rendering { 1u32 } else { 0u32 },
+            cloud_density: config.cloud_density,
+            cloud_height_min: config.cloud_height_range.0,
+            cloud_height_max: config.cloud_height_range.1,
+            fog_volume_count: self.fog_volumes.len() as u32,
+            _pad1: [0; 2],
+            inv_view_proj: self.inv_view_proj.to_cols_array_2d(),
+            camera_position: self.camera_position.to_array(),
+            _pad2: 0.0,
+        }
+    }
+
     /// 更新配置
     pub fn update_config(
         &mut self,
@@ -200,33 +383,7 @@ impl VolumetricRenderer {
 
         if config.enabled {
             // 更新统一缓冲区
-            let fog_type_u32 = match config.fog_type {
-                FogType::Linear => 0,
-                FogType::Exponential => 1,
-                FogType::ExponentialSquared => 2,
-                FogType::Height { .. } => 3,
-            };
-
-            let uniforms = VolumetricUniforms {
-                fog_type: fog_type_u32,
-                fog_color: [config.fog_color.x, config.fog_color.y, config.fog_color.z],
-                fog_density: config.fog_density,
-                fog_start: config.fog_start,
-                fog_end: config.fog_end,
-                volumetric_lighting: if config.volumetric_lighting {
-                    1u32
-                } else {
-                    0u32
-                },
-                volumetric_light_intensity: config.volumetric_light_intensity,
-                volumetric_light_samples: config.volumetric_light_samples,
-                cloud_rendering: if config.cloud_rendering { 1u32 } else { 0u32 },
-                cloud_density: config.cloud_density,
-                cloud_height_min: config.cloud_height_range.0,
-                cloud_height_max: config.cloud_height_range.1,
-            };
-
-            let uniforms_array = [uniforms];
+            let uniforms_array = [self.uniforms()];
             let uniform_data = bytemuck::cast_slice(&uniforms_array);
             let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Volumetric Uniform Buffer"),
@@ -293,12 +450,16 @@ impl VolumetricRenderer {
             return Ok(());
         };
 
+        let Some(quad_buffer) = &self.quad_buffer else {
+            return Ok(());
+        };
+
         render_pass.set_pipeline(pipeline);
         render_pass.set_bind_group(0, bind_group, &[]);
 
         // 渲染全屏四边形
-        // 注意：需要创建全屏四边形顶点缓冲区
-        // render_pass.draw(0, 6, 0, 1);
+        render_pass.set_vertex_buffer(0, quad_buffer.slice(..));
+        render_pass.draw(0..QuadVertex::FULLSCREEN.len() as u32, 0..1);
 
         Ok(())
     }
@@ -327,6 +488,12 @@ impl VolumetricRenderer {
             ));
         };
 
+        let Some(fog_volume_buffer) = &self.fog_volume_buffer else {
+            return Err(RenderError::InvalidState(
+                "Fog volume buffer not initialized".into(),
+            ));
+        };
+
         Ok(device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Volumetric Bind Group"),
             layout: bind_group_layout,
@@ -343,6 +510,10 @@ impl VolumetricRenderer {
                     binding: 2,
                     resource: wgpu::BindingResource::Sampler(depth_sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: fog_volume_buffer.as_entire_binding(),
+                },
             ],
         }))
     }
@@ -370,6 +541,34 @@ struct QuadVertex {
 }
 
 impl QuadVertex {
+    /// 覆盖整个屏幕的两个三角形，UV 原点在左上角
+    const FULLSCREEN: [QuadVertex; 6] = [
+        QuadVertex {
+            position: [-1.0, -1.0],
+            uv: [0.0, 1.0],
+        },
+        QuadVertex {
+            position: [1.0, -1.0],
+            uv: [1.0, 1.0],
+        },
+        QuadVertex {
+            position: [1.0, 1.0],
+            uv: [1.0, 0.0],
+        },
+        QuadVertex {
+            position: [-1.0, -1.0],
+            uv: [0.0, 1.0],
+        },
+        QuadVertex {
+            position: [1.0, 1.0],
+            uv: [1.0, 0.0],
+        },
+        QuadVertex {
+            position: [-1.0, 1.0],
+            uv: [0.0, 0.0],
+        },
+    ];
+
     fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<QuadVertex>() as wgpu::BufferAddress,
@@ -395,6 +594,8 @@ impl QuadVertex {
 #[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct VolumetricUniforms {
     fog_type: u32,
+    // WGSL 中 vec3 按 16 字节对齐
+    _pad0: [u32; 3],
     fog_color: [f32; 3],
     fog_density: f32,
     fog_start: f32,
@@ -406,6 +607,36 @@ struct VolumetricUniforms {
     cloud_density: f32,
     cloud_height_min: f32,
     cloud_height_max: f32,
+    fog_volume_count: u32,
+    _pad1: [u32; 2],
+    inv_view_proj: [[f32; 4]; 4],
+    camera_position: [f32; 3],
+    _pad2: f32,
+}
+
+/// 雾体积 GPU 数据
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GpuFogVolume {
+    min: [f32; 3],
+    density: f32,
+    max: [f32; 3],
+    _pad0: f32,
+    color: [f32; 3],
+    _pad1: f32,
+}
+
+impl From<&FogVolume> for GpuFogVolume {
+    fn from(volume: &FogVolume) -> Self {
+        Self {
+            min: volume.bounds.min.to_array(),
+            density: volume.density.max(0.0),
+            max: volume.bounds.max.to_array(),
+            _pad0: 0.0,
+            color: volume.color.to_array(),
+            _pad1: 0.0,
+        }
+    }
 }
 
 /// 体积渲染着色器
@@ -423,11 +654,31 @@ struct VolumetricUniforms {
     cloud_density: f32,
     cloud_height_min: f32,
     cloud_height_max: f32,
+    fog_volume_count: u32,
+    _pad1a: u32,
+    _pad1b: u32,
+    inv_view_proj: mat4x4<f32>,
+    camera_position: vec3<f32>,
+    _pad2: f32,
+}
+
+struct FogVolume {
+    min: vec3<f32>,
+    density: f32,
+    max: vec3<f32>,
+    _pad0: f32,
+    color: vec3<f32>,
+    _pad1: f32,
+}
+
+struct FogVolumes {
+    volumes: array<FogVolume, 16>,
 }
 
 @group(0) @binding(0) var<uniform> uniforms: VolumetricUniforms;
 @group(0) @binding(1) var depth_texture: texture_depth_2d;
 @group(0) @binding(2) var depth_sampler: sampler;
+@group(0) @binding(3) var<uniform> fog_volumes: FogVolumes;
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -442,30 +693,62 @@ fn vs_main(@location(0) position: vec2<f32>, @location(1) uv: vec2<f32>) -> Vert
     );
 }
 
+// 射线段 [0, max_t] 位于盒内的长度（与 Aabb::ray_overlap 一致）
+fn box_overlap(origin: vec3<f32>, dir: vec3<f32>, max_t: f32, box_min: vec3<f32>, box_max: vec3<f32>) -> f32 {
+    let inv = 1.0 / dir;
+    let t0 = (box_min - origin) * inv;
+    let t1 = (box_max - origin) * inv;
+    let t_min = min(t0, t1);
+    let t_max = max(t0, t1);
+    let t_near = max(max(max(t_min.x, t_min.y), t_min.z), 0.0);
+    let t_far = min(min(min(t_max.x, t_max.y), t_max.z), max_t);
+    return max(t_far - t_near, 0.0);
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // 从深度纹理重建世界位置
-    let depth = textureSample(depth_texture, depth_sampler, in.uv).r;
+    let depth = textureSample(depth_texture, depth_sampler, in.uv);
+    let ndc = vec4<f32>(in.uv.x * 2.0 - 1.0, 1.0 - in.uv.y * 2.0, depth, 1.0);
+    let world = uniforms.inv_view_proj * ndc;
+    let to_scene = world.xyz / world.w - uniforms.camera_position;
+    let distance = length(to_scene);
+    let dir = to_scene / max(distance, 0.0001);
     
-    // 计算雾因子
-    var fog_factor = 0.0;
+    // 全局雾的透射率
+    var fog_factor = 1.0;
     
     if (uniforms.fog_type == 0u) {
         // 线性雾
-        fog_factor = (uniforms.fog_end - depth) / (uniforms.fog_end - uniforms.fog_start);
+        fog_factor = (uniforms.fog_end - distance) / (uniforms.fog_end - uniforms.fog_start);
         fog_factor = clamp(fog_factor, 0.0, 1.0);
     } else if (uniforms.fog_type == 1u) {
         // 指数雾
-        fog_factor = exp(-uniforms.fog_density * depth);
+        fog_factor = exp(-uniforms.fog_density * distance);
     } else if (uniforms.fog_type == 2u) {
         // 指数平方雾
-        fog_factor = exp(-uniforms.fog_density * uniforms.fog_density * depth * depth);
+        fog_factor = exp(-uniforms.fog_density * uniforms.fog_density * distance * distance);
     }
+    let global_depth = -log(max(fog_factor, 0.0001));
     
-    // 应用雾颜色
-    let fog_color = uniforms.fog_color * (1.0 - fog_factor);
+    // 局部雾体积：密度均匀，沿视线的积分即密度乘以盒内长度，重叠区域相加
+    var volume_depth = 0.0;
+    var volume_color = vec3<f32>(0.0);
+    for (var i = 0u; i < min(uniforms.fog_volume_count, 16u); i++) {
+        let volume = fog_volumes.volumes[i];
+        let optical_depth = volume.density * box_overlap(uniforms.camera_position, dir, distance, volume.min, volume.max);
+        volume_depth += optical_depth;
+        volume_color += volume.color * optical_depth;
+    }
     
-    return vec4<f32>(fog_color, fog_factor);
+    let total_depth = global_depth + volume_depth;
+    if (total_depth <= 0.0) {
+        return vec4<f32>(0.0);
+    }
+    
+    // 按光学深度加权混合雾颜色，alpha 为不透明度
+    let fog_color = (uniforms.fog_color * global_depth + volume_color) / total_depth;
+    return vec4<f32>(fog_color, 1.0 - exp(-total_depth));
 }
 "#;
 
@@ -488,4 +771,32 @@ mod tests {
         };
         assert_ne!(height_fog, FogType::Linear);
     }
+
+    #[test]
+    fn test_fog_volume_integral() {
+        let room = FogVolume::new(
+            Aabb::new(Vec3::new(-2.0, 0.0, -2.0), Vec3::new(2.0, 3.0, 2.0)),
+            0.5,
+            Vec3::ONE,
+        );
+        let camera = Vec3::new(0.0, 1.0, 10.0);
+
+        // 穿过房间：盒内长度为 4
+        let through = fog_volumes_optical_depth(&[room], camera, Vec3::new(0.0, 1.0, -10.0));
+        assert!((through - 2.0).abs() < 1e-4);
+
+        // 从房间上方经过
+        let miss = fog_volumes_optical_depth(&[room], camera, Vec3::new(0.0, 10.0, -10.0));
+        assert_eq!(miss, 0.0);
+        assert!(through > miss);
+
+        // 视线在房间内结束
+        let inside = fog_volumes_optical_depth(&[room], camera, Vec3::new(0.0, 1.0, 1.0));
+        assert!((inside - 0.5).abs() < 1e-4);
+
+        // 重叠的体积密度相加
+        let overlapped =
+            fog_volumes_optical_depth(&[room, room], camera, Vec3::new(0.0, 1.0, -10.0));
+        assert!((overlapped - 2.0 * through).abs() < 1e-4);
+    }
 }
@@ -1,8 +1,7 @@
-use wgpu::util::DeviceExt;
 use winit::window::Window;
 
 use crate::core::error::RenderError;
-use crate::render::mesh::Vertex3D;
+use crate::render::mesh::VertexLayout;
 
 #[repr(C)]
 #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
@@ -362,8 +361,15 @@ pub struct WgpuRenderer<'a> {
     uniform_buffer: wgpu::Buffer,
     uniform_bind_group: wgpu::BindGroup,
     texture_bgl: wgpu::BindGroupLayout,
-    texture_bind_groups: Vec<wgpu::BindGroup>,
+    /// 纹理绑定组，已释放的槽位共享默认纹理（索引 0）的绑定组
+    texture_bind_groups: Vec<std::sync::Arc<wgpu::BindGroup>>,
     textures_size: Vec<[u32; 2]>,
+    /// 纹理显存登记，与 `texture_bind_groups` 一一对应，已释放的槽位为 `None`
+    texture_allocations: Vec<Option<crate::render::gpu_memory::GpuAllocation>>,
+    /// 由 `release_texture` 释放、可供后续加载复用的纹理槽位
+    free_texture_slots: Vec<u32>,
+    /// 顶点/索引/实例/uniform/光源等固定缓冲区的显存登记
+    _buffer_allocations: Vec<crate::render::gpu_memory::GpuAllocation>,
     layer_ranges: Vec<(u32, u32)>,
     draw_groups: Vec<DrawGroup>,
     scale_factor: f32,
@@ -375,6 +381,8 @@ pub struct WgpuRenderer<'a> {
     ui_count: u32,
     commands: Vec<crate::render::graph::RenderCommand>,
     pub offscreen_views: std::collections::HashMap<u32, wgpu::TextureView>,
+    /// 离屏目标的显存登记，键与 `offscreen_views` 一致
+    offscreen_allocations: std::collections::HashMap<u32, crate::render::gpu_memory::GpuAllocation>,
 
     // Lighting
     lights_buffer: wgpu::Buffer,
@@ -385,18 +393,25 @@ pub struct WgpuRenderer<'a> {
     pub depth_texture: wgpu::TextureView,
     /// 深度纹理（用于遮挡剔除）
     depth_texture_raw: Option<wgpu::Texture>,
+    /// 深度纹理的显存登记
+    _depth_allocation: crate::render::gpu_memory::GpuAllocation,
     pub pipeline_3d: wgpu::RenderPipeline,
+    /// 分离顶点流（`VertexLayout::Separate`）网格使用的 3D 管线
+    pub pipeline_3d_separate: wgpu::RenderPipeline,
     pub uniform_buffer_3d: wgpu::Buffer,
     pub uniform_bind_group_3d: wgpu::BindGroup,
     pub model_uniform_buffer: wgpu::Buffer,
     pub model_bind_group: wgpu::BindGroup,
     chunk_hashes: std::collections::HashMap<u32, u64>,
+    /// Instanced 瓦片地图的chunk缓冲区，键为地图实体，值为 `(revision, chunks)`
+    tile_chunks: std::collections::HashMap<u64, (u64, Vec<crate::render::tilemap::GpuTileChunk>)>,
 
     // PBR 3D Rendering
     pub pbr_renderer: Option<crate::render::pbr_renderer::PbrRenderer>,
 
     // 3D Instance Buffer for PBR instanced rendering
     pub instance_buffer_3d: wgpu::Buffer,
+    _instance_buffer_3d_allocation: crate::render::gpu_memory::GpuAllocation,
 
     // 脏标记追踪器（增量更新优化）
     dirty_tracker: InstanceDirtyTracker,
@@ -411,6 +426,10 @@ pub struct WgpuRenderer<'a> {
     occlusion_mapping_index: usize,
     // 完全GPU端剔除标志（如果为true，使用间接绘制命令，避免CPU读取）
     use_full_gpu_culling: bool,
+
+    // 帧回读：请求后在下一帧呈现前拷贝交换链图像
+    capture_requested: bool,
+    last_capture: Option<Vec<u8>>,
 }
 
 pub struct DrawGroup {
@@ -438,29 +457,7 @@ pub struct DoubleBufferedInstances {
 impl DoubleBufferedInstances {
     /// 创建双缓冲实例管理器
     pub fn new(device: &wgpu::Device, initial_capacity: u32) -> Self {
-        let buffer_size = (initial_capacity as usize * std::mem::size_of::<Instance>()) as u64;
-
-        let buffers = [
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 0"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 1"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-        ];
-
-        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Staging Buffer"),
-            size: buffer_size,
-            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
+        let (buffers, staging_buffer) = Self::create_buffers(device, initial_capacity);
 
         Self {
             buffers,
@@ -486,8 +483,46 @@ impl DoubleBufferedInstances {
         self.active_idx = 1 - self.active_idx;
     }
 
+    /// 创建两个实例缓冲区和 staging 缓冲区
+    fn create_buffers(device: &wgpu::Device, capacity: u32) -> ([wgpu::Buffer; 2], wgpu::Buffer) {
+        let buffer_size =
+            (capacity as usize * std::mem::size_of::<Instance>()) as wgpu::BufferAddress;
+        let instance_buffer = |label| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: buffer_size,
+                usage: wgpu::BufferUsages::VERTEX
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            })
+        };
+
+        let buffers = [
+            instance_buffer("Instance Buffer 0"),
+            instance_buffer("Instance Buffer 1"),
+        ];
+
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Staging Buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        (buffers, staging_buffer)
+    }
+
     /// 同步更新实例数据到后台缓冲区并交换
-    pub fn update_sync(&mut self, queue: &wgpu::Queue, instances: &[Instance]) {
+    ///
+    /// 实例数超过容量时先扩容，保证所有实例都被上传。
+    pub fn update_sync(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        instances: &[Instance],
+    ) {
+        self.grow_for_write(device, instances.len());
         self.count = instances.len() as u32;
         if !instances.is_empty() {
             queue.write_buffer(self.back_buffer(), 0, bytemuck::cast_slice(instances));
@@ -508,6 +543,7 @@ impl DoubleBufferedInstances {
             return None;
         }
 
+        self.grow_for_write(device, instances.len());
         self.count = instances.len() as u32;
         let byte_size = (instances.len() * std::mem::size_of::<Instance>()) as u64;
 
@@ -530,40 +566,40 @@ impl DoubleBufferedInstances {
         self.count
     }
 
-    /// 扩展缓冲区容量 (如果需要)
-    pub fn ensure_capacity(&mut self, device: &wgpu::Device, required: u32) {
+    /// 获取缓冲区容量 (实例数)
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// 扩展缓冲区容量到不小于 `required` 的下一个2的幂
+    ///
+    /// 两个缓冲区都会重新分配，旧数据不会保留。返回是否发生了扩容。
+    pub fn ensure_capacity(&mut self, device: &wgpu::Device, required: u32) -> bool {
         if required <= self.capacity {
-            return;
+            return false;
         }
 
-        // 扩展到所需容量的1.5倍
-        let new_capacity = (required as f32 * 1.5) as u32;
-        let buffer_size = (new_capacity as usize * std::mem::size_of::<Instance>()) as u64;
-
-        self.buffers = [
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 0"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 1"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-        ];
-
-        self.staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Staging Buffer"),
-            size: buffer_size,
-            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
-
+        let new_capacity = required.next_power_of_two();
+        let (buffers, staging_buffer) = Self::create_buffers(device, new_capacity);
+        self.buffers = buffers;
+        self.staging_buffer = staging_buffer;
         self.capacity = new_capacity;
         self.active_idx = 0;
+        true
+    }
+
+    /// 写入前检查容量，不足时扩容并记录警告
+    fn grow_for_write(&mut self, device: &wgpu::Device, required: usize) {
+        let old_capacity = self.capacity;
+        if self.ensure_capacity(device, required as u32) {
+            tracing::warn!(
+                target: "render",
+                "Instance buffer grew from {} to {} instances ({} requested)",
+                old_capacity,
+                self.capacity,
+                required
+            );
+        }
     }
 }
 
@@ -617,8 +653,14 @@ impl<'a> WgpuRenderer<'a> {
             caps.present_modes[0]
         };
         let alpha_mode = caps.alpha_modes[0];
+        // 交换链支持时允许拷贝，用于帧回读
+        let usage = if caps.usages.contains(wgpu::TextureUsages::COPY_SRC) {
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC
+        } else {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+        };
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format,
             width: size.width,
             height: size.height,
@@ -828,12 +870,15 @@ struct UiOut {
                 },
             ],
         });
-        let lights_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Lights Buffer"),
-            size: 1024 * std::mem::size_of::<GpuPointLight>() as wgpu::BufferAddress,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let (lights_buffer, lights_allocation) = crate::render::gpu_memory::create_buffer(
+            &device,
+            &wgpu::BufferDescriptor {
+                label: Some("Lights Buffer"),
+                size: 1024 * std::mem::size_of::<GpuPointLight>() as wgpu::BufferAddress,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
 
         let lights_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Lights BGL"),
@@ -1052,40 +1097,55 @@ struct UiOut {
             Vertex { pos: [0.5, 0.5] },
             Vertex { pos: [-0.5, 0.5] },
         ];
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: None,
-            contents: bytemuck::cast_slice(&quad),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
+        let (vertex_buffer, vertex_allocation) = crate::render::gpu_memory::create_buffer_init(
+            &device,
+            &wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&quad),
+                usage: wgpu::BufferUsages::VERTEX,
+            },
+        );
         let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: None,
-            contents: bytemuck::cast_slice(&indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: 1024 * std::mem::size_of::<Instance>() as wgpu::BufferAddress,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        let ui_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: 1024 * std::mem::size_of::<UiInstance>() as wgpu::BufferAddress,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let (index_buffer, index_allocation) = crate::render::gpu_memory::create_buffer_init(
+            &device,
+            &wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&indices),
+                usage: wgpu::BufferUsages::INDEX,
+            },
+        );
+        let (instance_buffer, instance_allocation) = crate::render::gpu_memory::create_buffer(
+            &device,
+            &wgpu::BufferDescriptor {
+                label: None,
+                size: 1024 * std::mem::size_of::<Instance>() as wgpu::BufferAddress,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
+        let (ui_instance_buffer, ui_instance_allocation) = crate::render::gpu_memory::create_buffer(
+            &device,
+            &wgpu::BufferDescriptor {
+                label: None,
+                size: 1024 * std::mem::size_of::<UiInstance>() as wgpu::BufferAddress,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+        );
 
         // screen uniform
-        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: None,
-            contents: bytemuck::bytes_of(&ScreenUniform {
-                screen_size: [size.width as f32, size.height as f32],
-                scale_factor: 1.0,
-                _pad: 0.0,
-            }),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
+        let (uniform_buffer, uniform_allocation) = crate::render::gpu_memory::create_buffer_init(
+            &device,
+            &wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::bytes_of(&ScreenUniform {
+                    screen_size: [size.width as f32, size.height as f32],
+                    scale_factor: 1.0,
+                    _pad: 0.0,
+                }),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            },
+        );
         let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: None,
             layout: &uniform_bgl,
@@ -1116,7 +1176,7 @@ struct UiOut {
                 data[idx + 3] = 255; // a
             }
         }
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
+        let texture_desc = wgpu::TextureDescriptor {
             label: None,
             size: wgpu::Extent3d {
                 width: tex_size,
@@ -1129,7 +1189,9 @@ struct UiOut {
             format: wgpu::TextureFormat::Rgba8UnormSrgb,
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
-        });
+        };
+        let (texture, texture_allocation) =
+            crate::render::gpu_memory::create_texture(&device, &texture_desc);
         queue.write_texture(
             wgpu::ImageCopyTexture {
                 texture: &texture,
@@ -1167,20 +1229,10 @@ struct UiOut {
         });
 
         // --- 3D Setup ---
-        let depth_texture_raw = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Depth Texture"),
-            size: wgpu::Extent3d {
-                width: config.width,
-                height: config.height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth32Float,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
+        let (depth_texture_raw, depth_allocation) = crate::render::gpu_memory::create_texture(
+            &device,
+            &Self::depth_texture_desc(config.width, config.height),
+        );
         let depth_view = depth_texture_raw.create_view(&wgpu::TextureViewDescriptor::default());
 
         let shader_3d = device.create_shader_module(wgpu::ShaderModuleDescriptor {
@@ -1230,13 +1282,17 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             )),
         });
 
-        let uniform_buffer_3d = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("3D Uniform Buffer"),
-            contents: bytemuck::cast_slice(&[Uniforms3D {
-                view_proj: [[0.0; 4]; 4],
-            }]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
+        let (uniform_buffer_3d, uniform_3d_allocation) =
+            crate::render::gpu_memory::create_buffer_init(
+                &device,
+                &wgpu::util::BufferInitDescriptor {
+                    label: Some("3D Uniform Buffer"),
+                    contents: bytemuck::cast_slice(&[Uniforms3D {
+                        view_proj: [[0.0; 4]; 4],
+                    }]),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                },
+            );
 
         let uniform_bind_group_layout_3d =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -1262,12 +1318,16 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             label: Some("3D Uniform Bind Group"),
         });
 
-        let model_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Model Uniform Buffer"),
-            size: (256 * 1000) as wgpu::BufferAddress, // Support 1000 meshes
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let (model_uniform_buffer, model_uniform_allocation) =
+            crate::render::gpu_memory::create_buffer(
+                &device,
+                &wgpu::BufferDescriptor {
+                    label: Some("Model Uniform Buffer"),
+                    size: (256 * 1000) as wgpu::BufferAddress, // Support 1000 meshes
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            );
 
         let model_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -1303,55 +1363,67 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             push_constant_ranges: &[],
         });
 
-        let pipeline_3d = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("3D Pipeline"),
-            layout: Some(&pipeline_layout_3d),
-            vertex: wgpu::VertexState {
-                module: &shader_3d,
-                entry_point: "vs_main",
-                buffers: &[Vertex3D::desc()],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader_3d,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-        });
+        // 两种顶点布局共用同一着色器，仅顶点缓冲区布局不同
+        let create_pipeline_3d = |label: &str, layout: VertexLayout| {
+            let buffers = layout.buffer_layouts();
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&pipeline_layout_3d),
+                vertex: wgpu::VertexState {
+                    module: &shader_3d,
+                    entry_point: "vs_main",
+                    buffers: &buffers,
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader_3d,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: config.format,
+                        blend: Some(wgpu::BlendState::REPLACE),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: Some(wgpu::Face::Back),
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: wgpu::TextureFormat::Depth32Float,
+                    depth_write_enabled: true,
+                    depth_compare: wgpu::CompareFunction::Less,
+                    stencil: wgpu::StencilState::default(),
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            })
+        };
+        let pipeline_3d = create_pipeline_3d("3D Pipeline", VertexLayout::Interleaved);
+        let pipeline_3d_separate =
+            create_pipeline_3d("3D Separate Streams Pipeline", VertexLayout::Separate);
 
         // Initialize PBR Renderer
         let pbr_renderer = crate::render::pbr_renderer::PbrRenderer::new(&device, format);
 
         // Initialize 3D Instance Buffer for PBR instanced rendering
-        let instance_buffer_3d = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("3D Instance Buffer"),
-            size: 1024 * std::mem::size_of::<crate::render::pbr_renderer::Instance3D>() as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let (instance_buffer_3d, instance_buffer_3d_allocation) =
+            crate::render::gpu_memory::create_buffer(
+                &device,
+                &wgpu::BufferDescriptor {
+                    label: Some("3D Instance Buffer"),
+                    size: 1024
+                        * std::mem::size_of::<crate::render::pbr_renderer::Instance3D>() as u64,
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+            );
 
         // 初始化脏标记追踪器
         let dirty_tracker = InstanceDirtyTracker::with_capacity(1024);
@@ -1365,6 +1437,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         let gpu_driven_config = GpuDrivenConfig {
             frustum_culling: true,
             occlusion_culling: true, // 启用遮挡剔除
+            occlusion_two_pass: true,
             lod_enabled: false,
             max_instances: 65536,
             workgroup_size: 64,
@@ -1397,8 +1470,20 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             uniform_buffer,
             uniform_bind_group,
             texture_bgl,
-            texture_bind_groups: vec![texture_bind_group],
+            texture_bind_groups: vec![std::sync::Arc::new(texture_bind_group)],
             textures_size: vec![[tex_size, tex_size]],
+            texture_allocations: vec![Some(texture_allocation)],
+            free_texture_slots: Vec::new(),
+            _buffer_allocations: vec![
+                vertex_allocation,
+                index_allocation,
+                instance_allocation,
+                ui_instance_allocation,
+                uniform_allocation,
+                lights_allocation,
+                uniform_3d_allocation,
+                model_uniform_allocation,
+            ],
             layer_ranges: Vec::new(),
             draw_groups: Vec::new(),
             scale_factor: 1.0,
@@ -1410,31 +1495,63 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             ui_count: 0,
             commands: Vec::new(),
             offscreen_views: std::collections::HashMap::new(),
+            offscreen_allocations: std::collections::HashMap::new(),
             lights_buffer,
             lights_bind_group,
             lights: Vec::new(),
             depth_texture: depth_view,
             depth_texture_raw: Some(depth_texture_raw),
+            _depth_allocation: depth_allocation,
             pipeline_3d,
+            pipeline_3d_separate,
             uniform_buffer_3d,
             uniform_bind_group_3d,
             model_uniform_buffer,
             model_bind_group,
             chunk_hashes: std::collections::HashMap::new(),
+            tile_chunks: std::collections::HashMap::new(),
             pbr_renderer: Some(pbr_renderer),
             instance_buffer_3d,
+            _instance_buffer_3d_allocation: instance_buffer_3d_allocation,
             dirty_tracker,
             gpu_culling_manager,
             gpu_driven_renderer,
             occlusion_mapping_buffer: [None, None],
             occlusion_mapping_index: 0,
             use_full_gpu_culling: false,  // 默认使用传统路径，可以逐步迁移
+            capture_requested: false,
+            last_capture: None,
         })
     }
 
     pub fn create_offscreen_target(&mut self, id: u32, width: u32, height: u32) {
-        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some(&format!("Offscreen Target {}", id)),
+        let (texture, allocation) = crate::render::gpu_memory::create_texture(
+            &self.device,
+            &wgpu::TextureDescriptor {
+                label: Some(&format!("Offscreen Target {}", id)),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.config.format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.offscreen_views.insert(id, view);
+        self.offscreen_allocations.insert(id, allocation);
+    }
+
+    /// 主深度缓冲的纹理描述
+    fn depth_texture_desc(width: u32, height: u32) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: Some("Depth Texture"),
             size: wgpu::Extent3d {
                 width,
                 height,
@@ -1443,12 +1560,10 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: self.config.format,
+            format: wgpu::TextureFormat::Depth32Float,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
-        });
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        self.offscreen_views.insert(id, view);
+        }
     }
 
     pub fn resize(&mut self, size: winit::dpi::PhysicalSize<u32>) {
@@ -1459,22 +1574,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             self.surface.configure(&self.device, &self.config);
 
             // Resize depth texture
-            let depth_texture = self.device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Depth Texture"),
-                size: wgpu::Extent3d {
-                    width: size.width,
-                    height: size.height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Depth32Float,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
-                    | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
+            let (depth_texture, allocation) = crate::render::gpu_memory::create_texture(
+                &self.device,
+                &Self::depth_texture_desc(size.width, size.height),
+            );
             self.depth_texture = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+            self.depth_texture_raw = Some(depth_texture);
+            self._depth_allocation = allocation;
         }
     }
 
@@ -1614,12 +1720,17 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     if target_id == 0 {
                         // Draw 3D Meshes
                         if !meshes.is_empty() {
-                            rpass.set_pipeline(&self.pipeline_3d);
                             rpass.set_bind_group(0, &self.uniform_bind_group_3d, &[]);
                             for (i, (mesh, _)) in meshes.iter().enumerate() {
+                                // 按网格的顶点布局选择管线
+                                let pipeline = match mesh.layout {
+                                    VertexLayout::Interleaved => &self.pipeline_3d,
+                                    VertexLayout::Separate => &self.pipeline_3d_separate,
+                                };
+                                rpass.set_pipeline(pipeline);
                                 let offset = (i * 256) as u32;
                                 rpass.set_bind_group(1, &self.model_bind_group, &[offset]);
-                                rpass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                                mesh.set_vertex_buffers(&mut rpass, 0);
                                 rpass.set_index_buffer(
                                     mesh.index_buffer.slice(..),
                                     wgpu::IndexFormat::Uint32,
@@ -1631,6 +1742,27 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
                     rpass.set_bind_group(0, &self.uniform_bind_group, &[]);
 
+                    // Instanced 瓦片地图：每个chunk一次绘制调用
+                    if target_id == 0 {
+                        for chunk in self.tile_chunks.values().flat_map(|(_, c)| c) {
+                            let Some(bind_group) =
+                                self.texture_bind_groups.get(chunk.tex_idx as usize)
+                            else {
+                                continue;
+                            };
+                            rpass.set_pipeline(&self.pipeline);
+                            rpass.set_bind_group(1, bind_group, &[]);
+                            rpass.set_bind_group(2, &self.lights_bind_group, &[]);
+                            rpass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                            rpass.set_vertex_buffer(1, chunk.buffer.slice(..));
+                            rpass.set_index_buffer(
+                                self.index_buffer.slice(..),
+                                wgpu::IndexFormat::Uint16,
+                            );
+                            rpass.draw_indexed(0..self.vertex_count, 0, 0..chunk.count);
+                        }
+                    }
+
                     i += 1;
                     while i < graph.commands.len() {
                         match &graph.commands[i] {
@@ -1697,9 +1829,35 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }
 
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.capture_if_requested(&frame.texture);
         frame.present();
     }
 
+    /// 同步 Instanced 瓦片地图的chunk缓冲区
+    ///
+    /// `maps` 为 `(地图实体, chunk实例, 图集纹理索引)`，`revision` 未变化的地图不会重新上传，
+    /// 不在列表中的地图会释放其缓冲区。
+    pub fn sync_tile_chunks(
+        &mut self,
+        maps: &[(u64, &crate::render::tilemap::TileChunkInstances, u32)],
+    ) {
+        self.tile_chunks
+            .retain(|key, _| maps.iter().any(|(map, _, _)| map == key));
+        for (map, chunks, tex_idx) in maps {
+            if matches!(self.tile_chunks.get(map), Some((rev, _)) if *rev == chunks.revision) {
+                continue;
+            }
+            let buffers = chunks
+                .chunks
+                .values()
+                .filter_map(|inst| {
+                    crate::render::tilemap::GpuTileChunk::new(&self.device, inst, *tex_idx)
+                })
+                .collect();
+            self.tile_chunks.insert(*map, (chunks.revision, buffers));
+        }
+    }
+
     pub fn set_lights(&mut self, lights: Vec<GpuPointLight>) {
         self.update_lights(&lights);
     }
@@ -1834,10 +1992,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         double_buffer: &mut DoubleBufferedInstances,
         instances: &[Instance],
     ) {
-        // 确保容量足够
-        double_buffer.ensure_capacity(&self.device, instances.len() as u32);
-
-        // 使用staging buffer进行异步更新
+        // 使用staging buffer进行异步更新，容量不足时在写入前扩容
         if let Some(cmd_buffer) =
             double_buffer.update_with_staging(&self.device, &self.queue, instances)
         {
@@ -1948,7 +2103,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         if let Ok(img) = image::open(path) {
             let rgba = img.to_rgba8();
             let (w, h) = rgba.dimensions();
-            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            let texture_desc = wgpu::TextureDescriptor {
                 label: None,
                 size: wgpu::Extent3d {
                     width: w,
@@ -1961,7 +2116,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 format: wgpu::TextureFormat::Rgba8UnormSrgb,
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
-            });
+            };
+            let (texture, allocation) =
+                crate::render::gpu_memory::create_texture(&self.device, &texture_desc);
             self.queue.write_texture(
                 wgpu::ImageCopyTexture {
                     texture: &texture,
@@ -1999,10 +2156,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     },
                 ],
             });
-            let idx = self.texture_bind_groups.len() as u32;
-            self.texture_bind_groups.push(bg);
-            self.textures_size.push([w, h]);
-            Some(idx)
+            Some(self.push_texture(bg, [w, h], allocation))
         } else {
             None
         }
@@ -2012,7 +2166,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         if let Ok(img) = image::open(path) {
             let rgba = img.to_rgba8();
             let (w, h) = rgba.dimensions();
-            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            let texture_desc = wgpu::TextureDescriptor {
                 label: None,
                 size: wgpu::Extent3d {
                     width: w,
@@ -2025,7 +2179,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 format: wgpu::TextureFormat::Rgba8Unorm,
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
-            });
+            };
+            let (texture, allocation) =
+                crate::render::gpu_memory::create_texture(&self.device, &texture_desc);
             self.queue.write_texture(
                 wgpu::ImageCopyTexture {
                     texture: &texture,
@@ -2063,10 +2219,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     },
                 ],
             });
-            let idx = self.texture_bind_groups.len() as u32;
-            self.texture_bind_groups.push(bg);
-            self.textures_size.push([w, h]);
-            Some(idx)
+            Some(self.push_texture(bg, [w, h], allocation))
         } else {
             None
         }
@@ -2086,7 +2239,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             } else {
                 wgpu::TextureFormat::Rgba8UnormSrgb
             };
-            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            let texture_desc = wgpu::TextureDescriptor {
                 label: None,
                 size: wgpu::Extent3d {
                     width: w,
@@ -2099,7 +2252,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 format,
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
-            });
+            };
+            let (texture, allocation) =
+                crate::render::gpu_memory::create_texture(&self.device, &texture_desc);
             self.queue.write_texture(
                 wgpu::ImageCopyTexture {
                     texture: &texture,
@@ -2138,9 +2293,15 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 ],
             });
             let idx = index as usize;
-            if idx < self.texture_bind_groups.len() {
-                self.texture_bind_groups[idx] = bg;
+            // 已释放的槽位在空闲列表中，不能在此复活
+            if self
+                .texture_allocations
+                .get(idx)
+                .is_some_and(Option::is_some)
+            {
+                self.texture_bind_groups[idx] = std::sync::Arc::new(bg);
                 self.textures_size[idx] = [w, h];
+                self.texture_allocations[idx] = Some(allocation);
                 return Some(());
             }
         }
@@ -2156,7 +2317,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             } else {
                 wgpu::TextureFormat::Rgba8UnormSrgb
             };
-            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            let texture_desc = wgpu::TextureDescriptor {
                 label: None,
                 size: wgpu::Extent3d {
                     width: w,
@@ -2169,7 +2330,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 format,
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
-            });
+            };
+            let (texture, allocation) =
+                crate::render::gpu_memory::create_texture(&self.device, &texture_desc);
             self.queue.write_texture(
                 wgpu::ImageCopyTexture {
                     texture: &texture,
@@ -2207,15 +2370,59 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     },
                 ],
             });
-            let idx = self.texture_bind_groups.len() as u32;
-            self.texture_bind_groups.push(bg);
-            self.textures_size.push([w, h]);
-            Some(idx)
+            Some(self.push_texture(bg, [w, h], allocation))
         } else {
             None
         }
     }
 
+    /// 登记新纹理，优先复用已释放的槽位，返回纹理索引
+    fn push_texture(
+        &mut self,
+        bind_group: wgpu::BindGroup,
+        size: [u32; 2],
+        allocation: crate::render::gpu_memory::GpuAllocation,
+    ) -> u32 {
+        let bind_group = std::sync::Arc::new(bind_group);
+        if let Some(idx) = self.free_texture_slots.pop() {
+            let slot = idx as usize;
+            self.texture_bind_groups[slot] = bind_group;
+            self.textures_size[slot] = size;
+            self.texture_allocations[slot] = Some(allocation);
+            return idx;
+        }
+        self.texture_bind_groups.push(bind_group);
+        self.textures_size.push(size);
+        self.texture_allocations.push(Some(allocation));
+        (self.texture_bind_groups.len() - 1) as u32
+    }
+
+    /// 按纹理索引排列的纹理像素尺寸
+    pub fn texture_sizes(&self) -> &[[u32; 2]] {
+        &self.textures_size
+    }
+
+    /// 释放纹理占用的显存，槽位留给后续加载复用
+    ///
+    /// 释放后该索引暂时绘制默认纹理。索引 0 为默认纹理，不能释放；
+    /// 索引无效或已释放时返回 `false`。
+    pub fn release_texture(&mut self, idx: u32) -> bool {
+        let slot = idx as usize;
+        if slot == 0
+            || !self
+                .texture_allocations
+                .get(slot)
+                .is_some_and(Option::is_some)
+        {
+            return false;
+        }
+        self.texture_bind_groups[slot] = self.texture_bind_groups[0].clone();
+        self.textures_size[slot] = self.textures_size[0];
+        self.texture_allocations[slot] = None;
+        self.free_texture_slots.push(idx);
+        true
+    }
+
     pub fn load_texture_from_image(
         &mut self,
         img: image::RgbaImage,
@@ -2227,7 +2434,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         } else {
             wgpu::TextureFormat::Rgba8UnormSrgb
         };
-        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+        let texture_desc = wgpu::TextureDescriptor {
             label: None,
             size: wgpu::Extent3d {
                 width: w,
@@ -2240,7 +2447,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
             format,
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
-        });
+        };
+        let (texture, allocation) =
+            crate::render::gpu_memory::create_texture(&self.device, &texture_desc);
         self.queue.write_texture(
             wgpu::ImageCopyTexture {
                 texture: &texture,
@@ -2278,10 +2487,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 },
             ],
         });
-        let idx = self.texture_bind_groups.len() as u32;
-        self.texture_bind_groups.push(bg);
-        self.textures_size.push([w, h]);
-        Some(idx)
+        Some(self.push_texture(bg, [w, h], allocation))
     }
 
     pub fn update_screen(&mut self) {
@@ -2321,6 +2527,36 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         &self.config
     }
 
+    /// 请求在下一帧呈现前回读交换链图像
+    pub fn request_capture(&mut self) {
+        self.capture_requested = true;
+    }
+
+    /// 交换链是否支持回读
+    pub fn capture_supported(&self) -> bool {
+        self.config.usage.contains(wgpu::TextureUsages::COPY_SRC)
+    }
+
+    /// 取出最近一次回读的帧，RGBA8 紧密排列，尺寸与 `config()` 一致
+    pub fn take_capture(&mut self) -> Option<Vec<u8>> {
+        self.last_capture.take()
+    }
+
+    fn capture_if_requested(&mut self, texture: &wgpu::Texture) {
+        if !std::mem::take(&mut self.capture_requested) {
+            return;
+        }
+        self.last_capture =
+            crate::render::capture::read_texture_rgba8(&self.device, &self.queue, texture);
+        if self.last_capture.is_none() {
+            tracing::warn!(
+                target: "render",
+                "Frame capture unavailable for surface format {:?}",
+                self.config.format
+            );
+        }
+    }
+
     // ========================================================================
     // Instance Batching Methods
     // ========================================================================
@@ -2809,13 +3045,13 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     if let Some(ref gpu_driven_renderer) = self.gpu_driven_renderer {
                         let indirect_buffer = gpu_driven_renderer.indirect_buffer();
                         
-                        // 绑定可见实例缓冲区作为顶点缓冲区
-                        // 注意：需要确保可见实例缓冲区包含正确的实例数据
-                        rpass.set_vertex_buffer(1, gpu_driven_renderer.visible_instance_buffer().slice(..));
-                        
                         // 获取第一个batch的mesh信息（用于绑定顶点和索引缓冲区）
                         if let Some(batch) = batch_manager.visible_batches().next() {
-                            rpass.set_vertex_buffer(0, batch.mesh.vertex_buffer.slice(..));
+                            rpass.set_pipeline(pbr.pipeline_for(batch.mesh.layout));
+                            let instance_slot = batch.mesh.set_vertex_buffers(&mut rpass, 0);
+                            // 绑定可见实例缓冲区作为顶点缓冲区
+                            // 注意：需要确保可见实例缓冲区包含正确的实例数据
+                            rpass.set_vertex_buffer(instance_slot, gpu_driven_renderer.visible_instance_buffer().slice(..));
                             rpass.set_index_buffer(batch.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
                             
                             // 使用间接绘制命令直接绘制（完全GPU端，零CPU读取）
@@ -2837,14 +3073,19 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                 } else {
                     // 使用传统渲染路径（CPU读取结果或CPU剔除）
                     // 使用 render_batches 函数渲染所有可见批次
-                    crate::render::instance_batch::render_batches(&mut rpass, batch_manager);
+                    crate::render::instance_batch::render_batches(&mut rpass, pbr, batch_manager);
                     // 渲染小批次（阈值过滤后）
-                    crate::render::instance_batch::render_small_batches(&mut rpass, batch_manager);
+                    crate::render::instance_batch::render_small_batches(
+                        &mut rpass,
+                        pbr,
+                        batch_manager,
+                    );
                 }
             }
         }
 
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.capture_if_requested(&frame.texture);
         frame.present();
     }
 
@@ -3076,12 +3317,17 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
                     * std::mem::size_of::<crate::render::pbr_renderer::Instance3D>())
                     as u64;
                 if self.instance_buffer_3d.size() < needed_size {
-                    self.instance_buffer_3d = self.device.create_buffer(&wgpu::BufferDescriptor {
-                        label: Some("3D Instance Buffer"),
-                        size: needed_size.max(1024),
-                        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                        mapped_at_creation: false,
-                    });
+                    let (buffer, allocation) = crate::render::gpu_memory::create_buffer(
+                        &self.device,
+                        &wgpu::BufferDescriptor {
+                            label: Some("3D Instance Buffer"),
+                            size: needed_size.max(1024),
+                            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                            mapped_at_creation: false,
+                        },
+                    );
+                    self.instance_buffer_3d = buffer;
+                    self._instance_buffer_3d_allocation = allocation;
                 }
 
                 self.queue.write_buffer(
@@ -3096,9 +3342,10 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
                     rpass.set_bind_group(1, &pbr.material_bind_group, &[]);
 
-                    rpass.set_vertex_buffer(0, batch.mesh.vertex_buffer.slice(..));
+                    rpass.set_pipeline(pbr.pipeline_for(batch.mesh.layout));
+                    let instance_slot = batch.mesh.set_vertex_buffers(&mut rpass, 0);
                     rpass.set_vertex_buffer(
-                        1,
+                        instance_slot,
                         self.instance_buffer_3d.slice(
                             (batch.start_instance as u64
                                 * std::mem::size_of::<crate::render::pbr_renderer::Instance3D>()
@@ -3118,6 +3365,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }
 
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.capture_if_requested(&frame.texture);
         frame.present();
     }
 
@@ -3131,10 +3379,21 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         vertices: &[crate::render::mesh::Vertex3D],
         indices: &[u32],
     ) -> std::sync::Arc<crate::render::mesh::GpuMesh> {
-        std::sync::Arc::new(crate::render::mesh::GpuMesh::new(
+        self.create_gpu_mesh_with_layout(vertices, indices, VertexLayout::Interleaved)
+    }
+
+    /// 按指定顶点布局创建网格
+    pub fn create_gpu_mesh_with_layout(
+        &self,
+        vertices: &[crate::render::mesh::Vertex3D],
+        indices: &[u32],
+        layout: VertexLayout,
+    ) -> std::sync::Arc<crate::render::mesh::GpuMesh> {
+        std::sync::Arc::new(crate::render::mesh::GpuMesh::with_layout(
             &self.device,
             vertices,
             indices,
+            layout,
         ))
     }
 
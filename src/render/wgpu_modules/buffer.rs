@@ -23,30 +23,7 @@ pub struct DoubleBufferedInstances {
 impl DoubleBufferedInstances {
     /// 创建双缓冲实例管理器
     pub fn new(device: &wgpu::Device, initial_capacity: u32) -> Self {
-        let buffer_size =
-            (initial_capacity as usize * std::mem::size_of::<Instance>()) as wgpu::BufferAddress;
-
-        let buffers = [
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 0"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 1"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-        ];
-
-        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Staging Buffer"),
-            size: buffer_size,
-            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
+        let (buffers, staging_buffer) = Self::create_buffers(device, initial_capacity);
 
         Self {
             buffers,
@@ -72,8 +49,46 @@ impl DoubleBufferedInstances {
         self.active_idx = 1 - self.active_idx;
     }
 
+    /// 创建两个实例缓冲区和 staging 缓冲区
+    fn create_buffers(device: &wgpu::Device, capacity: u32) -> ([wgpu::Buffer; 2], wgpu::Buffer) {
+        let buffer_size =
+            (capacity as usize * std::mem::size_of::<Instance>()) as wgpu::BufferAddress;
+        let instance_buffer = |label| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: buffer_size,
+                usage: wgpu::BufferUsages::VERTEX
+                    | wgpu::BufferUsages::COPY_DST
+                    | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            })
+        };
+
+        let buffers = [
+            instance_buffer("Instance Buffer 0"),
+            instance_buffer("Instance Buffer 1"),
+        ];
+
+        let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Staging Buffer"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        (buffers, staging_buffer)
+    }
+
     /// 同步更新实例数据到后台缓冲区并交换
-    pub fn update_sync(&mut self, queue: &wgpu::Queue, instances: &[Instance]) {
+    ///
+    /// 实例数超过容量时先扩容，保证所有实例都被上传。
+    pub fn update_sync(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        instances: &[Instance],
+    ) {
+        self.grow_for_write(device, instances.len());
         self.count = instances.len() as u32;
         if !instances.is_empty() {
             queue.write_buffer(self.back_buffer(), 0, bytemuck::cast_slice(instances));
@@ -93,6 +108,7 @@ impl DoubleBufferedInstances {
             return None;
         }
 
+        self.grow_for_write(device, instances.len());
         self.count = instances.len() as u32;
         let byte_size = (instances.len() * std::mem::size_of::<Instance>()) as wgpu::BufferAddress;
 
@@ -113,40 +129,73 @@ impl DoubleBufferedInstances {
         self.count
     }
 
-    /// 扩展缓冲区容量
-    pub fn ensure_capacity(&mut self, device: &wgpu::Device, required: u32) {
+    /// 获取缓冲区容量 (实例数)
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// 扩展缓冲区容量到不小于 `required` 的下一个2的幂
+    ///
+    /// 两个缓冲区都会重新分配，旧数据不会保留。返回是否发生了扩容。
+    pub fn ensure_capacity(&mut self, device: &wgpu::Device, required: u32) -> bool {
         if required <= self.capacity {
-            return;
+            return false;
         }
 
-        let new_capacity = (required as f32 * 1.5) as u32;
-        let buffer_size =
-            (new_capacity as usize * std::mem::size_of::<Instance>()) as wgpu::BufferAddress;
+        let new_capacity = required.next_power_of_two();
+        let (buffers, staging_buffer) = Self::create_buffers(device, new_capacity);
+        self.buffers = buffers;
+        self.staging_buffer = staging_buffer;
+        self.capacity = new_capacity;
+        self.active_idx = 0;
+        true
+    }
 
-        self.buffers = [
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 0"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-            device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Instance Buffer 1"),
-                size: buffer_size,
-                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            }),
-        ];
+    /// 写入前检查容量，不足时扩容并记录警告
+    fn grow_for_write(&mut self, device: &wgpu::Device, required: usize) {
+        let old_capacity = self.capacity;
+        if self.ensure_capacity(device, required as u32) {
+            tracing::warn!(
+                target: "render",
+                "Instance buffer grew from {} to {} instances ({} requested)",
+                old_capacity,
+                self.capacity,
+                required
+            );
+        }
+    }
+}
 
-        self.staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Instance Staging Buffer"),
-            size: buffer_size,
-            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
+/// 实例上传计划
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstanceUpload {
+    /// 没有变化
+    None,
+    /// 脏比例过高，整体上传
+    Full,
+    /// 按脏范围分段上传 (起始索引, 结束索引)
+    Partial(Vec<(u32, u32)>),
+}
 
-        self.capacity = new_capacity;
-        self.active_idx = 0;
+/// 实例上传统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstanceUploadStats {
+    /// `queue.write_buffer` 调用次数
+    pub writes: u32,
+    /// 实际上传的字节数
+    pub bytes_uploaded: u64,
+    /// 实例数据总字节数
+    pub total_bytes: u64,
+}
+
+impl InstanceUploadStats {
+    /// 上传字节占总字节的比例
+    pub fn upload_ratio(&self) -> f32 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.bytes_uploaded as f32 / self.total_bytes as f32
+        }
     }
 }
 
@@ -168,11 +217,21 @@ pub struct InstanceDirtyTracker {
     instance_count: usize,
     /// 是否需要完整重建
     needs_full_rebuild: bool,
+    /// 间隔不超过该实例数的脏范围会被合并
+    merge_gap: u32,
+    /// 脏实例比例超过该值时整体上传
+    full_upload_ratio: f32,
+    /// 最近一次上传的统计
+    last_upload: InstanceUploadStats,
 }
 
 impl InstanceDirtyTracker {
     /// 默认块大小
     pub const DEFAULT_CHUNK_SIZE: usize = 128;
+    /// 默认合并间隔 (实例数)
+    pub const DEFAULT_MERGE_GAP: u32 = 16;
+    /// 默认整体上传阈值
+    pub const DEFAULT_FULL_UPLOAD_RATIO: f32 = 0.5;
 
     /// 创建脏标记追踪器
     pub fn new(initial_capacity: usize, chunk_size: usize) -> Self {
@@ -185,9 +244,22 @@ impl InstanceDirtyTracker {
             dirty_ranges: Vec::new(),
             instance_count: 0,
             needs_full_rebuild: true,
+            merge_gap: Self::DEFAULT_MERGE_GAP,
+            full_upload_ratio: Self::DEFAULT_FULL_UPLOAD_RATIO,
+            last_upload: InstanceUploadStats::default(),
         }
     }
 
+    /// 设置脏范围合并间隔
+    pub fn set_merge_gap(&mut self, gap: u32) {
+        self.merge_gap = gap;
+    }
+
+    /// 设置整体上传阈值 (脏实例比例, 0.0-1.0)
+    pub fn set_full_upload_ratio(&mut self, ratio: f32) {
+        self.full_upload_ratio = ratio.clamp(0.0, 1.0);
+    }
+
     /// 使用默认配置创建
     pub fn with_capacity(capacity: usize) -> Self {
         Self::new(capacity, Self::DEFAULT_CHUNK_SIZE)
@@ -343,7 +415,7 @@ impl InstanceDirtyTracker {
         let mut current = self.dirty_ranges[0];
 
         for &(start, end) in &self.dirty_ranges[1..] {
-            if start <= current.1 + 16 {
+            if start <= current.1 + self.merge_gap {
                 current.1 = current.1.max(end);
             } else {
                 merged.push(current);
@@ -373,6 +445,67 @@ impl InstanceDirtyTracker {
         !self.dirty_ranges.is_empty()
     }
 
+    /// 根据当前脏范围生成上传计划
+    pub fn plan_upload(&self) -> InstanceUpload {
+        if self.dirty_ranges.is_empty() {
+            return InstanceUpload::None;
+        }
+
+        let dirty_ratio = self.dirty_instance_count() as f32 / self.instance_count.max(1) as f32;
+        if dirty_ratio > self.full_upload_ratio {
+            InstanceUpload::Full
+        } else {
+            InstanceUpload::Partial(self.dirty_ranges.clone())
+        }
+    }
+
+    /// 将脏实例上传到 `buffer`
+    ///
+    /// 每个脏范围调用一次 `queue.write_buffer`，脏比例过高时整体上传。
+    /// `instances` 应为最近一次传给 [`Self::update`] 的数据。
+    pub fn upload(
+        &mut self,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        instances: &[Instance],
+    ) -> InstanceUploadStats {
+        let stride = std::mem::size_of::<Instance>() as u64;
+        let mut stats = InstanceUploadStats {
+            total_bytes: instances.len() as u64 * stride,
+            ..Default::default()
+        };
+
+        match self.plan_upload() {
+            InstanceUpload::None => {}
+            InstanceUpload::Full => {
+                queue.write_buffer(buffer, 0, bytemuck::cast_slice(instances));
+                stats.writes = 1;
+                stats.bytes_uploaded = stats.total_bytes;
+            }
+            InstanceUpload::Partial(ranges) => {
+                for (start, end) in ranges {
+                    let end = (end as usize).min(instances.len());
+                    let start = (start as usize).min(end);
+                    if start == end {
+                        continue;
+                    }
+                    let data: &[u8] = bytemuck::cast_slice(&instances[start..end]);
+                    queue.write_buffer(buffer, start as u64 * stride, data);
+                    stats.writes += 1;
+                    stats.bytes_uploaded += data.len() as u64;
+                }
+            }
+        }
+
+        self.last_upload = stats;
+        stats
+    }
+
+    /// 最近一次上传的统计
+    pub fn upload_stats(&self) -> InstanceUploadStats {
+        self.last_upload
+    }
+
     /// 重置追踪器
     pub fn reset(&mut self) {
         self.chunk_dirty.clear();
@@ -381,6 +514,7 @@ impl InstanceDirtyTracker {
         self.dirty_ranges.clear();
         self.instance_count = 0;
         self.needs_full_rebuild = true;
+        self.last_upload = InstanceUploadStats::default();
     }
 }
 
@@ -389,3 +523,135 @@ impl Default for InstanceDirtyTracker {
         Self::with_capacity(1024)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_instances(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        buffer: &wgpu::Buffer,
+        count: usize,
+    ) -> Vec<Instance> {
+        let size = (count * std::mem::size_of::<Instance>()) as wgpu::BufferAddress;
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Instance Readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &readback, 0, size);
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let instances = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+        readback.unmap();
+        instances
+    }
+
+    #[test]
+    fn test_dirty_tracker_plans_partial_writes() {
+        let mut instances: Vec<Instance> = (0..256)
+            .map(|i| Instance {
+                pos: [i as f32, 0.0],
+                ..Default::default()
+            })
+            .collect();
+        let mut tracker = InstanceDirtyTracker::with_capacity(instances.len());
+        tracker.update(&instances);
+        assert_eq!(tracker.plan_upload(), InstanceUpload::Full);
+
+        // 相距较远的两个实例分两次写入
+        instances[3].rot = 1.0;
+        instances[200].rot = 1.0;
+        tracker.mark_instance_dirty(3);
+        tracker.mark_instance_dirty(200);
+        tracker.update(&instances);
+        assert_eq!(
+            tracker.plan_upload(),
+            InstanceUpload::Partial(vec![(3, 4), (200, 201)])
+        );
+
+        // 间隔在合并阈值内时合并为一次写入
+        instances[10].rot = 2.0;
+        instances[20].rot = 2.0;
+        tracker.mark_instance_dirty(10);
+        tracker.mark_instance_dirty(20);
+        tracker.update(&instances);
+        assert_eq!(
+            tracker.plan_upload(),
+            InstanceUpload::Partial(vec![(10, 21)])
+        );
+
+        // 没有变化时不上传
+        tracker.update(&instances);
+        assert_eq!(tracker.plan_upload(), InstanceUpload::None);
+    }
+
+    #[test]
+    fn test_dirty_tracker_upload_stats() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut instances = vec![Instance::default(); 64];
+        let double_buffer = DoubleBufferedInstances::new(&device, 64);
+        let buffer = double_buffer.back_buffer();
+        let mut tracker = InstanceDirtyTracker::with_capacity(instances.len());
+        tracker.update(&instances);
+        let full = tracker.upload(&queue, buffer, &instances);
+        assert_eq!(full.writes, 1);
+        assert_eq!(full.bytes_uploaded, full.total_bytes);
+
+        instances[1].tex_index = 7;
+        instances[40].tex_index = 9;
+        tracker.mark_instance_dirty(1);
+        tracker.mark_instance_dirty(40);
+        tracker.update(&instances);
+        let partial = tracker.upload(&queue, buffer, &instances);
+        let stride = std::mem::size_of::<Instance>() as u64;
+        assert_eq!(partial.writes, 2);
+        assert_eq!(partial.bytes_uploaded, 2 * stride);
+        assert_eq!(tracker.upload_stats(), partial);
+
+        let uploaded = read_instances(&device, &queue, buffer, instances.len());
+        assert!(uploaded[1].equals(&instances[1]));
+        assert!(uploaded[40].equals(&instances[40]));
+    }
+
+    #[test]
+    fn test_update_grows_past_capacity() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let capacity = 8;
+        let mut double_buffer = DoubleBufferedInstances::new(&device, capacity);
+        let instances: Vec<Instance> = (0..=capacity)
+            .map(|i| Instance {
+                pos: [i as f32, 0.0],
+                tex_index: i,
+                ..Default::default()
+            })
+            .collect();
+
+        double_buffer.update_sync(&device, &queue, &instances);
+        assert_eq!(double_buffer.capacity(), 16);
+        assert_eq!(double_buffer.count(), capacity + 1);
+
+        let uploaded = read_instances(
+            &device,
+            &queue,
+            double_buffer.active_buffer(),
+            instances.len(),
+        );
+        assert_eq!(uploaded.len(), instances.len());
+        for (expected, actual) in instances.iter().zip(&uploaded) {
+            assert!(expected.equals(actual));
+        }
+    }
+}
@@ -22,7 +22,9 @@ pub mod texture;
 pub mod types;
 
 // 重导出主要类型
-pub use buffer::{DoubleBufferedInstances, InstanceDirtyTracker};
+pub use buffer::{
+    DoubleBufferedInstances, InstanceDirtyTracker, InstanceUpload, InstanceUploadStats,
+};
 pub use types::{
     DrawGroup, GpuPointLight, Instance, ModelUniform, ScreenUniform, UiInstance, Uniforms3D, Vertex,
 };
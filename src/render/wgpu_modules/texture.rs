@@ -395,6 +395,14 @@ impl TextureManager {
                     width, height
                 );
             }
+            CompressedTextureFormat::BC5 => {
+                // 注意：BC5解码功能需要外部库支持
+                tracing::warn!(
+                    target: "render",
+                    "BC5 texture loading requires BC decoder library (not yet integrated). Size: {}x{}",
+                    width, height
+                );
+            }
             CompressedTextureFormat::BC7 => {
                 // 注意：BC7解码功能需要外部库支持
                 // 未来计划：实现BC7解码
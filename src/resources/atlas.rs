@@ -114,3 +114,182 @@ impl Atlas {
         self.sprites.get(name).copied()
     }
 }
+
+/// 图集中的矩形区域（像素）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl AtlasRect {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+
+    /// 两个矩形共享一条完整的边时合并为一个矩形
+    fn merge(&self, other: &Self) -> Option<Self> {
+        let stacked = self.x == other.x
+            && self.width == other.width
+            && (self.y + self.height == other.y || other.y + other.height == self.y);
+        if stacked {
+            let y = self.y.min(other.y);
+            return Some(Self::new(self.x, y, self.width, self.height + other.height));
+        }
+        let side_by_side = self.y == other.y
+            && self.height == other.height
+            && (self.x + self.width == other.x || other.x + other.width == self.x);
+        side_by_side.then(|| {
+            let x = self.x.min(other.x);
+            Self::new(x, self.y, self.width + other.width, self.height)
+        })
+    }
+}
+
+/// 运行时动态图集打包器
+///
+/// 使用 Guillotine 算法：每次从空闲矩形中选出面积最贴合的一块放置，
+/// 剩余部分沿较短的一边切成两块放回空闲列表。`remove` 归还的区域会与相邻空闲区域合并，
+/// 供后续插入复用。图集已满时 `insert` 返回 `None`，由调用方新建图集页。
+#[derive(Clone, Debug)]
+pub struct DynamicAtlas {
+    size: [u32; 2],
+    free_rects: Vec<AtlasRect>,
+    used_rects: Vec<AtlasRect>,
+}
+
+impl DynamicAtlas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            size: [width, height],
+            free_rects: vec![AtlasRect::new(0, 0, width, height)],
+            used_rects: Vec::new(),
+        }
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// 分配一块 `width x height` 的区域，空间不足时返回 `None`
+    pub fn insert(&mut self, width: u32, height: u32) -> Option<AtlasRect> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        // 最佳面积匹配，面积相同时取短边剩余最小者
+        let (index, free) = self
+            .free_rects
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, r)| r.width >= width && r.height >= height)
+            .min_by_key(|(_, r)| {
+                let leftover = r.area() - width as u64 * height as u64;
+                let short_side = (r.width - width).min(r.height - height);
+                (leftover, short_side)
+            })?;
+        self.free_rects.swap_remove(index);
+
+        let placed = AtlasRect::new(free.x, free.y, width, height);
+        self.split(free, placed);
+        self.used_rects.push(placed);
+        Some(placed)
+    }
+
+    /// 归还区域，返回是否为当前已分配的区域
+    pub fn remove(&mut self, rect: AtlasRect) -> bool {
+        let Some(index) = self.used_rects.iter().position(|r| *r == rect) else {
+            return false;
+        };
+        self.used_rects.swap_remove(index);
+        self.add_free(rect);
+        true
+    }
+
+    /// 清空图集
+    pub fn clear(&mut self) {
+        let [width, height] = self.size;
+        self.free_rects = vec![AtlasRect::new(0, 0, width, height)];
+        self.used_rects.clear();
+    }
+
+    /// 区域对应的 UV 偏移与缩放，格式与 `Atlas::get` 相同
+    pub fn uv(&self, rect: &AtlasRect) -> ([f32; 2], [f32; 2]) {
+        let w = self.size[0].max(1) as f32;
+        let h = self.size[1].max(1) as f32;
+        (
+            [rect.x as f32 / w, rect.y as f32 / h],
+            [rect.width as f32 / w, rect.height as f32 / h],
+        )
+    }
+
+    /// 已分配的区域数量
+    pub fn len(&self) -> usize {
+        self.used_rects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.used_rects.is_empty()
+    }
+
+    /// 已使用面积占比
+    pub fn occupancy(&self) -> f32 {
+        let total = self.size[0] as u64 * self.size[1] as u64;
+        if total == 0 {
+            return 0.0;
+        }
+        let used: u64 = self.used_rects.iter().map(AtlasRect::area).sum();
+        used as f32 / total as f32
+    }
+
+    /// 将空闲矩形放置 `placed` 后的剩余部分切分回空闲列表
+    fn split(&mut self, free: AtlasRect, placed: AtlasRect) {
+        let right_w = free.width - placed.width;
+        let bottom_h = free.height - placed.height;
+
+        // 沿剩余较短的一边切分，使另一块尽量完整
+        let (right, bottom) = if right_w < bottom_h {
+            (
+                AtlasRect::new(free.x + placed.width, free.y, right_w, placed.height),
+                AtlasRect::new(free.x, free.y + placed.height, free.width, bottom_h),
+            )
+        } else {
+            (
+                AtlasRect::new(free.x + placed.width, free.y, right_w, free.height),
+                AtlasRect::new(free.x, free.y + placed.height, placed.width, bottom_h),
+            )
+        };
+
+        for rect in [right, bottom] {
+            if rect.width > 0 && rect.height > 0 {
+                self.free_rects.push(rect);
+            }
+        }
+    }
+
+    /// 加入空闲列表并反复与相邻空闲矩形合并
+    fn add_free(&mut self, mut rect: AtlasRect) {
+        while let Some((index, merged)) = self
+            .free_rects
+            .iter()
+            .enumerate()
+            .find_map(|(i, r)| rect.merge(r).map(|m| (i, m)))
+        {
+            self.free_rects.swap_remove(index);
+            rect = merged;
+        }
+        self.free_rects.push(rect);
+    }
+}
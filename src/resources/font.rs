@@ -119,3 +119,90 @@ impl MsdfFontAtlas {
         ))
     }
 }
+
+// --- Font Fallback ---
+
+/// 可以查询是否包含某个字形的字体
+pub trait GlyphProvider {
+    fn has_glyph(&self, codepoint: u32) -> bool;
+}
+
+impl GlyphProvider for MsdfFontAtlas {
+    fn has_glyph(&self, codepoint: u32) -> bool {
+        self.glyphs.contains_key(&codepoint)
+    }
+}
+
+/// 字体回退链
+///
+/// 主字体缺少某个字形（如 CJK 字符、emoji）时，按添加顺序依次查找回退字体，
+/// 并按码点缓存查找结果。
+pub struct FontStack<F> {
+    fonts: Vec<F>,
+    /// 码点 -> 提供该字形的字体索引（`None` 表示整条链都没有）
+    resolved: std::sync::RwLock<std::collections::HashMap<u32, Option<usize>>>,
+}
+
+impl<F: GlyphProvider> FontStack<F> {
+    pub fn new(primary: F) -> Self {
+        Self {
+            fonts: vec![primary],
+            resolved: Default::default(),
+        }
+    }
+
+    /// 在链尾追加回退字体
+    pub fn with_fallback(mut self, font: F) -> Self {
+        self.push_fallback(font);
+        self
+    }
+
+    /// 在链尾追加回退字体，并清空已缓存的查找结果
+    pub fn push_fallback(&mut self, font: F) {
+        self.fonts.push(font);
+        self.resolved
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// 查找提供该码点字形的字体索引，0 为主字体
+    pub fn resolve(&self, codepoint: u32) -> Option<usize> {
+        if let Some(&index) = self
+            .resolved
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&codepoint)
+        {
+            return index;
+        }
+
+        let index = self.fonts.iter().position(|f| f.has_glyph(codepoint));
+        self.resolved
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(codepoint, index);
+        index
+    }
+
+    /// 提供该码点字形的字体
+    pub fn font_for(&self, codepoint: u32) -> Option<&F> {
+        self.resolve(codepoint).map(|i| &self.fonts[i])
+    }
+
+    pub fn primary(&self) -> &F {
+        &self.fonts[0]
+    }
+
+    pub fn fonts(&self) -> &[F] {
+        &self.fonts
+    }
+
+    /// 已缓存的码点数量
+    pub fn cached_count(&self) -> usize {
+        self.resolved
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+}
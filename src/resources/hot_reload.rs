@@ -1,20 +1,136 @@
-use notify::{Config, RecommendedWatcher, RecursiveMode, Result as NotifyResult, Watcher};
+use crate::platform::FsEvent;
+use notify::{
+    Config, EventKind, RecommendedWatcher, RecursiveMode, Result as NotifyResult, Watcher,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// 默认的防抖静默期
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// 防抖后对资源执行的动作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReloadAction {
+    /// 文件被创建或修改，需要重新加载
+    Reload(PathBuf),
+    /// 文件被删除，需要卸载对应资源
+    Unload(PathBuf),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingKind {
+    Reload,
+    Unload,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PendingEvent {
+    kind: PendingKind,
+    last_seen: Instant,
+}
+
+/// 文件系统事件防抖器
+///
+/// 编辑器保存文件时通常会产生多个事件（Created + Modified 等）。
+/// 防抖器按路径合并事件，只有在该路径静默超过 `debounce` 后才输出一个动作。
+#[derive(Debug)]
+pub struct ReloadDebouncer {
+    debounce: Duration,
+    pending: HashMap<PathBuf, PendingEvent>,
+}
+
+impl Default for ReloadDebouncer {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEBOUNCE)
+    }
+}
+
+impl ReloadDebouncer {
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn debounce(&self) -> Duration {
+        self.debounce
+    }
+
+    pub fn set_debounce(&mut self, debounce: Duration) {
+        self.debounce = debounce;
+    }
+
+    /// 记录一个文件系统事件，`now` 为事件到达时间
+    pub fn push(&mut self, event: FsEvent, now: Instant) {
+        // 以最后一个事件决定最终动作：删除后再创建视为重新加载（原子保存）
+        let (path, kind) = match event {
+            FsEvent::Created(p) | FsEvent::Modified(p) => (p, PendingKind::Reload),
+            FsEvent::Deleted(p) => (p, PendingKind::Unload),
+        };
+        self.pending.insert(
+            path,
+            PendingEvent {
+                kind,
+                last_seen: now,
+            },
+        );
+    }
+
+    /// 取出所有已静默超过防抖时间的动作
+    pub fn poll(&mut self, now: Instant) -> Vec<ReloadAction> {
+        let debounce = self.debounce;
+        let ready: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, e)| now.saturating_duration_since(e.last_seen) >= debounce)
+            .map(|(p, _)| p.clone())
+            .collect();
+
+        let mut actions = Vec::with_capacity(ready.len());
+        for path in ready {
+            if let Some(event) = self.pending.remove(&path) {
+                actions.push(match event.kind {
+                    PendingKind::Reload => ReloadAction::Reload(path),
+                    PendingKind::Unload => ReloadAction::Unload(path),
+                });
+            }
+        }
+        actions
+    }
+
+    /// 尚未输出的路径数量
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
 
 pub struct HotReloadService {
-    rx: Receiver<PathBuf>,
+    rx: Receiver<FsEvent>,
+    debouncer: ReloadDebouncer,
     _watcher: RecommendedWatcher,
 }
 
 impl HotReloadService {
     pub fn watch_dir(path: PathBuf) -> NotifyResult<Self> {
+        Self::watch_dir_with_debounce(path, DEFAULT_DEBOUNCE)
+    }
+
+    pub fn watch_dir_with_debounce(path: PathBuf, debounce: Duration) -> NotifyResult<Self> {
         let (tx, rx) = mpsc::channel();
         let mut watcher = RecommendedWatcher::new(
             move |res: notify::Result<notify::Event>| {
                 if let Ok(event) = res {
                     for p in event.paths {
-                        let _ = tx.send(p);
+                        let fs_event = match event.kind {
+                            EventKind::Create(_) => FsEvent::Created(p),
+                            EventKind::Remove(_) => FsEvent::Deleted(p),
+                            EventKind::Modify(_) => FsEvent::Modified(p),
+                            _ => continue,
+                        };
+                        let _ = tx.send(fs_event);
                     }
                 }
             },
@@ -23,10 +139,73 @@ impl HotReloadService {
         watcher.watch(&path, RecursiveMode::Recursive)?;
         Ok(Self {
             rx,
+            debouncer: ReloadDebouncer::new(debounce),
             _watcher: watcher,
         })
     }
-    pub fn poll(&self) -> Option<PathBuf> {
-        self.rx.try_recv().ok()
+
+    /// 收集新事件并返回已经防抖完成的动作
+    pub fn poll(&mut self) -> Vec<ReloadAction> {
+        let now = Instant::now();
+        while let Ok(event) = self.rx.try_recv() {
+            self.debouncer.push(event, now);
+        }
+        self.debouncer.poll(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rapid_modifications_reload_once() {
+        let mut debouncer = ReloadDebouncer::default();
+        let path = PathBuf::from("assets/player.png");
+        let t0 = Instant::now();
+
+        for i in 0..3 {
+            debouncer.push(
+                FsEvent::Modified(path.clone()),
+                t0 + Duration::from_millis(i * 20),
+            );
+        }
+
+        // 仍在静默期内，不应触发
+        assert!(debouncer.poll(t0 + Duration::from_millis(100)).is_empty());
+
+        let actions = debouncer.poll(t0 + Duration::from_millis(300));
+        assert_eq!(actions, vec![ReloadAction::Reload(path)]);
+        assert!(debouncer.poll(t0 + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn test_created_and_modified_coalesce() {
+        let mut debouncer = ReloadDebouncer::new(Duration::from_millis(50));
+        let path = PathBuf::from("assets/level.json");
+        let t0 = Instant::now();
+
+        debouncer.push(FsEvent::Created(path.clone()), t0);
+        debouncer.push(FsEvent::Modified(path.clone()), t0);
+
+        let actions = debouncer.poll(t0 + Duration::from_millis(60));
+        assert_eq!(actions, vec![ReloadAction::Reload(path)]);
+    }
+
+    #[test]
+    fn test_deleted_unloads() {
+        let mut debouncer = ReloadDebouncer::new(Duration::from_millis(50));
+        let path = PathBuf::from("assets/old.png");
+        let t0 = Instant::now();
+
+        debouncer.push(FsEvent::Modified(path.clone()), t0);
+        debouncer.push(
+            FsEvent::Deleted(path.clone()),
+            t0 + Duration::from_millis(10),
+        );
+
+        let actions = debouncer.poll(t0 + Duration::from_millis(100));
+        assert_eq!(actions, vec![ReloadAction::Unload(path)]);
+        assert_eq!(debouncer.pending_count(), 0);
     }
 }
@@ -1,7 +1,7 @@
 use bevy_ecs::prelude::*;
 use std::{
     path::{Path, PathBuf},
-    sync::{Arc, RwLock},
+    sync::{Arc, RwLock, Weak},
     time::Duration,
 };
 // use crossbeam_channel:: {unbounded, Receiver, Sender};
@@ -9,6 +9,7 @@ use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 // use futures::future::FutureExt;
 use super::atlas::Atlas;
+use super::hot_reload::{HotReloadService, ReloadAction};
 use crate::render::wgpu::WgpuRenderer;
 // use super::runtime::global_runtime;
 use std::collections::HashMap;
@@ -40,11 +41,20 @@ pub struct AssetContainer<T> {
     pub state: RwLock<LoadState<T>>,
 }
 
-#[derive(Clone, Component, Debug)]
+#[derive(Component, Debug)]
 pub struct Handle<T: 'static + Send + Sync> {
     pub container: Arc<AssetContainer<T>>,
 }
 
+// 手动实现 Clone，避免 derive 给资源类型本身加上 `T: Clone` 约束
+impl<T: 'static + Send + Sync> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            container: self.container.clone(),
+        }
+    }
+}
+
 impl<T: 'static + Send + Sync> Handle<T> {
     pub fn new_loading() -> Self {
         Self {
@@ -54,6 +64,31 @@ impl<T: 'static + Send + Sync> Handle<T> {
         }
     }
 
+    pub fn new_loaded(value: T) -> Self {
+        Self {
+            container: Arc::new(AssetContainer {
+                state: RwLock::new(LoadState::Loaded(value)),
+            }),
+        }
+    }
+
+    /// 创建不延长资源生命周期的弱句柄
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        WeakHandle {
+            container: Arc::downgrade(&self.container),
+        }
+    }
+
+    /// 当前强引用数量（包括 `AssetManager` 等缓存持有的引用）
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.container)
+    }
+
+    /// 两个句柄是否指向同一资源
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.container, &other.container)
+    }
+
     pub fn get(&self) -> Option<T>
     where
         T: Clone,
@@ -161,6 +196,240 @@ impl<T: 'static + Send + Sync> Handle<T> {
     }
 }
 
+/// 弱句柄：不会延长资源的生命周期，最后一个强句柄释放后无法再升级
+#[derive(Debug)]
+pub struct WeakHandle<T: 'static + Send + Sync> {
+    container: Weak<AssetContainer<T>>,
+}
+
+impl<T: 'static + Send + Sync> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            container: self.container.clone(),
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> WeakHandle<T> {
+    /// 尝试升级为强句柄，资源已被回收时返回 None
+    pub fn upgrade(&self) -> Option<Handle<T>> {
+        self.container
+            .upgrade()
+            .map(|container| Handle { container })
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.container.strong_count() > 0
+    }
+}
+
+// --- Asset Manager (LRU + memory budget) ---
+
+/// 资源淘汰统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvictionStats {
+    /// 累计淘汰的资源数量
+    pub evicted_count: u64,
+    /// 累计淘汰释放的字节数
+    pub evicted_bytes: u64,
+    /// 因剩余资源都被引用或固定而无法满足预算的次数
+    pub budget_overruns: u64,
+}
+
+struct CachedAsset<T: 'static + Send + Sync> {
+    handle: Handle<T>,
+    size_bytes: usize,
+    last_access: u64,
+    pinned: bool,
+}
+
+/// 带内存预算的资源缓存
+///
+/// 按路径缓存资源句柄并记录每个资源的大小与最近访问时间。插入新资源会超出预算时，
+/// 按最近最少使用（LRU）顺序淘汰没有外部强引用且未固定的资源。
+/// 被淘汰的句柄暂存到 `drain_evicted` 取走为止，持有 GPU 资源的调用方据此释放显存。
+#[derive(Resource)]
+pub struct AssetManager<T: 'static + Send + Sync> {
+    entries: HashMap<PathBuf, CachedAsset<T>>,
+    budget_bytes: usize,
+    used_bytes: usize,
+    /// 逻辑时钟，每次访问递增，用作最近访问时间
+    clock: u64,
+    stats: EvictionStats,
+    evicted: Vec<Handle<T>>,
+}
+
+impl<T: 'static + Send + Sync> Default for AssetManager<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            budget_bytes: usize::MAX,
+            used_bytes: 0,
+            clock: 0,
+            stats: EvictionStats::default(),
+            evicted: Vec::new(),
+        }
+    }
+}
+
+impl<T: 'static + Send + Sync> AssetManager<T> {
+    /// 创建不限预算的资源缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建指定字节预算的资源缓存
+    pub fn with_budget(budget_bytes: usize) -> Self {
+        Self {
+            budget_bytes,
+            ..Self::default()
+        }
+    }
+
+    /// 设置字节预算，超出时立即按LRU淘汰
+    pub fn set_budget(&mut self, budget_bytes: usize) {
+        self.budget_bytes = budget_bytes;
+        self.evict_to_fit(0);
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget_bytes
+    }
+
+    /// 当前缓存资源占用的字节数
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    pub fn eviction_stats(&self) -> EvictionStats {
+        self.stats
+    }
+
+    /// 缓存资源，必要时先淘汰旧资源；同一路径已存在时替换
+    pub fn insert(
+        &mut self,
+        path: impl AsRef<Path>,
+        handle: Handle<T>,
+        size_bytes: usize,
+    ) -> Handle<T> {
+        let path = path.as_ref().to_path_buf();
+        let pinned = self.take(&path).is_some_and(|old| old.pinned);
+        self.evict_to_fit(size_bytes);
+
+        let last_access = self.tick();
+        self.used_bytes += size_bytes;
+        self.entries.insert(
+            path,
+            CachedAsset {
+                handle: handle.clone(),
+                size_bytes,
+                last_access,
+                pinned,
+            },
+        );
+        handle
+    }
+
+    /// 更新已缓存资源的大小（例如异步加载完成后才知道实际字节数），必要时淘汰其他资源
+    pub fn resize(&mut self, path: impl AsRef<Path>, size_bytes: usize) -> bool {
+        let now = self.tick();
+        let Some(entry) = self.entries.get_mut(path.as_ref()) else {
+            return false;
+        };
+        self.used_bytes = self.used_bytes - entry.size_bytes + size_bytes;
+        entry.size_bytes = size_bytes;
+        entry.last_access = now;
+        self.evict_to_fit(0);
+        true
+    }
+
+    /// 取走上次调用以来被淘汰的句柄
+    ///
+    /// 返回的句柄通常是资源的最后一个强引用，调用方释放其 GPU 资源后丢弃即可。
+    pub fn drain_evicted(&mut self) -> Vec<Handle<T>> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// 获取资源句柄并刷新其最近访问时间
+    pub fn get(&mut self, path: impl AsRef<Path>) -> Option<Handle<T>> {
+        let now = self.tick();
+        let entry = self.entries.get_mut(path.as_ref())?;
+        entry.last_access = now;
+        Some(entry.handle.clone())
+    }
+
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        self.entries.contains_key(path.as_ref())
+    }
+
+    /// 固定资源，使其不参与淘汰（例如常驻UI字体、默认纹理）
+    pub fn pin(&mut self, path: impl AsRef<Path>) -> bool {
+        self.set_pinned(path.as_ref(), true)
+    }
+
+    /// 取消固定
+    pub fn unpin(&mut self, path: impl AsRef<Path>) -> bool {
+        self.set_pinned(path.as_ref(), false)
+    }
+
+    /// 从缓存中移除资源，返回其句柄
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> Option<Handle<T>> {
+        self.take(path.as_ref()).map(|entry| entry.handle)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn set_pinned(&mut self, path: &Path, pinned: bool) -> bool {
+        match self.entries.get_mut(path) {
+            Some(entry) => {
+                entry.pinned = pinned;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn take(&mut self, path: &Path) -> Option<CachedAsset<T>> {
+        let entry = self.entries.remove(path)?;
+        self.used_bytes -= entry.size_bytes;
+        Some(entry)
+    }
+
+    /// 淘汰资源直到能容纳 `incoming` 字节
+    fn evict_to_fit(&mut self, incoming: usize) {
+        while self.used_bytes.saturating_add(incoming) > self.budget_bytes {
+            // 只有缓存自身持有强引用的资源可以淘汰
+            let victim = self
+                .entries
+                .iter()
+                .filter(|(_, e)| !e.pinned && e.handle.strong_count() == 1)
+                .min_by_key(|(_, e)| e.last_access)
+                .map(|(path, _)| path.clone());
+
+            let Some(path) = victim else {
+                self.stats.budget_overruns += 1;
+                break;
+            };
+            if let Some(entry) = self.take(&path) {
+                self.stats.evicted_count += 1;
+                self.stats.evicted_bytes += entry.size_bytes as u64;
+                self.evicted.push(entry.handle);
+            }
+        }
+    }
+}
+
 // --- Asset Server ---
 
 enum AssetTask {
@@ -196,6 +465,26 @@ pub struct AssetServer {
     rx: mpsc::UnboundedReceiver<(AssetTask, Result<AssetResult, String>)>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     shutdown_tx: Option<oneshot::Sender<()>>,
+    /// 纹理缓存，按解码后的 RGBA 字节数计入预算，淘汰时在 `update` 中释放显存
+    textures: AssetManager<u32>,
+    /// 资源目录监视，启用后在 `update` 中处理防抖后的热重载动作
+    ///
+    /// 事件接收端不是 `Sync`，加锁后才能作为资源保存。
+    hot_reload: Option<std::sync::Mutex<HotReloadService>>,
+    /// 待在 `update` 中从磁盘重新上传的纹理：纹理索引、路径、是否线性
+    pending_reloads: Vec<(u32, PathBuf, bool)>,
+    /// 源文件被删除而移出缓存的纹理，在 `update` 中释放显存
+    unloaded: Vec<Handle<u32>>,
+}
+
+/// 纹理缓存键，线性与 sRGB 纹理是不同的 GPU 资源，线性纹理加 `#linear` 后缀区分
+fn texture_cache_key(path: &Path, is_linear: bool) -> PathBuf {
+    if !is_linear {
+        return path.to_path_buf();
+    }
+    let mut key = path.as_os_str().to_owned();
+    key.push("#linear");
+    PathBuf::from(key)
 }
 
 #[derive(Clone, Debug)]
@@ -309,31 +598,82 @@ impl AssetServer {
             rx: done_rx,
             worker_handle: Some(worker_handle),
             shutdown_tx: Some(shutdown_tx),
+            textures: AssetManager::new(),
+            hot_reload: None,
+            pending_reloads: Vec::new(),
+            unloaded: Vec::new(),
         }
     }
 
-    pub fn load_texture(&self, path: &Path) -> Handle<u32> {
-        let handle = Handle::new_loading();
-        let task = AssetTask::Texture {
-            path: path.to_path_buf(),
-            handle: handle.clone(),
-            is_linear: false,
-            start: std::time::Instant::now(),
-        };
-        let _ = self.tx.send(task);
-        handle
+    /// 监视资源目录，文件变化经防抖后在 `update` 中重新加载或卸载对应纹理
+    pub fn watch_for_changes(&mut self, dir: PathBuf) -> notify::Result<()> {
+        self.hot_reload = Some(std::sync::Mutex::new(HotReloadService::watch_dir(dir)?));
+        Ok(())
+    }
+
+    /// 处理一个热重载动作
+    ///
+    /// 重新加载只作用于已加载完成的纹理，仍在加载中的纹理会直接读到新文件；
+    /// 卸载将纹理移出缓存，之后再次加载会重新读取文件。GPU 端的工作在下次 `update` 中完成。
+    pub fn handle_reload_action(&mut self, action: ReloadAction) {
+        match action {
+            ReloadAction::Reload(path) => {
+                for is_linear in [false, true] {
+                    let tex_id = self
+                        .textures
+                        .get(texture_cache_key(&path, is_linear))
+                        .and_then(|handle| handle.get());
+                    if let Some(tex_id) = tex_id {
+                        self.pending_reloads.push((tex_id, path.clone(), is_linear));
+                    }
+                }
+            }
+            ReloadAction::Unload(path) => {
+                for is_linear in [false, true] {
+                    if let Some(handle) = self.textures.remove(texture_cache_key(&path, is_linear))
+                    {
+                        self.unloaded.push(handle);
+                    }
+                }
+            }
+        }
     }
 
-    pub fn load_texture_linear(&self, path: &Path) -> Handle<u32> {
+    /// 纹理缓存，可用于设置显存预算或固定常驻纹理
+    pub fn textures(&self) -> &AssetManager<u32> {
+        &self.textures
+    }
+
+    pub fn textures_mut(&mut self) -> &mut AssetManager<u32> {
+        &mut self.textures
+    }
+
+    /// 加载 sRGB 纹理，同一路径已缓存时直接返回已有句柄
+    pub fn load_texture(&mut self, path: &Path) -> Handle<u32> {
+        self.load_texture_cached(path, false)
+    }
+
+    /// 加载线性空间纹理（法线、粗糙度等数据贴图）
+    pub fn load_texture_linear(&mut self, path: &Path) -> Handle<u32> {
+        self.load_texture_cached(path, true)
+    }
+
+    fn load_texture_cached(&mut self, path: &Path, is_linear: bool) -> Handle<u32> {
+        let key = texture_cache_key(path, is_linear);
+        if let Some(handle) = self.textures.get(&key) {
+            return handle;
+        }
+
         let handle = Handle::new_loading();
         let task = AssetTask::Texture {
             path: path.to_path_buf(),
             handle: handle.clone(),
-            is_linear: true,
+            is_linear,
             start: std::time::Instant::now(),
         };
         let _ = self.tx.send(task);
-        handle
+        // 解码前不知道大小，先按 0 字节登记，加载完成后在 `update` 中更新
+        self.textures.insert(key, handle, 0)
     }
 
     pub fn load_atlas(&self, path: &Path) -> Handle<Atlas> {
@@ -361,15 +701,38 @@ impl AssetServer {
 
     // This must be called in the main thread loop
     pub fn update(&mut self, renderer: &mut WgpuRenderer) -> Vec<AssetEvent> {
+        if let Some(service) = self.hot_reload.as_mut() {
+            let actions = service.get_mut().unwrap_or_else(|e| e.into_inner()).poll();
+            for action in actions {
+                self.handle_reload_action(action);
+            }
+        }
+        for (tex_id, path, is_linear) in std::mem::take(&mut self.pending_reloads) {
+            if renderer
+                .reload_texture_file_at(tex_id, &path, is_linear)
+                .is_none()
+            {
+                log::warn!("Failed to reload texture {}", path.display());
+            }
+        }
+        // 其他地方仍持有的纹理保留显存，由持有者继续使用
+        for handle in std::mem::take(&mut self.unloaded) {
+            if handle.strong_count() == 1 {
+                if let Some(tex_id) = handle.get() {
+                    renderer.release_texture(tex_id);
+                }
+            }
+        }
+
         let mut events = Vec::new();
         while let Ok((task, result)) = self.rx.try_recv() {
             match (task, result) {
                 (
                     AssetTask::Texture {
+                        path,
                         handle,
                         is_linear,
                         start,
-                        ..
                     },
                     Ok(AssetResult::Image(img)),
                 ) => {
@@ -377,15 +740,19 @@ impl AssetServer {
                         .duration_since(start)
                         .as_secs_f32()
                         * 1000.0;
+                    let key = texture_cache_key(&path, is_linear);
+                    let size_bytes = img.as_raw().len();
                     if let Some(tex_id) = renderer.load_texture_from_image(img, is_linear) {
                         if let Ok(mut state) = handle.container.state.write() {
                             *state = LoadState::Loaded(tex_id);
                         } // ✅ 处理锁中毒情况，忽略更新失败
+                        self.textures.resize(&key, size_bytes);
                         events.push(AssetEvent::TextureLoaded(handle.clone(), ms));
                     } else {
                         if let Ok(mut state) = handle.container.state.write() {
                             *state = LoadState::Failed("Failed to create texture".to_string());
                         } // ✅ 处理锁中毒情况，忽略更新失败
+                        self.textures.remove(&key);
                         events.push(AssetEvent::TextureFailed(
                             handle.clone(),
                             "Failed to create texture".to_string(),
@@ -433,7 +800,17 @@ impl AssetServer {
                     } // ✅ 处理锁中毒情况，忽略更新失败
                     events.push(AssetEvent::GltfLoaded(handle.clone(), ms));
                 }
-                (AssetTask::Texture { handle, .. }, Err(e)) => {
+                (
+                    AssetTask::Texture {
+                        path,
+                        handle,
+                        is_linear,
+                        ..
+                    },
+                    Err(e),
+                ) => {
+                    // 失败的纹理不留在缓存中，之后再次加载会重试
+                    self.textures.remove(texture_cache_key(&path, is_linear));
                     if let Ok(mut state) = handle.container.state.write() {
                         *state = LoadState::Failed(e.clone());
                     } // ✅ 处理锁中毒情况，忽略更新失败
@@ -455,6 +832,13 @@ impl AssetServer {
                 _ => {}
             }
         }
+
+        // 超出预算被淘汰的纹理没有其他引用，释放其显存
+        for handle in self.textures.drain_evicted() {
+            if let Some(tex_id) = handle.get() {
+                renderer.release_texture(tex_id);
+            }
+        }
         events
     }
 
@@ -501,6 +885,8 @@ pub fn import_gltf_to_world(
         };
         let _sampler = renderer.create_sampler();
         // sampler用于纹理采样，在创建材质绑定组时使用（当前通过pbr内部管理）
+        // glTF 材质索引 -> 注册表分配的材质 ID，同一文档内的图元共享材质
+        let mut material_ids: HashMap<Option<usize>, u64> = HashMap::new();
 
         for mesh in doc.meshes() {
             for primitive in mesh.primitives() {
@@ -533,7 +919,7 @@ pub fn import_gltf_to_world(
                     .and_then(|tc| Some(tc.into_f32()))
                     .map(|it| it.collect())
                     .unwrap_or_else(|| vec![[0.0, 0.0]; positions.len()]);
-                let mut tangents: Vec<[f32; 4]> = reader
+                let tangents: Vec<[f32; 4]> = reader
                     .read_tangents()
                     .map(|it| it.collect())
                     .unwrap_or_default();
@@ -542,19 +928,18 @@ pub fn import_gltf_to_world(
                     .map(|r| r.into_u32().collect())
                     .unwrap_or_else(|| (0..positions.len() as u32).collect());
 
-                if tangents.is_empty() {
-                    tangents = generate_tangents(&positions, &normals, &uvs, &indices);
-                }
-
                 let mut vertices = Vec::with_capacity(positions.len());
                 for i in 0..positions.len() {
                     vertices.push(crate::render::mesh::Vertex3D {
                         pos: positions[i],
                         normal: normals[i],
                         uv: uvs[i],
-                        tangent: tangents[i],
+                        tangent: tangents.get(i).copied().unwrap_or([0.0; 4]),
                     });
                 }
+                if tangents.is_empty() {
+                    crate::render::mesh::generate_tangents(&mut vertices, &indices);
+                }
                 let gpu_mesh = renderer.create_gpu_mesh(&vertices, &indices);
 
                 // 构建纹理绑定组（五贴图）并持久化纹理
@@ -672,9 +1057,11 @@ pub fn import_gltf_to_world(
                 }
 
                 // 材质注册与复用
-                let mat_id = primitive.material().index().unwrap_or(0) as u64;
                 let mut registry =
                     world.get_resource_or_insert_with::<MaterialRegistry>(Default::default);
+                let mat_id = *material_ids
+                    .entry(primitive.material().index())
+                    .or_insert_with(|| registry.allocate_id());
                 let (material_bg, material_buf) = if let Some((bg, buf, tex)) =
                     registry.materials.get(&mat_id)
                 {
@@ -735,40 +1122,6 @@ fn to_rgba(data: &gltf::image::Data) -> image::RgbaImage {
     }
 }
 
-fn generate_tangents(
-    positions: &[[f32; 3]],
-    normals: &[[f32; 3]],
-    uvs: &[[f32; 2]],
-    indices: &[u32],
-) -> Vec<[f32; 4]> {
-    let mut tangents = vec![[0.0f32; 4]; positions.len()];
-    for tri in indices.chunks(3) {
-        if tri.len() < 3 {
-            continue;
-        }
-        let i0 = tri[0] as usize;
-        let i1 = tri[1] as usize;
-        let i2 = tri[2] as usize;
-        let p0 = glam::Vec3::from_array(positions[i0]);
-        let p1 = glam::Vec3::from_array(positions[i1]);
-        let p2 = glam::Vec3::from_array(positions[i2]);
-        let uv0 = glam::Vec2::from_array(uvs[i0]);
-        let uv1 = glam::Vec2::from_array(uvs[i1]);
-        let uv2 = glam::Vec2::from_array(uvs[i2]);
-        let dp1 = p1 - p0;
-        let dp2 = p2 - p0;
-        let duv1 = uv1 - uv0;
-        let duv2 = uv2 - uv0;
-        let r = 1.0 / (duv1.x * duv2.y - duv1.y * duv2.x);
-        let t = (dp1 * duv2.y - dp2 * duv1.y) * r;
-        let n0 = glam::Vec3::from_array(normals[i0]);
-        let t0 = (t - n0 * n0.dot(t)).normalize_or_zero();
-        tangents[i0] = [t0.x, t0.y, t0.z, 1.0];
-        tangents[i1] = tangents[i0];
-        tangents[i2] = tangents[i0];
-    }
-    tangents
-}
 #[derive(Resource, Default)]
 pub struct MaterialRegistry {
     pub materials: HashMap<
@@ -779,6 +1132,25 @@ pub struct MaterialRegistry {
             std::sync::Arc<wgpu::BindGroup>, // textures BG
         ),
     >,
+    /// 命名材质：名称 -> 稳定的材质 ID
+    names: HashMap<String, u64>,
+    /// 材质参数（CPU 侧副本）
+    params: HashMap<u64, crate::render::pbr::PbrMaterial>,
+    /// 参数已变更、尚未上传到 GPU 的材质
+    dirty: Vec<u64>,
+    /// 已变更、尚未由 `material_changed_system` 发送事件的材质
+    changed: Vec<u64>,
+    /// 下一个分配的材质 ID，命名材质与 glTF 导入共用
+    next_id: u64,
+}
+
+/// 材质变更事件，渲染器据此使缓存失效
+///
+/// 由 `material_changed_system` 发送到 `Events<MaterialChanged>`。
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct MaterialChanged {
+    pub id: u64,
+    pub name: Option<String>,
 }
 
 #[derive(Resource, Default)]
@@ -796,6 +1168,93 @@ impl MaterialPendingUpdates {
 }
 
 impl MaterialRegistry {
+    /// 插入或更新命名材质，同名材质始终返回同一个 ID
+    pub fn insert_named(&mut self, name: &str, material: crate::render::pbr::PbrMaterial) -> u64 {
+        let id = match self.names.get(name) {
+            Some(&id) => id,
+            None => {
+                let id = self.allocate_id();
+                self.names.insert(name.to_string(), id);
+                id
+            }
+        };
+        self.params.insert(id, material);
+        self.mark_changed(id);
+        id
+    }
+
+    /// 按名称更新材质参数，名称不存在时返回 None
+    pub fn update_named(
+        &mut self,
+        name: &str,
+        material: crate::render::pbr::PbrMaterial,
+    ) -> Option<u64> {
+        let id = *self.names.get(name)?;
+        self.params.insert(id, material);
+        self.mark_changed(id);
+        Some(id)
+    }
+
+    pub fn id_of(&self, name: &str) -> Option<u64> {
+        self.names.get(name).copied()
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&crate::render::pbr::PbrMaterial> {
+        self.names.get(name).and_then(|id| self.params.get(id))
+    }
+
+    pub fn params(&self, id: u64) -> Option<&crate::render::pbr::PbrMaterial> {
+        self.params.get(&id)
+    }
+
+    /// 分配新的材质 ID
+    pub fn allocate_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// 将命名接口修改过的参数上传到 GPU
+    pub fn sync_dirty(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        pbr: &crate::render::pbr_renderer::PbrRenderer,
+    ) {
+        for id in std::mem::take(&mut self.dirty) {
+            if let Some(mat) = self.params.get(&id).cloned() {
+                self.upload_params(device, queue, pbr, id, &mat);
+            }
+        }
+    }
+
+    fn name_of(&self, id: u64) -> Option<String> {
+        self.names
+            .iter()
+            .find(|(_, &v)| v == id)
+            .map(|(k, _)| k.clone())
+    }
+
+    fn mark_changed(&mut self, id: u64) {
+        if !self.dirty.contains(&id) {
+            self.dirty.push(id);
+        }
+        self.changed.push(id);
+    }
+
+    /// 释放不再被任何渲染组件引用的材质，返回释放数量
+    ///
+    /// 渲染组件持有绑定组的克隆，若注册表是唯一持有者则视为未使用。
+    pub fn cleanup_unused(&mut self) -> usize {
+        let before = self.materials.len();
+        self.materials.retain(|_, (bg, buf, tex)| {
+            std::sync::Arc::strong_count(bg) > 1
+                || std::sync::Arc::strong_count(buf) > 1
+                || std::sync::Arc::strong_count(tex) > 1
+        });
+        before - self.materials.len()
+    }
+
     pub fn update_material_params(
         &mut self,
         device: &wgpu::Device,
@@ -803,6 +1262,19 @@ impl MaterialRegistry {
         pbr: &crate::render::pbr_renderer::PbrRenderer,
         mat_id: u64,
         mat: &crate::render::pbr::PbrMaterial,
+    ) -> bool {
+        self.params.insert(mat_id, mat.clone());
+        self.changed.push(mat_id);
+        self.upload_params(device, queue, pbr, mat_id, mat)
+    }
+
+    fn upload_params(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        pbr: &crate::render::pbr_renderer::PbrRenderer,
+        mat_id: u64,
+        mat: &crate::render::pbr::PbrMaterial,
     ) -> bool {
         if let Some((bg, buf, tex)) = self.materials.get_mut(&mat_id) {
             let uniform = crate::render::pbr_renderer::PbrRenderer::encode_material_uniform(mat);
@@ -813,7 +1285,8 @@ impl MaterialRegistry {
             // bind group布局不变，无需重建
             true
         } else {
-            // 创建并登记
+            // 创建并登记；外部指定的新 ID 也推进计数器，避免之后分配到同一 ID
+            self.next_id = self.next_id.max(mat_id + 1);
             let (new_bg, new_buf) = pbr.create_material_bind_group(device, queue, mat);
             let new_tex = wgpu_dummy_bg(device, &pbr.textures_bgl);
             self.materials
@@ -843,6 +1316,27 @@ impl MaterialRegistry {
     }
 }
 
+/// 将注册表记录的材质变更发送为 `MaterialChanged` 事件
+pub fn material_changed_system(
+    mut registry: ResMut<MaterialRegistry>,
+    mut events: ResMut<crate::ecs::Events<MaterialChanged>>,
+) {
+    for id in std::mem::take(&mut registry.changed) {
+        let name = registry.name_of(id);
+        events.send(MaterialChanged { id, name });
+    }
+}
+
+/// 查询使用指定材质的所有实体
+pub fn material_dependents(world: &mut World, material_id: u64) -> Vec<Entity> {
+    let mut query = world.query::<(Entity, &crate::render::instance_batch::Mesh3DRenderer)>();
+    query
+        .iter(world)
+        .filter(|(_, renderer)| renderer.material_id == material_id)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
 fn wgpu_dummy_bg(
     device: &wgpu::Device,
     layout: &wgpu::BindGroupLayout,
@@ -1,8 +1,39 @@
 use std::path::Path;
+use thiserror::Error;
 
 pub mod runtime;
 pub use runtime::{block_on, global_runtime, spawn};
 
+/// 资源字节加载错误
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AssetLoadError {
+    /// 文件或URL不存在
+    #[error("Asset not found: {0}")]
+    NotFound(String),
+    /// 本地IO错误（包括权限不足、IndexedDB访问失败等）
+    #[error("IO error: {0}")]
+    Io(String),
+    /// 网络请求失败
+    #[error("Network error: {0}")]
+    Network(String),
+    /// 数据解码失败
+    #[error("Decode error: {0}")]
+    Decode(String),
+}
+
+impl AssetLoadError {
+    /// 将 `std::io::Error` 映射为对应的错误变体
+    pub fn from_io(path: &Path, err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::NotFound => Self::NotFound(path.display().to_string()),
+            std::io::ErrorKind::PermissionDenied => {
+                Self::Io(format!("Permission denied: {}", path.display()))
+            }
+            _ => Self::Io(format!("{}: {}", path.display(), err)),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct AssetLoader;
 
@@ -11,23 +42,29 @@ impl AssetLoader {
         Self::default()
     }
     #[cfg(not(target_arch = "wasm32"))]
-    pub async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
-        std::fs::read(path).map_err(|_| ())
+    pub async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>, AssetLoadError> {
+        std::fs::read(path).map_err(|e| AssetLoadError::from_io(path, e))
     }
     #[cfg(target_arch = "wasm32")]
-    pub async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>, ()> {
+    pub async fn load_bytes(&self, path: &Path) -> Result<Vec<u8>, AssetLoadError> {
         use wasm_bindgen::JsCast;
         use wasm_bindgen_futures::JsFuture;
-        let win = web_sys::window().ok_or(())?;
+        let no_window = || AssetLoadError::Network("No window object".to_string());
+        let win = web_sys::window().ok_or_else(no_window)?;
         // IndexedDB 读取封装，命中则直接返回
-        async fn idb_get_bytes(url: &str) -> Result<Option<Vec<u8>>, ()> {
+        async fn idb_get_bytes(url: &str) -> Result<Option<Vec<u8>>, AssetLoadError> {
             use wasm_bindgen::JsCast;
-            let win = web_sys::window().ok_or(())?;
-            let factory = win.indexed_db().map_err(|_| ())?;
+            let win = web_sys::window()
+                .ok_or_else(|| AssetLoadError::Network("No window object".to_string()))?;
+            let factory = win
+                .indexed_db()
+                .map_err(|e| AssetLoadError::Io(format!("IndexedDB unavailable: {:?}", e)))?;
             let Some(factory) = factory else {
                 return Ok(None);
             };
-            let req = factory.open_with_u32("assets-cache", 1).ok_or(())?;
+            let req = factory
+                .open_with_u32("assets-cache", 1)
+                .map_err(|e| AssetLoadError::Io(format!("Failed to open IndexedDB: {:?}", e)))?;
             // onupgradeneeded: create store if missing
             {
                 let closure =
@@ -109,8 +146,8 @@ impl AssetLoader {
                 req.set_onsuccess(Some(closure.as_ref().unchecked_ref()));
                 closure.forget();
             }
-            let res = rx.await.map_err(|_| ())?;
-            Ok(res)
+            // 打开或读取失败时发送端被丢弃，视为缓存未命中
+            Ok(rx.await.unwrap_or(None))
         }
         if let Some(bytes) = idb_get_bytes(&path.to_string_lossy()).await? {
             return Ok(bytes);
@@ -120,47 +157,44 @@ impl AssetLoader {
             if let Ok(promise) = caches.match_with_str(&path.to_string_lossy()) {
                 if let Ok(resp_val) = JsFuture::from(promise).await {
                     if !resp_val.is_undefined() && !resp_val.is_null() {
-                        let resp: web_sys::Response = resp_val.dyn_into().map_err(|_| ())?;
-                        if !resp.ok() {
-                            return Err(());
-                        }
-                        let buf_promise = resp.array_buffer().map_err(|_| ())?;
-                        let buf = JsFuture::from(buf_promise).await.map_err(|_| ())?;
-                        let u8arr = js_sys::Uint8Array::new(&buf);
-                        let mut v = vec![0u8; u8arr.length() as usize];
-                        u8arr.copy_to(&mut v[..]);
-                        return Ok(v);
+                        let resp: web_sys::Response = resp_val.dyn_into().map_err(|_| {
+                            AssetLoadError::Network("Invalid cached response".to_string())
+                        })?;
+                        check_status(&resp, path)?;
+                        return read_response_bytes(&resp).await;
                     }
                 }
             }
         }
-        if let Some(storage) = win.local_storage().map_err(|_| ())? {
-            if let Some(k) = storage.get_item(&path.to_string_lossy()).map_err(|_| ())? {
-                let bytes = base64::decode(k).map_err(|_| ())?;
+        let storage_error = |e: wasm_bindgen::JsValue| {
+            AssetLoadError::Io(format!("localStorage unavailable: {:?}", e))
+        };
+        if let Some(storage) = win.local_storage().map_err(storage_error)? {
+            if let Some(k) = storage
+                .get_item(&path.to_string_lossy())
+                .map_err(storage_error)?
+            {
+                let bytes = base64::decode(k).map_err(|e| AssetLoadError::Decode(e.to_string()))?;
                 return Ok(bytes);
             }
         }
         let url = path.to_string_lossy();
         let resp_val = JsFuture::from(win.fetch_with_str(&url))
             .await
-            .map_err(|_| ())?;
-        let resp: web_sys::Response = resp_val.dyn_into().map_err(|_| ())?;
-        if !resp.ok() {
-            return Err(());
-        }
+            .map_err(|e| AssetLoadError::Network(format!("Fetch failed: {:?}", e)))?;
+        let resp: web_sys::Response = resp_val
+            .dyn_into()
+            .map_err(|_| AssetLoadError::Network("Invalid response".to_string()))?;
+        check_status(&resp, path)?;
         let ctype = resp
             .headers()
             .get("Content-Type")
-            .map_err(|_| ())?
+            .map_err(|e| AssetLoadError::Network(format!("Invalid headers: {:?}", e)))?
             .unwrap_or_default();
-        let buf_promise = resp.array_buffer().map_err(|_| ())?;
-        let buf = JsFuture::from(buf_promise).await.map_err(|_| ())?;
-        let u8arr = js_sys::Uint8Array::new(&buf);
-        let mut v = vec![0u8; u8arr.length() as usize];
-        u8arr.copy_to(&mut v[..]);
-        // IndexedDB 持久缓存写入
-        if let Some(factory) = win.indexed_db().map_err(|_| ())? {
-            if let Some(dbreq) = factory.open_with_u32("assets-cache", 1) {
+        let v = read_response_bytes(&resp).await?;
+        // IndexedDB 持久缓存写入（失败不影响本次加载）
+        if let Ok(Some(factory)) = win.indexed_db() {
+            if let Ok(dbreq) = factory.open_with_u32("assets-cache", 1) {
                 let onup = {
                     let closure =
                         wasm_bindgen::closure::Closure::wrap(Box::new(move |e: web_sys::Event| {
@@ -231,7 +265,7 @@ impl AssetLoader {
                 let _ = (onup, onok);
             }
         }
-        if let Some(storage) = win.local_storage().map_err(|_| ())? {
+        if let Ok(Some(storage)) = win.local_storage() {
             // 按类型选择是否缓存
             let cacheable = ctype.starts_with("image/") || ctype.starts_with("application/json");
             if cacheable {
@@ -242,6 +276,32 @@ impl AssetLoader {
         Ok(v)
     }
 }
+
+/// 将HTTP错误状态映射为对应的错误变体
+#[cfg(target_arch = "wasm32")]
+fn check_status(resp: &web_sys::Response, path: &Path) -> Result<(), AssetLoadError> {
+    match resp.status() {
+        _ if resp.ok() => Ok(()),
+        404 | 410 => Err(AssetLoadError::NotFound(path.display().to_string())),
+        status => Err(AssetLoadError::Network(format!("HTTP {}", status))),
+    }
+}
+
+/// 读取响应体的全部字节
+#[cfg(target_arch = "wasm32")]
+async fn read_response_bytes(resp: &web_sys::Response) -> Result<Vec<u8>, AssetLoadError> {
+    let buf_promise = resp
+        .array_buffer()
+        .map_err(|e| AssetLoadError::Network(format!("Failed to get array buffer: {:?}", e)))?;
+    let buf = wasm_bindgen_futures::JsFuture::from(buf_promise)
+        .await
+        .map_err(|e| AssetLoadError::Network(format!("Failed to read array buffer: {:?}", e)))?;
+    let u8arr = js_sys::Uint8Array::new(&buf);
+    let mut v = vec![0u8; u8arr.length() as usize];
+    u8arr.copy_to(&mut v[..]);
+    Ok(v)
+}
+
 pub mod atlas;
 pub mod coroutine_loader;
 pub mod events;
@@ -19,9 +19,11 @@
 //! │                                                          │
 //! │  3. 回收机制                                              │
 //! │     - 帧结束后回收已完成的 Staging Buffer                  │
+//! │     - 空闲超过 N 帧的缓冲区释放，保留最少数量              │
 //! └─────────────────────────────────────────────────────────┘
 //! ```
 
+use crate::render::gpu_memory::{GpuAllocation, GpuMemoryTracker};
 use std::collections::VecDeque;
 
 // ============================================================================
@@ -37,6 +39,12 @@ const SHARED_STAGING_SIZE: u64 = 4 * 1024 * 1024;
 /// 最大保留的空闲缓冲区数量
 const MAX_FREE_BUFFERS: usize = 8;
 
+/// 默认空闲帧数阈值，超过后缓冲区可被释放
+const DEFAULT_IDLE_FRAMES: u64 = 120;
+
+/// 默认最少保留的空闲缓冲区数量
+const DEFAULT_MIN_RETAINED: usize = 2;
+
 // ============================================================================
 // Staging Buffer
 // ============================================================================
@@ -49,22 +57,41 @@ pub struct StagingBuffer {
     pub size: u64,
     /// 当前写入偏移
     pub offset: u64,
+    /// 进入空闲池后首次被 `collect_idle` 观察到的帧号
+    pub(crate) idle_since: Option<u64>,
+    /// 显存登记，随缓冲区释放
+    _allocation: GpuAllocation,
 }
 
 impl StagingBuffer {
     /// 创建新的 Staging Buffer
     pub fn new(device: &wgpu::Device, size: u64, label: Option<&str>) -> Self {
-        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label,
-            size,
-            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: true,
-        });
+        Self::with_tracker(device, size, label, GpuMemoryTracker::global())
+    }
+
+    /// 创建新的 Staging Buffer，显存登记到指定统计器
+    pub(crate) fn with_tracker(
+        device: &wgpu::Device,
+        size: u64,
+        label: Option<&str>,
+        tracker: &'static GpuMemoryTracker,
+    ) -> Self {
+        let (buffer, allocation) = tracker.create_buffer(
+            device,
+            &wgpu::BufferDescriptor {
+                label,
+                size,
+                usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: true,
+            },
+        );
 
         Self {
             buffer,
             size,
             offset: 0,
+            idle_since: None,
+            _allocation: allocation,
         }
     }
 
@@ -127,10 +154,11 @@ pub struct PoolStats {
     pub active_buffers: u32,
     /// 复用次数
     pub reuse_count: u64,
+    /// 已释放的空闲缓冲区数
+    pub buffers_freed: u64,
 }
 
 /// Staging Buffer 池 - 管理多个 Staging Buffer
-#[derive(Default)]
 pub struct StagingBufferPool {
     /// 共享 Staging Buffer（用于小数据）
     pub(crate) shared_buffer: Option<StagingBuffer>,
@@ -140,6 +168,23 @@ pub struct StagingBufferPool {
     pub(crate) free_buffers: VecDeque<StagingBuffer>,
     /// 统计信息
     pub(crate) stats: PoolStats,
+    /// 空闲多少帧后可释放
+    pub(crate) idle_frames: u64,
+    /// 最少保留的空闲缓冲区数量
+    pub(crate) min_retained: usize,
+}
+
+impl Default for StagingBufferPool {
+    fn default() -> Self {
+        Self {
+            shared_buffer: None,
+            dedicated_buffers: Vec::new(),
+            free_buffers: VecDeque::new(),
+            stats: PoolStats::default(),
+            idle_frames: DEFAULT_IDLE_FRAMES,
+            min_retained: DEFAULT_MIN_RETAINED,
+        }
+    }
 }
 
 impl StagingBufferPool {
@@ -147,6 +192,12 @@ impl StagingBufferPool {
         Self::default()
     }
 
+    /// 设置空闲回收策略：空闲 `idle_frames` 帧后释放，至少保留 `min_retained` 个
+    pub fn set_idle_policy(&mut self, idle_frames: u64, min_retained: usize) {
+        self.idle_frames = idle_frames;
+        self.min_retained = min_retained;
+    }
+
     /// 初始化共享缓冲区
     pub fn initialize(&mut self, device: &wgpu::Device) {
         if self.shared_buffer.is_none() {
@@ -182,6 +233,7 @@ impl StagingBufferPool {
         if let Some(mut buffer) = self.free_buffers.pop_front() {
             if buffer.size >= size {
                 buffer.reset();
+                buffer.idle_since = None;
                 let index = self.dedicated_buffers.len() + 1;
                 self.dedicated_buffers.push(buffer);
                 self.stats.reuse_count += 1;
@@ -252,13 +304,46 @@ impl StagingBufferPool {
         for buffer in self.dedicated_buffers.drain(..) {
             if self.free_buffers.len() < MAX_FREE_BUFFERS {
                 self.free_buffers.push_back(buffer);
+            } else {
+                // 超出限制的直接丢弃
+                self.stats.buffers_freed += 1;
             }
-            // 超出限制的直接丢弃
         }
 
         self.stats.active_buffers = 0;
     }
 
+    /// 释放空闲过久的缓冲区，返回释放数量
+    ///
+    /// 应每帧调用一次。缓冲区进入空闲池后连续 `idle_frames` 帧未被复用即可释放，
+    /// 但空闲池中至少保留 `min_retained` 个，以应对下一次上传高峰。
+    pub fn collect_idle(&mut self, current_frame: u64) -> usize {
+        let mut remaining = self.free_buffers.len();
+        let mut retained = VecDeque::with_capacity(remaining);
+        let mut freed = 0;
+
+        // 队首是最早回收的缓冲区，优先释放
+        for mut buffer in self.free_buffers.drain(..) {
+            let idle_since = *buffer.idle_since.get_or_insert(current_frame);
+            let expired = current_frame.saturating_sub(idle_since) >= self.idle_frames;
+            if expired && remaining > self.min_retained {
+                remaining -= 1;
+                freed += 1;
+            } else {
+                retained.push_back(buffer);
+            }
+        }
+
+        self.free_buffers = retained;
+        self.stats.buffers_freed += freed as u64;
+        freed
+    }
+
+    /// 空闲池中的缓冲区数量
+    pub fn free_buffer_count(&self) -> usize {
+        self.free_buffers.len()
+    }
+
     /// 获取统计信息
     pub fn stats(&self) -> PoolStats {
         self.stats
@@ -307,4 +392,60 @@ mod tests {
         assert_eq!(stats.total_allocations, 0);
         assert_eq!(stats.total_bytes_uploaded, 0);
     }
+
+    #[test]
+    fn test_staging_buffer_memory_tracking() {
+        use crate::render::gpu_memory::GpuMemoryStats;
+
+        // 独立统计器，不受并行测试中其他分配的影响
+        static TRACKER: GpuMemoryTracker = GpuMemoryTracker::new();
+
+        let Some((device, _queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let buffer = StagingBuffer::with_tracker(&device, 4096, Some("Tracked Staging"), &TRACKER);
+        assert_eq!(
+            TRACKER.stats(),
+            GpuMemoryStats {
+                bytes_used: 4096,
+                buffer_count: 1,
+                texture_count: 0,
+            }
+        );
+
+        drop(buffer);
+        assert_eq!(TRACKER.stats(), GpuMemoryStats::default());
+    }
+
+    #[test]
+    fn test_collect_idle_shrinks_to_minimum() {
+        let Some((device, _queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut pool = StagingBufferPool::new();
+        pool.set_idle_policy(3, 2);
+
+        // 一次上传高峰分配了 6 个独立缓冲区
+        for _ in 0..6 {
+            pool.allocate(&device, SMALL_BUFFER_THRESHOLD * 2, 4);
+        }
+        pool.end_frame(&device);
+        assert_eq!(pool.free_buffer_count(), 6);
+
+        // 空闲未满 3 帧时不释放
+        for frame in 1..=3 {
+            assert_eq!(pool.collect_idle(frame), 0);
+        }
+
+        // 空闲满 3 帧后释放到最少保留数量
+        assert_eq!(pool.collect_idle(4), 4);
+        assert_eq!(pool.free_buffer_count(), 2);
+        assert_eq!(pool.stats().buffers_freed, 4);
+
+        // 已到最少保留数量，不再继续释放
+        assert_eq!(pool.collect_idle(100), 0);
+        assert_eq!(pool.free_buffer_count(), 2);
+    }
 }
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::resources::manager::{AssetEvent, Handle, LoadState};
+    use crate::render::pbr::PbrMaterial;
+    use crate::resources::manager::{
+        AssetEvent, Handle, LoadState, MaterialChanged, MaterialRegistry,
+    };
     use std::sync::{Arc, RwLock};
 
     // Mock Handle for testing purposes
@@ -47,4 +50,207 @@ mod tests {
             panic!("Expected TextureFailed event");
         }
     }
+
+    #[test]
+    fn test_weak_handle_does_not_keep_asset_alive() {
+        let handle = Handle::new_loaded(7u32);
+        let weak = handle.downgrade();
+        let extra = handle.clone();
+        assert_eq!(handle.strong_count(), 2);
+
+        // 仍有强引用时可以升级
+        drop(handle);
+        assert!(weak.is_alive());
+        assert_eq!(weak.upgrade().and_then(|h| h.get()), Some(7));
+
+        drop(extra);
+        assert!(!weak.is_alive());
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_named_material_update_keeps_id_and_notifies() {
+        use crate::ecs::Events;
+        use crate::resources::manager::material_changed_system;
+        use bevy_ecs::prelude::{Schedule, World};
+
+        let mut world = World::new();
+        world.insert_resource(MaterialRegistry::default());
+        world.init_resource::<Events<MaterialChanged>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(material_changed_system);
+
+        let mut registry = world.resource_mut::<MaterialRegistry>();
+        let id = registry.insert_named("brick", PbrMaterial::default());
+        // 与 glTF 导入共用计数器，不会分配到已用的 ID
+        assert_ne!(registry.allocate_id(), id);
+        schedule.run(&mut world);
+        world.resource_mut::<Events<MaterialChanged>>().clear();
+
+        let updated = PbrMaterial {
+            roughness: 0.9,
+            ..Default::default()
+        };
+        let mut registry = world.resource_mut::<MaterialRegistry>();
+        assert_eq!(registry.update_named("brick", updated.clone()), Some(id));
+        assert_eq!(registry.insert_named("brick", updated.clone()), id);
+        assert_eq!(registry.get_by_name("brick"), Some(&updated));
+        assert!(registry.update_named("missing", updated).is_none());
+        assert!(world.resource::<Events<MaterialChanged>>().is_empty());
+
+        schedule.run(&mut world);
+        let events: Vec<_> = world
+            .resource_mut::<Events<MaterialChanged>>()
+            .drain()
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            MaterialChanged {
+                id,
+                name: Some("brick".to_string()),
+            }
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_load_bytes_missing_file_is_not_found() {
+        use crate::resources::{block_on, AssetLoadError, AssetLoader};
+
+        let loader = AssetLoader::new();
+        let path = std::env::temp_dir().join("game_engine_missing_asset.bin");
+        let result = block_on(loader.load_bytes(&path));
+        assert!(matches!(result, Err(AssetLoadError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_asset_manager_evicts_least_recently_used() {
+        use crate::resources::manager::AssetManager;
+
+        // 预算只够容纳两个 100 字节的资源
+        let mut manager = AssetManager::<u32>::with_budget(200);
+        manager.insert("a.png", Handle::new_loaded(1), 100);
+        manager.insert("b.png", Handle::new_loaded(2), 100);
+
+        // 访问 a 后 b 成为最近最少使用的资源
+        assert!(manager.get("a.png").is_some());
+        manager.insert("c.png", Handle::new_loaded(3), 100);
+
+        assert!(manager.contains("a.png"));
+        assert!(!manager.contains("b.png"));
+        assert!(manager.contains("c.png"));
+        assert_eq!(manager.used_bytes(), 200);
+        assert_eq!(manager.eviction_stats().evicted_count, 1);
+        assert_eq!(manager.eviction_stats().evicted_bytes, 100);
+
+        // 固定的资源和仍被外部引用的资源不会被淘汰
+        manager.pin("a.png");
+        let _held = manager.get("c.png").unwrap();
+        manager.insert("d.png", Handle::new_loaded(4), 100);
+        assert!(manager.contains("a.png"));
+        assert!(manager.contains("c.png"));
+        assert_eq!(manager.eviction_stats().budget_overruns, 1);
+
+        // 缩小预算时立即淘汰
+        manager.unpin("a.png");
+        manager.set_budget(100);
+        assert!(!manager.contains("a.png"));
+        assert!(manager.contains("c.png"));
+    }
+
+    #[test]
+    fn test_asset_manager_resize_queues_evicted_handles() {
+        use crate::resources::manager::AssetManager;
+
+        let mut manager = AssetManager::<u32>::with_budget(200);
+        // 加载中的资源先按 0 字节登记，完成后再更新实际大小
+        manager.insert("a.png", Handle::new_loaded(1), 0);
+        manager.insert("b.png", Handle::new_loaded(2), 150);
+        assert!(manager.drain_evicted().is_empty());
+
+        assert!(manager.resize("a.png", 100));
+        assert!(!manager.resize("missing.png", 100));
+        assert!(manager.contains("a.png"));
+        assert!(!manager.contains("b.png"));
+        assert_eq!(manager.used_bytes(), 100);
+
+        let evicted = manager.drain_evicted();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].get(), Some(2));
+        assert!(manager.drain_evicted().is_empty());
+    }
+
+    #[test]
+    fn test_asset_server_caches_texture_handles() {
+        use crate::resources::manager::AssetServer;
+        use std::path::Path;
+
+        let mut server = AssetServer::new();
+        let path = Path::new("assets/missing_texture.png");
+        let first = server.load_texture(path);
+        let second = server.load_texture(path);
+        let linear = server.load_texture_linear(path);
+
+        assert!(first.ptr_eq(&second));
+        assert!(!first.ptr_eq(&linear));
+        assert_eq!(server.textures().len(), 2);
+    }
+
+    #[test]
+    fn test_asset_server_unloads_deleted_texture() {
+        use crate::resources::hot_reload::ReloadAction;
+        use crate::resources::manager::AssetServer;
+        use std::path::Path;
+
+        let mut server = AssetServer::new();
+        let path = Path::new("assets/deleted_texture.png");
+        let first = server.load_texture(path);
+        server.load_texture_linear(path);
+        server.load_texture(Path::new("assets/other_texture.png"));
+
+        server.handle_reload_action(ReloadAction::Unload(path.to_path_buf()));
+        assert_eq!(server.textures().len(), 1);
+        assert!(!server.textures().contains(path));
+
+        // 卸载后再次加载会重新读取文件
+        let reloaded = server.load_texture(path);
+        assert!(!first.ptr_eq(&reloaded));
+    }
+
+    #[test]
+    fn test_dynamic_atlas_reuses_freed_region() {
+        use crate::resources::atlas::DynamicAtlas;
+
+        let mut atlas = DynamicAtlas::new(256, 256);
+        let rects: Vec<_> = (0..4).map(|_| atlas.insert(64, 64).unwrap()).collect();
+        assert_eq!(atlas.len(), 4);
+
+        // 已分配区域互不重叠
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                let overlap = a.x < b.x + b.width
+                    && b.x < a.x + a.width
+                    && a.y < b.y + b.height
+                    && b.y < a.y + a.height;
+                assert!(!overlap, "{:?} overlaps {:?}", a, b);
+            }
+        }
+
+        // 归还一块后，同尺寸的插入复用该区域
+        assert!(atlas.remove(rects[1]));
+        assert!(!atlas.remove(rects[1]));
+        assert_eq!(atlas.insert(64, 64), Some(rects[1]));
+
+        // 图集放不下时返回 None
+        assert!(atlas.insert(512, 16).is_none());
+
+        // 全部归还后空闲区域合并，可以再放下整页大小的区域
+        for rect in &rects {
+            assert!(atlas.remove(*rect));
+        }
+        assert!(atlas.is_empty());
+        assert_eq!(atlas.insert(256, 256).map(|r| r.area()), Some(256 * 256));
+        assert!(atlas.insert(1, 1).is_none());
+    }
 }
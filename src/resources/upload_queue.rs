@@ -118,6 +118,8 @@ pub struct UploadQueue {
     pub(crate) staging_pool: StagingBufferPool,
     /// 统计信息
     pub(crate) stats: UploadStats,
+    /// 已结束的帧数，用于判定 Staging Buffer 的空闲时长
+    pub(crate) frame_index: u64,
 }
 
 impl UploadQueue {
@@ -370,9 +372,11 @@ impl UploadQueue {
         }
     }
 
-    /// 帧结束时调用，回收资源
+    /// 帧结束时调用，回收资源并释放空闲过久的 Staging Buffer
     pub fn end_frame(&mut self, device: &wgpu::Device) {
         self.staging_pool.end_frame(device);
+        self.frame_index += 1;
+        self.staging_pool.collect_idle(self.frame_index);
 
         // 重置本帧统计
         self.stats.buffer_uploads = 0;
@@ -484,6 +488,42 @@ mod tests {
         assert_eq!(queue.pending_count(), 0);
     }
 
+    #[test]
+    fn test_end_frame_releases_idle_staging_buffers() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+
+        let mut uploads = UploadQueue::new();
+        uploads.initialize(&device);
+        uploads.staging_pool.set_idle_policy(2, 1);
+
+        // 三次大数据上传各占用一个独立 Staging Buffer
+        let data = vec![0u8; 128 * 1024];
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for _ in 0..3 {
+            let target = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Upload Target"),
+                size: data.len() as u64,
+                usage: wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            uploads.queue_buffer(&data, target, 0);
+        }
+        uploads.flush(&device, &queue, &mut encoder);
+        queue.submit(Some(encoder.finish()));
+
+        uploads.end_frame(&device);
+        assert_eq!(uploads.staging_pool.free_buffer_count(), 3);
+
+        // 空闲满 2 帧后释放到最少保留数量
+        uploads.end_frame(&device);
+        assert_eq!(uploads.staging_pool.free_buffer_count(), 3);
+        uploads.end_frame(&device);
+        assert_eq!(uploads.staging_pool.free_buffer_count(), 1);
+        assert_eq!(uploads.staging_stats().buffers_freed, 2);
+    }
+
     #[test]
     fn test_texture_upload_info_default() {
         let info = TextureUploadInfo::default();
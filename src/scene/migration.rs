@@ -0,0 +1,161 @@
+//! 场景版本迁移
+//!
+//! 场景文件中记录了序列化版本。当组件字段被新增或重命名后，旧版本的场景
+//! 在加载时会按版本依次经过已注册的迁移函数，升级到当前版本后再反序列化。
+
+use super::serialization::SerializedScene;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// 单步迁移：把 `from` 版本的场景 JSON 转换为 `from + 1` 版本
+pub type MigrationFn = fn(Value) -> Value;
+
+/// 场景加载错误
+#[derive(Debug, Error)]
+pub enum SceneLoadError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid scene JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid scene version field: {0}")]
+    InvalidVersion(Value),
+    #[error("Scene version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+/// 场景迁移注册表
+///
+/// 迁移以源版本为键注册。没有注册迁移的版本视为格式未变化，直接升级版本号。
+#[derive(Debug, Clone, Default)]
+pub struct SceneMigration {
+    migrations: BTreeMap<u32, MigrationFn>,
+}
+
+impl SceneMigration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册从 `from_version` 升级到 `from_version + 1` 的迁移
+    pub fn register(&mut self, from_version: u32, migration: MigrationFn) -> &mut Self {
+        self.migrations.insert(from_version, migration);
+        self
+    }
+
+    /// 是否注册了指定版本的迁移
+    pub fn has_migration(&self, from_version: u32) -> bool {
+        self.migrations.contains_key(&from_version)
+    }
+
+    /// 将场景 JSON 依次迁移到 `target_version`
+    pub fn migrate(&self, mut scene: Value, target_version: u32) -> Result<Value, SceneLoadError> {
+        let version = scene_version(&scene)?;
+        if version > target_version {
+            return Err(SceneLoadError::UnsupportedVersion {
+                found: version,
+                supported: target_version,
+            });
+        }
+
+        for from in version..target_version {
+            if let Some(migration) = self.migrations.get(&from) {
+                scene = migration(scene);
+            }
+            if let Value::Object(map) = &mut scene {
+                map.insert("version".to_string(), Value::from(from + 1));
+            }
+        }
+        Ok(scene)
+    }
+
+    /// 解析场景 JSON，必要时先迁移到当前版本
+    pub fn load_str(&self, json: &str) -> Result<SerializedScene, SceneLoadError> {
+        let value: Value = serde_json::from_str(json)?;
+        let migrated = self.migrate(value, SerializedScene::CURRENT_VERSION)?;
+        Ok(serde_json::from_value(migrated)?)
+    }
+}
+
+/// 读取场景版本，缺失时视为版本 0
+fn scene_version(scene: &Value) -> Result<u32, SceneLoadError> {
+    match scene.get("version") {
+        None | Some(Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| SceneLoadError::InvalidVersion(v.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::SerializedComponent;
+
+    /// 版本 0 中 Transform 的位置字段名为 `pos`
+    fn rename_transform_pos(mut scene: Value) -> Value {
+        if let Some(entities) = scene["entities"].as_array_mut() {
+            for entity in entities {
+                let Some(components) = entity["components"].as_object_mut() else {
+                    continue;
+                };
+                for component in components.values_mut() {
+                    if component["type"] != "Transform" {
+                        continue;
+                    }
+                    if let Some(map) = component.as_object_mut() {
+                        if let Some(pos) = map.remove("pos") {
+                            map.insert("position".to_string(), pos);
+                        }
+                    }
+                }
+            }
+        }
+        scene
+    }
+
+    #[test]
+    fn test_migrate_renamed_field() {
+        let old_scene = r#"{
+            "name": "old_level",
+            "version": 0,
+            "entities": [{
+                "id": 1,
+                "components": {
+                    "Transform": {
+                        "type": "Transform",
+                        "pos": [1.0, 2.0, 3.0],
+                        "rotation": [0.0, 0.0, 0.0, 1.0],
+                        "scale": [1.0, 1.0, 1.0]
+                    }
+                }
+            }]
+        }"#;
+
+        // 未注册迁移时旧字段无法解析
+        assert!(SceneMigration::new().load_str(old_scene).is_err());
+
+        let mut migrations = SceneMigration::new();
+        migrations.register(0, rename_transform_pos);
+        let scene = migrations.load_str(old_scene).unwrap();
+
+        assert_eq!(scene.version, SerializedScene::CURRENT_VERSION);
+        match &scene.entities[0].components["Transform"] {
+            SerializedComponent::Transform { position, .. } => {
+                assert_eq!(*position, [1.0, 2.0, 3.0]);
+            }
+            other => panic!("Expected Transform, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_newer_scene_is_rejected() {
+        let json = format!(
+            r#"{{"name": "future", "version": {}, "entities": []}}"#,
+            SerializedScene::CURRENT_VERSION + 1
+        );
+        let err = SceneMigration::new().load_str(&json).unwrap_err();
+        assert!(matches!(err, SceneLoadError::UnsupportedVersion { .. }));
+    }
+}
@@ -3,7 +3,9 @@
 //! 提供场景的加载、保存、切换和管理功能。
 
 pub mod manager;
+pub mod migration;
 pub mod serialization;
 
 pub use manager::{Scene, SceneId, SceneManager, SceneTransition};
+pub use migration::{MigrationFn, SceneLoadError, SceneMigration};
 pub use serialization::{SerializedComponent, SerializedEntity, SerializedScene};
@@ -3,6 +3,7 @@ use crate::ecs::{
     Camera, DirectionalLightComp, PbrMaterialComp, PointLight, PointLight3D, Projection, Sprite,
     Transform,
 };
+use super::migration::{SceneLoadError, SceneMigration};
 use crate::physics::{ColliderDesc, RigidBodyDesc};
 use bevy_ecs::prelude::*;
 use glam::{Quat, Vec3};
@@ -531,9 +532,16 @@ impl SerializedScene {
 
     /// 从JSON文件加载场景
     pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::load_from_file_with(path, &SceneMigration::default())?)
+    }
+
+    /// 从JSON文件加载场景，旧版本场景先经过迁移升级到当前版本
+    pub fn load_from_file_with(
+        path: &str,
+        migrations: &SceneMigration,
+    ) -> Result<Self, SceneLoadError> {
         let json = std::fs::read_to_string(path)?;
-        let scene = serde_json::from_str(&json)?;
-        Ok(scene)
+        migrations.load_str(&json)
     }
 }
 
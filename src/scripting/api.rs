@@ -1,17 +1,98 @@
+use super::lua_support::{LuaContext, LuaValue};
 use super::system::{ScriptResult, ScriptValue};
+use crate::core::error::ScriptError;
 use glam::{Quat, Vec2, Vec3};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// 脚本API版本
+///
+/// 新增接口时增加次版本号，删除或修改已有接口时增加主版本号。
+/// 脚本可在开头的注释中声明所需版本，例如 `-- @api_version 1.0`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ApiVersion {
+    /// 当前引擎提供的API版本
+    pub const CURRENT: ApiVersion = ApiVersion::new(1, 0);
+
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// 解析 `"主版本.次版本"` 或 `"主版本"`
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(2, '.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+        Some(Self::new(major, minor))
+    }
+
+    /// 从脚本开头的注释中读取 `@api_version` 声明
+    ///
+    /// 支持 `--`、`//` 和 `#` 注释，遇到第一行非注释代码后停止查找。
+    pub fn declared_in(source: &str) -> Result<Option<Self>, ScriptError> {
+        for line in source.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            let Some(comment) = ["--", "//", "#"]
+                .iter()
+                .find_map(|prefix| line.strip_prefix(prefix))
+            else {
+                break;
+            };
+            if let Some(version) = comment.trim().strip_prefix("@api_version") {
+                return Self::parse(version).map(Some).ok_or_else(|| {
+                    ScriptError::Compilation(format!(
+                        "Invalid @api_version declaration: {}",
+                        version.trim()
+                    ))
+                });
+            }
+        }
+        Ok(None)
+    }
+
+    /// 检查脚本声明的API版本，要求的版本高于引擎版本时拒绝执行
+    pub fn check_script(source: &str) -> Result<(), ScriptError> {
+        match Self::declared_in(source)? {
+            Some(required) if required > Self::CURRENT => {
+                Err(ScriptError::IncompatibleApiVersion {
+                    required: required.to_string(),
+                    current: Self::CURRENT.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
 
 /// 脚本API - 提供引擎功能的脚本接口
 pub struct ScriptApi {
     registered_functions:
         HashMap<String, Box<dyn Fn(&[ScriptValue]) -> ScriptResult + Send + Sync>>,
+    /// 可供脚本检测的功能，如 `"physics.raycast"`
+    ///
+    /// 与 `has_capability` 函数及已安装到脚本上下文的全局函数共享，之后声明的功能同样可见。
+    capabilities: Arc<RwLock<HashSet<String>>>,
 }
 
 impl ScriptApi {
     pub fn new() -> Self {
         let mut api = Self {
             registered_functions: HashMap::new(),
+            capabilities: Arc::new(RwLock::new(HashSet::new())),
         };
 
         // 注册内置函数
@@ -22,6 +103,22 @@ impl ScriptApi {
 
     /// 注册内置函数
     fn register_builtin_functions(&mut self) {
+        self.register_capability("log");
+        self.register_capability("math");
+
+        // 版本与功能查询
+        self.register_function("api_version", |_args| {
+            ScriptResult::Success(ApiVersion::CURRENT.to_string())
+        });
+
+        let capabilities = self.capabilities.clone();
+        self.register_function("has_capability", move |args| match args.first() {
+            Some(ScriptValue::String(name)) => {
+                ScriptResult::Success(contains(&capabilities, name).to_string())
+            }
+            _ => ScriptResult::Error("has_capability() requires a string argument".to_string()),
+        });
+
         // 日志函数
         self.register_function("log", |args| {
             if let Some(ScriptValue::String(msg)) = args.first() {
@@ -75,8 +172,56 @@ impl ScriptApi {
             ScriptResult::Error(format!("Function '{}' not found", name))
         }
     }
+
+    /// 声明一项可供脚本检测的功能
+    pub fn register_capability(&mut self, name: &str) {
+        if let Ok(mut capabilities) = self.capabilities.write() {
+            capabilities.insert(name.to_string());
+        }
+    }
+
+    /// 是否提供某项功能
+    pub fn has_capability(&self, name: &str) -> bool {
+        contains(&self.capabilities, name)
+    }
+
+    /// 所有已声明的功能，按名称排序
+    pub fn capabilities(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .capabilities
+            .read()
+            .map(|capabilities| capabilities.iter().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// 共享的功能集合，供脚本上下文安装 `has_capability` 全局函数
+    pub fn capability_set(&self) -> Arc<RwLock<HashSet<String>>> {
+        self.capabilities.clone()
+    }
+
+    /// 向Lua上下文暴露 `API_VERSION` 全局变量和 `has_capability(name)` 函数
+    pub fn install_lua_globals(&self, context: &mut LuaContext) {
+        context.set_global(
+            "API_VERSION",
+            LuaValue::String(ApiVersion::CURRENT.to_string()),
+        );
+        let capabilities = self.capability_set();
+        context.register_function("has_capability", move |args| match args.first() {
+            Some(LuaValue::String(name)) => Ok(LuaValue::Boolean(contains(&capabilities, name))),
+            _ => Err("has_capability() requires a string argument".to_string()),
+        });
+    }
 }
 
+/// 查询共享的功能集合，锁中毒时视为不提供
+pub(crate) fn contains(capabilities: &RwLock<HashSet<String>>, name: &str) -> bool {
+    capabilities
+        .read()
+        .map(|capabilities| capabilities.contains(name))
+        .unwrap_or(false)
+}
 
 /// 扩展的脚本值类型,支持更多的引擎类型
 #[derive(Debug, Clone)]
@@ -161,6 +306,7 @@ impl ExtendedScriptValue {
 
 #[cfg(test)]
 mod tests {
+    use super::super::system::{PythonContext, ScriptLanguage, ScriptSystem};
     use super::*;
 
     #[test]
@@ -179,6 +325,52 @@ mod tests {
         assert!(matches!(result, ScriptResult::Success(_)));
     }
 
+    #[test]
+    fn test_api_version_and_capabilities() {
+        let mut api = ScriptApi::new();
+        api.register_capability("physics.raycast");
+
+        let has = |api: &ScriptApi, name: &str| {
+            api.call("has_capability", &[ScriptValue::String(name.to_string())])
+        };
+        assert!(
+            matches!(has(&api, "physics.raycast"), ScriptResult::Success(ref s) if s == "true")
+        );
+        assert!(matches!(has(&api, "net.sockets"), ScriptResult::Success(ref s) if s == "false"));
+        assert!(matches!(
+            api.call("api_version", &[]),
+            ScriptResult::Success(ref s) if *s == ApiVersion::CURRENT.to_string()
+        ));
+
+        assert_eq!(ApiVersion::parse("1.2"), Some(ApiVersion::new(1, 2)));
+        assert_eq!(ApiVersion::parse("3"), Some(ApiVersion::new(3, 0)));
+        assert_eq!(ApiVersion::parse("x.1"), None);
+    }
+
+    #[test]
+    fn test_future_api_version_rejected() {
+        let script = "-- my script\n-- @api_version 99.0\nprint('hi')";
+        let err = ApiVersion::check_script(script).unwrap_err();
+        assert!(matches!(err, ScriptError::IncompatibleApiVersion { .. }));
+        let message = err.to_string();
+        assert!(message.contains("99.0") && message.contains(&ApiVersion::CURRENT.to_string()));
+
+        // 脚本系统拒绝执行
+        let system = ScriptSystem::new();
+        system.register_context(ScriptLanguage::Python, Box::new(PythonContext::new()));
+        let result = system.execute(ScriptLanguage::Python, "# @api_version 99.0\nx = 1");
+        assert!(
+            matches!(result, ScriptResult::Error(ref e) if e.contains("99.0")),
+            "Expected rejection, got {:?}",
+            result
+        );
+
+        // 当前或更早的版本、未声明版本以及代码之后的注释均可执行
+        assert!(ApiVersion::check_script("// @api_version 1.0\nlet x = 1;").is_ok());
+        assert!(ApiVersion::check_script("print('hi')\n-- @api_version 99.0").is_ok());
+        assert!(ApiVersion::check_script("-- @api_version one").is_err());
+    }
+
     #[test]
     fn test_extended_script_value() {
         let vec3 = ExtendedScriptValue::Vec3(Vec3::new(1.0, 2.0, 3.0));
@@ -1,22 +1,82 @@
 use super::api::{ExtendedScriptValue, ScriptApi};
+use super::lua_support::{LuaContext, LuaValue};
 use super::system::{ScriptResult, ScriptValue};
-use crate::ecs::Transform;
+use crate::ecs::{Sprite, Transform, Velocity};
 use bevy_ecs::prelude::*;
 use glam::{Quat, Vec3};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 /// ECS脚本绑定 - 提供实体和组件操作的脚本接口
 pub struct EcsScriptBindings {
     world: Arc<Mutex<World>>,
+    /// Lua脚本提交的延迟组件操作
+    commands: EcsCommandBuffer,
 }
 
 impl EcsScriptBindings {
     pub fn new(world: Arc<Mutex<World>>) -> Self {
-        Self { world }
+        Self {
+            world,
+            commands: EcsCommandBuffer::default(),
+        }
+    }
+
+    /// 注册Lua可调用的组件函数
+    ///
+    /// - `add_component(entity, kind, table)`：添加或替换组件，表中缺失的字段使用默认值
+    /// - `remove_component(entity, kind)`：移除组件
+    /// - `get_component(entity, kind)`：读取组件为表，不存在时返回 nil
+    ///
+    /// `kind` 为 `"Transform"`、`"Sprite"` 或 `"Velocity"`。添加和移除只写入命令缓冲，
+    /// 需在脚本执行结束后调用 `apply_commands`，脚本执行期间不能持有世界锁。
+    pub fn register_lua_api(&self, context: &mut LuaContext) {
+        let commands = self.commands.clone();
+        context.register_function("add_component", move |args| {
+            let entity = lua_entity(args.first())?;
+            let kind = lua_component_kind(args.get(1))?;
+            let data = ScriptComponent::from_lua(kind, args.get(2).unwrap_or(&LuaValue::Nil))?;
+            commands.push(EcsCommand::Add(entity, data));
+            Ok(LuaValue::Nil)
+        });
+
+        let commands = self.commands.clone();
+        context.register_function("remove_component", move |args| {
+            let entity = lua_entity(args.first())?;
+            let kind = lua_component_kind(args.get(1))?;
+            commands.push(EcsCommand::Remove(entity, kind));
+            Ok(LuaValue::Nil)
+        });
+
+        let world = self.world.clone();
+        let commands = self.commands.clone();
+        context.register_function("get_component", move |args| {
+            let entity = lua_entity(args.first())?;
+            let kind = lua_component_kind(args.get(1))?;
+            // 优先返回本次脚本尚未应用的修改
+            let component = match commands.pending(entity, kind) {
+                Some(pending) => pending,
+                None => ScriptComponent::read(&world.lock().unwrap(), entity, kind),
+            };
+            Ok(component.map_or(LuaValue::Nil, |c| c.to_lua()))
+        });
+    }
+
+    /// 将Lua脚本提交的组件操作应用到世界，返回应用的操作数
+    pub fn apply_commands(&self) -> usize {
+        self.commands.apply(&mut self.world.lock().unwrap())
+    }
+
+    /// 延迟组件操作缓冲
+    pub fn commands(&self) -> &EcsCommandBuffer {
+        &self.commands
     }
 
     /// 注册ECS相关的脚本API
     pub fn register_api(&self, api: &mut ScriptApi) {
+        api.register_capability("ecs.entity");
+        api.register_capability("ecs.transform");
+
         let world = self.world.clone();
 
         // 创建实体
@@ -182,6 +242,266 @@ impl EcsScriptBindings {
     }
 }
 
+/// 脚本可访问的组件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    Transform,
+    Sprite,
+    Velocity,
+}
+
+impl ComponentKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Transform" => Some(Self::Transform),
+            "Sprite" => Some(Self::Sprite),
+            "Velocity" => Some(Self::Velocity),
+            _ => None,
+        }
+    }
+}
+
+/// 脚本与ECS之间传递的组件数据
+#[derive(Debug, Clone)]
+pub enum ScriptComponent {
+    Transform(Transform),
+    Sprite(Sprite),
+    Velocity(Velocity),
+}
+
+impl ScriptComponent {
+    pub fn kind(&self) -> ComponentKind {
+        match self {
+            Self::Transform(_) => ComponentKind::Transform,
+            Self::Sprite(_) => ComponentKind::Sprite,
+            Self::Velocity(_) => ComponentKind::Velocity,
+        }
+    }
+
+    /// 从世界读取组件
+    pub fn read(world: &World, entity: Entity, kind: ComponentKind) -> Option<Self> {
+        match kind {
+            ComponentKind::Transform => {
+                world.get::<Transform>(entity).copied().map(Self::Transform)
+            }
+            ComponentKind::Sprite => world.get::<Sprite>(entity).cloned().map(Self::Sprite),
+            ComponentKind::Velocity => world.get::<Velocity>(entity).copied().map(Self::Velocity),
+        }
+    }
+
+    /// 从Lua表构建组件，缺失的字段使用默认值
+    ///
+    /// 向量可写作 `{x=, y=, z=}` 或数组 `{1, 2, 3}`。
+    pub fn from_lua(kind: ComponentKind, value: &LuaValue) -> Result<Self, String> {
+        let empty = HashMap::new();
+        let table = match value {
+            LuaValue::Table(table) => table,
+            LuaValue::Nil => &empty,
+            _ => return Err(format!("{:?} data must be a table", kind)),
+        };
+        Ok(match kind {
+            ComponentKind::Transform => {
+                let default = Transform::default();
+                let rot = lua_floats::<4>(table.get("rotation"), default.rot.to_array())?;
+                Self::Transform(Transform {
+                    pos: lua_vec3(table.get("position"), default.pos)?,
+                    rot: Quat::from_array(rot).normalize(),
+                    scale: lua_vec3(table.get("scale"), default.scale)?,
+                })
+            }
+            ComponentKind::Sprite => {
+                let default = Sprite::default();
+                Self::Sprite(Sprite {
+                    color: lua_floats(table.get("color"), default.color)?,
+                    tex_index: lua_number(table.get("tex_index"), default.tex_index as f64)? as u32,
+                    normal_tex_index: lua_number(
+                        table.get("normal_tex_index"),
+                        default.normal_tex_index as f64,
+                    )? as u32,
+                    uv_off: lua_floats(table.get("uv_off"), default.uv_off)?,
+                    uv_scale: lua_floats(table.get("uv_scale"), default.uv_scale)?,
+                    layer: lua_number(table.get("layer"), default.layer as f64)? as f32,
+                })
+            }
+            ComponentKind::Velocity => Self::Velocity(Velocity {
+                lin: lua_vec3(table.get("lin"), Vec3::ZERO)?,
+                ang: lua_vec3(table.get("ang"), Vec3::ZERO)?,
+            }),
+        })
+    }
+
+    /// 转换为Lua表
+    pub fn to_lua(&self) -> LuaValue {
+        let fields: Vec<(&str, LuaValue)> = match self {
+            Self::Transform(t) => vec![
+                ("position", floats_to_lua(&t.pos.to_array())),
+                ("rotation", floats_to_lua(&t.rot.to_array())),
+                ("scale", floats_to_lua(&t.scale.to_array())),
+            ],
+            Self::Sprite(s) => vec![
+                ("color", floats_to_lua(&s.color)),
+                ("tex_index", LuaValue::Number(s.tex_index as f64)),
+                (
+                    "normal_tex_index",
+                    LuaValue::Number(s.normal_tex_index as f64),
+                ),
+                ("uv_off", floats_to_lua(&s.uv_off)),
+                ("uv_scale", floats_to_lua(&s.uv_scale)),
+                ("layer", LuaValue::Number(s.layer as f64)),
+            ],
+            Self::Velocity(v) => vec![
+                ("lin", floats_to_lua(&v.lin.to_array())),
+                ("ang", floats_to_lua(&v.ang.to_array())),
+            ],
+        };
+        LuaValue::Table(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+
+    fn insert(self, entity: &mut EntityWorldMut) {
+        match self {
+            Self::Transform(t) => entity.insert(t),
+            Self::Sprite(s) => entity.insert(s),
+            Self::Velocity(v) => entity.insert(v),
+        };
+    }
+}
+
+/// 延迟执行的组件操作
+#[derive(Debug, Clone)]
+pub enum EcsCommand {
+    Add(Entity, ScriptComponent),
+    Remove(Entity, ComponentKind),
+}
+
+/// 组件操作缓冲，脚本执行期间只记录操作，执行结束后统一应用
+#[derive(Clone, Default)]
+pub struct EcsCommandBuffer {
+    commands: Arc<Mutex<Vec<EcsCommand>>>,
+}
+
+impl EcsCommandBuffer {
+    pub fn push(&self, command: EcsCommand) {
+        self.commands.lock().unwrap().push(command);
+    }
+
+    /// 实体某组件尚未应用的最新状态，`Some(None)` 表示已被移除
+    pub fn pending(&self, entity: Entity, kind: ComponentKind) -> Option<Option<ScriptComponent>> {
+        self.commands
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find_map(|command| match command {
+                EcsCommand::Add(e, data) if *e == entity && data.kind() == kind => {
+                    Some(Some(data.clone()))
+                }
+                EcsCommand::Remove(e, k) if *e == entity && *k == kind => Some(None),
+                _ => None,
+            })
+    }
+
+    /// 按提交顺序应用所有操作，目标实体不存在的操作被忽略
+    pub fn apply(&self, world: &mut World) -> usize {
+        let commands = std::mem::take(&mut *self.commands.lock().unwrap());
+        let mut applied = 0;
+        for command in commands {
+            match command {
+                EcsCommand::Add(entity, data) => {
+                    if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+                        data.insert(&mut entity_mut);
+                        applied += 1;
+                    }
+                }
+                EcsCommand::Remove(entity, kind) => {
+                    if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+                        match kind {
+                            ComponentKind::Transform => entity_mut.remove::<Transform>(),
+                            ComponentKind::Sprite => entity_mut.remove::<Sprite>(),
+                            ComponentKind::Velocity => entity_mut.remove::<Velocity>(),
+                        };
+                        applied += 1;
+                    }
+                }
+            }
+        }
+        applied
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn lua_entity(value: Option<&LuaValue>) -> Result<Entity, String> {
+    match value {
+        Some(LuaValue::Number(bits)) => {
+            Entity::try_from_bits(*bits as u64).map_err(|_| format!("Invalid entity id: {}", bits))
+        }
+        _ => Err("Expected an entity id as the first argument".to_string()),
+    }
+}
+
+fn lua_component_kind(value: Option<&LuaValue>) -> Result<ComponentKind, String> {
+    match value {
+        Some(LuaValue::String(name)) => ComponentKind::from_name(name)
+            .ok_or_else(|| format!("Unsupported component kind: {}", name)),
+        _ => Err("Expected a component kind name as the second argument".to_string()),
+    }
+}
+
+fn lua_number(value: Option<&LuaValue>, default: f64) -> Result<f64, String> {
+    match value {
+        None | Some(LuaValue::Nil) => Ok(default),
+        Some(LuaValue::Number(n)) => Ok(*n),
+        Some(other) => Err(format!("Expected a number, got {:?}", other)),
+    }
+}
+
+/// 读取定长浮点数组，支持 `{x, y, z, w}` 命名字段或数组写法
+fn lua_floats<const N: usize>(
+    value: Option<&LuaValue>,
+    default: [f32; N],
+) -> Result<[f32; N], String> {
+    const NAMES: [&str; 4] = ["x", "y", "z", "w"];
+    let table = match value {
+        None | Some(LuaValue::Nil) => return Ok(default),
+        Some(LuaValue::Table(table)) => table,
+        Some(other) => return Err(format!("Expected a table, got {:?}", other)),
+    };
+    let mut out = default;
+    for (i, slot) in out.iter_mut().enumerate() {
+        let field = table
+            .get(NAMES[i])
+            .or_else(|| table.get(&(i + 1).to_string()));
+        *slot = lua_number(field, *slot as f64)? as f32;
+    }
+    Ok(out)
+}
+
+fn lua_vec3(value: Option<&LuaValue>, default: Vec3) -> Result<Vec3, String> {
+    lua_floats(value, default.to_array()).map(Vec3::from_array)
+}
+
+fn floats_to_lua(values: &[f32]) -> LuaValue {
+    const NAMES: [&str; 4] = ["x", "y", "z", "w"];
+    LuaValue::Table(
+        NAMES
+            .iter()
+            .zip(values)
+            .map(|(name, v)| (name.to_string(), LuaValue::Number(*v as f64)))
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +538,75 @@ mod tests {
             assert!(matches!(result, ScriptResult::Success(_)));
         }
     }
+    #[test]
+    #[cfg(feature = "lua_scripting")]
+    fn test_lua_component_bindings() {
+        let mut world = World::new();
+        let entity = world
+            .spawn((Transform::default(), Velocity::default()))
+            .id();
+        let world = Arc::new(Mutex::new(world));
+
+        let bindings = EcsScriptBindings::new(world.clone());
+        let mut lua = LuaContext::new();
+        bindings.register_lua_api(&mut lua);
+        lua.set_global("entity", LuaValue::Number(entity.to_bits() as f64));
+
+        let script = r#"
+            local t = get_component(entity, "Transform")
+            t.position = { x = 1, y = 2, z = 3 }
+            add_component(entity, "Transform", t)
+            add_component(entity, "Sprite", { layer = 2, color = { 1, 0, 0, 1 } })
+            remove_component(entity, "Velocity")
+            return get_component(entity, "Transform").position.y
+        "#;
+        let result = lua.execute("move", script).unwrap();
+        // 脚本内可读到自己提交的修改
+        assert_eq!(result, LuaValue::Number(2.0));
+
+        // 应用前世界未被修改
+        assert_eq!(
+            world.lock().unwrap().get::<Transform>(entity).unwrap().pos,
+            Vec3::ZERO
+        );
+        assert_eq!(bindings.apply_commands(), 3);
+
+        let world = world.lock().unwrap();
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.pos, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(transform.scale, Vec3::ONE);
+        let sprite = world.get::<Sprite>(entity).unwrap();
+        assert_eq!(sprite.layer, 2.0);
+        assert_eq!(sprite.color, [1.0, 0.0, 0.0, 1.0]);
+        assert!(world.get::<Velocity>(entity).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "lua_scripting")]
+    fn test_lua_component_bindings_errors() {
+        let mut world = World::new();
+        let entity = world.spawn(Transform::default()).id();
+        let bindings = EcsScriptBindings::new(Arc::new(Mutex::new(world)));
+        let mut lua = LuaContext::new();
+        bindings.register_lua_api(&mut lua);
+        lua.set_global("entity", LuaValue::Number(entity.to_bits() as f64));
+
+        // 实体有效，错误来自组件类型和数据
+        let error = lua
+            .execute("bad", "add_component(entity, \"Camera\", {})")
+            .unwrap_err();
+        assert!(error.contains("Unsupported component kind"), "{}", error);
+        assert!(lua
+            .execute("bad", "add_component(entity, \"Transform\", 5)")
+            .is_err());
+        assert!(lua
+            .execute("bad", "add_component(1, \"Transform\", {})")
+            .unwrap_err()
+            .contains("Invalid entity id"));
+        assert!(bindings.commands().is_empty());
+
+        lua.execute("ok", "add_component(entity, \"Transform\", {})")
+            .unwrap();
+        assert_eq!(bindings.commands().len(), 1);
+    }
 }
@@ -16,6 +16,9 @@ impl ExtendedEcsBindings {
 
     /// 注册扩展的ECS相关的脚本API
     pub fn register_api(&self, api: &mut ScriptApi) {
+        api.register_capability("render.sprite");
+        api.register_capability("render.camera");
+
         // Sprite组件相关API
         self.register_sprite_api(api);
 
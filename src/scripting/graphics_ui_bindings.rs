@@ -16,6 +16,10 @@ impl GraphicsUiBindings {
 
     /// 注册图形和UI相关的脚本API
     pub fn register_api(&self, api: &mut ScriptApi) {
+        api.register_capability("render.draw");
+        api.register_capability("ui");
+        api.register_capability("input");
+
         // 图形相关API
         self.register_graphics_api(api);
 
@@ -1,18 +1,31 @@
+use crate::core::error::ScriptError;
 use std::collections::HashMap;
+#[cfg(feature = "lua_scripting")]
+use std::sync::Mutex;
 
-/// Lua脚本上下文 (简化版)
+/// Lua脚本上下文
+///
+/// 启用 `lua_scripting` 特性时基于 mlua (Lua 5.4) 执行脚本，
+/// 否则只记录脚本和变量，不实际执行。
 pub struct LuaContext {
     /// 脚本存储
     scripts: HashMap<String, String>,
-    /// 变量存储
+    /// 变量存储（未启用 `lua_scripting` 时代替 Lua 全局表）
+    #[cfg(not(feature = "lua_scripting"))]
     variables: HashMap<String, LuaValue>,
+    /// Lua虚拟机
+    #[cfg(feature = "lua_scripting")]
+    lua: Mutex<mlua::Lua>,
 }
 
 impl Default for LuaContext {
     fn default() -> Self {
         Self {
             scripts: HashMap::new(),
+            #[cfg(not(feature = "lua_scripting"))]
             variables: HashMap::new(),
+            #[cfg(feature = "lua_scripting")]
+            lua: Mutex::new(mlua::Lua::new()),
         }
     }
 }
@@ -29,10 +42,7 @@ pub enum LuaValue {
 
 impl LuaContext {
     pub fn new() -> Self {
-        Self {
-            scripts: HashMap::new(),
-            variables: HashMap::new(),
-        }
+        Self::default()
     }
 
     /// 执行Lua脚本
@@ -41,44 +51,190 @@ impl LuaContext {
         self.scripts
             .insert(script_name.to_string(), code.to_string());
 
-        // 实际实现需要集成mlua或rlua库
-        // 这里返回一个模拟值
+        #[cfg(feature = "lua_scripting")]
+        {
+            let lua = self.lua.lock().unwrap();
+            let value = lua
+                .load(code)
+                .set_name(script_name)
+                .eval::<mlua::Value>()
+                .map_err(|e| e.to_string())?;
+            from_lua(value).map_err(|e| e.to_string())
+        }
+
+        #[cfg(not(feature = "lua_scripting"))]
         Ok(LuaValue::Nil)
     }
 
-    /// 调用Lua函数
+    /// 按名称调用Lua全局函数
+    ///
+    /// 参数和返回值通过 `LuaValue` 转换，多个返回值时只取第一个。
+    /// 全局变量不存在或不是函数时返回错误。
     pub fn call_function(
         &mut self,
         function_name: &str,
-        args: Vec<LuaValue>,
-    ) -> Result<LuaValue, String> {
-        // 实际实现需要集成mlua或rlua库
-        // 这里返回一个模拟值
-        let _ = (function_name, args);
-        Ok(LuaValue::Nil)
+        args: &[LuaValue],
+    ) -> Result<LuaValue, ScriptError> {
+        #[cfg(feature = "lua_scripting")]
+        {
+            let lua = self.lua.lock().unwrap();
+            let function = match lua.globals().get::<_, mlua::Value>(function_name) {
+                Ok(mlua::Value::Function(function)) => function,
+                Ok(mlua::Value::Nil) => {
+                    return Err(ScriptError::NotFound(function_name.to_string()))
+                }
+                Ok(other) => {
+                    return Err(ScriptError::InvalidBinding(format!(
+                        "Lua global '{}' is a {}, not a function",
+                        function_name,
+                        other.type_name()
+                    )))
+                }
+                Err(e) => return Err(ScriptError::Runtime(e.to_string())),
+            };
+
+            let args = args
+                .iter()
+                .map(|arg| to_lua(&lua, arg))
+                .collect::<mlua::Result<mlua::MultiValue>>()
+                .map_err(|e| ScriptError::InvalidBinding(e.to_string()))?;
+            let result = function
+                .call::<_, mlua::Value>(args)
+                .map_err(|e| ScriptError::Runtime(e.to_string()))?;
+            from_lua(result)
+        }
+
+        #[cfg(not(feature = "lua_scripting"))]
+        {
+            let _ = args;
+            Err(ScriptError::Runtime(format!(
+                "Cannot call Lua function '{}': enable the `lua_scripting` feature",
+                function_name
+            )))
+        }
     }
 
     /// 设置全局变量
     pub fn set_global(&mut self, name: &str, value: LuaValue) {
+        #[cfg(feature = "lua_scripting")]
+        {
+            let lua = self.lua.lock().unwrap();
+            let result = to_lua(&lua, &value).and_then(|v| lua.globals().set(name, v));
+            if let Err(e) = result {
+                tracing::warn!(target: "scripting", "Failed to set Lua global '{}': {}", name, e);
+            }
+        }
+
+        #[cfg(not(feature = "lua_scripting"))]
         self.variables.insert(name.to_string(), value);
     }
 
     /// 获取全局变量
-    pub fn get_global(&self, name: &str) -> Option<&LuaValue> {
-        self.variables.get(name)
+    ///
+    /// 启用 `lua_scripting` 时读取 Lua 虚拟机的全局表，包括脚本中赋值的变量；
+    /// 变量为 nil 或无法转换为 `LuaValue`（如函数）时返回 None。
+    pub fn get_global(&self, name: &str) -> Option<LuaValue> {
+        #[cfg(feature = "lua_scripting")]
+        {
+            let lua = self.lua.lock().unwrap();
+            let value = lua.globals().get::<_, mlua::Value>(name);
+            match value.map(from_lua) {
+                Ok(Ok(LuaValue::Nil)) | Ok(Err(_)) => None,
+                Ok(Ok(value)) => Some(value),
+                Err(e) => {
+                    tracing::warn!(target: "scripting", "Failed to get Lua global '{}': {}", name, e);
+                    None
+                }
+            }
+        }
+
+        #[cfg(not(feature = "lua_scripting"))]
+        self.variables.get(name).cloned()
     }
 
     /// 注册Rust函数到Lua
-    pub fn register_function<F>(&mut self, name: &str, _func: F)
+    pub fn register_function<F>(&mut self, name: &str, func: F)
     where
-        F: Fn(Vec<LuaValue>) -> Result<LuaValue, String> + 'static,
+        F: Fn(Vec<LuaValue>) -> Result<LuaValue, String> + Send + 'static,
     {
-        // 实际实现需要集成mlua或rlua库
-        // 这里只是一个占位
-        let _ = name;
+        #[cfg(feature = "lua_scripting")]
+        {
+            let lua = self.lua.lock().unwrap();
+            let result = lua
+                .create_function(move |lua, args: mlua::MultiValue| {
+                    let args = args
+                        .into_iter()
+                        .map(from_lua)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(mlua::Error::external)?;
+                    let value = func(args).map_err(mlua::Error::RuntimeError)?;
+                    to_lua(lua, &value)
+                })
+                .and_then(|function| lua.globals().set(name, function));
+            if let Err(e) = result {
+                tracing::warn!(target: "scripting", "Failed to register Lua function '{}': {}", name, e);
+            }
+        }
+
+        #[cfg(not(feature = "lua_scripting"))]
+        let _ = (name, func);
     }
 }
 
+/// `LuaValue` 转换为 Lua 值
+#[cfg(feature = "lua_scripting")]
+fn to_lua<'lua>(lua: &'lua mlua::Lua, value: &LuaValue) -> mlua::Result<mlua::Value<'lua>> {
+    Ok(match value {
+        LuaValue::Nil => mlua::Value::Nil,
+        LuaValue::Boolean(b) => mlua::Value::Boolean(*b),
+        LuaValue::Number(n) => mlua::Value::Number(*n),
+        LuaValue::String(s) => mlua::Value::String(lua.create_string(s)?),
+        LuaValue::Table(entries) => {
+            let table = lua.create_table()?;
+            for (key, value) in entries {
+                table.set(key.as_str(), to_lua(lua, value)?)?;
+            }
+            mlua::Value::Table(table)
+        }
+    })
+}
+
+/// Lua 值转换为 `LuaValue`，表的非字符串键转为字符串，函数等类型不支持
+#[cfg(feature = "lua_scripting")]
+fn from_lua(value: mlua::Value) -> Result<LuaValue, ScriptError> {
+    Ok(match value {
+        mlua::Value::Nil => LuaValue::Nil,
+        mlua::Value::Boolean(b) => LuaValue::Boolean(b),
+        mlua::Value::Integer(i) => LuaValue::Number(i as f64),
+        mlua::Value::Number(n) => LuaValue::Number(n),
+        mlua::Value::String(s) => LuaValue::String(s.to_string_lossy().into_owned()),
+        mlua::Value::Table(table) => {
+            let mut entries = HashMap::new();
+            for pair in table.pairs::<mlua::Value, mlua::Value>() {
+                let (key, value) = pair.map_err(|e| ScriptError::Runtime(e.to_string()))?;
+                let key = match key {
+                    mlua::Value::String(s) => s.to_string_lossy().into_owned(),
+                    mlua::Value::Integer(i) => i.to_string(),
+                    mlua::Value::Number(n) => n.to_string(),
+                    other => {
+                        return Err(ScriptError::InvalidBinding(format!(
+                            "Unsupported Lua table key type: {}",
+                            other.type_name()
+                        )))
+                    }
+                };
+                entries.insert(key, from_lua(value)?);
+            }
+            LuaValue::Table(entries)
+        }
+        other => {
+            return Err(ScriptError::InvalidBinding(format!(
+                "Unsupported Lua value type: {}",
+                other.type_name()
+            )))
+        }
+    })
+}
 
 /// Lua脚本引擎
 pub struct LuaEngine {
@@ -102,8 +258,8 @@ impl LuaEngine {
     pub fn call_function(
         &mut self,
         function_name: &str,
-        args: Vec<LuaValue>,
-    ) -> Result<LuaValue, String> {
+        args: &[LuaValue],
+    ) -> Result<LuaValue, ScriptError> {
         self.context.call_function(function_name, args)
     }
 
@@ -145,7 +301,6 @@ impl LuaEngine {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,7 +314,28 @@ mod tests {
 
         // 获取全局变量
         let value = context.get_global("test_var");
-        assert_eq!(value, Some(&LuaValue::Number(42.0)));
+        assert_eq!(value, Some(LuaValue::Number(42.0)));
+    }
+
+    #[test]
+    #[cfg(feature = "lua_scripting")]
+    fn test_lua_get_global_reads_script_assignments() {
+        let mut context = LuaContext::new();
+        context.set_global("score", LuaValue::Number(1.0));
+        context
+            .execute(
+                "update",
+                "score = score + 9; name = 'player'; function f() end",
+            )
+            .unwrap();
+
+        assert_eq!(context.get_global("score"), Some(LuaValue::Number(10.0)));
+        assert_eq!(
+            context.get_global("name"),
+            Some(LuaValue::String("player".to_string()))
+        );
+        assert_eq!(context.get_global("missing"), None);
+        assert_eq!(context.get_global("f"), None);
     }
 
     #[test]
@@ -173,4 +349,45 @@ mod tests {
         let result = engine.execute("test", "print('Hello from Lua!')");
         assert!(result.is_ok());
     }
+    #[test]
+    #[cfg(feature = "lua_scripting")]
+    fn test_lua_call_function() {
+        let mut context = LuaContext::new();
+        context
+            .execute("math", "function add(a, b) return a + b end")
+            .unwrap();
+
+        let result = context
+            .call_function("add", &[LuaValue::Number(2.0), LuaValue::Number(3.5)])
+            .unwrap();
+        assert_eq!(result, LuaValue::Number(5.5));
+
+        assert!(matches!(
+            context.call_function("missing", &[]),
+            Err(ScriptError::NotFound(_))
+        ));
+        context.set_global("speed", LuaValue::Number(1.0));
+        assert!(matches!(
+            context.call_function("speed", &[]),
+            Err(ScriptError::InvalidBinding(_))
+        ));
+        assert!(matches!(
+            context.call_function("error", &[LuaValue::String("boom".to_string())]),
+            Err(ScriptError::Runtime(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "lua_scripting")]
+    fn test_lua_registered_function() {
+        let mut context = LuaContext::new();
+        context.register_function("double", |args| match args.first() {
+            Some(LuaValue::Number(n)) => Ok(LuaValue::Number(n * 2.0)),
+            _ => Err("expected a number".to_string()),
+        });
+
+        let result = context.execute("test", "return double(21)").unwrap();
+        assert_eq!(result, LuaValue::Number(42.0));
+        assert!(context.execute("test", "return double('x')").is_err());
+    }
 }
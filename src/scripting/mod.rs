@@ -15,6 +15,7 @@ pub mod system;
 pub mod thread_safe;
 pub mod wasm_support;
 
+pub use api::{ApiVersion, ScriptApi};
 pub use engine::*;
 pub use lua_support::{LuaContext, LuaEngine, LuaValue};
 pub use rust_scripting::{RustScriptContext, RustScriptContextAdapter, RustScriptEngine};
@@ -22,6 +23,11 @@ pub use system::{JavaScriptContext, PythonContext};
 pub use system::{ScriptContext, ScriptLanguage, ScriptResult, ScriptSystem, ScriptValue};
 
 use bevy_ecs::prelude::*;
+use ecs_bindings::EcsScriptBindings;
+use extended_bindings::ExtendedEcsBindings;
+use graphics_ui_bindings::GraphicsUiBindings;
+use physics_audio_bindings::PhysicsAudioBindings;
+use std::sync::{Arc, Mutex};
 
 /// 脚本系统配置
 #[derive(Debug, Clone)]
@@ -56,14 +62,28 @@ pub struct ScriptingResource {
     pub lua_engine: Option<LuaEngine>,
     pub rust_engine: Option<RustScriptEngine>,
     pub config: ScriptingConfig,
+    /// 由各绑定填充的脚本API，其版本与功能集合会安装到各脚本上下文
+    pub api: ScriptApi,
+    /// 脚本操作的ECS世界及Lua组件操作的延迟缓冲
+    pub ecs_bindings: EcsScriptBindings,
+    /// 各绑定共享的世界，`scripting_system` 执行脚本期间与应用世界交换
+    pub script_world: Arc<Mutex<World>>,
 }
 
-impl_default!(ScriptingResource {
-    system: ScriptSystem::new(),
-    lua_engine: None,
-    rust_engine: None,
-    config: ScriptingConfig::default(),
-});
+impl Default for ScriptingResource {
+    fn default() -> Self {
+        let script_world = Arc::new(Mutex::new(World::new()));
+        Self {
+            system: ScriptSystem::new(),
+            lua_engine: None,
+            rust_engine: None,
+            config: ScriptingConfig::default(),
+            api: ScriptApi::new(),
+            ecs_bindings: EcsScriptBindings::new(script_world.clone()),
+            script_world,
+        }
+    }
+}
 
 /// 脚本组件 - 附加到需要脚本行为的实体
 #[derive(Component, Debug, Clone)]
@@ -92,24 +112,30 @@ pub enum ScriptExecutionFrequency {
 }
 
 /// 脚本系统
-pub fn scripting_system(
-    mut scripting: ResMut<ScriptingResource>,
-    query: Query<(Entity, &ScriptComponent)>,
-    time: Res<crate::ecs::Time>,
-) {
-    // 执行所有启用的脚本组件
-    for (entity, script) in query.iter() {
-        if !script.enabled {
-            continue;
-        }
+///
+/// 独占系统：执行脚本期间把应用世界交换进绑定共享的 `script_world`，
+/// 使脚本的读取、实体操作和延迟组件操作都作用于应用世界，结束后再交换回来。
+pub fn scripting_system(world: &mut World) {
+    let Some(mut scripting) = world.remove_resource::<ScriptingResource>() else {
+        return;
+    };
 
-        // 检查执行频率
-        if !should_execute_script(script, &time) {
-            continue;
-        }
+    // 收集本帧需要执行的脚本组件
+    let mut query = world.query::<(Entity, &ScriptComponent)>();
+    let scripts: Vec<(Entity, ScriptComponent)> = match world.get_resource::<crate::ecs::Time>() {
+        Some(time) => query
+            .iter(world)
+            .filter(|(_, script)| script.enabled && should_execute_script(script, time))
+            .map(|(entity, script)| (entity, script.clone()))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let script_world = scripting.script_world.clone();
+    std::mem::swap(world, &mut *script_world.lock().unwrap());
 
-        // 执行脚本
-        let result = execute_script(&mut scripting, script, entity);
+    for (entity, script) in &scripts {
+        let result = execute_script(&mut scripting, script, *entity);
         if let Err(e) = result {
             tracing::error!(target: "scripting", "Script execution error for entity {:?}: {}", entity, e);
         }
@@ -121,10 +147,16 @@ pub fn scripting_system(
         let _ = lua_engine;
     }
 
+    // 脚本执行期间不持有世界锁，组件操作统一在脚本执行结束后应用
+    scripting.ecs_bindings.apply_commands();
+
     // 执行Rust脚本引擎更新
     if let Some(ref mut rust_engine) = scripting.rust_engine {
         rust_engine.update();
     }
+
+    std::mem::swap(world, &mut *script_world.lock().unwrap());
+    world.insert_resource(scripting);
 }
 
 /// 检查脚本是否应该执行
@@ -146,6 +178,8 @@ fn execute_script(
     script: &ScriptComponent,
     entity: Entity,
 ) -> Result<(), String> {
+    ApiVersion::check_script(&script.script_source).map_err(|e| e.to_string())?;
+
     match script.language {
         ScriptLanguage::Lua => {
             if let Some(ref mut lua_engine) = scripting.lua_engine {
@@ -205,17 +239,33 @@ fn execute_script(
 
 /// 初始化脚本系统
 pub fn setup_scripting(world: &mut World, config: ScriptingConfig) {
+    // 由各绑定注册函数与功能，绑定共享的世界在脚本执行期间与应用世界交换
+    let script_world = Arc::new(Mutex::new(World::new()));
+    let mut api = ScriptApi::new();
+    let ecs_bindings = EcsScriptBindings::new(script_world.clone());
+    ecs_bindings.register_api(&mut api);
+    ExtendedEcsBindings::new(script_world.clone()).register_api(&mut api);
+    GraphicsUiBindings::new(script_world.clone()).register_api(&mut api);
+    PhysicsAudioBindings::new(script_world.clone()).register_api(&mut api);
+
     let mut resource = ScriptingResource {
         system: ScriptSystem::new(),
         lua_engine: None,
         rust_engine: None,
         config: config.clone(),
+        api,
+        ecs_bindings,
+        script_world,
     };
 
     // 初始化Lua引擎
     if config.enable_lua {
         let mut lua_engine = LuaEngine::new();
         lua_engine.register_engine_api();
+        resource.api.install_lua_globals(&mut lua_engine.context);
+        resource
+            .ecs_bindings
+            .register_lua_api(&mut lua_engine.context);
         resource.lua_engine = Some(lua_engine);
     }
 
@@ -236,7 +286,7 @@ pub fn setup_scripting(world: &mut World, config: ScriptingConfig) {
     if config.enable_javascript {
         resource.system.register_context(
             ScriptLanguage::JavaScript,
-            Box::new(JavaScriptContext::new()),
+            Box::new(JavaScriptContext::with_api(&resource.api)),
         );
     }
 
@@ -17,6 +17,10 @@ impl PhysicsAudioBindings {
 
     /// 注册物理和音频相关的脚本API
     pub fn register_api(&self, api: &mut ScriptApi) {
+        api.register_capability("physics.velocity");
+        api.register_capability("physics.raycast");
+        api.register_capability("audio");
+
         // 物理相关API
         self.register_physics_api(api);
 
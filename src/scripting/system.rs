@@ -1,5 +1,9 @@
+use super::api::{ApiVersion, ScriptApi};
 use std::collections::HashMap;
-use std::sync::{mpsc, Arc, Mutex};
+#[cfg(feature = "js_scripting")]
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "js_scripting")]
 use std::thread;
 
 /// 脚本语言类型
@@ -69,7 +73,12 @@ impl ScriptSystem {
     }
 
     /// 执行脚本
+    ///
+    /// 脚本声明的API版本高于引擎版本时拒绝执行。
     pub fn execute(&self, language: ScriptLanguage, code: &str) -> ScriptResult {
+        if let Err(e) = ApiVersion::check_script(code) {
+            return ScriptResult::Error(e.to_string());
+        }
         let mut contexts = self.contexts.lock().unwrap();
         if let Some(context) = contexts.get_mut(&language) {
             context.execute(code)
@@ -134,6 +143,7 @@ impl Default for ScriptSystem {
 }
 
 /// JavaScript执行请求
+#[cfg(feature = "js_scripting")]
 enum JsCommand {
     Execute(String, mpsc::Sender<ScriptResult>),
     CallFunction(String, Vec<ScriptValue>, mpsc::Sender<ScriptResult>),
@@ -146,14 +156,22 @@ enum JsCommand {
 /// JavaScript上下文 - 基于rquickjs的线程安全实现
 ///
 /// 使用QuickJS引擎在专用线程中执行JavaScript代码，
-/// 通过channel通信保证线程安全
+/// 通过channel通信保证线程安全。需要启用 `js_scripting` 特性。
+#[cfg(feature = "js_scripting")]
 pub struct JavaScriptContext {
     sender: mpsc::Sender<JsCommand>,
     globals_cache: Arc<Mutex<HashMap<String, ScriptValue>>>,
 }
 
+#[cfg(feature = "js_scripting")]
 impl JavaScriptContext {
     pub fn new() -> Self {
+        Self::with_api(&ScriptApi::new())
+    }
+
+    /// 创建上下文，并暴露 `API_VERSION` 全局变量和基于 `api` 功能集合的 `has_capability(name)`
+    pub fn with_api(api: &ScriptApi) -> Self {
+        let capabilities = api.capability_set();
         let (tx, rx) = mpsc::channel::<JsCommand>();
         let globals_cache = Arc::new(Mutex::new(HashMap::new()));
         let globals_clone = Arc::clone(&globals_cache);
@@ -228,6 +246,44 @@ impl JavaScriptContext {
                     );
                     let _ = global.set("console", console_obj);
                 }
+
+                // 与Lua一致的引擎API全局函数
+                let _ = global.set(
+                    "spawn_entity",
+                    Function::new(ctx.clone(), || -> f64 {
+                        // 实际实现需要访问ECS World
+                        0.0
+                    }),
+                );
+                let _ = global.set(
+                    "despawn_entity",
+                    Function::new(ctx.clone(), |_entity: f64| {}),
+                );
+                let _ = global.set(
+                    "add_component",
+                    Function::new(ctx.clone(), |_entity: f64, _component: String| {}),
+                );
+                let _ = global.set(
+                    "get_component",
+                    Function::new(ctx.clone(), |_entity: f64, _component: String| {}),
+                );
+                let _ = global.set(
+                    "is_key_pressed",
+                    Function::new(ctx.clone(), |_key: String| -> bool {
+                        // 实际实现需要访问输入系统
+                        false
+                    }),
+                );
+                let _ = global.set("play_sound", Function::new(ctx.clone(), |_path: String| {}));
+
+                // 与Lua一致的版本与功能查询
+                let _ = global.set("API_VERSION", ApiVersion::CURRENT.to_string());
+                let _ = global.set(
+                    "has_capability",
+                    Function::new(ctx.clone(), move |name: String| -> bool {
+                        super::api::contains(&capabilities, &name)
+                    }),
+                );
             });
 
             // 消息循环
@@ -337,6 +393,7 @@ impl JavaScriptContext {
     }
 }
 
+#[cfg(feature = "js_scripting")]
 impl ScriptContext for JavaScriptContext {
     fn execute(&mut self, code: &str) -> ScriptResult {
         let (tx, rx) = mpsc::channel();
@@ -392,12 +449,69 @@ impl ScriptContext for JavaScriptContext {
     }
 }
 
+#[cfg(feature = "js_scripting")]
 impl Default for JavaScriptContext {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// JavaScript上下文 - 未启用 `js_scripting` 特性时的占位实现
+///
+/// 所有执行请求均返回错误，提示需要启用该特性。
+#[cfg(not(feature = "js_scripting"))]
+#[derive(Default)]
+pub struct JavaScriptContext {
+    globals: HashMap<String, ScriptValue>,
+}
+
+#[cfg(not(feature = "js_scripting"))]
+impl JavaScriptContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 与启用特性时的接口一致，只记录 `API_VERSION` 全局变量
+    pub fn with_api(_api: &ScriptApi) -> Self {
+        let mut context = Self::default();
+        context.globals.insert(
+            "API_VERSION".to_string(),
+            ScriptValue::String(ApiVersion::CURRENT.to_string()),
+        );
+        context
+    }
+
+    fn disabled() -> ScriptResult {
+        ScriptResult::Error(
+            "JavaScript scripting is disabled; enable the `js_scripting` feature".to_string(),
+        )
+    }
+}
+
+#[cfg(not(feature = "js_scripting"))]
+impl ScriptContext for JavaScriptContext {
+    fn execute(&mut self, _code: &str) -> ScriptResult {
+        Self::disabled()
+    }
+
+    fn call_function(&mut self, _name: &str, _args: &[ScriptValue]) -> ScriptResult {
+        Self::disabled()
+    }
+
+    fn set_global(&mut self, name: &str, value: ScriptValue) -> ScriptResult {
+        self.globals.insert(name.to_string(), value);
+        ScriptResult::Void
+    }
+
+    fn get_global(&self, name: &str) -> Option<ScriptValue> {
+        self.globals.get(name).cloned()
+    }
+
+    fn reset(&mut self) {
+        self.globals.clear();
+    }
+}
+
 /// Python上下文的简单实现 (占位)
 #[derive(Default)]
 pub struct PythonContext {
@@ -434,12 +548,12 @@ impl ScriptContext for PythonContext {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "js_scripting")]
     fn test_script_system() {
         let system = ScriptSystem::new();
 
@@ -488,6 +602,56 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "js_scripting")]
+    fn test_javascript_numeric_result() {
+        let mut context = JavaScriptContext::new();
+
+        match context.execute("1 + 2") {
+            ScriptResult::Success(value) => assert_eq!(value.parse::<f64>().unwrap(), 3.0),
+            other => panic!("Expected numeric result, got {:?}", other),
+        }
+
+        // 引擎API以全局函数形式暴露
+        let result = context.execute("typeof spawn_entity + ',' + is_key_pressed('Space')");
+        assert!(
+            matches!(result, ScriptResult::Success(ref s) if s == "function,false"),
+            "Unexpected result {:?}",
+            result
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "js_scripting")]
+    fn test_javascript_api_version_and_capabilities() {
+        let mut api = ScriptApi::new();
+        let mut context = JavaScriptContext::with_api(&api);
+        // 创建上下文之后声明的功能同样可见
+        api.register_capability("physics.raycast");
+
+        let result = context.execute(
+            "API_VERSION + ',' + has_capability('physics.raycast') + ',' + has_capability('net')",
+        );
+        let expected = format!("{},true,false", ApiVersion::CURRENT);
+        assert!(
+            matches!(result, ScriptResult::Success(ref s) if *s == expected),
+            "Unexpected result {:?}",
+            result
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "js_scripting"))]
+    fn test_javascript_disabled() {
+        let mut context = JavaScriptContext::new();
+        let result = context.execute("1 + 2");
+        assert!(
+            matches!(result, ScriptResult::Error(ref e) if e.contains("js_scripting")),
+            "Expected disabled error, got {:?}",
+            result
+        );
+    }
+
     #[test]
     fn test_script_system_rust_language() {
         use super::super::rust_scripting::RustScriptContextAdapter;
@@ -1,4 +1,12 @@
 use std::collections::HashMap;
+#[cfg(feature = "wasm_scripting")]
+use {
+    super::system::{ScriptResult, ScriptValue},
+    crate::core::error::ScriptError,
+    bevy_ecs::world::World,
+    std::sync::{Arc, Mutex},
+    wasmtime::{Caller, Engine, Instance, Linker, Module, Store, Val, ValType},
+};
 
 /// WASM模块
 pub struct WasmModule {
@@ -158,6 +166,211 @@ impl WasmRuntime {
     }
 }
 
+/// 已加载模块的句柄
+#[cfg(feature = "wasm_scripting")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModuleHandle(usize);
+
+/// WASM宿主状态，供宿主函数访问引擎
+#[cfg(feature = "wasm_scripting")]
+#[derive(Default)]
+pub struct WasmHostState {
+    world: Option<Arc<Mutex<World>>>,
+    /// 模块通过 `env.log` 输出的消息
+    logs: Vec<String>,
+}
+
+/// 基于wasmtime的WASM脚本引擎
+///
+/// 模块可导入以下宿主函数（模块名 `env`）：
+/// - `log(ptr: i32, len: i32)`：输出模块导出内存 `memory` 中的UTF-8字符串
+/// - `spawn() -> i64`：创建空实体并返回实体ID，未关联世界时返回 -1
+///
+/// 需要启用 `wasm_scripting` 特性。
+#[cfg(feature = "wasm_scripting")]
+pub struct WasmScriptEngine {
+    engine: Engine,
+    linker: Linker<WasmHostState>,
+    store: Store<WasmHostState>,
+    instances: Vec<Instance>,
+}
+
+#[cfg(feature = "wasm_scripting")]
+impl WasmScriptEngine {
+    pub fn new() -> Self {
+        Self::with_state(WasmHostState::default())
+    }
+
+    /// 创建可通过 `spawn` 访问世界的引擎，调用导出函数期间不能持有世界锁
+    pub fn with_world(world: Arc<Mutex<World>>) -> Self {
+        Self::with_state(WasmHostState {
+            world: Some(world),
+            ..Default::default()
+        })
+    }
+
+    fn with_state(state: WasmHostState) -> Self {
+        let engine = Engine::default();
+        let mut linker = Linker::new(&engine);
+        Self::register_host_functions(&mut linker);
+        let store = Store::new(&engine, state);
+        Self {
+            engine,
+            linker,
+            store,
+            instances: Vec::new(),
+        }
+    }
+
+    fn register_host_functions(linker: &mut Linker<WasmHostState>) {
+        let result = linker
+            .func_wrap(
+                "env",
+                "log",
+                |mut caller: Caller<'_, WasmHostState>,
+                 ptr: i32,
+                 len: i32|
+                 -> wasmtime::Result<()> {
+                    let memory = caller
+                        .get_export("memory")
+                        .and_then(|export| export.into_memory())
+                        .ok_or_else(|| wasmtime::Error::msg("log() requires an exported memory"))?;
+                    let start = ptr as u32 as usize;
+                    let bytes = memory
+                        .data(&caller)
+                        .get(start..start.saturating_add(len as u32 as usize))
+                        .ok_or_else(|| wasmtime::Error::msg("log() string out of bounds"))?;
+                    let message = String::from_utf8_lossy(bytes).into_owned();
+                    tracing::info!(target: "scripting", "[WASM]: {}", message);
+                    caller.data_mut().logs.push(message);
+                    Ok(())
+                },
+            )
+            .and_then(|linker| {
+                linker.func_wrap("env", "spawn", |caller: Caller<'_, WasmHostState>| -> i64 {
+                    caller.data().world.as_ref().map_or(-1, |world| {
+                        world.lock().unwrap().spawn_empty().id().to_bits() as i64
+                    })
+                })
+            });
+        if let Err(e) = result {
+            tracing::error!(target: "scripting", "Failed to register WASM host functions: {}", e);
+        }
+    }
+
+    /// 编译并实例化模块，接受二进制或WAT文本
+    ///
+    /// 实例化前校验模块的所有导入，缺少宿主函数时返回错误。
+    pub fn load_module(&mut self, bytes: &[u8]) -> Result<ModuleHandle, ScriptError> {
+        let module = Module::new(&self.engine, bytes)
+            .map_err(|e| ScriptError::Compilation(e.to_string()))?;
+
+        for import in module.imports() {
+            if self
+                .linker
+                .get(&mut self.store, import.module(), import.name())
+                .is_none()
+            {
+                return Err(ScriptError::InvalidBinding(format!(
+                    "Missing host function {}::{}",
+                    import.module(),
+                    import.name()
+                )));
+            }
+        }
+
+        let instance = self
+            .linker
+            .instantiate(&mut self.store, &module)
+            .map_err(|e| ScriptError::InvalidBinding(e.to_string()))?;
+        self.instances.push(instance);
+        Ok(ModuleHandle(self.instances.len() - 1))
+    }
+
+    /// 调用模块的导出函数
+    ///
+    /// 参数按函数签名转换：整数和布尔值可传给整数参数，数值可传给浮点参数。
+    /// 无返回值时返回 `Void`，多个返回值以逗号分隔。
+    pub fn call_export(
+        &mut self,
+        handle: ModuleHandle,
+        name: &str,
+        args: &[ScriptValue],
+    ) -> ScriptResult {
+        let Some(instance) = self.instances.get(handle.0).copied() else {
+            return ScriptResult::Error(format!("Invalid module handle {:?}", handle));
+        };
+        let Some(func) = instance.get_func(&mut self.store, name) else {
+            return ScriptResult::Error(format!("Export '{}' is not a function", name));
+        };
+
+        let ty = func.ty(&self.store);
+        if ty.params().len() != args.len() {
+            return ScriptResult::Error(format!(
+                "{}() expects {} arguments, got {}",
+                name,
+                ty.params().len(),
+                args.len()
+            ));
+        }
+        let params = match ty
+            .params()
+            .zip(args)
+            .map(|(ty, arg)| to_wasm_val(&ty, arg))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(params) => params,
+            Err(e) => return ScriptResult::Error(format!("{}(): {}", name, e)),
+        };
+
+        let mut results = vec![Val::I32(0); ty.results().len()];
+        if let Err(e) = func.call(&mut self.store, &params, &mut results) {
+            return ScriptResult::Error(format!("{}() trapped: {}", name, e));
+        }
+
+        if results.is_empty() {
+            return ScriptResult::Void;
+        }
+        let values: Vec<String> = results
+            .iter()
+            .map(|value| match value {
+                Val::I32(v) => v.to_string(),
+                Val::I64(v) => v.to_string(),
+                Val::F32(bits) => f32::from_bits(*bits).to_string(),
+                Val::F64(bits) => f64::from_bits(*bits).to_string(),
+                _ => "[reference]".to_string(),
+            })
+            .collect();
+        ScriptResult::Success(values.join(","))
+    }
+
+    /// 模块通过 `env.log` 输出的消息
+    pub fn logs(&self) -> &[String] {
+        &self.store.data().logs
+    }
+}
+
+#[cfg(feature = "wasm_scripting")]
+impl Default for WasmScriptEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "wasm_scripting")]
+fn to_wasm_val(ty: &ValType, arg: &ScriptValue) -> Result<Val, String> {
+    Ok(match (ty, arg) {
+        (ValType::I32, ScriptValue::Int(v)) => Val::I32(*v as i32),
+        (ValType::I32, ScriptValue::Bool(b)) => Val::I32(*b as i32),
+        (ValType::I64, ScriptValue::Int(v)) => Val::I64(*v),
+        (ValType::F32, ScriptValue::Float(v)) => Val::F32((*v as f32).to_bits()),
+        (ValType::F32, ScriptValue::Int(v)) => Val::F32((*v as f32).to_bits()),
+        (ValType::F64, ScriptValue::Float(v)) => Val::F64(v.to_bits()),
+        (ValType::F64, ScriptValue::Int(v)) => Val::F64((*v as f64).to_bits()),
+        (ty, arg) => return Err(format!("cannot pass {:?} as {}", arg, ty)),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,4 +393,66 @@ mod tests {
         let result = runtime.load_module("test", vec![0, 1, 2, 3]);
         assert!(result.is_ok());
     }
+    #[test]
+    #[cfg(feature = "wasm_scripting")]
+    fn test_wasm_script_engine_call_export() {
+        let world = Arc::new(Mutex::new(World::new()));
+        let mut engine = WasmScriptEngine::with_world(world.clone());
+        let wat = r#"
+            (module
+                (import "env" "log" (func $log (param i32 i32)))
+                (import "env" "spawn" (func $spawn (result i64)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "hello")
+                (func (export "add") (param i32 i32) (result i32)
+                    local.get 0
+                    local.get 1
+                    i32.add)
+                (func (export "greet") (result i64)
+                    i32.const 0
+                    i32.const 5
+                    call $log
+                    call $spawn))
+        "#;
+        let handle = engine.load_module(wat.as_bytes()).unwrap();
+
+        let result = engine.call_export(handle, "add", &[ScriptValue::Int(2), ScriptValue::Int(3)]);
+        assert!(
+            matches!(result, ScriptResult::Success(ref s) if s == "5"),
+            "Expected 5, got {:?}",
+            result
+        );
+
+        let result = engine.call_export(handle, "greet", &[]);
+        let ScriptResult::Success(id) = result else {
+            panic!("Expected entity id, got {:?}", result);
+        };
+        let entity = bevy_ecs::entity::Entity::from_bits(id.parse().unwrap());
+        assert!(world.lock().unwrap().get_entity(entity).is_some());
+        assert_eq!(engine.logs(), ["hello"]);
+
+        assert!(matches!(
+            engine.call_export(handle, "missing", &[]),
+            ScriptResult::Error(_)
+        ));
+        assert!(matches!(
+            engine.call_export(handle, "add", &[ScriptValue::Int(1)]),
+            ScriptResult::Error(_)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "wasm_scripting")]
+    fn test_wasm_script_engine_missing_import() {
+        let mut engine = WasmScriptEngine::new();
+        let wat = r#"(module (import "env" "teleport" (func)))"#;
+        assert!(matches!(
+            engine.load_module(wat.as_bytes()),
+            Err(ScriptError::InvalidBinding(msg)) if msg.contains("env::teleport")
+        ));
+        assert!(matches!(
+            engine.load_module(b"not wasm"),
+            Err(ScriptError::Compilation(_))
+        ));
+    }
 }
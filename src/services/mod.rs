@@ -1,5 +1,6 @@
 pub mod audio;
 pub mod render;
+#[cfg(feature = "js_scripting")]
 pub mod scripting;
 
 #[cfg(test)]
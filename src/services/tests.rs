@@ -1483,15 +1483,15 @@ mod domain_service_tests {
 
         // 解析服务
         let audio_service = container.resolve::<AudioDomainService>();
-        assert!(audio_service.is_some());
+        assert!(audio_service.is_ok());
 
         let physics_service = container.resolve::<PhysicsDomainService>();
-        assert!(physics_service.is_some());
+        assert!(physics_service.is_ok());
 
         // 解析未注册的服务
         assert!(!container.is_registered::<SceneManager>());
         let scene_service = container.resolve::<SceneManager>();
-        assert!(scene_service.is_none());
+        assert!(scene_service.is_err());
     }
 
     #[test]
@@ -1551,10 +1551,10 @@ mod domain_service_tests {
 
         // 验证可以解析
         let resolved = container.resolve::<AudioDomainService>();
-        assert!(resolved.is_some());
+        assert!(resolved.is_ok());
 
         // 验证是同一个实例（Arc指针比较）
-        if let Some(resolved_service) = resolved {
+        if let Ok(resolved_service) = resolved {
             assert!(Arc::ptr_eq(&service, &resolved_service));
         }
     }
@@ -1579,7 +1579,7 @@ mod domain_service_tests {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "js_scripting"))]
 mod scripting_service_tests {
     use super::super::scripting::*;
 
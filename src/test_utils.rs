@@ -0,0 +1,42 @@
+//! 单元测试共享的辅助函数
+
+/// 创建无窗口的 GPU 设备与队列
+///
+/// 没有可用适配器或设备创建失败时打印提示并返回 `None`，调用方据此跳过测试。
+pub(crate) fn headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    headless_device_with_features(wgpu::Features::empty())
+}
+
+/// 创建开启指定特性的无窗口 GPU 设备与队列
+///
+/// 适配器不支持所需特性时同样打印提示并返回 `None`。
+pub(crate) fn headless_device_with_features(
+    features: wgpu::Features,
+) -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+    let Some(adapter) =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+    else {
+        eprintln!("No GPU adapter found, skipping");
+        return None;
+    };
+    if !adapter.features().contains(features) {
+        eprintln!("GPU adapter lacks {:?}, skipping", features);
+        return None;
+    }
+    let desc = wgpu::DeviceDescriptor {
+        required_features: features,
+        ..Default::default()
+    };
+    match pollster::block_on(adapter.request_device(&desc, None)) {
+        Ok(pair) => Some(pair),
+        Err(e) => {
+            eprintln!("Failed to create GPU device ({}), skipping", e);
+            None
+        }
+    }
+}
@@ -47,6 +47,8 @@ pub struct HandTracker {
     last_update_time: u64,
     /// 追踪状态
     tracking_state: HandTrackingState,
+    /// 上一次识别的手势（左、右），用于滞后判定
+    last_gestures: [HandGesture; 2],
 }
 
 /// 手部关节集合
@@ -190,6 +192,72 @@ impl HandJoints {
     }
 }
 
+/// 捏合完全闭合时拇指尖与食指尖的距离（米）
+pub const PINCH_CLOSED_DISTANCE: f32 = 0.015;
+/// 捏合强度为 0 时拇指尖与食指尖的距离（米）
+pub const PINCH_OPEN_DISTANCE: f32 = 0.08;
+/// 握拳完全闭合时中指、无名指、小指指尖到手掌的平均距离（米）
+pub const GRAB_CLOSED_DISTANCE: f32 = 0.035;
+/// 握拳强度为 0 时指尖到手掌的平均距离（米）
+pub const GRAB_OPEN_DISTANCE: f32 = 0.09;
+/// 食指尖到手掌超过此距离视为伸直（米）
+pub const INDEX_EXTENDED_DISTANCE: f32 = 0.07;
+/// 进入手势所需的强度
+pub const GESTURE_ENGAGE_STRENGTH: f32 = 0.8;
+/// 保持手势所需的最低强度，低于进入阈值以避免在阈值附近闪烁
+pub const GESTURE_RELEASE_STRENGTH: f32 = 0.6;
+
+/// 识别出的手势
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HandGesture {
+    /// 拇指与食指捏合
+    Pinch { strength: f32 },
+    /// 握拳
+    Grab { strength: f32 },
+    /// 食指伸直、其余手指弯曲
+    Point,
+    /// 张开或未识别
+    Open,
+}
+
+/// 将距离映射为 0..1 的强度，`closed` 处为 1，`open` 处为 0
+fn closure_strength(distance: f32, closed: f32, open: f32) -> f32 {
+    ((open - distance) / (open - closed)).clamp(0.0, 1.0)
+}
+
+impl HandJoints {
+    /// 捏合强度（拇指尖到食指尖距离）
+    pub fn pinch_strength(&self) -> Option<f32> {
+        let thumb = self.get_finger_tip(Finger::Thumb)?;
+        let index = self.get_finger_tip(Finger::Index)?;
+        Some(closure_strength(
+            thumb.distance(index),
+            PINCH_CLOSED_DISTANCE,
+            PINCH_OPEN_DISTANCE,
+        ))
+    }
+
+    /// 握拳强度（中指、无名指、小指指尖到手掌的平均距离）
+    pub fn grab_strength(&self) -> Option<f32> {
+        let palm = self.get_palm_position()?;
+        let mut total = 0.0;
+        for finger in [Finger::Middle, Finger::Ring, Finger::Little] {
+            total += self.get_finger_tip(finger)?.distance(palm);
+        }
+        Some(closure_strength(
+            total / 3.0,
+            GRAB_CLOSED_DISTANCE,
+            GRAB_OPEN_DISTANCE,
+        ))
+    }
+
+    /// 食指是否伸直
+    pub fn is_index_extended(&self) -> Option<bool> {
+        let palm = self.get_palm_position()?;
+        let index = self.get_finger_tip(Finger::Index)?;
+        Some(index.distance(palm) > INDEX_EXTENDED_DISTANCE)
+    }
+}
 
 /// 手指类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -228,6 +296,7 @@ impl HandTracker {
             right_hand_joints: HandJoints::new(),
             last_update_time: 0,
             tracking_state: HandTrackingState::Uninitialized,
+            last_gestures: [HandGesture::Open; 2],
         })
     }
 
@@ -338,6 +407,55 @@ impl HandTracker {
         }
     }
 
+    /// 识别手势
+    ///
+    /// 基于关节距离判定，已处于某手势时只需强度不低于
+    /// `GESTURE_RELEASE_STRENGTH` 即保持，新进入手势需达到 `GESTURE_ENGAGE_STRENGTH`。
+    /// 握拳优先于捏合，避免握拳时拇指与食指靠近被误判为捏合。
+    pub fn detect_gesture(&mut self, hand: Hand) -> HandGesture {
+        let slot = match hand {
+            Hand::Left => 0,
+            Hand::Right => 1,
+        };
+        let joints = match hand {
+            Hand::Left => &self.left_hand_joints,
+            Hand::Right => &self.right_hand_joints,
+        };
+        if !joints.is_valid() {
+            self.last_gestures[slot] = HandGesture::Open;
+            return HandGesture::Open;
+        }
+
+        let pinch = joints.pinch_strength().unwrap_or(0.0);
+        let grab = joints.grab_strength().unwrap_or(0.0);
+        let index_extended = joints.is_index_extended().unwrap_or(false);
+        let previous = self.last_gestures[slot];
+        let threshold = |held: bool| {
+            if held {
+                GESTURE_RELEASE_STRENGTH
+            } else {
+                GESTURE_ENGAGE_STRENGTH
+            }
+        };
+
+        let grabbing = grab >= threshold(matches!(previous, HandGesture::Grab { .. }));
+        let pinching = pinch >= threshold(matches!(previous, HandGesture::Pinch { .. }));
+        let pointing = grab >= threshold(previous == HandGesture::Point) && index_extended;
+
+        let gesture = if grabbing && !index_extended {
+            HandGesture::Grab { strength: grab }
+        } else if pinching {
+            HandGesture::Pinch { strength: pinch }
+        } else if pointing {
+            HandGesture::Point
+        } else {
+            HandGesture::Open
+        };
+
+        self.last_gestures[slot] = gesture;
+        gesture
+    }
+
     /// 手动设置手部关节数据（用于测试或模拟）
     pub fn set_hand_joints(&mut self, hand: Hand, joints: HandJoints) {
         match hand {
@@ -381,6 +499,7 @@ impl Default for HandTracker {
             right_hand_joints: HandJoints::new(),
             last_update_time: 0,
             tracking_state: HandTrackingState::Uninitialized,
+            last_gestures: [HandGesture::Open; 2],
         })
     }
 }
@@ -460,4 +579,92 @@ mod tests {
 
         assert!(tracker.get_hand_joints(Hand::Left).is_some());
     }
+
+    fn synthetic_hand(thumb_tip: Vec3, index_tip: Vec3, curl_distance: f32) -> HandJoints {
+        let mut joints = HandJoints::new();
+        let mut set = |joint_type: HandJointType, position: Vec3| {
+            joints.update_joint(
+                joint_type,
+                HandJoint {
+                    joint_type,
+                    pose: Pose {
+                        position,
+                        orientation: Quat::IDENTITY,
+                    },
+                    radius: 0.01,
+                    is_valid: true,
+                },
+            );
+        };
+        set(HandJointType::Palm, Vec3::ZERO);
+        set(HandJointType::ThumbTip, thumb_tip);
+        set(HandJointType::IndexTip, index_tip);
+        set(HandJointType::MiddleTip, Vec3::new(0.0, curl_distance, 0.0));
+        set(HandJointType::RingTip, Vec3::new(0.01, curl_distance, 0.0));
+        set(
+            HandJointType::LittleTip,
+            Vec3::new(0.02, curl_distance, 0.0),
+        );
+        joints.set_valid(true);
+        joints
+    }
+
+    #[test]
+    fn test_detect_pinch() {
+        let mut tracker = HandTracker::new().unwrap();
+        assert_eq!(tracker.detect_gesture(Hand::Right), HandGesture::Open);
+
+        // 拇指尖与食指尖相距1cm，其余手指张开
+        let joints = synthetic_hand(Vec3::new(0.04, 0.06, 0.0), Vec3::new(0.05, 0.06, 0.0), 0.1);
+        tracker.set_hand_joints(Hand::Right, joints);
+        match tracker.detect_gesture(Hand::Right) {
+            HandGesture::Pinch { strength } => assert!(strength > 0.95, "strength {strength}"),
+            other => panic!("expected pinch, got {other:?}"),
+        }
+        assert_eq!(tracker.detect_gesture(Hand::Left), HandGesture::Open);
+    }
+
+    #[test]
+    fn test_gesture_hysteresis() {
+        let mut tracker = HandTracker::new().unwrap();
+        // 强度约0.7：介于释放与进入阈值之间
+        let distance = PINCH_OPEN_DISTANCE - 0.7 * (PINCH_OPEN_DISTANCE - PINCH_CLOSED_DISTANCE);
+        let halfway = synthetic_hand(
+            Vec3::new(0.04, 0.06, 0.0),
+            Vec3::new(0.04 + distance, 0.06, 0.0),
+            0.1,
+        );
+
+        // 未捏合时不会进入
+        tracker.set_hand_joints(Hand::Left, halfway.clone());
+        assert_eq!(tracker.detect_gesture(Hand::Left), HandGesture::Open);
+
+        // 先完全捏合，再松开到同一位置时保持捏合
+        let closed = synthetic_hand(Vec3::new(0.04, 0.06, 0.0), Vec3::new(0.045, 0.06, 0.0), 0.1);
+        tracker.set_hand_joints(Hand::Left, closed);
+        assert!(matches!(
+            tracker.detect_gesture(Hand::Left),
+            HandGesture::Pinch { .. }
+        ));
+        tracker.set_hand_joints(Hand::Left, halfway);
+        assert!(matches!(
+            tracker.detect_gesture(Hand::Left),
+            HandGesture::Pinch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_detect_grab_and_point() {
+        let mut tracker = HandTracker::new().unwrap();
+        let fist = synthetic_hand(Vec3::new(0.03, 0.02, 0.0), Vec3::new(0.0, 0.03, 0.01), 0.03);
+        tracker.set_hand_joints(Hand::Right, fist);
+        assert!(matches!(
+            tracker.detect_gesture(Hand::Right),
+            HandGesture::Grab { .. }
+        ));
+
+        let point = synthetic_hand(Vec3::new(0.03, 0.02, 0.0), Vec3::new(0.0, 0.12, 0.0), 0.03);
+        tracker.set_hand_joints(Hand::Right, point);
+        assert_eq!(tracker.detect_gesture(Hand::Right), HandGesture::Point);
+    }
 }
@@ -21,6 +21,83 @@ pub struct XrInputManager {
     haptic_queue: Vec<HapticFeedback>,
     /// 控制器连接状态
     controller_connected: HashMap<Hand, bool>,
+    /// 按交互配置文件注册的动作绑定
+    action_bindings: HashMap<String, ActionBindingMap>,
+    /// 当前交互配置文件
+    interaction_profile: Option<String>,
+    /// 当前生效的动作绑定
+    active_bindings: ActionBindingMap,
+    /// 正在播放的触觉模式
+    haptic_playback: HashMap<Hand, HapticPlayback>,
+}
+
+/// 触觉模式中的一步
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HapticStep {
+    /// 振幅 (0.0 - 1.0)
+    pub amplitude: f32,
+    /// 震动持续时间 (纳秒)
+    pub duration_ns: i64,
+    /// 震动结束后到下一步开始的间隔 (纳秒)
+    pub gap_ns: i64,
+}
+
+/// 预先编排的触觉模式，由若干震动步骤组成
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HapticPattern {
+    pub steps: Vec<HapticStep>,
+}
+
+impl HapticPattern {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一步
+    pub fn step(mut self, amplitude: f32, duration_ns: i64, gap_ns: i64) -> Self {
+        self.steps.push(HapticStep {
+            amplitude,
+            duration_ns,
+            gap_ns,
+        });
+        self
+    }
+
+    /// 两次短促震动
+    pub fn double_tap(amplitude: f32) -> Self {
+        Self::new()
+            .step(amplitude, 40_000_000, 60_000_000)
+            .step(amplitude, 40_000_000, 0)
+    }
+
+    /// 振幅从 `from` 线性渐变到 `to`，共 `steps` 步，无间隔
+    pub fn ramp(from: f32, to: f32, steps: usize, total_duration_ns: i64) -> Self {
+        let steps = steps.max(1);
+        let step_duration = total_duration_ns / steps as i64;
+        (0..steps).fold(Self::new(), |pattern, i| {
+            let t = if steps == 1 {
+                1.0
+            } else {
+                i as f32 / (steps - 1) as f32
+            };
+            pattern.step(from + (to - from) * t, step_duration, 0)
+        })
+    }
+
+    /// 模式总时长 (纳秒)
+    pub fn total_duration_ns(&self) -> i64 {
+        self.steps.iter().map(|s| s.duration_ns + s.gap_ns).sum()
+    }
+}
+
+/// 触觉模式播放进度
+#[derive(Debug, Clone)]
+struct HapticPlayback {
+    pattern: HapticPattern,
+    /// 下一个待触发的步骤
+    next_step: usize,
+    /// 距离下一步触发的剩余时间 (纳秒)
+    wait_ns: i64,
 }
 
 /// 手部追踪数据
@@ -205,6 +282,59 @@ impl XrInputManager {
         });
     }
 
+    /// 播放触觉模式，会取消该手上正在播放的模式
+    ///
+    /// 第一步立即触发，后续步骤由 `update_haptics` 按时间推进。
+    pub fn play_haptic(&mut self, hand: Hand, pattern: HapticPattern) {
+        self.stop_haptic(hand);
+        self.haptic_playback.insert(
+            hand,
+            HapticPlayback {
+                pattern,
+                next_step: 0,
+                wait_ns: 0,
+            },
+        );
+        self.update_haptics_for(hand, 0);
+    }
+
+    /// 停止该手上正在播放的触觉模式，并丢弃该手尚未取走的震动
+    pub fn stop_haptic(&mut self, hand: Hand) {
+        self.haptic_playback.remove(&hand);
+        self.haptic_queue.retain(|feedback| feedback.hand != hand);
+    }
+
+    /// 是否有触觉模式正在播放
+    pub fn is_haptic_playing(&self, hand: Hand) -> bool {
+        self.haptic_playback.contains_key(&hand)
+    }
+
+    /// 推进触觉模式播放，每帧调用
+    pub fn update_haptics(&mut self, delta_ns: i64) {
+        for hand in [Hand::Left, Hand::Right] {
+            self.update_haptics_for(hand, delta_ns);
+        }
+    }
+
+    fn update_haptics_for(&mut self, hand: Hand, delta_ns: i64) {
+        let Some(mut playback) = self.haptic_playback.remove(&hand) else {
+            return;
+        };
+        playback.wait_ns -= delta_ns;
+        // 一帧内可能跨过多个步骤
+        while playback.wait_ns <= 0 {
+            let Some(step) = playback.pattern.steps.get(playback.next_step).copied() else {
+                return;
+            };
+            self.vibrate(hand, step.amplitude, step.duration_ns);
+            playback.next_step += 1;
+            playback.wait_ns += step.duration_ns + step.gap_ns;
+        }
+        if playback.next_step < playback.pattern.steps.len() {
+            self.haptic_playback.insert(hand, playback);
+        }
+    }
+
     /// 检查按钮是否按下
     pub fn is_button_pressed(&self, hand: Hand, button: ControllerButton) -> bool {
         if let Some(state) = self.get_controller(hand) {
@@ -254,6 +384,74 @@ impl XrInputManager {
     pub fn get_grip_pose(&self, hand: Hand) -> Option<Pose> {
         self.get_controller(hand).map(|s| s.grip_pose)
     }
+
+    /// 为交互配置文件设置动作绑定，若为当前配置文件则立即生效
+    pub fn set_action_bindings(&mut self, profile: impl Into<String>, bindings: ActionBindingMap) {
+        let profile = profile.into();
+        self.action_bindings.insert(profile.clone(), bindings);
+        if self.interaction_profile.as_deref() == Some(profile.as_str()) {
+            self.rebind_actions();
+        }
+    }
+
+    /// 切换当前交互配置文件并重新绑定动作
+    pub fn set_interaction_profile(&mut self, profile: impl Into<String>) {
+        self.interaction_profile = Some(profile.into());
+        self.rebind_actions();
+    }
+
+    /// 当前交互配置文件
+    pub fn interaction_profile(&self) -> Option<&str> {
+        self.interaction_profile.as_deref()
+    }
+
+    /// 处理 XR 运行时事件
+    pub fn handle_xr_event(&mut self, event: &XrEvent) {
+        if let XrEvent::InteractionProfileChanged { profile } = event {
+            self.set_interaction_profile(profile.clone());
+        }
+    }
+
+    /// 动作是否处于激活状态（任一绑定按钮按下）
+    pub fn is_action_active(&self, name: &str) -> bool {
+        self.active_bindings
+            .bindings(name)
+            .iter()
+            .any(|b| self.is_button_pressed(b.hand, b.button))
+    }
+
+    /// 动作的模拟值 (0.0 - 1.0)
+    ///
+    /// 绑定到扳机/握把时取对应的模拟量，其余按钮按下为 1.0；多个绑定取最大值。
+    pub fn action_value(&self, name: &str) -> f32 {
+        self.active_bindings
+            .bindings(name)
+            .iter()
+            .map(|b| match b.button {
+                ControllerButton::TriggerClick => self.get_trigger_value(b.hand),
+                ControllerButton::SqueezeClick => self.get_squeeze_value(b.hand),
+                button if self.is_button_pressed(b.hand, button) => 1.0,
+                _ => 0.0,
+            })
+            .fold(0.0, f32::max)
+    }
+
+    fn rebind_actions(&mut self) {
+        let Some(profile) = self.interaction_profile.as_deref() else {
+            return;
+        };
+        self.active_bindings = match self.action_bindings.get(profile) {
+            Some(bindings) => bindings.clone(),
+            None => {
+                tracing::warn!(
+                    target: "xr",
+                    "No action bindings registered for interaction profile {}",
+                    profile
+                );
+                ActionBindingMap::default()
+            }
+        };
+    }
 }
 
 impl Default for XrInputManager {
@@ -265,10 +463,65 @@ impl Default for XrInputManager {
             hand_tracking: None,
             haptic_queue: Vec::new(),
             controller_connected: HashMap::new(),
+            action_bindings: HashMap::new(),
+            interaction_profile: None,
+            active_bindings: ActionBindingMap::default(),
+            haptic_playback: HashMap::new(),
         }
     }
 }
 
+/// 动作绑定到的物理按钮
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActionBinding {
+    pub button: ControllerButton,
+    pub hand: Hand,
+}
+
+/// 单个交互配置文件下的动作绑定表
+///
+/// 同一动作可绑定多个按钮，任一按下即视为激活。
+#[derive(Debug, Clone, Default)]
+pub struct ActionBindingMap {
+    actions: HashMap<String, Vec<ActionBinding>>,
+}
+
+impl ActionBindingMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 将动作绑定到指定手的按钮
+    pub fn register_action(
+        &mut self,
+        name: impl Into<String>,
+        button: ControllerButton,
+        hand: Hand,
+    ) -> &mut Self {
+        let bindings = self.actions.entry(name.into()).or_default();
+        let binding = ActionBinding { button, hand };
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+        self
+    }
+
+    /// 移除动作的所有绑定
+    pub fn unregister_action(&mut self, name: &str) -> bool {
+        self.actions.remove(name).is_some()
+    }
+
+    /// 动作的所有绑定
+    pub fn bindings(&self, name: &str) -> &[ActionBinding] {
+        self.actions.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 已注册的动作名
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+}
+
 /// 控制器按钮枚举
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ControllerButton {
@@ -354,3 +607,127 @@ impl XrInputEventQueue {
         self.events.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOUCH: &str = "/interaction_profiles/oculus/touch_controller";
+    const INDEX: &str = "/interaction_profiles/valve/index_controller";
+
+    fn press_a(input: &mut XrInputManager, pressed: bool) {
+        let mut state = ControllerState::default();
+        state.buttons.a = pressed;
+        input.update_controller(Hand::Right, state);
+    }
+
+    #[test]
+    fn test_action_binding_follows_controller_state() {
+        let mut input = XrInputManager::new();
+        let mut touch = ActionBindingMap::new();
+        touch.register_action("jump", ControllerButton::A, Hand::Right);
+        input.set_action_bindings(TOUCH, touch);
+
+        // 配置文件未确定前不解析动作
+        press_a(&mut input, true);
+        assert!(!input.is_action_active("jump"));
+
+        input.handle_xr_event(&XrEvent::InteractionProfileChanged {
+            profile: TOUCH.to_string(),
+        });
+        assert!(input.is_action_active("jump"));
+        assert_eq!(input.action_value("jump"), 1.0);
+        assert!(!input.is_action_active("crouch"));
+
+        press_a(&mut input, false);
+        assert!(!input.is_action_active("jump"));
+        assert_eq!(input.action_value("jump"), 0.0);
+    }
+
+    #[test]
+    fn test_haptic_pattern_schedules_steps() {
+        let mut input = XrInputManager::new();
+        let pattern = HapticPattern::new()
+            .step(0.8, 50_000_000, 100_000_000)
+            .step(0.3, 20_000_000, 0);
+        input.play_haptic(Hand::Left, pattern);
+
+        let first = input.process_haptic_queue();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].amplitude, 0.8);
+        assert_eq!(first[0].duration_ns, 50_000_000);
+
+        // 第一步的震动和间隔尚未结束
+        input.update_haptics(100_000_000);
+        assert!(input.process_haptic_queue().is_empty());
+
+        input.update_haptics(50_000_000);
+        let second = input.process_haptic_queue();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].amplitude, 0.3);
+        assert!(matches!(second[0].hand, Hand::Left));
+        assert!(!input.is_haptic_playing(Hand::Left));
+    }
+
+    #[test]
+    fn test_new_haptic_pattern_cancels_previous() {
+        let mut input = XrInputManager::new();
+        input.play_haptic(Hand::Right, HapticPattern::double_tap(1.0));
+        input.play_haptic(Hand::Right, HapticPattern::new().step(0.5, 10_000_000, 0));
+        input.update_haptics(1_000_000_000);
+
+        // 被取消模式已排队的震动也一并丢弃
+        let amplitudes: Vec<f32> = input
+            .process_haptic_queue()
+            .iter()
+            .map(|f| f.amplitude)
+            .collect();
+        assert_eq!(amplitudes, vec![0.5]);
+    }
+
+    #[test]
+    fn test_stop_haptic_drops_queued_pulses() {
+        let mut input = XrInputManager::new();
+        input.play_haptic(Hand::Left, HapticPattern::double_tap(1.0));
+        input.vibrate(Hand::Right, 0.4, 10_000_000);
+        input.stop_haptic(Hand::Left);
+        input.update_haptics(1_000_000_000);
+
+        let queued = input.process_haptic_queue();
+        assert_eq!(queued.len(), 1);
+        assert!(matches!(queued[0].hand, Hand::Right));
+        assert!(!input.is_haptic_playing(Hand::Left));
+    }
+
+    #[test]
+    fn test_profile_change_rebinds_actions() {
+        let mut input = XrInputManager::new();
+        let mut touch = ActionBindingMap::new();
+        touch.register_action("jump", ControllerButton::A, Hand::Right);
+        let mut index = ActionBindingMap::new();
+        index
+            .register_action("jump", ControllerButton::B, Hand::Right)
+            .register_action("grab", ControllerButton::TriggerClick, Hand::Left);
+        input.set_action_bindings(TOUCH, touch);
+        input.set_action_bindings(INDEX, index);
+
+        press_a(&mut input, true);
+        input.set_interaction_profile(INDEX);
+        assert!(!input.is_action_active("jump"));
+
+        input.update_controller(
+            Hand::Left,
+            ControllerState {
+                trigger: 0.4,
+                ..Default::default()
+            },
+        );
+        assert!((input.action_value("grab") - 0.4).abs() < 1e-6);
+
+        input.handle_xr_event(&XrEvent::InteractionProfileChanged {
+            profile: TOUCH.to_string(),
+        });
+        assert!(input.is_action_active("jump"));
+        assert_eq!(input.action_value("grab"), 0.0);
+    }
+}
@@ -114,7 +114,8 @@ impl XrView {
 pub enum XrEvent {
     SessionStateChanged(XrSessionState),
     ReferenceSpaceChanged,
-    InteractionProfileChanged,
+    /// 当前交互配置文件变化，`profile` 为 OpenXR 路径，如 `/interaction_profiles/oculus/touch_controller`
+    InteractionProfileChanged { profile: String },
 }
 
 /// XR 会话配置
@@ -270,18 +271,21 @@ pub use openxr_impl::{OpenXrBackend, OpenXrError, OpenXrSwapchain};
 
 // XR 渲染器
 pub mod renderer;
-pub use renderer::XrRenderer;
+pub use renderer::{MirrorBlit, MirrorMode, XrRenderer};
 
 // XR 输入系统
 pub mod input;
 pub use input::{
-    ControllerButton, HandJoint, HandJointType, HandTrackingData, HapticFeedback, XrInputEvent,
-    XrInputEventHandler, XrInputEventQueue, XrInputManager,
+    ActionBinding, ActionBindingMap, ControllerButton, HandJoint, HandJointType, HandTrackingData,
+    HapticFeedback, HapticPattern, HapticStep, XrInputEvent, XrInputEventHandler,
+    XrInputEventQueue, XrInputManager,
 };
 
 // XR 手部追踪
 pub mod hand_tracking;
-pub use hand_tracking::{Finger, HandJoints, HandTracker, HandTrackingConfig, HandTrackingState};
+pub use hand_tracking::{
+    Finger, HandGesture, HandJoints, HandTracker, HandTrackingConfig, HandTrackingState,
+};
 
 // XR 空间锚点
 pub mod spatial_anchors;
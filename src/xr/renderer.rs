@@ -24,8 +24,97 @@ pub struct XrRenderer {
     foveated_enabled: bool,
     /// 注视点配置
     foveated_config: foveated::FoveatedConfig,
+    /// 桌面镜像目标
+    mirror_target: Option<(Arc<TextureView>, MirrorMode)>,
+    /// 镜像目标的纹理格式
+    mirror_format: TextureFormat,
+    /// 镜像目标尺寸（像素）
+    mirror_size: (u32, u32),
+    /// 镜像 blit 管线，按目标格式缓存
+    mirror_pipeline: Option<(TextureFormat, RenderPipeline)>,
 }
 
+/// 桌面镜像显示哪只眼睛
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorMode {
+    LeftEye,
+    RightEye,
+    /// 左右眼并排显示
+    BothEyes,
+}
+
+/// 一次镜像 blit：源眼睛纹理及其在镜像目标上的区域
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MirrorBlit<'a, T> {
+    pub source: &'a T,
+    /// x, y, width, height（像素）
+    pub dest_rect: [u32; 4],
+}
+
+impl MirrorMode {
+    /// 根据模式从眼睛纹理中选出 blit 源及目标区域
+    ///
+    /// `eyes` 按左、右眼排列；缺少所需眼睛时返回空列表。
+    pub fn blits<'a, T>(self, eyes: &'a [T], target_size: (u32, u32)) -> Vec<MirrorBlit<'a, T>> {
+        let (width, height) = target_size;
+        let full = [0, 0, width, height];
+        match self {
+            MirrorMode::LeftEye => eyes
+                .first()
+                .map(|source| MirrorBlit {
+                    source,
+                    dest_rect: full,
+                })
+                .into_iter()
+                .collect(),
+            MirrorMode::RightEye => eyes
+                .get(1)
+                .map(|source| MirrorBlit {
+                    source,
+                    dest_rect: full,
+                })
+                .into_iter()
+                .collect(),
+            MirrorMode::BothEyes => {
+                let half = width / 2;
+                eyes.iter()
+                    .take(2)
+                    .enumerate()
+                    .map(|(i, source)| MirrorBlit {
+                        source,
+                        dest_rect: [i as u32 * half, 0, half, height],
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// 镜像 blit 着色器：全屏三角形采样眼睛纹理
+const MIRROR_BLIT_SHADER: &str = r#"
+@group(0) @binding(0) var eye_texture: texture_2d<f32>;
+@group(0) @binding(1) var eye_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(eye_texture, eye_sampler, in.uv);
+}
+"#;
+
 impl XrRenderer {
     /// 创建新的 XR 渲染器
     pub fn new(device: Arc<Device>, queue: Arc<Queue>) -> Self {
@@ -39,6 +128,10 @@ impl XrRenderer {
             atw_enabled: true,
             foveated_enabled: false,
             foveated_config: foveated::FoveatedConfig::default(),
+            mirror_target: None,
+            mirror_format: TextureFormat::Bgra8UnormSrgb,
+            mirror_size: (0, 0),
+            mirror_pipeline: None,
         }
     }
 
@@ -230,6 +323,8 @@ impl XrRenderer {
     }
 
     /// 渲染立体视图
+    ///
+    /// 所有眼睛渲染完成后，若设置了镜像目标，则按 `render_targets` 将眼睛图像 blit 到镜像目标。
     pub fn render_stereo(
         &mut self,
         encoder: &mut CommandEncoder,
@@ -255,6 +350,10 @@ impl XrRenderer {
             render_callback(view, render_target, depth_target);
         }
 
+        if self.mirror_target.is_some() {
+            self.blit_mirror(encoder, render_targets, self.mirror_size)?;
+        }
+
         Ok(())
     }
 
@@ -542,4 +641,382 @@ impl XrRenderer {
     pub fn set_foveated_config(&mut self, config: foveated::FoveatedConfig) {
         self.foveated_config = config;
     }
+
+    /// 设置桌面镜像目标，`render_stereo` 提交眼睛图层后由 `blit_mirror` 复制到该视图
+    ///
+    /// 还需通过 `set_mirror_size` 设置目标尺寸。
+    pub fn set_mirror_target(&mut self, view: Arc<TextureView>, mode: MirrorMode) {
+        self.mirror_target = Some((view, mode));
+    }
+
+    /// 移除桌面镜像目标
+    pub fn clear_mirror_target(&mut self) {
+        self.mirror_target = None;
+    }
+
+    /// 当前镜像模式
+    pub fn mirror_mode(&self) -> Option<MirrorMode> {
+        self.mirror_target.as_ref().map(|(_, mode)| *mode)
+    }
+
+    /// 设置镜像目标的纹理格式（默认 `Bgra8UnormSrgb`，与常见窗口表面一致）
+    pub fn set_mirror_format(&mut self, format: TextureFormat) {
+        self.mirror_format = format;
+    }
+
+    /// 设置镜像目标尺寸，窗口大小变化时需要同步更新
+    pub fn set_mirror_size(&mut self, width: u32, height: u32) {
+        self.mirror_size = (width, height);
+    }
+
+    /// 将眼睛图像复制到镜像目标
+    ///
+    /// 应在提交眼睛图层之后调用，`eye_views` 按左、右眼排列。
+    /// 未设置镜像目标时不做任何操作。
+    pub fn blit_mirror(
+        &mut self,
+        encoder: &mut CommandEncoder,
+        eye_views: &[Arc<TextureView>],
+        target_size: (u32, u32),
+    ) -> Result<(), XrError> {
+        let Some((target, mode)) = self.mirror_target.clone() else {
+            return Ok(());
+        };
+        if target_size.0 == 0 || target_size.1 == 0 {
+            return Err(XrError::RuntimeFailure(
+                "Mirror target size is not set".to_string(),
+            ));
+        }
+        let blits = mode.blits(eye_views, target_size);
+        if blits.is_empty() {
+            return Err(XrError::RuntimeFailure(format!(
+                "Mirror mode {:?} needs more than {} eye views",
+                mode,
+                eye_views.len()
+            )));
+        }
+
+        self.ensure_mirror_pipeline();
+        let Some((_, pipeline)) = self.mirror_pipeline.as_ref() else {
+            return Ok(());
+        };
+        let layout = pipeline.get_bind_group_layout(0);
+        let sampler = self.device.create_sampler(&SamplerDescriptor {
+            label: Some("XR Mirror Sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        let bind_groups: Vec<BindGroup> = blits
+            .iter()
+            .map(|blit| {
+                self.device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("XR Mirror Bind Group"),
+                    layout: &layout,
+                    entries: &[
+                        BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureView(blit.source),
+                        },
+                        BindGroupEntry {
+                            binding: 1,
+                            resource: BindingResource::Sampler(&sampler),
+                        },
+                    ],
+                })
+            })
+            .collect();
+
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("XR Mirror Pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &target,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(pipeline);
+        for (blit, bind_group) in blits.iter().zip(&bind_groups) {
+            let [x, y, w, h] = blit.dest_rect;
+            pass.set_viewport(x as f32, y as f32, w as f32, h as f32, 0.0, 1.0);
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+
+    /// 按当前镜像格式创建 blit 管线
+    fn ensure_mirror_pipeline(&mut self) {
+        if matches!(&self.mirror_pipeline, Some((format, _)) if *format == self.mirror_format) {
+            return;
+        }
+        let shader = self.device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("XR Mirror Shader"),
+            source: ShaderSource::Wgsl(MIRROR_BLIT_SHADER.into()),
+        });
+        let pipeline = self
+            .device
+            .create_render_pipeline(&RenderPipelineDescriptor {
+                label: Some("XR Mirror Pipeline"),
+                layout: None,
+                vertex: VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    buffers: &[],
+                    compilation_options: PipelineCompilationOptions::default(),
+                },
+                fragment: Some(FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(ColorTargetState {
+                        format: self.mirror_format,
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    })],
+                    compilation_options: PipelineCompilationOptions::default(),
+                }),
+                primitive: PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: MultisampleState::default(),
+                multiview: None,
+            });
+        self.mirror_pipeline = Some((self.mirror_format, pipeline));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_left_eye_source() {
+        // 以字符串模拟交换链中的眼睛纹理
+        let eyes = ["left_eye_texture", "right_eye_texture"];
+
+        let blits = MirrorMode::LeftEye.blits(&eyes, (1280, 720));
+        assert_eq!(blits.len(), 1);
+        assert_eq!(*blits[0].source, "left_eye_texture");
+        assert_eq!(blits[0].dest_rect, [0, 0, 1280, 720]);
+
+        let blits = MirrorMode::RightEye.blits(&eyes, (1280, 720));
+        assert_eq!(*blits[0].source, "right_eye_texture");
+
+        let blits = MirrorMode::BothEyes.blits(&eyes, (1280, 720));
+        assert_eq!(blits.len(), 2);
+        assert_eq!(blits[0].dest_rect, [0, 0, 640, 720]);
+        assert_eq!(*blits[1].source, "right_eye_texture");
+        assert_eq!(blits[1].dest_rect, [640, 0, 640, 720]);
+
+        // 单眼交换链无法镜像右眼
+        assert!(MirrorMode::RightEye
+            .blits(&eyes[..1], (1280, 720))
+            .is_empty());
+    }
+
+    const EYE_SIZE: u32 = 4;
+
+    /// 单张纹理的模拟交换链，创建时清除为指定颜色
+    struct MockSwapchain {
+        view: Arc<TextureView>,
+    }
+
+    impl MockSwapchain {
+        fn new(device: &Device, queue: &Queue, color: Color) -> Self {
+            let texture = device.create_texture(&TextureDescriptor {
+                label: Some("Mock Eye Texture"),
+                size: Extent3d {
+                    width: EYE_SIZE,
+                    height: EYE_SIZE,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&TextureViewDescriptor::default());
+            clear(device, queue, &view, color);
+            Self {
+                view: Arc::new(view),
+            }
+        }
+    }
+
+    impl XrSwapchain for MockSwapchain {
+        fn acquire_image(&mut self) -> Result<u32, XrError> {
+            Ok(0)
+        }
+
+        fn wait_image(&mut self, _timeout_ns: i64) -> Result<(), XrError> {
+            Ok(())
+        }
+
+        fn release_image(&mut self) -> Result<(), XrError> {
+            Ok(())
+        }
+
+        fn get_texture_view(&self, _index: u32) -> Arc<TextureView> {
+            self.view.clone()
+        }
+
+        fn resolution(&self) -> (u32, u32) {
+            (EYE_SIZE, EYE_SIZE)
+        }
+    }
+
+    fn clear(device: &Device, queue: &Queue, view: &TextureView, color: Color) {
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(color),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        queue.submit(Some(encoder.finish()));
+    }
+
+    /// 读回镜像目标一行像素的 RGBA
+    fn read_row(device: &Device, queue: &Queue, texture: &Texture, width: u32) -> Vec<[u8; 4]> {
+        // 每行字节数需按 256 对齐
+        let bytes_per_row = 256;
+        let readback = device.create_buffer(&BufferDescriptor {
+            label: Some("Mirror Readback"),
+            size: bytes_per_row as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &readback,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(1),
+                },
+            },
+            Extent3d {
+                width,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(MapMode::Read, |_| {});
+        device.poll(Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let row = data[..width as usize * 4]
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+            .collect();
+        drop(data);
+        readback.unmap();
+        row
+    }
+
+    #[test]
+    fn test_render_stereo_blits_mirror() {
+        let Some((device, queue)) = crate::test_utils::headless_device() else {
+            return;
+        };
+        let device = Arc::new(device);
+        let queue = Arc::new(queue);
+
+        let swapchains: Vec<Box<dyn XrSwapchain>> = vec![
+            Box::new(MockSwapchain::new(&device, &queue, Color::RED)),
+            Box::new(MockSwapchain::new(&device, &queue, Color::GREEN)),
+        ];
+        let eye_views: Vec<_> = swapchains.iter().map(|s| s.get_texture_view(0)).collect();
+        let views: Vec<_> = (0..2)
+            .map(|view_index| XrView {
+                pose: Pose::default(),
+                fov: Fov {
+                    angle_left: -0.8,
+                    angle_right: 0.8,
+                    angle_up: 0.8,
+                    angle_down: -0.8,
+                },
+                view_index,
+            })
+            .collect();
+
+        let mirror_width = EYE_SIZE * 2;
+        let mirror = device.create_texture(&TextureDescriptor {
+            label: Some("Mirror Target"),
+            size: Extent3d {
+                width: mirror_width,
+                height: EYE_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let mirror_view = Arc::new(mirror.create_view(&TextureViewDescriptor::default()));
+
+        let mut renderer = XrRenderer::new(device.clone(), queue.clone());
+        renderer.set_mirror_format(TextureFormat::Rgba8Unorm);
+        renderer.set_mirror_size(mirror_width, EYE_SIZE);
+
+        let red = [255, 0, 0, 255];
+        let green = [0, 255, 0, 255];
+        for (mode, expected) in [
+            (MirrorMode::LeftEye, [red; 8]),
+            (MirrorMode::RightEye, [green; 8]),
+            (
+                MirrorMode::BothEyes,
+                [red, red, red, red, green, green, green, green],
+            ),
+        ] {
+            renderer.set_mirror_target(mirror_view.clone(), mode);
+            let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+            renderer
+                .render_stereo(&mut encoder, &views, &eye_views, &eye_views, |_, _, _| {})
+                .unwrap();
+            queue.submit(Some(encoder.finish()));
+
+            assert_eq!(
+                read_row(&device, &queue, &mirror, mirror_width),
+                expected,
+                "{:?}",
+                mode
+            );
+        }
+
+        // 未设置镜像目标时不写入
+        renderer.clear_mirror_target();
+        clear(&device, &queue, &mirror_view, Color::BLUE);
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+        renderer
+            .render_stereo(&mut encoder, &views, &eye_views, &eye_views, |_, _, _| {})
+            .unwrap();
+        queue.submit(Some(encoder.finish()));
+        assert_eq!(
+            read_row(&device, &queue, &mirror, mirror_width),
+            [[0, 0, 255, 255]; 8]
+        );
+    }
 }
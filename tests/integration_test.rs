@@ -960,3 +960,53 @@ fn test_error_handling_integration_recovery() {
     // 验证刚体1仍然存在
     assert!(physics_service.get_body_position(RigidBodyId(1)).is_ok());
 }
+
+/// 测试Lua脚本经由scripting_system读写应用世界中的组件
+#[cfg(feature = "lua_scripting")]
+#[test]
+fn test_lua_scripting_system_integration() {
+    use game_engine::ecs::Velocity;
+    use game_engine::scripting::{
+        create_lua_script, scripting_system, setup_scripting, ScriptingConfig, ScriptingResource,
+    };
+
+    let mut world = World::new();
+    world.insert_resource(Time::default());
+    setup_scripting(&mut world, ScriptingConfig::default());
+
+    let script = create_lua_script(
+        "move",
+        r#"
+            local t = get_component(current_entity, "Transform")
+            t.position = { x = t.position.x + 1, y = 2, z = 3 }
+            add_component(current_entity, "Transform", t)
+            remove_component(current_entity, "Velocity")
+        "#,
+    );
+    let entity = world
+        .spawn((
+            Transform {
+                pos: Vec3::new(4.0, 0.0, 0.0),
+                ..Default::default()
+            },
+            Velocity::default(),
+            script,
+        ))
+        .id();
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(scripting_system);
+    schedule.run(&mut world);
+
+    // 脚本读取到实体的实际位置，修改写回应用世界
+    assert_eq!(
+        world.get::<Transform>(entity).unwrap().pos,
+        Vec3::new(5.0, 2.0, 3.0)
+    );
+    assert!(world.get::<Velocity>(entity).is_none());
+    assert!(world.contains_resource::<ScriptingResource>());
+    assert!(world.contains_resource::<Time>());
+
+    schedule.run(&mut world);
+    assert_eq!(world.get::<Transform>(entity).unwrap().pos.x, 6.0);
+}